@@ -20,6 +20,11 @@ pub struct BackendConfig {
     /// Required: backend must at least support install
     pub install_cmd: String,
 
+    /// Optional: Command to reinstall packages that are already present
+    /// Use {packages} as placeholder for package list
+    /// Falls back to install_cmd when not set
+    pub reinstall_cmd: Option<String>,
+
     /// Command to remove packages
     /// Optional: if not set, packages cannot be removed via declarch
     pub remove_cmd: Option<String>,
@@ -27,6 +32,10 @@ pub struct BackendConfig {
     /// Optional: Command to query package info (for dependencies)
     pub query_cmd: Option<String>,
 
+    /// Optional: Command printing the download/install size of one package
+    /// Use {package} as placeholder; output is bytes or e.g. "12.3 MiB"
+    pub size_cmd: Option<String>,
+
     /// How to parse the output of list_cmd
     pub list_format: OutputFormat,
 
@@ -55,19 +64,83 @@ pub struct BackendConfig {
     /// Capture group index for package version in regex
     pub list_regex_version_group: Option<usize>,
 
+    /// Key name for installed size in JSON objects (bytes or "12.3 MiB")
+    pub list_size_key: Option<String>,
+
+    /// Column index for installed size (for SplitWhitespace/TabSeparated)
+    pub list_size_col: Option<usize>,
+
+    /// Capture group index for installed size in regex
+    pub list_regex_size_group: Option<usize>,
+
+    /// Key name for the originating remote in JSON objects (flatpak `origin`)
+    pub list_origin_key: Option<String>,
+
+    /// Column index for the originating remote (for SplitWhitespace/TabSeparated)
+    pub list_origin_col: Option<usize>,
+
+    /// Capture group index for the originating remote in regex
+    pub list_regex_origin_group: Option<usize>,
+
+    /// Keep only list output lines matching this regex before parsing
+    pub list_filter_regex: Option<String>,
+
+    /// Drop list output lines matching this regex before parsing
+    /// (banners, deprecation warnings, footers)
+    pub list_skip_lines_matching: Option<String>,
+
+    /// Match the list regex against each output line separately instead of
+    /// the whole output, so a name cannot pair with a version on the next line
+    pub list_per_line: bool,
+
     /// Flag to pass for auto-confirmation (e.g., "--noconfirm", "-y")
     pub noconfirm_flag: Option<String>,
 
     /// Whether command needs sudo
     pub needs_sudo: bool,
 
+    /// Pass all packages to one install/reinstall command (default); when
+    /// false the command runs once per package
+    pub batch: bool,
+
     /// Environment variables to set before running commands
     pub preinstall_env: Option<HashMap<String, String>>,
 
+    /// Optional: Backend-defined setup command run once before this backend's install batch
+    /// Example: "flatpak remote-add --if-not-exists flathub https://flathub.org/repo/flathub.flatpakrepo"
+    /// A failure aborts this backend's installs for the current sync
+    pub preinstall_cmd: Option<String>,
+
+    /// Optional: Backend-defined command run once after this backend's install batch
+    /// A failure only produces a warning
+    pub postinstall_cmd: Option<String>,
+
+    /// Optional: Read-only command that checks an install without performing it
+    /// Example: "pacman -S --print {packages}"
+    /// Run before sync executes; a non-zero exit aborts the sync
+    pub dry_install_cmd: Option<String>,
+
+    /// Optional: Read-only command that checks a removal without performing it
+    /// Example: "pacman -R --print {packages}"
+    pub dry_remove_cmd: Option<String>,
+
+    /// Optional: Install/reinstall output that means failure even on exit 0
+    /// Example: "^error: target not found"
+    /// Setting this or install_success_regex captures the command's output
+    pub install_failure_regex: Option<String>,
+
+    /// Optional: Install/reinstall output required for success, whatever the exit status
+    /// Example: "Successfully installed"
+    pub install_success_regex: Option<String>,
+
     /// Optional package sources/repositories injected into command templates
-    /// Use {repos} placeholder in command templates
+    /// Use {repos} (all, in priority order) or {repo:first} in command templates
     pub package_sources: Option<Vec<String>>,
 
+    /// Registry or mirror URL injected as {registry} in command templates
+    /// `registry "..."` sets the default; `options:<backend> { registry }` overrides it
+    pub registry: Option<String>,
+
     /// Whether to use the Rust implementation instead of generic
     pub use_rust_fallback: bool,
 
@@ -77,6 +150,11 @@ pub struct BackendConfig {
     /// Example: "my-pm search {query} --json"
     pub search_cmd: Option<String>,
 
+    /// Optional: Command that searches package descriptions
+    /// Output is parsed like search_cmd; when unset, `search --in-description`
+    /// uses search_cmd and filters results by description
+    pub search_desc_cmd: Option<String>,
+
     /// How to parse the output of search_cmd
     pub search_format: Option<OutputFormat>,
 
@@ -112,11 +190,27 @@ pub struct BackendConfig {
     /// Example: paru → pacman, yarn → npm
     pub fallback: Option<String>,
 
+    /// Other names this backend answers to (e.g. "paru" for "aur")
+    /// Packages declared under an alias resolve to this backend
+    pub aliases: Vec<String>,
+
     /// Optional list of supported operating systems for this backend.
     /// If omitted, backend is treated as cross-platform.
     /// Examples: ["linux"], ["linux", "macos"], ["windows"]
     pub supported_os: Option<Vec<String>>,
 
+    /// ===== VERSION REQUIREMENT =====
+    /// Optional minimum binary version (e.g. "1.5.0").
+    /// A binary that is present but older is treated as unavailable.
+    pub min_version: Option<String>,
+
+    /// Command printing the binary version (default: "{binary} --version")
+    pub version_check_cmd: Option<String>,
+
+    /// Regex extracting the version from version_check_cmd output.
+    /// Uses capture group 1 when present, otherwise the whole match.
+    pub version_regex: Option<String>,
+
     /// ===== UPDATE SUPPORT =====
     /// Optional: Command to update package list/index
     /// Example: "apt update", "pacman -Sy", "npm update"
@@ -136,6 +230,89 @@ pub struct BackendConfig {
     /// Note: Some backends use {packages} for selective upgrade
     pub upgrade_cmd: Option<String>,
 
+    /// ===== OUTDATED SUPPORT =====
+    /// Optional: Command listing installed packages that have a newer version
+    /// Example: "pacman -Qu", "npm outdated -g --json"
+    /// Use {binary} as placeholder for binary name
+    pub outdated_cmd: Option<String>,
+
+    /// How to parse the output of outdated_cmd (default: whitespace)
+    pub outdated_format: Option<OutputFormat>,
+
+    /// JSON path to the outdated entries (for Json format)
+    pub outdated_json_path: Option<String>,
+
+    /// Key name for package name in outdated JSON objects (default: "name")
+    pub outdated_name_key: Option<String>,
+
+    /// Key name for the installed version in outdated JSON objects (default: "current")
+    pub outdated_current_key: Option<String>,
+
+    /// Key name for the available version in outdated JSON objects (default: "latest")
+    pub outdated_available_key: Option<String>,
+
+    /// Column index for package name in outdated output (default: 0)
+    pub outdated_name_col: Option<usize>,
+
+    /// Column index for the installed version in outdated output (default: 1)
+    pub outdated_current_col: Option<usize>,
+
+    /// Column index for the available version in outdated output (default: last column)
+    pub outdated_available_col: Option<usize>,
+
+    /// Regex pattern to extract outdated entries
+    pub outdated_regex: Option<String>,
+
+    /// Capture group index for package name in outdated regex (default: 1)
+    pub outdated_regex_name_group: Option<usize>,
+
+    /// Capture group index for the installed version in outdated regex (default: 2)
+    pub outdated_regex_current_group: Option<usize>,
+
+    /// Capture group index for the available version in outdated regex (default: 3)
+    pub outdated_regex_available_group: Option<usize>,
+
+    /// ===== ORPHANS SUPPORT =====
+    /// Optional: Command listing dependencies no installed package requires
+    /// any more, one name per line (`sync --prune-orphans`)
+    /// Example: "pacman -Qtdq"
+    /// Use {binary} as placeholder for binary name
+    pub orphans_cmd: Option<String>,
+
+    /// ===== DEPENDENCY GRAPH SUPPORT =====
+    /// Optional: Command describing every installed package as `Key : value`
+    /// blocks separated by blank lines, with `Name`, `Required By` and
+    /// `Install Reason` fields (orphan preview before pruning)
+    /// Example: "pacman -Qi"
+    pub dependencies_cmd: Option<String>,
+
+    /// ===== INFO SUPPORT =====
+    /// Optional: Command printing details about one package (`show`)
+    /// Example: "pacman -Si {package}", "npm view {package} --json"
+    /// Use {package} as placeholder for the package name
+    pub info_cmd: Option<String>,
+
+    /// How to parse the output of info_cmd (default: "Key: value" lines)
+    pub info_format: Option<InfoFormat>,
+
+    /// JSON path to the package object (for Json format)
+    pub info_json_path: Option<String>,
+
+    /// Key (field label for key_value) for the package name (default: "name")
+    pub info_name_key: Option<String>,
+
+    /// Key for the version (default: "version")
+    pub info_version_key: Option<String>,
+
+    /// Key for the description (default: "description")
+    pub info_desc_key: Option<String>,
+
+    /// Key for the homepage (default: "homepage", or "URL" for key_value)
+    pub info_homepage_key: Option<String>,
+
+    /// Key for the dependency list (default: "dependencies", or "Depends On" for key_value)
+    pub info_depends_key: Option<String>,
+
     /// ===== LOCAL SEARCH SUPPORT =====
     /// Optional: Command to search locally installed packages
     /// Example: "pacman -Q {query}", "dpkg -l {query}"
@@ -180,17 +357,19 @@ pub enum BinarySpecifier {
 impl BinarySpecifier {
     /// Get the first available binary
     pub fn find_available(&self) -> Option<String> {
-        match self {
-            BinarySpecifier::Single(bin) => which::which(bin).ok().map(|_| bin.clone()),
-            BinarySpecifier::Multiple(binaries) => {
-                for bin in binaries {
-                    if which::which(bin).is_ok() {
-                        return Some(bin.clone());
-                    }
-                }
-                None
-            }
-        }
+        self.find_available_where(|_| true)
+    }
+
+    /// Get the first available binary that also passes `accept`
+    pub fn find_available_where(&self, mut accept: impl FnMut(&str) -> bool) -> Option<String> {
+        let binaries = match self {
+            BinarySpecifier::Single(bin) => std::slice::from_ref(bin),
+            BinarySpecifier::Multiple(binaries) => binaries.as_slice(),
+        };
+        binaries
+            .iter()
+            .find(|bin| which::which(bin).is_ok() && accept(bin))
+            .cloned()
     }
 
     /// Get the primary binary name (for display/error messages)
@@ -205,6 +384,17 @@ impl BinarySpecifier {
     }
 }
 
+/// Output format for info command
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InfoFormat {
+    /// `Key : value` lines (pacman -Si, apt show, dnf info); indented lines continue a value
+    KeyValue,
+
+    /// A JSON object, optionally nested under info_json_path
+    Json,
+}
+
 /// Output format for list command
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -244,8 +434,10 @@ impl Default for BackendConfig {
             binary: BinarySpecifier::Single("unknown".to_string()),
             list_cmd: None,
             install_cmd: String::new(),
+            reinstall_cmd: None,
             remove_cmd: None,
             query_cmd: None,
+            size_cmd: None,
             list_format: OutputFormat::SplitWhitespace,
             list_name_col: Some(0),
             list_version_col: Some(1),
@@ -255,13 +447,31 @@ impl Default for BackendConfig {
             list_regex: None,
             list_regex_name_group: None,
             list_regex_version_group: None,
+            list_size_key: None,
+            list_size_col: None,
+            list_regex_size_group: None,
+            list_origin_key: None,
+            list_origin_col: None,
+            list_regex_origin_group: None,
+            list_filter_regex: None,
+            list_skip_lines_matching: None,
+            list_per_line: false,
             noconfirm_flag: None,
             needs_sudo: false,
+            batch: true,
             preinstall_env: None,
+            preinstall_cmd: None,
+            postinstall_cmd: None,
+            dry_install_cmd: None,
+            dry_remove_cmd: None,
+            install_failure_regex: None,
+            install_success_regex: None,
             package_sources: None,
+            registry: None,
             use_rust_fallback: false,
             // Search support - all optional
             search_cmd: None,
+            search_desc_cmd: None,
             search_format: None,
             search_json_path: None,
             search_name_key: None,
@@ -273,10 +483,37 @@ impl Default for BackendConfig {
             search_regex_name_group: None,
             search_regex_desc_group: None,
             fallback: None,
+            aliases: Vec::new(),
             supported_os: None,
+            min_version: None,
+            version_check_cmd: None,
+            version_regex: None,
             update_cmd: None,
             cache_clean_cmd: None,
             upgrade_cmd: None,
+            outdated_cmd: None,
+            outdated_format: None,
+            outdated_json_path: None,
+            outdated_name_key: None,
+            outdated_current_key: None,
+            outdated_available_key: None,
+            outdated_name_col: None,
+            outdated_current_col: None,
+            outdated_available_col: None,
+            outdated_regex: None,
+            outdated_regex_name_group: None,
+            outdated_regex_current_group: None,
+            outdated_regex_available_group: None,
+            orphans_cmd: None,
+            dependencies_cmd: None,
+            info_cmd: None,
+            info_format: None,
+            info_json_path: None,
+            info_name_key: None,
+            info_version_key: None,
+            info_desc_key: None,
+            info_homepage_key: None,
+            info_depends_key: None,
             search_local_cmd: None,
             search_local_format: None,
             search_local_json_path: None,
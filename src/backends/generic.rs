@@ -1,26 +1,50 @@
 mod command_exec;
+mod dependency_parsing;
+mod info_parsing;
+mod install_output;
+mod outdated_parsing;
 mod runtime;
 mod search_parsing;
+mod version_check;
 
 use crate::backends::config::BackendConfig;
 use crate::backends::parsers;
 use crate::constants::BACKEND_COMMAND_TIMEOUT_SECS;
 use crate::core::types::{Backend as CoreBackend, PackageMetadata};
 use crate::error::{DeclarchError, Result};
-use crate::packages::traits::{PackageManager, PackageSearchResult};
+use crate::packages::traits::{
+    DependencyGraph, HealthReport, HealthStatus, OutdatedPackage, PackageInfo, PackageManager,
+    PackageSearchResult, PlannedOperation,
+};
 use crate::ui;
 use crate::utils::sanitize;
+use crate::utils::size::parse_size;
 use std::collections::HashMap;
+use std::sync::OnceLock;
 use std::time::Duration;
+use version_check::find_usable_binary;
 
 /// Default timeout for backend commands (5 minutes)
 const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(BACKEND_COMMAND_TIMEOUT_SECS);
 
+/// `list_cmd` timeout for health checks, so a hung backend can't stall diagnostics
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Generic package manager that works with any backend configuration
 pub struct GenericManager {
     config: BackendConfig,
     noconfirm: bool,
     backend_type: CoreBackend,
+    /// Primary binary resolved once, so the version check runs at most once
+    usable_binary: OnceLock<Option<String>>,
+    /// Run mutating commands without `sudo` (`--no-sudo` or running as root)
+    no_sudo: bool,
+    /// Program prefixed to mutating commands of `needs_sudo` backends
+    escalation: String,
+    /// Capture install/remove/upgrade output, only showing it on failure
+    quiet_output: bool,
+    /// Print each resolved command before running it (`--verbose`)
+    echo_commands: bool,
 }
 
 #[derive(Clone, Copy)]
@@ -48,16 +72,87 @@ impl GenericManager {
             config,
             noconfirm,
             backend_type,
+            usable_binary: OnceLock::new(),
+            no_sudo: crate::utils::platform::is_root(),
+            escalation: crate::utils::platform::escalation_program(None),
+            quiet_output: false,
+            echo_commands: false,
         }
     }
+
+    /// Elevate mutating commands through `escalation` (the config's
+    /// [`MergedConfig::escalation_program`](crate::config::loader::MergedConfig::escalation_program))
+    /// instead of `DECLARCH_SUDO` or `sudo`.
+    pub fn with_escalation(mut self, escalation: String) -> Self {
+        self.escalation = escalation;
+        self
+    }
+
+    /// Run mutating commands without escalation (`--no-sudo`); running as
+    /// root always skips it.
+    pub fn with_no_sudo(mut self, no_sudo: bool) -> Self {
+        self.no_sudo = no_sudo || crate::utils::platform::is_root();
+        self
+    }
+
+    /// Capture install/remove/upgrade output, only showing it on failure
+    /// (`--quiet-backends`).
+    pub fn with_quiet_output(mut self, quiet_output: bool) -> Self {
+        self.quiet_output = quiet_output;
+        self
+    }
+
+    /// Print each resolved command before running it (`--verbose`).
+    pub fn with_echo_commands(mut self, echo_commands: bool) -> Self {
+        self.echo_commands = echo_commands;
+        self
+    }
+
+    /// First available primary binary meeting `min_version`, if configured
+    pub(super) fn usable_binary(&self) -> Option<&str> {
+        self.usable_binary
+            .get_or_init(|| find_usable_binary(&self.config))
+            .as_deref()
+    }
 }
 
-impl PackageManager for GenericManager {
-    fn backend_type(&self) -> CoreBackend {
-        self.backend_type.clone()
+impl GenericManager {
+    /// Command template used for a search, preferring `search_desc_cmd` for description searches.
+    pub(super) fn search_template(&self, in_description: bool) -> Option<&str> {
+        if in_description && let Some(cmd) = self.config.search_desc_cmd.as_deref() {
+            return Some(cmd);
+        }
+        self.config.search_cmd.as_deref()
     }
 
-    fn list_installed(&self) -> Result<HashMap<String, PackageMetadata>> {
+    fn run_search(&self, query: &str, in_description: bool) -> Result<Vec<PackageSearchResult>> {
+        // Security: Validate search query before shell execution
+        sanitize::validate_search_query(query)?;
+
+        let search_cmd = self.search_template(in_description).ok_or_else(|| {
+            DeclarchError::PackageManagerError(format!(
+                "Backend '{}' does not support search",
+                self.config.name
+            ))
+        })?;
+
+        // Replace query placeholder; common placeholders are handled by build_command
+        let cmd_str = search_cmd.replace("{query}", &sanitize::shell_escape(query));
+        let mut cmd = self.build_command(&cmd_str, CommandMode::ReadOnly)?;
+
+        // Use shorter timeout for search (30 seconds)
+        let output = self.run_output_command(&mut cmd, &cmd_str, Duration::from_secs(30))?;
+
+        if !output.status.success() {
+            return Ok(Vec::new());
+        }
+
+        // Parse search results using the configured format
+        self.parse_search_results(&output.stdout)
+    }
+
+    /// Run `list_cmd` with the given timeout and parse its output.
+    fn list_installed_within(&self, timeout: Duration) -> Result<HashMap<String, PackageMetadata>> {
         // Get list command or return error if not configured
         let list_cmd = self.config.list_cmd.as_ref().ok_or_else(|| {
             DeclarchError::PackageManagerError(format!(
@@ -69,7 +164,7 @@ impl PackageManager for GenericManager {
         let cmd_str = list_cmd.clone();
         let mut cmd = self.build_command(&cmd_str, CommandMode::ReadOnly)?;
 
-        let output = self.run_output_command(&mut cmd, &cmd_str, DEFAULT_COMMAND_TIMEOUT)?;
+        let output = self.run_output_command(&mut cmd, &cmd_str, timeout)?;
 
         if !output.status.success() {
             return Err(DeclarchError::PackageManagerError(format!(
@@ -81,42 +176,57 @@ impl PackageManager for GenericManager {
 
         parsers::parse_package_list(&output.stdout, &self.config)
     }
+}
 
-    fn install(&self, packages: &[String]) -> Result<()> {
-        if packages.is_empty() {
-            return Ok(());
-        }
-
-        // Security: Validate all package names before shell execution
-        sanitize::validate_package_names(packages)?;
+impl PackageManager for GenericManager {
+    fn backend_type(&self) -> CoreBackend {
+        self.backend_type.clone()
+    }
 
-        let cmd_template = &self.config.install_cmd;
-        let package_list = self.format_packages(packages);
-        let mut cmd_str = cmd_template.replace("{packages}", &package_list);
+    fn list_installed(&self) -> Result<HashMap<String, PackageMetadata>> {
+        self.list_installed_within(DEFAULT_COMMAND_TIMEOUT)
+    }
 
-        // Append noconfirm flag if configured and enabled
-        if self.noconfirm
-            && let Some(flag) = &self.config.noconfirm_flag
-        {
-            cmd_str.push(' ');
-            cmd_str.push_str(flag);
+    fn health_check(&self) -> HealthReport {
+        if !self.is_available() {
+            return HealthReport::binary_missing(self.backend_type.clone());
         }
+        if self.config.list_cmd.is_none() {
+            return HealthReport {
+                backend: self.backend_type.clone(),
+                status: HealthStatus::Ok,
+                package_count: None,
+                detail: Some("install-only (no list_cmd configured)".to_string()),
+            };
+        }
+        HealthReport::from_list_result(
+            self.backend_type.clone(),
+            self.list_installed_within(HEALTH_CHECK_TIMEOUT),
+        )
+    }
 
-        let mut cmd = self.build_command(&cmd_str, CommandMode::Mutating)?;
+    fn install(&self, packages: &[String]) -> Result<()> {
+        let cmd_template = self.install_template(None);
+        self.run_install_template(&cmd_template, packages, "install", "installing")
+    }
 
-        // Use interactive timeout function (5 minute timeout for install)
-        let timeout = Duration::from_secs(300);
-        let status =
-            self.run_interactive_status(&mut cmd, &format!("install: {}", cmd_str), timeout)?;
+    fn install_with_args(&self, packages: &[String], extra_args: &str) -> Result<()> {
+        let cmd_template = self.install_template_with_args(None, extra_args)?;
+        self.run_install_template(&cmd_template, packages, "install", "installing")
+    }
 
-        if !status.success() {
-            return Err(DeclarchError::PackageManagerError(format!(
-                "{} install failed",
-                self.config.name
-            )));
-        }
+    fn install_from_remote(&self, packages: &[String], remote: &str) -> Result<()> {
+        let cmd_template = self.install_template(Some(remote));
+        self.run_install_template(&cmd_template, packages, "install", "installing")
+    }
 
-        Ok(())
+    fn reinstall(&self, packages: &[String]) -> Result<()> {
+        match &self.config.reinstall_cmd {
+            Some(cmd_template) => {
+                self.run_install_template(cmd_template, packages, "reinstall", "reinstalling")
+            }
+            None => self.install(packages),
+        }
     }
 
     fn remove(&self, packages: &[String]) -> Result<()> {
@@ -134,23 +244,21 @@ impl PackageManager for GenericManager {
 
         // Security: Validate all package names before shell execution
         sanitize::validate_package_names(packages)?;
-        let package_list = self.format_packages(packages);
-        let mut cmd_str = cmd_template.replace("{packages}", &package_list);
-
-        // Append noconfirm flag if configured and enabled
-        if self.noconfirm
-            && let Some(flag) = &self.config.noconfirm_flag
-        {
-            cmd_str.push(' ');
-            cmd_str.push_str(flag);
-        }
-
+        let cmd_str = self.package_command(cmd_template, packages);
         let mut cmd = self.build_command(&cmd_str, CommandMode::Mutating)?;
 
         // Use interactive timeout function (5 minute timeout for remove)
         let timeout = Duration::from_secs(300);
-        let status =
-            self.run_interactive_status(&mut cmd, &format!("remove: {}", cmd_str), timeout)?;
+        let status = self.run_mutating_status(
+            &mut cmd,
+            &format!("remove: {}", cmd_str),
+            &format!(
+                "{}: removing {} package(s)",
+                self.config.name,
+                packages.len()
+            ),
+            timeout,
+        )?;
 
         if !status.success() {
             return Err(DeclarchError::PackageManagerError(format!(
@@ -162,9 +270,54 @@ impl PackageManager for GenericManager {
         Ok(())
     }
 
+    fn planned_commands(&self, operation: PlannedOperation<'_>) -> Result<Vec<String>> {
+        let hook = |cmd: &Option<String>| -> Result<Vec<String>> {
+            cmd.iter()
+                .map(|cmd_str| self.command_line(cmd_str, CommandMode::Mutating))
+                .collect()
+        };
+        match operation {
+            PlannedOperation::Preinstall => hook(&self.config.preinstall_cmd),
+            PlannedOperation::Postinstall => hook(&self.config.postinstall_cmd),
+            PlannedOperation::Install {
+                packages,
+                extra_args,
+                remote,
+            } => {
+                let cmd_template = match extra_args {
+                    Some(extra_args) => self.install_template_with_args(remote, extra_args)?,
+                    None => self.install_template(remote),
+                };
+                self.install_command_lines(&cmd_template, packages)
+            }
+            PlannedOperation::Reinstall(packages) => {
+                let cmd_template = match &self.config.reinstall_cmd {
+                    Some(cmd_template) => cmd_template.clone(),
+                    None => self.install_template(None),
+                };
+                self.install_command_lines(&cmd_template, packages)
+            }
+            PlannedOperation::Remove(packages) => {
+                if packages.is_empty() {
+                    return Ok(Vec::new());
+                }
+                let cmd_template = self.config.remove_cmd.as_ref().ok_or_else(|| {
+                    DeclarchError::PackageManagerError(format!(
+                        "Backend '{}' does not support removing packages (no remove_cmd configured)",
+                        self.config.name
+                    ))
+                })?;
+                sanitize::validate_package_names(packages)?;
+                let cmd_str = self.package_command(cmd_template, packages);
+                Ok(vec![self.command_line(&cmd_str, CommandMode::Mutating)?])
+            }
+            PlannedOperation::Update => hook(&self.config.update_cmd),
+        }
+    }
+
     fn is_available(&self) -> bool {
         // Check primary binary
-        if self.config.binary.find_available().is_some() {
+        if self.usable_binary().is_some() {
             return true;
         }
 
@@ -173,7 +326,7 @@ impl PackageManager for GenericManager {
             && let Ok(all_backends) = crate::backends::load_all_backends_unified()
             && let Some(fallback_config) = all_backends.get(fallback_name)
         {
-            return fallback_config.binary.find_available().is_some();
+            return find_usable_binary(fallback_config).is_some();
         }
 
         false
@@ -190,29 +343,11 @@ impl PackageManager for GenericManager {
     }
 
     fn search(&self, query: &str) -> Result<Vec<PackageSearchResult>> {
-        // Security: Validate search query before shell execution
-        sanitize::validate_search_query(query)?;
-
-        let search_cmd = self.config.search_cmd.as_ref().ok_or_else(|| {
-            DeclarchError::PackageManagerError(format!(
-                "Backend '{}' does not support search",
-                self.config.name
-            ))
-        })?;
-
-        // Replace query placeholder; common placeholders are handled by build_command
-        let cmd_str = search_cmd.replace("{query}", &sanitize::shell_escape(query));
-        let mut cmd = self.build_command(&cmd_str, CommandMode::ReadOnly)?;
-
-        // Use shorter timeout for search (30 seconds)
-        let output = self.run_output_command(&mut cmd, &cmd_str, Duration::from_secs(30))?;
-
-        if !output.status.success() {
-            return Ok(Vec::new());
-        }
+        self.run_search(query, false)
+    }
 
-        // Parse search results using the configured format
-        self.parse_search_results(&output.stdout)
+    fn search_description(&self, query: &str) -> Result<Vec<PackageSearchResult>> {
+        self.run_search(query, true)
     }
 
     fn supports_update(&self) -> bool {
@@ -296,7 +431,12 @@ impl PackageManager for GenericManager {
         ui::info(&format!("Upgrading {} packages...", self.config.name));
 
         // Use longer timeout for upgrade (10 minutes - can be slow)
-        let output = self.run_interactive_status(&mut cmd, &cmd_str, Duration::from_secs(600))?;
+        let output = self.run_mutating_status(
+            &mut cmd,
+            &cmd_str,
+            &format!("{}: upgrading", self.config.name),
+            Duration::from_secs(600),
+        )?;
 
         if !output.success() {
             return Err(DeclarchError::PackageManagerError(format!(
@@ -309,6 +449,170 @@ impl PackageManager for GenericManager {
         Ok(())
     }
 
+    fn preinstall(&self) -> Result<()> {
+        match &self.config.preinstall_cmd {
+            Some(cmd_str) => self.run_backend_install_hook("preinstall", cmd_str),
+            None => Ok(()),
+        }
+    }
+
+    fn postinstall(&self) -> Result<()> {
+        match &self.config.postinstall_cmd {
+            Some(cmd_str) => self.run_backend_install_hook("postinstall", cmd_str),
+            None => Ok(()),
+        }
+    }
+
+    fn validate_transaction(&self, to_install: &[String], to_remove: &[String]) -> Result<()> {
+        if let Some(cmd_template) = &self.config.dry_install_cmd {
+            self.run_dry_check("install", cmd_template, to_install)?;
+        }
+        if let Some(cmd_template) = &self.config.dry_remove_cmd {
+            self.run_dry_check("remove", cmd_template, to_remove)?;
+        }
+        Ok(())
+    }
+
+    fn supports_package_size(&self) -> bool {
+        self.config.size_cmd.is_some()
+    }
+
+    fn package_size(&self, package: &str) -> Result<Option<u64>> {
+        let Some(size_cmd) = self.config.size_cmd.as_ref() else {
+            return Ok(None);
+        };
+
+        let cmd_str = size_cmd.replace("{package}", &sanitize::shell_escape(package));
+        let mut cmd = self.build_command(&cmd_str, CommandMode::ReadOnly)?;
+        let output = self.run_output_command(&mut cmd, &cmd_str, Duration::from_secs(30))?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(parse_size))
+    }
+
+    fn supports_info(&self) -> bool {
+        self.config.info_cmd.is_some()
+    }
+
+    fn info(&self, package: &str) -> Result<PackageInfo> {
+        let info_cmd = self.config.info_cmd.as_ref().ok_or_else(|| {
+            DeclarchError::PackageManagerError(format!(
+                "info unsupported for backend '{}' (no info_cmd configured)",
+                self.config.name
+            ))
+        })?;
+        sanitize::validate_package_name(package)?;
+
+        let cmd_str = info_cmd.replace("{package}", &sanitize::shell_escape(package));
+        let mut cmd = self.build_command(&cmd_str, CommandMode::ReadOnly)?;
+        let output = self.run_output_command(&mut cmd, &cmd_str, Duration::from_secs(30))?;
+
+        if !output.status.success() {
+            return Err(DeclarchError::PackageManagerError(format!(
+                "Failed to get info for {}:{}: {}",
+                self.config.name,
+                package,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        self.parse_info(package, &output.stdout)
+    }
+
+    fn supports_outdated(&self) -> bool {
+        self.config.outdated_cmd.is_some()
+    }
+
+    fn list_outdated(&self) -> Result<Vec<OutdatedPackage>> {
+        let outdated_cmd = self.config.outdated_cmd.as_ref().ok_or_else(|| {
+            DeclarchError::PackageManagerError(format!(
+                "Backend '{}' cannot report outdated packages (no outdated_cmd configured)",
+                self.config.name
+            ))
+        })?;
+
+        let cmd_str = outdated_cmd.clone();
+        let mut cmd = self.build_command(&cmd_str, CommandMode::ReadOnly)?;
+        let output = self.run_output_command(&mut cmd, &cmd_str, Duration::from_secs(120))?;
+
+        // Several managers exit non-zero for "nothing outdated" (pacman -Qu) or
+        // for "something outdated" (npm outdated), so only an empty stdout with
+        // an error message counts as a failure.
+        if !output.status.success()
+            && output.stdout.iter().all(u8::is_ascii_whitespace)
+            && !output.stderr.iter().all(u8::is_ascii_whitespace)
+        {
+            return Err(DeclarchError::PackageManagerError(format!(
+                "Failed to list outdated packages for {}: {}",
+                self.config.name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        self.parse_outdated(&output.stdout)
+    }
+
+    fn supports_orphans(&self) -> bool {
+        self.config.orphans_cmd.is_some()
+    }
+
+    fn list_orphans(&self) -> Result<Vec<String>> {
+        let Some(orphans_cmd) = self.config.orphans_cmd.as_ref() else {
+            return Ok(Vec::new());
+        };
+
+        let cmd_str = orphans_cmd.clone();
+        let mut cmd = self.build_command(&cmd_str, CommandMode::ReadOnly)?;
+        let output = self.run_output_command(&mut cmd, &cmd_str, Duration::from_secs(60))?;
+
+        // `pacman -Qtdq` exits 1 when there are no orphans, so as with
+        // outdated_cmd only an empty stdout with an error message is a failure.
+        if !output.status.success()
+            && output.stdout.iter().all(u8::is_ascii_whitespace)
+            && !output.stderr.iter().all(u8::is_ascii_whitespace)
+        {
+            return Err(DeclarchError::PackageManagerError(format!(
+                "Failed to list orphaned dependencies for {}: {}",
+                self.config.name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(parse_orphan_names(&output.stdout))
+    }
+
+    fn supports_dependency_graph(&self) -> bool {
+        self.config.dependencies_cmd.is_some()
+    }
+
+    fn dependency_graph(&self) -> Result<DependencyGraph> {
+        let Some(dependencies_cmd) = self.config.dependencies_cmd.as_ref() else {
+            return Ok(DependencyGraph::default());
+        };
+
+        let cmd_str = dependencies_cmd.clone();
+        let mut cmd = self.build_command(&cmd_str, CommandMode::ReadOnly)?;
+        // Field labels are matched in English
+        cmd.env("LC_ALL", "C");
+        let output = self.run_output_command(&mut cmd, &cmd_str, DEFAULT_COMMAND_TIMEOUT)?;
+        if !output.status.success() {
+            return Err(DeclarchError::PackageManagerError(format!(
+                "Failed to read the dependency graph for {}: {}",
+                self.config.name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(dependency_parsing::parse_dependency_blocks(
+            &String::from_utf8_lossy(&output.stdout),
+        ))
+    }
+
     fn supports_search_local(&self) -> bool {
         self.config.search_local_cmd.is_some()
     }
@@ -341,5 +645,19 @@ impl PackageManager for GenericManager {
     }
 }
 
+/// First word of each non-empty line (`pacman -Qtd` also prints versions)
+fn parse_orphan_names(stdout: &[u8]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for name in String::from_utf8_lossy(stdout)
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+    {
+        if !names.iter().any(|known| known == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,56 @@
+use crate::packages::traits::DependencyGraph;
+use std::collections::HashMap;
+
+/// Parse `Key : value` package blocks (`pacman -Qi`, C locale) into a
+/// dependency graph; indented lines continue the previous value.
+pub(super) fn parse_dependency_blocks(stdout: &str) -> DependencyGraph {
+    let mut graph = DependencyGraph::default();
+
+    for block in stdout.split("\n\n") {
+        let mut fields: HashMap<&str, String> = HashMap::new();
+        let mut last_key: Option<&str> = None;
+
+        for line in block.lines() {
+            if line.starts_with(char::is_whitespace) {
+                // Wrapped continuation of the previous field
+                if let Some(key) = last_key
+                    && let Some(value) = fields.get_mut(key)
+                {
+                    value.push(' ');
+                    value.push_str(line.trim());
+                }
+                continue;
+            }
+            if let Some((key, value)) = line.split_once(':') {
+                let key = key.trim();
+                fields.insert(key, value.trim().to_string());
+                last_key = Some(key);
+            }
+        }
+
+        let Some(name) = fields.get("Name").cloned() else {
+            continue;
+        };
+
+        if fields
+            .get("Install Reason")
+            .is_some_and(|reason| reason.contains("dependency"))
+        {
+            graph.as_dependency.insert(name.clone());
+        }
+
+        let dependents = fields
+            .get("Required By")
+            .map(|value| {
+                value
+                    .split_whitespace()
+                    .filter(|d| *d != "None")
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        graph.required_by.insert(name, dependents);
+    }
+
+    graph
+}
@@ -0,0 +1,148 @@
+use super::GenericManager;
+use crate::backends::config::InfoFormat;
+use crate::error::{DeclarchError, Result};
+use crate::packages::traits::PackageInfo;
+use serde_json::Value;
+
+impl GenericManager {
+    /// Parse `info_cmd` output; `package` names the result when the output does not
+    pub(super) fn parse_info(&self, package: &str, stdout: &[u8]) -> Result<PackageInfo> {
+        let stdout = String::from_utf8_lossy(stdout);
+        if stdout.trim().is_empty() {
+            return Err(DeclarchError::PackageManagerError(format!(
+                "{} printed no info for '{}'",
+                self.config.name, package
+            )));
+        }
+
+        match self
+            .config
+            .info_format
+            .clone()
+            .unwrap_or(InfoFormat::KeyValue)
+        {
+            InfoFormat::KeyValue => Ok(self.parse_info_key_value(package, &stdout)),
+            InfoFormat::Json => self.parse_info_json(package, &stdout),
+        }
+    }
+
+    /// `Name : bat` lines (pacman -Si, apt show); indented lines continue the
+    /// previous value. Labels match case-insensitively; the first record wins.
+    fn parse_info_key_value(&self, package: &str, stdout: &str) -> PackageInfo {
+        let mut fields: Vec<(String, String)> = Vec::new();
+        for line in stdout.lines() {
+            if line.trim().is_empty() {
+                if !fields.is_empty() {
+                    break;
+                }
+                continue;
+            }
+            if line.starts_with(char::is_whitespace) {
+                if let Some((_, value)) = fields.last_mut() {
+                    value.push(' ');
+                    value.push_str(line.trim());
+                }
+                continue;
+            }
+            if let Some((label, value)) = line.split_once(':') {
+                fields.push((label.trim().to_lowercase(), value.trim().to_string()));
+            }
+        }
+
+        let field = |key: &Option<String>, default: &str| {
+            let key = key.as_deref().unwrap_or(default).to_lowercase();
+            fields
+                .iter()
+                .find(|(label, _)| *label == key)
+                .map(|(_, value)| value.clone())
+                .filter(|value| !value.is_empty() && value != "None")
+        };
+
+        PackageInfo {
+            name: field(&self.config.info_name_key, "name").unwrap_or_else(|| package.to_string()),
+            version: field(&self.config.info_version_key, "version"),
+            description: field(&self.config.info_desc_key, "description"),
+            homepage: field(&self.config.info_homepage_key, "url"),
+            depends: field(&self.config.info_depends_key, "depends on")
+                .map(|value| split_depends(&value))
+                .unwrap_or_default(),
+            backend: self.backend_type.clone(),
+        }
+    }
+
+    /// One JSON object (or the first element of an array), optionally under `info_json_path`
+    fn parse_info_json(&self, package: &str, stdout: &str) -> Result<PackageInfo> {
+        let value: Value = serde_json::from_str(stdout)?;
+        let value = match self.config.info_json_path.as_deref() {
+            Some(path) if !path.is_empty() => self.navigate_json_path(&value, path)?,
+            _ => value,
+        };
+        let object = match &value {
+            Value::Array(items) => items.first().cloned().unwrap_or(Value::Null),
+            _ => value,
+        };
+        if !object.is_object() {
+            return Err(DeclarchError::PackageManagerError(format!(
+                "{} info output for '{}' is not a JSON object",
+                self.config.name, package
+            )));
+        }
+
+        let field = |key: &Option<String>, default: &str| {
+            object
+                .get(key.as_deref().unwrap_or(default))
+                .and_then(json_text)
+        };
+        let depends = object
+            .get(
+                self.config
+                    .info_depends_key
+                    .as_deref()
+                    .unwrap_or("dependencies"),
+            )
+            .map(json_depends)
+            .unwrap_or_default();
+
+        Ok(PackageInfo {
+            name: field(&self.config.info_name_key, "name").unwrap_or_else(|| package.to_string()),
+            version: field(&self.config.info_version_key, "version"),
+            description: field(&self.config.info_desc_key, "description"),
+            homepage: field(&self.config.info_homepage_key, "homepage"),
+            depends,
+            backend: self.backend_type.clone(),
+        })
+    }
+}
+
+/// `glibc  gcc-libs` (pacman) or `libc6 (>= 2.34), libgcc-s1` (apt)
+fn split_depends(value: &str) -> Vec<String> {
+    let parts: Vec<&str> = if value.contains(',') {
+        value.split(',').collect()
+    } else {
+        value.split_whitespace().collect()
+    };
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|dep| !dep.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// An array of names, an object keyed by name (`npm view --json`), or a string
+fn json_depends(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(items) => items.iter().filter_map(json_text).collect(),
+        Value::Object(entries) => entries.keys().cloned().collect(),
+        Value::String(s) => split_depends(s),
+        _ => Vec::new(),
+    }
+}
+
+fn json_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,85 @@
+//! Install outcome from captured output
+//!
+//! Some package managers exit 0 after failing to install a package and only
+//! say so in their output. Backends with `install_failure_regex` or
+//! `install_success_regex` have their install output scanned instead of
+//! trusting the exit status alone.
+
+use crate::backends::config::BackendConfig;
+use crate::error::{DeclarchError, Result};
+use regex::Regex;
+
+/// Output lines quoted in a soft-failure error before eliding the rest
+const MAX_LINES_SHOWN: usize = 5;
+
+/// Whether installs of this backend are judged by their output
+pub(super) fn checks_output(config: &BackendConfig) -> bool {
+    config.install_failure_regex.is_some() || config.install_success_regex.is_some()
+}
+
+/// Decide whether an install succeeded from its captured output.
+///
+/// A failure regex match is an error whatever the exit status; otherwise a
+/// success regex, when set, decides alone; otherwise the exit status does.
+pub(super) fn check_install_output(
+    config: &BackendConfig,
+    verb: &str,
+    exit_ok: bool,
+    stdout: &str,
+    stderr: &str,
+) -> Result<()> {
+    let lines = || stdout.lines().chain(stderr.lines());
+
+    if let Some(pattern) = &config.install_failure_regex {
+        let failure = compile(config, "install_failure_regex", pattern)?;
+        let offending: Vec<&str> = lines().filter(|line| failure.is_match(line)).collect();
+        if !offending.is_empty() {
+            return Err(DeclarchError::PackageManagerError(format!(
+                "{} {} failed: output matched install_failure_regex:\n{}",
+                config.name,
+                verb,
+                quote_lines(&offending)
+            )));
+        }
+    }
+
+    if let Some(pattern) = &config.install_success_regex {
+        let success = compile(config, "install_success_regex", pattern)?;
+        if lines().any(|line| success.is_match(line)) {
+            return Ok(());
+        }
+        return Err(DeclarchError::PackageManagerError(format!(
+            "{} {} failed: output did not match install_success_regex",
+            config.name, verb
+        )));
+    }
+
+    if !exit_ok {
+        return Err(DeclarchError::PackageManagerError(format!(
+            "{} {} failed",
+            config.name, verb
+        )));
+    }
+    Ok(())
+}
+
+fn compile(config: &BackendConfig, field: &str, pattern: &str) -> Result<Regex> {
+    Regex::new(pattern).map_err(|e| {
+        DeclarchError::ConfigError(format!(
+            "Backend '{}' has invalid {}: {}",
+            config.name, field, e
+        ))
+    })
+}
+
+fn quote_lines(lines: &[&str]) -> String {
+    let mut quoted: Vec<String> = lines
+        .iter()
+        .take(MAX_LINES_SHOWN)
+        .map(|line| format!("  {}", line.trim_end()))
+        .collect();
+    if lines.len() > MAX_LINES_SHOWN {
+        quoted.push(format!("  (+{} more)", lines.len() - MAX_LINES_SHOWN));
+    }
+    quoted.join("\n")
+}
@@ -0,0 +1,188 @@
+use super::GenericManager;
+use crate::backends::config::OutputFormat;
+use crate::error::{DeclarchError, Result};
+use crate::packages::traits::OutdatedPackage;
+use crate::utils::regex_cache;
+use serde_json::Value;
+
+impl GenericManager {
+    /// Parse `outdated_cmd` output into name/current/available entries, sorted by name
+    pub(super) fn parse_outdated(&self, stdout: &[u8]) -> Result<Vec<OutdatedPackage>> {
+        let stdout = String::from_utf8_lossy(stdout);
+        let format = self
+            .config
+            .outdated_format
+            .clone()
+            .unwrap_or(OutputFormat::SplitWhitespace);
+
+        let mut packages = match format {
+            OutputFormat::SplitWhitespace => self.parse_outdated_columns(&stdout, None),
+            OutputFormat::TabSeparated => self.parse_outdated_columns(&stdout, Some('\t')),
+            OutputFormat::Json => self.parse_outdated_json(&stdout)?,
+            OutputFormat::JsonLines => {
+                let mut packages = Vec::new();
+                for line in stdout.lines().filter(|line| !line.trim().is_empty()) {
+                    let value: Value = serde_json::from_str(line)?;
+                    packages.extend(self.outdated_from_object(&value, None));
+                }
+                packages
+            }
+            OutputFormat::JsonObjectKeys => {
+                if stdout.trim().is_empty() {
+                    return Ok(Vec::new());
+                }
+                let value: Value = serde_json::from_str(&stdout)?;
+                self.outdated_from_keyed_object(&value)
+            }
+            OutputFormat::Regex => self.parse_outdated_regex(&stdout)?,
+            OutputFormat::NpmJson | OutputFormat::Custom => {
+                return Err(DeclarchError::PackageManagerError(format!(
+                    "Format {:?} is not supported for outdated output",
+                    format
+                )));
+            }
+        };
+
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(packages)
+    }
+
+    /// Columns such as `bat 0.24.0-1 -> 0.25.0-1` (pacman) or `bat (0.24.0) < 0.25.0` (brew)
+    fn parse_outdated_columns(
+        &self,
+        stdout: &str,
+        separator: Option<char>,
+    ) -> Vec<OutdatedPackage> {
+        let name_col = self.config.outdated_name_col.unwrap_or(0);
+        let current_col = self.config.outdated_current_col.unwrap_or(1);
+
+        let mut packages = Vec::new();
+        for line in stdout.lines() {
+            let parts: Vec<&str> = match separator {
+                Some(sep) => line.split(sep).map(str::trim).collect(),
+                None => line.split_whitespace().collect(),
+            };
+            let Some(name) = parts.get(name_col).filter(|name| !name.is_empty()) else {
+                continue;
+            };
+            let available_col = self
+                .config
+                .outdated_available_col
+                .unwrap_or(parts.len() - 1);
+            let version_at = |col: usize| {
+                parts
+                    .get(col)
+                    .filter(|_| col != name_col)
+                    .map(|value| strip_version_decoration(value))
+                    .filter(|value| !value.is_empty())
+            };
+
+            packages.push(OutdatedPackage {
+                name: name.to_string(),
+                current: version_at(current_col),
+                available: version_at(available_col),
+                backend: self.backend_type.clone(),
+            });
+        }
+        packages
+    }
+
+    /// An array of objects, or an object keyed by package name (`npm outdated --json`)
+    fn parse_outdated_json(&self, stdout: &str) -> Result<Vec<OutdatedPackage>> {
+        if stdout.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let value: Value = serde_json::from_str(stdout)?;
+        let entries = match self.config.outdated_json_path.as_deref() {
+            Some(path) if !path.is_empty() => self.navigate_json_path(&value, path)?,
+            _ => value,
+        };
+
+        match &entries {
+            Value::Array(items) => Ok(items
+                .iter()
+                .filter_map(|item| self.outdated_from_object(item, None))
+                .collect()),
+            Value::Object(_) => Ok(self.outdated_from_keyed_object(&entries)),
+            _ => Err(DeclarchError::PackageManagerError(
+                "Outdated output is not a JSON array or object".into(),
+            )),
+        }
+    }
+
+    fn outdated_from_keyed_object(&self, value: &Value) -> Vec<OutdatedPackage> {
+        value
+            .as_object()
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|(name, item)| self.outdated_from_object(item, Some(name)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// One JSON entry; `name` is given when the entry is keyed by package name
+    fn outdated_from_object(&self, item: &Value, name: Option<&str>) -> Option<OutdatedPackage> {
+        let name_key = self.config.outdated_name_key.as_deref().unwrap_or("name");
+        let current_key = self
+            .config
+            .outdated_current_key
+            .as_deref()
+            .unwrap_or("current");
+        let available_key = self
+            .config
+            .outdated_available_key
+            .as_deref()
+            .unwrap_or("latest");
+
+        let name = name.or_else(|| item.get(name_key).and_then(Value::as_str))?;
+        Some(OutdatedPackage {
+            name: name.to_string(),
+            current: item.get(current_key).and_then(json_version),
+            available: item.get(available_key).and_then(json_version),
+            backend: self.backend_type.clone(),
+        })
+    }
+
+    fn parse_outdated_regex(&self, stdout: &str) -> Result<Vec<OutdatedPackage>> {
+        let pattern = self.config.outdated_regex.as_ref().ok_or_else(|| {
+            DeclarchError::PackageManagerError("outdated_regex not configured".into())
+        })?;
+        let name_group = self.config.outdated_regex_name_group.unwrap_or(1);
+        let current_group = self.config.outdated_regex_current_group.unwrap_or(2);
+        let available_group = self.config.outdated_regex_available_group.unwrap_or(3);
+
+        let regex = regex_cache::get_cached_regex(pattern).map_err(|e| {
+            DeclarchError::PackageManagerError(format!("Invalid outdated regex: {}", e))
+        })?;
+
+        Ok(regex
+            .captures_iter(stdout)
+            .filter_map(|caps| {
+                let group = |index: usize| caps.get(index).map(|m| m.as_str().to_string());
+                Some(OutdatedPackage {
+                    name: group(name_group)?,
+                    current: group(current_group),
+                    available: group(available_group),
+                    backend: self.backend_type.clone(),
+                })
+            })
+            .collect())
+    }
+}
+
+/// Version as printed, without the brackets some managers wrap it in (`(1.0)`)
+fn strip_version_decoration(value: &str) -> String {
+    value
+        .trim_matches(|c| matches!(c, '(' | ')' | '[' | ']' | ','))
+        .to_string()
+}
+
+fn json_version(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
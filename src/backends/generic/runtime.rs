@@ -1,9 +1,12 @@
-use super::{CommandMode, GenericManager};
+use super::install_output::{check_install_output, checks_output};
+use super::{CommandMode, DEFAULT_COMMAND_TIMEOUT, GenericManager};
 use crate::backends::generic::command_exec::{
     run_command_with_timeout, run_interactive_command_with_timeout,
 };
 use crate::error::{DeclarchError, Result};
+use crate::ui::progress::Spinner;
 use crate::utils::sanitize;
+use std::io::Write;
 use std::process::{Command, ExitStatus, Output};
 use std::time::Duration;
 
@@ -11,8 +14,8 @@ impl GenericManager {
     /// Get the actual binary to use (first available from alternatives).
     /// Handles fallback if primary binary not available and fallback configured.
     pub(super) fn get_binary(&self) -> Result<String> {
-        if let Some(bin) = self.config.binary.find_available() {
-            return Ok(bin);
+        if let Some(bin) = self.usable_binary() {
+            return Ok(bin.to_string());
         }
 
         if let Some(fallback_name) = &self.config.fallback {
@@ -21,12 +24,22 @@ impl GenericManager {
             })?;
 
             if let Some(fallback_config) = all_backends.get(fallback_name)
-                && let Some(fallback_bin) = fallback_config.binary.find_available()
+                && let Some(fallback_bin) =
+                    super::version_check::find_usable_binary(fallback_config)
             {
                 return Ok(fallback_bin);
             }
         }
 
+        if let Some(min_version) = &self.config.min_version {
+            return Err(DeclarchError::PackageManagerError(format!(
+                "{} {} or newer not found. Please install or upgrade {} first.",
+                self.config.binary.primary(),
+                min_version,
+                self.config.name
+            )));
+        }
+
         Err(DeclarchError::PackageManagerError(format!(
             "{} not found. Please install {} first.",
             self.config.binary.primary(),
@@ -34,14 +47,17 @@ impl GenericManager {
         )))
     }
 
-    /// Build command with optional sudo.
+    /// Build command with optional escalation (`sudo` unless configured otherwise).
     /// Uses the resolved binary (respecting fallback if needed).
     pub(super) fn build_command(&self, cmd_str: &str, mode: CommandMode) -> Result<Command> {
-        let binary = self.get_binary()?;
-        let cmd_str = self.replace_common_placeholders(cmd_str, &binary);
-
-        let use_sudo = self.config.needs_sudo && matches!(mode, CommandMode::Mutating);
-        let mut cmd = crate::utils::platform::build_shell_command(&cmd_str, use_sudo)?;
+        let (cmd_str, escalation) = self.resolve_command(cmd_str, mode)?;
+        if escalation.is_some() {
+            crate::utils::platform::require_escalation(&self.escalation)?;
+        }
+        let mut cmd = crate::utils::platform::build_shell_command_as(&cmd_str, escalation)?;
+        if self.echo_commands {
+            crate::ui::info(&command_echo(&self.config.name, &cmd_str, escalation));
+        }
 
         if let Some(env_vars) = &self.config.preinstall_env {
             for (key, value) in env_vars {
@@ -52,6 +68,67 @@ impl GenericManager {
         Ok(cmd)
     }
 
+    /// The command line `build_command` would run for `cmd_str`, without
+    /// running it, e.g. `sudo sh -c 'pacman -S bat'`.
+    pub(super) fn command_line(&self, cmd_str: &str, mode: CommandMode) -> Result<String> {
+        let (cmd_str, escalation) = self.resolve_command(cmd_str, mode)?;
+        Ok(crate::utils::platform::shell_command_line(
+            &cmd_str, escalation,
+        ))
+    }
+
+    /// Shell command text with placeholders filled in, and the escalation
+    /// program it runs through, if any.
+    fn resolve_command(&self, cmd_str: &str, mode: CommandMode) -> Result<(String, Option<&str>)> {
+        let binary = self.get_binary()?;
+        let cmd_str = self.replace_common_placeholders(cmd_str, &binary);
+
+        let use_sudo =
+            self.config.needs_sudo && !self.no_sudo && matches!(mode, CommandMode::Mutating);
+        Ok((cmd_str, use_sudo.then_some(self.escalation.as_str())))
+    }
+
+    /// `cmd_template` with `{packages}` filled in and the noconfirm flag
+    /// appended when enabled.
+    pub(super) fn package_command(&self, cmd_template: &str, packages: &[String]) -> String {
+        let mut cmd_str = cmd_template.replace("{packages}", &self.format_packages(packages));
+        if self.noconfirm
+            && let Some(flag) = &self.config.noconfirm_flag
+        {
+            cmd_str.push(' ');
+            cmd_str.push_str(flag);
+        }
+        cmd_str
+    }
+
+    /// Commands an install-style template runs for `packages`: one per
+    /// package for `batch false` backends, otherwise one for all of them.
+    pub(super) fn install_command_lines(
+        &self,
+        cmd_template: &str,
+        packages: &[String],
+    ) -> Result<Vec<String>> {
+        if packages.is_empty() {
+            return Ok(Vec::new());
+        }
+        sanitize::validate_package_names(packages)?;
+
+        let batches: Vec<&[String]> = if self.config.batch {
+            vec![packages]
+        } else {
+            packages.iter().map(std::slice::from_ref).collect()
+        };
+        batches
+            .into_iter()
+            .map(|batch| {
+                self.command_line(
+                    &self.package_command(cmd_template, batch),
+                    CommandMode::Mutating,
+                )
+            })
+            .collect()
+    }
+
     /// Format package list for command.
     ///
     /// SECURITY: Each package name is shell-escaped to prevent injection attacks.
@@ -91,6 +168,226 @@ impl GenericManager {
         })
     }
 
+    /// Execute a mutating command: interactive by default, captured behind a
+    /// progress line with `--quiet-backends`.
+    ///
+    /// Captured output is only shown when the command fails.
+    pub(super) fn run_mutating_status(
+        &self,
+        cmd: &mut Command,
+        command_label: &str,
+        progress_label: &str,
+        timeout: Duration,
+    ) -> Result<ExitStatus> {
+        if !self.quiet_output {
+            return self.run_interactive_status(cmd, command_label, timeout);
+        }
+
+        let mut spinner = Spinner::new(progress_label);
+        spinner.update_message(progress_label);
+        let output = match self.run_output_command(cmd, command_label, timeout) {
+            Ok(output) => output,
+            Err(e) => {
+                spinner.finish_with_error(&format!("{} failed", progress_label));
+                return Err(e);
+            }
+        };
+
+        if output.status.success() {
+            spinner.finish_with_success(&format!("{} done", progress_label));
+        } else {
+            spinner.finish_with_error(&format!("{} failed ({})", progress_label, output.status));
+            dump_captured_output(&output);
+        }
+        Ok(output.status)
+    }
+
+    /// Run an install-style command template (`{packages}` placeholder) for `packages`.
+    ///
+    /// `verb`/`progress_verb` label the command, e.g. "install"/"installing".
+    pub(super) fn run_install_template(
+        &self,
+        cmd_template: &str,
+        packages: &[String],
+        verb: &str,
+        progress_verb: &str,
+    ) -> Result<()> {
+        if packages.is_empty() {
+            return Ok(());
+        }
+
+        // Security: Validate all package names before shell execution
+        sanitize::validate_package_names(packages)?;
+
+        if !self.config.batch && packages.len() > 1 {
+            return self.run_install_per_package(cmd_template, packages, verb, progress_verb);
+        }
+
+        let cmd_str = self.package_command(cmd_template, packages);
+        let mut cmd = self.build_command(&cmd_str, CommandMode::Mutating)?;
+
+        // Use interactive timeout function (5 minute timeout for install)
+        let timeout = Duration::from_secs(300);
+        let command_label = format!("{}: {}", verb, cmd_str);
+        let progress_label = format!(
+            "{}: {} {} package(s)",
+            self.config.name,
+            progress_verb,
+            packages.len()
+        );
+        if checks_output(&self.config) {
+            return self.run_checked_install(
+                &mut cmd,
+                &command_label,
+                &progress_label,
+                verb,
+                timeout,
+            );
+        }
+        let status =
+            self.run_mutating_status(&mut cmd, &command_label, &progress_label, timeout)?;
+
+        if !status.success() {
+            return Err(DeclarchError::PackageManagerError(format!(
+                "{} {} failed",
+                self.config.name, verb
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Run an install whose outcome is read from its output
+    /// (`install_failure_regex` / `install_success_regex`).
+    ///
+    /// The output is always captured; it is replayed afterwards unless
+    /// `--quiet-backends` is set and the install succeeded.
+    fn run_checked_install(
+        &self,
+        cmd: &mut Command,
+        command_label: &str,
+        progress_label: &str,
+        verb: &str,
+        timeout: Duration,
+    ) -> Result<()> {
+        let mut spinner = Spinner::new(progress_label);
+        spinner.update_message(progress_label);
+        let output = match self.run_output_command(cmd, command_label, timeout) {
+            Ok(output) => output,
+            Err(e) => {
+                spinner.finish_with_error(&format!("{} failed", progress_label));
+                return Err(e);
+            }
+        };
+
+        let verdict = check_install_output(
+            &self.config,
+            verb,
+            output.status.success(),
+            &String::from_utf8_lossy(&output.stdout),
+            &String::from_utf8_lossy(&output.stderr),
+        );
+        match &verdict {
+            Ok(()) => spinner.finish_with_success(&format!("{} done", progress_label)),
+            Err(_) => {
+                spinner.finish_with_error(&format!("{} failed ({})", progress_label, output.status))
+            }
+        }
+        if !self.quiet_output || verdict.is_err() {
+            dump_captured_output(&output);
+        }
+        verdict
+    }
+
+    /// `batch false`: one command per package, trying every package even
+    /// after a failure so one bad package does not block the rest.
+    fn run_install_per_package(
+        &self,
+        cmd_template: &str,
+        packages: &[String],
+        verb: &str,
+        progress_verb: &str,
+    ) -> Result<()> {
+        let mut failed = Vec::new();
+        for package in packages {
+            if let Err(e) = self.run_install_template(
+                cmd_template,
+                std::slice::from_ref(package),
+                verb,
+                progress_verb,
+            ) {
+                crate::ui::warning(&format!("{}: {}", package, e));
+                failed.push(package.as_str());
+            }
+        }
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(DeclarchError::PackageManagerError(format!(
+                "{} {} failed for: {}",
+                self.config.name,
+                verb,
+                failed.join(", ")
+            )))
+        }
+    }
+
+    /// Run a backend-defined preinstall/postinstall command.
+    ///
+    /// Labelled as backend-defined so it is not confused with user lifecycle hooks.
+    pub(super) fn run_backend_install_hook(&self, phase: &str, cmd_str: &str) -> Result<()> {
+        let mut cmd = self.build_command(cmd_str, CommandMode::Mutating)?;
+
+        crate::ui::info(&format!(
+            "Running backend-defined {} for {}: {}",
+            phase, self.config.name, cmd_str
+        ));
+
+        let status = self.run_interactive_status(&mut cmd, cmd_str, DEFAULT_COMMAND_TIMEOUT)?;
+        if !status.success() {
+            return Err(DeclarchError::PackageManagerError(format!(
+                "Backend-defined {} for {} failed ({})",
+                phase, self.config.name, status
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Run a read-only `dry_install`/`dry_remove` command for `packages`.
+    ///
+    /// A non-zero exit is reported with the backend's own error output.
+    pub(super) fn run_dry_check(
+        &self,
+        action: &str,
+        cmd_template: &str,
+        packages: &[String],
+    ) -> Result<()> {
+        if packages.is_empty() {
+            return Ok(());
+        }
+
+        sanitize::validate_package_names(packages)?;
+        let cmd_str = cmd_template.replace("{packages}", &self.format_packages(packages));
+        let mut cmd = self.build_command(&cmd_str, CommandMode::ReadOnly)?;
+        let output = self.run_output_command(
+            &mut cmd,
+            &format!("dry {}: {}", action, cmd_str),
+            DEFAULT_COMMAND_TIMEOUT,
+        )?;
+
+        if output.status.success() {
+            return Ok(());
+        }
+        Err(DeclarchError::PackageManagerError(dry_check_error(
+            &self.config.name,
+            action,
+            &String::from_utf8_lossy(&output.stderr),
+            &output.status.to_string(),
+        )))
+    }
+
     fn format_sources(&self) -> String {
         self.config
             .package_sources
@@ -105,9 +402,103 @@ impl GenericManager {
             .unwrap_or_default()
     }
 
-    fn replace_common_placeholders(&self, template: &str, binary: &str) -> String {
+    fn first_source(&self) -> String {
+        self.config
+            .package_sources
+            .as_ref()
+            .and_then(|sources| sources.first())
+            .map(|s| sanitize::shell_escape(s))
+            .unwrap_or_default()
+    }
+
+    /// `install_cmd` with `{remote}` filled in: the given remote, else the first
+    /// package source.
+    pub(super) fn install_template(&self, remote: Option<&str>) -> String {
+        self.config
+            .install_cmd
+            .replace("{remote}", &self.remote_arg(remote))
+            .replace("{extra_args}", "")
+    }
+
+    /// [`Self::install_template`] with per-package `extra_args` at `{extra_args}`,
+    /// or just before `{packages}` when the command has no such placeholder.
+    ///
+    /// The args are split shell-style and each word escaped again, so they
+    /// stay arguments of the install command.
+    pub(super) fn install_template_with_args(
+        &self,
+        remote: Option<&str>,
+        extra_args: &str,
+    ) -> Result<String> {
+        let words = shlex::split(extra_args).ok_or_else(|| {
+            DeclarchError::ConfigError(format!(
+                "Invalid extra-args '{}': unbalanced quotes or escapes",
+                sanitize::sanitize_for_display(extra_args)
+            ))
+        })?;
+        let args = words
+            .iter()
+            .map(|word| sanitize::shell_escape(word))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let template = if self.config.install_cmd.contains("{extra_args}") {
+            self.config.install_cmd.replace("{extra_args}", &args)
+        } else if self.config.install_cmd.contains("{packages}") {
+            self.config
+                .install_cmd
+                .replacen("{packages}", &format!("{} {{packages}}", args), 1)
+        } else {
+            format!("{} {}", self.config.install_cmd, args)
+        };
+        Ok(template.replace("{remote}", &self.remote_arg(remote)))
+    }
+
+    fn remote_arg(&self, remote: Option<&str>) -> String {
+        match remote {
+            Some(remote) => sanitize::shell_escape(remote),
+            None => self.first_source(),
+        }
+    }
+
+    pub(super) fn replace_common_placeholders(&self, template: &str, binary: &str) -> String {
         template
             .replace("{binary}", binary)
             .replace("{repos}", &self.format_sources())
+            .replace("{repo:first}", &self.first_source())
+            .replace("{registry}", &self.registry())
+    }
+
+    /// Configured registry URL, shell-escaped; empty when none is set.
+    fn registry(&self) -> String {
+        self.config
+            .registry
+            .as_deref()
+            .map(sanitize::shell_escape)
+            .unwrap_or_default()
     }
 }
+
+/// Verbose echo line for a resolved backend command, noting any escalation.
+pub(super) fn command_echo(backend: &str, cmd_str: &str, escalation: Option<&str>) -> String {
+    match escalation {
+        Some(program) => format!("[{}] $ {}  (run via {})", backend, cmd_str, program),
+        None => format!("[{}] $ {}", backend, cmd_str),
+    }
+}
+
+/// Error for a rejected dry check: the backend's stderr, or its exit status when silent.
+pub(super) fn dry_check_error(backend: &str, action: &str, stderr: &str, status: &str) -> String {
+    let detail = stderr.trim();
+    let detail = if detail.is_empty() { status } else { detail };
+    format!(
+        "{} rejected the planned {} (nothing was changed): {}",
+        backend, action, detail
+    )
+}
+
+/// Replay captured stdout/stderr of a failed command, unmodified.
+fn dump_captured_output(output: &Output) {
+    let _ = std::io::stdout().write_all(&output.stdout);
+    let _ = std::io::stderr().write_all(&output.stderr);
+}
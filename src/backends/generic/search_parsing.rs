@@ -93,6 +93,7 @@ impl GenericManager {
                     version,
                     description,
                     backend: self.backend_type.clone(),
+                    installed_version: None,
                 });
             }
         }
@@ -138,6 +139,7 @@ impl GenericManager {
                             version,
                             description,
                             backend: self.backend_type.clone(),
+                            installed_version: None,
                         });
                     }
                 }
@@ -194,6 +196,7 @@ impl GenericManager {
                             version,
                             description,
                             backend: self.backend_type.clone(),
+                            installed_version: None,
                         });
                     }
                 }
@@ -208,7 +211,7 @@ impl GenericManager {
     }
 
     /// Navigate JSON path (simple implementation)
-    fn navigate_json_path(
+    pub(super) fn navigate_json_path(
         &self,
         value: &serde_json::Value,
         path: &str,
@@ -249,6 +252,7 @@ impl GenericManager {
                     version: None,
                     description,
                     backend: self.backend_type.clone(),
+                    installed_version: None,
                 });
             }
         }
@@ -280,6 +284,7 @@ impl GenericManager {
                     version: None,
                     description,
                     backend: self.backend_type.clone(),
+                    installed_version: None,
                 });
             }
         }
@@ -332,6 +337,7 @@ impl GenericManager {
                     version: None,
                     description,
                     backend: self.backend_type.clone(),
+                    installed_version: None,
                 });
             }
         } else {
@@ -350,6 +356,7 @@ impl GenericManager {
                         version: None,
                         description,
                         backend: self.backend_type.clone(),
+                        installed_version: None,
                     });
                 }
             }
@@ -396,6 +403,7 @@ impl GenericManager {
                             version: None,
                             description: None,
                             backend: self.backend_type.clone(),
+                            installed_version: None,
                         });
                     }
                 }
@@ -432,6 +440,7 @@ impl GenericManager {
                         version,
                         description: None,
                         backend: self.backend_type.clone(),
+                        installed_version: None,
                     });
                 }
             }
@@ -460,6 +469,7 @@ impl GenericManager {
                         version: None,
                         description: None,
                         backend: self.backend_type.clone(),
+                        installed_version: None,
                     });
                 }
             }
@@ -523,6 +533,7 @@ impl GenericManager {
                     version,
                     description: None,
                     backend: self.backend_type.clone(),
+                    installed_version: None,
                 });
             }
         }
@@ -571,6 +582,7 @@ impl GenericManager {
                     version: None,
                     description: None,
                     backend: self.backend_type.clone(),
+                    installed_version: None,
                 });
             }
         }
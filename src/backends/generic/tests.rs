@@ -1,5 +1,5 @@
 use super::*;
-use crate::backends::config::{BackendConfig, BinarySpecifier};
+use crate::backends::config::{BackendConfig, BinarySpecifier, OutputFormat};
 use crate::core::types::Backend;
 
 #[test]
@@ -48,7 +48,10 @@ fn test_sudo_applies_only_to_mutating_operations() {
         needs_sudo: true,
         ..Default::default()
     };
-    let manager = GenericManager::from_config(config, Backend::from("aur"), false);
+    let mut manager = GenericManager::from_config(config, Backend::from("aur"), false);
+    // Independent of whether the test runner itself is root or has sudo
+    manager.no_sudo = false;
+    manager.escalation = "sudo".to_string();
 
     let read_cmd = manager
         .build_command("{binary} -c 'echo read'", CommandMode::ReadOnly)
@@ -60,6 +63,13 @@ fn test_sudo_applies_only_to_mutating_operations() {
         read_debug
     );
 
+    if which::which("sudo").is_err() {
+        let err = manager
+            .build_command("{binary} -c 'echo write'", CommandMode::Mutating)
+            .expect_err("missing escalation command should fail early");
+        assert!(err.to_string().contains("'sudo' not found"));
+        return;
+    }
     let write_cmd = manager
         .build_command("{binary} -c 'echo write'", CommandMode::Mutating)
         .expect("write command should build");
@@ -70,3 +80,679 @@ fn test_sudo_applies_only_to_mutating_operations() {
         write_debug
     );
 }
+
+#[test]
+fn test_mutating_commands_use_configured_escalation() {
+    let config = BackendConfig {
+        name: "test".to_string(),
+        binary: BinarySpecifier::Single("sh".to_string()),
+        needs_sudo: true,
+        ..Default::default()
+    };
+    let mut manager = GenericManager::from_config(config, Backend::from("aur"), false);
+    manager.no_sudo = false;
+    // `env` stands in for doas/run0: present everywhere, and `env sh -c` works
+    manager.escalation = "env".to_string();
+
+    let cmd = manager
+        .build_command("{binary} -c 'echo write'", CommandMode::Mutating)
+        .expect("write command should build");
+    assert_eq!(cmd.get_program(), "env");
+    let args: Vec<_> = cmd.get_args().collect();
+    assert_eq!(args, ["sh", "-c", "sh -c 'echo write'"]);
+
+    manager.escalation = "declarch-missing-escalator".to_string();
+    let err = manager
+        .build_command("{binary} -c 'echo write'", CommandMode::Mutating)
+        .expect_err("missing escalation command should fail early");
+    let msg = err.to_string();
+    assert!(msg.contains("declarch-missing-escalator"));
+    assert!(msg.contains(&crate::project_identity::env_key("SUDO")));
+}
+
+#[test]
+fn test_repo_placeholders_follow_source_order() {
+    let config = BackendConfig {
+        name: "test".to_string(),
+        binary: BinarySpecifier::Single("echo".to_string()),
+        package_sources: Some(vec!["main".to_string(), "backports".to_string()]),
+        ..Default::default()
+    };
+    let manager = GenericManager::from_config(config, Backend::from("apt"), false);
+
+    assert_eq!(
+        manager.replace_common_placeholders("{binary} -t {repo:first} {repos}", "apt"),
+        "apt -t main main backports"
+    );
+}
+
+#[test]
+fn test_remote_placeholder_prefers_recorded_remote() {
+    let config = BackendConfig {
+        name: "flatpak".to_string(),
+        binary: BinarySpecifier::Single("flatpak".to_string()),
+        install_cmd: "flatpak install -y {remote} {packages}".to_string(),
+        package_sources: Some(vec!["flathub".to_string()]),
+        ..Default::default()
+    };
+    let manager = GenericManager::from_config(config, Backend::from("flatpak"), false);
+
+    assert_eq!(
+        manager.install_template(None),
+        "flatpak install -y flathub {packages}"
+    );
+    assert_eq!(
+        manager.install_template(Some("flathub-beta")),
+        "flatpak install -y flathub-beta {packages}"
+    );
+}
+
+#[test]
+fn test_extra_args_are_escaped_and_placed_before_packages() {
+    let manager = GenericManager::from_config(
+        BackendConfig {
+            name: "pip".to_string(),
+            binary: BinarySpecifier::Single("pip".to_string()),
+            install_cmd: "pip install {packages} --quiet".to_string(),
+            ..Default::default()
+        },
+        Backend::from("pip"),
+        false,
+    );
+    assert_eq!(
+        manager
+            .install_template_with_args(None, "--config-settings 'key=a b'")
+            .unwrap(),
+        "pip install --config-settings 'key=a b' {packages} --quiet"
+    );
+    assert!(
+        manager
+            .install_template_with_args(None, "--opt 'unterminated")
+            .is_err()
+    );
+
+    let placed = GenericManager::from_config(
+        BackendConfig {
+            name: "pip".to_string(),
+            binary: BinarySpecifier::Single("pip".to_string()),
+            install_cmd: "pip install {packages} {extra_args}".to_string(),
+            ..Default::default()
+        },
+        Backend::from("pip"),
+        false,
+    );
+    assert_eq!(
+        placed
+            .install_template_with_args(None, "--no-build-isolation")
+            .unwrap(),
+        "pip install {packages} --no-build-isolation"
+    );
+    assert_eq!(placed.install_template(None), "pip install {packages} ");
+}
+
+#[test]
+fn test_planned_commands_include_escalation_and_noconfirm() {
+    let config = BackendConfig {
+        name: "test".to_string(),
+        binary: BinarySpecifier::Single("sh".to_string()),
+        install_cmd: "{binary} install {packages}".to_string(),
+        remove_cmd: Some("{binary} remove {packages}".to_string()),
+        query_cmd: Some("{binary} list".to_string()),
+        noconfirm_flag: Some("--yes".to_string()),
+        needs_sudo: true,
+        batch: false,
+        ..Default::default()
+    };
+    let mut manager = GenericManager::from_config(config, Backend::from("aur"), true);
+    manager.no_sudo = false;
+    manager.escalation = "doas".to_string();
+    let packages = vec!["bat".to_string(), "fd".to_string()];
+
+    let installs = manager
+        .planned_commands(PlannedOperation::Install {
+            packages: &packages,
+            extra_args: None,
+            remote: None,
+        })
+        .expect("install commands should build");
+    assert_eq!(
+        installs,
+        vec![
+            "doas sh -c 'sh install bat --yes'".to_string(),
+            "doas sh -c 'sh install fd --yes'".to_string(),
+        ]
+    );
+
+    let removes = manager
+        .planned_commands(PlannedOperation::Remove(&packages))
+        .expect("remove command should build");
+    assert_eq!(
+        removes,
+        vec!["doas sh -c 'sh remove bat fd --yes'".to_string()]
+    );
+    assert!(
+        manager
+            .planned_commands(PlannedOperation::Preinstall)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_registry_placeholder_uses_configured_registry() {
+    let config = BackendConfig {
+        name: "soar".to_string(),
+        binary: BinarySpecifier::Single("soar".to_string()),
+        registry: Some("https://mirror.example.org/soar".to_string()),
+        ..Default::default()
+    };
+    let manager = GenericManager::from_config(config, Backend::from("soar"), false);
+    assert_eq!(
+        manager.replace_common_placeholders("{binary} list --registry {registry}", "soar"),
+        "soar list --registry https://mirror.example.org/soar"
+    );
+
+    let unset = GenericManager::from_config(
+        BackendConfig {
+            name: "soar".to_string(),
+            ..Default::default()
+        },
+        Backend::from("soar"),
+        false,
+    );
+    assert_eq!(
+        unset.replace_common_placeholders("{binary} list {registry}", "soar"),
+        "soar list "
+    );
+}
+
+#[test]
+fn test_no_sudo_skips_sudo_for_mutating_operations() {
+    let config = BackendConfig {
+        name: "test".to_string(),
+        binary: BinarySpecifier::Single("sh".to_string()),
+        needs_sudo: true,
+        ..Default::default()
+    };
+    let manager =
+        GenericManager::from_config(config, Backend::from("aur"), false).with_no_sudo(true);
+
+    let write_cmd = manager
+        .build_command("{binary} -c 'echo write'", CommandMode::Mutating)
+        .expect("write command should build");
+    let write_debug = format!("{:?}", write_cmd);
+    assert!(
+        !write_debug.contains("\"sudo\""),
+        "--no-sudo must drop sudo even when needs_sudo=true: {}",
+        write_debug
+    );
+}
+
+#[test]
+fn test_description_search_routes_to_search_desc_cmd_when_configured() {
+    let config = BackendConfig {
+        name: "test".to_string(),
+        binary: BinarySpecifier::Single("echo".to_string()),
+        search_cmd: Some("{binary} -Ss {query}".to_string()),
+        search_desc_cmd: Some("{binary} -Ss --desc {query}".to_string()),
+        ..Default::default()
+    };
+    let manager = GenericManager::from_config(config, Backend::from("aur"), false);
+    assert_eq!(
+        manager.search_template(true),
+        Some("{binary} -Ss --desc {query}")
+    );
+    assert_eq!(manager.search_template(false), Some("{binary} -Ss {query}"));
+
+    let config = BackendConfig {
+        name: "test".to_string(),
+        binary: BinarySpecifier::Single("echo".to_string()),
+        search_cmd: Some("{binary} -Ss {query}".to_string()),
+        ..Default::default()
+    };
+    let manager = GenericManager::from_config(config, Backend::from("aur"), false);
+    assert_eq!(manager.search_template(true), Some("{binary} -Ss {query}"));
+}
+
+#[test]
+fn test_min_version_rejects_binary_below_requirement() {
+    let versioned = |min_version: &str| BackendConfig {
+        name: "test".to_string(),
+        binary: BinarySpecifier::Single("sh".to_string()),
+        min_version: Some(min_version.to_string()),
+        version_check_cmd: Some("echo 'tool version 1.4.2'".to_string()),
+        ..Default::default()
+    };
+
+    let too_old = GenericManager::from_config(versioned("1.5.0"), Backend::from("test"), false);
+    assert!(!too_old.is_available());
+    assert!(too_old.get_binary().is_err());
+
+    let recent = GenericManager::from_config(versioned("1.4"), Backend::from("test"), false);
+    assert!(recent.is_available());
+    assert_eq!(recent.get_binary().unwrap(), "sh");
+}
+
+#[test]
+fn test_extract_version_prefers_first_capture_group() {
+    assert_eq!(
+        version_check::extract_version("Flatpak 1.14.4\n", r"(\d+(?:\.\d+)+)"),
+        Some("1.14.4".to_string())
+    );
+    assert_eq!(
+        version_check::extract_version("npm@10.2.0 build 7", r"npm@(\S+)"),
+        Some("10.2.0".to_string())
+    );
+    assert_eq!(version_check::extract_version("no digits", r"\d+"), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_health_check_categorizes_list_outcomes() {
+    let probe = |binary: &str, list_cmd: &str, list_format: OutputFormat| {
+        let config = BackendConfig {
+            name: "test".to_string(),
+            binary: BinarySpecifier::Single(binary.to_string()),
+            list_cmd: Some(list_cmd.to_string()),
+            list_format,
+            list_name_key: Some("name".to_string()),
+            ..Default::default()
+        };
+        GenericManager::from_config(config, Backend::from("test"), false).health_check()
+    };
+
+    let ok = probe(
+        "sh",
+        "{binary} -c 'printf \"bat 0.24\\nfd 10.1\\n\"'",
+        OutputFormat::SplitWhitespace,
+    );
+    assert_eq!(ok.status, HealthStatus::Ok);
+    assert_eq!(ok.package_count, Some(2));
+
+    let failed = probe("sh", "{binary} -c 'exit 3'", OutputFormat::SplitWhitespace);
+    assert_eq!(failed.status, HealthStatus::CommandFailed);
+    assert!(failed.detail.is_some());
+
+    let unparsable = probe("sh", "{binary} -c 'echo [1'", OutputFormat::Json);
+    assert_eq!(unparsable.status, HealthStatus::ParseFailed);
+
+    let missing = probe(
+        "declarch-test-missing-binary",
+        "{binary} list",
+        OutputFormat::SplitWhitespace,
+    );
+    assert_eq!(missing.status, HealthStatus::BinaryMissing);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_quiet_backends_captures_install_and_reports_failure() {
+    let config = BackendConfig {
+        name: "test".to_string(),
+        binary: BinarySpecifier::Single("sh".to_string()),
+        install_cmd: "{binary} -c 'exit 0' {packages}".to_string(),
+        remove_cmd: Some("{binary} -c 'echo boom >&2; exit 3' {packages}".to_string()),
+        ..Default::default()
+    };
+    let mut manager = GenericManager::from_config(config, Backend::from("aur"), false);
+    manager.no_sudo = true;
+    manager.quiet_output = true;
+
+    manager
+        .install(&["pkg".to_string()])
+        .expect("captured install should succeed");
+    assert!(manager.remove(&["pkg".to_string()]).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unbatched_install_runs_one_command_per_package() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let log = dir.path().join("installs.log");
+    let config = BackendConfig {
+        name: "test".to_string(),
+        binary: BinarySpecifier::Single("sh".to_string()),
+        install_cmd: format!(
+            "{{binary}} -c 'echo \"$0\" >> {}; test \"$0\" != broken' {{packages}}",
+            log.display()
+        ),
+        batch: false,
+        ..Default::default()
+    };
+    let mut manager = GenericManager::from_config(config, Backend::from("cargo"), false);
+    manager.no_sudo = true;
+    manager.quiet_output = true;
+
+    let packages = vec![
+        "ripgrep".to_string(),
+        "broken".to_string(),
+        "fd-find".to_string(),
+    ];
+    let err = manager
+        .install(&packages)
+        .expect_err("one package fails")
+        .to_string();
+
+    let logged = std::fs::read_to_string(&log).expect("read log");
+    assert_eq!(
+        logged.lines().collect::<Vec<_>>(),
+        vec!["ripgrep", "broken", "fd-find"]
+    );
+    assert!(err.contains("broken"));
+    assert!(!err.contains("ripgrep"));
+}
+
+#[test]
+fn test_parse_outdated_columns_strips_version_decoration() {
+    let config = BackendConfig {
+        name: "pacman".to_string(),
+        binary: BinarySpecifier::Single("echo".to_string()),
+        outdated_cmd: Some("pacman -Qu".to_string()),
+        ..Default::default()
+    };
+    let manager = GenericManager::from_config(config, Backend::from("pacman"), false);
+
+    let outdated = manager
+        .parse_outdated(b"zsh 5.9-1 -> 5.9-2\nbat (0.24.0) < 0.25.0\n\n")
+        .unwrap();
+
+    assert_eq!(
+        outdated,
+        vec![
+            OutdatedPackage {
+                name: "bat".to_string(),
+                current: Some("0.24.0".to_string()),
+                available: Some("0.25.0".to_string()),
+                backend: Backend::from("pacman"),
+            },
+            OutdatedPackage {
+                name: "zsh".to_string(),
+                current: Some("5.9-1".to_string()),
+                available: Some("5.9-2".to_string()),
+                backend: Backend::from("pacman"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_outdated_json_keyed_by_package_name() {
+    let config = BackendConfig {
+        name: "npm".to_string(),
+        binary: BinarySpecifier::Single("echo".to_string()),
+        outdated_cmd: Some("npm outdated -g --json".to_string()),
+        outdated_format: Some(OutputFormat::Json),
+        ..Default::default()
+    };
+    let manager = GenericManager::from_config(config, Backend::from("npm"), false);
+
+    let stdout = br#"{"typescript": {"current": "5.3.3", "wanted": "5.3.3", "latest": "5.4.2"}}"#;
+    let outdated = manager.parse_outdated(stdout).unwrap();
+
+    assert_eq!(outdated.len(), 1);
+    assert_eq!(outdated[0].name, "typescript");
+    assert_eq!(outdated[0].current.as_deref(), Some("5.3.3"));
+    assert_eq!(outdated[0].available.as_deref(), Some("5.4.2"));
+    assert!(manager.parse_outdated(b"").unwrap().is_empty());
+}
+
+#[test]
+fn test_command_echo_notes_escalation() {
+    use super::runtime::command_echo;
+
+    assert_eq!(
+        command_echo("aur", "paru -S bat --needed", None),
+        "[aur] $ paru -S bat --needed"
+    );
+    assert_eq!(
+        command_echo("apt", "apt-get install -y fd-find", Some("sudo")),
+        "[apt] $ apt-get install -y fd-find  (run via sudo)"
+    );
+}
+
+#[test]
+fn test_validate_transaction_runs_dry_commands_and_reports_backend_error() {
+    let config = BackendConfig {
+        name: "test".to_string(),
+        binary: BinarySpecifier::Single("sh".to_string()),
+        dry_install_cmd: Some(
+            "{binary} -c 'for p; do [ \"$p\" = nosuchpkg ] && echo \"target not found: $p\" >&2 && exit 1; done; exit 0' sh {packages}"
+                .to_string(),
+        ),
+        ..Default::default()
+    };
+    let manager = GenericManager::from_config(config, Backend::from("test"), false);
+
+    manager
+        .validate_transaction(&["bat".to_string()], &["anything".to_string()])
+        .expect("known packages pass, removals are unchecked without dry_remove");
+
+    let err = manager
+        .validate_transaction(&["bat".to_string(), "nosuchpkg".to_string()], &[])
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("test rejected the planned install"));
+    assert!(err.contains("target not found: nosuchpkg"));
+}
+
+#[test]
+fn test_parse_info_key_value_joins_continuation_lines() {
+    let config = BackendConfig {
+        name: "pacman".to_string(),
+        binary: BinarySpecifier::Single("echo".to_string()),
+        info_cmd: Some("pacman -Si {package}".to_string()),
+        ..Default::default()
+    };
+    let manager = GenericManager::from_config(config, Backend::from("pacman"), false);
+
+    let stdout = b"Repository      : extra
+Name            : bat
+Version         : 0.24.0-1
+Description     : Cat clone with syntax highlighting
+URL             : https://github.com/sharkdp/bat
+Depends On      : gcc-libs  glibc  libgit2
+                  oniguruma
+Optional Deps   : None
+";
+    let info = manager.parse_info("bat", stdout).unwrap();
+
+    assert_eq!(
+        info,
+        PackageInfo {
+            name: "bat".to_string(),
+            version: Some("0.24.0-1".to_string()),
+            description: Some("Cat clone with syntax highlighting".to_string()),
+            homepage: Some("https://github.com/sharkdp/bat".to_string()),
+            depends: vec![
+                "gcc-libs".to_string(),
+                "glibc".to_string(),
+                "libgit2".to_string(),
+                "oniguruma".to_string(),
+            ],
+            backend: Backend::from("pacman"),
+        }
+    );
+    assert!(manager.parse_info("bat", b"\n").is_err());
+}
+
+#[test]
+fn test_parse_info_json_with_configured_keys() {
+    let config = BackendConfig {
+        name: "npm".to_string(),
+        binary: BinarySpecifier::Single("echo".to_string()),
+        info_cmd: Some("npm view {package} --json".to_string()),
+        info_format: Some(crate::backends::config::InfoFormat::Json),
+        info_version_key: Some("latest".to_string()),
+        ..Default::default()
+    };
+    let manager = GenericManager::from_config(config, Backend::from("npm"), false);
+
+    let stdout = br#"{"name": "typescript", "latest": "5.4.2", "homepage": "https://www.typescriptlang.org/", "dependencies": {"tslib": "^2.0.0"}}"#;
+    let info = manager.parse_info("typescript", stdout).unwrap();
+
+    assert_eq!(info.name, "typescript");
+    assert_eq!(info.version.as_deref(), Some("5.4.2"));
+    assert_eq!(info.description, None);
+    assert_eq!(
+        info.homepage.as_deref(),
+        Some("https://www.typescriptlang.org/")
+    );
+    assert_eq!(info.depends, vec!["tslib".to_string()]);
+}
+
+/// Output of a package manager that exits 0 although the install failed
+const SOFT_FAILURE_OUTPUT: &str = "\
+resolving dependencies...
+error: target not found: nosuchpkg
+error: target not found: typo-pkg
+";
+
+const SUCCESS_OUTPUT: &str = "\
+Collecting requests
+Installing collected packages: requests
+Successfully installed requests-2.32.3
+";
+
+fn output_checked_config(failure: Option<&str>, success: Option<&str>) -> BackendConfig {
+    BackendConfig {
+        name: "test".to_string(),
+        binary: BinarySpecifier::Single("sh".to_string()),
+        install_failure_regex: failure.map(str::to_string),
+        install_success_regex: success.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_failure_regex_turns_exit_zero_into_error_listing_output() {
+    let config = output_checked_config(Some("^error: "), None);
+
+    let err =
+        install_output::check_install_output(&config, "install", true, SOFT_FAILURE_OUTPUT, "")
+            .expect_err("matched failure output fails the install")
+            .to_string();
+    assert!(err.contains("error: target not found: nosuchpkg"));
+    assert!(err.contains("error: target not found: typo-pkg"));
+    assert!(!err.contains("resolving dependencies"));
+
+    assert!(
+        install_output::check_install_output(&config, "install", true, SUCCESS_OUTPUT, "").is_ok()
+    );
+    // Without a failure match the exit status still counts
+    assert!(
+        install_output::check_install_output(&config, "install", false, SUCCESS_OUTPUT, "")
+            .is_err()
+    );
+}
+
+#[test]
+fn test_success_regex_decides_regardless_of_exit_status() {
+    let config = output_checked_config(None, Some("^Successfully installed"));
+
+    assert!(
+        install_output::check_install_output(&config, "install", false, SUCCESS_OUTPUT, "").is_ok()
+    );
+    let err = install_output::check_install_output(&config, "install", true, "", "nothing to do\n")
+        .expect_err("missing success line fails the install")
+        .to_string();
+    assert!(err.contains("did not match install_success_regex"));
+}
+
+#[test]
+fn test_failure_regex_wins_over_success_regex_and_scans_stderr() {
+    let config = output_checked_config(Some("^ERROR: "), Some("^Successfully installed"));
+
+    assert!(
+        install_output::check_install_output(
+            &config,
+            "install",
+            true,
+            SUCCESS_OUTPUT,
+            "ERROR: pip's dependency resolver found conflicts\n",
+        )
+        .is_err()
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_install_with_failure_regex_fails_despite_exit_zero() {
+    let mut config = output_checked_config(Some("^error: target not found"), None);
+    config.install_cmd =
+        "{binary} -c 'echo \"error: target not found: $0\"' {packages}".to_string();
+    let mut manager = GenericManager::from_config(config, Backend::from("aur"), false);
+    manager.no_sudo = true;
+    manager.quiet_output = true;
+
+    let err = manager
+        .install(&["nosuchpkg".to_string()])
+        .expect_err("soft failure is detected")
+        .to_string();
+    assert!(err.contains("error: target not found: nosuchpkg"));
+
+    let mut plain = output_checked_config(None, None);
+    plain.install_cmd = "{binary} -c 'echo \"error: target not found: $0\"' {packages}".to_string();
+    let mut manager = GenericManager::from_config(plain, Backend::from("aur"), false);
+    manager.no_sudo = true;
+    manager.quiet_output = true;
+    assert!(manager.install(&["nosuchpkg".to_string()]).is_ok());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_list_orphans_reads_first_word_and_tolerates_empty_exit_one() {
+    let config = BackendConfig {
+        name: "test".to_string(),
+        binary: BinarySpecifier::Single("sh".to_string()),
+        orphans_cmd: Some(
+            "{binary} -c 'printf \"libfoo 1.2-1\\nlibbar 0.3\\n\\nlibfoo 1.2-1\\n\"'".to_string(),
+        ),
+        ..Default::default()
+    };
+    let mut manager = GenericManager::from_config(config, Backend::from("aur"), false);
+    manager.no_sudo = true;
+    assert!(manager.supports_orphans());
+    assert_eq!(
+        manager.list_orphans().unwrap(),
+        vec!["libfoo".to_string(), "libbar".to_string()]
+    );
+
+    manager.config.orphans_cmd = Some("{binary} -c 'exit 1'".to_string());
+    assert!(manager.list_orphans().unwrap().is_empty());
+}
+
+#[test]
+fn test_parse_dependency_blocks_reads_reasons_and_dependents() {
+    let stdout = "Name            : libfoo\nRequired By     : bar baz\n                  qux\nInstall Reason  : Installed as a dependency for another package\n\nName            : bar\nRequired By     : None\nInstall Reason  : Explicitly installed\n";
+
+    let graph = dependency_parsing::parse_dependency_blocks(stdout);
+
+    assert_eq!(graph.required_by["libfoo"], vec!["bar", "baz", "qux"]);
+    assert!(graph.required_by["bar"].is_empty());
+    assert!(graph.as_dependency.contains("libfoo"));
+    assert!(!graph.as_dependency.contains("bar"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_dependency_graph_runs_configured_command() {
+    let config = BackendConfig {
+        name: "test".to_string(),
+        binary: BinarySpecifier::Single("sh".to_string()),
+        dependencies_cmd: Some(
+            "{binary} -c 'printf \"Name : libfoo\\nRequired By : bar\\nInstall Reason : Installed as a dependency\\n\"'"
+                .to_string(),
+        ),
+        ..Default::default()
+    };
+    let mut manager = GenericManager::from_config(config, Backend::from("aur"), false);
+    manager.no_sudo = true;
+    assert!(manager.supports_dependency_graph());
+
+    let graph = manager.dependency_graph().unwrap();
+    assert_eq!(graph.required_by["libfoo"], vec!["bar"]);
+    assert!(graph.as_dependency.contains("libfoo"));
+
+    manager.config.dependencies_cmd = Some("{binary} -c 'exit 1'".to_string());
+    assert!(manager.dependency_graph().is_err());
+}
@@ -0,0 +1,76 @@
+//! Minimum binary version enforcement
+//!
+//! Backends with `min_version` only accept a binary whose reported version
+//! meets it; an older binary is treated as if it were not installed.
+
+use super::command_exec::run_command_with_timeout;
+use crate::backends::config::BackendConfig;
+use crate::ui;
+use crate::utils::update_check::compare_versions;
+use regex::Regex;
+use std::cmp::Ordering;
+use std::time::Duration;
+
+const DEFAULT_VERSION_CHECK_CMD: &str = "{binary} --version";
+const DEFAULT_VERSION_REGEX: &str = r"(\d+(?:\.\d+)+)";
+const VERSION_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// First available binary that satisfies the backend's `min_version`, if set.
+pub(super) fn find_usable_binary(config: &BackendConfig) -> Option<String> {
+    let Some(min_version) = config.min_version.as_deref() else {
+        return config.binary.find_available();
+    };
+
+    config
+        .binary
+        .find_available_where(|bin| binary_meets_min_version(config, bin, min_version))
+}
+
+fn binary_meets_min_version(config: &BackendConfig, bin: &str, min_version: &str) -> bool {
+    let Some(version) = detect_binary_version(config, bin) else {
+        ui::warning(&format!(
+            "Could not determine {} version for backend '{}'; skipping min_version check",
+            bin, config.name
+        ));
+        return true;
+    };
+
+    if compare_versions(&version, min_version) == Ordering::Less {
+        ui::warning(&format!(
+            "{} {} is older than {} required by backend '{}'. Please upgrade {}.",
+            bin, version, min_version, config.name, bin
+        ));
+        return false;
+    }
+    true
+}
+
+fn detect_binary_version(config: &BackendConfig, bin: &str) -> Option<String> {
+    let cmd_str = config
+        .version_check_cmd
+        .as_deref()
+        .unwrap_or(DEFAULT_VERSION_CHECK_CMD)
+        .replace("{binary}", bin);
+    let mut cmd = crate::utils::platform::build_shell_command(&cmd_str, false).ok()?;
+    let output = run_command_with_timeout(&mut cmd, VERSION_CHECK_TIMEOUT).ok()?;
+
+    // Some tools print their version on stderr.
+    let text = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let pattern = config
+        .version_regex
+        .as_deref()
+        .unwrap_or(DEFAULT_VERSION_REGEX);
+    extract_version(&text, pattern)
+}
+
+/// Extract a version with `pattern`, preferring capture group 1 over the whole match.
+pub(super) fn extract_version(text: &str, pattern: &str) -> Option<String> {
+    let caps = Regex::new(pattern).ok()?.captures(text)?;
+    caps.get(1)
+        .or_else(|| caps.get(0))
+        .map(|m| m.as_str().to_string())
+}
@@ -69,8 +69,10 @@
 //!     binary: BinarySpecifier::Single("mybackend".to_string()),
 //!     list_cmd: Some("mybackend list --json".to_string()),
 //!     install_cmd: "mybackend install {packages}".to_string(),
+//!     reinstall_cmd: None,
 //!     remove_cmd: Some("mybackend uninstall {packages}".to_string()),
 //!     query_cmd: None,
+//!     size_cmd: None,
 //!     list_format: OutputFormat::Json,
 //!     list_name_col: None,
 //!     list_version_col: None,
@@ -80,14 +82,31 @@
 //!     list_regex: None,
 //!     list_regex_name_group: None,
 //!     list_regex_version_group: None,
+//!     list_size_key: None,
+//!     list_size_col: None,
+//!     list_regex_size_group: None,
+//!     list_origin_key: None,
+//!     list_origin_col: None,
+//!     list_regex_origin_group: None,
+//!     list_per_line: false,
 //!     noconfirm_flag: Some("--yes".to_string()),
 //!     needs_sudo: false,
+//!     batch: true,
 //!     preinstall_env: None,
+//!     preinstall_cmd: None,
+//!     postinstall_cmd: None,
+//!     dry_install_cmd: None,
+//!     dry_remove_cmd: None,
 //!     package_sources: None,
 //!     use_rust_fallback: false,
 //!     fallback: None,
+//!     aliases: Vec::new(),
 //!     supported_os: None,
+//!     min_version: None,
+//!     version_check_cmd: None,
+//!     version_regex: None,
 //!     search_cmd: None,
+//!     search_desc_cmd: None,
 //!     search_format: None,
 //!     search_json_path: None,
 //!     search_name_key: None,
@@ -109,7 +128,21 @@
 //!     update_cmd: None,
 //!     cache_clean_cmd: None,
 //!     upgrade_cmd: None,
+//!     outdated_cmd: None,
+//!     outdated_format: None,
+//!     outdated_json_path: None,
+//!     outdated_name_key: None,
+//!     outdated_current_key: None,
+//!     outdated_available_key: None,
+//!     outdated_name_col: None,
+//!     outdated_current_col: None,
+//!     outdated_available_col: None,
+//!     outdated_regex: None,
+//!     outdated_regex_name_group: None,
+//!     outdated_regex_current_group: None,
+//!     outdated_regex_available_group: None,
 //!     prefer_list_for_local_search: false,
+//!     ..Default::default()
 //! };
 //! ```
 //!
@@ -5,6 +5,7 @@
 use crate::backends::config::BackendConfig;
 use crate::core::types::PackageMetadata;
 use crate::error::{DeclarchError, Result};
+use crate::utils::size::parse_size;
 use chrono::Utc;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -61,6 +62,8 @@ pub fn parse_json(
                                 variant: None,
                                 installed_at: Utc::now(),
                                 source_file: None,
+                                install_size: size_field(pkg, config),
+                                origin: origin_field(pkg, config),
                             },
                         );
                     }
@@ -83,6 +86,8 @@ pub fn parse_json(
                                 variant: None,
                                 installed_at: Utc::now(),
                                 source_file: None,
+                                install_size: size_field(metadata, config),
+                                origin: origin_field(metadata, config),
                             },
                         );
                     }
@@ -135,6 +140,8 @@ pub fn parse_json_lines(
                             variant: None,
                             installed_at: Utc::now(),
                             source_file: None,
+                            install_size: size_field(&json, config),
+                            origin: origin_field(&json, config),
                         },
                     );
                 }
@@ -198,6 +205,8 @@ pub fn parse_npm_json(
                             variant: None,
                             installed_at: Utc::now(),
                             source_file: None,
+                            install_size: size_field(&json, config),
+                            origin: origin_field(&json, config),
                         },
                     );
                 }
@@ -264,6 +273,8 @@ pub fn parse_json_object_keys(
                     variant: None,
                     installed_at: Utc::now(),
                     source_file: None,
+                    install_size: size_field(metadata, config),
+                    origin: origin_field(metadata, config),
                 },
             );
         }
@@ -272,6 +283,24 @@ pub fn parse_json_object_keys(
     Ok(installed)
 }
 
+/// Installed size under `list_size_key`, as a byte count or a size string
+fn size_field(object: &Value, config: &BackendConfig) -> Option<u64> {
+    match object.get(config.list_size_key.as_deref()?)? {
+        Value::Number(n) => n.as_u64().or_else(|| n.as_f64().map(|f| f as u64)),
+        Value::String(s) => parse_size(s),
+        _ => None,
+    }
+}
+
+/// Remote/repository under `list_origin_key`
+fn origin_field(object: &Value, config: &BackendConfig) -> Option<String> {
+    object
+        .get(config.list_origin_key.as_deref()?)?
+        .as_str()
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
 /// Navigate through JSON structure using dot notation path
 fn navigate_json_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
     let parts: Vec<&str> = path.split('.').collect();
@@ -151,3 +151,25 @@ fn test_parse_json_object_keys_empty() {
     let result = parse_json_object_keys(output, &config).unwrap();
     assert!(result.is_empty());
 }
+
+#[test]
+fn test_parse_json_size_key() {
+    let output = r#"[
+            {"name": "npm", "version": "10.0.0", "size": 1048576},
+            {"name": "pnpm", "version": "8.0.0", "size": "2 MiB"},
+            {"name": "yarn", "version": "1.22.0"}
+        ]"#;
+
+    let config = BackendConfig {
+        list_name_key: Some("name".to_string()),
+        list_version_key: Some("version".to_string()),
+        list_size_key: Some("size".to_string()),
+        ..Default::default()
+    };
+
+    let result = parse_json(output, &config).unwrap();
+
+    assert_eq!(result["npm"].install_size, Some(1_048_576));
+    assert_eq!(result["pnpm"].install_size, Some(2_097_152));
+    assert_eq!(result["yarn"].install_size, None);
+}
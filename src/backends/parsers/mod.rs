@@ -5,7 +5,9 @@ pub mod whitespace;
 
 use crate::backends::config::BackendConfig;
 use crate::core::types::PackageMetadata;
-use crate::error::Result;
+use crate::error::{DeclarchError, Result};
+use crate::utils::regex_cache;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 /// Parse package list from command output
@@ -13,7 +15,7 @@ pub fn parse_package_list(
     output: &[u8],
     config: &BackendConfig,
 ) -> Result<HashMap<String, PackageMetadata>> {
-    let stdout = String::from_utf8_lossy(output);
+    let stdout = filter_list_lines(String::from_utf8_lossy(output), config)?;
 
     match config.list_format {
         crate::backends::config::OutputFormat::SplitWhitespace => {
@@ -40,5 +42,30 @@ pub fn parse_package_list(
     }
 }
 
+/// Apply `filter_regex` (keep only matching lines) and `skip_lines_matching`
+/// (drop matching lines) so banners and warnings never reach the parser.
+fn filter_list_lines<'a>(stdout: Cow<'a, str>, config: &BackendConfig) -> Result<Cow<'a, str>> {
+    if config.list_filter_regex.is_none() && config.list_skip_lines_matching.is_none() {
+        return Ok(stdout);
+    }
+
+    let compile = |pattern: &Option<String>| {
+        pattern
+            .as_deref()
+            .map(regex_cache::get_cached_regex)
+            .transpose()
+            .map_err(|e| DeclarchError::ConfigError(format!("Invalid list line filter: {}", e)))
+    };
+    let keep = compile(&config.list_filter_regex)?;
+    let skip = compile(&config.list_skip_lines_matching)?;
+
+    let filtered: Vec<&str> = stdout
+        .lines()
+        .filter(|line| keep.as_ref().is_none_or(|re| re.is_match(line)))
+        .filter(|line| !skip.as_ref().is_some_and(|re| re.is_match(line)))
+        .collect();
+    Ok(Cow::Owned(filtered.join("\n")))
+}
+
 #[cfg(test)]
 mod tests;
@@ -2,11 +2,16 @@ use crate::backends::config::BackendConfig;
 use crate::core::types::PackageMetadata;
 use crate::error::{DeclarchError, Result};
 use crate::utils::regex_cache;
+use crate::utils::size::parse_size;
 use chrono::Utc;
 use std::collections::HashMap;
 
 /// Parse output using regex pattern
 ///
+/// The pattern runs over the whole output, so block formats where one package
+/// spans several lines work as written. `list_per_line` matches each line
+/// separately instead.
+///
 /// This function uses a global regex cache to avoid recompiling patterns
 /// for repeated calls with the same backend configuration.
 pub fn parse_regex(
@@ -25,10 +30,27 @@ pub fn parse_regex(
 
     let mut installed = HashMap::new();
 
-    for caps in regex.captures_iter(output) {
+    let chunks: Vec<&str> = if config.list_per_line {
+        output.lines().collect()
+    } else {
+        vec![output]
+    };
+
+    for caps in chunks
+        .into_iter()
+        .flat_map(|chunk| regex.captures_iter(chunk))
+    {
         if let Some(name_match) = caps.get(name_group) {
             let name = name_match.as_str().to_string();
             let version = caps.get(version_group).map(|m| m.as_str().to_string());
+            let install_size = config
+                .list_regex_size_group
+                .and_then(|group| caps.get(group))
+                .and_then(|m| parse_size(m.as_str()));
+            let origin = config
+                .list_regex_origin_group
+                .and_then(|group| caps.get(group))
+                .map(|m| m.as_str().to_string());
 
             installed.insert(
                 name,
@@ -37,6 +59,8 @@ pub fn parse_regex(
                     version,
                     installed_at: Utc::now(),
                     source_file: None,
+                    install_size,
+                    origin,
                 },
             );
         }
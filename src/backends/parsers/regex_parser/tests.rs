@@ -108,3 +108,66 @@ fn test_invalid_regex_error() {
         _ => panic!("Expected ConfigError for invalid regex"),
     }
 }
+
+const BLOCK_LIST_FIXTURE: &str = "Name    : firefox
+Version : 128.0-1
+Repo    : extra
+
+Name    : ripgrep
+Version : 14.1.0-1
+Repo    : extra
+";
+
+#[test]
+fn test_parse_regex_matches_block_format_across_lines() {
+    setup();
+
+    let config = BackendConfig {
+        list_regex: Some(r"Name\s*:\s*(\S+)\s+Version\s*:\s*(\S+)".to_string()),
+        list_regex_name_group: Some(1),
+        list_regex_version_group: Some(2),
+        ..Default::default()
+    };
+
+    let result = parse_regex(BLOCK_LIST_FIXTURE, &config).expect("parse block list");
+
+    assert_eq!(result.len(), 2);
+    assert_eq!(result["firefox"].version.as_deref(), Some("128.0-1"));
+    assert_eq!(result["ripgrep"].version.as_deref(), Some("14.1.0-1"));
+}
+
+#[test]
+fn test_parse_regex_dotall_pattern_spans_blocks() {
+    setup();
+
+    let config = BackendConfig {
+        list_regex: Some(r"(?s)Name\s*:\s*(\S+).*?Version\s*:\s*(\S+)".to_string()),
+        list_regex_name_group: Some(1),
+        list_regex_version_group: Some(2),
+        ..Default::default()
+    };
+
+    let result = parse_regex(BLOCK_LIST_FIXTURE, &config).expect("parse block list");
+
+    assert_eq!(result.len(), 2);
+    assert_eq!(result["ripgrep"].version.as_deref(), Some("14.1.0-1"));
+}
+
+#[test]
+fn test_parse_regex_per_line_does_not_cross_lines() {
+    setup();
+
+    let output = "orphan\npacman 6.0.2\n";
+    let config = BackendConfig {
+        list_regex: Some(r"(\S+)\s+(\S+)".to_string()),
+        list_regex_name_group: Some(1),
+        list_regex_version_group: Some(2),
+        list_per_line: true,
+        ..Default::default()
+    };
+
+    let result = parse_regex(output, &config).expect("parse regex");
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(result["pacman"].version.as_deref(), Some("6.0.2"));
+}
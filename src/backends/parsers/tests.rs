@@ -17,3 +17,31 @@ fn test_parse_whitespace() {
     assert!(result.contains_key("package2"));
     assert_eq!(result["package1"].version.as_deref(), Some("1.0.0"));
 }
+
+#[test]
+fn test_list_line_filters_drop_noise_before_parsing() {
+    let output = b"==> Formulae\nbat 0.24.0\nWARNING: pip is deprecated\nfd 10.1.0\n";
+
+    let skipping = BackendConfig {
+        list_name_col: Some(0),
+        list_version_col: Some(1),
+        list_skip_lines_matching: Some("^(==>|WARNING:)".to_string()),
+        ..Default::default()
+    };
+    let result = parse_package_list(output, &skipping).expect("parse package list");
+    let mut names: Vec<_> = result.keys().map(String::as_str).collect();
+    names.sort();
+    assert_eq!(names, vec!["bat", "fd"]);
+    assert_eq!(result["fd"].version.as_deref(), Some("10.1.0"));
+
+    let keeping = BackendConfig {
+        list_name_col: Some(0),
+        list_version_col: Some(1),
+        list_filter_regex: Some(r"^\S+ \d".to_string()),
+        ..Default::default()
+    };
+    let result = parse_package_list(output, &keeping).expect("parse package list");
+    assert_eq!(result.len(), 2);
+    assert!(!result.contains_key("==>"));
+    assert!(!result.contains_key("WARNING:"));
+}
@@ -1,6 +1,7 @@
 use crate::backends::config::BackendConfig;
 use crate::core::types::PackageMetadata;
 use crate::error::Result;
+use crate::utils::size::parse_size;
 use chrono::Utc;
 use std::collections::HashMap;
 
@@ -20,6 +21,16 @@ pub fn parse_tsv(output: &str, config: &BackendConfig) -> Result<HashMap<String,
 
         if let Some(name) = parts.get(name_col) {
             let version = parts.get(version_col).map(|&v| v.to_string());
+            let install_size = config
+                .list_size_col
+                .and_then(|col| parts.get(col))
+                .and_then(|v| parse_size(v));
+            let origin = config
+                .list_origin_col
+                .and_then(|col| parts.get(col))
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+                .map(str::to_string);
 
             installed.insert(
                 name.to_string(),
@@ -28,6 +39,8 @@ pub fn parse_tsv(output: &str, config: &BackendConfig) -> Result<HashMap<String,
                     version,
                     installed_at: Utc::now(),
                     source_file: None,
+                    install_size,
+                    origin,
                 },
             );
         }
@@ -57,3 +57,22 @@ fn test_parse_empty_lines() {
 
     assert_eq!(result.len(), 2);
 }
+
+#[test]
+fn test_parse_origin_column() {
+    let output = "org.mozilla.firefox\t120.0\tflathub-beta\ncom.spotify.Client\t1.2.3\t\n";
+    let config = BackendConfig {
+        list_name_col: Some(0),
+        list_version_col: Some(1),
+        list_origin_col: Some(2),
+        ..Default::default()
+    };
+
+    let result = parse_tsv(output, &config).expect("parse tsv");
+
+    assert_eq!(
+        result["org.mozilla.firefox"].origin.as_deref(),
+        Some("flathub-beta")
+    );
+    assert_eq!(result["com.spotify.Client"].origin, None);
+}
@@ -1,6 +1,7 @@
 use crate::backends::config::BackendConfig;
 use crate::core::types::PackageMetadata;
 use crate::error::Result;
+use crate::utils::size::parse_size;
 use chrono::Utc;
 use std::collections::HashMap;
 
@@ -25,6 +26,13 @@ pub fn parse_whitespace_split(
 
         if let Some(name) = parts.get(name_col) {
             let version = parts.get(version_col).map(|&v| v.to_string());
+            let install_size = config
+                .list_size_col
+                .and_then(|col| column_size(&parts, col));
+            let origin = config
+                .list_origin_col
+                .and_then(|col| parts.get(col))
+                .map(|v| v.to_string());
 
             installed.insert(
                 name.to_string(),
@@ -33,6 +41,8 @@ pub fn parse_whitespace_split(
                     version,
                     installed_at: Utc::now(),
                     source_file: None,
+                    install_size,
+                    origin,
                 },
             );
         }
@@ -41,5 +51,14 @@ pub fn parse_whitespace_split(
     Ok(installed)
 }
 
+/// Size in column `col`, joined with a unit in the next column when split ("12.3 MiB").
+fn column_size(parts: &[&str], col: usize) -> Option<u64> {
+    let value = parts.get(col)?;
+    parts
+        .get(col + 1)
+        .and_then(|unit| parse_size(&format!("{} {}", value, unit)))
+        .or_else(|| parse_size(value))
+}
+
 #[cfg(test)]
 mod tests;
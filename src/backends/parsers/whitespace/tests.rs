@@ -48,3 +48,17 @@ fn test_parse_custom_columns() {
     assert_eq!(result.len(), 2);
     assert_eq!(result["pacman"].version.as_deref(), Some("6.0.2"));
 }
+
+#[test]
+fn test_parse_size_column_with_split_unit() {
+    let output = "bat 0.24.0 4.2 MiB\nfd 10.1.0 2048\n";
+    let config = BackendConfig {
+        list_size_col: Some(2),
+        ..Default::default()
+    };
+
+    let result = parse_whitespace_split(output, &config).expect("parse whitespace");
+
+    assert_eq!(result["bat"].install_size, Some(4_404_019));
+    assert_eq!(result["fd"].install_size, Some(2048));
+}
@@ -74,7 +74,7 @@ pub fn load_backends_from_config_with_sources() -> crate::error::Result<Backends
 
     let config_path = paths::config_file()?;
 
-    if !config_path.exists() {
+    if !crate::config::loader::root_config_available(&config_path) {
         return Ok((Vec::new(), HashMap::new()));
     }
 
@@ -104,7 +104,7 @@ fn build_backend_source_map(
 ///
 pub fn load_all_backends_unified() -> crate::error::Result<HashMap<String, BackendConfig>> {
     let config_path = paths::config_file()?;
-    if !config_path.exists() {
+    if !crate::config::loader::root_config_available(&config_path) {
         return Ok(HashMap::new());
     }
 
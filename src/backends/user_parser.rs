@@ -5,7 +5,9 @@
 
 mod command_fields;
 mod imports;
+mod info_fields;
 mod list_fields;
+mod outdated_fields;
 mod parse_utils;
 mod search_fields;
 mod validation;
@@ -15,14 +17,19 @@ use crate::backends::config::OutputFormat;
 use crate::backends::config::{BackendConfig, BinarySpecifier};
 use crate::error::{DeclarchError, Result};
 use command_fields::{
-    parse_cache_clean_cmd, parse_fallback, parse_install_cmd, parse_noconfirm, parse_remove_cmd,
-    parse_update_cmd, parse_upgrade_cmd,
+    parse_cache_clean_cmd, parse_dependencies_cmd, parse_dry_install_cmd, parse_dry_remove_cmd,
+    parse_fallback, parse_install_cmd, parse_install_output_regex, parse_min_version,
+    parse_noconfirm, parse_orphans_cmd, parse_postinstall_cmd, parse_preinstall_cmd,
+    parse_registry, parse_reinstall_cmd, parse_remove_cmd, parse_size_cmd, parse_update_cmd,
+    parse_upgrade_cmd, parse_version_check_cmd,
 };
 use imports::{collect_import_backends, collect_imports_block_backends};
+use info_fields::parse_info_cmd;
 use kdl::{KdlDocument, KdlNode};
 use list_fields::parse_list_cmd;
-use parse_utils::{parse_bool, parse_env, parse_supported_os};
-use search_fields::{parse_search_cmd, parse_search_local_cmd};
+use outdated_fields::parse_outdated_cmd;
+use parse_utils::{parse_aliases, parse_bool, parse_env, parse_supported_os};
+use search_fields::{parse_search_cmd, parse_search_desc_cmd, parse_search_local_cmd};
 use std::path::Path;
 use validation::validate_backend_config;
 
@@ -60,10 +67,28 @@ pub fn load_user_backends(path: &Path) -> Result<Vec<BackendConfig>> {
     Ok(backends)
 }
 
+/// Load the backend defined in a single file, for ad-hoc use (`--backend-file`).
+///
+/// Unlike imports, a missing file or one without a `backend` node is an error.
+pub fn load_backend_definition(path: &Path) -> Result<BackendConfig> {
+    let content = std::fs::read_to_string(path).map_err(|e| DeclarchError::IoError {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+
+    parse_backend_file(&content)
+        .map_err(|e| {
+            DeclarchError::ConfigError(format!("Invalid backend file '{}': {}", path.display(), e))
+        })?
+        .ok_or_else(|| {
+            DeclarchError::ConfigError(format!("No `backend` node found in '{}'", path.display()))
+        })
+}
+
 /// Parse a single backend from file content
 ///
 /// Used for individual backend files in backends/ directory
-pub(super) fn parse_backend_file(content: &str) -> Result<Option<BackendConfig>> {
+pub(crate) fn parse_backend_file(content: &str) -> Result<Option<BackendConfig>> {
     let doc = KdlDocument::parse(content)
         .map_err(|e| DeclarchError::Other(format!("Failed to parse backend file: {}", e)))?;
 
@@ -103,19 +128,41 @@ fn parse_backend_node(node: &KdlNode) -> Result<BackendConfig> {
                 "binary" => parse_binary(child, &mut config)?,
                 "list" => parse_list_cmd(child, &mut config)?,
                 "install" => parse_install_cmd(child, &mut config)?,
+                "reinstall" => parse_reinstall_cmd(child, &mut config)?,
                 "remove" => parse_remove_cmd(child, &mut config)?,
+                "size" => parse_size_cmd(child, &mut config)?,
                 "search" => parse_search_cmd(child, &mut config)?,
                 "search_local" => parse_search_local_cmd(child, &mut config)?,
+                "search_desc" => parse_search_desc_cmd(child, &mut config)?,
                 "update" => parse_update_cmd(child, &mut config)?,
                 "cache_clean" => parse_cache_clean_cmd(child, &mut config)?,
                 "upgrade" => parse_upgrade_cmd(child, &mut config)?,
+                "outdated" => parse_outdated_cmd(child, &mut config)?,
+                "orphans" => parse_orphans_cmd(child, &mut config)?,
+                "dependencies" => parse_dependencies_cmd(child, &mut config)?,
+                "info" => parse_info_cmd(child, &mut config)?,
+                "preinstall" => parse_preinstall_cmd(child, &mut config)?,
+                "postinstall" => parse_postinstall_cmd(child, &mut config)?,
+                "dry_install" => parse_dry_install_cmd(child, &mut config)?,
+                "dry_remove" => parse_dry_remove_cmd(child, &mut config)?,
+                "install_failure_regex" => {
+                    config.install_failure_regex = parse_install_output_regex(child)?
+                }
+                "install_success_regex" => {
+                    config.install_success_regex = parse_install_output_regex(child)?
+                }
                 "noconfirm" => parse_noconfirm(child, &mut config)?,
                 "needs_sudo" | "sudo" => config.needs_sudo = parse_bool(child)?,
+                "batch" => config.batch = parse_bool(child)?,
                 "prefer_list_for_local_search" => {
                     config.prefer_list_for_local_search = parse_bool(child)?
                 }
                 "env" => parse_env(child, &mut config)?,
                 "fallback" => parse_fallback(child, &mut config)?,
+                "registry" => parse_registry(child, &mut config)?,
+                "alias" | "aliases" => parse_aliases(child, &mut config),
+                "min_version" => parse_min_version(child, &mut config)?,
+                "version_check" => parse_version_check_cmd(child, &mut config)?,
                 "platforms" | "supported_os" | "os" => parse_supported_os(child, &mut config),
                 _ => {
                     // Ignore unknown fields for forward compatibility
@@ -15,6 +15,74 @@ pub(super) fn parse_install_cmd(node: &KdlNode, config: &mut BackendConfig) -> R
     Ok(())
 }
 
+pub(super) fn parse_reinstall_cmd(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
+    let cmd = node
+        .entries()
+        .first()
+        .and_then(|entry| entry.value().as_string())
+        .ok_or_else(|| {
+            DeclarchError::Other(
+                "Reinstall command required. Usage: reinstall \"command\"".to_string(),
+            )
+        })?
+        .to_string();
+
+    if cmd != "-" {
+        config.reinstall_cmd = Some(cmd);
+    }
+    Ok(())
+}
+
+pub(super) fn parse_size_cmd(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
+    let cmd = node
+        .entries()
+        .first()
+        .and_then(|entry| entry.value().as_string())
+        .ok_or_else(|| {
+            DeclarchError::Other("Size command required. Usage: size \"command\"".to_string())
+        })?
+        .to_string();
+
+    if cmd != "-" {
+        config.size_cmd = Some(cmd);
+    }
+    Ok(())
+}
+
+pub(super) fn parse_orphans_cmd(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
+    let cmd = node
+        .entries()
+        .first()
+        .and_then(|entry| entry.value().as_string())
+        .ok_or_else(|| {
+            DeclarchError::Other("Orphans command required. Usage: orphans \"command\"".to_string())
+        })?
+        .to_string();
+
+    if cmd != "-" {
+        config.orphans_cmd = Some(cmd);
+    }
+    Ok(())
+}
+
+pub(super) fn parse_dependencies_cmd(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
+    let cmd = node
+        .entries()
+        .first()
+        .and_then(|entry| entry.value().as_string())
+        .ok_or_else(|| {
+            DeclarchError::Other(
+                "Dependencies command required. Usage: dependencies \"command\"".to_string(),
+            )
+        })?
+        .to_string();
+
+    if cmd != "-" {
+        config.dependencies_cmd = Some(cmd);
+    }
+    Ok(())
+}
+
 pub(super) fn parse_remove_cmd(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
     let cmd = node
         .entries()
@@ -81,6 +149,122 @@ pub(super) fn parse_upgrade_cmd(node: &KdlNode, config: &mut BackendConfig) -> R
     Ok(())
 }
 
+pub(super) fn parse_preinstall_cmd(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
+    let cmd = node
+        .entries()
+        .first()
+        .and_then(|entry| entry.value().as_string())
+        .ok_or_else(|| {
+            DeclarchError::Other(
+                "Preinstall command required. Usage: preinstall \"command\"".to_string(),
+            )
+        })?
+        .to_string();
+
+    if cmd != "-" {
+        config.preinstall_cmd = Some(cmd);
+    }
+    Ok(())
+}
+
+pub(super) fn parse_postinstall_cmd(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
+    let cmd = node
+        .entries()
+        .first()
+        .and_then(|entry| entry.value().as_string())
+        .ok_or_else(|| {
+            DeclarchError::Other(
+                "Postinstall command required. Usage: postinstall \"command\"".to_string(),
+            )
+        })?
+        .to_string();
+
+    if cmd != "-" {
+        config.postinstall_cmd = Some(cmd);
+    }
+    Ok(())
+}
+
+pub(super) fn parse_min_version(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
+    let version = node
+        .entries()
+        .first()
+        .and_then(|entry| entry.value().as_string())
+        .ok_or_else(|| {
+            DeclarchError::Other(
+                "Minimum version required. Usage: min_version \"1.5.0\"".to_string(),
+            )
+        })?;
+
+    config.min_version = Some(version.to_string());
+    Ok(())
+}
+
+pub(super) fn parse_version_check_cmd(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
+    let cmd = node
+        .entries()
+        .first()
+        .and_then(|entry| entry.value().as_string())
+        .ok_or_else(|| {
+            DeclarchError::Other(
+                "Version check command required. Usage: version_check \"{binary} --version\" { regex \"...\" }"
+                    .to_string(),
+            )
+        })?;
+
+    config.version_check_cmd = Some(cmd.to_string());
+
+    if let Some(children) = node.children() {
+        for child in children.nodes() {
+            if child.name().value() == "regex" {
+                config.version_regex = child
+                    .entries()
+                    .first()
+                    .and_then(|entry| entry.value().as_string())
+                    .map(ToString::to_string);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(super) fn parse_dry_install_cmd(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
+    let cmd = node
+        .entries()
+        .first()
+        .and_then(|entry| entry.value().as_string())
+        .ok_or_else(|| {
+            DeclarchError::Other(
+                "Dry install command required. Usage: dry_install \"command {packages}\""
+                    .to_string(),
+            )
+        })?
+        .to_string();
+
+    if cmd != "-" {
+        config.dry_install_cmd = Some(cmd);
+    }
+    Ok(())
+}
+
+pub(super) fn parse_dry_remove_cmd(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
+    let cmd = node
+        .entries()
+        .first()
+        .and_then(|entry| entry.value().as_string())
+        .ok_or_else(|| {
+            DeclarchError::Other(
+                "Dry remove command required. Usage: dry_remove \"command {packages}\"".to_string(),
+            )
+        })?
+        .to_string();
+
+    if cmd != "-" {
+        config.dry_remove_cmd = Some(cmd);
+    }
+    Ok(())
+}
+
 pub(super) fn parse_noconfirm(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
     config.noconfirm_flag = node
         .entries()
@@ -98,3 +282,35 @@ pub(super) fn parse_fallback(node: &KdlNode, config: &mut BackendConfig) -> Resu
         .map(|s| s.to_string());
     Ok(())
 }
+
+/// `install_failure_regex "..."` / `install_success_regex "..."`; `"-"` clears it
+pub(super) fn parse_install_output_regex(node: &KdlNode) -> Result<Option<String>> {
+    let pattern = node
+        .entries()
+        .first()
+        .and_then(|entry| entry.value().as_string())
+        .ok_or_else(|| {
+            DeclarchError::Other(format!(
+                "Regex required. Usage: {} \"pattern\"",
+                node.name().value()
+            ))
+        })?;
+
+    Ok((pattern != "-").then(|| pattern.to_string()))
+}
+
+/// `registry "https://..."`: default for the {registry} placeholder
+pub(super) fn parse_registry(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
+    let url = node
+        .entries()
+        .first()
+        .and_then(|entry| entry.value().as_string())
+        .ok_or_else(|| {
+            DeclarchError::Other(
+                "Registry URL required. Usage: registry \"https://...\"".to_string(),
+            )
+        })?;
+    crate::utils::sanitize::validate_registry_url(url)?;
+    config.registry = Some(url.to_string());
+    Ok(())
+}
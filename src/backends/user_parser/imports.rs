@@ -1,17 +1,19 @@
 use super::{BackendConfig, parse_backend_file};
 use crate::error::{DeclarchError, Result};
 use crate::ui;
+use crate::utils::paths;
 use kdl::KdlNode;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub(super) fn collect_import_backends(node: &KdlNode) -> Result<Vec<BackendConfig>> {
     let mut backends = Vec::new();
     if let Some(path_val) = node.entries().first().and_then(|e| e.value().as_string())
         && let Ok(config_dir) = crate::utils::paths::config_dir()
     {
-        let import_path = config_dir.join(path_val);
-        if let Some(config) = load_backend_file(&import_path)? {
-            backends.push(config);
+        for import_path in import_paths(&config_dir, path_val) {
+            if let Some(config) = load_backend_file(&import_path)? {
+                backends.push(config);
+            }
         }
     }
     Ok(backends)
@@ -25,12 +27,13 @@ pub(super) fn collect_imports_block_backends(node: &KdlNode) -> Result<Vec<Backe
             && path_val.ends_with(".kdl")
             && let Ok(config_dir) = crate::utils::paths::config_dir()
         {
-            let import_path = config_dir.join(path_val);
-            match load_backend_file(&import_path) {
-                Ok(Some(config)) => backends.push(config),
-                Ok(None) => {}
-                Err(e) => {
-                    eprintln!("Warning: Failed to load backend from '{}': {}", path_val, e);
+            for import_path in import_paths(&config_dir, path_val) {
+                match load_backend_file(&import_path) {
+                    Ok(Some(config)) => backends.push(config),
+                    Ok(None) => {}
+                    Err(e) => {
+                        eprintln!("Warning: Failed to load backend from '{}': {}", path_val, e);
+                    }
                 }
             }
         }
@@ -44,33 +47,35 @@ pub(super) fn collect_imports_block_backends(node: &KdlNode) -> Result<Vec<Backe
                 if let Some(path_val) = child.entries().first().and_then(|e| e.value().as_string())
                     && let Ok(config_dir) = crate::utils::paths::config_dir()
                 {
-                    let import_path = config_dir.join(path_val);
+                    for import_path in import_paths(&config_dir, path_val) {
+                        match load_backend_file(&import_path) {
+                            Ok(Some(config)) => backends.push(config),
+                            Ok(None) => {}
+                            Err(e) => {
+                                ui::warning(&format!(
+                                    "Failed to load backend from '{}': {}",
+                                    path_val, e
+                                ));
+                            }
+                        }
+                    }
+                }
+            } else if child_name.ends_with(".kdl")
+                && child_name.contains('/')
+                && let Ok(config_dir) = crate::utils::paths::config_dir()
+            {
+                for import_path in import_paths(&config_dir, child_name) {
                     match load_backend_file(&import_path) {
                         Ok(Some(config)) => backends.push(config),
                         Ok(None) => {}
                         Err(e) => {
                             ui::warning(&format!(
                                 "Failed to load backend from '{}': {}",
-                                path_val, e
+                                child_name, e
                             ));
                         }
                     }
                 }
-            } else if child_name.ends_with(".kdl")
-                && child_name.contains('/')
-                && let Ok(config_dir) = crate::utils::paths::config_dir()
-            {
-                let import_path = config_dir.join(child_name);
-                match load_backend_file(&import_path) {
-                    Ok(Some(config)) => backends.push(config),
-                    Ok(None) => {}
-                    Err(e) => {
-                        ui::warning(&format!(
-                            "Failed to load backend from '{}': {}",
-                            child_name, e
-                        ));
-                    }
-                }
             }
         }
     }
@@ -78,6 +83,34 @@ pub(super) fn collect_imports_block_backends(node: &KdlNode) -> Result<Vec<Backe
     Ok(backends)
 }
 
+/// Files an import names, relative to the config directory. A glob
+/// (`backends/*.kdl`) expands to its matches in sorted order, never including
+/// the root config.
+fn import_paths(config_dir: &Path, import: &str) -> Vec<PathBuf> {
+    let import_path = config_dir.join(import);
+    if !paths::is_glob(import) {
+        return vec![import_path];
+    }
+
+    let root = paths::config_file()
+        .ok()
+        .and_then(|root| std::fs::canonicalize(root).ok());
+    let matches = match paths::glob_files(&import_path) {
+        Ok(matches) => matches,
+        Err(e) => {
+            ui::warning(&e.to_string());
+            return Vec::new();
+        }
+    };
+    if matches.is_empty() {
+        ui::warning(&format!("Import '{}' matched no files", import));
+    }
+    matches
+        .into_iter()
+        .filter(|path| root.is_none() || std::fs::canonicalize(path).ok() != root)
+        .collect()
+}
+
 fn load_backend_file(path: &Path) -> Result<Option<BackendConfig>> {
     if !path.exists() {
         return Ok(None);
@@ -0,0 +1,60 @@
+use super::parse_utils::get_entry_string;
+use crate::backends::config::{BackendConfig, InfoFormat};
+use crate::error::{DeclarchError, Result};
+use kdl::KdlNode;
+
+pub(super) fn parse_info_cmd(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
+    let cmd = node
+        .entries()
+        .first()
+        .and_then(get_entry_string)
+        .ok_or_else(|| {
+            DeclarchError::Other(
+                "Info command required. Usage: info \"command {package}\" { ... }".to_string(),
+            )
+        })?;
+
+    if cmd == "-" {
+        return Ok(());
+    }
+
+    config.info_cmd = Some(cmd);
+
+    if let Some(children) = node.children() {
+        for child in children.nodes() {
+            match child.name().value() {
+                "format" => {
+                    let format_str = child_string(child).ok_or_else(|| {
+                        DeclarchError::Other(
+                            "Format value required. Usage: format key_value|json".to_string(),
+                        )
+                    })?;
+
+                    config.info_format = Some(match format_str.as_str() {
+                        "key_value" => InfoFormat::KeyValue,
+                        "json" => InfoFormat::Json,
+                        _ => {
+                            return Err(DeclarchError::Other(format!(
+                                "Unknown info format '{}'. Valid: key_value, json",
+                                format_str
+                            )));
+                        }
+                    });
+                }
+                "json_path" => config.info_json_path = child_string(child),
+                "name_key" => config.info_name_key = child_string(child),
+                "version_key" => config.info_version_key = child_string(child),
+                "desc_key" => config.info_desc_key = child_string(child),
+                "homepage_key" => config.info_homepage_key = child_string(child),
+                "depends_key" => config.info_depends_key = child_string(child),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn child_string(node: &KdlNode) -> Option<String> {
+    node.entries().first().and_then(get_entry_string)
+}
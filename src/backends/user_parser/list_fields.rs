@@ -1,4 +1,4 @@
-use super::parse_utils::get_entry_string;
+use super::parse_utils::{get_entry_string, parse_bool};
 use crate::backends::config::{BackendConfig, OutputFormat};
 use crate::error::{DeclarchError, Result};
 use kdl::KdlNode;
@@ -59,6 +59,12 @@ pub(super) fn parse_list_cmd(node: &KdlNode, config: &mut BackendConfig) -> Resu
                 "version_key" => {
                     config.list_version_key = child.entries().first().and_then(get_entry_string);
                 }
+                "size_key" => {
+                    config.list_size_key = child.entries().first().and_then(get_entry_string);
+                }
+                "origin_key" => {
+                    config.list_origin_key = child.entries().first().and_then(get_entry_string);
+                }
                 "json" => {
                     if let Some(json_children) = child.children() {
                         for json_child in json_children.nodes() {
@@ -84,6 +90,17 @@ pub(super) fn parse_list_cmd(node: &KdlNode, config: &mut BackendConfig) -> Resu
                                         .and_then(|entry| entry.value().as_string())
                                         .map(|s| s.to_string());
                                 }
+                                "size_key" => {
+                                    config.list_size_key = json_child
+                                        .entries()
+                                        .first()
+                                        .and_then(|entry| entry.value().as_string())
+                                        .map(|s| s.to_string());
+                                }
+                                "origin_key" => {
+                                    config.list_origin_key =
+                                        json_child.entries().first().and_then(get_entry_string);
+                                }
                                 "desc_key" => {}
                                 _ => {}
                             }
@@ -114,6 +131,21 @@ pub(super) fn parse_list_cmd(node: &KdlNode, config: &mut BackendConfig) -> Resu
                             })
                     });
                 }
+                "size_col" => {
+                    config.list_size_col = child.entries().first().and_then(|entry| {
+                        entry
+                            .value()
+                            .as_string()
+                            .and_then(|s| s.parse::<usize>().ok())
+                            .or_else(|| {
+                                let val_str = entry.value().to_string();
+                                val_str.parse::<usize>().ok()
+                            })
+                    });
+                }
+                "origin_col" => {
+                    config.list_origin_col = child_usize(child);
+                }
                 "regex" => {
                     if let Some(regex_children) = child.children() {
                         for regex_child in regex_children.nodes() {
@@ -151,6 +183,25 @@ pub(super) fn parse_list_cmd(node: &KdlNode, config: &mut BackendConfig) -> Resu
                                                 })
                                         });
                                 }
+                                "size_group" => {
+                                    config.list_regex_size_group =
+                                        regex_child.entries().first().and_then(|entry| {
+                                            entry
+                                                .value()
+                                                .as_string()
+                                                .and_then(|s| s.parse::<usize>().ok())
+                                                .or_else(|| {
+                                                    let val_str = entry.value().to_string();
+                                                    val_str.parse::<usize>().ok()
+                                                })
+                                        });
+                                }
+                                "origin_group" => {
+                                    config.list_regex_origin_group = child_usize(regex_child);
+                                }
+                                "per_line" => {
+                                    config.list_per_line = parse_bool(regex_child)?;
+                                }
                                 _ => {}
                             }
                         }
@@ -183,6 +234,31 @@ pub(super) fn parse_list_cmd(node: &KdlNode, config: &mut BackendConfig) -> Resu
                             })
                     });
                 }
+                "per_line" => {
+                    config.list_per_line = parse_bool(child)?;
+                }
+                "origin_group" => {
+                    config.list_regex_origin_group = child_usize(child);
+                }
+                "filter_regex" => {
+                    config.list_filter_regex = child.entries().first().and_then(get_entry_string);
+                }
+                "skip_lines_matching" => {
+                    config.list_skip_lines_matching =
+                        child.entries().first().and_then(get_entry_string);
+                }
+                "size_group" => {
+                    config.list_regex_size_group = child.entries().first().and_then(|entry| {
+                        entry
+                            .value()
+                            .as_string()
+                            .and_then(|s| s.parse::<usize>().ok())
+                            .or_else(|| {
+                                let val_str = entry.value().to_string();
+                                val_str.parse::<usize>().ok()
+                            })
+                    });
+                }
                 "version_group" => {
                     config.list_regex_version_group = child.entries().first().and_then(|entry| {
                         entry
@@ -202,3 +278,10 @@ pub(super) fn parse_list_cmd(node: &KdlNode, config: &mut BackendConfig) -> Resu
 
     Ok(())
 }
+
+fn child_usize(node: &KdlNode) -> Option<usize> {
+    node.entries()
+        .first()
+        .and_then(get_entry_string)
+        .and_then(|value| value.parse::<usize>().ok())
+}
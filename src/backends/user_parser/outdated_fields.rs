@@ -0,0 +1,74 @@
+use super::parse_utils::get_entry_string;
+use crate::backends::config::{BackendConfig, OutputFormat};
+use crate::error::{DeclarchError, Result};
+use kdl::KdlNode;
+
+pub(super) fn parse_outdated_cmd(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
+    let cmd = node
+        .entries()
+        .first()
+        .and_then(get_entry_string)
+        .ok_or_else(|| {
+            DeclarchError::Other(
+                "Outdated command required. Usage: outdated \"command\" { ... }".to_string(),
+            )
+        })?;
+
+    if cmd == "-" {
+        return Ok(());
+    }
+
+    config.outdated_cmd = Some(cmd);
+
+    if let Some(children) = node.children() {
+        for child in children.nodes() {
+            match child.name().value() {
+                "format" => {
+                    let format_str = child_string(child).ok_or_else(|| {
+                        DeclarchError::Other(
+                            "Format value required. Usage: format whitespace|tsv|json|json_lines|json_object_keys|regex"
+                                .to_string(),
+                        )
+                    })?;
+
+                    config.outdated_format = Some(match format_str.as_str() {
+                        "json" => OutputFormat::Json,
+                        "json_lines" | "jsonl" | "ndjson" => OutputFormat::JsonLines,
+                        "json_object_keys" => OutputFormat::JsonObjectKeys,
+                        "whitespace" => OutputFormat::SplitWhitespace,
+                        "tsv" => OutputFormat::TabSeparated,
+                        "regex" => OutputFormat::Regex,
+                        _ => {
+                            return Err(DeclarchError::Other(format!(
+                                "Unknown outdated format '{}'. Valid: json, json_lines, json_object_keys, whitespace, tsv, regex",
+                                format_str
+                            )));
+                        }
+                    });
+                }
+                "json_path" => config.outdated_json_path = child_string(child),
+                "name_key" => config.outdated_name_key = child_string(child),
+                "current_key" => config.outdated_current_key = child_string(child),
+                "available_key" => config.outdated_available_key = child_string(child),
+                "name_col" => config.outdated_name_col = child_usize(child),
+                "current_col" => config.outdated_current_col = child_usize(child),
+                "available_col" => config.outdated_available_col = child_usize(child),
+                "regex" => config.outdated_regex = child_string(child),
+                "name_group" => config.outdated_regex_name_group = child_usize(child),
+                "current_group" => config.outdated_regex_current_group = child_usize(child),
+                "available_group" => config.outdated_regex_available_group = child_usize(child),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn child_string(node: &KdlNode) -> Option<String> {
+    node.entries().first().and_then(get_entry_string)
+}
+
+fn child_usize(node: &KdlNode) -> Option<usize> {
+    child_string(node).and_then(|value| value.parse::<usize>().ok())
+}
@@ -2,6 +2,23 @@ use crate::backends::config::BackendConfig;
 use crate::error::{DeclarchError, Result};
 use kdl::{KdlEntry, KdlNode};
 
+/// Parse `alias "paru" "yay"`: other names for this backend, lowercased.
+pub(super) fn parse_aliases(node: &KdlNode, config: &mut BackendConfig) {
+    for entry in node.entries() {
+        if entry.name().is_none()
+            && let Some(val) = entry.value().as_string()
+        {
+            let alias = val.trim().to_lowercase();
+            if !alias.is_empty()
+                && alias != config.name.to_lowercase()
+                && !config.aliases.contains(&alias)
+            {
+                config.aliases.push(alias);
+            }
+        }
+    }
+}
+
 pub(super) fn parse_supported_os(node: &KdlNode, config: &mut BackendConfig) {
     let mut values: Vec<String> = Vec::new();
 
@@ -3,6 +3,23 @@ use crate::backends::config::{BackendConfig, OutputFormat};
 use crate::error::{DeclarchError, Result};
 use kdl::KdlNode;
 
+pub(super) fn parse_search_desc_cmd(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
+    let cmd = node
+        .entries()
+        .first()
+        .and_then(|entry| entry.value().as_string())
+        .ok_or_else(|| {
+            DeclarchError::Other(
+                "Description search command required. Usage: search_desc \"command\"".to_string(),
+            )
+        })?;
+
+    if cmd != "-" {
+        config.search_desc_cmd = Some(cmd.to_string());
+    }
+    Ok(())
+}
+
 pub(super) fn parse_search_cmd(node: &KdlNode, config: &mut BackendConfig) -> Result<()> {
     let cmd = node
         .entries()
@@ -110,6 +110,119 @@ fn test_parse_with_env() {
     assert_eq!(env.get("DEBUG"), Some(&"1".to_string()));
 }
 
+#[test]
+fn test_parse_preinstall_and_postinstall_commands() {
+    let kdl = r#"
+            backend "flatpak" {
+                binary "flatpak"
+                list "flatpak list --app --columns=application,version" {
+                    format "whitespace"
+                    name_col 0
+                    version_col 1
+                }
+                install "flatpak install -y {packages}"
+                remove "flatpak uninstall -y {packages}"
+                preinstall "flatpak remote-add --if-not-exists flathub https://flathub.org/repo/flathub.flatpakrepo"
+                postinstall "-"
+            }
+        "#;
+
+    let doc = KdlDocument::parse(kdl).unwrap();
+    let node = doc.nodes().first().unwrap();
+    let config = parse_backend_node(node).unwrap();
+
+    assert_eq!(
+        config.preinstall_cmd.as_deref(),
+        Some(
+            "flatpak remote-add --if-not-exists flathub https://flathub.org/repo/flathub.flatpakrepo"
+        )
+    );
+    assert_eq!(config.postinstall_cmd, None);
+}
+
+#[test]
+fn test_parse_dry_install_and_dry_remove_commands() {
+    let kdl = r#"
+            backend "pacman" {
+                binary "pacman"
+                list "pacman -Q" {
+                    format "whitespace"
+                    name_col 0
+                    version_col 1
+                }
+                install "pacman -S {packages}"
+                remove "pacman -R {packages}"
+                dry_install "pacman -S --print {packages}"
+                dry_remove "pacman -R --print {packages}"
+            }
+        "#;
+
+    let doc = KdlDocument::parse(kdl).unwrap();
+    let node = doc.nodes().first().unwrap();
+    let config = parse_backend_node(node).unwrap();
+
+    assert_eq!(
+        config.dry_install_cmd.as_deref(),
+        Some("pacman -S --print {packages}")
+    );
+    assert_eq!(
+        config.dry_remove_cmd.as_deref(),
+        Some("pacman -R --print {packages}")
+    );
+}
+
+#[test]
+fn test_parse_install_output_regexes() {
+    let kdl = r#"
+            backend "pip" {
+                binary "pip"
+                list "pip list"
+                install "pip install {packages}"
+                install_failure_regex "^ERROR: "
+                install_success_regex "^Successfully installed"
+            }
+        "#;
+
+    let doc = KdlDocument::parse(kdl).unwrap();
+    let config = parse_backend_node(doc.nodes().first().unwrap()).unwrap();
+    assert_eq!(config.install_failure_regex.as_deref(), Some("^ERROR: "));
+    assert_eq!(
+        config.install_success_regex.as_deref(),
+        Some("^Successfully installed")
+    );
+
+    let invalid = KdlDocument::parse(
+        r#"backend "pip" { binary "pip"; install "pip install {packages}"; install_failure_regex "(" }"#,
+    )
+    .unwrap();
+    let err = parse_backend_node(invalid.nodes().first().unwrap()).unwrap_err();
+    assert!(err.to_string().contains("install_failure_regex"));
+}
+
+#[test]
+fn test_parse_list_line_filters() {
+    let kdl = r#"
+            backend "brew" {
+                binary "brew"
+                list "brew list --versions" {
+                    format "whitespace"
+                    name_col 0
+                    version_col 1
+                    filter_regex "^\\S+ "
+                    skip_lines_matching "^==>"
+                }
+                install "brew install {packages}"
+            }
+        "#;
+
+    let doc = KdlDocument::parse(kdl).unwrap();
+    let node = doc.nodes().first().unwrap();
+    let config = parse_backend_node(node).unwrap();
+
+    assert_eq!(config.list_filter_regex.as_deref(), Some("^\\S+ "));
+    assert_eq!(config.list_skip_lines_matching.as_deref(), Some("^==>"));
+}
+
 #[test]
 fn test_parse_needs_sudo() {
     let kdl = r#"
@@ -133,6 +246,22 @@ fn test_parse_needs_sudo() {
     assert!(config.needs_sudo);
 }
 
+#[test]
+fn test_parse_batch_defaults_to_true() {
+    let kdl = r#"
+            backend "cargo" {
+                binary "cargo"
+                install "cargo install {packages}"
+                batch #false
+            }
+        "#;
+
+    let doc = KdlDocument::parse(kdl).unwrap();
+    let config = parse_backend_node(doc.nodes().first().unwrap()).unwrap();
+    assert!(!config.batch);
+    assert!(BackendConfig::default().batch);
+}
+
 #[test]
 fn test_parse_supported_os() {
     let kdl = r#"
@@ -159,6 +288,31 @@ fn test_parse_supported_os() {
     );
 }
 
+#[test]
+fn test_parse_min_version_and_version_check() {
+    let kdl = r#"
+            backend "flatpak" {
+                binary "flatpak"
+                install "flatpak install {packages}"
+                min_version "1.2.0"
+                version_check "{binary} --version" {
+                    regex "Flatpak (\\S+)"
+                }
+            }
+        "#;
+
+    let doc = KdlDocument::parse(kdl).unwrap();
+    let node = doc.nodes().first().unwrap();
+    let config = parse_backend_node(node).unwrap();
+
+    assert_eq!(config.min_version.as_deref(), Some("1.2.0"));
+    assert_eq!(
+        config.version_check_cmd.as_deref(),
+        Some("{binary} --version")
+    );
+    assert_eq!(config.version_regex.as_deref(), Some("Flatpak (\\S+)"));
+}
+
 #[test]
 fn test_parse_prefer_list_for_local_search() {
     let kdl = r#"
@@ -242,3 +396,204 @@ fn test_validate_missing_remove_cmd() {
     let result = validate_backend_config(&config);
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_load_backend_definition_requires_valid_backend_node() {
+    let dir = tempfile::tempdir().expect("tempdir");
+
+    let valid = dir.path().join("mypm.kdl");
+    std::fs::write(
+        &valid,
+        r#"backend "mypm" {
+    binary "mypm"
+    install "mypm add {packages}"
+}"#,
+    )
+    .expect("write backend file");
+    let config = load_backend_definition(&valid).expect("valid backend file");
+    assert_eq!(config.name, "mypm");
+
+    let invalid = dir.path().join("broken.kdl");
+    std::fs::write(
+        &invalid,
+        r#"backend "broken" {
+    binary "broken"
+    install "broken add"
+}"#,
+    )
+    .expect("write backend file");
+    assert!(load_backend_definition(&invalid).is_err());
+
+    let empty = dir.path().join("empty.kdl");
+    std::fs::write(&empty, "// no backend here\n").expect("write backend file");
+    assert!(load_backend_definition(&empty).is_err());
+
+    assert!(load_backend_definition(&dir.path().join("missing.kdl")).is_err());
+}
+
+#[test]
+fn test_parse_backend_aliases() {
+    let kdl = r#"
+            backend "aur" {
+                binary "paru" "yay"
+                install "{binary} -S {packages}"
+                alias "Paru" "yay" "aur" "paru"
+            }
+        "#;
+
+    let doc = KdlDocument::parse(kdl).unwrap();
+    let config = parse_backend_node(doc.nodes().first().unwrap()).unwrap();
+
+    // Lowercased, deduped, and never the backend's own name
+    assert_eq!(config.aliases, vec!["paru".to_string(), "yay".to_string()]);
+}
+
+#[test]
+fn test_parse_size_fields() {
+    let kdl = r#"
+            backend "npm" {
+                binary "npm"
+                install "{binary} install -g {packages}"
+                size "{binary} view {package} dist.unpackedSize"
+                list "{binary} ls -g --json" {
+                    format "json"
+                    name_key "name"
+                    version_key "version"
+                    size_key "size"
+                }
+            }
+        "#;
+
+    let doc = KdlDocument::parse(kdl).unwrap();
+    let config = parse_backend_node(doc.nodes().first().unwrap()).unwrap();
+
+    assert_eq!(
+        config.size_cmd.as_deref(),
+        Some("{binary} view {package} dist.unpackedSize")
+    );
+    assert_eq!(config.list_size_key.as_deref(), Some("size"));
+}
+
+#[test]
+fn test_parse_outdated_fields() {
+    let kdl = r#"
+            backend "pacman" {
+                binary "pacman"
+                install "{binary} -S {packages}"
+                outdated "{binary} -Qu" {
+                    format "whitespace"
+                    name_col 0
+                    current_col 1
+                    available_col 3
+                }
+            }
+        "#;
+
+    let doc = KdlDocument::parse(kdl).unwrap();
+    let config = parse_backend_node(doc.nodes().first().unwrap()).unwrap();
+
+    assert_eq!(config.outdated_cmd.as_deref(), Some("{binary} -Qu"));
+    assert_eq!(config.outdated_format, Some(OutputFormat::SplitWhitespace));
+    assert_eq!(config.outdated_name_col, Some(0));
+    assert_eq!(config.outdated_current_col, Some(1));
+    assert_eq!(config.outdated_available_col, Some(3));
+}
+
+#[test]
+fn test_parse_info_fields() {
+    let kdl = r#"
+            backend "npm" {
+                binary "npm"
+                install "{binary} install -g {packages}"
+                info "{binary} view {package} --json" {
+                    format "json"
+                    desc_key "description"
+                    depends_key "dependencies"
+                }
+            }
+        "#;
+
+    let doc = KdlDocument::parse(kdl).unwrap();
+    let config = parse_backend_node(doc.nodes().first().unwrap()).unwrap();
+
+    assert_eq!(
+        config.info_cmd.as_deref(),
+        Some("{binary} view {package} --json")
+    );
+    assert_eq!(
+        config.info_format,
+        Some(crate::backends::config::InfoFormat::Json)
+    );
+    assert_eq!(config.info_desc_key.as_deref(), Some("description"));
+    assert_eq!(config.info_depends_key.as_deref(), Some("dependencies"));
+}
+
+#[test]
+fn test_info_cmd_requires_package_placeholder() {
+    let kdl = r#"
+            backend "pacman" {
+                binary "pacman"
+                install "{binary} -S {packages}"
+                info "{binary} -Si"
+            }
+        "#;
+
+    let doc = KdlDocument::parse(kdl).unwrap();
+    assert!(parse_backend_node(doc.nodes().first().unwrap()).is_err());
+}
+
+#[test]
+fn test_outdated_regex_format_requires_regex() {
+    let kdl = r#"
+            backend "brew" {
+                binary "brew"
+                install "{binary} install {packages}"
+                outdated "{binary} outdated --verbose" {
+                    format "regex"
+                }
+            }
+        "#;
+
+    let doc = KdlDocument::parse(kdl).unwrap();
+    assert!(parse_backend_node(doc.nodes().first().unwrap()).is_err());
+}
+
+#[test]
+fn test_parse_orphans_cmd() {
+    let kdl = r#"
+            backend "pacman" {
+                binary "pacman"
+                install "{binary} -S {packages}"
+                orphans "{binary} -Qtdq"
+                dependencies "{binary} -Qi"
+            }
+        "#;
+
+    let doc = KdlDocument::parse(kdl).unwrap();
+    let config = parse_backend_node(doc.nodes().first().unwrap()).unwrap();
+
+    assert_eq!(config.orphans_cmd.as_deref(), Some("{binary} -Qtdq"));
+    assert_eq!(config.dependencies_cmd.as_deref(), Some("{binary} -Qi"));
+}
+
+#[test]
+fn test_parse_registry_validates_url() {
+    let kdl = r#"
+            backend "soar" {
+                binary "soar"
+                install "{binary} install --registry {registry} {packages}"
+                registry "https://registry.example.org/soar"
+            }
+        "#;
+
+    let doc = KdlDocument::parse(kdl).unwrap();
+    let config = parse_backend_node(doc.nodes().first().unwrap()).unwrap();
+    assert_eq!(
+        config.registry.as_deref(),
+        Some("https://registry.example.org/soar")
+    );
+
+    let bad =
+        KdlDocument::parse(&kdl.replace("https://registry.example.org/soar", "not a url")).unwrap();
+    assert!(parse_backend_node(bad.nodes().first().unwrap()).is_err());
+}
@@ -47,6 +47,36 @@ pub(super) fn validate_backend_config(config: &BackendConfig) -> Result<()> {
         )));
     }
 
+    // reinstall_cmd is optional and takes packages like install_cmd
+    if let Some(ref reinstall_cmd) = config.reinstall_cmd
+        && !reinstall_cmd.contains("{packages}")
+    {
+        return Err(DeclarchError::ConfigError(format!(
+            "Backend '{}' reinstall_cmd must contain '{{packages}}' placeholder",
+            config.name
+        )));
+    }
+
+    // size_cmd is optional and runs once per package
+    if let Some(ref size_cmd) = config.size_cmd
+        && !size_cmd.contains("{package}")
+    {
+        return Err(DeclarchError::ConfigError(format!(
+            "Backend '{}' size_cmd must contain '{{package}}' placeholder",
+            config.name
+        )));
+    }
+
+    // info_cmd is optional and runs once per package
+    if let Some(ref info_cmd) = config.info_cmd
+        && !info_cmd.contains("{package}")
+    {
+        return Err(DeclarchError::ConfigError(format!(
+            "Backend '{}' info_cmd must contain '{{package}}' placeholder",
+            config.name
+        )));
+    }
+
     // remove_cmd is optional
     if let Some(ref remove_cmd) = config.remove_cmd {
         // remove_cmd should contain {packages} placeholder
@@ -75,6 +105,56 @@ pub(super) fn validate_backend_config(config: &BackendConfig) -> Result<()> {
         }
     }
 
+    // search_desc_cmd follows the same placeholder rules as search_cmd
+    if let Some(ref search_desc_cmd) = config.search_desc_cmd
+        && !search_desc_cmd.contains("{query}")
+    {
+        return Err(DeclarchError::ConfigError(format!(
+            "Backend '{}' search_desc_cmd must contain '{{query}}' placeholder",
+            config.name
+        )));
+    }
+
+    // version_regex must compile so the version check can't silently pass
+    if let Some(ref version_regex) = config.version_regex
+        && let Err(e) = regex::Regex::new(version_regex)
+    {
+        return Err(DeclarchError::ConfigError(format!(
+            "Backend '{}' has invalid version_check regex: {}",
+            config.name, e
+        )));
+    }
+
+    // List line filters must compile, otherwise noise lines would silently stay
+    for (field, pattern) in [
+        ("filter_regex", &config.list_filter_regex),
+        ("skip_lines_matching", &config.list_skip_lines_matching),
+    ] {
+        if let Some(pattern) = pattern
+            && let Err(e) = regex::Regex::new(pattern)
+        {
+            return Err(DeclarchError::ConfigError(format!(
+                "Backend '{}' has invalid list {} regex: {}",
+                config.name, field, e
+            )));
+        }
+    }
+
+    // Install output checks must compile, otherwise soft failures would go unnoticed
+    for (field, pattern) in [
+        ("install_failure_regex", &config.install_failure_regex),
+        ("install_success_regex", &config.install_success_regex),
+    ] {
+        if let Some(pattern) = pattern
+            && let Err(e) = regex::Regex::new(pattern)
+        {
+            return Err(DeclarchError::ConfigError(format!(
+                "Backend '{}' has invalid {}: {}",
+                config.name, field, e
+            )));
+        }
+    }
+
     // update_cmd should contain {binary} if backend has multiple binaries
     if let Some(ref update_cmd) = config.update_cmd
         && needs_binary_placeholder
@@ -108,6 +188,23 @@ pub(super) fn validate_backend_config(config: &BackendConfig) -> Result<()> {
         ));
     }
 
+    // outdated_cmd should contain {binary} if backend has multiple binaries
+    if let Some(ref outdated_cmd) = config.outdated_cmd
+        && needs_binary_placeholder
+        && !outdated_cmd.contains("{binary}")
+    {
+        ui::warning(&format!(
+            "Backend '{}' has multiple binaries but outdated_cmd missing '{{binary}}' placeholder",
+            config.name
+        ));
+    }
+
+    if config.outdated_format == Some(OutputFormat::Regex) && config.outdated_regex.is_none() {
+        return Err(DeclarchError::Other(
+            "Regex format requires 'regex' to be specified in outdated block".to_string(),
+        ));
+    }
+
     // Validate format-specific requirements
     match config.list_format {
         OutputFormat::Json | OutputFormat::JsonLines | OutputFormat::NpmJson => {
@@ -166,7 +166,7 @@ fn tools_list_response(id: Option<Value>) -> Value {
         json!({
             "name": sync_dry_tool,
             "description": format!("Run `{}` in machine-output mode (v1).", project_identity::cli_with("--dry-run sync")),
-            "inputSchema": {"type":"object","properties":{"target":{"type":"string"},"profile":{"type":"string"},"host":{"type":"string"},"modules":{"type":"array","items":{"type":"string"}}}}
+            "inputSchema": {"type":"object","properties":{"target":{"type":"string"},"profile":{"oneOf":[{"type":"string"},{"type":"array","items":{"type":"string"}}]},"host":{"type":"string"},"modules":{"type":"array","items":{"type":"string"}}}}
         }),
     ];
 
@@ -174,7 +174,7 @@ fn tools_list_response(id: Option<Value>) -> Value {
         tools.push(json!({
             "name": sync_apply_tool,
             "description": format!("Run `{}` (apply). Requires mcp config allow + {}=1 + confirm=\"APPLY_SYNC\".", project_identity::cli_with("sync"), project_identity::env_key("MCP_ALLOW_APPLY")),
-            "inputSchema": {"type":"object","required":["confirm"],"properties":{"confirm":{"type":"string"},"target":{"type":"string"},"profile":{"type":"string"},"host":{"type":"string"},"modules":{"type":"array","items":{"type":"string"}}}}
+            "inputSchema": {"type":"object","required":["confirm"],"properties":{"confirm":{"type":"string"},"target":{"type":"string"},"profile":{"oneOf":[{"type":"string"},{"type":"array","items":{"type":"string"}}]},"host":{"type":"string"},"modules":{"type":"array","items":{"type":"string"}}}}
         }));
     }
 
@@ -331,10 +331,7 @@ fn build_declarch_args(
                 args.push("--target".into());
                 args.push(target.to_string());
             }
-            if let Some(profile) = arguments.get("profile").and_then(Value::as_str) {
-                args.push("--profile".into());
-                args.push(profile.to_string());
-            }
+            push_profile_args(arguments, &mut args);
             if let Some(host) = arguments.get("host").and_then(Value::as_str) {
                 args.push("--host".into());
                 args.push(host.to_string());
@@ -361,10 +358,7 @@ fn build_declarch_args(
                 args.push("--target".into());
                 args.push(target.to_string());
             }
-            if let Some(profile) = arguments.get("profile").and_then(Value::as_str) {
-                args.push("--profile".into());
-                args.push(profile.to_string());
-            }
+            push_profile_args(arguments, &mut args);
             if let Some(host) = arguments.get("host").and_then(Value::as_str) {
                 args.push("--host".into());
                 args.push(host.to_string());
@@ -392,6 +386,19 @@ fn build_declarch_args(
     Ok(args)
 }
 
+/// `--profile` once per profile; `profile` may be one name or a list.
+fn push_profile_args(arguments: &serde_json::Map<String, Value>, args: &mut Vec<String>) {
+    let profiles: Vec<&str> = match arguments.get("profile") {
+        Some(Value::String(profile)) => vec![profile.as_str()],
+        Some(Value::Array(arr)) => arr.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    for profile in profiles {
+        args.push("--profile".into());
+        args.push(profile.to_string());
+    }
+}
+
 fn enforce_apply_safety(arguments: &serde_json::Map<String, Value>) -> Result<(), String> {
     let sync_apply_tool = stable_tool_name("sync_apply");
     let allow_apply_key = project_identity::env_key("MCP_ALLOW_APPLY");
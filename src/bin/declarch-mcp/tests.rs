@@ -30,3 +30,22 @@ fn normalize_tool_name_with_ids_keeps_unknown_names() {
     );
     assert_eq!(out, "other_tool");
 }
+
+#[test]
+fn sync_dry_run_accepts_one_or_several_profiles() {
+    let tool = stable_tool_name("sync_dry_run");
+    let profile_args = |profile: Value| -> Vec<String> {
+        let arguments = json!({ "profile": profile });
+        let args = build_declarch_args(&tool, arguments.as_object().unwrap()).unwrap();
+        args.windows(2)
+            .filter(|pair| pair[0] == "--profile")
+            .map(|pair| pair[1].clone())
+            .collect()
+    };
+
+    assert_eq!(profile_args(json!("gaming")), vec!["gaming"]);
+    assert_eq!(
+        profile_args(json!(["gaming", "dev"])),
+        vec!["gaming", "dev"]
+    );
+}
@@ -1,6 +1,7 @@
 use crate::project_identity;
 use clap::{Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -30,14 +31,22 @@ pub struct Cli {
 
 #[derive(Parser, Debug)]
 pub struct GlobalFlags {
-    /// Verbose output
-    #[arg(short = 'v', long, global = true)]
-    pub verbose: bool,
+    /// Verbose output (repeat for more detail: -vv adds timing)
+    #[arg(short = 'v', long, action = clap::ArgAction::Count, global = true)]
+    pub verbose: u8,
+
+    /// Output level (overrides -v/-q)
+    #[arg(long, value_enum, value_name = "LEVEL", global = true)]
+    pub log_level: Option<LogLevelArg>,
 
     /// Quiet mode
     #[arg(short = 'q', long, global = true)]
     pub quiet: bool,
 
+    /// When to use colors (overrides NO_COLOR, CLICOLOR_FORCE and the color setting)
+    #[arg(long, value_enum, value_name = "WHEN", global = true)]
+    pub color: Option<ColorArg>,
+
     /// Skip confirmation prompts
     #[arg(short = 'y', long = "yes", global = true)]
     pub yes: bool,
@@ -50,15 +59,87 @@ pub struct GlobalFlags {
     #[arg(long, global = true)]
     pub dry_run: bool,
 
-    /// Output format (table, json, yaml)
+    /// Output format (table, json, yaml, toml; jsonl streams search results)
     #[arg(long, value_name = "FORMAT", global = true)]
     pub format: Option<String>,
 
     /// Machine output contract version (placeholder, e.g. v1)
     #[arg(long, value_name = "VERSION", global = true)]
     pub output_version: Option<String>,
+
+    /// Write machine-readable output to this file instead of stdout (implies --format json)
+    #[arg(long, value_name = "PATH", global = true)]
+    pub output: Option<PathBuf>,
+
+    /// Never prefix backend commands with sudo (implied when running as root)
+    #[arg(long, global = true)]
+    pub no_sudo: bool,
+
+    /// Capture backend install/remove/upgrade output; show it only on failure
+    #[arg(long, global = true)]
+    pub quiet_backends: bool,
+
+    /// Remove the state lock first if the process that took it is gone
+    #[arg(long, global = true)]
+    pub force_unlock: bool,
+
+    /// Use an alternate state file (also DECLARCH_STATE_FILE)
+    #[arg(long, value_name = "PATH", global = true)]
+    pub state_file: Option<PathBuf>,
+
+    /// Use an alternate root config file; modules resolve next to it ("-" reads stdin)
+    #[arg(long, value_name = "PATH", global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Reparse every config file instead of using the parse cache
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+
+    /// Refuse anything that needs the network (also DECLARCH_OFFLINE=1)
+    #[arg(long, global = true)]
+    pub frozen: bool,
 }
 
+impl GlobalFlags {
+    /// Output level after applying `--log-level`, `-q`, and `-v` precedence.
+    pub fn effective_log_level(&self) -> crate::ui::LogLevel {
+        use crate::ui::LogLevel;
+        let explicit = self.log_level.map(|level| match level {
+            LogLevelArg::Error => LogLevel::Error,
+            LogLevelArg::Warn => LogLevel::Warn,
+            LogLevelArg::Info => LogLevel::Info,
+            LogLevelArg::Debug => LogLevel::Debug,
+            LogLevelArg::Trace => LogLevel::Trace,
+        });
+        LogLevel::from_flags(self.quiet, self.verbose, explicit)
+    }
+
+    /// Whether commands should print verbose detail: `-v` or `--log-level debug`
+    /// and above, unless `-q` or a lower `--log-level` wins.
+    pub fn is_verbose(&self) -> bool {
+        self.effective_log_level() >= crate::ui::LogLevel::Debug
+    }
+
+    /// `--color`, when given.
+    pub fn color_mode(&self) -> Option<crate::ui::ColorMode> {
+        use crate::ui::ColorMode;
+        self.color.map(|color| match color {
+            ColorArg::Auto => ColorMode::Auto,
+            ColorArg::Always => ColorMode::Always,
+            ColorArg::Never => ColorMode::Never,
+        })
+    }
+
+    /// `--output` without `--format` writes JSON.
+    pub fn apply_output_defaults(&mut self) {
+        if self.output.is_some() && self.format.is_none() {
+            self.format = Some("json".to_string());
+        }
+    }
+}
+
+// Parsed once per process; boxing the sync flags would only add noise.
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand, Debug)]
 pub enum Command {
     /// Initialize configuration
@@ -94,12 +175,14 @@ pub enum Command {
         /// Create new backend configuration file(s)
         ///
         /// Creates backend definition files in your config backends directory.
-        /// Supports multiple backends via multiple flags
+        /// Supports multiple backends via multiple flags. A path to a local
+        /// .kdl file adopts that backend definition without network access.
         ///
         /// Examples:
         ///   <bin> init --backend cargo
         ///   <bin> init --backend apt --backend cargo
         ///   <bin> init --backend apt --backend cargo -y
+        ///   <bin> init --backend ./my-backend.kdl
         #[arg(
             long,
             value_name = "NAME",
@@ -139,9 +222,9 @@ Other useful commands:
   <bin> sync update
   <bin> sync prune")]
     Sync {
-        /// Sync only specific package or scope (e.g. "firefox", "backend-name")
+        /// Sync only specific packages or scopes (repeatable, e.g. "firefox", "backend-name")
         #[arg(long, value_name = "TARGET", help_heading = "Targeting")]
-        target: Option<String>,
+        target: Vec<String>,
 
         /// Show diff before syncing (like git diff)
         #[arg(long, help_heading = "Advanced")]
@@ -155,9 +238,30 @@ Other useful commands:
         #[arg(long, help_heading = "Advanced")]
         hooks: bool,
 
-        /// Activate optional profile block from config (e.g. profile "desktop" { ... })
+        /// Disable hooks for this run, even when policy run-hooks enables them
+        #[arg(long, conflicts_with = "hooks", help_heading = "Advanced")]
+        no_hooks: bool,
+
+        /// Run only the hooks of one phase (e.g. post-sync), without package work
+        #[arg(
+            long,
+            value_name = "PHASE",
+            conflicts_with = "no_hooks",
+            help_heading = "Advanced"
+        )]
+        only_hooks: Option<String>,
+
+        /// Print the backend commands the sync would run (with sudo and noconfirm), without running them
+        #[arg(long, help_heading = "Advanced")]
+        dump_commands: bool,
+
+        /// Print how long each sync phase took (config, snapshot, plan, execute per backend)
+        #[arg(long, help_heading = "Advanced")]
+        stats: bool,
+
+        /// Activate optional profile block from config (e.g. profile "desktop" { ... }); repeat to combine profiles
         #[arg(long, value_name = "NAME", help_heading = "Targeting")]
-        profile: Option<String>,
+        profile: Vec<String>,
 
         /// Activate optional host block from config (e.g. host "vps-1" { ... })
         #[arg(long, value_name = "NAME", help_heading = "Targeting")]
@@ -167,6 +271,66 @@ Other useful commands:
         #[arg(long, value_name = "MODULES", help_heading = "Advanced")]
         modules: Vec<String>,
 
+        /// Treat a package as already installed for this run (repeatable)
+        #[arg(long, value_name = "PACKAGE", help_heading = "Targeting")]
+        assume_installed: Vec<String>,
+
+        /// Prune undeclared packages only for this backend (repeatable)
+        #[arg(long, value_name = "BACKEND", help_heading = "Targeting")]
+        prune_backend: Vec<String>,
+
+        /// Remove orphaned dependencies reported by backends with an orphans command, whatever policy.orphan-deps says
+        #[arg(long, help_heading = "Targeting")]
+        prune_orphans: bool,
+
+        /// Load an extra backend definition for this run (repeatable)
+        #[arg(long, value_name = "PATH", help_heading = "Advanced")]
+        backend_file: Vec<PathBuf>,
+
+        /// Force reinstall of a declared package even if present (repeatable)
+        #[arg(long, value_name = "PACKAGE", help_heading = "Targeting")]
+        reinstall: Vec<String>,
+
+        /// Sync only packages tagged with this tag (repeatable; any tag matches)
+        #[arg(long, value_name = "TAG", help_heading = "Targeting")]
+        tag: Vec<String>,
+
+        /// Sync only packages declared in this module (stem or path, e.g. "base"); never prunes
+        #[arg(long, value_name = "MODULE", help_heading = "Targeting")]
+        target_module: Option<String>,
+
+        /// With --dry-run: show why each package is planned
+        #[arg(long, help_heading = "Advanced")]
+        explain: bool,
+
+        /// Choose which declaration to keep for each cross-backend conflict
+        #[arg(long, help_heading = "Advanced")]
+        interactive_conflicts: bool,
+
+        /// With --interactive-conflicts: remove the declarations not kept from config
+        #[arg(long, requires = "interactive_conflicts", help_heading = "Advanced")]
+        remember: bool,
+
+        /// Keep going after a backend fails; report all failures at the end
+        #[arg(long, help_heading = "Advanced")]
+        continue_on_error: bool,
+
+        /// Prompt as usual, ignoring policy noconfirm/auto-confirm for this run
+        #[arg(long, conflicts_with = "noconfirm", help_heading = "Advanced")]
+        confirm: bool,
+
+        /// Print the merged config sync would use, then exit without syncing
+        #[arg(long, help_heading = "Advanced")]
+        print_config: bool,
+
+        /// Upgrade installed packages of each backend (or each --target backend) after syncing
+        #[arg(long, help_heading = "Advanced")]
+        upgrade: bool,
+
+        /// Exit with code 7 if sync would change anything, 0 if in sync (implies --dry-run)
+        #[arg(long, help_heading = "Advanced")]
+        check_only: bool,
+
         #[command(subcommand)]
         command: Option<SyncCommand>,
     },
@@ -185,6 +349,10 @@ Other useful commands:
         #[arg(long)]
         plan: bool,
 
+        /// Summarize declared and tracked package counts per backend
+        #[arg(long)]
+        summary: bool,
+
         /// List managed packages (same as old `info list`)
         #[arg(long)]
         list: bool,
@@ -193,6 +361,10 @@ Other useful commands:
         #[arg(long, value_enum, requires = "list")]
         scope: Option<InfoListScope>,
 
+        /// With --list: only packages installed/adopted since a duration (7d, 12h, 2w) or date (2024-01-01)
+        #[arg(long, value_name = "WHEN", requires = "list")]
+        since: Option<String>,
+
         /// Filter by backend name
         #[arg(long, value_name = "BACKEND")]
         backend: Option<String>,
@@ -201,9 +373,9 @@ Other useful commands:
         #[arg(long, value_name = "PACKAGE")]
         package: Option<String>,
 
-        /// Activate optional profile block (profile "NAME" { ... })
+        /// Activate optional profile block (profile "NAME" { ... }); repeat to combine profiles
         #[arg(long, value_name = "NAME", help_heading = "Targeting")]
-        profile: Option<String>,
+        profile: Vec<String>,
 
         /// Activate optional host block (host "NAME" { ... })
         #[arg(long, value_name = "NAME", help_heading = "Targeting")]
@@ -214,6 +386,26 @@ Other useful commands:
         modules: Vec<String>,
     },
 
+    /// Adopt an already-installed package into config and state
+    ///
+    /// Adds the package to a module and tracks it as adopted without
+    /// installing anything. Fails if the package is not installed.
+    ///
+    /// Examples:
+    ///   <bin> adopt soar:bat
+    ///   <bin> adopt aur:firefox --module desktop
+    Adopt {
+        /// Package to adopt (format: backend:package)
+        #[arg(value_name = "BACKEND:PACKAGE")]
+        package: String,
+
+        /// Target module file (e.g., "base" or "linux/notes")
+        ///
+        /// If not specified, the package is added to modules/others.kdl
+        #[arg(short = 'm', long, value_name = "MODULE")]
+        module: Option<String>,
+    },
+
     /// Switch package variant (e.g., hyprland -> hyprland-git)
     Switch {
         /// Old package name to remove
@@ -311,6 +503,8 @@ Other useful commands:
     ///   <bin> search bat --installed-only   Show only installed matches
     ///   <bin> search backend:package        Search in specific backend (alternative syntax)
     ///   <bin> search firefox --local        Search only in installed packages
+    ///   <bin> search bat --exact            Show only packages named exactly "bat"
+    ///   <bin> search bat --max-parallel 4   Query at most 4 backends at once
     Search {
         /// Search query (can use "backend:query" syntax for specific backend)
         #[arg(value_name = "QUERY")]
@@ -330,6 +524,10 @@ Other useful commands:
         #[arg(long, value_name = "NUM", help_heading = "Filtering")]
         limit: Option<String>,
 
+        /// Show every result (same as --limit 0)
+        #[arg(long, conflicts_with = "limit", help_heading = "Filtering")]
+        all: bool,
+
         /// Show only installed packages
         ///
         /// Uses managed state tracking (managed/adopted entries),
@@ -341,12 +539,49 @@ Other useful commands:
         #[arg(long, help_heading = "Filtering")]
         available_only: bool,
 
+        /// Show only results whose name exactly matches the query
+        #[arg(long, help_heading = "Filtering")]
+        exact: bool,
+
+        /// Match the query against package descriptions too, showing why each result matched
+        #[arg(long, conflicts_with = "local", help_heading = "Filtering")]
+        in_description: bool,
+
+        /// Show installed and available versions side by side for installed results
+        #[arg(long, conflicts_with = "local")]
+        installed_version: bool,
+
         /// Search only in locally installed packages (OS/backend installed set)
         ///
         /// Uses backend local-search command when available, otherwise falls back
         /// to installed-list filtering for compatible backends.
         #[arg(long, help_heading = "Filtering")]
         local: bool,
+
+        /// Load an extra backend definition for this search (repeatable)
+        #[arg(long, value_name = "PATH")]
+        backend_file: Vec<PathBuf>,
+
+        /// Search at most N backends at once (default: all at once)
+        #[arg(
+            long,
+            value_name = "N",
+            value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+            help_heading = "Advanced"
+        )]
+        max_parallel: Option<usize>,
+
+        /// Seconds to wait for backend results (default: 30, or 8 with --local)
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            value_parser = clap::value_parser!(u64).range(1..),
+            help_heading = "Advanced"
+        )]
+        timeout: Option<u64>,
+        /// Columns to show, comma-separated: name, version, description, backend, installed
+        #[arg(long, value_name = "FIELDS", value_delimiter = ',')]
+        fields: Vec<String>,
     },
 
     /// Lint configuration quality with beginner-friendly checks
@@ -355,7 +590,9 @@ Other useful commands:
         #[arg(long)]
         strict: bool,
 
-        /// Apply safe automatic fixes (currently import sorting/format cleanup)
+        /// Apply safe automatic fixes (import sorting, duplicate declarations)
+        ///
+        /// Shows a diff and asks for confirmation unless `--yes`; edited files are backed up.
         #[arg(long)]
         fix: bool,
 
@@ -396,9 +633,9 @@ Other useful commands:
         #[arg(long, requires = "state_rm_backend", help_heading = "Advanced")]
         state_rm_all: bool,
 
-        /// Activate optional profile block (profile \"NAME\" { ... })
+        /// Activate optional profile block (profile \"NAME\" { ... }); repeat to combine profiles
         #[arg(long, value_name = "NAME", help_heading = "Targeting")]
-        profile: Option<String>,
+        profile: Vec<String>,
 
         /// Activate optional host block (host \"NAME\" { ... })
         #[arg(long, value_name = "NAME", help_heading = "Targeting")]
@@ -409,6 +646,123 @@ Other useful commands:
         modules: Vec<String>,
     },
 
+    /// Reclaim disk space without syncing
+    ///
+    /// Cleans caches for backends that support it and optionally removes
+    /// old state history snapshots. Use global `--dry-run` to list what
+    /// would be cleaned.
+    ///
+    /// Examples:
+    ///   <bin> gc                      Clean all backend caches
+    ///   <bin> gc --backend npm        Clean only npm cache
+    ///   <bin> gc --keep-days 30       Also drop history snapshots older than 30 days
+    Gc {
+        /// Target specific backend(s)
+        #[arg(short, long, value_name = "BACKEND")]
+        backend: Vec<String>,
+
+        /// Remove state history snapshots older than this many days
+        #[arg(long, value_name = "DAYS")]
+        keep_days: Option<u64>,
+    },
+
+    /// Show recent syncs recorded in the state history
+    ///
+    /// Each sync that changed packages is listed with its time, the number
+    /// of packages installed (+) and pruned (-), and the target it used.
+    /// Entries are numbered from 1, the most recent.
+    ///
+    /// Examples:
+    ///   <bin> history                  Last 20 syncs
+    ///   <bin> history --limit 0        Every recorded sync
+    ///   <bin> history --format json    Machine-readable list
+    History {
+        /// Show at most this many entries (0 = all)
+        #[arg(long, value_name = "N", default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// List installed packages with a newer version available
+    ///
+    /// Asks each backend with an `outdated` command what can be upgraded.
+    /// Backends without one are skipped. Nothing is upgraded.
+    ///
+    /// Examples:
+    ///   <bin> outdated                     Check every backend
+    ///   <bin> outdated --backend npm       Check only npm
+    ///   <bin> outdated --format json       Machine-readable list
+    Outdated {
+        /// Target specific backend(s)
+        #[arg(short, long, value_name = "BACKEND")]
+        backend: Vec<String>,
+    },
+
+    /// Preview what declaring a package would do, without changing anything
+    ///
+    /// Plans the package against current state as if it were declared and
+    /// shows the resulting install/adopt plan. Never prompts, installs, or
+    /// writes config or state.
+    ///
+    /// Examples:
+    ///   <bin> plan aur:hyprland       What would adding hyprland entail?
+    ///   <bin> plan npm:typescript
+    Plan {
+        /// Package to evaluate (backend:name)
+        #[arg(value_name = "BACKEND:PACKAGE")]
+        package: String,
+    },
+
+    /// Show details about one package from its backend
+    ///
+    /// Runs the backend's `info` command and prints the version, description,
+    /// homepage and dependencies. Backends without one report that info is
+    /// unsupported. The package need not be declared or installed.
+    ///
+    /// Examples:
+    ///   <bin> show aur:bat                 Details from the AUR
+    ///   <bin> show npm:typescript --format json
+    Show {
+        /// Package to show (backend:name)
+        #[arg(value_name = "BACKEND:PACKAGE")]
+        package: String,
+    },
+
+    /// Diagnose common misconfigurations
+    ///
+    /// Checks that config parses, referenced backends are defined and their
+    /// binaries available, state is readable, no stale lock is left and
+    /// experimental flags are recognized. Fails if any check fails.
+    ///
+    /// Examples:
+    ///   <bin> doctor
+    ///   <bin> doctor -v                Also show hints for passing checks
+    Doctor,
+
+    /// Remove a state lock left behind by a crashed run
+    ///
+    /// Only removes the lock when its recorded process is no longer alive.
+    Unlock,
+
+    /// Inspect and maintain the state file
+    ///
+    /// Examples:
+    ///   <bin> state repair --dry-run
+    ///   <bin> state repair --adopt-untracked
+    State {
+        #[command(subcommand)]
+        command: StateCommand,
+    },
+
+    /// Manage config modules
+    ///
+    /// Examples:
+    ///   <bin> module rename base core
+    ///   <bin> --dry-run module rename desktop linux/desktop
+    Module {
+        #[command(subcommand)]
+        command: ModuleCommand,
+    },
+
     /// Self-update command (hidden; primarily for curl/manual installs)
     #[command(hide = true)]
     SelfUpdate {
@@ -421,12 +775,19 @@ Other useful commands:
         version: Option<String>,
     },
 
-    /// Generate shell completions (hidden from main help)
-    #[command(hide = true)]
+    /// Generate shell completion scripts
+    #[command(after_help = "Examples:
+  <bin> completions bash > ~/.local/share/bash-completion/completions/<bin>
+  <bin> completions zsh > \"${fpath[1]}/_<bin>\"
+  <bin> completions fish > ~/.config/fish/completions/<bin>.fish")]
     Completions {
         /// The shell to generate completions for
-        #[arg(value_enum)]
-        shell: Shell,
+        #[arg(value_enum, required_unless_present = "names")]
+        shell: Option<Shell>,
+
+        /// Print backend and module names from config, one per line (used by the scripts)
+        #[arg(long, hide = true)]
+        names: bool,
     },
 
     /// Extension protocol placeholder (hidden)
@@ -442,6 +803,22 @@ pub enum LintMode {
     Conflicts,
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ColorArg {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum LogLevelArg {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum InfoListScope {
     All,
@@ -459,9 +836,9 @@ pub enum SyncCommand {
     ///
     /// Runs system package manager update before syncing packages.
     Update {
-        /// Sync only specific package or scope (e.g. "firefox", "backend-name")
+        /// Sync only specific packages or scopes (repeatable, e.g. "firefox", "backend-name")
         #[arg(long, value_name = "TARGET", help_heading = "Targeting")]
-        target: Option<String>,
+        target: Vec<String>,
 
         /// Show diff before syncing (like git diff)
         #[arg(long, help_heading = "Advanced")]
@@ -475,9 +852,9 @@ pub enum SyncCommand {
         #[arg(long, help_heading = "Advanced")]
         hooks: bool,
 
-        /// Activate optional profile block from config (e.g. profile "desktop" { ... })
+        /// Activate optional profile block from config (e.g. profile "desktop" { ... }); repeat to combine profiles
         #[arg(long, value_name = "NAME", help_heading = "Targeting")]
-        profile: Option<String>,
+        profile: Vec<String>,
 
         /// Activate optional host block from config (e.g. host "vps-1" { ... })
         #[arg(long, value_name = "NAME", help_heading = "Targeting")]
@@ -486,15 +863,19 @@ pub enum SyncCommand {
         /// Load additional modules temporarily
         #[arg(long, value_name = "MODULES", help_heading = "Advanced")]
         modules: Vec<String>,
+
+        /// Treat a package as already installed for this run (repeatable)
+        #[arg(long, value_name = "PACKAGE", help_heading = "Targeting")]
+        assume_installed: Vec<String>,
     },
 
     /// Sync with package removal
     ///
     /// Removes packages that are not defined in your configuration (Strict Mode).
     Prune {
-        /// Sync only specific package or scope (e.g. "firefox", "backend-name")
+        /// Sync only specific packages or scopes (repeatable, e.g. "firefox", "backend-name")
         #[arg(long, value_name = "TARGET", help_heading = "Targeting")]
-        target: Option<String>,
+        target: Vec<String>,
 
         /// Show diff before syncing (like git diff)
         #[arg(long, help_heading = "Advanced")]
@@ -508,9 +889,9 @@ pub enum SyncCommand {
         #[arg(long, help_heading = "Advanced")]
         hooks: bool,
 
-        /// Activate optional profile block from config (e.g. profile "desktop" { ... })
+        /// Activate optional profile block from config (e.g. profile "desktop" { ... }); repeat to combine profiles
         #[arg(long, value_name = "NAME", help_heading = "Targeting")]
-        profile: Option<String>,
+        profile: Vec<String>,
 
         /// Activate optional host block from config (e.g. host "vps-1" { ... })
         #[arg(long, value_name = "NAME", help_heading = "Targeting")]
@@ -519,6 +900,10 @@ pub enum SyncCommand {
         /// Load additional modules temporarily
         #[arg(long, value_name = "MODULES", help_heading = "Advanced")]
         modules: Vec<String>,
+
+        /// Treat a package as already installed for this run (repeatable)
+        #[arg(long, value_name = "PACKAGE", help_heading = "Targeting")]
+        assume_installed: Vec<String>,
     },
 
     /// Clean package manager caches
@@ -555,3 +940,44 @@ pub enum SyncCommand {
         no_sync: bool,
     },
 }
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum StateCommand {
+    /// Reconcile state with what is actually installed
+    ///
+    /// Drops entries for packages that are no longer installed and refreshes
+    /// versions of the rest. Never installs or removes packages; backends that
+    /// are unavailable on this machine are left untouched. Use global
+    /// `--dry-run` to preview.
+    ///
+    /// Examples:
+    ///   <bin> state repair
+    ///   <bin> state repair --adopt-untracked
+    Repair {
+        /// Also track installed packages that are declared but missing from state
+        #[arg(long)]
+        adopt_untracked: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ModuleCommand {
+    /// Rename a module file and rewrite every import of it
+    ///
+    /// Moves modules/<OLD>.kdl to modules/<NEW>.kdl, updates `imports`
+    /// references in the root config and other modules (leaving the rest of
+    /// each file as written), and retags state entries that came from the
+    /// old module. Refuses when the new module already exists. Use global
+    /// `--dry-run` to list every file that would change.
+    ///
+    /// Examples:
+    ///   <bin> module rename base core
+    ///   <bin> module rename notes linux/notes
+    Rename {
+        /// Current module name (e.g., "base" or "linux/notes")
+        old: String,
+
+        /// New module name
+        new: String,
+    },
+}
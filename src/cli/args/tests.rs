@@ -68,3 +68,16 @@ fn help_does_not_show_self_update() {
     assert!(!help.contains("self-update"));
     assert!(!help.contains("selfupdate"));
 }
+
+#[test]
+fn verbose_follows_effective_log_level() {
+    let verbose = |flags: &[&str]| {
+        let mut argv = vec![project_identity::BINARY_NAME, "info"];
+        argv.extend_from_slice(flags);
+        Cli::try_parse_from(argv).unwrap().global.is_verbose()
+    };
+    assert!(!verbose(&[]));
+    assert!(verbose(&["-v"]));
+    assert!(verbose(&["--log-level", "debug"]));
+    assert!(!verbose(&["-v", "--log-level", "warn"]));
+}
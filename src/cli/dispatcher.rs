@@ -6,20 +6,24 @@ mod normalization;
 mod output_contract;
 mod routing;
 
-use crate::cli::args::{Cli, Command};
+use crate::cli::args::{Cli, Command, ModuleCommand, StateCommand};
 use crate::commands;
 use crate::error::Result;
 use crate::project_identity;
 use crate::ui as output;
+use normalization::list_to_optional_vec;
 use output_contract::validate_machine_output_contract;
 use routing::{
-    handle_info_command, handle_init_command, handle_lint_command, handle_search_command,
+    SyncArgs, handle_info_command, handle_init_command, handle_lint_command, handle_search_command,
     handle_sync_command,
 };
 
 /// Dispatch the parsed CLI command to the appropriate handler.
 pub fn dispatch(args: &Cli) -> Result<()> {
     validate_machine_output_contract(args)?;
+    if args.global.force_unlock && !matches!(args.command, Some(Command::Unlock)) {
+        commands::unlock::force_before_command()?;
+    }
 
     match &args.command {
         Some(Command::Init {
@@ -39,26 +43,97 @@ pub fn dispatch(args: &Cli) -> Result<()> {
             profile,
             host,
             modules,
+            assume_installed,
+            prune_backend,
+            backend_file,
+            reinstall,
+            tag,
+            target_module,
+            explain,
+            interactive_conflicts,
+            remember,
+            continue_on_error,
+            confirm,
+            print_config,
+            upgrade,
+            check_only,
+            no_hooks,
+            only_hooks,
+            dump_commands,
+            stats,
+            prune_orphans,
             command,
         }) => handle_sync_command(
-            args, target, *diff, *noconfirm, *hooks, profile, host, modules, command,
+            args,
+            SyncArgs {
+                target,
+                diff: *diff,
+                noconfirm: *noconfirm,
+                hooks: *hooks,
+                profile,
+                host,
+                modules,
+                assume_installed,
+                prune_backend,
+                backend_file,
+                reinstall,
+                tag,
+                target_module,
+                explain: *explain,
+                interactive_conflicts: *interactive_conflicts,
+                remember: *remember,
+                continue_on_error: *continue_on_error,
+                confirm: *confirm,
+                print_config: *print_config,
+                upgrade: *upgrade,
+                check_only: *check_only,
+                no_hooks: *no_hooks,
+                only_hooks,
+                dump_commands: *dump_commands,
+                stats: *stats,
+                prune_orphans: *prune_orphans,
+                command,
+            },
         ),
 
         Some(Command::Info {
             query,
             doctor,
             plan,
+            summary,
             list,
             scope,
+            since,
             backend,
             package,
             profile,
             host,
             modules,
         }) => handle_info_command(
-            args, query, *doctor, *plan, *list, scope, backend, package, profile, host, modules,
+            args,
+            query,
+            *doctor,
+            *plan,
+            *summary,
+            *list,
+            scope,
+            since.as_deref(),
+            backend,
+            package,
+            profile,
+            host,
+            modules,
         ),
 
+        Some(Command::Adopt { package, module }) => {
+            commands::adopt::run(commands::adopt::AdoptOptions {
+                package: package.clone(),
+                module: module.clone(),
+                dry_run: args.global.dry_run,
+                verbose: args.global.is_verbose(),
+            })
+        }
+
         Some(Command::Switch {
             old_package,
             new_package,
@@ -70,6 +145,9 @@ pub fn dispatch(args: &Cli) -> Result<()> {
             dry_run: args.global.dry_run,
             yes: args.global.yes,
             force: args.global.force,
+            verbose: args.global.is_verbose(),
+            no_sudo: args.global.no_sudo,
+            quiet_backends: args.global.quiet_backends,
         }),
 
         Some(Command::Edit {
@@ -103,24 +181,41 @@ pub fn dispatch(args: &Cli) -> Result<()> {
             no_sync: *no_sync,
             yes: args.global.yes,
             dry_run: args.global.dry_run,
-            verbose: args.global.verbose,
+            verbose: args.global.is_verbose(),
+            no_sudo: args.global.no_sudo,
+            quiet_backends: args.global.quiet_backends,
         }),
 
         Some(Command::Search {
             query,
             backends,
             limit,
+            all,
             installed_only,
             available_only,
+            exact,
+            in_description,
+            installed_version,
             local,
+            backend_file,
+            max_parallel,
+            timeout,
+            fields,
         }) => handle_search_command(
             args,
             query,
             backends,
-            limit.as_deref(),
+            if *all { Some("0") } else { limit.as_deref() },
             *installed_only,
             *available_only,
+            *exact,
+            *in_description,
+            *installed_version,
             *local,
+            backend_file,
+            *max_parallel,
+            *timeout,
+            fields,
         ),
 
         Some(Command::Lint {
@@ -154,7 +249,75 @@ pub fn dispatch(args: &Cli) -> Result<()> {
             modules,
         ),
 
-        Some(Command::Completions { shell }) => commands::completions::run(*shell),
+        Some(Command::Gc { backend, keep_days }) => commands::gc::run(commands::gc::GcOptions {
+            backends: list_to_optional_vec(backend),
+            keep_days: *keep_days,
+            dry_run: args.global.dry_run,
+            verbose: args.global.is_verbose(),
+            no_sudo: args.global.no_sudo,
+            quiet_backends: args.global.quiet_backends,
+        }),
+
+        Some(Command::History { limit }) => {
+            commands::history::run(commands::history::HistoryOptions {
+                limit: *limit,
+                verbose: args.global.is_verbose(),
+                format: args.global.format.clone(),
+                output_version: args.global.output_version.clone(),
+                output: args.global.output.clone(),
+            })
+        }
+
+        Some(Command::Outdated { backend }) => {
+            commands::outdated::run(commands::outdated::OutdatedOptions {
+                backends: list_to_optional_vec(backend),
+                verbose: args.global.is_verbose(),
+                format: args.global.format.clone(),
+                output_version: args.global.output_version.clone(),
+                output: args.global.output.clone(),
+            })
+        }
+
+        Some(Command::Show { package }) => commands::show::run(commands::show::ShowOptions {
+            package: package.clone(),
+            format: args.global.format.clone(),
+            output_version: args.global.output_version.clone(),
+            output: args.global.output.clone(),
+        }),
+
+        Some(Command::Plan { package }) => commands::plan::run(commands::plan::PlanOptions {
+            package: package.clone(),
+            verbose: args.global.is_verbose(),
+        }),
+
+        Some(Command::Doctor) => commands::doctor::run(commands::doctor::DoctorOptions {
+            verbose: args.global.is_verbose(),
+        }),
+
+        Some(Command::Unlock) => commands::unlock::run(),
+
+        Some(Command::State { command }) => match command {
+            StateCommand::Repair { adopt_untracked } => {
+                commands::state_repair::run(commands::state_repair::StateRepairOptions {
+                    adopt_untracked: *adopt_untracked,
+                    dry_run: args.global.dry_run,
+                    verbose: args.global.is_verbose(),
+                })
+            }
+        },
+
+        Some(Command::Module { command }) => match command {
+            ModuleCommand::Rename { old, new } => {
+                commands::module_rename::run(commands::module_rename::ModuleRenameOptions {
+                    old: old.clone(),
+                    new: new.clone(),
+                    dry_run: args.global.dry_run,
+                    verbose: args.global.is_verbose(),
+                })
+            }
+        },
+
+        Some(Command::Completions { shell, names }) => commands::completions::run(*shell, *names),
         Some(Command::Ext) => commands::ext::run(),
         Some(Command::SelfUpdate { check, version }) => {
             commands::self_update::run(commands::self_update::SelfUpdateOptions {
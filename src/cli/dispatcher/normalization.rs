@@ -1,6 +1,7 @@
 use crate::cli::args::LintMode;
 use crate::commands;
 use crate::error::{DeclarchError, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 
 pub(super) fn map_lint_mode(mode: &LintMode) -> commands::lint::LintMode {
     match mode {
@@ -31,3 +32,47 @@ pub(super) fn parse_limit_option(limit: Option<&str>) -> Result<Option<usize>> {
         }),
     }
 }
+
+/// Parse `--since` as a relative duration (`12h`, `7d`, `2w`) or an absolute
+/// date (`2024-01-01`, RFC 3339) into a cutoff relative to `now`.
+pub(super) fn parse_since_option(
+    since: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>> {
+    let Some(raw) = since.map(str::trim) else {
+        return Ok(None);
+    };
+
+    let invalid = || {
+        DeclarchError::Other(format!(
+            "Invalid --since value '{}'. Use a duration like 12h, 7d, 2w or a date like 2024-01-01.",
+            raw
+        ))
+    };
+
+    if let Some(unit) = raw.chars().last().filter(|c| c.is_ascii_alphabetic()) {
+        let amount: i64 = raw[..raw.len() - 1].parse().map_err(|_| invalid())?;
+        let duration = match unit {
+            'h' => Duration::try_hours(amount),
+            'd' => Duration::try_days(amount),
+            'w' => Duration::try_weeks(amount),
+            _ => None,
+        }
+        .filter(|_| amount >= 0)
+        .ok_or_else(invalid)?;
+        return now
+            .checked_sub_signed(duration)
+            .map(Some)
+            .ok_or_else(invalid);
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        return Ok(Some(
+            date.and_hms_opt(0, 0, 0).ok_or_else(invalid)?.and_utc(),
+        ));
+    }
+
+    DateTime::parse_from_rfc3339(raw)
+        .map(|dt| Some(dt.with_timezone(&Utc)))
+        .map_err(|_| invalid())
+}
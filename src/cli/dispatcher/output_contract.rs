@@ -4,6 +4,18 @@ use crate::project_identity;
 use crate::ui as output;
 
 pub(super) fn validate_machine_output_contract(args: &Cli) -> Result<()> {
+    if args.global.output.is_some()
+        && !matches!(
+            args.global.format.as_deref(),
+            Some("json" | "yaml" | "toml" | "jsonl")
+        )
+    {
+        return Err(DeclarchError::Other(
+            "--output writes machine-readable output; use --format json|yaml|toml|jsonl"
+                .to_string(),
+        ));
+    }
+
     if let Some(version) = args.global.output_version.as_deref() {
         if version != "v1" {
             return Err(DeclarchError::Other(format!(
@@ -13,10 +25,18 @@ pub(super) fn validate_machine_output_contract(args: &Cli) -> Result<()> {
         }
 
         match args.global.format.as_deref() {
-            Some("json") | Some("yaml") => {}
+            Some("json") | Some("yaml") | Some("toml") => {}
+            Some("jsonl") => {
+                if !matches!(args.command, Some(Command::Search { .. })) {
+                    return Err(DeclarchError::Other(format!(
+                        "--format jsonl is only supported by `{}`",
+                        project_identity::cli_with("search")
+                    )));
+                }
+            }
             Some(other) => {
                 return Err(DeclarchError::Other(format!(
-                    "--output-version v1 requires --format json|yaml (got '{}')",
+                    "--output-version v1 requires --format json|yaml|toml, or jsonl for search (got '{}')",
                     other
                 )));
             }
@@ -29,11 +49,13 @@ pub(super) fn validate_machine_output_contract(args: &Cli) -> Result<()> {
 
         if !supports_v1_contract(args) {
             return Err(DeclarchError::Other(format!(
-                "This command does not support --output-version v1 yet.\nSupported now: `{}`, `{}`, `{}`, `{}`, `{}`.",
+                "This command does not support --output-version v1 yet.\nSupported now: `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`.",
                 project_identity::cli_with("info"),
                 project_identity::cli_with("info --list"),
                 project_identity::cli_with("lint"),
                 project_identity::cli_with("search"),
+                project_identity::cli_with("outdated"),
+                project_identity::cli_with("show"),
                 project_identity::cli_with("--dry-run sync"),
             )));
         }
@@ -46,7 +68,14 @@ fn supports_v1_contract(args: &Cli) -> bool {
     match &args.command {
         Some(Command::Lint { .. }) => true,
         Some(Command::Search { .. }) => true,
-        Some(Command::Sync { command: None, .. }) => args.global.dry_run,
+        Some(Command::Outdated { .. }) => true,
+        Some(Command::Show { .. }) => true,
+        Some(Command::Sync {
+            command: None,
+            print_config,
+            check_only,
+            ..
+        }) => args.global.dry_run || *print_config || *check_only,
         Some(Command::Info {
             doctor,
             plan,
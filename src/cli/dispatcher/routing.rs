@@ -1,8 +1,12 @@
-use super::normalization::{list_to_optional_vec, map_lint_mode, parse_limit_option};
+use super::normalization::{
+    list_to_optional_vec, map_lint_mode, parse_limit_option, parse_since_option,
+};
 use crate::cli::args::{Cli, InfoListScope, LintMode, SyncCommand};
 use crate::commands;
 use crate::error::{DeclarchError, Result};
 use crate::project_identity;
+use chrono::Utc;
+use std::path::PathBuf;
 
 #[allow(clippy::too_many_arguments)]
 pub(super) fn handle_init_command(
@@ -16,7 +20,10 @@ pub(super) fn handle_init_command(
 ) -> Result<()> {
     if let Some(what) = list {
         return match what.as_str() {
-            "backends" => commands::init::list_available_backends(),
+            "backends" => commands::init::list_available_backends(
+                args.global.format.as_deref(),
+                args.global.output.as_deref(),
+            ),
             "modules" => commands::init::list_available_modules(),
             _ => Err(DeclarchError::Other(format!(
                 "Unknown init list target '{}'. Use '{}' or '{}'.",
@@ -41,30 +48,57 @@ pub(super) fn handle_init_command(
     })
 }
 
-#[allow(clippy::too_many_arguments)]
-pub(super) fn handle_sync_command(
-    args: &Cli,
-    target: &Option<String>,
-    diff: bool,
-    noconfirm: bool,
-    hooks: bool,
-    profile: &Option<String>,
-    host: &Option<String>,
-    modules: &[String],
-    command: &Option<SyncCommand>,
-) -> Result<()> {
-    match command {
+/// Flags of `sync`, borrowed from the parsed command.
+///
+/// `sync update`/`sync prune` take their own copies of the shared flags
+/// (target, diff, noconfirm, ...), which replace these.
+pub(super) struct SyncArgs<'a> {
+    pub(super) target: &'a [String],
+    pub(super) diff: bool,
+    pub(super) noconfirm: bool,
+    pub(super) hooks: bool,
+    pub(super) profile: &'a [String],
+    pub(super) host: &'a Option<String>,
+    pub(super) modules: &'a [String],
+    pub(super) assume_installed: &'a [String],
+    pub(super) prune_backend: &'a [String],
+    pub(super) backend_file: &'a [PathBuf],
+    pub(super) reinstall: &'a [String],
+    pub(super) tag: &'a [String],
+    pub(super) target_module: &'a Option<String>,
+    pub(super) explain: bool,
+    pub(super) interactive_conflicts: bool,
+    pub(super) remember: bool,
+    pub(super) continue_on_error: bool,
+    pub(super) confirm: bool,
+    pub(super) print_config: bool,
+    pub(super) upgrade: bool,
+    pub(super) check_only: bool,
+    pub(super) no_hooks: bool,
+    pub(super) only_hooks: &'a Option<String>,
+    pub(super) dump_commands: bool,
+    pub(super) stats: bool,
+    pub(super) prune_orphans: bool,
+    pub(super) command: &'a Option<SyncCommand>,
+}
+
+pub(super) fn handle_sync_command(args: &Cli, sync: SyncArgs) -> Result<()> {
+    match sync.command {
         Some(SyncCommand::Cache { backend }) => {
             commands::cache::run(commands::cache::CacheOptions {
                 backends: list_to_optional_vec(backend),
-                verbose: args.global.verbose,
+                verbose: args.global.is_verbose(),
+                no_sudo: args.global.no_sudo,
+                quiet_backends: args.global.quiet_backends,
             })
         }
         Some(SyncCommand::Upgrade { backend, no_sync }) => {
             commands::upgrade::run(commands::upgrade::UpgradeOptions {
                 backends: list_to_optional_vec(backend),
                 no_sync: *no_sync,
-                verbose: args.global.verbose,
+                verbose: args.global.is_verbose(),
+                no_sudo: args.global.no_sudo,
+                quiet_backends: args.global.quiet_backends,
             })
         }
         Some(SyncCommand::Update {
@@ -75,9 +109,19 @@ pub(super) fn handle_sync_command(
             profile,
             host,
             modules,
-        }) => commands::sync::run(build_sync_options(
-            args, target, *noconfirm, *hooks, profile, host, modules, *diff, false, true,
-        )),
+            assume_installed,
+        }) => commands::sync::run(commands::sync::SyncOptions {
+            update: true,
+            target: target.to_vec(),
+            diff: *diff,
+            noconfirm: *noconfirm,
+            hooks: *hooks,
+            profiles: profile.to_vec(),
+            host: host.clone(),
+            modules: modules.to_vec(),
+            assume_installed: assume_installed.to_vec(),
+            ..build_sync_options(args, &sync)
+        }),
         Some(SyncCommand::Prune {
             target,
             diff,
@@ -86,44 +130,63 @@ pub(super) fn handle_sync_command(
             profile,
             host,
             modules,
-        }) => commands::sync::run(build_sync_options(
-            args, target, *noconfirm, *hooks, profile, host, modules, *diff, true, false,
-        )),
-        _ => commands::sync::run(build_sync_options(
-            args, target, noconfirm, hooks, profile, host, modules, diff, false, false,
-        )),
+            assume_installed,
+        }) => commands::sync::run(commands::sync::SyncOptions {
+            prune: true,
+            target: target.to_vec(),
+            diff: *diff,
+            noconfirm: *noconfirm,
+            hooks: *hooks,
+            profiles: profile.to_vec(),
+            host: host.clone(),
+            modules: modules.to_vec(),
+            assume_installed: assume_installed.to_vec(),
+            ..build_sync_options(args, &sync)
+        }),
+        _ => commands::sync::run(build_sync_options(args, &sync)),
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-fn build_sync_options(
-    args: &Cli,
-    target: &Option<String>,
-    noconfirm: bool,
-    hooks: bool,
-    profile: &Option<String>,
-    host: &Option<String>,
-    modules: &[String],
-    diff: bool,
-    prune: bool,
-    update: bool,
-) -> commands::sync::SyncOptions {
+/// Options for a plain `sync` (no `update`/`prune` subcommand).
+fn build_sync_options(args: &Cli, sync: &SyncArgs) -> commands::sync::SyncOptions {
     commands::sync::SyncOptions {
-        dry_run: args.global.dry_run,
-        prune,
-        update,
-        verbose: args.global.verbose,
+        dry_run: args.global.dry_run || sync.check_only || sync.dump_commands,
+        prune: false,
+        update: false,
+        verbose: args.global.is_verbose(),
         yes: args.global.yes,
         force: args.global.force,
-        target: target.clone(),
-        noconfirm,
-        hooks,
-        profile: profile.clone(),
-        host: host.clone(),
-        modules: modules.to_vec(),
-        diff,
+        target: sync.target.to_vec(),
+        noconfirm: sync.noconfirm,
+        hooks: sync.hooks,
+        profiles: sync.profile.to_vec(),
+        host: sync.host.clone(),
+        modules: sync.modules.to_vec(),
+        diff: sync.diff,
         format: args.global.format.clone(),
         output_version: args.global.output_version.clone(),
+        output: args.global.output.clone(),
+        assume_installed: sync.assume_installed.to_vec(),
+        prune_backends: sync.prune_backend.to_vec(),
+        backend_files: sync.backend_file.to_vec(),
+        reinstall: sync.reinstall.to_vec(),
+        tags: sync.tag.to_vec(),
+        target_module: sync.target_module.clone(),
+        explain: sync.explain,
+        interactive_conflicts: sync.interactive_conflicts,
+        remember_conflicts: sync.remember,
+        continue_on_error: sync.continue_on_error,
+        confirm: sync.confirm,
+        print_config: sync.print_config,
+        upgrade: sync.upgrade,
+        check_only: sync.check_only,
+        no_hooks: sync.no_hooks,
+        only_hooks: sync.only_hooks.clone(),
+        dump_commands: sync.dump_commands,
+        stats: sync.stats,
+        prune_orphans: sync.prune_orphans,
+        no_sudo: args.global.no_sudo,
+        quiet_backends: args.global.quiet_backends,
     }
 }
 
@@ -133,11 +196,13 @@ pub(super) fn handle_info_command(
     query: &Option<String>,
     doctor: bool,
     plan: bool,
+    summary: bool,
     list: bool,
     scope: &Option<InfoListScope>,
+    since: Option<&str>,
     backend: &Option<String>,
     package: &Option<String>,
-    profile: &Option<String>,
+    profile: &[String],
     host: &Option<String>,
     modules: &[String],
 ) -> Result<()> {
@@ -148,6 +213,9 @@ pub(super) fn handle_info_command(
     if plan {
         mode_count += 1;
     }
+    if summary {
+        mode_count += 1;
+    }
     if query.is_some() {
         mode_count += 1;
     }
@@ -156,35 +224,49 @@ pub(super) fn handle_info_command(
     }
     if mode_count > 1 {
         return Err(DeclarchError::Other(
-            "Use only one info mode at a time: status, query, --plan, --doctor, or --list [--scope ...]".to_string(),
+            "Use only one info mode at a time: status, query, --plan, --summary, --doctor, or --list [--scope ...]".to_string(),
         ));
     }
 
     if doctor {
         return commands::info::run(commands::info::InfoOptions {
             doctor: true,
+            summary: false,
             format: args.global.format.clone(),
             output_version: args.global.output_version.clone(),
+            output: args.global.output.clone(),
             backend: backend.clone(),
             package: package.clone(),
-            verbose: args.global.verbose,
+            profiles: Vec::new(),
+            host: None,
+            verbose: args.global.is_verbose(),
         });
     }
 
     if list || scope.is_some() {
+        // Validate before any state is loaded
+        let since = parse_since_option(since, Utc::now())?;
         let (orphans, synced, unmanaged) = match scope {
             Some(InfoListScope::Orphans) => (true, false, false),
             Some(InfoListScope::Synced) => (false, true, false),
             Some(InfoListScope::Unmanaged) => (false, false, true),
             _ => (false, false, false),
         };
+        if unmanaged && since.is_some() {
+            return Err(DeclarchError::Other(
+                "--since applies to tracked packages; it cannot be combined with --scope unmanaged"
+                    .to_string(),
+            ));
+        }
         return commands::list::run(commands::list::ListOptions {
             backend: backend.clone(),
             orphans,
             synced,
             unmanaged,
+            since,
             format: args.global.format.clone(),
             output_version: args.global.output_version.clone(),
+            output: args.global.output.clone(),
         });
     }
 
@@ -196,20 +278,24 @@ pub(super) fn handle_info_command(
             } else {
                 None
             },
-            profile: profile.clone(),
+            profiles: profile.to_vec(),
             host: host.clone(),
             modules: modules.to_vec(),
-            verbose: args.global.verbose,
+            verbose: args.global.is_verbose(),
         });
     }
 
     commands::info::run(commands::info::InfoOptions {
         doctor: false,
+        summary,
         format: args.global.format.clone(),
         output_version: args.global.output_version.clone(),
+        output: args.global.output.clone(),
         backend: backend.clone(),
         package: package.clone(),
-        verbose: args.global.verbose,
+        profiles: profile.to_vec(),
+        host: host.clone(),
+        verbose: args.global.is_verbose(),
     })
 }
 
@@ -221,20 +307,36 @@ pub(super) fn handle_search_command(
     limit: Option<&str>,
     installed_only: bool,
     available_only: bool,
+    exact: bool,
+    in_description: bool,
+    installed_version: bool,
     local: bool,
+    backend_file: &[PathBuf],
+    max_parallel: Option<usize>,
+    timeout: Option<u64>,
+    fields: &[String],
 ) -> Result<()> {
-    let parsed_limit = parse_limit_option(limit)?;
+    // `parse_limit_option` yields `None` for unlimited, which search spells `Some(0)`
+    let parsed_limit = parse_limit_option(limit)?.unwrap_or(0);
 
     commands::search::run(commands::search::SearchOptions {
         query: query.to_string(),
         backends: list_to_optional_vec(backends),
-        limit: parsed_limit,
+        limit: Some(parsed_limit),
         installed_only,
         available_only,
+        exact,
+        in_description,
+        installed_version,
         local,
-        verbose: args.global.verbose,
+        backend_files: backend_file.to_vec(),
+        max_parallel,
+        timeout_secs: timeout,
+        fields: commands::search::parse_fields(fields)?,
+        verbose: args.global.is_verbose(),
         format: args.global.format.clone(),
         output_version: args.global.output_version.clone(),
+        output: args.global.output.clone(),
     })
 }
 
@@ -251,7 +353,7 @@ pub(super) fn handle_lint_command(
     state_rm: &[String],
     state_rm_backend: &Option<String>,
     state_rm_all: bool,
-    profile: &Option<String>,
+    profile: &[String],
     host: &Option<String>,
     modules: &[String],
 ) -> Result<()> {
@@ -270,8 +372,9 @@ pub(super) fn handle_lint_command(
         yes: args.global.yes,
         format: args.global.format.clone(),
         output_version: args.global.output_version.clone(),
-        verbose: args.global.verbose,
-        profile: profile.clone(),
+        output: args.global.output.clone(),
+        verbose: args.global.is_verbose(),
+        profiles: profile.to_vec(),
         host: host.clone(),
         modules: modules.to_vec(),
     })
@@ -1,17 +1,27 @@
-use super::normalization::parse_limit_option;
+use super::normalization::{parse_limit_option, parse_since_option};
 use super::output_contract::validate_machine_output_contract;
 use crate::cli::args::{Cli, GlobalFlags};
 
 fn base_cli() -> Cli {
     Cli {
         global: GlobalFlags {
-            verbose: false,
+            verbose: 0,
+            log_level: None,
             quiet: false,
+            color: None,
             yes: false,
             force: false,
             dry_run: false,
             format: None,
             output_version: None,
+            output: None,
+            no_sudo: false,
+            quiet_backends: false,
+            force_unlock: false,
+            state_file: None,
+            config: None,
+            no_cache: false,
+            frozen: false,
         },
         command: None,
     }
@@ -34,7 +44,7 @@ fn output_version_v1_allows_json_format() {
         state_rm: Vec::new(),
         state_rm_backend: None,
         state_rm_all: false,
-        profile: None,
+        profile: Vec::new(),
         host: None,
         modules: Vec::new(),
     });
@@ -57,6 +67,27 @@ fn output_version_requires_structured_format() {
     assert!(validate_machine_output_contract(&cli).is_err());
 }
 
+#[test]
+fn output_file_defaults_format_to_json() {
+    let mut cli = base_cli();
+    cli.global.output = Some("reports/out.json".into());
+    cli.global.apply_output_defaults();
+    assert_eq!(cli.global.format.as_deref(), Some("json"));
+    assert!(validate_machine_output_contract(&cli).is_ok());
+
+    cli.global.format = Some("yaml".to_string());
+    cli.global.apply_output_defaults();
+    assert_eq!(cli.global.format.as_deref(), Some("yaml"));
+}
+
+#[test]
+fn output_file_rejects_human_format() {
+    let mut cli = base_cli();
+    cli.global.output = Some("out.txt".into());
+    cli.global.format = Some("table".to_string());
+    assert!(validate_machine_output_contract(&cli).is_err());
+}
+
 #[test]
 fn output_version_rejects_unsupported_command() {
     use crate::cli::args::{Command, SyncCommand};
@@ -64,21 +95,41 @@ fn output_version_rejects_unsupported_command() {
     cli.global.output_version = Some("v1".to_string());
     cli.global.format = Some("json".to_string());
     cli.command = Some(Command::Sync {
-        target: None,
+        target: Vec::new(),
         diff: false,
         noconfirm: false,
         hooks: false,
-        profile: None,
+        profile: Vec::new(),
         host: None,
         modules: Vec::new(),
+        assume_installed: Vec::new(),
+        prune_backend: Vec::new(),
+        backend_file: Vec::new(),
+        reinstall: Vec::new(),
+        tag: Vec::new(),
+        target_module: None,
+        explain: false,
+        interactive_conflicts: false,
+        remember: false,
+        continue_on_error: false,
+        confirm: false,
+        print_config: false,
+        upgrade: false,
+        check_only: false,
+        no_hooks: false,
+        only_hooks: None,
+        dump_commands: false,
+        stats: false,
+        prune_orphans: false,
         command: Some(SyncCommand::Update {
-            target: None,
+            target: Vec::new(),
             diff: false,
             noconfirm: false,
             hooks: false,
-            profile: None,
+            profile: Vec::new(),
             host: None,
             modules: Vec::new(),
+            assume_installed: Vec::new(),
         }),
     });
     assert!(validate_machine_output_contract(&cli).is_err());
@@ -92,13 +143,32 @@ fn output_version_allows_dry_run_sync() {
     cli.global.format = Some("json".to_string());
     cli.global.dry_run = true;
     cli.command = Some(Command::Sync {
-        target: None,
+        target: Vec::new(),
         diff: false,
         noconfirm: false,
         hooks: false,
-        profile: None,
+        profile: Vec::new(),
         host: None,
         modules: Vec::new(),
+        assume_installed: Vec::new(),
+        prune_backend: Vec::new(),
+        backend_file: Vec::new(),
+        reinstall: Vec::new(),
+        tag: Vec::new(),
+        target_module: None,
+        explain: false,
+        interactive_conflicts: false,
+        remember: false,
+        continue_on_error: false,
+        confirm: false,
+        print_config: false,
+        upgrade: false,
+        check_only: false,
+        no_hooks: false,
+        only_hooks: None,
+        dump_commands: false,
+        stats: false,
+        prune_orphans: false,
         command: None,
     });
     assert!(validate_machine_output_contract(&cli).is_ok());
@@ -119,3 +189,36 @@ fn parse_limit_option_supports_unlimited() {
 fn parse_limit_option_rejects_invalid_input() {
     assert!(parse_limit_option(Some("abc")).is_err());
 }
+
+#[test]
+fn parse_since_option_accepts_durations_and_dates() {
+    use chrono::{Duration, TimeZone, Utc};
+    let now = Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+
+    assert_eq!(parse_since_option(None, now).unwrap(), None);
+    assert_eq!(
+        parse_since_option(Some("7d"), now).unwrap(),
+        Some(now - Duration::days(7))
+    );
+    assert_eq!(
+        parse_since_option(Some("12h"), now).unwrap(),
+        Some(now - Duration::hours(12))
+    );
+    assert_eq!(
+        parse_since_option(Some("2w"), now).unwrap(),
+        Some(now - Duration::weeks(2))
+    );
+    assert_eq!(
+        parse_since_option(Some("2024-01-01"), now).unwrap(),
+        Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+    );
+}
+
+#[test]
+fn parse_since_option_rejects_invalid_values() {
+    let now = chrono::Utc::now();
+    for raw in ["7", "7y", "-3d", "d", "yesterday", "2024-13-01"] {
+        let err = parse_since_option(Some(raw), now).expect_err(raw);
+        assert!(err.to_string().contains("Invalid --since value"));
+    }
+}
@@ -0,0 +1,172 @@
+//! Adopt command
+//!
+//! Moves a package that is already installed into config and state without
+//! installing anything: the targeted counterpart of sync's automatic adoption.
+
+use crate::commands::install::{cleanup_install_backups, finalize_edits, rollback_install_edits};
+use crate::config::editor::ConfigEditor;
+use crate::config::loader;
+use crate::config::types::GlobalConfig;
+use crate::core::resolver;
+use crate::core::types::{PackageId, PackageMetadata};
+use crate::error::{DeclarchError, Result};
+use crate::packages::create_manager;
+use crate::project_identity;
+use crate::state::{self, types::PackageState};
+use crate::ui as output;
+use crate::utils::paths;
+use chrono::Utc;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct AdoptOptions {
+    /// Package to adopt (format: backend:package)
+    pub package: String,
+    /// Target module file (e.g., "base" or "linux/notes")
+    pub module: Option<String>,
+    /// Preview changes without executing
+    pub dry_run: bool,
+    /// Verbose output
+    pub verbose: bool,
+}
+
+pub fn run(options: AdoptOptions) -> Result<()> {
+    let pkg_id: PackageId = options.package.parse().map_err(DeclarchError::Other)?;
+
+    let config_path = paths::config_file()?;
+    if loader::root_config_available(&config_path) {
+        let config = loader::load_root_config(&config_path)?;
+        if let Some(sources) = config.packages.get(&pkg_id) {
+            let declared_in: Vec<String> = sources
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            output::info(&format!(
+                "{} is already declared{}; nothing to adopt",
+                pkg_id,
+                if declared_in.is_empty() {
+                    String::new()
+                } else {
+                    format!(" in {}", declared_in.join(", "))
+                }
+            ));
+            return Ok(());
+        }
+    }
+
+    let lock = state::io::acquire_lock().map_err(|e| {
+        DeclarchError::Other(format!(
+            "Cannot start adopt: {}\n\
+             If no other {} process is running, delete the lock file manually.",
+            e,
+            project_identity::BINARY_NAME
+        ))
+    })?;
+
+    let manager = create_manager(&pkg_id.backend, &GlobalConfig::default(), false)
+        .map_err(|e| DeclarchError::Other(format!("Failed to create package manager: {}", e)))?;
+    if !manager.is_available() {
+        return Err(DeclarchError::Other(format!(
+            "Backend '{}' is not available on this system",
+            pkg_id.backend
+        )));
+    }
+
+    let installed = manager.list_installed()?;
+    let mut entry = adopted_package_state(&pkg_id, &installed)?;
+
+    if options.dry_run {
+        output::info(&format!(
+            "Would add {} to {} and track it as adopted",
+            pkg_id,
+            options.module.as_deref().unwrap_or("the default module")
+        ));
+        return Ok(());
+    }
+
+    let edit = ConfigEditor::new().add_package(
+        &pkg_id.name,
+        Some(pkg_id.backend.name()),
+        options.module.as_deref(),
+    )?;
+    if options.verbose {
+        output::verbose(&format!("Edited {}", edit.file_path.display()));
+    }
+    let edits = vec![edit];
+    let finalize = finalize_edits(&edits)?;
+
+    entry.source_module = finalize.modified_modules.first().cloned();
+    let mut state = state::io::load_state()?;
+    state
+        .packages
+        .insert(resolver::make_state_key(&pkg_id), entry);
+    if let Err(e) = state::io::save_state_locked(&state, &lock) {
+        rollback_install_edits(&edits, finalize.root_backup.as_ref());
+        return Err(e);
+    }
+
+    cleanup_install_backups(&edits, finalize.root_backup.as_ref());
+    output::success(&format!("Adopted {} (no packages were installed)", pkg_id));
+    Ok(())
+}
+
+/// State entry for an installed package; errors when it isn't installed.
+fn adopted_package_state(
+    pkg_id: &PackageId,
+    installed: &HashMap<String, PackageMetadata>,
+) -> Result<PackageState> {
+    let meta = installed.get(&pkg_id.name).ok_or_else(|| {
+        DeclarchError::Other(format!(
+            "{} is not installed; use `{}` to install and declare it",
+            pkg_id,
+            project_identity::cli_with(&format!("install {}", pkg_id))
+        ))
+    })?;
+
+    let mut entry = PackageState::from_config(pkg_id.name.clone(), pkg_id.backend.clone(), None);
+    entry.version = meta.version.clone();
+    entry.installed_at = meta.installed_at;
+    entry.last_seen_at = Some(Utc::now());
+    entry.install_reason = Some("adopted".to_string());
+    Ok(entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Backend;
+
+    fn pkg() -> PackageId {
+        PackageId {
+            name: "bat".to_string(),
+            backend: Backend::from("soar"),
+        }
+    }
+
+    #[test]
+    fn adopted_state_records_installed_version_and_reason() {
+        let mut installed = HashMap::new();
+        installed.insert(
+            "bat".to_string(),
+            PackageMetadata {
+                version: Some("0.24.0".to_string()),
+                variant: None,
+                installed_at: Utc::now(),
+                source_file: None,
+                install_size: None,
+                origin: None,
+            },
+        );
+
+        let entry = adopted_package_state(&pkg(), &installed).expect("bat is installed");
+        assert_eq!(entry.install_reason.as_deref(), Some("adopted"));
+        assert_eq!(entry.version.as_deref(), Some("0.24.0"));
+        assert_eq!(entry.config_name, "bat");
+    }
+
+    #[test]
+    fn adopting_a_missing_package_fails() {
+        let err = adopted_package_state(&pkg(), &HashMap::new()).expect_err("not installed");
+        assert!(err.to_string().contains("not installed"));
+    }
+}
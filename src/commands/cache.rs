@@ -17,10 +17,66 @@ pub struct CacheOptions {
     pub backends: Option<Vec<String>>,
     /// Verbose output
     pub verbose: bool,
+    /// Clean caches without escalation (`--no-sudo`)
+    pub no_sudo: bool,
+    /// Capture cache clean output, showing it only on failure (`--quiet-backends`)
+    pub quiet_backends: bool,
 }
 
 /// Run cache clean for configured backends
 pub fn run(options: CacheOptions) -> Result<()> {
+    let cleanable_backends = collect_cleanable_backends(&options)?;
+    if cleanable_backends.is_empty() {
+        return Ok(());
+    }
+
+    // Clean backends
+    output::info("Cleaning caches...");
+    output::separator();
+
+    let mut cleaned_count = 0;
+    let mut failed_count = 0;
+
+    for (name, manager) in cleanable_backends {
+        match manager.clean_cache() {
+            Ok(()) => {
+                cleaned_count += 1;
+            }
+            Err(e) => {
+                if options.verbose {
+                    output::warning(&format!("Failed to clean '{}': {}", name, e));
+                    output::info(
+                        "  Recommendation: Check backend configuration or run with --force to ignore errors",
+                    );
+                } else {
+                    output::warning(&format!(
+                        "Failed to clean '{}' (use --verbose for details)",
+                        name
+                    ));
+                }
+                failed_count += 1;
+            }
+        }
+    }
+
+    output::separator();
+    if cleaned_count > 0 {
+        output::success(&format!("Cleaned {} backend cache(s)", cleaned_count));
+    }
+    if failed_count > 0 {
+        output::warning(&format!("Failed {} backend(s)", failed_count));
+    }
+
+    Ok(())
+}
+
+/// Resolve configured backends that can clean their cache on this device.
+///
+/// Reports unknown, unsupported, and unavailable backends as warnings and
+/// returns the remaining managers sorted by backend name.
+pub(crate) fn collect_cleanable_backends(
+    options: &CacheOptions,
+) -> Result<Vec<(String, Box<dyn PackageManager>)>> {
     // Load all configured backends (import-based or legacy)
     let all_backends = load_all_backends_unified()?;
 
@@ -30,7 +86,7 @@ pub fn run(options: CacheOptions) -> Result<()> {
             "Run '{}' to add a backend",
             project_identity::cli_with("init --backend <name>")
         ));
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     // Filter backends if specific ones requested
@@ -61,7 +117,7 @@ pub fn run(options: CacheOptions) -> Result<()> {
 
     if backends_to_clean.is_empty() {
         output::warning("No matching backends found");
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     let runtime_config = load_runtime_config_for_command("cache command");
@@ -87,12 +143,17 @@ pub fn run(options: CacheOptions) -> Result<()> {
             continue;
         }
 
-        let manager: Box<dyn PackageManager> =
-            Box::new(crate::backends::GenericManager::from_config(
+        let manager: Box<dyn PackageManager> = Box::new(
+            crate::backends::GenericManager::from_config(
                 config,
                 crate::core::types::Backend::from(name.as_str()),
                 false,
-            ));
+            )
+            .with_escalation(runtime_config.escalation_program())
+            .with_no_sudo(options.no_sudo)
+            .with_quiet_output(options.quiet_backends)
+            .with_echo_commands(options.verbose),
+        );
         if manager.is_available() && manager.supports_cache_clean() {
             cleanable_backends.push((name, manager));
         } else if !manager.is_available() {
@@ -118,45 +179,7 @@ pub fn run(options: CacheOptions) -> Result<()> {
 
     if cleanable_backends.is_empty() {
         output::info("No backends to clean");
-        return Ok(());
     }
 
-    // Clean backends
-    output::info("Cleaning caches...");
-    output::separator();
-
-    let mut cleaned_count = 0;
-    let mut failed_count = 0;
-
-    for (name, manager) in cleanable_backends {
-        match manager.clean_cache() {
-            Ok(()) => {
-                cleaned_count += 1;
-            }
-            Err(e) => {
-                if options.verbose {
-                    output::warning(&format!("Failed to clean '{}': {}", name, e));
-                    output::info(
-                        "  Recommendation: Check backend configuration or run with --force to ignore errors",
-                    );
-                } else {
-                    output::warning(&format!(
-                        "Failed to clean '{}' (use --verbose for details)",
-                        name
-                    ));
-                }
-                failed_count += 1;
-            }
-        }
-    }
-
-    output::separator();
-    if cleaned_count > 0 {
-        output::success(&format!("Cleaned {} backend cache(s)", cleaned_count));
-    }
-    if failed_count > 0 {
-        output::warning(&format!("Failed {} backend(s)", failed_count));
-    }
-
-    Ok(())
+    Ok(cleanable_backends)
 }
@@ -1,14 +1,148 @@
+//! Shell completion scripts
+//!
+//! The static part comes from the clap definition. Bash and fish scripts also
+//! ask `completions --names` for backend and module names from the config, so
+//! `--target <TAB>` offers what is actually declared.
+
 use crate::cli::args::Cli;
+use crate::config::loader;
 use crate::error::Result;
+use crate::utils::paths;
 use clap::CommandFactory;
 use clap_complete::{Shell, generate};
-use std::io;
+use std::io::{self, Write};
+
+/// Options whose values are backend or module names.
+const NAME_OPTIONS: &[&str] = &["target", "backend", "backends", "prune-backend", "modules"];
+
+pub fn run(shell: Option<Shell>, names: bool) -> Result<()> {
+    let output = if names {
+        let mut names = completion_names_from(&load_config_quietly());
+        names.push(String::new());
+        names.join("\n")
+    } else {
+        // clap requires a shell unless --names is given
+        completion_script(shell.unwrap_or(Shell::Bash))
+    };
+
+    // A closed pipe (`| head`) is not an error worth reporting
+    match io::stdout().write_all(output.as_bytes()) {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
 
-pub fn run(shell: Shell) -> Result<()> {
+fn completion_script(shell: Shell) -> String {
     let mut cmd = Cli::command();
     let bin_name = cmd.get_name().to_string();
 
-    generate(shell, &mut cmd, bin_name, &mut io::stdout());
+    let mut buf = Vec::new();
+    generate(shell, &mut cmd, bin_name.clone(), &mut buf);
+    let mut script = String::from_utf8_lossy(&buf).into_owned();
+    script.push_str(&dynamic_names_snippet(shell, &bin_name));
+    script
+}
+
+/// Shell glue completing `NAME_OPTIONS` values from `completions --names`.
+fn dynamic_names_snippet(shell: Shell, bin: &str) -> String {
+    let fn_name = bin.replace('-', "_");
+    match shell {
+        Shell::Bash => {
+            let options: Vec<String> = NAME_OPTIONS.iter().map(|o| format!("--{}", o)).collect();
+            format!(
+                r#"
+_{fn_name}_names() {{
+    case "${{COMP_WORDS[COMP_CWORD-1]}}" in
+        {options})
+            COMPREPLY=( $(compgen -W "$({bin} completions --names 2>/dev/null)" -- "${{COMP_WORDS[COMP_CWORD]}}") )
+            return 0
+            ;;
+    esac
+    _{fn_name} "$@"
+}}
+complete -F _{fn_name}_names -o bashdefault -o default {bin}
+"#,
+                options = options.join("|"),
+            )
+        }
+        Shell::Fish => NAME_OPTIONS
+            .iter()
+            .map(|option| {
+                format!(
+                    "complete -c {bin} -l {option} -f -a '({bin} completions --names 2>/dev/null)'\n"
+                )
+            })
+            .collect(),
+        _ => String::new(),
+    }
+}
+
+fn load_config_quietly() -> loader::MergedConfig {
+    match paths::config_file() {
+        Ok(path) if loader::root_config_available(&path) => {
+            loader::load_root_config(&path).unwrap_or_default()
+        }
+        _ => loader::MergedConfig::default(),
+    }
+}
+
+/// Backend names and module stems a user can pass to `--target` and friends.
+fn completion_names_from(config: &loader::MergedConfig) -> Vec<String> {
+    let mut names: Vec<String> = config
+        .packages
+        .keys()
+        .map(|pkg| pkg.backend.to_string())
+        .chain(config.backends.iter().map(|backend| backend.name.clone()))
+        .chain(
+            config
+                .config_files
+                .iter()
+                .skip(1)
+                .filter_map(|file| file.file_stem())
+                .map(|stem| stem.to_string_lossy().into_owned()),
+        )
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Backend, PackageId};
+    use std::path::PathBuf;
+
+    #[test]
+    fn names_cover_backends_and_modules_but_not_root() {
+        let mut config = loader::MergedConfig::default();
+        config.packages.insert(
+            PackageId {
+                name: "bat".to_string(),
+                backend: Backend::from("aur"),
+            },
+            vec![],
+        );
+        config.config_files = vec![
+            PathBuf::from("/cfg/declarch.kdl"),
+            PathBuf::from("/cfg/modules/dev.kdl"),
+            PathBuf::from("/cfg/modules/base.kdl"),
+        ];
+
+        assert_eq!(completion_names_from(&config), vec!["aur", "base", "dev"]);
+    }
+
+    #[test]
+    fn bash_and_fish_scripts_complete_names_dynamically() {
+        let bash = completion_script(Shell::Bash);
+        assert!(bash.contains("--target|--backend|--backends|--prune-backend|--modules)"));
+        assert!(bash.contains("complete -F _declarch_names"));
+
+        let fish = completion_script(Shell::Fish);
+        assert!(
+            fish.contains("complete -c declarch -l target -f -a '(declarch completions --names")
+        );
 
-    Ok(())
+        assert!(!completion_script(Shell::Zsh).contains("completions --names"));
+    }
 }
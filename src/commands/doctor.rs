@@ -0,0 +1,475 @@
+//! Configuration doctor
+//!
+//! Runs a battery of checks for common misconfigurations (config that does
+//! not parse, backends without a definition or binary, unreadable state, a
+//! stale lock file, unknown experimental flags) and reports each as
+//! pass/warn/fail with a remediation hint. Fails when any check fails.
+//!
+//! Backend health and state consistency reuse the probes behind
+//! `info --doctor`; this command grades them instead of narrating them.
+
+use crate::backends::config::BackendConfig;
+use crate::commands::info::{backend_health_reports, collect_state_signature_duplicates};
+use crate::config::loader::{self, MergedConfig};
+use crate::constants::KNOWN_EXPERIMENTAL_FLAGS;
+use crate::error::{DeclarchError, Result};
+use crate::packages::traits::{HealthReport, HealthStatus};
+use crate::project_identity;
+use crate::state;
+use crate::ui as output;
+use crate::utils::paths;
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug)]
+struct CheckResult {
+    name: &'static str,
+    status: CheckStatus,
+    message: String,
+    hint: Option<String>,
+}
+
+impl CheckResult {
+    fn pass(name: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pass,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: &'static str, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(name: &'static str, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+pub struct DoctorOptions {
+    /// Verbose output
+    pub verbose: bool,
+}
+
+pub fn run(options: DoctorOptions) -> Result<()> {
+    output::header("Doctor");
+
+    let mut results = Vec::new();
+    let config = check_config(&mut results)?;
+
+    let backend_configs = match crate::backends::load_all_backends_unified() {
+        Ok(mut backends) => {
+            if let Some(config) = &config {
+                for backend in &config.backends {
+                    backends.insert(backend.name.clone(), backend.clone());
+                }
+            }
+            backends
+        }
+        Err(e) => {
+            results.push(CheckResult::fail(
+                "backends",
+                format!("Backend definitions could not be loaded: {}", e),
+                format!(
+                    "Fix the backend file named in the error, then run '{}'",
+                    project_identity::cli_with("lint")
+                ),
+            ));
+            HashMap::new()
+        }
+    };
+
+    if let Some(config) = &config {
+        results.push(check_backend_definitions(config, &backend_configs));
+        results.push(check_backend_binaries(config, &backend_configs));
+        results.push(check_experimental_flags(config));
+    }
+    results.push(check_state());
+    results.push(check_lock_file());
+
+    for result in &results {
+        print_result(result, options.verbose);
+    }
+
+    output::separator();
+    let failed = count(&results, CheckStatus::Fail);
+    let warned = count(&results, CheckStatus::Warn);
+    match worst_status(&results) {
+        CheckStatus::Pass => {
+            output::success("All checks passed");
+            Ok(())
+        }
+        CheckStatus::Warn => {
+            output::warning(&format!("{} check(s) need attention", warned));
+            Ok(())
+        }
+        CheckStatus::Fail => Err(DeclarchError::Other(format!(
+            "{} check(s) failed, {} warning(s)",
+            failed, warned
+        ))),
+    }
+}
+
+fn print_result(result: &CheckResult, verbose: bool) {
+    let line = format!("{}: {}", result.name, result.message);
+    match result.status {
+        CheckStatus::Pass => output::success(&line),
+        CheckStatus::Warn => output::warning(&line),
+        CheckStatus::Fail => output::error(&line),
+    }
+    if let Some(hint) = &result.hint
+        && (verbose || result.status != CheckStatus::Pass)
+    {
+        output::indent(&format!("→ {}", hint), 2);
+    }
+}
+
+fn count(results: &[CheckResult], status: CheckStatus) -> usize {
+    results.iter().filter(|r| r.status == status).count()
+}
+
+fn worst_status(results: &[CheckResult]) -> CheckStatus {
+    results
+        .iter()
+        .map(|r| r.status)
+        .max()
+        .unwrap_or(CheckStatus::Pass)
+}
+
+/// Config file exists and parses; returns the config when it does.
+fn check_config(results: &mut Vec<CheckResult>) -> Result<Option<MergedConfig>> {
+    let config_path = paths::config_file()?;
+    if !loader::root_config_available(&config_path) {
+        results.push(CheckResult::fail(
+            "config",
+            format!("No config at {}", config_path.display()),
+            format!("Run '{}' to create one", project_identity::cli_with("init")),
+        ));
+        return Ok(None);
+    }
+
+    match loader::load_root_config(&config_path) {
+        Ok(config) => {
+            results.push(CheckResult::pass(
+                "config",
+                format!(
+                    "Parses cleanly ({} packages declared)",
+                    config.packages.len()
+                ),
+            ));
+            Ok(Some(config))
+        }
+        Err(e) => {
+            results.push(CheckResult::fail(
+                "config",
+                format!("Does not parse: {}", e),
+                format!(
+                    "Fix the reported line, then run '{}'",
+                    project_identity::cli_with("lint")
+                ),
+            ));
+            Ok(None)
+        }
+    }
+}
+
+/// Backends that packages are declared for, excluding the legacy `default`.
+fn referenced_backends(config: &MergedConfig) -> BTreeSet<String> {
+    config
+        .packages
+        .keys()
+        .map(|pkg| pkg.backend.name().to_string())
+        .filter(|name| name != "default")
+        .collect()
+}
+
+fn missing_backend_definitions(
+    referenced: &BTreeSet<String>,
+    backend_configs: &HashMap<String, BackendConfig>,
+) -> Vec<String> {
+    referenced
+        .iter()
+        .filter(|name| !backend_configs.contains_key(*name))
+        .cloned()
+        .collect()
+}
+
+fn check_backend_definitions(
+    config: &MergedConfig,
+    backend_configs: &HashMap<String, BackendConfig>,
+) -> CheckResult {
+    if backend_configs.is_empty() {
+        return CheckResult::fail(
+            "backends",
+            "No backends are configured",
+            format!(
+                "Run '{}' to add one",
+                project_identity::cli_with("init --backend <name>")
+            ),
+        );
+    }
+
+    let missing = missing_backend_definitions(&referenced_backends(config), backend_configs);
+    if missing.is_empty() {
+        return CheckResult::pass(
+            "backends",
+            format!("{} backend definition(s) loaded", backend_configs.len()),
+        );
+    }
+
+    CheckResult::fail(
+        "backends",
+        format!(
+            "Packages reference backends without a definition: {}",
+            missing.join(", ")
+        ),
+        format!(
+            "Run '{}' for each, or fix the backend name in your config",
+            project_identity::cli_with("init --backend <name>")
+        ),
+    )
+}
+
+fn check_backend_binaries(
+    config: &MergedConfig,
+    backend_configs: &HashMap<String, BackendConfig>,
+) -> CheckResult {
+    let referenced = referenced_backends(config).into_iter().filter_map(|name| {
+        let backend_config = backend_configs.get(&name)?.clone();
+        Some((name, backend_config))
+    });
+    grade_backend_health(&backend_health_reports(referenced, false))
+}
+
+/// Missing binaries and backends whose list command fails are both warnings.
+fn grade_backend_health(reports: &[HealthReport]) -> CheckResult {
+    let with_status = |status: HealthStatus| -> Vec<&str> {
+        reports
+            .iter()
+            .filter(|report| report.status == status)
+            .map(|report| report.backend.name())
+            .collect()
+    };
+    let missing = with_status(HealthStatus::BinaryMissing);
+    let mut failing = with_status(HealthStatus::CommandFailed);
+    failing.extend(with_status(HealthStatus::ParseFailed));
+
+    if !missing.is_empty() {
+        return CheckResult::warn(
+            "binaries",
+            format!("Backend binaries not found: {}", missing.join(", ")),
+            "Install them or make sure they are on PATH; their packages are skipped during sync",
+        );
+    }
+    if !failing.is_empty() {
+        return CheckResult::warn(
+            "binaries",
+            format!(
+                "Backends failed to list installed packages: {}",
+                failing.join(", ")
+            ),
+            format!(
+                "Run '{}' to see the error for each",
+                project_identity::cli_with("info --doctor --verbose")
+            ),
+        );
+    }
+    CheckResult::pass("binaries", "Every referenced backend binary is available")
+}
+
+fn unknown_experimental_flags(config: &MergedConfig) -> Vec<String> {
+    let mut unknown: Vec<String> = config
+        .experimental
+        .iter()
+        .filter(|flag| !KNOWN_EXPERIMENTAL_FLAGS.contains(&flag.as_str()))
+        .cloned()
+        .collect();
+    unknown.sort();
+    unknown
+}
+
+fn check_experimental_flags(config: &MergedConfig) -> CheckResult {
+    let unknown = unknown_experimental_flags(config);
+    if unknown.is_empty() {
+        return CheckResult::pass("experimental", "All experimental flags are recognized");
+    }
+
+    CheckResult::warn(
+        "experimental",
+        format!("Unrecognized experimental flag(s): {}", unknown.join(", ")),
+        format!(
+            "Known flags: {}. Check for typos; unknown flags are ignored",
+            KNOWN_EXPERIMENTAL_FLAGS.join(", ")
+        ),
+    )
+}
+
+fn check_state() -> CheckResult {
+    let state_path = match state::io::get_state_path() {
+        Ok(path) => path,
+        Err(e) => {
+            return CheckResult::fail(
+                "state",
+                format!("State location unavailable: {}", e),
+                "Check permissions of the state directory, or pass --state-file",
+            );
+        }
+    };
+    if !state_path.exists() {
+        return CheckResult::warn(
+            "state",
+            "No state file yet",
+            format!("Run '{}' to create it", project_identity::cli_with("sync")),
+        );
+    }
+
+    match state::io::load_state_strict() {
+        Ok(state) => {
+            let duplicates = collect_state_signature_duplicates(&state);
+            if duplicates.is_empty() {
+                CheckResult::pass(
+                    "state",
+                    format!("Readable ({} packages tracked)", state.packages.len()),
+                )
+            } else {
+                CheckResult::warn(
+                    "state",
+                    format!(
+                        "{} duplicate package signature(s) (backend:name)",
+                        duplicates.len()
+                    ),
+                    format!(
+                        "Run '{}' to repair malformed state entries",
+                        project_identity::cli_with("lint --repair-state")
+                    ),
+                )
+            }
+        }
+        Err(e) => CheckResult::fail(
+            "state",
+            format!("Unreadable: {}", e),
+            format!(
+                "Run '{}' or restore a snapshot from the state history",
+                project_identity::cli_with("lint --repair-state")
+            ),
+        ),
+    }
+}
+
+fn check_lock_file() -> CheckResult {
+    match state::io::is_lock_stale() {
+        Ok(false) => CheckResult::pass("lock", "No stale lock file"),
+        Ok(true) => CheckResult::warn(
+            "lock",
+            "Stale state lock: the process that took it is gone",
+            format!(
+                "Run '{}' or let the next sync remove it",
+                project_identity::cli_with("unlock")
+            ),
+        ),
+        Err(e) => CheckResult::warn(
+            "lock",
+            format!("Could not inspect lock file: {}", e),
+            "Check permissions of the state directory",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Backend, PackageId};
+
+    fn config_with(packages: &[(&str, &str)]) -> MergedConfig {
+        let mut config = MergedConfig::default();
+        for (backend, name) in packages {
+            config.packages.insert(
+                PackageId {
+                    name: name.to_string(),
+                    backend: Backend::from(*backend),
+                },
+                Vec::new(),
+            );
+        }
+        config
+    }
+
+    #[test]
+    fn missing_definitions_ignore_legacy_default_backend() {
+        let config = config_with(&[("aur", "bat"), ("npm", "typescript"), ("default", "git")]);
+        let configs = HashMap::from([("aur".to_string(), BackendConfig::default())]);
+
+        let missing = missing_backend_definitions(&referenced_backends(&config), &configs);
+
+        assert_eq!(missing, vec!["npm".to_string()]);
+    }
+
+    #[test]
+    fn unknown_experimental_flags_are_reported_sorted() {
+        let mut config = MergedConfig::default();
+        config.experimental.insert("enable-hooks".to_string());
+        config.experimental.insert("zeta".to_string());
+        config.experimental.insert("enable-hook".to_string());
+
+        assert_eq!(
+            unknown_experimental_flags(&config),
+            vec!["enable-hook".to_string(), "zeta".to_string()]
+        );
+    }
+
+    #[test]
+    fn backend_health_grades_missing_binaries_before_failing_lists() {
+        let report = |backend: &str, status| HealthReport {
+            backend: Backend::from(backend),
+            status,
+            package_count: None,
+            detail: None,
+        };
+
+        let graded = grade_backend_health(&[
+            report("aur", HealthStatus::Ok),
+            report("npm", HealthStatus::ParseFailed),
+        ]);
+        assert_eq!(graded.status, CheckStatus::Warn);
+        assert!(graded.message.contains("npm"));
+
+        let graded = grade_backend_health(&[
+            report("npm", HealthStatus::ParseFailed),
+            report("cargo", HealthStatus::BinaryMissing),
+        ]);
+        assert_eq!(graded.message, "Backend binaries not found: cargo");
+
+        assert_eq!(
+            grade_backend_health(&[report("aur", HealthStatus::Ok)]).status,
+            CheckStatus::Pass
+        );
+    }
+
+    #[test]
+    fn worst_status_drives_the_outcome() {
+        let results = vec![
+            CheckResult::pass("config", "ok"),
+            CheckResult::warn("lock", "stale", "delete it"),
+        ];
+        assert_eq!(worst_status(&results), CheckStatus::Warn);
+        assert_eq!(worst_status(&[]), CheckStatus::Pass);
+    }
+}
@@ -0,0 +1,229 @@
+//! Garbage collection command
+//!
+//! Reclaims disk space without running a sync: cleans backend caches and
+//! optionally expires old state history snapshots.
+
+use crate::commands::cache::{CacheOptions, collect_cleanable_backends};
+use crate::error::{DeclarchError, Result};
+use crate::ui as output;
+use crate::utils::paths;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+pub struct GcOptions {
+    /// Target specific backends (None = all backends)
+    pub backends: Option<Vec<String>>,
+    /// Remove state history snapshots older than this many days
+    pub keep_days: Option<u64>,
+    /// List what would be cleaned without cleaning
+    pub dry_run: bool,
+    /// Verbose output
+    pub verbose: bool,
+    /// Clean caches without escalation (`--no-sudo`)
+    pub no_sudo: bool,
+    /// Capture cache clean output, showing it only on failure (`--quiet-backends`)
+    pub quiet_backends: bool,
+}
+
+/// Run cache cleanup and history expiry.
+pub fn run(options: GcOptions) -> Result<()> {
+    let cleanable_backends = collect_cleanable_backends(&CacheOptions {
+        backends: options.backends.clone(),
+        verbose: options.verbose,
+        no_sudo: options.no_sudo,
+        quiet_backends: options.quiet_backends,
+    })?;
+
+    let expired_snapshots = match options.keep_days {
+        Some(days) => {
+            let history_dir = paths::state_history_dir()?;
+            collect_expired_snapshots(&history_dir, days, SystemTime::now())?
+        }
+        None => Vec::new(),
+    };
+
+    if options.dry_run {
+        output::header("Garbage collection (dry-run)");
+        if cleanable_backends.is_empty() {
+            output::info("No backend caches would be cleaned");
+        } else {
+            output::info("Backend caches that would be cleaned:");
+            for (name, _) in &cleanable_backends {
+                output::indent(&format!("• {}", name), 1);
+            }
+        }
+        if let Some(days) = options.keep_days {
+            if expired_snapshots.is_empty() {
+                output::info(&format!("No history snapshots older than {} day(s)", days));
+            } else {
+                output::info(&format!(
+                    "History snapshots older than {} day(s) that would be removed:",
+                    days
+                ));
+                for path in &expired_snapshots {
+                    output::indent(&format!("• {}", path.display()), 1);
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    let mut reclaimed = Vec::new();
+    let mut failed = Vec::new();
+
+    if !cleanable_backends.is_empty() {
+        output::info("Cleaning caches...");
+        output::separator();
+    }
+
+    // One backend failing must not stop the others from being cleaned.
+    for (name, manager) in cleanable_backends {
+        match manager.clean_cache() {
+            Ok(()) => reclaimed.push(name),
+            Err(e) => {
+                if options.verbose {
+                    output::warning(&format!("Failed to clean '{}': {}", name, e));
+                }
+                failed.push((name, e.to_string()));
+            }
+        }
+    }
+
+    let mut removed_snapshots = 0usize;
+    for path in &expired_snapshots {
+        match remove_snapshot(path) {
+            Ok(()) => removed_snapshots += 1,
+            Err(e) => output::warning(&format!("{}", e)),
+        }
+    }
+
+    output::separator();
+    if !reclaimed.is_empty() {
+        output::success(&format!(
+            "Reclaimed cache for {} backend(s): {}",
+            reclaimed.len(),
+            reclaimed.join(", ")
+        ));
+    }
+    if !failed.is_empty() {
+        let names: Vec<&str> = failed.iter().map(|(name, _)| name.as_str()).collect();
+        output::warning(&format!(
+            "Failed {} backend(s): {}{}",
+            failed.len(),
+            names.join(", "),
+            if options.verbose {
+                ""
+            } else {
+                " (use --verbose for details)"
+            }
+        ));
+    }
+    if let Some(days) = options.keep_days {
+        output::info(&format!(
+            "Removed {} history snapshot(s) older than {} day(s)",
+            removed_snapshots, days
+        ));
+    }
+
+    Ok(())
+}
+
+/// List history snapshot files whose modification time is older than `keep_days`.
+fn collect_expired_snapshots(
+    history_dir: &Path,
+    keep_days: u64,
+    now: SystemTime,
+) -> Result<Vec<PathBuf>> {
+    if !history_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(history_dir).map_err(|e| DeclarchError::IoError {
+        path: history_dir.to_path_buf(),
+        source: e,
+    })?;
+
+    let mut snapshots = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        if let Ok(modified) = metadata.modified() {
+            snapshots.push((path, modified));
+        }
+    }
+
+    let mut expired = select_expired(&snapshots, keep_days, now);
+    expired.sort();
+    Ok(expired)
+}
+
+fn select_expired(
+    snapshots: &[(PathBuf, SystemTime)],
+    keep_days: u64,
+    now: SystemTime,
+) -> Vec<PathBuf> {
+    let max_age = Duration::from_secs(keep_days.saturating_mul(24 * 60 * 60));
+    snapshots
+        .iter()
+        .filter(|(_, modified)| {
+            now.duration_since(*modified)
+                .map(|age| age > max_age)
+                .unwrap_or(false)
+        })
+        .map(|(path, _)| path.clone())
+        .collect()
+}
+
+fn remove_snapshot(path: &Path) -> Result<()> {
+    fs::remove_file(path).map_err(|e| DeclarchError::IoError {
+        path: path.to_path_buf(),
+        source: e,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    #[test]
+    fn select_expired_keeps_recent_snapshots() {
+        let now = SystemTime::UNIX_EPOCH + DAY * 100;
+        let snapshots = vec![
+            (PathBuf::from("old.json"), now - DAY * 10),
+            (PathBuf::from("recent.json"), now - DAY * 2),
+            (PathBuf::from("future.json"), now + DAY),
+        ];
+
+        let expired = select_expired(&snapshots, 7, now);
+        assert_eq!(expired, vec![PathBuf::from("old.json")]);
+    }
+
+    #[test]
+    fn collect_expired_snapshots_handles_missing_dir() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let missing = dir.path().join("history");
+        let expired = collect_expired_snapshots(&missing, 7, SystemTime::now())
+            .expect("missing dir should not fail");
+        assert!(expired.is_empty());
+    }
+
+    #[test]
+    fn collect_expired_snapshots_zero_days_expires_existing_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(dir.path().join("snap-1.json"), "{}").expect("write snapshot");
+        fs::create_dir(dir.path().join("nested")).expect("create nested dir");
+
+        let later = SystemTime::now() + Duration::from_secs(60);
+        let expired =
+            collect_expired_snapshots(dir.path(), 0, later).expect("collect should succeed");
+        assert_eq!(expired, vec![dir.path().join("snap-1.json")]);
+    }
+}
@@ -0,0 +1,105 @@
+//! History command
+//!
+//! Lists recent syncs recorded in the state history directory, newest first.
+//! Entries are numbered from 1 (most recent). Read-only.
+
+use crate::error::Result;
+use crate::state::history::{self, HistoryEntry};
+use crate::ui as output;
+use crate::utils::{machine_output, paths};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::PathBuf;
+
+pub struct HistoryOptions {
+    /// Show at most this many entries (0 = all)
+    pub limit: usize,
+    /// List the packages of each entry
+    pub verbose: bool,
+    /// Output format (json, yaml, toml; list otherwise)
+    pub format: Option<String>,
+    pub output_version: Option<String>,
+    /// Write machine output to this file (`--output`) instead of stdout
+    pub output: Option<PathBuf>,
+}
+
+/// Machine-readable view of one entry (without the saved state)
+#[derive(Debug, Serialize)]
+struct HistoryRecord<'a> {
+    number: usize,
+    timestamp: DateTime<Utc>,
+    target: Option<&'a str>,
+    installed: &'a [String],
+    pruned: &'a [String],
+    adopted: &'a [String],
+}
+
+pub fn run(options: HistoryOptions) -> Result<()> {
+    let mut entries = history::load_entries(&paths::state_history_dir()?)?;
+    if options.limit > 0 {
+        entries.truncate(options.limit);
+    }
+
+    if matches!(options.format.as_deref(), Some("json" | "yaml" | "toml")) {
+        return emit_machine(&options, &history_records(&entries));
+    }
+
+    if entries.is_empty() {
+        output::info("No sync history recorded yet");
+        return Ok(());
+    }
+
+    output::header(&format!("Sync History ({})", entries.len()));
+    for (number, entry) in entries.iter().enumerate() {
+        println!("  {:>3}  {}", number + 1, entry.summary());
+        if options.verbose {
+            for (sign, packages) in [
+                ("+", &entry.installed),
+                ("-", &entry.pruned),
+                ("~", &entry.adopted),
+            ] {
+                for pkg in packages {
+                    output::indent(&format!("{} {}", sign, pkg), 3);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn history_records(entries: &[HistoryEntry]) -> Vec<HistoryRecord<'_>> {
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| HistoryRecord {
+            number: i + 1,
+            timestamp: entry.timestamp,
+            target: entry.target.as_deref(),
+            installed: &entry.installed,
+            pruned: &entry.pruned,
+            adopted: &entry.adopted,
+        })
+        .collect()
+}
+
+fn emit_machine(options: &HistoryOptions, records: &[HistoryRecord<'_>]) -> Result<()> {
+    let format = options.format.as_deref().unwrap_or("json");
+    if options.output_version.as_deref() == Some("v1") {
+        return machine_output::emit_v1(
+            "history",
+            records,
+            Vec::new(),
+            Vec::new(),
+            format,
+            options.output.as_deref(),
+        );
+    }
+
+    let out = match format {
+        "yaml" => serde_yml::to_string(&serde_json::to_value(records)?)?,
+        "toml" => machine_output::to_toml_keyed("entries", records)?,
+        _ => serde_json::to_string_pretty(records)?,
+    };
+    machine_output::write_report(&out, options.output.as_deref())
+}
@@ -1,11 +1,108 @@
 mod execution;
 mod presentation;
 
-use crate::config::kdl::{LifecycleAction, LifecycleConfig, LifecyclePhase};
-use crate::error::Result;
+use crate::config::kdl::{ActionCondition, LifecycleAction, LifecycleConfig, LifecyclePhase};
+use crate::config::kdl_modules::helpers::hooks::parse_hook_phase;
+use crate::core::types::Backend;
+use crate::error::{DeclarchError, Result};
+use crate::project_identity;
 use crate::ui as output;
+use crate::utils::platform;
 use execution::execute_single_hook;
-use presentation::{display_hooks, show_disabled_hooks_warning};
+use presentation::{display_dry_run_hooks, display_hooks, show_disabled_hooks_warning};
+use std::collections::HashMap;
+
+/// Number of installs/removals per backend in the current transaction
+pub type BackendChangeCounts = HashMap<Backend, usize>;
+
+/// Env suffix of the version before the change, set for `on-upgrade` hooks
+const OLD_VERSION_ENV_SUFFIX: &str = "OLD_VERSION";
+/// Env suffix of the version after the change, set for `on-upgrade` hooks
+const NEW_VERSION_ENV_SUFFIX: &str = "NEW_VERSION";
+
+/// What a hook's `when` predicate is evaluated against
+struct HookContext<'a> {
+    /// Installs/removals per backend; only post-sync hooks may ask about
+    /// them (config loading rejects `backend:` predicates elsewhere)
+    backend_changes: &'a BackendChangeCounts,
+    os_tag: &'a str,
+    /// Distribution id from `/etc/os-release` (e.g. "arch")
+    distro_id: Option<String>,
+    dry_run: bool,
+}
+
+/// Evaluate a `when` predicate; `None` when it is not understood.
+///
+/// Predicates: `backend:NAME changed`, `os:NAME` (OS tag such as `linux`, or
+/// distribution id such as `arch`) and `dry-run`, each negatable with a
+/// leading `!` or `not `.
+fn evaluate_when(expr: &str, ctx: &HookContext) -> Option<bool> {
+    let expr = expr.trim();
+    if let Some(inner) = expr.strip_prefix('!').or_else(|| expr.strip_prefix("not ")) {
+        return evaluate_when(inner, ctx).map(|holds| !holds);
+    }
+    if expr == "dry-run" {
+        return Some(ctx.dry_run);
+    }
+    if let Some(os) = expr.strip_prefix("os:") {
+        let os = os.trim();
+        return Some(
+            !os.is_empty()
+                && (os.eq_ignore_ascii_case(ctx.os_tag)
+                    || ctx
+                        .distro_id
+                        .as_deref()
+                        .is_some_and(|id| id.eq_ignore_ascii_case(os))),
+        );
+    }
+    if let Some(rest) = expr.strip_prefix("backend:") {
+        let (backend, state) = rest.split_once(char::is_whitespace)?;
+        if state.trim() != "changed" {
+            return None;
+        }
+        return Some(
+            ctx.backend_changes
+                .get(&Backend::from(backend))
+                .is_some_and(|count| *count > 0),
+        );
+    }
+    None
+}
+
+/// Drop hooks whose `when` predicate does not hold.
+///
+/// A predicate that is not understood skips its hook with a warning rather
+/// than running it unconditionally.
+fn hooks_passing_when<'a>(
+    hooks: Vec<&'a LifecycleAction>,
+    backend_changes: &BackendChangeCounts,
+    dry_run: bool,
+) -> Vec<&'a LifecycleAction> {
+    if hooks.iter().all(|hook| hook.when.is_none()) {
+        return hooks;
+    }
+    let ctx = HookContext {
+        backend_changes,
+        os_tag: platform::current_os_tag(),
+        distro_id: platform::os_release_id(),
+        dry_run,
+    };
+    hooks
+        .into_iter()
+        .filter(|hook| {
+            let Some(when) = hook.when.as_deref() else {
+                return true;
+            };
+            evaluate_when(when, &ctx).unwrap_or_else(|| {
+                output::warning(&format!(
+                    "Skipping hook '{}': unknown when predicate '{}'",
+                    hook.command, when
+                ));
+                false
+            })
+        })
+        .collect()
+}
 
 /// Execute hooks for a specific phase
 pub fn execute_hooks_by_phase(
@@ -21,21 +118,50 @@ pub fn execute_hooks_by_phase(
 
     // Filter hooks by phase
     let phase_hooks: Vec<_> = hooks.actions.iter().filter(|h| h.phase == phase).collect();
+    let phase_hooks = hooks_passing_when(phase_hooks, &BackendChangeCounts::new(), dry_run);
 
     if phase_hooks.is_empty() {
         return Ok(());
     }
 
     let phase_name = format!("{:?}", phase);
-    execute_hooks(&phase_hooks, &phase_name, hooks_enabled, dry_run)
+    execute_hooks_with_env(
+        &phase_hooks,
+        &phase_name,
+        &[],
+        hooks.escalation.as_deref(),
+        hooks.no_sudo,
+        hooks_enabled,
+        dry_run,
+    )
 }
 
 /// Execute a list of hooks
+///
+/// In dry-run mode nothing is spawned: each hook that would run is printed
+/// with its phase, command, sudo usage and error behavior instead. Root hooks
+/// are elevated with `DECLARCH_SUDO` or `sudo`; hooks run from a
+/// [`LifecycleConfig`] also honour its policy escalation program.
 pub fn execute_hooks(
     hooks: &[&LifecycleAction],
     phase_name: &str,
     hooks_enabled: bool,
     dry_run: bool,
+) -> Result<()> {
+    execute_hooks_with_env(hooks, phase_name, &[], None, false, hooks_enabled, dry_run)
+}
+
+/// [`execute_hooks`], with `env` added to each hook's environment and root
+/// hooks elevated through `escalation` (`policy { escalation }`) when set,
+/// or not elevated at all with `no_sudo`
+fn execute_hooks_with_env(
+    hooks: &[&LifecycleAction],
+    phase_name: &str,
+    env: &[(&str, &str)],
+    escalation: Option<&str>,
+    no_sudo: bool,
+    hooks_enabled: bool,
+    dry_run: bool,
 ) -> Result<()> {
     if hooks.is_empty() {
         return Ok(());
@@ -43,21 +169,23 @@ pub fn execute_hooks(
 
     // Header logic
     output::separator();
-    if !hooks_enabled && !dry_run {
+    // Disabled hooks would not run on a real sync either, so a dry run
+    // reports them as disabled rather than as "would run"
+    if !hooks_enabled {
         show_disabled_hooks_warning(hooks, phase_name);
         return Ok(());
     }
-
-    // Execution logic
-    display_hooks(hooks, &format!("Executing {} Hooks", phase_name), false);
-
     if dry_run {
+        display_dry_run_hooks(hooks, phase_name);
         output::info("Dry-run: Hooks not executed");
         return Ok(());
     }
 
+    // Execution logic
+    display_hooks(hooks, &format!("Executing {} Hooks", phase_name), false);
+
     for hook in hooks {
-        execute_single_hook(hook)?;
+        execute_single_hook(hook, env, escalation, no_sudo)?;
     }
 
     Ok(())
@@ -73,12 +201,92 @@ pub fn execute_pre_sync(
 }
 
 /// Helper to execute post-sync hooks
+///
+/// Backend-scoped hooks (`aur:post-sync`) are skipped when their backend had
+/// no changes in this transaction, unless they were declared with `--always`.
 pub fn execute_post_sync(
     hooks: &Option<LifecycleConfig>,
+    backend_changes: &BackendChangeCounts,
     hooks_enabled: bool,
     dry_run: bool,
 ) -> Result<()> {
-    execute_hooks_by_phase(hooks, LifecyclePhase::PostSync, hooks_enabled, dry_run)
+    let hooks = match hooks {
+        Some(h) => h,
+        None => return Ok(()),
+    };
+
+    let phase_hooks: Vec<_> = hooks
+        .actions
+        .iter()
+        .filter(|h| h.phase == LifecyclePhase::PostSync)
+        .filter(|h| backend_changed(h, backend_changes))
+        .collect();
+    let phase_hooks = hooks_passing_when(phase_hooks, backend_changes, dry_run);
+
+    execute_hooks_with_env(
+        &phase_hooks,
+        "PostSync",
+        &[],
+        hooks.escalation.as_deref(),
+        hooks.no_sudo,
+        hooks_enabled,
+        dry_run,
+    )
+}
+
+/// Run the hooks of a single sync-level phase on their own (`sync --only-hooks`).
+///
+/// No transaction backs the run, so backend-scoped post-sync hooks are run as
+/// if their backend had changed. Package-level phases need a package and are
+/// rejected.
+pub fn execute_phase_only(
+    hooks: &Option<LifecycleConfig>,
+    phase: &str,
+    hooks_enabled: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let phase = parse_hook_phase(phase)?;
+    match phase {
+        LifecyclePhase::PostSync => {
+            let backend_changes: BackendChangeCounts = hooks
+                .iter()
+                .flat_map(|h| h.actions.iter())
+                .flat_map(|action| action.conditions.iter())
+                .filter_map(|condition| match condition {
+                    ActionCondition::IfChanged(backend) => {
+                        Some((Backend::from(backend.as_str()), 1))
+                    }
+                    _ => None,
+                })
+                .collect();
+            execute_post_sync(hooks, &backend_changes, hooks_enabled, dry_run)
+        }
+        LifecyclePhase::PreSync
+        | LifecyclePhase::OnSuccess
+        | LifecyclePhase::OnFailure
+        | LifecyclePhase::OnUpdate => execute_hooks_by_phase(hooks, phase, hooks_enabled, dry_run),
+        _ => Err(DeclarchError::ConfigError(format!(
+            "--only-hooks runs sync-level phases only: {}",
+            [
+                "pre-sync",
+                "post-sync",
+                "on-success",
+                "on-failure",
+                "on-update"
+            ]
+            .join(", ")
+        ))),
+    }
+}
+
+/// Whether every `IfChanged` backend condition of the hook saw a change.
+fn backend_changed(hook: &LifecycleAction, backend_changes: &BackendChangeCounts) -> bool {
+    hook.conditions.iter().all(|condition| match condition {
+        ActionCondition::IfChanged(backend) => backend_changes
+            .get(&Backend::from(backend.as_str()))
+            .is_some_and(|count| *count > 0),
+        _ => true,
+    })
 }
 
 /// Helper to execute on-success hooks
@@ -112,6 +320,7 @@ fn execute_package_phase(
     hooks: &Option<LifecycleConfig>,
     package_name: &str,
     phase: LifecyclePhase,
+    env: &[(&str, &str)],
     hooks_enabled: bool,
     dry_run: bool,
 ) -> Result<()> {
@@ -127,14 +336,18 @@ fn execute_package_phase(
         .filter(|h| h.phase == phase)
         .filter(|h| h.package.as_deref().is_none() || h.package.as_deref() == Some(package_name))
         .collect();
+    let package_hooks = hooks_passing_when(package_hooks, &BackendChangeCounts::new(), dry_run);
 
     if package_hooks.is_empty() {
         return Ok(());
     }
 
-    execute_hooks(
+    execute_hooks_with_env(
         &package_hooks,
         &format!("{:?} ({})", phase, package_name),
+        env,
+        hooks.escalation.as_deref(),
+        hooks.no_sudo,
         hooks_enabled,
         dry_run,
     )
@@ -151,6 +364,7 @@ pub fn execute_pre_install(
         hooks,
         package_name,
         LifecyclePhase::PreInstall,
+        &[],
         hooks_enabled,
         dry_run,
     )
@@ -167,6 +381,7 @@ pub fn execute_post_install(
         hooks,
         package_name,
         LifecyclePhase::PostInstall,
+        &[],
         hooks_enabled,
         dry_run,
     )
@@ -183,6 +398,7 @@ pub fn execute_pre_remove(
         hooks,
         package_name,
         LifecyclePhase::PreRemove,
+        &[],
         hooks_enabled,
         dry_run,
     )
@@ -199,6 +415,33 @@ pub fn execute_post_remove(
         hooks,
         package_name,
         LifecyclePhase::PostRemove,
+        &[],
+        hooks_enabled,
+        dry_run,
+    )
+}
+
+/// Helper to execute on-upgrade hooks for a package whose version changed
+///
+/// Hooks see the versions as `DECLARCH_OLD_VERSION` and `DECLARCH_NEW_VERSION`.
+pub fn execute_on_upgrade(
+    hooks: &Option<LifecycleConfig>,
+    package_name: &str,
+    old_version: &str,
+    new_version: &str,
+    hooks_enabled: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let old_version_key = project_identity::env_key(OLD_VERSION_ENV_SUFFIX);
+    let new_version_key = project_identity::env_key(NEW_VERSION_ENV_SUFFIX);
+    execute_package_phase(
+        hooks,
+        package_name,
+        LifecyclePhase::OnUpgrade,
+        &[
+            (old_version_key.as_str(), old_version),
+            (new_version_key.as_str(), new_version),
+        ],
         hooks_enabled,
         dry_run,
     )
@@ -206,8 +449,15 @@ pub fn execute_post_remove(
 
 #[cfg(test)]
 mod tests {
-    use super::execute_hooks;
-    use crate::config::kdl::{ActionType, ErrorBehavior, LifecycleAction, LifecyclePhase};
+    use super::{
+        BackendChangeCounts, HookContext, backend_changed, evaluate_when, execute_hooks,
+        execute_on_upgrade, execute_phase_only, hooks_passing_when,
+    };
+    use crate::config::kdl::{
+        ActionCondition, ActionType, ErrorBehavior, LifecycleAction, LifecycleConfig,
+        LifecyclePhase,
+    };
+    use crate::core::types::Backend;
 
     fn hook(command: &str, error_behavior: ErrorBehavior) -> LifecycleAction {
         LifecycleAction {
@@ -217,6 +467,7 @@ mod tests {
             package: None,
             conditions: vec![],
             error_behavior,
+            when: None,
         }
     }
 
@@ -241,4 +492,182 @@ mod tests {
         let res = execute_hooks(&refs, "PreSync", true, false);
         assert!(res.is_err());
     }
+
+    #[test]
+    fn backend_scoped_hook_requires_changes_on_its_backend() {
+        let mut h = hook("mkinitcpio -P", ErrorBehavior::Warn);
+        h.phase = LifecyclePhase::PostSync;
+        h.package = Some("aur".to_string());
+        h.conditions = vec![ActionCondition::IfChanged("aur".to_string())];
+
+        let mut changes = BackendChangeCounts::new();
+        assert!(!backend_changed(&h, &changes));
+
+        changes.insert(Backend::from("flatpak"), 2);
+        changes.insert(Backend::from("aur"), 0);
+        assert!(!backend_changed(&h, &changes));
+
+        changes.insert(Backend::from("aur"), 1);
+        assert!(backend_changed(&h, &changes));
+
+        // `--always` hooks carry no condition and run regardless
+        h.conditions.clear();
+        assert!(backend_changed(&h, &BackendChangeCounts::new()));
+    }
+
+    fn context(backend_changes: &BackendChangeCounts) -> HookContext<'_> {
+        HookContext {
+            backend_changes,
+            os_tag: "linux",
+            distro_id: Some("arch".to_string()),
+            dry_run: false,
+        }
+    }
+
+    #[test]
+    fn when_backend_changed_checks_the_named_backend() {
+        let mut changes = BackendChangeCounts::new();
+        changes.insert(Backend::from("aur"), 2);
+        changes.insert(Backend::from("flatpak"), 0);
+        let ctx = context(&changes);
+
+        assert_eq!(evaluate_when("backend:aur changed", &ctx), Some(true));
+        assert_eq!(evaluate_when("backend:flatpak changed", &ctx), Some(false));
+        assert_eq!(evaluate_when("backend:npm changed", &ctx), Some(false));
+        assert_eq!(evaluate_when("backend:aur removed", &ctx), None);
+    }
+
+    #[test]
+    fn when_os_matches_os_tag_or_distribution() {
+        let changes = BackendChangeCounts::new();
+        let ctx = context(&changes);
+
+        assert_eq!(evaluate_when("os:linux", &ctx), Some(true));
+        assert_eq!(evaluate_when("os:Arch", &ctx), Some(true));
+        assert_eq!(evaluate_when("os:macos", &ctx), Some(false));
+        assert_eq!(evaluate_when("os:", &ctx), Some(false));
+    }
+
+    #[test]
+    fn when_negation_inverts_known_predicates_only() {
+        let mut changes = BackendChangeCounts::new();
+        changes.insert(Backend::from("aur"), 1);
+        let ctx = context(&changes);
+
+        assert_eq!(evaluate_when("!os:macos", &ctx), Some(true));
+        assert_eq!(evaluate_when("not backend:aur changed", &ctx), Some(false));
+        assert_eq!(evaluate_when("!dry-run", &ctx), Some(true));
+        assert_eq!(evaluate_when("!weekday:monday", &ctx), None);
+    }
+
+    #[test]
+    fn unknown_when_predicate_skips_the_hook() {
+        let mut unknown = hook("update-grub", ErrorBehavior::Warn);
+        unknown.when = Some("weekday:monday".to_string());
+        let mut dry_run_only = hook("echo preview", ErrorBehavior::Warn);
+        dry_run_only.when = Some("dry-run".to_string());
+        let plain = hook("notify-send done", ErrorBehavior::Warn);
+
+        let kept = hooks_passing_when(
+            vec![&unknown, &dry_run_only, &plain],
+            &BackendChangeCounts::new(),
+            false,
+        );
+
+        let commands: Vec<_> = kept.iter().map(|h| h.command.as_str()).collect();
+        assert_eq!(commands, vec!["notify-send done"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn execute_hooks_dry_run_never_spawns_commands() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let sentinel = dir.path().join("sentinel");
+        let h = hook(
+            &format!("touch {}", sentinel.display()),
+            ErrorBehavior::Required,
+        );
+        let refs = vec![&h];
+
+        execute_hooks(&refs, "PreSync", true, true).expect("dry-run succeeds");
+        assert!(!sentinel.exists(), "dry-run must not execute hooks");
+
+        // Same hook outside dry-run does run, so the check above is meaningful
+        execute_hooks(&refs, "PreSync", true, false).expect("hook runs");
+        assert!(sentinel.exists());
+    }
+
+    #[test]
+    fn only_hooks_post_sync_runs_only_post_sync_actions() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let marker = |name: &str| dir.path().join(name);
+        let phase_hook = |phase: LifecyclePhase, name: &str| LifecycleAction {
+            phase,
+            ..hook(
+                &format!("touch {}", marker(name).display()),
+                ErrorBehavior::Required,
+            )
+        };
+        let mut scoped = phase_hook(LifecyclePhase::PostSync, "post-sync-aur");
+        scoped.conditions = vec![ActionCondition::IfChanged("aur".to_string())];
+        let hooks = Some(LifecycleConfig {
+            actions: vec![
+                phase_hook(LifecyclePhase::PreSync, "pre-sync"),
+                phase_hook(LifecyclePhase::PostSync, "post-sync"),
+                scoped,
+                phase_hook(LifecyclePhase::OnSuccess, "on-success"),
+            ],
+            ..Default::default()
+        });
+
+        execute_phase_only(&hooks, "post-sync", true, false).expect("post-sync hooks run");
+
+        assert!(marker("post-sync").exists());
+        assert!(marker("post-sync-aur").exists());
+        assert!(!marker("pre-sync").exists());
+        assert!(!marker("on-success").exists());
+    }
+
+    #[test]
+    fn on_upgrade_hooks_run_for_their_package_with_versions_in_env() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let script = dir.path().join("record.sh");
+        std::fs::write(
+            &script,
+            format!(
+                "printf '%s %s' \"${}\" \"${}\" > {}/\"$1\"\n",
+                crate::project_identity::env_key("OLD_VERSION"),
+                crate::project_identity::env_key("NEW_VERSION"),
+                dir.path().display()
+            ),
+        )
+        .expect("write script");
+        let upgrade_hook = |package: &str| LifecycleAction {
+            phase: LifecyclePhase::OnUpgrade,
+            package: Some(package.to_string()),
+            ..hook(
+                &format!("sh {} {}", script.display(), package),
+                ErrorBehavior::Required,
+            )
+        };
+        let hooks = Some(LifecycleConfig {
+            actions: vec![upgrade_hook("linux"), upgrade_hook("bat")],
+            ..Default::default()
+        });
+
+        execute_on_upgrade(&hooks, "linux", "6.9.1", "6.9.2", true, false)
+            .expect("on-upgrade hook runs");
+
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("linux")).expect("hook output"),
+            "6.9.1 6.9.2"
+        );
+        assert!(!dir.path().join("bat").exists());
+    }
+
+    #[test]
+    fn only_hooks_rejects_package_phases() {
+        assert!(execute_phase_only(&None, "post-install", true, false).is_err());
+        assert!(execute_phase_only(&None, "post-sinc", true, false).is_err());
+    }
 }
@@ -13,7 +13,16 @@ const DEFAULT_HOOK_TIMEOUT: Duration = Duration::from_secs(HOOK_TIMEOUT_SECS);
 static SAFE_CHAR_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^[a-zA-Z0-9_\-.\s/:]+$").expect("Valid regex pattern"));
 
-pub(super) fn execute_single_hook(hook: &LifecycleAction) -> Result<()> {
+/// Run one hook, with `env` added to its environment.
+///
+/// Root hooks are elevated through `DECLARCH_SUDO`, then `configured_escalation`,
+/// then `sudo`, unless `no_sudo` is set or we already run as root.
+pub(super) fn execute_single_hook(
+    hook: &LifecycleAction,
+    env: &[(&str, &str)],
+    configured_escalation: Option<&str>,
+    no_sudo: bool,
+) -> Result<()> {
     validate_hook_command(hook)?;
 
     let args = shlex::split(&hook.command).ok_or_else(|| {
@@ -29,16 +38,25 @@ pub(super) fn execute_single_hook(hook: &LifecycleAction) -> Result<()> {
 
     let program = &args[0];
     let program_args = &args[1..];
-    let use_sudo = matches!(hook.action_type, ActionType::Root);
-
-    if use_sudo {
-        output::info(&format!("Executing hook with sudo: {}", program));
+    let use_sudo = matches!(hook.action_type, ActionType::Root)
+        && !no_sudo
+        && !crate::utils::platform::is_root();
+    let escalation =
+        use_sudo.then(|| crate::utils::platform::escalation_program(configured_escalation));
+
+    if let Some(escalation) = &escalation {
+        output::info(&format!("Executing hook with {}: {}", escalation, program));
     } else {
         output::info(&format!("Executing hook: {}", program));
     }
 
-    let mut cmd = crate::utils::platform::build_program_command(program, program_args, use_sudo)?;
-    cmd.stdin(Stdio::inherit())
+    let mut cmd = crate::utils::platform::build_program_command(
+        program,
+        program_args,
+        escalation.as_deref(),
+    )?;
+    cmd.envs(env.iter().copied())
+        .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit());
 
@@ -164,6 +182,7 @@ mod tests {
             package: None,
             conditions: vec![],
             error_behavior: ErrorBehavior::Warn,
+            when: None,
         }
     }
 
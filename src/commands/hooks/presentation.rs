@@ -1,4 +1,4 @@
-use crate::config::kdl::{ActionType, LifecycleAction};
+use crate::config::kdl::{ActionType, ErrorBehavior, LifecycleAction};
 use crate::project_identity;
 use crate::ui as output;
 use crate::utils::sanitize;
@@ -49,6 +49,42 @@ pub(super) fn display_hooks(hooks: &[&LifecycleAction], title: &str, warn_mode:
     }
 }
 
+pub(super) fn display_dry_run_hooks(hooks: &[&LifecycleAction], phase_name: &str) {
+    println!(
+        "\n{}:",
+        format!("{} Hooks (would run)", phase_name).cyan().bold()
+    );
+    for hook in hooks {
+        println!("{}", render_dry_run_hook_line(hook));
+    }
+}
+
+/// Hook line with everything needed to review it before a real run.
+pub(super) fn render_dry_run_hook_line(hook: &LifecycleAction) -> String {
+    let on_error = match hook.error_behavior {
+        ErrorBehavior::Warn => "warn",
+        ErrorBehavior::Required => "required",
+        ErrorBehavior::Ignore => "ignore",
+    };
+    let when = hook
+        .when
+        .as_deref()
+        .map(|when| format!(", when: {}", when))
+        .unwrap_or_default();
+    format!(
+        "{} ({:?}, sudo: {}, on error: {}{})",
+        render_hook_line(hook),
+        hook.phase,
+        if matches!(hook.action_type, ActionType::Root) {
+            "yes"
+        } else {
+            "no"
+        },
+        on_error,
+        when
+    )
+}
+
 pub(super) fn render_hook_line(hook: &LifecycleAction) -> String {
     let sudo_marker = matches!(hook.action_type, ActionType::Root);
     let package_info = if let Some(pkg) = &hook.package {
@@ -67,7 +103,7 @@ pub(super) fn render_hook_line(hook: &LifecycleAction) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::render_hook_line;
+    use super::{render_dry_run_hook_line, render_hook_line};
     use crate::config::kdl::{ActionType, ErrorBehavior, LifecycleAction, LifecyclePhase};
 
     #[test]
@@ -79,6 +115,7 @@ mod tests {
             package: Some("hyprland".to_string()),
             conditions: vec![],
             error_behavior: ErrorBehavior::Warn,
+            when: None,
         };
 
         let line = render_hook_line(&hook);
@@ -96,10 +133,30 @@ mod tests {
             package: None,
             conditions: vec![],
             error_behavior: ErrorBehavior::Warn,
+            when: None,
         };
 
         let line = render_hook_line(&hook);
         assert!(line.contains("→"));
         assert!(line.contains("..."));
     }
+
+    #[test]
+    fn render_dry_run_hook_line_shows_phase_sudo_and_error_behavior() {
+        let hook = LifecycleAction {
+            command: "mkinitcpio -P".to_string(),
+            action_type: ActionType::Root,
+            phase: LifecyclePhase::PostSync,
+            package: None,
+            conditions: vec![],
+            error_behavior: ErrorBehavior::Required,
+            when: None,
+        };
+
+        let line = render_dry_run_hook_line(&hook);
+        assert!(line.contains("mkinitcpio -P"));
+        assert!(line.contains("PostSync"));
+        assert!(line.contains("sudo: yes"));
+        assert!(line.contains("on error: required"));
+    }
 }
@@ -1,16 +1,27 @@
 use crate::error::Result;
 use crate::state;
 use crate::ui as output;
+use std::path::{Path, PathBuf};
 
 mod diagnostics;
 mod output_view;
+mod summary;
+
+pub(crate) use diagnostics::{backend_health_reports, collect_state_signature_duplicates};
 
 pub struct InfoOptions {
     pub doctor: bool,
+    /// Show declared/tracked counts instead of the package listing
+    pub summary: bool,
     pub format: Option<String>,
     pub output_version: Option<String>,
+    /// Write machine output to this file (`--output`) instead of stdout
+    pub output: Option<PathBuf>,
     pub backend: Option<String>,
     pub package: Option<String>,
+    /// Profile and host selectors applied when loading config for `summary`
+    pub profiles: Vec<String>,
+    pub host: Option<String>,
     pub verbose: bool,
 }
 
@@ -18,9 +29,18 @@ pub fn run(options: InfoOptions) -> Result<()> {
     if options.doctor {
         return run_doctor(options.verbose);
     }
+    if options.summary {
+        return summary::run_summary(
+            options.profiles,
+            options.host,
+            options.format.as_deref(),
+            options.output_version.as_deref(),
+            options.output.as_deref(),
+        );
+    }
 
     let result = run_info(&options);
-    if result.is_ok() && !matches!(options.format.as_deref(), Some("json" | "yaml")) {
+    if result.is_ok() && !matches!(options.format.as_deref(), Some("json" | "yaml" | "toml")) {
         maybe_print_update_notification();
     }
     result
@@ -58,7 +78,7 @@ fn run_info(options: &InfoOptions) -> Result<()> {
         };
 
     let format_str = options.format.as_deref().unwrap_or("table");
-    if options.verbose && !matches!(format_str, "json" | "yaml") {
+    if options.verbose && !matches!(format_str, "json" | "yaml" | "toml") {
         output::header("Info Context");
         output::keyval(
             "Backend filter",
@@ -75,8 +95,21 @@ fn run_info(options: &InfoOptions) -> Result<()> {
     }
 
     match format_str {
-        "json" => output_json_filtered(&filtered_packages, options.output_version.as_deref()),
-        "yaml" => output_yaml_filtered(&filtered_packages, options.output_version.as_deref()),
+        "json" => output_json_filtered(
+            &filtered_packages,
+            options.output_version.as_deref(),
+            options.output.as_deref(),
+        ),
+        "yaml" => output_yaml_filtered(
+            &filtered_packages,
+            options.output_version.as_deref(),
+            options.output.as_deref(),
+        ),
+        "toml" => output_toml_filtered(
+            &filtered_packages,
+            options.output_version.as_deref(),
+            options.output.as_deref(),
+        ),
         _ => output_table_filtered(&state, &filtered_packages),
     }
 }
@@ -91,15 +124,25 @@ fn output_table_filtered(
 fn output_json_filtered(
     filtered_packages: &[(&String, &state::types::PackageState)],
     output_version: Option<&str>,
+    output_file: Option<&Path>,
 ) -> Result<()> {
-    output_view::output_json_filtered(filtered_packages, output_version)
+    output_view::output_json_filtered(filtered_packages, output_version, output_file)
 }
 
 fn output_yaml_filtered(
     filtered_packages: &[(&String, &state::types::PackageState)],
     output_version: Option<&str>,
+    output_file: Option<&Path>,
+) -> Result<()> {
+    output_view::output_yaml_filtered(filtered_packages, output_version, output_file)
+}
+
+fn output_toml_filtered(
+    filtered_packages: &[(&String, &state::types::PackageState)],
+    output_version: Option<&str>,
+    output_file: Option<&Path>,
 ) -> Result<()> {
-    output_view::output_yaml_filtered(filtered_packages, output_version)
+    output_view::output_toml_filtered(filtered_packages, output_version, output_file)
 }
 
 fn extract_package_name(key: &str) -> &str {
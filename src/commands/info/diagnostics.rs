@@ -5,7 +5,9 @@ use crate::state;
 use crate::ui as output;
 use crate::utils::paths;
 use checks::check_backends_dynamically;
-pub(super) use checks::collect_state_signature_duplicates;
+#[cfg(test)]
+pub(super) use checks::{HEALTH_TABLE_HEADERS, health_table_rows};
+pub(crate) use checks::{backend_health_reports, collect_state_signature_duplicates};
 pub(super) use update_notice::maybe_print_update_notification;
 
 mod checks;
@@ -44,7 +46,7 @@ pub(super) fn run_doctor(verbose: bool) -> Result<()> {
 
     output::info("Checking configuration file...");
     let config_path = paths::config_file()?;
-    if config_path.exists() {
+    if loader::root_config_available(&config_path) {
         if verbose {
             output::success(&format!("Config found: {}", config_path.display()));
         } else {
@@ -92,7 +94,7 @@ pub(super) fn run_doctor(verbose: bool) -> Result<()> {
                     state.packages.len()
                 ));
 
-                if config_path.exists()
+                if loader::root_config_available(&config_path)
                     && let Ok(config) = loader::load_root_config(&config_path)
                 {
                     use crate::core::types::PackageId;
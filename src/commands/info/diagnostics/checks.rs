@@ -1,45 +1,43 @@
+use crate::backends::config::BackendConfig;
 use crate::commands::runtime_overrides::{
     apply_runtime_backend_overrides, load_runtime_config_for_command,
 };
 use crate::error::Result;
-use crate::packages::traits::PackageManager;
+use crate::packages::traits::{HealthReport, HealthStatus, PackageManager};
 use crate::project_identity;
 use crate::ui as output;
 
 pub(super) fn check_backends_dynamically(verbose: bool) -> Result<Vec<String>> {
-    let mut available = Vec::new();
-    let runtime_config = load_runtime_config_for_command("doctor backend checks");
-
-    match crate::backends::load_all_backends_unified() {
-        Ok(backends) => {
-            for (name, mut config) in backends {
-                apply_runtime_backend_overrides(&mut config, &name, &runtime_config);
-
-                if !crate::utils::platform::backend_supports_current_os(&config) {
-                    if verbose {
-                        output::info(&format!("{}: Skipped (not for this OS)", name));
-                    }
-                    continue;
-                }
-
-                let manager = crate::backends::GenericManager::from_config(
-                    config,
-                    crate::core::types::Backend::from(name.as_str()),
-                    false,
-                );
-
-                if manager.is_available() {
-                    if verbose {
-                        output::success(&format!("{}: Available", name));
-                    }
-                    available.push(name);
-                } else {
-                    output::warning(&format!("{}: Backend binary not found", name));
-                }
-            }
-        }
+    let reports = match crate::backends::load_all_backends_unified() {
+        Ok(backends) => backend_health_reports(backends, verbose),
         Err(e) => {
             output::warning(&format!("Could not load backend configs: {}", e));
+            Vec::new()
+        }
+    };
+    let available: Vec<String> = reports
+        .iter()
+        .filter(|report| report.status != HealthStatus::BinaryMissing)
+        .map(|report| report.backend.name().to_string())
+        .collect();
+
+    if !reports.is_empty() {
+        output::info("Backend health:");
+        output::table(&HEALTH_TABLE_HEADERS, &health_table_rows(&reports, verbose));
+        let failing = reports
+            .iter()
+            .filter(|r| {
+                matches!(
+                    r.status,
+                    HealthStatus::CommandFailed | HealthStatus::ParseFailed
+                )
+            })
+            .count();
+        if failing > 0 {
+            output::warning(&format!(
+                "{} backend(s) failed to list installed packages; check their list command and format",
+                failing
+            ));
         }
     }
 
@@ -54,6 +52,63 @@ pub(super) fn check_backends_dynamically(verbose: bool) -> Result<Vec<String>> {
     Ok(available)
 }
 
+/// Probe each backend that supports this OS, with runtime overrides applied.
+pub(crate) fn backend_health_reports(
+    backends: impl IntoIterator<Item = (String, BackendConfig)>,
+    verbose: bool,
+) -> Vec<HealthReport> {
+    let runtime_config = load_runtime_config_for_command("doctor backend checks");
+    let mut reports = Vec::new();
+    for (name, mut config) in backends {
+        apply_runtime_backend_overrides(&mut config, &name, &runtime_config);
+
+        if !crate::utils::platform::backend_supports_current_os(&config) {
+            if verbose {
+                output::info(&format!("{}: Skipped (not for this OS)", name));
+            }
+            continue;
+        }
+
+        let manager = crate::backends::GenericManager::from_config(
+            config,
+            crate::core::types::Backend::from(name.as_str()),
+            false,
+        );
+        reports.push(manager.health_check());
+    }
+    reports
+}
+
+/// `backend / status / packages / detail` rows for health reports.
+///
+/// Details are only shown for problems unless `verbose` is set.
+pub(crate) fn health_table_rows(reports: &[HealthReport], verbose: bool) -> Vec<Vec<String>> {
+    reports
+        .iter()
+        .map(|report| {
+            let count = report
+                .package_count
+                .map(|count| count.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            let detail = match &report.detail {
+                Some(detail) if verbose || report.status != HealthStatus::Ok => {
+                    detail.lines().next().unwrap_or_default()
+                }
+                _ => "",
+            };
+            vec![
+                report.backend.name().to_string(),
+                report.status.label().to_string(),
+                count,
+                detail.to_string(),
+            ]
+        })
+        .collect()
+}
+
+/// Column headers for [`health_table_rows`]
+pub(crate) const HEALTH_TABLE_HEADERS: [&str; 4] = ["BACKEND", "STATUS", "PACKAGES", "DETAIL"];
+
 pub(crate) fn collect_state_signature_duplicates(
     state: &crate::state::types::State,
 ) -> Vec<(String, Vec<String>)> {
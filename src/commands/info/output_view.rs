@@ -4,6 +4,7 @@ use crate::ui as output;
 use crate::utils::machine_output;
 use colored::Colorize;
 use std::collections::HashMap;
+use std::path::Path;
 use terminal_size::{Width, terminal_size};
 
 /// Indentation width used for package display formatting
@@ -57,34 +58,73 @@ pub(super) fn output_table_filtered(
 pub(super) fn output_json_filtered(
     filtered_packages: &[(&String, &state::types::PackageState)],
     output_version: Option<&str>,
+    output_file: Option<&Path>,
 ) -> Result<()> {
     let packages: Vec<&state::types::PackageState> =
         filtered_packages.iter().map(|(_, pkg)| *pkg).collect();
 
     if output_version == Some("v1") {
-        return machine_output::emit_v1("info", &packages, Vec::new(), Vec::new(), "json");
+        return machine_output::emit_v1(
+            "info",
+            &packages,
+            Vec::new(),
+            Vec::new(),
+            "json",
+            output_file,
+        );
     }
 
     let json = serde_json::to_string_pretty(&packages)?;
-    println!("{}", json);
-    Ok(())
+    machine_output::write_report(&json, output_file)
 }
 
 pub(super) fn output_yaml_filtered(
     filtered_packages: &[(&String, &state::types::PackageState)],
     output_version: Option<&str>,
+    output_file: Option<&Path>,
 ) -> Result<()> {
     let packages: Vec<&state::types::PackageState> =
         filtered_packages.iter().map(|(_, pkg)| *pkg).collect();
 
     if output_version == Some("v1") {
-        return machine_output::emit_v1("info", &packages, Vec::new(), Vec::new(), "yaml");
+        return machine_output::emit_v1(
+            "info",
+            &packages,
+            Vec::new(),
+            Vec::new(),
+            "yaml",
+            output_file,
+        );
     }
 
     let json_value = serde_json::to_value(&packages)?;
     let yaml = serde_yml::to_string(&json_value)?;
-    println!("{}", yaml);
-    Ok(())
+    machine_output::write_report(&yaml, output_file)
+}
+
+pub(super) fn output_toml_filtered(
+    filtered_packages: &[(&String, &state::types::PackageState)],
+    output_version: Option<&str>,
+    output_file: Option<&Path>,
+) -> Result<()> {
+    let packages: Vec<&state::types::PackageState> =
+        filtered_packages.iter().map(|(_, pkg)| *pkg).collect();
+
+    if output_version == Some("v1") {
+        return machine_output::emit_v1(
+            "info",
+            &packages,
+            Vec::new(),
+            Vec::new(),
+            "toml",
+            output_file,
+        );
+    }
+
+    machine_output::write_report(
+        &machine_output::to_toml_keyed("packages", &packages)?,
+        output_file,
+    )
 }
 
 pub(super) fn count_backends_filtered(
@@ -0,0 +1,133 @@
+//! Config summary (`info --summary`)
+//!
+//! Counts what the merged config declares and what state tracks, so a user
+//! can check the config loaded as expected. Only config and state are read;
+//! no backend is queried.
+
+use crate::config::loader::{self, LoadSelectors, MergedConfig};
+use crate::error::{DeclarchError, Result};
+use crate::state::{self, types::State};
+use crate::ui as output;
+use crate::utils::{machine_output, paths};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub(super) struct ConfigSummary {
+    pub declared_total: usize,
+    pub declared_by_backend: BTreeMap<String, usize>,
+    pub tracked_total: usize,
+    pub tracked_by_backend: BTreeMap<String, usize>,
+    /// Config files imported by the root config, directly or transitively
+    pub modules_imported: usize,
+    pub profiles: Vec<String>,
+    pub host: Option<String>,
+}
+
+pub(super) fn run_summary(
+    profiles: Vec<String>,
+    host: Option<String>,
+    format: Option<&str>,
+    output_version: Option<&str>,
+    output_file: Option<&Path>,
+) -> Result<()> {
+    let config_path = paths::config_file()?;
+    if !loader::root_config_available(&config_path) {
+        return Err(DeclarchError::ConfigNotFound { path: config_path });
+    }
+
+    let selectors = LoadSelectors { profiles, host };
+    let config = loader::load_root_config_with_selectors(&config_path, &selectors)?;
+    let state = state::io::load_state()?;
+    let summary = build_summary(&config, &state, selectors);
+
+    match format {
+        Some(format @ ("json" | "yaml" | "toml")) if output_version == Some("v1") => {
+            machine_output::emit_v1(
+                "info",
+                &summary,
+                Vec::new(),
+                Vec::new(),
+                format,
+                output_file,
+            )
+        }
+        Some("json") => {
+            machine_output::write_report(&serde_json::to_string_pretty(&summary)?, output_file)
+        }
+        Some("yaml") => machine_output::write_report(
+            &serde_yml::to_string(&serde_json::to_value(&summary)?)?,
+            output_file,
+        ),
+        Some("toml") => machine_output::write_report(
+            &machine_output::to_toml_keyed("summary", &summary)?,
+            output_file,
+        ),
+        _ => {
+            render_summary(&summary);
+            Ok(())
+        }
+    }
+}
+
+pub(super) fn build_summary(
+    config: &MergedConfig,
+    state: &State,
+    selectors: LoadSelectors,
+) -> ConfigSummary {
+    let mut declared_by_backend = BTreeMap::new();
+    for pkg in config.packages.keys() {
+        *declared_by_backend
+            .entry(pkg.backend.name().to_string())
+            .or_insert(0) += 1;
+    }
+
+    let mut tracked_by_backend = BTreeMap::new();
+    for pkg in state.packages.values() {
+        *tracked_by_backend
+            .entry(pkg.backend.name().to_string())
+            .or_insert(0) += 1;
+    }
+
+    ConfigSummary {
+        declared_total: config.packages.len(),
+        declared_by_backend,
+        tracked_total: state.packages.len(),
+        tracked_by_backend,
+        modules_imported: config.config_files.len().saturating_sub(1),
+        profiles: selectors.profiles,
+        host: selectors.host,
+    }
+}
+
+fn render_summary(summary: &ConfigSummary) {
+    output::header("Config Summary");
+    if summary.profiles.is_empty() {
+        output::keyval("Profile", "(none)");
+    } else {
+        output::keyval("Profile", &summary.profiles.join(", "));
+    }
+    output::keyval("Host", summary.host.as_deref().unwrap_or("(none)"));
+    output::keyval("Modules imported", &summary.modules_imported.to_string());
+
+    println!();
+    output::tag("Declared", &summary.declared_total.to_string());
+    for (backend, count) in &summary.declared_by_backend {
+        let tracked = summary.tracked_by_backend.get(backend).unwrap_or(&0);
+        output::indent(
+            &format!("• {}: {} ({} tracked)", backend, count, tracked),
+            2,
+        );
+    }
+
+    output::tag("Tracked in state", &summary.tracked_total.to_string());
+    let untracked_backends: Vec<_> = summary
+        .tracked_by_backend
+        .iter()
+        .filter(|(backend, _)| !summary.declared_by_backend.contains_key(*backend))
+        .collect();
+    for (backend, count) in untracked_backends {
+        output::indent(&format!("• {}: {} (none declared)", backend, count), 2);
+    }
+}
@@ -17,6 +17,7 @@ fn pkg(backend: &str, config_name: &str) -> PackageState {
         version: None,
         install_reason: None,
         source_module: None,
+        pinned: false,
         last_seen_at: None,
         backend_meta: None,
     }
@@ -54,3 +55,88 @@ fn state_signature_duplicates_empty_when_clean() {
     let duplicates = diagnostics::collect_state_signature_duplicates(&state);
     assert!(duplicates.is_empty());
 }
+
+#[test]
+fn health_table_rows_hide_ok_details_unless_verbose() {
+    use crate::packages::traits::{HealthReport, HealthStatus};
+
+    let reports = vec![
+        HealthReport {
+            backend: Backend::from("flatpak"),
+            status: HealthStatus::Ok,
+            package_count: Some(12),
+            detail: Some("install-only".to_string()),
+        },
+        HealthReport {
+            backend: Backend::from("npm"),
+            status: HealthStatus::ParseFailed,
+            package_count: None,
+            detail: Some("Failed to parse JSON\nat line 1".to_string()),
+        },
+    ];
+
+    let rows = diagnostics::health_table_rows(&reports, false);
+    let lines = crate::ui::format_table(&diagnostics::HEALTH_TABLE_HEADERS, &rows);
+    assert_eq!(
+        lines,
+        vec![
+            "BACKEND  STATUS        PACKAGES  DETAIL",
+            "flatpak  ok            12",
+            "npm      parse-failed  -         Failed to parse JSON",
+        ]
+    );
+
+    let verbose = diagnostics::health_table_rows(&reports, true);
+    assert_eq!(verbose[0][3], "install-only");
+}
+
+#[test]
+fn summary_counts_declared_and_tracked_packages_per_backend() {
+    use super::summary::build_summary;
+    use crate::config::loader::{LoadSelectors, MergedConfig};
+    use crate::core::types::PackageId;
+    use std::path::PathBuf;
+
+    let mut config = MergedConfig::default();
+    for (backend, name) in [("aur", "bat"), ("aur", "fd"), ("npm", "typescript")] {
+        config.packages.insert(
+            PackageId {
+                name: name.to_string(),
+                backend: Backend::from(backend),
+            },
+            Vec::new(),
+        );
+    }
+    config.config_files = vec![
+        PathBuf::from("declarch.kdl"),
+        PathBuf::from("modules/base.kdl"),
+        PathBuf::from("modules/dev.kdl"),
+    ];
+
+    let mut state = sample_state();
+    state
+        .packages
+        .insert("aur:bat".to_string(), pkg("aur", "bat"));
+    state.packages.insert(
+        "flatpak:org.gimp.GIMP".to_string(),
+        pkg("flatpak", "org.gimp.GIMP"),
+    );
+
+    let summary = build_summary(
+        &config,
+        &state,
+        LoadSelectors {
+            profiles: vec!["desktop".to_string()],
+            host: None,
+        },
+    );
+
+    assert_eq!(summary.declared_total, 3);
+    assert_eq!(summary.declared_by_backend.get("aur"), Some(&2));
+    assert_eq!(summary.declared_by_backend.get("npm"), Some(&1));
+    assert_eq!(summary.tracked_total, 2);
+    assert_eq!(summary.tracked_by_backend.get("flatpak"), Some(&1));
+    assert_eq!(summary.modules_imported, 2);
+    assert_eq!(summary.profiles, vec!["desktop"]);
+    assert_eq!(summary.host, None);
+}
@@ -24,7 +24,7 @@ use targets::{explain_backend, explain_module, explain_target};
 pub struct InfoReasonOptions {
     pub query: Option<String>,
     pub target: Option<String>,
-    pub profile: Option<String>,
+    pub profiles: Vec<String>,
     pub host: Option<String>,
     pub modules: Vec<String>,
     pub verbose: bool,
@@ -38,12 +38,12 @@ pub fn run(options: InfoReasonOptions) -> Result<()> {
     }
 
     let config_path = paths::config_file()?;
-    if !config_path.exists() {
+    if !loader::root_config_available(&config_path) {
         return Err(DeclarchError::ConfigNotFound { path: config_path });
     }
 
     let selectors = LoadSelectors {
-        profile: options.profile.clone(),
+        profiles: options.profiles.clone(),
         host: options.host.clone(),
     };
 
@@ -118,10 +118,23 @@ fn explain_query(query: &str, config: &MergedConfig) -> Result<()> {
             if let Some(version) = &pkg_state.version {
                 output::keyval("Version", version);
             }
+            if let Some(remote) = pkg_state.remote() {
+                output::keyval("Remote", remote);
+            }
+            if pkg_state.pinned {
+                output::keyval("Pinned", "yes (kept by prune)");
+            }
         } else {
             output::keyval("Installed", "no");
         }
 
+        if let Some(version) = config.package_versions.get(&pkg) {
+            output::keyval("Declared version", version);
+        }
+        if let Some(reason) = config.package_reasons.get(&pkg) {
+            output::keyval("Reason", reason);
+        }
+
         if let Some(sources) = config.packages.get(&pkg) {
             let mut srcs: BTreeSet<String> = BTreeSet::new();
             for src in sources {
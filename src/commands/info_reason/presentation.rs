@@ -6,10 +6,11 @@ use std::path::Path;
 pub(super) fn show_active_context(options: &InfoReasonOptions, config: &MergedConfig) {
     output::header("Active Context");
 
-    output::keyval(
-        "Profile",
-        options.profile.as_deref().unwrap_or("(default only)"),
-    );
+    if options.profiles.is_empty() {
+        output::keyval("Profile", "(default only)");
+    } else {
+        output::keyval("Profile", &options.profiles.join(", "));
+    }
     output::keyval("Host", options.host.as_deref().unwrap_or("(default only)"));
 
     if options.modules.is_empty() {
@@ -15,20 +15,34 @@ pub(super) fn explain_sync_plan(config: &MergedConfig) -> Result<()> {
     let config_set: HashSet<PackageId> = config.packages.keys().cloned().collect();
     let mut state_set: HashSet<PackageId> = HashSet::new();
 
+    let mut pinned_set: HashSet<PackageId> = HashSet::new();
+
     for pkg_state in state.packages.values() {
-        state_set.insert(PackageId {
+        let pkg = PackageId {
             backend: pkg_state.backend.clone(),
             name: pkg_state.config_name.clone(),
-        });
+        };
+        if pkg_state.pinned {
+            pinned_set.insert(pkg.clone());
+        }
+        state_set.insert(pkg);
     }
 
     let to_install: Vec<_> = config_set.difference(&state_set).cloned().collect();
-    let to_remove: Vec<_> = state_set.difference(&config_set).cloned().collect();
+    // Pinned entries are never pruned, so they are not remove drift
+    let to_remove: Vec<_> = state_set
+        .difference(&config_set)
+        .filter(|pkg| !pinned_set.contains(pkg))
+        .cloned()
+        .collect();
 
     output::keyval("Declared packages", &config.packages.len().to_string());
     output::keyval("State packages", &state.packages.len().to_string());
     output::keyval("Planned install", &to_install.len().to_string());
     output::keyval("Planned remove", &to_remove.len().to_string());
+    if !pinned_set.is_empty() {
+        output::keyval("Pinned", &pinned_set.len().to_string());
+    }
 
     let duplicates = config.get_duplicates();
     if !duplicates.is_empty() {
@@ -5,6 +5,9 @@
 //! 2. Validate and sanitize backend name
 //! 3. Write backend file to `backends/<name>.kdl`
 //! 4. Add import to `declarch.kdl` in `backends { ... }`
+//!
+//! `declarch init --backend ./path/to/backend.kdl` adopts a local backend
+//! file instead of fetching, without any network access.
 
 use crate::error::{DeclarchError, Result};
 use crate::project_identity;
@@ -12,6 +15,7 @@ use crate::ui as output;
 use crate::utils::{paths, remote};
 use presentation::print_backend_meta;
 use std::fs;
+use std::path::Path;
 
 mod meta_parser;
 mod presentation;
@@ -19,6 +23,9 @@ mod root_import;
 pub use root_import::add_backend_to_declarch;
 
 /// Initialize a new backend configuration file
+///
+/// `backend_name` is either a registry backend name or a path to a local
+/// backend `.kdl` file.
 pub fn init_backend(backend_name: &str, force: bool) -> Result<()> {
     let root_dir = paths::config_dir()?;
     if output::is_verbose() {
@@ -28,40 +35,25 @@ pub fn init_backend(backend_name: &str, force: bool) -> Result<()> {
     // Ensure declarch environment exists (auto-init if needed)
     super::root::ensure_environment()?;
 
-    // Sanitize backend name
-    let sanitized_name: String = backend_name
-        .chars()
-        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
-        .collect::<String>()
-        .to_lowercase();
-
-    if sanitized_name.is_empty() {
-        return Err(DeclarchError::Other(
-            "Invalid backend name. Use alphanumeric characters, hyphens, or underscores."
-                .to_string(),
-        ));
+    if is_local_backend_source(backend_name) {
+        let source = paths::expand_home(Path::new(backend_name))?;
+        return adopt_local_backend_file(&root_dir, &source, force);
     }
 
-    // Create backends directory
-    let backends_dir = root_dir.join("backends");
-    if !backends_dir.exists() {
-        fs::create_dir_all(&backends_dir)?;
-        if output::is_verbose() {
-            output::verbose(&format!(
-                "Created backend directory: {}",
-                backends_dir.display()
-            ));
-        }
-    }
+    let sanitized_name = sanitize_backend_name(backend_name)?;
+    remote::ensure_online(&format!("Fetching backend '{}'", sanitized_name))?;
 
+    let configured_registries = configured_registries();
+    let registries = remote::backend_registries(&configured_registries)?;
+    let registry_names: Vec<&str> = registries.iter().map(|r| r.name.as_str()).collect();
     println!(
         "fetching '{}' from {}",
         sanitized_name,
-        project_identity::REGISTRY_SLUG
+        registry_names.join(", ")
     );
 
-    let backend_content = match remote::fetch_backend_content(&sanitized_name) {
-        Ok(content) => content,
+    let fetched = match remote::fetch_backend_content(&sanitized_name, &configured_registries) {
+        Ok(fetched) => fetched,
         Err(e) => {
             if output::is_verbose() {
                 output::verbose(&format!("Backend fetch error detail: {}", e));
@@ -74,6 +66,8 @@ pub fn init_backend(backend_name: &str, force: bool) -> Result<()> {
             )));
         }
     };
+    println!("found '{}' in {}", sanitized_name, fetched.registry);
+    let backend_content = fetched.content;
 
     // Validate KDL (warning only, can bypass with --force)
     if let Err(e) = super::validate_kdl(&backend_content, &format!("backend '{}'", sanitized_name))
@@ -92,8 +86,119 @@ pub fn init_backend(backend_name: &str, force: bool) -> Result<()> {
         }
     }
 
+    adopt_backend_content(&root_dir, &sanitized_name, &backend_content, force)
+}
+
+/// Backend registries from the root config's `policy { registries ... }`.
+///
+/// A root config that fails to load only costs the extra registries; the
+/// default registry is still tried.
+pub(super) fn configured_registries() -> Vec<String> {
+    let Ok(path) = paths::config_file() else {
+        return Vec::new();
+    };
+    if !path.exists() {
+        return Vec::new();
+    }
+    match crate::config::loader::load_root_config(&path) {
+        Ok(config) => config.policy.map(|p| p.registries).unwrap_or_default(),
+        Err(e) => {
+            if output::is_verbose() {
+                output::verbose(&format!("Ignoring policy registries: {}", e));
+            }
+            Vec::new()
+        }
+    }
+}
+
+/// Adopt a backend definition from a local `.kdl` file.
+///
+/// The file is parsed and validated, then copied into `backends/<name>.kdl`
+/// (named after the first `backend` node) and imported in the root config.
+pub fn adopt_local_backend_file(root_dir: &Path, source: &Path, force: bool) -> Result<()> {
+    if !source.is_file() {
+        return Err(DeclarchError::Other(format!(
+            "Backend file not found: {}",
+            source.display()
+        )));
+    }
+
+    let backend_content = fs::read_to_string(source).map_err(|e| DeclarchError::IoError {
+        path: source.to_path_buf(),
+        source: e,
+    })?;
+
+    let backends = crate::backends::user_parser::load_user_backends(source).map_err(|e| {
+        DeclarchError::ConfigError(format!(
+            "Invalid backend file '{}': {}",
+            source.display(),
+            e
+        ))
+    })?;
+    let first = backends.first().ok_or_else(|| {
+        DeclarchError::ConfigError(format!(
+            "No backend definition found in '{}'. Expected: backend \"name\" {{ ... }}",
+            source.display()
+        ))
+    })?;
+    let sanitized_name = sanitize_backend_name(&first.name)?;
+
+    println!(
+        "adopting '{}' from local file {}",
+        sanitized_name,
+        source.display()
+    );
+
+    adopt_backend_content(root_dir, &sanitized_name, &backend_content, force)
+}
+
+/// Returns true when a `--backend` value refers to a local file instead of a registry name.
+fn is_local_backend_source(value: &str) -> bool {
+    value.ends_with(".kdl")
+        || value.starts_with('.')
+        || value.starts_with('~')
+        || value.contains('/')
+        || value.contains(std::path::MAIN_SEPARATOR)
+}
+
+fn sanitize_backend_name(backend_name: &str) -> Result<String> {
+    let sanitized_name: String = backend_name
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+        .collect::<String>()
+        .to_lowercase();
+
+    if sanitized_name.is_empty() {
+        return Err(DeclarchError::Other(
+            "Invalid backend name. Use alphanumeric characters, hyphens, or underscores."
+                .to_string(),
+        ));
+    }
+
+    Ok(sanitized_name)
+}
+
+/// Write backend content into `backends/<name>.kdl` and import it in the root config.
+fn adopt_backend_content(
+    root_dir: &Path,
+    sanitized_name: &str,
+    backend_content: &str,
+    force: bool,
+) -> Result<()> {
+    // Create backends directory
+    let backends_dir = root_dir.join("backends");
+    if !backends_dir.exists() {
+        fs::create_dir_all(&backends_dir)?;
+        if output::is_verbose() {
+            output::verbose(&format!(
+                "Created backend directory: {}",
+                backends_dir.display()
+            ));
+        }
+    }
+
     // Parse and display meta information
-    if let Ok(meta) = extract_backend_meta(&backend_content) {
+    if let Ok(meta) = extract_backend_meta(backend_content) {
         print_backend_meta(&meta);
     }
 
@@ -118,14 +223,14 @@ pub fn init_backend(backend_name: &str, force: bool) -> Result<()> {
         return Ok(());
     }
 
-    fs::write(&backend_file, &backend_content)?;
+    fs::write(&backend_file, backend_content)?;
     if output::is_verbose() {
         output::verbose(&format!("Backend file written: {}", backend_file.display()));
     }
 
     // Always import directly to root config file.
     let root_config_path = root_dir.join(project_identity::CONFIG_FILE_BASENAME);
-    match add_backend_to_declarch(&root_config_path, &sanitized_name) {
+    match add_backend_to_declarch(&root_config_path, sanitized_name) {
         Ok(true) => {
             println!("Backend '{}' adopted.", sanitized_name);
             if output::is_verbose() {
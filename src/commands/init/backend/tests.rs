@@ -76,3 +76,54 @@ backend "nala" {
     );
     assert_eq!(meta.requires, vec!["apt".to_string(), "nala".to_string()]);
 }
+
+#[test]
+fn test_is_local_backend_source() {
+    assert!(is_local_backend_source("./my-backend.kdl"));
+    assert!(is_local_backend_source("my-backend.kdl"));
+    assert!(is_local_backend_source("~/backends/custom.kdl"));
+    assert!(is_local_backend_source("/tmp/custom"));
+    assert!(!is_local_backend_source("flatpak"));
+    assert!(!is_local_backend_source("aur_helper"));
+}
+
+#[test]
+fn test_adopt_local_backend_file_copies_and_imports() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let root_config = root.path().join(project_identity::CONFIG_FILE_BASENAME);
+    fs::write(&root_config, "backends {\n}\n").expect("write root config");
+
+    let source_dir = tempfile::tempdir().expect("source tempdir");
+    let source = source_dir.path().join("custom.kdl");
+    let backend_kdl = r#"backend "My-PM" {
+    binary "mypm"
+    install "{binary} install {packages}"
+}
+"#;
+    fs::write(&source, backend_kdl).expect("write backend file");
+
+    adopt_local_backend_file(root.path(), &source, true).expect("adopt local backend");
+
+    let adopted = root.path().join("backends").join("my-pm.kdl");
+    assert_eq!(
+        fs::read_to_string(&adopted).expect("read adopted backend"),
+        backend_kdl
+    );
+    let root_content = fs::read_to_string(&root_config).expect("read root config");
+    assert!(root_content.contains(r#""backends/my-pm.kdl""#));
+}
+
+#[test]
+fn test_adopt_local_backend_file_rejects_invalid_definition() {
+    let root = tempfile::tempdir().expect("tempdir");
+    let source = root.path().join("broken.kdl");
+    fs::write(&source, "meta { title \"no backend here\" }\n").expect("write file");
+
+    let err = adopt_local_backend_file(root.path(), &source, true)
+        .expect_err("file without backend node should fail");
+    assert!(err.to_string().contains("No backend definition"));
+    assert!(!root.path().join("backends").exists());
+
+    let missing = root.path().join("missing.kdl");
+    assert!(adopt_local_backend_file(root.path(), &missing, true).is_err());
+}
@@ -1,12 +1,18 @@
 //! List available backends and modules from the registry
 //!
-//! Provides static lists of available backends and modules.
-//! In the future, these could be fetched dynamically from the registry API.
+//! Provides static lists of available backends and modules. Backends are
+//! annotated with the operations and platforms of their registry definition.
 
-use crate::error::Result;
+use super::backend::{configured_registries, extract_backend_meta};
+use crate::backends::user_parser::parse_backend_file;
+use crate::error::{DeclarchError, Result};
 use crate::project_identity;
 use crate::ui as output;
+use crate::utils::{machine_output, remote};
 use colored::Colorize;
+use serde::Serialize;
+use std::path::Path;
+use std::thread;
 
 /// Backend info tuple: (name, description, tags)
 type BackendInfo<'a> = (&'a str, &'a str, Vec<&'a str>);
@@ -14,13 +20,11 @@ type BackendInfo<'a> = (&'a str, &'a str, Vec<&'a str>);
 /// Module info tuple: (path, description, tags)
 type ModuleInfo<'a> = (&'a str, &'a str, Vec<&'a str>);
 
-/// List available backends from the registry
-pub fn list_available_backends() -> Result<()> {
-    output::header("Available Backends");
-
-    // Static list of known backends in the registry
-    // This could be fetched dynamically from the registry API in the future
-    let backends: Vec<BackendInfo> = vec![
+/// Backends known to the registry
+///
+/// This could be fetched dynamically from the registry API in the future.
+fn known_backends() -> Vec<BackendInfo<'static>> {
+    vec![
         (
             "apt",
             "Debian/Ubuntu package manager",
@@ -104,26 +108,64 @@ pub fn list_available_backends() -> Result<()> {
             "Yet Another Yogurt AUR helper",
             vec!["arch", "linux", "aur", "go"],
         ),
-    ];
+    ]
+}
 
-    println!();
-    const SYSTEM_PMS: &[&str] = &[
-        "apt", "aur", "dnf", "pacman", "paru", "yay", "flatpak", "snap", "nix", "soar", "brew",
-    ];
-    const LANG_PMS: &[&str] = &["npm", "yarn", "pnpm", "bun", "pip", "cargo", "gem", "go"];
+const LANG_PMS: &[&str] = &["npm", "yarn", "pnpm", "bun", "pip", "cargo", "gem", "go"];
 
-    println!("{}", "System Package Managers:".bold().cyan());
-    for (name, desc, _) in &backends {
-        if SYSTEM_PMS.contains(name) {
-            println!("  {} - {}", name.bold(), desc.dimmed());
-        }
+/// One `init --list backends` entry, with what its registry definition supports
+#[derive(Debug, Serialize)]
+struct BackendListing {
+    name: String,
+    description: String,
+    category: &'static str,
+    tags: Vec<String>,
+    /// Operations the definition configures; `None` when it could not be fetched or parsed
+    capabilities: Option<Vec<&'static str>>,
+    platforms: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// List available backends from the registry, with their capabilities
+///
+/// Machine formats go to `output_file` (`--output`) when set.
+pub fn list_available_backends(format: Option<&str>, output_file: Option<&Path>) -> Result<()> {
+    let listings = backend_listings();
+
+    if let Some(format @ ("json" | "yaml" | "toml")) = format {
+        let out = match format {
+            "yaml" => serde_yml::to_string(&listings)?,
+            "toml" => toml::to_string_pretty(&BackendListingsOut {
+                backends: &listings,
+            })?,
+            _ => serde_json::to_string_pretty(&listings)?,
+        };
+        return machine_output::write_report(&out, output_file);
     }
 
-    println!();
-    println!("{}", "Language-Specific Package Managers:".bold().cyan());
-    for (name, desc, _) in &backends {
-        if LANG_PMS.contains(name) {
-            println!("  {} - {}", name.bold(), desc.dimmed());
+    output::header("Available Backends");
+    // With nothing fetched (offline, registry down) one warning beats a line per backend
+    let any_fetched = listings.iter().any(|l| l.capabilities.is_some());
+    if !any_fetched && let Some(reason) = listings.iter().find_map(|l| l.error.as_deref()) {
+        output::warning(&format!("Backend capabilities unavailable: {}", reason));
+    }
+
+    for (title, category) in [
+        ("System Package Managers:", "system"),
+        ("Language-Specific Package Managers:", "language"),
+    ] {
+        println!();
+        println!("{}", title.bold().cyan());
+        for listing in listings.iter().filter(|l| l.category == category) {
+            println!(
+                "  {} - {}",
+                listing.name.bold(),
+                listing.description.dimmed()
+            );
+            if any_fetched {
+                println!("      {}", capability_line(listing));
+            }
         }
     }
 
@@ -145,6 +187,133 @@ pub fn list_available_backends() -> Result<()> {
     Ok(())
 }
 
+/// TOML needs a table at the top level
+#[derive(Serialize)]
+struct BackendListingsOut<'a> {
+    backends: &'a [BackendListing],
+}
+
+/// Known backends annotated from their registry definitions, fetched in
+/// parallel; a backend that cannot be fetched or parsed keeps its entry
+/// without capabilities.
+fn backend_listings() -> Vec<BackendListing> {
+    let known = known_backends();
+    let offline = remote::ensure_online("Listing backend capabilities").err();
+    let registries = configured_registries();
+
+    let definitions: Vec<Result<Definition>> = match &offline {
+        Some(e) => known
+            .iter()
+            .map(|_| Err(DeclarchError::Other(e.to_string())))
+            .collect(),
+        None => thread::scope(|scope| {
+            let handles: Vec<_> = known
+                .iter()
+                .map(|(name, _, _)| {
+                    let registries = &registries;
+                    scope.spawn(move || {
+                        remote::fetch_backend_content_silent(name, registries)
+                            .and_then(|fetched| describe_definition(&fetched.content))
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle.join().unwrap_or_else(|_| {
+                        Err(DeclarchError::Other("backend fetch panicked".to_string()))
+                    })
+                })
+                .collect()
+        }),
+    };
+
+    known
+        .into_iter()
+        .zip(definitions)
+        .map(|((name, desc, tags), definition)| {
+            let category = if LANG_PMS.contains(&name) {
+                "language"
+            } else {
+                "system"
+            };
+            let (capabilities, platforms, error) = match definition {
+                Ok(def) => (Some(def.capabilities), def.platforms, None),
+                Err(e) => (None, Vec::new(), Some(e.to_string())),
+            };
+            BackendListing {
+                name: name.to_string(),
+                description: desc.to_string(),
+                category,
+                tags: tags.into_iter().map(str::to_string).collect(),
+                capabilities,
+                platforms,
+                error,
+            }
+        })
+        .collect()
+}
+
+/// What a backend definition supports, from its command blocks and `meta`
+#[derive(Debug, PartialEq)]
+struct Definition {
+    capabilities: Vec<&'static str>,
+    platforms: Vec<String>,
+}
+
+fn describe_definition(content: &str) -> Result<Definition> {
+    let meta = extract_backend_meta(content)?;
+    let config = parse_backend_file(content)?.ok_or_else(|| {
+        DeclarchError::ConfigError("definition has no `backend` node".to_string())
+    })?;
+
+    let optional = [
+        ("remove", config.remove_cmd.is_some()),
+        ("search", config.search_cmd.is_some()),
+        ("search_local", config.search_local_cmd.is_some()),
+        ("update", config.update_cmd.is_some()),
+        ("upgrade", config.upgrade_cmd.is_some()),
+        ("outdated", config.outdated_cmd.is_some()),
+        ("info", config.info_cmd.is_some()),
+        ("cache_clean", config.cache_clean_cmd.is_some()),
+        ("orphans", config.orphans_cmd.is_some()),
+        ("dependencies", config.dependencies_cmd.is_some()),
+    ];
+    let mut capabilities = vec!["install"];
+    capabilities.extend(
+        optional
+            .into_iter()
+            .filter(|(_, supported)| *supported)
+            .map(|(name, _)| name),
+    );
+
+    let platforms = if meta.platforms.is_empty() {
+        config.supported_os.unwrap_or_default()
+    } else {
+        meta.platforms
+    };
+    Ok(Definition {
+        capabilities,
+        platforms,
+    })
+}
+
+/// `install, remove, search  [linux, macos]`
+fn capability_line(listing: &BackendListing) -> String {
+    let Some(capabilities) = &listing.capabilities else {
+        return "(capabilities unavailable)".dimmed().to_string();
+    };
+    let mut line = capabilities.join(", ");
+    if !listing.platforms.is_empty() {
+        line.push_str(
+            &format!("  [{}]", listing.platforms.join(", "))
+                .purple()
+                .to_string(),
+        );
+    }
+    line
+}
+
 /// Check if a module exists in the registry
 pub fn is_module_available(name: &str) -> bool {
     let modules: Vec<ModuleInfo> = vec![
@@ -261,3 +430,47 @@ pub fn list_available_modules() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFINITION: &str = r#"
+backend "pacman" {
+    meta {
+        title "Pacman"
+        platforms "linux"
+    }
+    binary "pacman"
+    list "{binary} -Q" {
+        format "whitespace"
+        name_col 0
+        version_col 1
+    }
+    install "{binary} -S {packages}"
+    remove "{binary} -R {packages}"
+    search "{binary} -Ss {query}" {
+        format "whitespace"
+        name_col 0
+    }
+    orphans "{binary} -Qtdq"
+}
+"#;
+
+    #[test]
+    fn definition_capabilities_follow_configured_commands() {
+        assert_eq!(
+            describe_definition(DEFINITION).unwrap(),
+            Definition {
+                capabilities: vec!["install", "remove", "search", "orphans"],
+                platforms: vec!["linux".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn unparsable_definition_is_an_error_not_a_panic() {
+        assert!(describe_definition("backend \"x\" {").is_err());
+        assert!(describe_definition("meta { title \"x\" }").is_err());
+    }
+}
@@ -10,8 +10,9 @@ use crate::packages::get_registry;
 use crate::project_identity;
 use crate::ui as output;
 use crate::utils::paths;
-use file_ops::cleanup_install_backups;
-use finalize::{finalize_edits, run_auto_sync};
+pub(crate) use file_ops::{cleanup_install_backups, rollback_install_edits};
+pub(crate) use finalize::finalize_edits;
+use finalize::run_auto_sync;
 use planning::plan_installs;
 use presentation::{show_dry_run_install, show_install_summary};
 
@@ -37,6 +38,10 @@ pub struct InstallOptions {
     pub dry_run: bool,
     /// Verbose output
     pub verbose: bool,
+    /// Sync without escalation (`--no-sudo`)
+    pub no_sudo: bool,
+    /// Capture backend output of the sync, showing it only on failure (`--quiet-backends`)
+    pub quiet_backends: bool,
 }
 
 /// Run the install command
@@ -50,10 +55,16 @@ pub fn run(options: InstallOptions) -> Result<()> {
 
     // Step 1: Load existing config to check for duplicates
     let config_path = paths::config_file()?;
+    if loader::is_stdin_root(&config_path) {
+        // New modules are imported by editing the root file, which stdin has none of.
+        return Err(DeclarchError::ConfigError(
+            "install edits config files on disk and cannot be used with --config -".to_string(),
+        ));
+    }
     if options.verbose {
         output::verbose(&format!("Root config: {}", config_path.display()));
     }
-    let existing_packages = if config_path.exists() {
+    let existing_packages = if loader::root_config_available(&config_path) {
         let config = loader::load_root_config(&config_path)?;
         Some(config.packages)
     } else {
@@ -197,8 +208,7 @@ pub fn run(options: InstallOptions) -> Result<()> {
     if !options.no_sync {
         run_auto_sync(
             &finalize.modified_modules,
-            options.verbose,
-            options.yes,
+            &options,
             &all_edits,
             finalize.root_backup.as_ref(),
         )?;
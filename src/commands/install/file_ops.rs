@@ -5,7 +5,7 @@ use regex::Regex;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-pub(super) fn rollback_install_edits(all_edits: &[ModuleEdit], root_backup: Option<&PathBuf>) {
+pub(crate) fn rollback_install_edits(all_edits: &[ModuleEdit], root_backup: Option<&PathBuf>) {
     for edit in all_edits {
         if let Some(ref backup) = edit.backup_path {
             let _ = restore_from_backup(backup);
@@ -19,7 +19,7 @@ pub(super) fn rollback_install_edits(all_edits: &[ModuleEdit], root_backup: Opti
     }
 }
 
-pub(super) fn cleanup_install_backups(all_edits: &[ModuleEdit], root_backup: Option<&PathBuf>) {
+pub(crate) fn cleanup_install_backups(all_edits: &[ModuleEdit], root_backup: Option<&PathBuf>) {
     for edit in all_edits {
         if let Some(ref backup) = edit.backup_path {
             let _ = fs::remove_file(backup);
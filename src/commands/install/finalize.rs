@@ -3,16 +3,17 @@ use crate::error::{DeclarchError, Result};
 use crate::ui as output;
 use crate::utils::paths;
 
+use super::InstallOptions;
 use super::file_ops::{inject_import_to_root, rollback_install_edits};
 
 #[derive(Debug, Default)]
-pub(super) struct FinalizeResult {
-    pub(super) modified_modules: Vec<String>,
-    pub(super) all_packages: Vec<String>,
-    pub(super) root_backup: Option<std::path::PathBuf>,
+pub(crate) struct FinalizeResult {
+    pub(crate) modified_modules: Vec<String>,
+    pub(crate) all_packages: Vec<String>,
+    pub(crate) root_backup: Option<std::path::PathBuf>,
 }
 
-pub(super) fn finalize_edits(all_edits: &[ModuleEdit]) -> Result<FinalizeResult> {
+pub(crate) fn finalize_edits(all_edits: &[ModuleEdit]) -> Result<FinalizeResult> {
     let mut result = FinalizeResult::default();
 
     for edit in all_edits {
@@ -47,31 +48,21 @@ pub(super) fn finalize_edits(all_edits: &[ModuleEdit]) -> Result<FinalizeResult>
 
 pub(super) fn run_auto_sync(
     modified_modules: &[String],
-    verbose: bool,
-    yes: bool,
+    options: &InstallOptions,
     all_edits: &[ModuleEdit],
     root_backup: Option<&std::path::PathBuf>,
 ) -> Result<()> {
     use crate::commands::sync::{self, SyncOptions};
 
     let sync_result = sync::run(SyncOptions {
-        update: false,
-        prune: false,
-        dry_run: false,
-        verbose,
-        target: None,
-        yes,
-        force: false,
-        noconfirm: false,
-        hooks: false,
-        profile: None,
-        host: None,
+        verbose: options.verbose,
+        yes: options.yes,
         modules: modified_modules.to_vec(),
-        diff: false,
-        format: None,
-        output_version: None,
+        no_sudo: options.no_sudo,
+        quiet_backends: options.quiet_backends,
+        ..Default::default()
     });
-    if verbose {
+    if options.verbose {
         output::verbose(&format!(
             "Auto-sync modules: {}",
             if modified_modules.is_empty() {
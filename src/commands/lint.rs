@@ -7,6 +7,7 @@ use crate::utils::paths;
 use serde::Serialize;
 use std::path::PathBuf;
 
+mod autofix;
 mod file_checks;
 mod file_graph;
 mod merged_checks;
@@ -16,10 +17,12 @@ mod state_ops;
 #[cfg(test)]
 mod tests;
 
-use file_checks::{apply_safe_fixes, collect_file_issues};
+use autofix::{apply_safe_fixes, plan_safe_fixes};
+use file_checks::collect_file_issues;
 use file_graph::{collect_lint_files, load_config_with_modules};
 use merged_checks::{
     collect_conflict_issues, collect_duplicate_issues, collect_misc_merged_issues,
+    collect_shadowed_packages, collect_state_key_issues,
 };
 use plan_diff::show_diff;
 use reporting::{count_issues, display_issues, issues_to_machine_report};
@@ -40,8 +43,10 @@ pub struct LintOptions {
     pub yes: bool,
     pub format: Option<String>,
     pub output_version: Option<String>,
+    /// Write machine output to this file (`--output`) instead of stdout
+    pub output: Option<PathBuf>,
     pub verbose: bool,
-    pub profile: Option<String>,
+    pub profiles: Vec<String>,
     pub host: Option<String>,
     pub modules: Vec<String>,
 }
@@ -100,6 +105,15 @@ struct LintReportOut {
     warnings_count: usize,
     errors_count: usize,
     issues: Vec<LintIssueOut>,
+    /// Packages declared in more than one source file (duplicate checks only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    shadowed: Vec<ShadowedPackageOut>,
+}
+
+#[derive(Debug, Serialize)]
+struct ShadowedPackageOut {
+    package: String,
+    sources: Vec<String>,
 }
 
 pub fn run(options: LintOptions) -> Result<()> {
@@ -109,28 +123,35 @@ pub fn run(options: LintOptions) -> Result<()> {
     }
 
     let config_path = paths::config_file()?;
-    if !config_path.exists() {
+    if !loader::root_config_available(&config_path) {
         return Err(DeclarchError::ConfigNotFound { path: config_path });
     }
 
     let selectors = LoadSelectors {
-        profile: options.profile.clone(),
+        profiles: options.profiles.clone(),
         host: options.host.clone(),
     };
 
-    let merged = if options.modules.is_empty() {
-        loader::load_root_config_with_selectors(&config_path, &selectors)?
-    } else {
-        load_config_with_modules(&config_path, &options.modules, &selectors)?
+    let load_merged = || {
+        if options.modules.is_empty() {
+            loader::load_root_config_with_selectors(&config_path, &selectors)
+        } else {
+            load_config_with_modules(&config_path, &options.modules, &selectors)
+        }
     };
+    let mut merged = load_merged()?;
 
     let lint_files = collect_lint_files(&config_path, &options.modules)?;
 
     let machine_mode = matches!(options.output_version.as_deref(), Some("v1"))
-        && matches!(options.format.as_deref(), Some("json" | "yaml"));
+        && matches!(options.format.as_deref(), Some("json" | "yaml" | "toml"));
 
+    // Conflicts and policy violations are never auto-fixed, only reported below.
     if options.fix {
-        apply_safe_fixes(&lint_files)?;
+        let fixes = plan_safe_fixes(&lint_files, &merged, options.backend.as_deref())?;
+        if apply_safe_fixes(&fixes, options.yes, options.dry_run)? {
+            merged = load_merged()?;
+        }
     }
 
     maybe_repair_state(options.repair_state, machine_mode)?;
@@ -162,12 +183,18 @@ pub fn run(options: LintOptions) -> Result<()> {
     };
 
     if machine_mode {
+        let shadowed = if matches!(options.mode, LintMode::All | LintMode::Duplicates) {
+            collect_shadowed_packages(&merged, options.backend.as_deref())
+        } else {
+            Vec::new()
+        };
         let report = issues_to_machine_report(
             &issues,
             options.mode,
             lint_files.len(),
             warn_count,
             err_count,
+            shadowed,
         );
         machine_output::emit_v1(
             "lint",
@@ -175,6 +202,7 @@ pub fn run(options: LintOptions) -> Result<()> {
             Vec::new(),
             Vec::new(),
             options.format.as_deref().unwrap_or("json"),
+            options.output.as_deref(),
         )?;
     }
 
@@ -255,6 +283,7 @@ fn collect_issues_for_mode(
         LintMode::All => {
             collect_duplicate_issues(merged, options.backend.as_deref(), &mut issues);
             collect_conflict_issues(merged, options.backend.as_deref(), &mut issues);
+            collect_state_key_issues(merged, options.backend.as_deref(), &mut issues);
             collect_misc_merged_issues(merged, &mut issues);
             for file in lint_files {
                 collect_file_issues(file, &mut issues)?;
@@ -271,6 +300,7 @@ fn collect_issues_for_mode(
         }
         LintMode::Conflicts => {
             collect_conflict_issues(merged, options.backend.as_deref(), &mut issues);
+            collect_state_key_issues(merged, options.backend.as_deref(), &mut issues);
         }
     }
 
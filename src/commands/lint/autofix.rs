@@ -0,0 +1,174 @@
+use super::file_checks::sort_import_lines;
+use crate::config::editor::{ConfigEditor, backup_kdl_file};
+use crate::config::loader::MergedConfig;
+use crate::error::Result;
+use crate::ui as output;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A file rewrite produced by safe autofixes, not yet written.
+#[derive(Debug)]
+pub(super) struct PlannedFix {
+    pub(super) path: PathBuf,
+    pub(super) original: String,
+    pub(super) fixed: String,
+    pub(super) notes: Vec<String>,
+}
+
+/// Compute safe fixes for lint files without touching disk.
+///
+/// - Sorts and dedupes import lines
+/// - Drops duplicate package declarations, keeping the first one
+pub(super) fn plan_safe_fixes(
+    files: &[PathBuf],
+    merged: &MergedConfig,
+    backend_filter: Option<&str>,
+) -> Result<Vec<PlannedFix>> {
+    let editor = ConfigEditor::new();
+    let mut duplicates = merged.get_duplicates();
+    duplicates.sort_by_key(|(pkg, _)| pkg.to_string());
+    let mut fixes = Vec::new();
+
+    for file in files {
+        let Ok(original) = fs::read_to_string(file) else {
+            continue;
+        };
+        let mut notes = Vec::new();
+
+        let mut fixed = sort_import_lines(&original);
+        if fixed != original {
+            notes.push("sorted imports".to_string());
+        }
+
+        for (pkg, sources) in &duplicates {
+            if backend_filter.is_some_and(|filter| pkg.backend.name() != filter) {
+                continue;
+            }
+            // The first declaration is kept: later files lose the package,
+            // and the first file loses its repeats.
+            let updated = if same_file(&sources[0], file) {
+                if sources
+                    .iter()
+                    .filter(|source| same_file(source, file))
+                    .count()
+                    < 2
+                {
+                    continue;
+                }
+                editor.remove_repeated_package_from_content(
+                    &fixed,
+                    &pkg.name,
+                    pkg.backend.name(),
+                )?
+            } else if sources.iter().any(|source| same_file(source, file)) {
+                editor.remove_package_from_content(&fixed, &pkg.name, pkg.backend.name())?
+            } else {
+                continue;
+            };
+            if let Some(updated) = updated {
+                fixed = updated;
+                notes.push(format!(
+                    "removed duplicate {} (kept in {})",
+                    pkg,
+                    sources[0].display()
+                ));
+            }
+        }
+
+        if fixed != original {
+            fixes.push(PlannedFix {
+                path: file.clone(),
+                original,
+                fixed,
+                notes,
+            });
+        }
+    }
+
+    Ok(fixes)
+}
+
+/// Show planned fixes as diffs, confirm, then back up and rewrite each file.
+///
+/// Returns whether any file was written.
+pub(super) fn apply_safe_fixes(fixes: &[PlannedFix], yes: bool, dry_run: bool) -> Result<bool> {
+    output::header("Applying Lint Fixes");
+
+    if fixes.is_empty() {
+        output::info("No safe fixes to apply");
+        return Ok(false);
+    }
+
+    for fix in fixes {
+        output::info(&format!(
+            "{} ({})",
+            fix.path.display(),
+            fix.notes.join(", ")
+        ));
+        for line in line_diff(&fix.original, &fix.fixed) {
+            output::indent(&line, 1);
+        }
+    }
+
+    if dry_run {
+        output::info("Dry-run: no files were changed");
+        return Ok(false);
+    }
+
+    if !yes && !output::prompt_yes_no(&format!("Apply fixes to {} file(s)?", fixes.len())) {
+        output::info("Lint fixes skipped");
+        return Ok(false);
+    }
+
+    for fix in fixes {
+        let backup = backup_kdl_file(&fix.path)?;
+        fs::write(&fix.path, &fix.fixed)?;
+        output::success(&format!(
+            "Fixed {} (backup: {})",
+            fix.path.display(),
+            backup.display()
+        ));
+    }
+
+    Ok(true)
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    a == b || fs::canonicalize(a).ok() == fs::canonicalize(b).ok()
+}
+
+/// Minimal line diff (`-`/`+` prefixed) of two texts, based on their LCS.
+pub(super) fn line_diff(before: &str, after: &str) -> Vec<String> {
+    let old: Vec<&str> = before.lines().collect();
+    let new: Vec<&str> = after.lines().collect();
+
+    // lcs[i][j] = LCS length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            diff.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+    diff.extend(old[i..].iter().map(|line| format!("- {}", line)));
+    diff.extend(new[j..].iter().map(|line| format!("+ {}", line)));
+    diff
+}
@@ -1,10 +1,9 @@
 use super::LintIssue;
 use crate::config::kdl::parse_kdl_content_with_path;
 use crate::error::Result;
-use crate::ui as output;
 use kdl::KdlDocument;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 pub(super) fn collect_file_issues(path: &Path, issues: &mut Vec<LintIssue>) -> Result<()> {
     let content = fs::read_to_string(path)?;
@@ -57,7 +56,7 @@ pub(super) fn collect_file_issues(path: &Path, issues: &mut Vec<LintIssue>) -> R
         }
 
         let resolved = super::file_graph::resolve_import_path(path, &import)?;
-        if !resolved.exists() {
+        if super::file_graph::existing_import_files(path, &import)?.is_empty() {
             issues.push(LintIssue::warning(
                 Some(path.to_path_buf()),
                 format!(
@@ -83,7 +82,7 @@ pub(super) fn collect_file_issues(path: &Path, issues: &mut Vec<LintIssue>) -> R
         }
 
         let resolved = super::file_graph::resolve_import_path(path, &import)?;
-        if !resolved.exists() {
+        if super::file_graph::existing_import_files(path, &import)?.is_empty() {
             issues.push(LintIssue::warning(
                 Some(path.to_path_buf()),
                 format!(
@@ -98,21 +97,6 @@ pub(super) fn collect_file_issues(path: &Path, issues: &mut Vec<LintIssue>) -> R
     Ok(())
 }
 
-pub(super) fn apply_safe_fixes(files: &[PathBuf]) -> Result<()> {
-    output::header("Applying Lint Fixes");
-
-    for file in files {
-        let content = fs::read_to_string(file)?;
-        let fixed = sort_import_lines(&content);
-        if fixed != content {
-            fs::write(file, fixed)?;
-            output::success(&format!("Fixed {}", file.display()));
-        }
-    }
-
-    Ok(())
-}
-
 pub(super) fn sort_import_lines(content: &str) -> String {
     let mut result = String::new();
     let mut imports: Vec<String> = Vec::new();
@@ -30,6 +30,20 @@ pub(super) fn resolve_import_path(base_file: &Path, import: &str) -> Result<Path
     }
 }
 
+/// Existing files an import names; glob imports (`modules/*.kdl`) expand to
+/// their matches.
+pub(super) fn existing_import_files(base_file: &Path, import: &str) -> Result<Vec<PathBuf>> {
+    let resolved = resolve_import_path(base_file, import)?;
+    if paths::is_glob(import) {
+        return paths::glob_files(&resolved);
+    }
+    Ok(if resolved.exists() {
+        vec![resolved]
+    } else {
+        Vec::new()
+    })
+}
+
 pub(super) fn collect_lint_files(config_path: &Path, modules: &[String]) -> Result<Vec<PathBuf>> {
     let mut files: BTreeSet<PathBuf> = BTreeSet::new();
     discover_lint_files_recursive(config_path, &mut files)?;
@@ -75,8 +89,7 @@ pub(super) fn discover_lint_files_recursive(
             continue;
         }
 
-        let resolved = resolve_import_path(&canonical, &import)?;
-        if resolved.exists() {
+        for resolved in existing_import_files(&canonical, &import)? {
             discover_lint_files_recursive(&resolved, files)?;
         }
     }
@@ -90,8 +103,7 @@ pub(super) fn discover_lint_files_recursive(
             continue;
         }
 
-        let resolved = resolve_import_path(&canonical, &import)?;
-        if resolved.exists() {
+        for resolved in existing_import_files(&canonical, &import)? {
             discover_lint_files_recursive(&resolved, files)?;
         }
     }
@@ -111,8 +123,7 @@ pub(super) fn load_config_with_modules(
         output::info(&format!("  Loading module: {}", final_path.display()));
 
         let module_config = loader::load_root_config_with_selectors(&final_path, selectors)?;
-        merged.packages.extend(module_config.packages);
-        merged.excludes.extend(module_config.excludes);
+        merged.merge_module(module_config);
     }
 
     Ok(merged)
@@ -1,5 +1,7 @@
-use super::LintIssue;
+use super::{LintIssue, ShadowedPackageOut};
 use crate::config::loader::MergedConfig;
+use crate::constants::EXPERIMENTAL_ENABLE_HOOKS;
+use crate::core::resolver;
 
 pub(super) fn collect_duplicate_issues(
     merged: &MergedConfig,
@@ -7,19 +9,38 @@ pub(super) fn collect_duplicate_issues(
     issues: &mut Vec<LintIssue>,
 ) {
     let policy = merged.policy.as_ref();
-    let duplicates = merged.get_duplicates();
     let duplicate_as_error = policy.is_some_and(|p| p.duplicate_is_error());
+    let mut duplicates = merged.get_duplicates();
+    duplicates.sort_by_key(|(pkg, _)| pkg.to_string());
     for (pkg, sources) in duplicates {
         if let Some(filter) = backend_filter
             && pkg.backend.name() != filter
         {
             continue;
         }
-        let msg = format!(
-            "Duplicate declaration: {} appears in {} source file(s)",
-            pkg,
-            sources.len()
-        );
+        let mut distinct: Vec<String> = Vec::new();
+        for source in sources {
+            let source = source.display().to_string();
+            if !distinct.contains(&source) {
+                distinct.push(source);
+            }
+        }
+        // Repeats within one file are duplicates too; only the wording differs.
+        let msg = if distinct.len() > 1 {
+            format!(
+                "Duplicate declaration: {} is shadowed across {} source file(s): {}",
+                pkg,
+                distinct.len(),
+                distinct.join(", ")
+            )
+        } else {
+            format!(
+                "Duplicate declaration: {} is declared {} times in {}",
+                pkg,
+                sources.len(),
+                distinct.join(", ")
+            )
+        };
         if duplicate_as_error {
             issues.push(LintIssue::error(None, msg));
         } else {
@@ -28,6 +49,22 @@ pub(super) fn collect_duplicate_issues(
     }
 }
 
+/// Machine-readable view of packages declared in more than one source file.
+pub(super) fn collect_shadowed_packages(
+    merged: &MergedConfig,
+    backend_filter: Option<&str>,
+) -> Vec<ShadowedPackageOut> {
+    merged
+        .get_shadowed()
+        .into_iter()
+        .filter(|(pkg, _)| backend_filter.is_none_or(|filter| pkg.backend.name() == filter))
+        .map(|(pkg, sources)| ShadowedPackageOut {
+            package: pkg.to_string(),
+            sources: sources.iter().map(|p| p.display().to_string()).collect(),
+        })
+        .collect()
+}
+
 pub(super) fn collect_conflict_issues(
     merged: &MergedConfig,
     backend_filter: Option<&str>,
@@ -60,9 +97,43 @@ pub(super) fn collect_conflict_issues(
     }
 }
 
+/// Declared packages that would share one state entry (`backend:name` key).
+pub(super) fn collect_state_key_issues(
+    merged: &MergedConfig,
+    backend_filter: Option<&str>,
+    issues: &mut Vec<LintIssue>,
+) {
+    let resolved: Vec<_> = merged
+        .packages
+        .keys()
+        .map(|pkg| resolver::resolve_package_alias(merged, pkg))
+        .collect();
+    for (key, ids) in resolver::state_key_collisions(&resolved) {
+        if let Some(filter) = backend_filter
+            && !ids.iter().any(|pkg| pkg.backend.name() == filter)
+        {
+            continue;
+        }
+        let declared = ids
+            .iter()
+            .map(|pkg| format!("'{}' on backend '{}'", pkg.name, pkg.backend))
+            .collect::<Vec<_>>()
+            .join(", ");
+        issues.push(LintIssue::warning(
+            None,
+            format!(
+                "State key collision: {} all map to '{}'; only one would be tracked",
+                declared, key
+            ),
+        ));
+    }
+}
+
 pub(super) fn collect_misc_merged_issues(merged: &MergedConfig, issues: &mut Vec<LintIssue>) {
     let policy = merged.policy.as_ref();
-    if merged.lifecycle_actions.is_some() && !merged.is_experimental_enabled("enable-hooks") {
+    if merged.lifecycle_actions.is_some()
+        && !merged.is_experimental_enabled(EXPERIMENTAL_ENABLE_HOOKS)
+    {
         issues.push(LintIssue::warning(
             None,
             "Hooks configured but not executable: add experimental { \"enable-hooks\" } if intentional",
@@ -1,4 +1,4 @@
-use super::{LintIssue, LintIssueOut, LintMode, LintReportOut, Severity};
+use super::{LintIssue, LintIssueOut, LintMode, LintReportOut, Severity, ShadowedPackageOut};
 use crate::ui as output;
 
 pub(super) fn count_issues(issues: &[LintIssue]) -> (usize, usize) {
@@ -45,6 +45,7 @@ pub(super) fn issues_to_machine_report(
     files_checked: usize,
     warnings_count: usize,
     errors_count: usize,
+    shadowed: Vec<ShadowedPackageOut>,
 ) -> LintReportOut {
     let issues = issues
         .iter()
@@ -65,5 +66,6 @@ pub(super) fn issues_to_machine_report(
         warnings_count,
         errors_count,
         issues,
+        shadowed,
     }
 }
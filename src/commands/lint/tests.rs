@@ -1,4 +1,4 @@
-use super::{file_checks, file_graph, state_ops};
+use super::{autofix, file_checks, file_graph, merged_checks, state_ops};
 use crate::core::types::Backend;
 use crate::project_identity;
 use crate::state::types::PackageState;
@@ -77,6 +77,7 @@ fn pkg_state(backend: &str, name: &str) -> PackageState {
         version: None,
         install_reason: Some("declared".to_string()),
         source_module: None,
+        pinned: false,
         last_seen_at: None,
         backend_meta: None,
     }
@@ -135,3 +136,127 @@ fn resolve_state_remove_keys_ambiguous_plain_name_errors() {
             .expect_err("plain name should be ambiguous");
     assert!(err.to_string().contains("matched multiple backends"));
 }
+
+#[test]
+fn shadowed_packages_keep_every_module_source() {
+    use crate::config::loader::MergedConfig;
+    use crate::core::types::PackageId;
+    use std::path::PathBuf;
+
+    let bat = PackageId {
+        name: "bat".to_string(),
+        backend: Backend::from("aur"),
+    };
+    let mut merged = MergedConfig::default();
+    merged
+        .packages
+        .insert(bat.clone(), vec![PathBuf::from("/cfg/modules/base.kdl")]);
+
+    let mut module_packages = HashMap::new();
+    module_packages.insert(bat, vec![PathBuf::from("/cfg/modules/dev.kdl")]);
+    module_packages.insert(
+        PackageId {
+            name: "fd".to_string(),
+            backend: Backend::from("aur"),
+        },
+        vec![PathBuf::from("/cfg/modules/dev.kdl")],
+    );
+    merged.extend_packages(module_packages);
+
+    let shadowed = merged_checks::collect_shadowed_packages(&merged, None);
+    assert_eq!(shadowed.len(), 1);
+    assert_eq!(shadowed[0].package, "aur:bat");
+    assert_eq!(
+        shadowed[0].sources,
+        vec!["/cfg/modules/base.kdl", "/cfg/modules/dev.kdl"]
+    );
+    assert!(merged_checks::collect_shadowed_packages(&merged, Some("npm")).is_empty());
+}
+
+#[test]
+fn plan_safe_fixes_drops_duplicates_from_later_sources_only() {
+    use crate::config::loader::MergedConfig;
+    use crate::core::types::PackageId;
+
+    let dir = tempdir().expect("tempdir");
+    let first = dir.path().join("base.kdl");
+    let second = dir.path().join("dev.kdl");
+    fs::write(&first, "pkg {\n  aur {\n    bat\n  }\n}\n").expect("write base");
+    fs::write(&second, "pkg {\n  aur {\n    bat\n    fd\n  }\n}\n").expect("write dev");
+
+    let mut merged = MergedConfig::default();
+    merged.packages.insert(
+        PackageId {
+            name: "bat".to_string(),
+            backend: Backend::from("aur"),
+        },
+        vec![first.clone(), second.clone()],
+    );
+
+    let fixes = autofix::plan_safe_fixes(&[first.clone(), second.clone()], &merged, None)
+        .expect("plan fixes");
+
+    assert_eq!(fixes.len(), 1);
+    assert_eq!(fixes[0].path, second);
+    assert!(!fixes[0].fixed.contains("bat"));
+    assert!(fixes[0].fixed.contains("fd"));
+    assert_eq!(
+        autofix::line_diff(&fixes[0].original, &fixes[0].fixed),
+        vec!["-     bat".to_string()]
+    );
+    // Nothing is written while planning
+    assert!(fs::read_to_string(&second).unwrap().contains("bat"));
+}
+
+#[test]
+fn plan_safe_fixes_drops_repeats_within_one_file() {
+    use crate::config::loader::MergedConfig;
+    use crate::core::types::PackageId;
+
+    let dir = tempdir().expect("tempdir");
+    let base = dir.path().join("base.kdl");
+    fs::write(&base, "pkg {\n  aur {\n    bat\n    fd\n    bat\n  }\n}\n").expect("write base");
+
+    let mut merged = MergedConfig::default();
+    merged.packages.insert(
+        PackageId {
+            name: "bat".to_string(),
+            backend: Backend::from("aur"),
+        },
+        vec![base.clone(), base.clone()],
+    );
+
+    let fixes =
+        autofix::plan_safe_fixes(std::slice::from_ref(&base), &merged, None).expect("plan fixes");
+
+    assert_eq!(fixes.len(), 1);
+    assert_eq!(fixes[0].fixed, "pkg {\n  aur {\n    bat\n    fd\n  }\n}\n");
+}
+
+#[test]
+fn duplicate_issues_include_repeats_within_one_file() {
+    use crate::config::loader::MergedConfig;
+    use crate::core::types::PackageId;
+    use std::path::PathBuf;
+
+    let base = PathBuf::from("/cfg/modules/base.kdl");
+    let mut merged = MergedConfig::default();
+    merged.packages.insert(
+        PackageId {
+            name: "bat".to_string(),
+            backend: Backend::from("aur"),
+        },
+        vec![base.clone(), base],
+    );
+
+    let mut issues = Vec::new();
+    merged_checks::collect_duplicate_issues(&merged, None, &mut issues);
+    assert_eq!(issues.len(), 1);
+    assert!(
+        issues[0]
+            .message
+            .contains("aur:bat is declared 2 times in /cfg/modules/base.kdl")
+    );
+    // Same-file repeats are not shadowing across modules
+    assert!(merged_checks::collect_shadowed_packages(&merged, None).is_empty());
+}
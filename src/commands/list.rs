@@ -9,10 +9,12 @@ use crate::project_identity;
 use crate::state;
 use crate::ui as output;
 use crate::utils::paths;
+use chrono::{DateTime, Utc};
 use colored::Colorize;
 use filters::{find_orphans, find_synced};
-use output_formats::{output_json, output_yaml};
+use output_formats::{output_json, output_toml, output_yaml};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::str::FromStr;
 use unmanaged_output::emit_unmanaged_output;
 
@@ -22,8 +24,12 @@ pub struct ListOptions {
     pub orphans: bool,
     pub synced: bool,
     pub unmanaged: bool,
+    /// Only packages installed/adopted at or after this time
+    pub since: Option<DateTime<Utc>>,
     pub format: Option<String>,
     pub output_version: Option<String>,
+    /// Write machine output to this file (`--output`) instead of stdout
+    pub output: Option<PathBuf>,
 }
 
 #[derive(serde::Serialize)]
@@ -43,7 +49,7 @@ pub fn run(options: ListOptions) -> Result<()> {
     // Load config for orphan/synced detection
     let config_packages = if options.orphans || options.synced {
         let config_path = paths::config_file()?;
-        if config_path.exists() {
+        if loader::root_config_available(&config_path) {
             let config = loader::load_root_config(&config_path)?;
             Some(config.packages)
         } else {
@@ -63,6 +69,11 @@ pub fn run(options: ListOptions) -> Result<()> {
         packages.retain(|p| p.backend == backend);
     }
 
+    // Filter by install time
+    if let Some(cutoff) = options.since {
+        packages.retain(|p| p.installed_at >= cutoff);
+    }
+
     // Filter orphans
     if options.orphans {
         if let Some(config_pkgs) = &config_packages {
@@ -85,8 +96,21 @@ pub fn run(options: ListOptions) -> Result<()> {
     let format_str = options.format.as_deref().unwrap_or("table");
 
     match format_str {
-        "json" => output_json(&packages, options.output_version.as_deref()),
-        "yaml" => output_yaml(&packages, options.output_version.as_deref()),
+        "json" => output_json(
+            &packages,
+            options.output_version.as_deref(),
+            options.output.as_deref(),
+        ),
+        "yaml" => output_yaml(
+            &packages,
+            options.output_version.as_deref(),
+            options.output.as_deref(),
+        ),
+        "toml" => output_toml(
+            &packages,
+            options.output_version.as_deref(),
+            options.output.as_deref(),
+        ),
         "table" => {
             let total = packages.len();
             display_packages(&packages, options.orphans, total);
@@ -102,7 +126,7 @@ pub fn run(options: ListOptions) -> Result<()> {
 
 fn run_unmanaged_list(options: ListOptions) -> Result<()> {
     let config_path = paths::config_file()?;
-    if !config_path.exists() {
+    if !loader::root_config_available(&config_path) {
         return Err(crate::error::DeclarchError::ConfigNotFound { path: config_path });
     }
     let config = loader::load_root_config(&config_path)?;
@@ -189,11 +213,16 @@ fn display_packages(packages: &[&state::types::PackageState], is_orphans: bool,
                 };
 
                 let version = pkg.version.as_ref().map(|v| v as &str).unwrap_or("-");
+                let remote = pkg
+                    .remote()
+                    .map(|remote| format!("  @{}", remote))
+                    .unwrap_or_default();
                 println!(
-                    "  {} {:<30} {:>10}",
+                    "  {} {:<30} {:>10}{}",
                     status,
                     &pkg.config_name,
-                    version.dimmed()
+                    version.dimmed(),
+                    remote.dimmed()
                 );
             }
         }
@@ -1,30 +1,67 @@
 use crate::error::Result;
 use crate::state;
 use crate::utils::machine_output;
+use std::path::Path;
 
 pub(super) fn output_json(
     packages: &[&state::types::PackageState],
     output_version: Option<&str>,
+    output_file: Option<&Path>,
 ) -> Result<()> {
     if output_version == Some("v1") {
-        return machine_output::emit_v1("info --list", packages, Vec::new(), Vec::new(), "json");
+        return machine_output::emit_v1(
+            "info --list",
+            packages,
+            Vec::new(),
+            Vec::new(),
+            "json",
+            output_file,
+        );
     }
 
     let json = serde_json::to_string_pretty(packages)?;
-    println!("{}", json);
-    Ok(())
+    machine_output::write_report(&json, output_file)
 }
 
 pub(super) fn output_yaml(
     packages: &[&state::types::PackageState],
     output_version: Option<&str>,
+    output_file: Option<&Path>,
 ) -> Result<()> {
     if output_version == Some("v1") {
-        return machine_output::emit_v1("info --list", packages, Vec::new(), Vec::new(), "yaml");
+        return machine_output::emit_v1(
+            "info --list",
+            packages,
+            Vec::new(),
+            Vec::new(),
+            "yaml",
+            output_file,
+        );
     }
 
     let json_value = serde_json::to_value(packages)?;
     let yaml = serde_yml::to_string(&json_value)?;
-    println!("{}", yaml);
-    Ok(())
+    machine_output::write_report(&yaml, output_file)
+}
+
+pub(super) fn output_toml(
+    packages: &[&state::types::PackageState],
+    output_version: Option<&str>,
+    output_file: Option<&Path>,
+) -> Result<()> {
+    if output_version == Some("v1") {
+        return machine_output::emit_v1(
+            "info --list",
+            packages,
+            Vec::new(),
+            Vec::new(),
+            "toml",
+            output_file,
+        );
+    }
+
+    machine_output::write_report(
+        &machine_output::to_toml_keyed("packages", packages)?,
+        output_file,
+    )
 }
@@ -3,6 +3,7 @@ use crate::ui as output;
 use crate::utils::machine_output;
 use colored::Colorize;
 use std::collections::HashMap;
+use std::path::Path;
 
 use super::{ListOptions, UnmanagedPackageOut};
 
@@ -12,27 +13,85 @@ pub(super) fn emit_unmanaged_output(
 ) -> Result<()> {
     let format_str = options.format.as_deref().unwrap_or("table");
     match format_str {
-        "json" => emit_json(out, options.output_version.as_deref()),
-        "yaml" => emit_yaml(out, options.output_version.as_deref()),
+        "json" => emit_json(
+            out,
+            options.output_version.as_deref(),
+            options.output.as_deref(),
+        ),
+        "yaml" => emit_yaml(
+            out,
+            options.output_version.as_deref(),
+            options.output.as_deref(),
+        ),
+        "toml" => emit_toml(
+            out,
+            options.output_version.as_deref(),
+            options.output.as_deref(),
+        ),
         _ => emit_table(out),
     }
 }
 
-fn emit_json(out: &[UnmanagedPackageOut], output_version: Option<&str>) -> Result<()> {
+fn emit_json(
+    out: &[UnmanagedPackageOut],
+    output_version: Option<&str>,
+    output_file: Option<&Path>,
+) -> Result<()> {
     if output_version == Some("v1") {
-        machine_output::emit_v1("info --list", out, Vec::new(), Vec::new(), "json")?;
+        machine_output::emit_v1(
+            "info --list",
+            out,
+            Vec::new(),
+            Vec::new(),
+            "json",
+            output_file,
+        )?;
     } else {
-        println!("{}", serde_json::to_string_pretty(out)?);
+        machine_output::write_report(&serde_json::to_string_pretty(out)?, output_file)?;
     }
     Ok(())
 }
 
-fn emit_yaml(out: &[UnmanagedPackageOut], output_version: Option<&str>) -> Result<()> {
+fn emit_yaml(
+    out: &[UnmanagedPackageOut],
+    output_version: Option<&str>,
+    output_file: Option<&Path>,
+) -> Result<()> {
     if output_version == Some("v1") {
-        machine_output::emit_v1("info --list", out, Vec::new(), Vec::new(), "yaml")?;
+        machine_output::emit_v1(
+            "info --list",
+            out,
+            Vec::new(),
+            Vec::new(),
+            "yaml",
+            output_file,
+        )?;
     } else {
         let yaml = serde_yml::to_string(&serde_json::to_value(out)?)?;
-        println!("{}", yaml);
+        machine_output::write_report(&yaml, output_file)?;
+    }
+    Ok(())
+}
+
+fn emit_toml(
+    out: &[UnmanagedPackageOut],
+    output_version: Option<&str>,
+    output_file: Option<&Path>,
+) -> Result<()> {
+    if output_version == Some("v1") {
+        machine_output::emit_v1(
+            "info --list",
+            out,
+            Vec::new(),
+            Vec::new(),
+            "toml",
+            output_file,
+        )?;
+    } else {
+        machine_output::write_report(
+            &machine_output::to_toml_keyed("packages", out)?,
+            output_file,
+        )?;
     }
     Ok(())
 }
@@ -1,7 +1,11 @@
+pub mod adopt;
 pub mod cache;
 pub mod completions;
+pub mod doctor;
 pub mod edit;
 pub mod ext;
+pub mod gc;
+pub mod history;
 pub mod hooks;
 pub mod info;
 pub mod info_reason;
@@ -9,9 +13,15 @@ pub mod init;
 pub mod install;
 pub mod lint;
 pub mod list;
+pub mod module_rename;
+pub mod outdated;
+pub mod plan;
 pub mod runtime_overrides;
 pub mod search;
 pub mod self_update;
+pub mod show;
+pub mod state_repair;
 pub mod switch;
 pub mod sync;
+pub mod unlock;
 pub mod upgrade;
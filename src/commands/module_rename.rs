@@ -0,0 +1,554 @@
+//! Module rename command
+//!
+//! Moves `modules/<old>.kdl` to `modules/<new>.kdl` and rewrites every
+//! `imports` reference to it in the root config and the other modules. Only
+//! the import strings themselves are touched, so comments and layout survive.
+//! State entries recorded as coming from the old module follow the rename.
+
+use crate::constants::CONFIG_EXTENSION;
+use crate::error::{DeclarchError, Result};
+use crate::project_identity;
+use crate::state;
+use crate::ui as output;
+use crate::utils::paths;
+use kdl::KdlDocument;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+pub struct ModuleRenameOptions {
+    /// Current module name (e.g., "base" or "linux/notes")
+    pub old: String,
+    /// New module name
+    pub new: String,
+    /// Show what would change without touching files or state
+    pub dry_run: bool,
+    /// Verbose output
+    pub verbose: bool,
+}
+
+/// A config file whose imports must be rewritten.
+struct ImportRewrite {
+    /// Where the file lives once the module has been moved
+    path: PathBuf,
+    content: String,
+    /// Content before the rewrite, put back if the rename fails
+    original: String,
+}
+
+pub fn run(options: ModuleRenameOptions) -> Result<()> {
+    validate_module_name(&options.old)?;
+    validate_module_name(&options.new)?;
+
+    let config_dir = paths::config_dir()?;
+    let modules_dir = paths::modules_dir()?;
+    let old_path = module_path(&modules_dir, &options.old);
+    let new_path = module_path(&modules_dir, &options.new);
+
+    if !old_path.exists() {
+        return Err(DeclarchError::Other(format!(
+            "Module '{}' not found\n  Tried: {}",
+            options.old,
+            old_path.display()
+        )));
+    }
+    if new_path.exists() {
+        return Err(DeclarchError::Other(format!(
+            "Module '{}' already exists at {}; refusing to overwrite it",
+            options.new,
+            new_path.display()
+        )));
+    }
+
+    let mut files = Vec::new();
+    let config_path = paths::config_file()?;
+    if config_path.is_file() {
+        files.push(config_path);
+    }
+    collect_kdl_files(&modules_dir, &mut files)?;
+
+    let mut rewrites = Vec::new();
+    for file in files {
+        let content = fs::read_to_string(&file).map_err(|e| DeclarchError::IoError {
+            path: file.clone(),
+            source: e,
+        })?;
+        let moved = file == old_path;
+        let from_dir = file.parent().unwrap_or(&config_dir);
+        let to_dir = if moved {
+            new_path.parent().unwrap_or(&modules_dir)
+        } else {
+            from_dir
+        };
+
+        let updated = rewrite_imports(&content, from_dir, to_dir, &old_path, &new_path)
+            .map_err(|e| DeclarchError::Other(format!("{}: {}", file.display(), e)))?;
+        if let Some(updated) = updated {
+            rewrites.push(ImportRewrite {
+                path: if moved { new_path.clone() } else { file },
+                content: updated,
+                original: content,
+            });
+        }
+    }
+
+    let old_stem = module_stem(&old_path);
+    let new_stem = module_stem(&new_path);
+
+    let lock = state::io::acquire_lock().map_err(|e| {
+        DeclarchError::Other(format!(
+            "Cannot start module rename: {}\n\
+             If no other {} process is running, run '{}' to clear the lock.",
+            e,
+            project_identity::BINARY_NAME,
+            project_identity::cli_with("unlock")
+        ))
+    })?;
+    let mut state = state::io::load_state()?;
+    let mut retagged = 0usize;
+    if old_stem != new_stem {
+        for pkg_state in state.packages.values_mut() {
+            if pkg_state.source_module.as_deref() == Some(old_stem.as_str()) {
+                pkg_state.source_module = Some(new_stem.clone());
+                retagged += 1;
+            }
+        }
+    }
+
+    let display = |path: &Path| {
+        path.strip_prefix(&config_dir)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+
+    output::header(if options.dry_run {
+        "Module rename (dry-run)"
+    } else {
+        "Module rename"
+    });
+
+    if options.dry_run {
+        output::info(&format!(
+            "Would rename {} → {}",
+            display(&old_path),
+            display(&new_path)
+        ));
+        if rewrites.is_empty() {
+            output::info("No imports reference this module");
+        } else {
+            output::info("Would update imports in:");
+            for rewrite in &rewrites {
+                output::indent(&format!("• {}", display(&rewrite.path)), 1);
+            }
+        }
+        if retagged > 0 {
+            output::info(&format!(
+                "Would update the source module of {} state entr{}",
+                retagged,
+                if retagged == 1 { "y" } else { "ies" }
+            ));
+        }
+        return Ok(());
+    }
+
+    apply_rename(&old_path, &new_path, &rewrites)?;
+    if options.verbose {
+        for rewrite in &rewrites {
+            output::verbose(&format!("Updated imports in {}", display(&rewrite.path)));
+        }
+    }
+    if retagged > 0
+        && let Err(e) = state::io::save_state_locked(&state, &lock)
+    {
+        roll_back_rename(&old_path, &new_path, &rewrites);
+        return Err(e);
+    }
+
+    output::success(&format!(
+        "Renamed module '{}' to '{}' ({} file{} updated)",
+        options.old,
+        options.new,
+        rewrites.len(),
+        if rewrites.len() == 1 { "" } else { "s" }
+    ));
+    Ok(())
+}
+
+/// Move the module, then write the import rewrites.
+///
+/// A failed write restores the files already rewritten and moves the module
+/// back, so the config never points at a module that is not there.
+fn apply_rename(old_path: &Path, new_path: &Path, rewrites: &[ImportRewrite]) -> Result<()> {
+    if let Some(parent) = new_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(old_path, new_path).map_err(|e| DeclarchError::IoError {
+        path: old_path.to_path_buf(),
+        source: e,
+    })?;
+
+    for (written, rewrite) in rewrites.iter().enumerate() {
+        if let Err(e) = fs::write(&rewrite.path, &rewrite.content) {
+            roll_back_rename(old_path, new_path, &rewrites[..written]);
+            return Err(DeclarchError::IoError {
+                path: rewrite.path.clone(),
+                source: e,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Put back the original content of `written` and move the module back.
+fn roll_back_rename(old_path: &Path, new_path: &Path, written: &[ImportRewrite]) {
+    for rewrite in written {
+        if let Err(e) = fs::write(&rewrite.path, &rewrite.original) {
+            output::warning(&format!(
+                "Could not restore {}: {}",
+                rewrite.path.display(),
+                e
+            ));
+        }
+    }
+    if let Err(e) = fs::rename(new_path, old_path) {
+        output::warning(&format!(
+            "Could not move {} back to {}: {}",
+            new_path.display(),
+            old_path.display(),
+            e
+        ));
+    }
+}
+
+fn validate_module_name(name: &str) -> Result<()> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() || trimmed.ends_with('/') {
+        return Err(DeclarchError::Other(format!(
+            "Invalid module name '{}'",
+            name
+        )));
+    }
+
+    let path = Path::new(trimmed);
+    if path.is_absolute()
+        || path
+            .components()
+            .any(|c| matches!(c, Component::ParentDir | Component::RootDir))
+    {
+        return Err(DeclarchError::Other(format!(
+            "Invalid module name '{}': use a path inside the modules directory",
+            name
+        )));
+    }
+
+    Ok(())
+}
+
+/// `modules/<name>.kdl`, accepting names given with or without the extension.
+fn module_path(modules_dir: &Path, name: &str) -> PathBuf {
+    let trimmed = name.trim();
+    let suffix = format!(".{}", CONFIG_EXTENSION);
+    let base = trimmed.strip_suffix(&suffix).unwrap_or(trimmed);
+    modules_dir.join(format!("{}{}", base, suffix))
+}
+
+/// File stem, as recorded in `PackageState::source_module`.
+fn module_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Every `.kdl` file below `dir`, in a stable order.
+fn collect_kdl_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect();
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            collect_kdl_files(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == CONFIG_EXTENSION) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Rewrite `import`/`imports` strings of one file after the module moves.
+///
+/// `from_dir` is where the file lives now and `to_dir` where it will live
+/// (they differ only for the renamed module itself). Returns `None` when no
+/// import needs to change.
+fn rewrite_imports(
+    content: &str,
+    from_dir: &Path,
+    to_dir: &Path,
+    old_path: &Path,
+    new_path: &Path,
+) -> Result<Option<String>> {
+    let doc: KdlDocument = content
+        .parse()
+        .map_err(|e| DeclarchError::Other(format!("KDL parsing error: {}", e)))?;
+
+    // (span start, span end, current value)
+    let mut candidates: Vec<(usize, usize, String)> = Vec::new();
+    for node in doc.nodes() {
+        if !matches!(node.name().value(), "import" | "imports") {
+            continue;
+        }
+        for entry in node.entries() {
+            if let Some(value) = entry.value().as_string() {
+                let span = entry.span();
+                candidates.push((span.offset(), span.offset() + span.len(), value.to_string()));
+            }
+        }
+        if let Some(children) = node.children() {
+            for child in children.nodes() {
+                let span = child.name().span();
+                candidates.push((
+                    span.offset(),
+                    span.offset() + span.len(),
+                    child.name().value().to_string(),
+                ));
+                for entry in child.entries() {
+                    if let Some(value) = entry.value().as_string() {
+                        let span = entry.span();
+                        candidates.push((
+                            span.offset(),
+                            span.offset() + span.len(),
+                            value.to_string(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut edits = Vec::new();
+    for (start, end, value) in candidates {
+        if let Some(replacement) = rewrite_import(&value, from_dir, to_dir, old_path, new_path)? {
+            edits.push((start, end, value, replacement));
+        }
+    }
+    if edits.is_empty() {
+        return Ok(None);
+    }
+
+    // Splice from the end so earlier offsets stay valid
+    edits.sort_by_key(|(start, ..)| std::cmp::Reverse(*start));
+    let mut updated = content.to_string();
+    for (start, end, value, replacement) in edits {
+        let Some(pos) = updated[start..end].find(&value) else {
+            continue;
+        };
+        let at = start + pos;
+        updated.replace_range(at..at + value.len(), &replacement);
+    }
+
+    Ok(Some(updated))
+}
+
+/// New spelling for one import string, or `None` when it can stay as written.
+fn rewrite_import(
+    import: &str,
+    from_dir: &Path,
+    to_dir: &Path,
+    old_path: &Path,
+    new_path: &Path,
+) -> Result<Option<String>> {
+    let home_relative = import.starts_with("~/");
+    let anchored = home_relative || import.starts_with('/');
+    let has_extension = Path::new(import).extension().is_some();
+
+    let mut target = if home_relative {
+        paths::expand_home(Path::new(import))?
+    } else if anchored {
+        PathBuf::from(import)
+    } else {
+        from_dir.join(import)
+    };
+    if !has_extension {
+        target.set_extension(CONFIG_EXTENSION);
+    }
+    let target = normalize(&target);
+
+    let retargeted = target == normalize(old_path);
+    if !retargeted && (anchored || from_dir == to_dir) {
+        return Ok(None);
+    }
+
+    let destination = if retargeted {
+        normalize(new_path)
+    } else {
+        target
+    };
+    let spell = |path: &Path| {
+        let mut spelled = path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if !has_extension {
+            let suffix = format!(".{}", CONFIG_EXTENSION);
+            if let Some(stripped) = spelled.strip_suffix(&suffix) {
+                spelled = stripped.to_string();
+            }
+        }
+        spelled
+    };
+
+    let spelled = if home_relative {
+        let home = paths::expand_home(Path::new("~"))?;
+        match destination.strip_prefix(&home) {
+            Ok(rest) => format!("~/{}", spell(rest)),
+            Err(_) => destination.display().to_string(),
+        }
+    } else if anchored {
+        destination.display().to_string()
+    } else {
+        let relative = destination.strip_prefix(normalize(to_dir)).map_err(|_| {
+            DeclarchError::Other(format!(
+                "import \"{}\" cannot reach {} without '..'; move it by hand",
+                import,
+                destination.display()
+            ))
+        })?;
+        let spelled = spell(relative);
+        if import.starts_with("./") {
+            format!("./{}", spelled)
+        } else {
+            spelled
+        }
+    };
+
+    Ok((spelled != import).then_some(spelled))
+}
+
+/// Drop `.` components so lexically equal paths compare equal.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rewrite(content: &str, from: &str, to: &str) -> Option<String> {
+        let root = Path::new("/cfg");
+        rewrite_imports(
+            content,
+            &root.join(from),
+            &root.join(to),
+            Path::new("/cfg/modules/base.kdl"),
+            Path::new("/cfg/modules/linux/core.kdl"),
+        )
+        .expect("rewrite should succeed")
+    }
+
+    #[test]
+    fn rewrites_root_imports_and_keeps_layout() {
+        let content = "// root\nimports {\n    \"modules/base.kdl\"  // core\n    \"modules/desktop\"\n}\n\nimports \"modules/base\"\n";
+        let updated = rewrite(content, "", "").expect("root imports base");
+        assert_eq!(
+            updated,
+            "// root\nimports {\n    \"modules/linux/core.kdl\"  // core\n    \"modules/desktop\"\n}\n\nimports \"modules/linux/core\"\n"
+        );
+    }
+
+    #[test]
+    fn rewrites_sibling_imports_and_ignores_unrelated_files() {
+        let sibling = "imports {\n    \"./base.kdl\"\n}\n";
+        assert_eq!(
+            rewrite(sibling, "modules", "modules").as_deref(),
+            Some("imports {\n    \"./linux/core.kdl\"\n}\n")
+        );
+
+        let unrelated = "pkg {\n    aur { base }\n}\nimports { \"desktop.kdl\" }\n";
+        assert_eq!(rewrite(unrelated, "modules", "modules"), None);
+    }
+
+    #[test]
+    fn moved_module_keeps_its_own_imports_pointing_at_the_same_files() {
+        let content = "imports { \"linux/extra.kdl\" }\n";
+        assert_eq!(
+            rewrite(content, "modules", "modules/linux").as_deref(),
+            Some("imports { \"extra.kdl\" }\n")
+        );
+
+        let unreachable = "imports { \"desktop.kdl\" }\n";
+        let err = rewrite_imports(
+            unreachable,
+            Path::new("/cfg/modules"),
+            Path::new("/cfg/modules/linux"),
+            Path::new("/cfg/modules/base.kdl"),
+            Path::new("/cfg/modules/linux/core.kdl"),
+        )
+        .expect_err("sibling import cannot be expressed without '..'");
+        assert!(err.to_string().contains("without '..'"));
+    }
+
+    #[test]
+    fn module_names_are_confined_to_the_modules_directory() {
+        assert!(validate_module_name("linux/notes").is_ok());
+        assert!(validate_module_name("../escape").is_err());
+        assert!(validate_module_name("/etc/passwd").is_err());
+        assert!(validate_module_name(" ").is_err());
+        assert_eq!(
+            module_path(Path::new("/m"), "base.kdl"),
+            PathBuf::from("/m/base.kdl")
+        );
+    }
+
+    #[test]
+    fn failed_import_rewrite_rolls_back_the_rename() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path().join("declarch.kdl");
+        let old_path = dir.path().join("modules/base.kdl");
+        let new_path = dir.path().join("modules/linux/core.kdl");
+        // A directory where a file is expected makes the second write fail
+        let unwritable = dir.path().join("modules/blocked.kdl");
+        fs::create_dir_all(&unwritable).expect("mkdir");
+        fs::write(&root, "imports { \"modules/base\" }\n").expect("write root");
+        fs::write(&old_path, "pkg { aur { bat } }\n").expect("write module");
+
+        let rewrites = vec![
+            ImportRewrite {
+                path: root.clone(),
+                content: "imports { \"modules/linux/core\" }\n".to_string(),
+                original: "imports { \"modules/base\" }\n".to_string(),
+            },
+            ImportRewrite {
+                path: unwritable,
+                content: String::new(),
+                original: String::new(),
+            },
+        ];
+
+        apply_rename(&old_path, &new_path, &rewrites).expect_err("second write fails");
+        assert!(old_path.is_file());
+        assert!(!new_path.exists());
+        assert_eq!(
+            fs::read_to_string(&root).expect("read root"),
+            "imports { \"modules/base\" }\n"
+        );
+
+        apply_rename(&old_path, &new_path, &rewrites[..1]).expect("rename succeeds");
+        assert!(!old_path.exists());
+        assert_eq!(
+            fs::read_to_string(&new_path).expect("read module"),
+            "pkg { aur { bat } }\n"
+        );
+        assert_eq!(
+            fs::read_to_string(&root).expect("read root"),
+            "imports { \"modules/linux/core\" }\n"
+        );
+    }
+}
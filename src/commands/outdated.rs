@@ -0,0 +1,244 @@
+//! Outdated command
+//!
+//! Lists installed packages that have a newer version available, as reported
+//! by each backend's `outdated_cmd`. Read-only: nothing is upgraded and state
+//! is not touched. Backends without `outdated_cmd` are skipped with a note.
+
+use crate::backends::load_all_backends_unified;
+use crate::commands::runtime_overrides::{
+    apply_runtime_backend_overrides, load_runtime_config_for_command,
+};
+use crate::core::types::Backend;
+use crate::error::Result;
+use crate::packages::traits::{OutdatedPackage, PackageManager};
+use crate::project_identity;
+use crate::ui as output;
+use crate::utils::machine_output;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+pub struct OutdatedOptions {
+    /// Target specific backends (None = all backends)
+    pub backends: Option<Vec<String>>,
+    /// Verbose output
+    pub verbose: bool,
+    /// Output format (json, yaml, toml; table otherwise)
+    pub format: Option<String>,
+    pub output_version: Option<String>,
+    /// Write machine output to this file (`--output`) instead of stdout
+    pub output: Option<PathBuf>,
+}
+
+/// Report outdated packages across configured backends
+pub fn run(options: OutdatedOptions) -> Result<()> {
+    let machine_mode = matches!(options.format.as_deref(), Some("json" | "yaml" | "toml"));
+    let mut notes = Vec::new();
+    let managers = collect_outdated_backends(&options, &mut notes)?;
+
+    let mut packages = Vec::new();
+    let mut errors = Vec::new();
+    for (name, manager) in managers {
+        match manager.list_outdated() {
+            Ok(found) => {
+                if options.verbose && !machine_mode {
+                    output::verbose(&format!("{}: {} outdated", name, found.len()));
+                }
+                packages.extend(found);
+            }
+            // One backend failing must not hide the others' results.
+            Err(e) => errors.push(format!("{}: {}", name, e)),
+        }
+    }
+    sort_outdated(&mut packages);
+
+    if machine_mode {
+        return emit_machine(&options, &packages, notes, errors);
+    }
+
+    for note in &notes {
+        output::info(note);
+    }
+    for error in &errors {
+        output::warning(&format!("Could not check {}", error));
+    }
+
+    if packages.is_empty() {
+        output::success("Everything is up to date");
+        return Ok(());
+    }
+
+    output::header(&format!("Outdated Packages ({})", packages.len()));
+    for line in output::format_table(
+        &["BACKEND", "PACKAGE", "CURRENT", "AVAILABLE"],
+        &outdated_rows(&packages),
+    ) {
+        println!("  {}", line);
+    }
+    println!();
+    output::info(&format!(
+        "Run '{}' to upgrade them",
+        project_identity::cli_with("sync upgrade")
+    ));
+
+    Ok(())
+}
+
+fn emit_machine(
+    options: &OutdatedOptions,
+    packages: &[OutdatedPackage],
+    notes: Vec<String>,
+    errors: Vec<String>,
+) -> Result<()> {
+    let format = options.format.as_deref().unwrap_or("json");
+    if options.output_version.as_deref() == Some("v1") {
+        return machine_output::emit_v1(
+            "outdated",
+            packages,
+            notes,
+            errors,
+            format,
+            options.output.as_deref(),
+        );
+    }
+
+    let out = match format {
+        "yaml" => serde_yml::to_string(&serde_json::to_value(packages)?)?,
+        "toml" => machine_output::to_toml_keyed("packages", packages)?,
+        _ => serde_json::to_string_pretty(packages)?,
+    };
+    machine_output::write_report(&out, options.output.as_deref())
+}
+
+/// Resolve configured backends that can report outdated packages.
+///
+/// Backends without `outdated_cmd` or whose binary is missing are summarized
+/// in `notes`; unknown requested backends are warned about.
+fn collect_outdated_backends(
+    options: &OutdatedOptions,
+    notes: &mut Vec<String>,
+) -> Result<Vec<(String, Box<dyn PackageManager>)>> {
+    let all_backends = load_all_backends_unified()?;
+
+    let mut selected: Vec<_> = match &options.backends {
+        Some(target_backends) => {
+            let target_set: HashSet<_> = target_backends.iter().cloned().collect();
+            let mut unknown: Vec<_> = target_set
+                .iter()
+                .filter(|name| !all_backends.contains_key(*name))
+                .cloned()
+                .collect();
+            unknown.sort();
+            if !unknown.is_empty() {
+                notes.push(format!("Unknown backend(s): {}", unknown.join(", ")));
+            }
+            all_backends
+                .into_iter()
+                .filter(|(name, _)| target_set.contains(name))
+                .collect()
+        }
+        None => all_backends.into_iter().collect(),
+    };
+    selected.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let runtime_config = load_runtime_config_for_command("outdated command");
+
+    let mut managers = Vec::new();
+    let mut skipped_no_cmd = Vec::new();
+    let mut skipped_not_available = Vec::new();
+    for (name, mut config) in selected {
+        apply_runtime_backend_overrides(&mut config, &name, &runtime_config);
+        if !crate::utils::platform::backend_supports_current_os(&config) {
+            continue;
+        }
+        if config.outdated_cmd.is_none() {
+            skipped_no_cmd.push(name);
+            continue;
+        }
+
+        let manager: Box<dyn PackageManager> = Box::new(
+            crate::backends::GenericManager::from_config(
+                config,
+                Backend::from(name.as_str()),
+                false,
+            )
+            .with_echo_commands(options.verbose),
+        );
+        if manager.is_available() {
+            managers.push((name, manager));
+        } else {
+            skipped_not_available.push(name);
+        }
+    }
+
+    if !skipped_no_cmd.is_empty() {
+        notes.push(format!(
+            "Skipped (no outdated_cmd): {}",
+            skipped_no_cmd.join(", ")
+        ));
+    }
+    if !skipped_not_available.is_empty() {
+        notes.push(format!(
+            "Skipped (not available): {}",
+            skipped_not_available.join(", ")
+        ));
+    }
+
+    Ok(managers)
+}
+
+fn sort_outdated(packages: &mut [OutdatedPackage]) {
+    packages.sort_by(|a, b| {
+        a.backend
+            .name()
+            .cmp(b.backend.name())
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// Table rows; versions a backend did not report are shown as `?`
+fn outdated_rows(packages: &[OutdatedPackage]) -> Vec<Vec<String>> {
+    packages
+        .iter()
+        .map(|pkg| {
+            vec![
+                pkg.backend.to_string(),
+                pkg.name.clone(),
+                pkg.current.clone().unwrap_or_else(|| "?".to_string()),
+                pkg.available.clone().unwrap_or_else(|| "?".to_string()),
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outdated(backend: &str, name: &str, current: Option<&str>) -> OutdatedPackage {
+        OutdatedPackage {
+            name: name.to_string(),
+            current: current.map(str::to_string),
+            available: Some("2.0".to_string()),
+            backend: Backend::from(backend),
+        }
+    }
+
+    #[test]
+    fn rows_are_grouped_by_backend_and_mark_unknown_versions() {
+        let mut packages = vec![
+            outdated("npm", "typescript", Some("1.0")),
+            outdated("aur", "fd", None),
+            outdated("aur", "bat", Some("1.0")),
+        ];
+        sort_outdated(&mut packages);
+
+        assert_eq!(
+            outdated_rows(&packages),
+            vec![
+                vec!["aur", "bat", "1.0", "2.0"],
+                vec!["aur", "fd", "?", "2.0"],
+                vec!["npm", "typescript", "1.0", "2.0"],
+            ]
+        );
+    }
+}
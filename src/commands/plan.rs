@@ -0,0 +1,151 @@
+//! Hypothetical install plan
+//!
+//! Answers "what would adding this package entail?" before it goes into
+//! config. The package is planned against current state as if it were the
+//! only declared entry; config and state are never written and nothing is
+//! installed.
+
+use crate::commands::sync::{self, PruneReasonMap};
+use crate::config::loader::{self, MergedConfig};
+use crate::core::types::{PackageId, SyncTarget};
+use crate::error::{DeclarchError, Result};
+use crate::state;
+use crate::ui as output;
+use crate::utils::paths;
+use std::collections::HashMap;
+
+pub struct PlanOptions {
+    /// Package to evaluate, as `backend:name`
+    pub package: String,
+    /// Verbose output
+    pub verbose: bool,
+}
+
+pub fn run(options: PlanOptions) -> Result<()> {
+    let pkg_id = parse_plan_target(&options.package)?;
+
+    // Backend definitions, options and excludes still come from the real config.
+    let config_path = paths::config_file()?;
+    let base = if loader::root_config_available(&config_path) {
+        loader::load_root_config(&config_path)?
+    } else {
+        MergedConfig::default()
+    };
+    let mut config = hypothetical_config(base, &pkg_id);
+
+    let state = state::io::load_state()?;
+    let sync_target = SyncTarget::All;
+    let (installed_snapshot, managers, _) = sync::initialize_managers_and_snapshot(
+        &config,
+        &sync::SyncOptions::default(),
+        &sync_target,
+    )?;
+
+    output::header(&format!("Plan: {}", pkg_id));
+
+    let Some(manager) = managers.get(&pkg_id.backend) else {
+        output::warning(&format!(
+            "Backend '{}' is not available on this system; nothing to plan.",
+            pkg_id.backend
+        ));
+        return Ok(());
+    };
+
+    let mut tx = sync::create_transaction(
+        &mut config,
+        &state,
+        &installed_snapshot,
+        &managers,
+        &sync_target,
+        &sync::SyncOptions::default(),
+    )?;
+    // Only the hypothetical package is declared, so prunes and metadata
+    // updates are artifacts of the throwaway config.
+    tx.to_prune.clear();
+    tx.to_update_project_metadata.clear();
+
+    if tx.to_install.is_empty() && tx.to_adopt.is_empty() {
+        if config.is_excluded(&pkg_id) {
+            output::info(&format!(
+                "{} is excluded by config; declaring it would change nothing",
+                pkg_id
+            ));
+        } else {
+            output::success(&format!(
+                "{} is already installed and tracked; declaring it would change nothing",
+                pkg_id
+            ));
+        }
+        return Ok(());
+    }
+
+    let install_sizes = sync::estimate_install_sizes(&tx, &installed_snapshot, &managers);
+    sync::display_transaction_plan(&tx, false, &PruneReasonMap::new(), &install_sizes);
+
+    match manager.get_required_by(&pkg_id.name) {
+        Ok(required_by) if !required_by.is_empty() => {
+            output::info(&format!(
+                "Already required by {} installed package(s): {}",
+                required_by.len(),
+                required_by.join(", ")
+            ));
+        }
+        Ok(_) => {}
+        Err(e) => {
+            if options.verbose {
+                output::verbose(&format!(
+                    "Could not query dependency info for {}: {}",
+                    pkg_id, e
+                ));
+            }
+        }
+    }
+
+    output::info("No changes were made. Add the package to your config and run sync to apply.");
+    Ok(())
+}
+
+fn parse_plan_target(package: &str) -> Result<PackageId> {
+    package.parse::<PackageId>().map_err(DeclarchError::Other)
+}
+
+/// Replace declared packages with just `pkg`, keeping the rest of the config.
+fn hypothetical_config(mut config: MergedConfig, pkg: &PackageId) -> MergedConfig {
+    config.packages = HashMap::from([(pkg.clone(), Vec::new())]);
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Backend;
+
+    #[test]
+    fn plan_target_requires_backend_prefix() {
+        let pkg = parse_plan_target("aur:hyprland").expect("valid target");
+        assert_eq!(pkg.backend, Backend::from("aur"));
+        assert_eq!(pkg.name, "hyprland");
+
+        assert!(parse_plan_target("hyprland").is_err());
+    }
+
+    #[test]
+    fn hypothetical_config_declares_only_the_planned_package() {
+        let mut config = MergedConfig::default();
+        config.packages.insert(
+            PackageId {
+                name: "bat".to_string(),
+                backend: Backend::from("aur"),
+            },
+            Vec::new(),
+        );
+        config.excludes.push("ripgrep".to_string());
+
+        let planned = parse_plan_target("npm:typescript").expect("valid target");
+        let config = hypothetical_config(config, &planned);
+
+        assert_eq!(config.packages.len(), 1);
+        assert!(config.packages.contains_key(&planned));
+        assert_eq!(config.excludes, vec!["ripgrep".to_string()]);
+    }
+}
@@ -7,7 +7,7 @@ use crate::utils::paths;
 /// Returns default config if no root config exists or if parsing fails.
 pub(crate) fn load_runtime_config_for_command(command_name: &str) -> loader::MergedConfig {
     match paths::config_file() {
-        Ok(path) if path.exists() => match loader::load_root_config(&path) {
+        Ok(path) if loader::root_config_available(&path) => match loader::load_root_config(&path) {
             Ok(cfg) => cfg,
             Err(e) => {
                 output::warning(&format!(
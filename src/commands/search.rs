@@ -4,6 +4,7 @@
 //! Results from faster backends are displayed immediately without waiting for slower ones.
 
 mod backend_runtime;
+mod fields;
 mod managed;
 mod matching;
 mod preface;
@@ -22,23 +23,32 @@ use crate::error::Result;
 use crate::project_identity;
 use crate::state;
 use crate::ui as output;
+use crate::utils::machine_output::ReportWriter;
+use crate::utils::remote;
 use serde::Serialize;
 use std::collections::HashMap;
-use std::sync::mpsc;
+use std::path::PathBuf;
+use std::sync::{Arc, mpsc};
 use std::thread;
 use std::time::Duration;
 
-use backend_runtime::{BackendResult, create_manager_from_config, search_single_backend};
+use backend_runtime::{
+    BackendResult, SearchSlots, create_manager_from_config, search_single_backend,
+};
+pub use fields::{SearchField, parse_fields};
 use managed::{collect_managed_hits, run_managed_installed_search};
 #[cfg(test)]
 use matching::normalize_package_name;
 use matching::{
-    canonical_backend_group, is_installed_result, mark_installed, parse_backend_query,
-    should_show_backend_error,
+    canonical_backend_group, description_match_reason, installed_entry, is_installed_result,
+    mark_installed, parse_backend_query, should_show_backend_error,
 };
 use preface::{append_managed_machine_hits, render_human_managed_preface};
 use render::display_backend_results;
-use reporting::{emit_machine_report, emit_no_backends_report, show_human_summary};
+use reporting::{
+    emit_machine_report, emit_no_backends_report, emit_stream_summary, show_human_summary,
+    stream_results,
+};
 use selection::get_backends_to_search;
 use setup::normalize_search_request;
 
@@ -46,17 +56,53 @@ use setup::normalize_search_request;
 const BACKEND_TIMEOUT_SECONDS: u64 = SEARCH_BACKEND_TIMEOUT_SECS;
 /// Local search should feel responsive even with slow/misconfigured backends.
 const LOCAL_BACKEND_TIMEOUT_SECONDS: u64 = 8;
+/// Results shown per backend when no `--limit` is given
+const DEFAULT_RESULT_LIMIT: usize = 10;
+
+/// Per-backend cap for `SearchOptions::limit`: `None` means the default of
+/// 10, `Some(0)` means unlimited.
+fn effective_limit(limit: Option<usize>) -> Option<usize> {
+    match limit {
+        None => Some(DEFAULT_RESULT_LIMIT),
+        Some(0) => None,
+        Some(n) => Some(n),
+    }
+}
+
+/// Truncate to an effective limit; a stray `Some(0)` still keeps everything.
+fn truncate_to_limit<T>(results: &mut Vec<T>, limit: Option<usize>) {
+    if let Some(limit_value) = limit.filter(|n| *n > 0) {
+        results.truncate(limit_value);
+    }
+}
 
 pub struct SearchOptions {
     pub query: String,
     pub backends: Option<Vec<String>>,
+    /// Results per backend: `None` for the default of 10, `Some(0)` for all
     pub limit: Option<usize>,
     pub installed_only: bool,
     pub available_only: bool,
+    /// Keep only results whose name exactly matches the query
+    pub exact: bool,
+    /// Also match the query against descriptions and report the match reason
+    pub in_description: bool,
+    /// Show installed vs available versions for installed results
+    pub installed_version: bool,
     pub local: bool,
+    /// Extra backend definitions to search, overriding same-named ones
+    pub backend_files: Vec<PathBuf>,
+    /// Search at most this many backends at once (`None` = all at once)
+    pub max_parallel: Option<usize>,
+    /// Seconds to wait for backend results (`None` = the default for the mode)
+    pub timeout_secs: Option<u64>,
+    /// Columns/keys to output (`--fields`); `None` keeps the default output
+    pub fields: Option<Vec<SearchField>>,
     pub verbose: bool,
     pub format: Option<String>,
     pub output_version: Option<String>,
+    /// Write machine output to this file (`--output`) instead of stdout
+    pub output: Option<PathBuf>,
 }
 
 #[derive(Debug, Serialize)]
@@ -66,16 +112,33 @@ struct SearchResultOut {
     version: Option<String>,
     description: Option<String>,
     installed: bool,
+    /// Version recorded in state, set for installed results of a repository search
+    #[serde(skip_serializing_if = "Option::is_none")]
+    installed_version: Option<String>,
+    /// Why the result matched (`name` or `description`), set for --in-description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    matched_in: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct SearchReportOut<R> {
+    query: String,
+    local: bool,
+    requested_backends: Option<Vec<String>>,
+    total_matches: usize,
+    shown_results: usize,
+    results: Vec<R>,
 }
 
+/// Final record of a `--format jsonl` search stream
 #[derive(Debug, Serialize)]
-struct SearchReportOut {
+struct SearchSummaryOut {
     query: String,
     local: bool,
     requested_backends: Option<Vec<String>>,
     total_matches: usize,
     shown_results: usize,
-    results: Vec<SearchResultOut>,
+    warnings: Vec<String>,
 }
 
 pub fn run(options: SearchOptions) -> Result<()> {
@@ -87,6 +150,9 @@ pub fn run(options: SearchOptions) -> Result<()> {
     if updated_options.installed_only && !updated_options.local {
         return run_managed_installed_search(&actual_query, &state, &updated_options, machine_mode);
     }
+    if !updated_options.local {
+        remote::ensure_online("Repository search (use --local to search installed packages)")?;
+    }
     let include_managed_hits = !updated_options.local
         && !updated_options.installed_only
         && !updated_options.available_only;
@@ -103,6 +169,10 @@ pub fn run(options: SearchOptions) -> Result<()> {
     let runtime_config = load_runtime_config_for_command("search command");
 
     let mut backend_configs = crate::backends::load_all_backends_unified()?;
+    for path in &options.backend_files {
+        let backend = crate::backends::user_parser::load_backend_definition(path)?;
+        backend_configs.insert(backend.name.clone(), backend);
+    }
     for (name, cfg) in &mut backend_configs {
         apply_runtime_backend_overrides(cfg, name, &runtime_config);
     }
@@ -124,7 +194,7 @@ pub fn run(options: SearchOptions) -> Result<()> {
                 options.local,
                 updated_options.backends.clone(),
                 selection_warnings,
-                options.format.as_deref().unwrap_or("json"),
+                &options,
             )?;
         } else {
             output::warning("No backends available for search");
@@ -136,8 +206,7 @@ pub fn run(options: SearchOptions) -> Result<()> {
         return Ok(());
     }
 
-    // Default limit is 10 if not specified
-    let effective_limit = updated_options.limit.or(Some(10));
+    let effective_limit = effective_limit(updated_options.limit);
 
     // Create channel for streaming results
     let (tx, rx) = mpsc::channel::<BackendResult>();
@@ -145,6 +214,9 @@ pub fn run(options: SearchOptions) -> Result<()> {
     // Spawn a thread for each backend
     let query_clone = actual_query.clone();
     let local_mode = options.local;
+    let exact = updated_options.exact;
+    let in_description = updated_options.in_description;
+    let slots = Arc::new(SearchSlots::new(options.max_parallel));
     for backend in backends_to_search {
         let Some(backend_config) = backend_configs.get(backend.name()).cloned() else {
             output::warning(&format!(
@@ -159,7 +231,7 @@ pub fn run(options: SearchOptions) -> Result<()> {
         // This avoids password prompts/timeouts in non-interactive flows.
         backend_config.needs_sudo = false;
 
-        let manager = match create_manager_from_config(&backend_config) {
+        let manager = match create_manager_from_config(&backend_config, options.verbose) {
             Ok(m) => m,
             Err(e) => {
                 output::warning(&format!("Skipping '{}': {}", backend, e));
@@ -169,8 +241,10 @@ pub fn run(options: SearchOptions) -> Result<()> {
 
         let tx = tx.clone();
         let query = query_clone.clone();
+        let slots = Arc::clone(&slots);
 
         thread::spawn(move || {
+            let _slot = slots.acquire();
             let started_at = std::time::Instant::now();
             let result = search_single_backend(
                 manager,
@@ -178,6 +252,8 @@ pub fn run(options: SearchOptions) -> Result<()> {
                 local_mode,
                 effective_limit,
                 prefer_list_for_local_search,
+                exact,
+                in_description,
             );
             let duration_ms = started_at.elapsed().as_millis();
 
@@ -205,7 +281,10 @@ pub fn run(options: SearchOptions) -> Result<()> {
     // Drop original sender so channel closes when all threads done
     drop(tx);
 
-    // Collect and display results as they arrive
+    // Collect and display results as they arrive; jsonl writes them out immediately
+    let streaming = machine_mode && options.format.as_deref() == Some("jsonl");
+    let mut stream_out = ReportWriter::new(options.output.as_deref());
+    let mut streamed = 0usize;
     let mut total_found = 0;
     let mut has_results = false;
     let mut machine_results: Vec<SearchResultOut> = Vec::new();
@@ -217,6 +296,13 @@ pub fn run(options: SearchOptions) -> Result<()> {
         &mut has_results,
         &mut machine_results,
     );
+    if streaming {
+        streamed += stream_results(
+            &mut stream_out,
+            &mut machine_results,
+            options.fields.as_deref(),
+        )?;
+    }
     if !machine_mode {
         render_human_managed_preface(
             include_managed_hits,
@@ -224,16 +310,17 @@ pub fn run(options: SearchOptions) -> Result<()> {
             &state,
             &actual_query,
             &mut has_results,
+            options.fields.as_deref(),
         );
     }
 
     // Receive results with timeout
     let start_time = std::time::Instant::now();
-    let timeout = Duration::from_secs(if options.local {
+    let timeout = Duration::from_secs(options.timeout_secs.unwrap_or(if options.local {
         LOCAL_BACKEND_TIMEOUT_SECONDS
     } else {
         BACKEND_TIMEOUT_SECONDS
-    });
+    }));
 
     while let Ok(result) = rx.recv_timeout(timeout) {
         match result {
@@ -258,17 +345,34 @@ pub fn run(options: SearchOptions) -> Result<()> {
                             continue;
                         }
                         shown_for_backend += 1;
+                        let installed_version = (!local_mode)
+                            .then(|| installed_entry(&result, &state))
+                            .flatten()
+                            .and_then(|entry| entry.version.clone());
+                        let matched_in = in_description
+                            .then(|| description_match_reason(&result, &actual_query))
+                            .flatten()
+                            .map(ToString::to_string);
                         machine_results.push(SearchResultOut {
                             backend: backend.to_string(),
                             name: result.name,
                             version: result.version,
                             description: result.description,
                             installed,
+                            installed_version,
+                            matched_in,
                         });
                     }
                     if shown_for_backend > 0 {
                         has_results = true;
                     }
+                    if streaming {
+                        streamed += stream_results(
+                            &mut stream_out,
+                            &mut machine_results,
+                            options.fields.as_deref(),
+                        )?;
+                    }
                 } else {
                     // Mark installed packages
                     let mut marked_results = mark_installed(results, &state, local_mode);
@@ -290,6 +394,9 @@ pub fn run(options: SearchOptions) -> Result<()> {
                             &marked_results,
                             backend_total,
                             effective_limit,
+                            in_description.then_some(actual_query.as_str()),
+                            options.installed_version,
+                            options.fields.as_deref(),
                         );
                     }
                 }
@@ -324,7 +431,17 @@ pub fn run(options: SearchOptions) -> Result<()> {
         ));
     }
 
-    if machine_mode {
+    if streaming {
+        emit_stream_summary(
+            &mut stream_out,
+            &actual_query,
+            options.local,
+            updated_options.backends.clone(),
+            total_found,
+            streamed,
+            machine_warnings,
+        )?;
+    } else if machine_mode {
         emit_machine_report(
             &actual_query,
             options.local,
@@ -332,7 +449,7 @@ pub fn run(options: SearchOptions) -> Result<()> {
             total_found,
             machine_results,
             machine_warnings,
-            options.format.as_deref().unwrap_or("json"),
+            &options,
         )?;
     } else {
         show_human_summary(has_results, total_found, effective_limit, &actual_query);
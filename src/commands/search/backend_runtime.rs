@@ -1,6 +1,9 @@
 use crate::core::types::Backend;
 use crate::error::Result;
 use crate::packages::traits::{PackageManager, PackageSearchResult};
+use std::sync::{Condvar, Mutex, PoisonError};
+
+use super::matching::{description_match_reason, is_exact_match};
 
 #[derive(Debug)]
 pub(super) enum BackendResult {
@@ -17,12 +20,68 @@ pub(super) enum BackendResult {
     },
 }
 
+/// Bounds how many backend searches run at once (`--max-parallel`).
+///
+/// Every backend still gets its own thread; threads beyond the limit wait
+/// here until a running search finishes.
+pub(super) struct SearchSlots {
+    limit: Option<usize>,
+    running: Mutex<usize>,
+    freed: Condvar,
+}
+
+impl SearchSlots {
+    /// `None` never blocks, matching the default of searching every backend at once
+    pub(super) fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            running: Mutex::new(0),
+            freed: Condvar::new(),
+        }
+    }
+
+    /// Wait for a free slot; it is released when the guard drops.
+    pub(super) fn acquire(&self) -> SearchSlot<'_> {
+        if let Some(limit) = self.limit {
+            let mut running = self.running.lock().unwrap_or_else(PoisonError::into_inner);
+            while *running >= limit {
+                running = self
+                    .freed
+                    .wait(running)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+            *running += 1;
+        }
+        SearchSlot { slots: self }
+    }
+}
+
+pub(super) struct SearchSlot<'a> {
+    slots: &'a SearchSlots,
+}
+
+impl Drop for SearchSlot<'_> {
+    fn drop(&mut self) {
+        if self.slots.limit.is_some() {
+            let mut running = self
+                .slots
+                .running
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            *running -= 1;
+            self.slots.freed.notify_one();
+        }
+    }
+}
+
 pub(super) fn search_single_backend(
     manager: Box<dyn PackageManager>,
     query: &str,
     local_mode: bool,
     limit: Option<usize>,
     prefer_list_for_local_search: bool,
+    exact: bool,
+    in_description: bool,
 ) -> std::result::Result<(Vec<PackageSearchResult>, usize), String> {
     if local_mode {
         let backend_name = manager.backend_type().name().to_string();
@@ -46,30 +105,38 @@ pub(super) fn search_single_backend(
                     version: meta.version,
                     description: None,
                     backend: Backend::from(backend_name.clone()),
+                    installed_version: None,
                 })
                 .collect()
         };
 
-        let total = results.len();
-        if let Some(limit_value) = limit
-            && results.len() > limit_value
-        {
-            results.truncate(limit_value);
+        if exact {
+            results.retain(|r| is_exact_match(&r.name, query));
         }
+        let total = results.len();
+        super::truncate_to_limit(&mut results, limit);
         Ok((results, total))
     } else {
         if !manager.supports_search() {
             return Err("Does not support search".to_string());
         }
 
-        match manager.search(query) {
+        let search_result = if in_description {
+            manager.search_description(query)
+        } else {
+            manager.search(query)
+        };
+
+        match search_result {
             Ok(mut results) => {
-                let total = results.len();
-                if let Some(limit_value) = limit
-                    && results.len() > limit_value
-                {
-                    results.truncate(limit_value);
+                if in_description {
+                    results.retain(|r| description_match_reason(r, query).is_some());
+                }
+                if exact {
+                    results.retain(|r| is_exact_match(&r.name, query));
                 }
+                let total = results.len();
+                super::truncate_to_limit(&mut results, limit);
                 Ok((results, total))
             }
             Err(e) => Err(format!("Search failed: {}", e)),
@@ -79,13 +146,12 @@ pub(super) fn search_single_backend(
 
 pub(super) fn create_manager_from_config(
     config: &crate::backends::config::BackendConfig,
+    verbose: bool,
 ) -> Result<Box<dyn PackageManager>> {
     use crate::backends::GenericManager;
 
     let backend = Backend::from(config.name.clone());
-    Ok(Box::new(GenericManager::from_config(
-        config.clone(),
-        backend,
-        false,
-    )))
+    Ok(Box::new(
+        GenericManager::from_config(config.clone(), backend, false).with_echo_commands(verbose),
+    ))
 }
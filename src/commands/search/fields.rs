@@ -0,0 +1,114 @@
+//! Output columns chosen with `search --fields`
+
+use super::SearchResultOut;
+use crate::error::{DeclarchError, Result};
+use crate::packages::traits::PackageSearchResult;
+use colored::Colorize;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchField {
+    Name,
+    Version,
+    Description,
+    Backend,
+    Installed,
+}
+
+impl SearchField {
+    const ALL: [SearchField; 5] = [
+        SearchField::Name,
+        SearchField::Version,
+        SearchField::Description,
+        SearchField::Backend,
+        SearchField::Installed,
+    ];
+
+    /// Field name as given to `--fields` and used as the machine output key
+    pub fn key(self) -> &'static str {
+        match self {
+            SearchField::Name => "name",
+            SearchField::Version => "version",
+            SearchField::Description => "description",
+            SearchField::Backend => "backend",
+            SearchField::Installed => "installed",
+        }
+    }
+}
+
+/// Parse `--fields` values; an empty list keeps the default output.
+pub fn parse_fields(raw: &[String]) -> Result<Option<Vec<SearchField>>> {
+    if raw.is_empty() {
+        return Ok(None);
+    }
+    let mut fields = Vec::new();
+    for value in raw {
+        let value = value.trim().to_lowercase();
+        let field = SearchField::ALL
+            .into_iter()
+            .find(|field| field.key() == value)
+            .ok_or_else(|| {
+                DeclarchError::Other(format!(
+                    "Unknown search field '{}'. Valid fields: {}",
+                    value,
+                    SearchField::ALL.map(SearchField::key).join(", ")
+                ))
+            })?;
+        if !fields.contains(&field) {
+            fields.push(field);
+        }
+    }
+    Ok(Some(fields))
+}
+
+/// A machine-output result limited to the `--fields` keys, in their order
+pub(super) struct SelectedResult<'a> {
+    pub result: &'a SearchResultOut,
+    pub fields: &'a [SearchField],
+}
+
+impl Serialize for SelectedResult<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let result = self.result;
+        let mut out = serializer.serialize_struct("SearchResultOut", self.fields.len())?;
+        for field in self.fields {
+            match field {
+                SearchField::Name => out.serialize_field("name", &result.name)?,
+                SearchField::Version => out.serialize_field("version", &result.version)?,
+                SearchField::Description => {
+                    out.serialize_field("description", &result.description)?
+                }
+                SearchField::Backend => out.serialize_field("backend", &result.backend)?,
+                SearchField::Installed => out.serialize_field("installed", &result.installed)?,
+            }
+        }
+        out.end()
+    }
+}
+
+/// Human-output cells of one result (already marked by `mark_installed`)
+pub(super) fn field_cells(result: &PackageSearchResult, fields: &[SearchField]) -> Vec<String> {
+    let installed = result.name.contains('✓');
+    fields
+        .iter()
+        .map(|field| match field {
+            SearchField::Name => {
+                let name = result.name.trim_end_matches('✓').trim_end();
+                if installed {
+                    name.green().to_string()
+                } else {
+                    name.cyan().to_string()
+                }
+            }
+            SearchField::Version => result.version.clone().unwrap_or_else(|| "-".to_string()),
+            SearchField::Description => result
+                .description
+                .as_deref()
+                .unwrap_or_default()
+                .dimmed()
+                .to_string(),
+            SearchField::Backend => result.backend.to_string(),
+            SearchField::Installed => if installed { "yes" } else { "no" }.to_string(),
+        })
+        .collect()
+}
@@ -1,14 +1,14 @@
-use super::{SearchOptions, SearchReportOut, SearchResultOut};
+use super::reporting::emit_machine_report;
+use super::{SearchOptions, SearchResultOut};
 use crate::core::types::Backend;
 use crate::error::Result;
 use crate::packages::traits::PackageSearchResult;
 use crate::state;
 use crate::ui as output;
-use crate::utils::machine_output;
 use colored::Colorize;
 use std::collections::{HashMap, HashSet};
 
-use super::matching::mark_installed;
+use super::matching::{is_exact_match, mark_installed};
 use super::render::{display_backend_results, sorted_backend_keys};
 
 pub(super) fn run_managed_installed_search(
@@ -31,26 +31,21 @@ pub(super) fn run_managed_installed_search(
                         version: result.version.clone(),
                         description: None,
                         installed: true,
+                        installed_version: result.installed_version.clone(),
+                        matched_in: None,
                     });
                 }
             }
         }
-        let report = SearchReportOut {
-            query: query.to_string(),
-            local: true,
-            requested_backends: options.backends.clone(),
-            total_matches: out_results.len(),
-            shown_results: out_results.len(),
-            results: out_results,
-        };
-        machine_output::emit_v1(
-            "search",
-            report,
-            Vec::new(),
+        return emit_machine_report(
+            query,
+            true,
+            options.backends.clone(),
+            out_results.len(),
+            out_results,
             Vec::new(),
-            options.format.as_deref().unwrap_or("json"),
-        )?;
-        return Ok(());
+            options,
+        );
     }
 
     println!();
@@ -73,7 +68,7 @@ pub(super) fn run_managed_installed_search(
     }
 
     let mut total_found = 0usize;
-    let limit = options.limit.or(Some(10));
+    let limit = super::effective_limit(options.limit);
     let mut has_results = false;
 
     for backend_name in backends {
@@ -82,16 +77,20 @@ pub(super) fn run_managed_installed_search(
         let backend = Backend::from(backend_name.clone());
         let backend_total = results.len();
 
-        if let Some(limit_value) = limit
-            && results.len() > limit_value
-        {
-            results.truncate(limit_value);
-        }
+        super::truncate_to_limit(&mut results, limit);
 
         let marked_results = mark_installed(results, state, true);
         if !marked_results.is_empty() {
             has_results = true;
-            display_backend_results(&backend, &marked_results, backend_total, limit);
+            display_backend_results(
+                &backend,
+                &marked_results,
+                backend_total,
+                limit,
+                None,
+                false,
+                options.fields.as_deref(),
+            );
         }
     }
 
@@ -101,7 +100,7 @@ pub(super) fn run_managed_installed_search(
             && total_found > limit_value
         {
             output::info(&format!(
-                "Showing limited results. Use --limit 0 for all {} matches.",
+                "Showing limited results. Use --all (or --limit 0) for all {} matches.",
                 total_found
             ));
         }
@@ -138,7 +137,12 @@ pub(super) fn collect_managed_hits(
         } else {
             pkg.config_name.as_str()
         };
-        if !search_name.to_lowercase().contains(&query_lower) {
+        let matched = if options.exact {
+            is_exact_match(search_name, query)
+        } else {
+            search_name.to_lowercase().contains(&query_lower)
+        };
+        if !matched {
             continue;
         }
 
@@ -150,6 +154,7 @@ pub(super) fn collect_managed_hits(
                 version: pkg.version.clone(),
                 description: None,
                 backend: pkg.backend.clone(),
+                installed_version: pkg.version.clone(),
             });
     }
 
@@ -36,8 +36,9 @@ pub(super) fn mark_installed(
         }
     } else {
         for result in &mut results {
-            if is_installed_result(result, state, local_mode) {
+            if let Some(entry) = installed_entry(result, state) {
                 result.name = format!("{} ✓", result.name);
+                result.installed_version = entry.version.clone();
             }
         }
     }
@@ -49,28 +50,75 @@ pub(super) fn is_installed_result(
     state: &state::types::State,
     local_mode: bool,
 ) -> bool {
-    if local_mode {
-        return true;
-    }
+    local_mode || installed_entry(result, state).is_some()
+}
 
+/// State entry tracking the package behind a repository search result.
+pub(super) fn installed_entry<'a>(
+    result: &PackageSearchResult,
+    state: &'a state::types::State,
+) -> Option<&'a state::types::PackageState> {
     let exact_pkg = PackageId {
         name: result.name.clone(),
         backend: result.backend.clone(),
     };
     let exact_key = resolver::make_state_key(&exact_pkg);
-    if state.packages.contains_key(&exact_key) {
-        return true;
+    if let Some(entry) = state.packages.get(&exact_key) {
+        return Some(entry);
     }
 
     let normalized_name = normalize_package_name(&result.name);
     let result_backend_group = canonical_backend_group(result.backend.name());
 
-    state.packages.values().any(|pkg| {
+    state.packages.values().find(|pkg| {
         normalize_package_name(&pkg.config_name) == normalized_name
             && canonical_backend_group(pkg.backend.name()) == result_backend_group
     })
 }
 
+/// `installed X → available Y` when the installed version differs from the available one.
+pub(super) fn version_drift(result: &PackageSearchResult) -> Option<String> {
+    let installed = result.installed_version.as_deref()?;
+    let available = result.version.as_deref()?;
+    (installed != available).then(|| format!("installed {} → available {}", installed, available))
+}
+
+/// Check whether a result name exactly equals the query, ignoring the
+/// installed marker (`✓`) and any repository prefix (`extra/bat`).
+pub(super) fn is_exact_match(name: &str, query: &str) -> bool {
+    let name = name.trim_end_matches('✓').trim_end();
+    normalize_package_name(name) == query
+}
+
+/// Why a result matches `query` in description mode: every query word must
+/// appear in the name or the description. Returns `None` when it doesn't match.
+pub(super) fn description_match_reason(
+    result: &PackageSearchResult,
+    query: &str,
+) -> Option<&'static str> {
+    let name = result.name.to_lowercase();
+    let description = result
+        .description
+        .as_deref()
+        .unwrap_or_default()
+        .to_lowercase();
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if words.is_empty() {
+        return None;
+    }
+
+    if words.iter().all(|w| name.contains(w.as_str())) {
+        Some("name")
+    } else if words
+        .iter()
+        .all(|w| name.contains(w.as_str()) || description.contains(w.as_str()))
+    {
+        Some("description")
+    } else {
+        None
+    }
+}
+
 pub(super) fn normalize_package_name(name: &str) -> &str {
     name.rsplit('/').next().unwrap_or(name)
 }
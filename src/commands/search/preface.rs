@@ -5,9 +5,9 @@ use crate::ui as output;
 use colored::Colorize;
 use std::collections::HashMap;
 
-use super::SearchResultOut;
 use super::matching::mark_installed;
 use super::render::{display_backend_results, sorted_backend_keys};
+use super::{SearchField, SearchResultOut};
 
 pub(super) fn append_managed_machine_hits(
     include_managed_hits: bool,
@@ -30,6 +30,8 @@ pub(super) fn append_managed_machine_hits(
                     version: result.version.clone(),
                     description: None,
                     installed: true,
+                    installed_version: result.installed_version.clone(),
+                    matched_in: None,
                 });
             }
             *has_results = *has_results || !results.is_empty();
@@ -43,6 +45,7 @@ pub(super) fn render_human_managed_preface(
     state: &state::types::State,
     actual_query: &str,
     has_results: &mut bool,
+    fields: Option<&[SearchField]>,
 ) {
     println!();
     output::info(&format!(
@@ -64,6 +67,9 @@ pub(super) fn render_human_managed_preface(
                     &marked_results,
                     marked_results.len(),
                     None,
+                    None,
+                    false,
+                    fields,
                 );
             }
         }
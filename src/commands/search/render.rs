@@ -1,5 +1,8 @@
+use super::fields::{SearchField, field_cells};
+use super::matching::{description_match_reason, version_drift};
 use crate::core::types::Backend;
 use crate::packages::traits::PackageSearchResult;
+use crate::ui;
 use colored::Colorize;
 use std::collections::HashMap;
 
@@ -17,6 +20,9 @@ pub(super) fn display_backend_results(
     results: &[PackageSearchResult],
     total_found: usize,
     limit: Option<usize>,
+    description_query: Option<&str>,
+    show_installed_version: bool,
+    fields: Option<&[SearchField]>,
 ) {
     println!("{}", format!("{}:", backend).cyan().bold());
 
@@ -31,24 +37,47 @@ pub(super) fn display_backend_results(
         );
     }
 
-    for result in results {
-        print_search_result(result);
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .map(|result| {
+            if let Some(fields) = fields {
+                return field_cells(result, fields);
+            }
+            let matched_in =
+                description_query.and_then(|query| description_match_reason(result, query));
+            search_result_row(result, matched_in, show_installed_version)
+        })
+        .collect();
+    for line in ui::format_table(&[], &rows) {
+        println!("  {}", line);
     }
 
     println!();
 }
 
-/// Print a single search result
-pub(super) fn print_search_result(result: &PackageSearchResult) {
-    let name_colored = if result.name.contains('✓') {
-        result.name.green()
+/// Name and description cells for one result, noting why it matched in description mode
+/// and, with `--installed-version`, when the installed version lags the available one
+pub(super) fn search_result_row(
+    result: &PackageSearchResult,
+    matched_in: Option<&str>,
+    show_installed_version: bool,
+) -> Vec<String> {
+    let mut name_colored = if result.name.contains('✓') {
+        result.name.green().to_string()
     } else {
-        result.name.cyan()
+        result.name.cyan().to_string()
     };
-
-    if let Some(ref desc) = result.description {
-        println!("  {} - {}", name_colored, desc.as_str().dimmed());
-    } else {
-        println!("  {}", name_colored);
+    if show_installed_version && let Some(drift) = version_drift(result) {
+        name_colored = format!("{} {}", name_colored, format!("({})", drift).yellow());
     }
+    let reason = matched_in
+        .map(|reason| format!("[matched: {}]", reason).dimmed().to_string())
+        .unwrap_or_default();
+
+    let details = match result.description.as_deref() {
+        Some(desc) if reason.is_empty() => desc.dimmed().to_string(),
+        Some(desc) => format!("{} {}", desc.dimmed(), reason),
+        None => reason,
+    };
+    vec![name_colored, details]
 }
@@ -1,27 +1,34 @@
-use super::{SearchReportOut, SearchResultOut};
+use super::fields::{SearchField, SelectedResult};
+use super::{SearchOptions, SearchReportOut, SearchResultOut, SearchSummaryOut};
 use crate::error::Result;
 use crate::ui as output;
-use crate::utils::machine_output;
+use crate::utils::machine_output::{self, ReportWriter};
 use colored::Colorize;
+use serde::Serialize;
 
 pub(super) fn emit_no_backends_report(
     query: &str,
     local: bool,
     requested_backends: Option<Vec<String>>,
     warnings: Vec<String>,
-    format: &str,
+    options: &SearchOptions,
 ) -> Result<()> {
+    if options.format.as_deref() == Some("jsonl") {
+        let mut out = ReportWriter::new(options.output.as_deref());
+        return emit_stream_summary(&mut out, query, local, requested_backends, 0, 0, warnings);
+    }
     let report = SearchReportOut {
         query: query.to_string(),
         local,
         requested_backends,
         total_matches: 0,
         shown_results: 0,
-        results: Vec::new(),
+        results: Vec::<SearchResultOut>::new(),
     };
-    machine_output::emit_v1("search", report, warnings, Vec::new(), format)
+    emit_v1(report, warnings, options)
 }
 
+/// Emit the search report; with `--fields`, results carry only those keys.
 pub(super) fn emit_machine_report(
     query: &str,
     local: bool,
@@ -29,7 +36,42 @@ pub(super) fn emit_machine_report(
     total_matches: usize,
     results: Vec<SearchResultOut>,
     warnings: Vec<String>,
-    format: &str,
+    options: &SearchOptions,
+) -> Result<()> {
+    let Some(fields) = options.fields.as_deref() else {
+        return emit_report(
+            query,
+            local,
+            requested_backends,
+            total_matches,
+            results,
+            warnings,
+            options,
+        );
+    };
+    let selected: Vec<SelectedResult> = results
+        .iter()
+        .map(|result| SelectedResult { result, fields })
+        .collect();
+    emit_report(
+        query,
+        local,
+        requested_backends,
+        total_matches,
+        selected,
+        warnings,
+        options,
+    )
+}
+
+fn emit_report<R: Serialize>(
+    query: &str,
+    local: bool,
+    requested_backends: Option<Vec<String>>,
+    total_matches: usize,
+    results: Vec<R>,
+    warnings: Vec<String>,
+    options: &SearchOptions,
 ) -> Result<()> {
     let report = SearchReportOut {
         query: query.to_string(),
@@ -39,7 +81,62 @@ pub(super) fn emit_machine_report(
         shown_results: results.len(),
         results,
     };
-    machine_output::emit_v1("search", report, warnings, Vec::new(), format)
+    emit_v1(report, warnings, options)
+}
+
+fn emit_v1<T: Serialize>(report: T, warnings: Vec<String>, options: &SearchOptions) -> Result<()> {
+    machine_output::emit_v1(
+        "search",
+        report,
+        warnings,
+        Vec::new(),
+        options.format.as_deref().unwrap_or("json"),
+        options.output.as_deref(),
+    )
+}
+
+/// Write pending results as `result` records and clear them; returns how many were written.
+pub(super) fn stream_results(
+    out: &mut ReportWriter,
+    results: &mut Vec<SearchResultOut>,
+    fields: Option<&[SearchField]>,
+) -> Result<usize> {
+    let count = results.len();
+    for result in results.drain(..) {
+        match fields {
+            Some(fields) => machine_output::emit_v1_stream(
+                out,
+                "search",
+                "result",
+                &SelectedResult {
+                    result: &result,
+                    fields,
+                },
+            )?,
+            None => machine_output::emit_v1_stream(out, "search", "result", &result)?,
+        }
+    }
+    Ok(count)
+}
+
+pub(super) fn emit_stream_summary(
+    out: &mut ReportWriter,
+    query: &str,
+    local: bool,
+    requested_backends: Option<Vec<String>>,
+    total_matches: usize,
+    shown_results: usize,
+    warnings: Vec<String>,
+) -> Result<()> {
+    let summary = SearchSummaryOut {
+        query: query.to_string(),
+        local,
+        requested_backends,
+        total_matches,
+        shown_results,
+        warnings,
+    };
+    machine_output::emit_v1_stream(out, "search", "summary", &summary)
 }
 
 pub(super) fn show_human_summary(
@@ -54,7 +151,7 @@ pub(super) fn show_human_summary(
             && total_found > limit
         {
             output::info(&format!(
-                "Showing limited results. Use --limit 0 for all {} matches.",
+                "Showing limited results. Use --all (or --limit 0) for all {} matches.",
                 total_found
             ));
         }
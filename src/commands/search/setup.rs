@@ -21,13 +21,24 @@ pub(super) fn normalize_search_request(
         limit: options.limit,
         installed_only: options.installed_only,
         available_only: options.available_only,
+        exact: options.exact,
+        in_description: options.in_description,
+        installed_version: options.installed_version,
         local: options.local,
+        backend_files: options.backend_files.clone(),
+        max_parallel: options.max_parallel,
+        timeout_secs: options.timeout_secs,
+        fields: options.fields.clone(),
         verbose: options.verbose,
         format: options.format.clone(),
         output_version: options.output_version.clone(),
+        output: options.output.clone(),
     };
     let machine_mode = matches!(options.output_version.as_deref(), Some("v1"))
-        && matches!(options.format.as_deref(), Some("json" | "yaml"));
+        && matches!(
+            options.format.as_deref(),
+            Some("json" | "jsonl" | "yaml" | "toml")
+        );
 
     Ok((updated_options, actual_query, machine_mode))
 }
@@ -182,10 +182,18 @@ fn selection_warnings_are_quiet_in_auto_mode_without_verbose() {
         limit: None,
         installed_only: false,
         available_only: false,
+        exact: false,
+        in_description: false,
+        installed_version: false,
         local: false,
+        backend_files: Vec::new(),
+        max_parallel: None,
+        timeout_secs: None,
+        fields: None,
         verbose: false,
         format: None,
         output_version: None,
+        output: None,
     };
     assert!(!super::selection::should_emit_selection_warning(
         &auto_quiet
@@ -214,6 +222,7 @@ fn installed_match_handles_repo_prefix_and_backend_alias() {
             version: Some("0.25.0".to_string()),
             install_reason: Some("declared".to_string()),
             source_module: None,
+            pinned: false,
             last_seen_at: None,
             backend_meta: None,
         },
@@ -224,11 +233,51 @@ fn installed_match_handles_repo_prefix_and_backend_alias() {
         version: Some("0.25.0".to_string()),
         description: None,
         backend: Backend::from("pacman"),
+        installed_version: None,
     };
 
     assert!(is_installed_result(&result, &state, false));
 }
 
+#[test]
+fn mark_installed_attaches_installed_version() {
+    let mut state = State::default();
+    state.packages.insert(
+        "aur:bat".to_string(),
+        PackageState {
+            backend: Backend::from("aur"),
+            config_name: "bat".to_string(),
+            provides_name: "bat".to_string(),
+            actual_package_name: None,
+            installed_at: Utc::now(),
+            version: Some("0.24.0".to_string()),
+            install_reason: Some("declared".to_string()),
+            source_module: None,
+            pinned: false,
+            last_seen_at: None,
+            backend_meta: None,
+        },
+    );
+    let result = |name: &str| PackageSearchResult {
+        name: name.to_string(),
+        version: Some("0.25.0".to_string()),
+        description: None,
+        backend: Backend::from("aur"),
+        installed_version: None,
+    };
+
+    let marked = mark_installed(vec![result("bat"), result("bat-extras")], &state, false);
+
+    assert_eq!(marked[0].name, "bat ✓");
+    assert_eq!(marked[0].installed_version.as_deref(), Some("0.24.0"));
+    assert_eq!(
+        super::matching::version_drift(&marked[0]).as_deref(),
+        Some("installed 0.24.0 → available 0.25.0")
+    );
+    assert_eq!(marked[1].installed_version, None);
+    assert_eq!(super::matching::version_drift(&marked[1]), None);
+}
+
 #[test]
 fn collect_managed_hits_respects_backend_filter() {
     let mut state = State::default();
@@ -243,6 +292,7 @@ fn collect_managed_hits_respects_backend_filter() {
             version: Some("0.25.0".to_string()),
             install_reason: Some("declared".to_string()),
             source_module: None,
+            pinned: false,
             last_seen_at: None,
             backend_meta: None,
         },
@@ -258,6 +308,7 @@ fn collect_managed_hits_respects_backend_filter() {
             version: Some("2.12.2".to_string()),
             install_reason: Some("declared".to_string()),
             source_module: None,
+            pinned: false,
             last_seen_at: None,
             backend_meta: None,
         },
@@ -269,13 +320,268 @@ fn collect_managed_hits_respects_backend_filter() {
         limit: None,
         installed_only: false,
         available_only: false,
+        exact: false,
+        in_description: false,
+        installed_version: false,
         local: false,
+        backend_files: Vec::new(),
+        max_parallel: None,
+        timeout_secs: None,
+        fields: None,
         verbose: false,
         format: None,
         output_version: None,
+        output: None,
     };
 
     let grouped = collect_managed_hits("hello", &state, &options);
     assert_eq!(grouped.len(), 1);
     assert!(grouped.contains_key("brew"));
 }
+
+#[test]
+fn exact_match_ignores_installed_marker_and_repo_prefix() {
+    use super::matching::is_exact_match;
+
+    assert!(is_exact_match("bat", "bat"));
+    assert!(is_exact_match("bat ✓", "bat"));
+    assert!(is_exact_match("extra/bat", "bat"));
+    assert!(!is_exact_match("bat-extras", "bat"));
+    assert!(!is_exact_match("batsignal ✓", "bat"));
+}
+
+#[test]
+fn collect_managed_hits_exact_skips_partial_matches() {
+    let mut state = State::default();
+    for name in ["bat", "bat-extras"] {
+        state.packages.insert(
+            format!("aur:{}", name),
+            PackageState {
+                backend: Backend::from("aur"),
+                config_name: name.to_string(),
+                provides_name: name.to_string(),
+                actual_package_name: None,
+                installed_at: Utc::now(),
+                version: None,
+                install_reason: Some("declared".to_string()),
+                source_module: None,
+                pinned: false,
+                last_seen_at: None,
+                backend_meta: None,
+            },
+        );
+    }
+
+    let options = SearchOptions {
+        query: "bat".to_string(),
+        backends: None,
+        limit: None,
+        installed_only: false,
+        available_only: false,
+        exact: true,
+        in_description: false,
+        installed_version: false,
+        local: false,
+        backend_files: Vec::new(),
+        max_parallel: None,
+        timeout_secs: None,
+        fields: None,
+        verbose: false,
+        format: None,
+        output_version: None,
+        output: None,
+    };
+
+    let grouped = collect_managed_hits("bat", &state, &options);
+    let names: Vec<_> = grouped["aur"].iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["bat"]);
+}
+
+struct ManyResultsManager {
+    count: usize,
+}
+
+impl crate::packages::PackageManager for ManyResultsManager {
+    fn backend_type(&self) -> Backend {
+        Backend::from("npm")
+    }
+
+    fn list_installed(&self) -> Result<HashMap<String, crate::core::types::PackageMetadata>> {
+        Ok(HashMap::new())
+    }
+
+    fn install(&self, _packages: &[String]) -> Result<()> {
+        Ok(())
+    }
+
+    fn remove(&self, _packages: &[String]) -> Result<()> {
+        Ok(())
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn get_required_by(&self, _package: &str) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    fn supports_search(&self) -> bool {
+        true
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<PackageSearchResult>> {
+        Ok((0..self.count)
+            .map(|i| PackageSearchResult {
+                name: format!("{}-{}", query, i),
+                version: None,
+                description: None,
+                backend: Backend::from("npm"),
+                installed_version: None,
+            })
+            .collect())
+    }
+}
+
+#[test]
+fn limit_none_defaults_to_ten_and_zero_means_unlimited() {
+    assert_eq!(effective_limit(None), Some(DEFAULT_RESULT_LIMIT));
+    assert_eq!(effective_limit(Some(0)), None);
+    assert_eq!(effective_limit(Some(3)), Some(3));
+}
+
+#[test]
+fn search_single_backend_applies_effective_limit() {
+    let search = |limit: Option<usize>| {
+        search_single_backend(
+            Box::new(ManyResultsManager { count: 15 }),
+            "tool",
+            false,
+            effective_limit(limit),
+            false,
+            false,
+            false,
+        )
+        .expect("search succeeds")
+    };
+
+    let (results, total) = search(None);
+    assert_eq!((results.len(), total), (10, 15));
+
+    let (results, total) = search(Some(0));
+    assert_eq!((results.len(), total), (15, 15));
+
+    let (results, total) = search(Some(3));
+    assert_eq!((results.len(), total), (3, 15));
+}
+
+#[test]
+fn truncate_to_limit_never_truncates_to_zero() {
+    let mut results = vec![1, 2, 3];
+    truncate_to_limit(&mut results, Some(0));
+    assert_eq!(results.len(), 3);
+    truncate_to_limit(&mut results, Some(2));
+    assert_eq!(results, [1, 2]);
+}
+
+#[test]
+fn search_slots_bound_concurrent_backend_searches() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let slots = Arc::new(SearchSlots::new(Some(2)));
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let (slots, running, peak) =
+                (Arc::clone(&slots), Arc::clone(&running), Arc::clone(&peak));
+            thread::spawn(move || {
+                let _slot = slots.acquire();
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(20));
+                running.fetch_sub(1, Ordering::SeqCst);
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let peak = peak.load(Ordering::SeqCst);
+    assert!((1..=2).contains(&peak), "peak concurrency {}", peak);
+}
+
+#[test]
+fn search_slots_without_limit_never_block() {
+    let slots = SearchSlots::new(None);
+    let held: Vec<_> = (0..16).map(|_| slots.acquire()).collect();
+    assert_eq!(held.len(), 16);
+}
+
+#[test]
+fn fields_parse_case_insensitively_and_reject_unknown_names() {
+    assert_eq!(parse_fields(&[]).unwrap(), None);
+    assert_eq!(
+        parse_fields(&[
+            "Name".to_string(),
+            "version".to_string(),
+            "name".to_string()
+        ])
+        .unwrap(),
+        Some(vec![SearchField::Name, SearchField::Version])
+    );
+
+    let err = parse_fields(&["size".to_string()]).unwrap_err().to_string();
+    assert!(err.contains("Unknown search field 'size'"));
+    assert!(err.contains("name, version, description, backend, installed"));
+}
+
+#[test]
+fn selected_fields_limit_machine_keys_and_human_cells() {
+    let out = SearchResultOut {
+        backend: "aur".to_string(),
+        name: "bat".to_string(),
+        version: None,
+        description: Some("cat clone".to_string()),
+        installed: true,
+        installed_version: Some("0.24.0".to_string()),
+        matched_in: None,
+    };
+    let fields = [
+        SearchField::Name,
+        SearchField::Version,
+        SearchField::Installed,
+    ];
+    let json = serde_json::to_value(super::fields::SelectedResult {
+        result: &out,
+        fields: &fields,
+    })
+    .unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"name": "bat", "version": null, "installed": true})
+    );
+
+    let result = PackageSearchResult {
+        name: "bat ✓".to_string(),
+        version: Some("0.24.0".to_string()),
+        description: None,
+        backend: Backend::from("aur"),
+        installed_version: None,
+    };
+    let cells = super::fields::field_cells(
+        &result,
+        &[
+            SearchField::Backend,
+            SearchField::Name,
+            SearchField::Installed,
+        ],
+    );
+    assert_eq!(cells.len(), 3);
+    assert_eq!(cells[0], "aur");
+    assert!(cells[1].contains("bat") && !cells[1].contains('✓'));
+    assert_eq!(cells[2], "yes");
+}
@@ -1,6 +1,7 @@
 use crate::error::{DeclarchError, Result};
 use crate::project_identity;
 use crate::ui as output;
+use crate::utils::remote;
 use crate::utils::update_check::{
     compare_versions, current_version, detect_install_owner, is_managed_by_package_manager,
     latest_version_live,
@@ -25,6 +26,8 @@ pub struct SelfUpdateOptions {
 }
 
 pub fn run(options: SelfUpdateOptions) -> Result<()> {
+    remote::ensure_online("Self-update")?;
+
     let current = current_version();
     let target = match options.version {
         Some(version) => normalize_requested_version(&version)?,
@@ -0,0 +1,131 @@
+//! Show command
+//!
+//! Prints details about one package (version, description, homepage,
+//! dependencies) as reported by its backend's `info_cmd`. Read-only: the
+//! package does not need to be declared or installed.
+
+use crate::backends::{GenericManager, load_all_backends_unified};
+use crate::commands::runtime_overrides::{
+    apply_runtime_backend_overrides, load_runtime_config_for_command,
+};
+use crate::core::types::PackageId;
+use crate::error::{DeclarchError, Result};
+use crate::packages::traits::{PackageInfo, PackageManager};
+use crate::ui as output;
+use crate::utils::machine_output;
+use std::path::PathBuf;
+
+pub struct ShowOptions {
+    /// Package to show, as `backend:name`
+    pub package: String,
+    /// Output format (json, yaml, toml; details otherwise)
+    pub format: Option<String>,
+    pub output_version: Option<String>,
+    /// Write machine output to this file (`--output`) instead of stdout
+    pub output: Option<PathBuf>,
+}
+
+pub fn run(options: ShowOptions) -> Result<()> {
+    let pkg_id = options
+        .package
+        .parse::<PackageId>()
+        .map_err(DeclarchError::Other)?;
+    let manager = info_manager(&pkg_id)?;
+    let info = manager.info(&pkg_id.name)?;
+
+    if let Some(format @ ("json" | "yaml" | "toml")) = options.format.as_deref() {
+        if options.output_version.as_deref() == Some("v1") {
+            return machine_output::emit_v1(
+                "show",
+                &info,
+                Vec::new(),
+                Vec::new(),
+                format,
+                options.output.as_deref(),
+            );
+        }
+        let out = match format {
+            "yaml" => serde_yml::to_string(&serde_json::to_value(&info)?)?,
+            "toml" => toml::to_string_pretty(&info)?,
+            _ => serde_json::to_string_pretty(&info)?,
+        };
+        return machine_output::write_report(&out, options.output.as_deref());
+    }
+
+    output::header(&format!("{}:{}", info.backend, info.name));
+    for (label, value) in info_fields(&info) {
+        output::keyval(label, &value);
+    }
+    Ok(())
+}
+
+/// Manager for the package's backend, with config overrides applied
+fn info_manager(pkg_id: &PackageId) -> Result<GenericManager> {
+    let backend_name = pkg_id.backend.name();
+    let mut config = load_all_backends_unified()?
+        .remove(backend_name)
+        .ok_or_else(|| DeclarchError::Other(format!("Unknown backend '{}'", backend_name)))?;
+
+    let runtime_config = load_runtime_config_for_command("show command");
+    apply_runtime_backend_overrides(&mut config, backend_name, &runtime_config);
+    if !crate::utils::platform::backend_supports_current_os(&config) {
+        return Err(DeclarchError::Other(format!(
+            "Backend '{}' is not supported on this platform",
+            backend_name
+        )));
+    }
+
+    Ok(GenericManager::from_config(
+        config,
+        pkg_id.backend.clone(),
+        false,
+    ))
+}
+
+/// Labelled values for display; fields the backend did not report are left out
+fn info_fields(info: &PackageInfo) -> Vec<(&'static str, String)> {
+    let mut fields = Vec::new();
+    for (label, value) in [
+        ("Version", &info.version),
+        ("Description", &info.description),
+        ("Homepage", &info.homepage),
+    ] {
+        if let Some(value) = value {
+            fields.push((label, value.clone()));
+        }
+    }
+    let depends = if info.depends.is_empty() {
+        "(none)".to_string()
+    } else {
+        info.depends.join(", ")
+    };
+    fields.push(("Depends on", depends));
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Backend;
+
+    #[test]
+    fn info_fields_skip_missing_values() {
+        let info = PackageInfo {
+            name: "bat".to_string(),
+            version: Some("0.24.0".to_string()),
+            description: None,
+            homepage: Some("https://github.com/sharkdp/bat".to_string()),
+            depends: Vec::new(),
+            backend: Backend::from("aur"),
+        };
+
+        assert_eq!(
+            info_fields(&info),
+            vec![
+                ("Version", "0.24.0".to_string()),
+                ("Homepage", "https://github.com/sharkdp/bat".to_string()),
+                ("Depends on", "(none)".to_string()),
+            ]
+        );
+    }
+}
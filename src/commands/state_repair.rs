@@ -0,0 +1,344 @@
+//! State repair command
+//!
+//! Reconciles state with what backends actually report as installed, without
+//! installing or removing anything. Entries for backends that are unavailable
+//! on this machine or failed to list are left untouched, since they cannot be
+//! verified.
+
+use crate::commands::sync::{self, InstalledSnapshot};
+use crate::config::loader::{self, MergedConfig};
+use crate::core::resolver;
+use crate::core::types::{Backend, PackageId, SyncTarget};
+use crate::error::{DeclarchError, Result};
+use crate::project_identity;
+use crate::state::{
+    self,
+    types::{PackageState, State},
+};
+use crate::ui as output;
+use crate::utils::paths;
+use chrono::Utc;
+use std::collections::HashSet;
+
+pub struct StateRepairOptions {
+    /// Track installed, declared packages that are missing from state
+    pub adopt_untracked: bool,
+    /// Show what would change without writing state
+    pub dry_run: bool,
+    /// Verbose output
+    pub verbose: bool,
+}
+
+/// Changes applied (or planned) by a repair, by state key.
+#[derive(Debug, Default, PartialEq)]
+struct RepairSummary {
+    added: Vec<String>,
+    updated: Vec<String>,
+    removed: Vec<String>,
+}
+
+impl RepairSummary {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+pub fn run(options: StateRepairOptions) -> Result<()> {
+    let lock = state::io::acquire_lock().map_err(|e| {
+        DeclarchError::Other(format!(
+            "Cannot start state repair: {}\n\
+             If no other {} process is running, delete the lock file manually.",
+            e,
+            project_identity::BINARY_NAME
+        ))
+    })?;
+
+    let config_path = paths::config_file()?;
+    let mut config = if loader::root_config_available(&config_path) {
+        loader::load_root_config(&config_path)?
+    } else {
+        MergedConfig::default()
+    };
+    let declared: HashSet<PackageId> = config.packages.keys().cloned().collect();
+
+    let mut state = state::io::load_state()?;
+
+    // Snapshot every backend state refers to, not only the declared ones.
+    for pkg_state in state.packages.values() {
+        config
+            .packages
+            .entry(PackageId {
+                name: pkg_state.config_name.clone(),
+                backend: pkg_state.backend.clone(),
+            })
+            .or_default();
+    }
+    // Only backends that listed successfully can be trusted to say what is
+    // missing; a failed listing would otherwise drop all of its entries.
+    let (installed_snapshot, _, available) = sync::initialize_managers_and_snapshot(
+        &config,
+        &sync::SyncOptions {
+            verbose: options.verbose,
+            ..Default::default()
+        },
+        &SyncTarget::All,
+    )?;
+
+    let summary = reconcile_state(
+        &mut state,
+        &installed_snapshot,
+        &available,
+        &declared,
+        options.adopt_untracked,
+    );
+
+    output::header(if options.dry_run {
+        "State repair (dry-run)"
+    } else {
+        "State repair"
+    });
+
+    if options.verbose || options.dry_run {
+        print_keys("Pinned (never pruned)", &pinned_keys(&state));
+    }
+
+    if summary.is_empty() {
+        output::success("State already matches installed packages");
+        return Ok(());
+    }
+
+    if options.verbose || options.dry_run {
+        print_keys("Added", &summary.added);
+        print_keys("Updated", &summary.updated);
+        print_keys("Removed", &summary.removed);
+    }
+
+    let counts = format!(
+        "{} added, {} updated, {} removed",
+        summary.added.len(),
+        summary.updated.len(),
+        summary.removed.len()
+    );
+
+    if options.dry_run {
+        output::info(&format!("Would apply: {}", counts));
+        return Ok(());
+    }
+
+    state::io::save_state_locked(&state, &lock)?;
+    output::success(&format!("State repaired: {}", counts));
+    Ok(())
+}
+
+fn print_keys(label: &str, keys: &[String]) {
+    if keys.is_empty() {
+        return;
+    }
+    output::info(&format!("{}:", label));
+    for key in keys {
+        output::indent(&format!("• {}", key), 1);
+    }
+}
+
+/// State keys of pinned entries, sorted.
+fn pinned_keys(state: &State) -> Vec<String> {
+    let mut keys: Vec<String> = state
+        .packages
+        .iter()
+        .filter(|(_, pkg_state)| pkg_state.pinned)
+        .map(|(key, _)| key.clone())
+        .collect();
+    keys.sort();
+    keys
+}
+
+/// Rewrite `state.packages` to match the installed snapshot.
+///
+/// Only entries on `available` backends (those that listed successfully) are
+/// considered; others are kept as-is.
+fn reconcile_state(
+    state: &mut State,
+    installed: &InstalledSnapshot,
+    available: &HashSet<Backend>,
+    declared: &HashSet<PackageId>,
+    adopt_untracked: bool,
+) -> RepairSummary {
+    let mut summary = RepairSummary::default();
+    let now = Utc::now();
+
+    state.packages.retain(|key, pkg_state| {
+        if !available.contains(&pkg_state.backend) {
+            return true;
+        }
+
+        let Some(meta) = installed_metadata(pkg_state, installed) else {
+            summary.removed.push(key.clone());
+            return false;
+        };
+
+        if pkg_state.version != meta.version {
+            pkg_state.version = meta.version.clone();
+            summary.updated.push(key.clone());
+        }
+        pkg_state.last_seen_at = Some(now);
+        true
+    });
+
+    if adopt_untracked {
+        for pkg_id in declared {
+            let key = resolver::make_state_key(pkg_id);
+            if !available.contains(&pkg_id.backend) || state.packages.contains_key(&key) {
+                continue;
+            }
+            let Some(meta) = installed.get(pkg_id) else {
+                continue;
+            };
+
+            let mut entry =
+                PackageState::from_config(pkg_id.name.clone(), pkg_id.backend.clone(), None);
+            entry.version = meta.version.clone();
+            entry.installed_at = meta.installed_at;
+            entry.last_seen_at = Some(now);
+            entry.install_reason = Some("adopted".to_string());
+            state.packages.insert(key.clone(), entry);
+            summary.added.push(key);
+        }
+    }
+
+    summary.added.sort();
+    summary.updated.sort();
+    summary.removed.sort();
+    summary
+}
+
+/// Installed metadata for a state entry, trying the actual system name first.
+fn installed_metadata<'a>(
+    pkg_state: &PackageState,
+    installed: &'a InstalledSnapshot,
+) -> Option<&'a crate::core::types::PackageMetadata> {
+    [
+        pkg_state.actual_package_name.as_deref(),
+        Some(pkg_state.provides_name.as_str()),
+        Some(pkg_state.config_name.as_str()),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|name| !name.is_empty())
+    .find_map(|name| {
+        installed.get(&PackageId {
+            name: name.to_string(),
+            backend: pkg_state.backend.clone(),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::sync::test_support::pkg;
+    use crate::core::types::PackageMetadata;
+
+    fn installed(entries: &[(PackageId, &str)]) -> InstalledSnapshot {
+        entries
+            .iter()
+            .map(|(id, version)| {
+                (
+                    id.clone(),
+                    PackageMetadata {
+                        version: Some(version.to_string()),
+                        variant: None,
+                        installed_at: Utc::now(),
+                        source_file: None,
+                        install_size: None,
+                        origin: None,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn tracked(state: &mut State, id: &PackageId, version: &str) {
+        let entry = PackageState::from_config(
+            id.name.clone(),
+            id.backend.clone(),
+            Some(version.to_string()),
+        );
+        state.packages.insert(resolver::make_state_key(id), entry);
+    }
+
+    #[test]
+    fn reconcile_drops_missing_and_refreshes_versions() {
+        let bat = pkg("aur", "bat");
+        let gone = pkg("aur", "ripgrep");
+        let offline = pkg("flatpak", "org.gimp.GIMP");
+
+        let mut state = State::default();
+        tracked(&mut state, &bat, "0.23.0");
+        tracked(&mut state, &gone, "14.0.0");
+        tracked(&mut state, &offline, "2.10");
+
+        let snapshot = installed(&[(bat.clone(), "0.24.0")]);
+        let available = HashSet::from([Backend::from("aur")]);
+
+        let summary = reconcile_state(&mut state, &snapshot, &available, &HashSet::new(), false);
+
+        assert_eq!(summary.updated, vec![resolver::make_state_key(&bat)]);
+        assert_eq!(summary.removed, vec![resolver::make_state_key(&gone)]);
+        assert!(summary.added.is_empty());
+
+        let bat_state = &state.packages[&resolver::make_state_key(&bat)];
+        assert_eq!(bat_state.version.as_deref(), Some("0.24.0"));
+        // Unavailable backend entries cannot be verified and must survive.
+        assert!(
+            state
+                .packages
+                .contains_key(&resolver::make_state_key(&offline))
+        );
+    }
+
+    #[test]
+    fn reconcile_keeps_entries_of_backends_that_failed_to_list() {
+        let bat = pkg("aur", "bat");
+        let node = pkg("npm", "typescript");
+
+        let mut state = State::default();
+        tracked(&mut state, &bat, "0.24.0");
+        tracked(&mut state, &node, "5.4.0");
+
+        // npm has a manager but its listing failed, so it is not in the
+        // listed set and its empty snapshot says nothing about its packages.
+        let snapshot = installed(&[(bat.clone(), "0.24.0")]);
+        let listed = HashSet::from([Backend::from("aur")]);
+
+        let summary = reconcile_state(&mut state, &snapshot, &listed, &HashSet::new(), false);
+
+        assert!(summary.is_empty());
+        assert!(
+            state
+                .packages
+                .contains_key(&resolver::make_state_key(&node))
+        );
+    }
+
+    #[test]
+    fn adopt_untracked_adds_only_installed_declared_packages() {
+        let fd = pkg("aur", "fd");
+        let missing = pkg("aur", "eza");
+        let undeclared = pkg("aur", "htop");
+
+        let mut state = State::default();
+        let snapshot = installed(&[(fd.clone(), "10.1.0"), (undeclared.clone(), "3.3.0")]);
+        let available = HashSet::from([Backend::from("aur")]);
+        let declared = HashSet::from([fd.clone(), missing.clone()]);
+
+        let without = reconcile_state(&mut state, &snapshot, &available, &declared, false);
+        assert!(without.is_empty());
+
+        let summary = reconcile_state(&mut state, &snapshot, &available, &declared, true);
+        assert_eq!(summary.added, vec![resolver::make_state_key(&fd)]);
+        let entry = &state.packages[&resolver::make_state_key(&fd)];
+        assert_eq!(entry.install_reason.as_deref(), Some("adopted"));
+        assert_eq!(entry.version.as_deref(), Some("10.1.0"));
+        assert_eq!(state.packages.len(), 1);
+    }
+}
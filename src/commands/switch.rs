@@ -1,6 +1,6 @@
-use crate::config::types::GlobalConfig;
 use crate::error::{DeclarchError, Result};
-use crate::packages::{PackageManager, create_manager};
+use crate::packages::PackageManager;
+use crate::packages::registry::create_generic_manager;
 use crate::project_identity;
 use crate::state;
 use crate::ui as output;
@@ -19,6 +19,12 @@ pub struct SwitchOptions {
     pub dry_run: bool,
     pub yes: bool,
     pub force: bool,
+    /// Echo resolved backend commands before running them
+    pub verbose: bool,
+    /// Run the transition without escalation (`--no-sudo`)
+    pub no_sudo: bool,
+    /// Capture backend output, showing it only on failure (`--quiet-backends`)
+    pub quiet_backends: bool,
 }
 
 pub fn run(options: SwitchOptions) -> Result<()> {
@@ -48,8 +54,6 @@ pub fn run(options: SwitchOptions) -> Result<()> {
     )?;
 
     // 3. Get package manager
-    let global_config = GlobalConfig::default();
-
     if let Ok(backends) = crate::backends::load_all_backends_unified()
         && let Some(cfg) = backends.get(backend.name())
         && !crate::utils::platform::backend_supports_current_os(cfg)
@@ -62,8 +66,13 @@ pub fn run(options: SwitchOptions) -> Result<()> {
         return Ok(());
     }
 
-    let manager: Box<dyn PackageManager> = create_manager(&backend, &global_config, false)
-        .map_err(|e| DeclarchError::Other(format!("Failed to create package manager: {}", e)))?;
+    let manager: Box<dyn PackageManager> = Box::new(
+        create_generic_manager(&backend, false)
+            .map_err(|e| DeclarchError::Other(format!("Failed to create package manager: {}", e)))?
+            .with_no_sudo(options.no_sudo)
+            .with_quiet_output(options.quiet_backends)
+            .with_echo_commands(options.verbose),
+    );
 
     // Check manager availability
     if !manager.is_available() {
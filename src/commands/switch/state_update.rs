@@ -39,6 +39,7 @@ pub(super) fn persist_successful_transition(
             .and_then(|m| m.version.clone()),
         install_reason: Some("manual-sync".to_string()),
         source_module: None,
+        pinned: false,
         last_seen_at: Some(Utc::now()),
         backend_meta: None,
     };
@@ -45,6 +45,18 @@ pub(crate) fn apply_backend_option_overrides(
                     backend_config.install_cmd = value.clone();
                 }
             }
+            "reinstall_cmd" => {
+                if disable {
+                    backend_config.reinstall_cmd = None;
+                } else if !normalized.contains("{packages}") {
+                    output::warning(&format!(
+                        "Ignoring invalid reinstall_cmd override for options:{}: missing '{{packages}}' placeholder",
+                        backend_name
+                    ));
+                } else {
+                    backend_config.reinstall_cmd = Some(value.clone());
+                }
+            }
             "remove_cmd" => {
                 if disable {
                     backend_config.remove_cmd = None;
@@ -93,6 +105,31 @@ pub(crate) fn apply_backend_option_overrides(
             "upgrade_cmd" => {
                 backend_config.upgrade_cmd = if disable { None } else { Some(value.clone()) }
             }
+            "outdated_cmd" => {
+                backend_config.outdated_cmd = if disable { None } else { Some(value.clone()) }
+            }
+            "batch" => {
+                if let Some(parsed) = parse_bool_option(value) {
+                    backend_config.batch = parsed;
+                } else {
+                    output::warning(&format!(
+                        "Invalid boolean for options:{} -> {}={}",
+                        backend_name, key, value
+                    ));
+                }
+            }
+            "registry" => {
+                if disable {
+                    backend_config.registry = None;
+                } else if let Err(e) = crate::utils::sanitize::validate_registry_url(normalized) {
+                    output::warning(&format!(
+                        "Ignoring registry override for options:{}: {}",
+                        backend_name, e
+                    ));
+                } else {
+                    backend_config.registry = Some(normalized.to_string());
+                }
+            }
             "needs_sudo" | "sudo" => {
                 if let Some(parsed) = parse_bool_option(value) {
                     backend_config.needs_sudo = parsed;
@@ -169,6 +206,19 @@ pub(crate) fn apply_backend_package_sources(
         }
     }
 
+    // Prioritized sources first (by priority, then name, so the result does not
+    // depend on merge order); the rest keep their declaration order.
+    if let Some(priorities) = config.package_source_priorities.get(backend_name) {
+        sources.sort_by(
+            |a, b| match (priorities.get(a.as_str()), priorities.get(b.as_str())) {
+                (Some(pa), Some(pb)) => pa.cmp(pb).then_with(|| a.cmp(b)),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            },
+        );
+    }
+
     if sources.is_empty() {
         backend_config.package_sources = None;
     } else {
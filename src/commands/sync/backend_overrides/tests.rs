@@ -1,4 +1,7 @@
-use super::parse_bool_option;
+use super::{apply_backend_option_overrides, parse_bool_option};
+use crate::backends::config::BackendConfig;
+use crate::config::loader::MergedConfig;
+use std::collections::HashMap;
 
 #[test]
 fn parse_bool_option_variants() {
@@ -12,3 +15,42 @@ fn parse_bool_option_variants() {
     assert_eq!(parse_bool_option("0"), Some(false));
     assert_eq!(parse_bool_option("maybe"), None);
 }
+
+#[test]
+fn registry_option_overrides_backend_default_when_valid() {
+    let options_for = |registry: &str| {
+        let mut config = MergedConfig::default();
+        config.backend_options.insert(
+            "soar".to_string(),
+            HashMap::from([("registry".to_string(), registry.to_string())]),
+        );
+        config
+    };
+    let backend = || BackendConfig {
+        name: "soar".to_string(),
+        registry: Some("https://default.example.org".to_string()),
+        ..Default::default()
+    };
+
+    let mut chosen = backend();
+    apply_backend_option_overrides(
+        &mut chosen,
+        "soar",
+        &options_for("https://mirror.example.org"),
+    );
+    assert_eq!(
+        chosen.registry.as_deref(),
+        Some("https://mirror.example.org")
+    );
+
+    let mut invalid = backend();
+    apply_backend_option_overrides(&mut invalid, "soar", &options_for("mirror.example.org"));
+    assert_eq!(
+        invalid.registry.as_deref(),
+        Some("https://default.example.org")
+    );
+
+    let mut cleared = backend();
+    apply_backend_option_overrides(&mut cleared, "soar", &options_for("-"));
+    assert_eq!(cleared.registry, None);
+}
@@ -1,35 +1,58 @@
 use super::{
-    InstalledSnapshot, ManagerMap, SyncOptions, apply_backend_env_overrides,
-    apply_backend_option_overrides, apply_backend_package_sources,
+    BackendTimings, InstalledSnapshot, ManagerMap, PackageSizes, SyncOptions,
+    apply_backend_env_overrides, apply_backend_option_overrides, apply_backend_package_sources,
 };
 use crate::config::loader;
-use crate::core::types::{PackageId, SyncTarget};
+use crate::core::resolver;
+use crate::core::types::{PackageId, PackageMetadata, SyncTarget};
 use crate::error::Result;
 use crate::packages::PackageManager;
 use crate::project_identity;
 use crate::state::types::Backend;
 use crate::ui as output;
-use std::collections::HashMap;
+use crate::utils::remote;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
 
-pub(super) fn initialize_managers_and_snapshot(
+/// Build managers for every backend referenced by config packages and
+/// snapshot what each available backend reports as installed
+///
+/// Backends are listed in parallel, at most `DECLARCH_MAX_PARALLEL_LIST`
+/// at a time (default: available CPUs). The returned set holds the backends
+/// whose listing succeeded; a backend that failed to list keeps its manager
+/// but its snapshot is incomplete.
+pub fn initialize_managers_and_snapshot(
     config: &loader::MergedConfig,
     options: &SyncOptions,
     sync_target: &SyncTarget,
-) -> Result<(InstalledSnapshot, ManagerMap)> {
-    let mut installed_snapshot: InstalledSnapshot = HashMap::new();
-    let mut managers: ManagerMap = HashMap::new();
+) -> Result<(InstalledSnapshot, ManagerMap, HashSet<Backend>)> {
+    initialize_managers_and_snapshot_timed(
+        config,
+        options,
+        sync_target,
+        &mut BackendTimings::default(),
+    )
+}
 
+/// `initialize_managers_and_snapshot`, adding how long each backend took to list
+pub(super) fn initialize_managers_and_snapshot_timed(
+    config: &loader::MergedConfig,
+    options: &SyncOptions,
+    sync_target: &SyncTarget,
+    list_timings: &mut BackendTimings,
+) -> Result<(InstalledSnapshot, ManagerMap, HashSet<Backend>)> {
     let mut known_backends = crate::backends::load_all_backends_unified()?;
     for backend in &config.backends {
         known_backends.insert(backend.name.clone(), backend.clone());
     }
 
-    let configured_backends: std::collections::HashSet<Backend> = config
+    let configured_backends: HashSet<Backend> = config
         .packages
         .keys()
         .map(|pkg_id| pkg_id.backend.clone())
         .collect();
 
+    let mut candidates: Vec<(Backend, Box<dyn PackageManager>)> = Vec::new();
     for backend in configured_backends {
         let backend_name = backend.name().to_string();
         let Some(mut backend_config) = known_backends.get(&backend_name).cloned() else {
@@ -56,42 +79,122 @@ pub(super) fn initialize_managers_and_snapshot(
             continue;
         }
 
-        let manager: Box<dyn PackageManager> =
-            Box::new(crate::backends::GenericManager::from_config(
+        let manager: Box<dyn PackageManager> = Box::new(
+            crate::backends::GenericManager::from_config(
                 backend_config,
                 backend.clone(),
                 options.noconfirm,
-            ));
+            )
+            .with_escalation(config.escalation_program())
+            .with_no_sudo(options.no_sudo)
+            .with_quiet_output(options.quiet_backends)
+            .with_echo_commands(options.verbose),
+        );
+        candidates.push((backend, manager));
+    }
 
-        let available = manager.is_available();
+    Ok(collect_probed(
+        probe_backends(candidates, max_parallel_list()),
+        sync_target,
+        list_timings,
+    ))
+}
 
-        if !available && matches!(sync_target, SyncTarget::Backend(b) if b == &backend) {
-            output::warning(&format!(
-                "Backend '{}' is not available on this system.",
-                backend
-            ));
-        }
+/// Fold probe results into the snapshot, the managers of available backends
+/// and the set of backends whose listing succeeded.
+fn collect_probed(
+    probed: Vec<ProbedBackend>,
+    sync_target: &SyncTarget,
+    list_timings: &mut BackendTimings,
+) -> (InstalledSnapshot, ManagerMap, HashSet<Backend>) {
+    let mut installed_snapshot: InstalledSnapshot = HashMap::new();
+    let mut managers: ManagerMap = HashMap::new();
+    let mut listed_backends = HashSet::new();
 
-        if available {
-            match manager.list_installed() {
-                Ok(packages) => {
-                    for (name, meta) in packages {
-                        let pkg_id = PackageId {
-                            name: name.clone(),
-                            backend: backend.clone(),
-                        };
-                        installed_snapshot.insert(pkg_id, meta);
-                    }
-                }
-                Err(e) => {
-                    output::warning(&format!("Failed to list packages for {}: {}", backend, e));
+    for (backend, manager, listed, elapsed) in probed {
+        list_timings.add(backend.clone(), elapsed);
+        let Some(listed) = listed else {
+            if sync_target.targets_backend(&backend) {
+                output::warning(&format!(
+                    "Backend '{}' is not available on this system.",
+                    backend
+                ));
+            }
+            continue;
+        };
+
+        match listed {
+            Ok(packages) => {
+                for (name, meta) in packages {
+                    let pkg_id = PackageId {
+                        name,
+                        backend: backend.clone(),
+                    };
+                    installed_snapshot.insert(pkg_id, meta);
                 }
+                listed_backends.insert(backend.clone());
+            }
+            Err(e) => {
+                output::warning(&format!("Failed to list packages for {}: {}", backend, e));
             }
-            managers.insert(backend.clone(), manager);
         }
+        managers.insert(backend, manager);
     }
 
-    Ok((installed_snapshot, managers))
+    (installed_snapshot, managers, listed_backends)
+}
+
+/// A backend, its manager, its installed packages (`None` if unavailable),
+/// and how long the probe took.
+type ProbedBackend = (
+    Backend,
+    Box<dyn PackageManager>,
+    Option<Result<HashMap<String, PackageMetadata>>>,
+    std::time::Duration,
+);
+
+/// Check availability and list installed packages, running up to `limit`
+/// backends at once. Results keep the order of `candidates`.
+fn probe_backends(
+    candidates: Vec<(Backend, Box<dyn PackageManager>)>,
+    limit: usize,
+) -> Vec<ProbedBackend> {
+    let probe = |(backend, manager): (Backend, Box<dyn PackageManager>)| {
+        let started = std::time::Instant::now();
+        let listed = manager.is_available().then(|| manager.list_installed());
+        (backend, manager, listed, started.elapsed())
+    };
+
+    if limit <= 1 || candidates.len() <= 1 {
+        return candidates.into_iter().map(probe).collect();
+    }
+
+    match rayon::ThreadPoolBuilder::new()
+        .num_threads(limit.min(candidates.len()))
+        .build()
+    {
+        Ok(pool) => pool.install(|| candidates.into_par_iter().map(probe).collect()),
+        Err(_) => candidates.into_iter().map(probe).collect(),
+    }
+}
+
+/// Concurrency cap for backend listing: `DECLARCH_MAX_PARALLEL_LIST`, else CPU count.
+fn max_parallel_list() -> usize {
+    parse_parallel_limit(project_identity::env_get("MAX_PARALLEL_LIST").as_deref()).unwrap_or_else(
+        || {
+            std::thread::available_parallelism()
+                .map(std::num::NonZeroUsize::get)
+                .unwrap_or(1)
+        },
+    )
+}
+
+fn parse_parallel_limit(value: Option<&str>) -> Option<usize> {
+    value?
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|limit| *limit > 0)
 }
 
 pub(super) fn refresh_installed_snapshot(managers: &ManagerMap) -> InstalledSnapshot {
@@ -123,7 +226,79 @@ pub(super) fn refresh_installed_snapshot(managers: &ManagerMap) -> InstalledSnap
     snapshot
 }
 
+/// Approximate download/install size of each planned install
+///
+/// Packages already present (reinstalls) use the size their backend listed;
+/// others are queried through the backend's `size_cmd`. Packages whose size
+/// is unknown are left out.
+pub fn estimate_install_sizes(
+    tx: &resolver::Transaction,
+    installed_snapshot: &InstalledSnapshot,
+    managers: &ManagerMap,
+) -> PackageSizes {
+    tx.to_install
+        .par_iter()
+        .filter_map(|pkg| {
+            let listed = installed_snapshot
+                .get(pkg)
+                .and_then(|meta| meta.install_size);
+            let size = listed.or_else(|| {
+                let manager = managers.get(&pkg.backend)?;
+                if !manager.supports_package_size() {
+                    return None;
+                }
+                manager.package_size(&pkg.name).ok().flatten()
+            })?;
+            Some((pkg.clone(), size))
+        })
+        .collect()
+}
+
+/// Backends `sync --upgrade` covers: every one that can upgrade, or only the
+/// backends named with `--target`.
+pub(super) fn upgrade_candidates(managers: &ManagerMap, sync_target: &SyncTarget) -> Vec<Backend> {
+    let mut backends: Vec<Backend> = managers
+        .iter()
+        .filter(|(_, manager)| manager.is_available() && manager.supports_upgrade())
+        .filter(|(backend, _)| {
+            matches!(sync_target, SyncTarget::All) || sync_target.targets_backend(backend)
+        })
+        .map(|(backend, _)| backend.clone())
+        .collect();
+    backends.sort_by_key(|backend| backend.to_string());
+    backends
+}
+
+/// Upgrade every installed package of the given backends.
+///
+/// Returns the backends that upgraded successfully; failures only warn.
+pub(super) fn execute_backend_upgrades(
+    managers: &ManagerMap,
+    backends: &[Backend],
+) -> Vec<Backend> {
+    output::separator();
+    output::info("Upgrading packages...");
+
+    let mut upgraded = Vec::new();
+    for backend in backends {
+        let Some(manager) = managers.get(backend) else {
+            continue;
+        };
+        match manager.upgrade() {
+            Ok(()) => upgraded.push(backend.clone()),
+            Err(e) => output::warning(&format!("Failed to upgrade '{}': {}", backend, e)),
+        }
+    }
+
+    if !upgraded.is_empty() {
+        output::info(&format!("Upgraded {} backend(s)", upgraded.len()));
+    }
+    upgraded
+}
+
 pub(super) fn execute_backend_updates(managers: &ManagerMap, verbose: bool) -> Result<()> {
+    remote::ensure_online("Updating package indices")?;
+
     output::separator();
     output::info("Updating package indices...");
 
@@ -36,6 +36,74 @@ impl PackageManager for MockManager {
     }
 }
 
+struct FailingListManager;
+
+impl PackageManager for FailingListManager {
+    fn backend_type(&self) -> CoreBackend {
+        CoreBackend::from("npm")
+    }
+
+    fn list_installed(&self) -> Result<HashMap<String, PackageMetadata>> {
+        Err(crate::error::DeclarchError::Other(
+            "npm ls failed".to_string(),
+        ))
+    }
+
+    fn install(&self, _packages: &[String]) -> Result<()> {
+        Ok(())
+    }
+
+    fn remove(&self, _packages: &[String]) -> Result<()> {
+        Ok(())
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn get_required_by(&self, _package: &str) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+}
+
+struct SizeManager {
+    sizes: HashMap<String, u64>,
+}
+
+impl PackageManager for SizeManager {
+    fn backend_type(&self) -> CoreBackend {
+        CoreBackend::from("npm")
+    }
+
+    fn list_installed(&self) -> Result<HashMap<String, PackageMetadata>> {
+        Ok(HashMap::new())
+    }
+
+    fn install(&self, _packages: &[String]) -> Result<()> {
+        Ok(())
+    }
+
+    fn remove(&self, _packages: &[String]) -> Result<()> {
+        Ok(())
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn get_required_by(&self, _package: &str) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    fn supports_package_size(&self) -> bool {
+        true
+    }
+
+    fn package_size(&self, package: &str) -> Result<Option<u64>> {
+        Ok(self.sizes.get(package).copied())
+    }
+}
+
 #[test]
 fn refresh_installed_snapshot_skips_unavailable_backends() {
     let mut managers: ManagerMap = HashMap::new();
@@ -50,6 +118,8 @@ fn refresh_installed_snapshot_skips_unavailable_backends() {
             variant: None,
             installed_at: Utc::now(),
             source_file: None,
+            install_size: None,
+            origin: None,
         },
     );
 
@@ -74,3 +144,103 @@ fn refresh_installed_snapshot_skips_unavailable_backends() {
     assert_eq!(snapshot.len(), 1);
     assert!(snapshot.keys().any(|k| k.backend == available_backend));
 }
+
+#[test]
+fn probe_backends_keeps_order_and_skips_listing_unavailable() {
+    let candidates: Vec<(CoreBackend, Box<dyn PackageManager>)> = ["aur", "npm", "brew", "pip"]
+        .into_iter()
+        .map(|name| {
+            let backend = CoreBackend::from(name);
+            let manager: Box<dyn PackageManager> = Box::new(MockManager {
+                backend: backend.clone(),
+                available: name != "brew",
+                installed: HashMap::new(),
+            });
+            (backend, manager)
+        })
+        .collect();
+
+    let probed = probe_backends(candidates, 2);
+
+    let names: Vec<&str> = probed.iter().map(|(b, _, _, _)| b.name()).collect();
+    assert_eq!(names, vec!["aur", "npm", "brew", "pip"]);
+    assert!(probed[2].2.is_none());
+    assert!(probed[0].2.as_ref().is_some_and(|listed| listed.is_ok()));
+}
+
+#[test]
+fn collect_probed_leaves_failed_listings_out_of_listed_backends() {
+    let aur = CoreBackend::from("aur");
+    let npm = CoreBackend::from("npm");
+    let candidates: Vec<(CoreBackend, Box<dyn PackageManager>)> = vec![
+        (
+            aur.clone(),
+            Box::new(MockManager {
+                backend: aur.clone(),
+                available: true,
+                installed: HashMap::new(),
+            }),
+        ),
+        (npm.clone(), Box::new(FailingListManager)),
+    ];
+
+    let (snapshot, managers, listed) = collect_probed(
+        probe_backends(candidates, 1),
+        &SyncTarget::All,
+        &mut BackendTimings::default(),
+    );
+
+    assert!(snapshot.is_empty());
+    assert!(managers.contains_key(&npm));
+    assert_eq!(listed, HashSet::from([aur]));
+}
+
+#[test]
+fn parallel_limit_accepts_only_positive_integers() {
+    assert_eq!(parse_parallel_limit(Some(" 4 ")), Some(4));
+    assert_eq!(parse_parallel_limit(Some("0")), None);
+    assert_eq!(parse_parallel_limit(Some("many")), None);
+    assert_eq!(parse_parallel_limit(None), None);
+}
+
+#[test]
+fn estimate_install_sizes_prefers_listed_size_and_skips_unknown() {
+    let npm = CoreBackend::from("npm");
+    let pkg = |name: &str| PackageId {
+        name: name.to_string(),
+        backend: npm.clone(),
+    };
+
+    let mut managers: ManagerMap = HashMap::new();
+    managers.insert(
+        npm.clone(),
+        Box::new(SizeManager {
+            sizes: HashMap::from([("typescript".to_string(), 4096), ("eslint".to_string(), 1)]),
+        }),
+    );
+    let mut snapshot = InstalledSnapshot::new();
+    snapshot.insert(
+        pkg("eslint"),
+        PackageMetadata {
+            version: None,
+            variant: None,
+            installed_at: Utc::now(),
+            source_file: None,
+            install_size: Some(2048),
+            origin: None,
+        },
+    );
+    let tx = resolver::Transaction {
+        to_install: vec![pkg("typescript"), pkg("eslint"), pkg("unknown")],
+        to_prune: Vec::new(),
+        to_adopt: Vec::new(),
+        to_update_project_metadata: Vec::new(),
+        install_reasons: HashMap::new(),
+    };
+
+    let sizes = estimate_install_sizes(&tx, &snapshot, &managers);
+
+    assert_eq!(sizes.len(), 2);
+    assert_eq!(sizes[&pkg("typescript")], 4096);
+    assert_eq!(sizes[&pkg("eslint")], 2048);
+}
@@ -0,0 +1,223 @@
+//! `sync --print-config`: the merged config exactly as sync would use it
+//!
+//! Printed after module/profile/host selection, ad-hoc backend files and
+//! backend alias canonicalization, so the package list is the one the
+//! planner sees. Nothing is installed and state is not read.
+
+use super::SyncOptions;
+use crate::config::kdl::PolicyConfig;
+use crate::config::loader::MergedConfig;
+use crate::error::Result;
+use crate::ui as output;
+use crate::utils::machine_output;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize)]
+pub(super) struct ConfigDump {
+    pub profiles: Vec<String>,
+    pub host: Option<String>,
+    pub config_files: Vec<PathBuf>,
+    /// Declared packages per backend, each with the files declaring it
+    pub packages: BTreeMap<String, Vec<DumpedPackage>>,
+    pub excludes: Vec<String>,
+    /// Backend-scoped excludes (`excludes:<backend>`)
+    pub backend_excludes: BTreeMap<String, Vec<String>>,
+    pub package_aliases: BTreeMap<String, String>,
+    pub backend_package_aliases: BTreeMap<String, BTreeMap<String, String>>,
+    /// Alias backend name -> primary backend name
+    pub backend_aliases: BTreeMap<String, String>,
+    pub backend_options: BTreeMap<String, BTreeMap<String, String>>,
+    pub env: BTreeMap<String, Vec<String>>,
+    pub package_sources: BTreeMap<String, Vec<String>>,
+    pub policy: Option<PolicyConfig>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub(super) struct DumpedPackage {
+    pub name: String,
+    pub sources: Vec<PathBuf>,
+}
+
+pub(super) fn build_config_dump(config: &MergedConfig, options: &SyncOptions) -> ConfigDump {
+    let mut packages: BTreeMap<String, Vec<DumpedPackage>> = BTreeMap::new();
+    for (pkg, sources) in &config.packages {
+        let mut sources = sources.clone();
+        sources.dedup();
+        packages
+            .entry(pkg.backend.name().to_string())
+            .or_default()
+            .push(DumpedPackage {
+                name: pkg.name.clone(),
+                sources,
+            });
+    }
+    for entries in packages.values_mut() {
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    let mut excludes = config.excludes.clone();
+    excludes.sort();
+    excludes.dedup();
+
+    ConfigDump {
+        profiles: options.profiles.clone(),
+        host: options.host.clone(),
+        config_files: config.config_files.clone(),
+        packages,
+        excludes,
+        backend_excludes: config
+            .backend_excludes
+            .iter()
+            .map(|(backend, names)| {
+                let mut names: Vec<String> = names.iter().cloned().collect();
+                names.sort();
+                (backend.clone(), names)
+            })
+            .collect(),
+        package_aliases: config.package_aliases.clone().into_iter().collect(),
+        backend_package_aliases: config
+            .backend_package_aliases
+            .iter()
+            .map(|(backend, aliases)| (backend.clone(), aliases.clone().into_iter().collect()))
+            .collect(),
+        backend_aliases: config.backend_alias_map().into_iter().collect(),
+        backend_options: config
+            .backend_options
+            .iter()
+            .map(|(backend, opts)| (backend.clone(), opts.clone().into_iter().collect()))
+            .collect(),
+        env: config.env.clone().into_iter().collect(),
+        package_sources: config.package_sources.clone().into_iter().collect(),
+        policy: config.policy.clone(),
+    }
+}
+
+pub(super) fn print_config_dump(config: &MergedConfig, options: &SyncOptions) -> Result<()> {
+    let dump = build_config_dump(config, options);
+    let output_file = options.output.as_deref();
+
+    match options.format.as_deref() {
+        Some(format @ ("json" | "yaml" | "toml"))
+            if options.output_version.as_deref() == Some("v1") =>
+        {
+            machine_output::emit_v1("sync", &dump, Vec::new(), Vec::new(), format, output_file)
+        }
+        Some("json") => {
+            machine_output::write_report(&serde_json::to_string_pretty(&dump)?, output_file)
+        }
+        Some("yaml") => machine_output::write_report(
+            &serde_yml::to_string(&serde_json::to_value(&dump)?)?,
+            output_file,
+        ),
+        Some("toml") => machine_output::write_report(&toml::to_string_pretty(&dump)?, output_file),
+        _ => {
+            render_config_dump(&dump);
+            Ok(())
+        }
+    }
+}
+
+fn render_config_dump(dump: &ConfigDump) {
+    output::header("Merged Config");
+    if dump.profiles.is_empty() {
+        output::keyval("Profile", "(none)");
+    } else {
+        output::keyval("Profile", &dump.profiles.join(", "));
+    }
+    output::keyval("Host", dump.host.as_deref().unwrap_or("(none)"));
+    output::keyval("Config files", &dump.config_files.len().to_string());
+    for file in &dump.config_files {
+        output::indent(&file.display().to_string(), 2);
+    }
+
+    for (backend, packages) in &dump.packages {
+        println!();
+        output::tag(backend, &packages.len().to_string());
+        for pkg in packages {
+            let sources: Vec<String> = pkg
+                .sources
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            output::indent(&format!("{}  ({})", pkg.name, sources.join(", ")), 2);
+        }
+    }
+
+    render_list("Excludes", dump.excludes.iter().cloned());
+    render_list(
+        "Backend excludes",
+        dump.backend_excludes
+            .iter()
+            .map(|(backend, names)| format!("{}: {}", backend, names.join(", "))),
+    );
+    render_list(
+        "Package aliases",
+        dump.package_aliases
+            .iter()
+            .map(|(alias, target)| format!("{} -> {}", alias, target)),
+    );
+    render_list(
+        "Backend package aliases",
+        dump.backend_package_aliases
+            .iter()
+            .flat_map(|(backend, aliases)| {
+                aliases
+                    .iter()
+                    .map(move |(alias, target)| format!("{}: {} -> {}", backend, alias, target))
+            }),
+    );
+    render_list(
+        "Backend aliases",
+        dump.backend_aliases
+            .iter()
+            .map(|(alias, primary)| format!("{} -> {}", alias, primary)),
+    );
+    render_list(
+        "Backend options",
+        dump.backend_options.iter().flat_map(|(backend, opts)| {
+            opts.iter()
+                .map(move |(key, value)| format!("options:{} {} = {}", backend, key, value))
+        }),
+    );
+    render_list(
+        "Env",
+        dump.env
+            .iter()
+            .flat_map(|(scope, vars)| vars.iter().map(move |var| format!("env:{} {}", scope, var))),
+    );
+    render_list(
+        "Package sources",
+        dump.package_sources
+            .iter()
+            .map(|(backend, sources)| format!("{}: {}", backend, sources.join(", "))),
+    );
+
+    if let Some(policy) = &dump.policy {
+        println!();
+        output::tag("Policy", "");
+        match serde_json::to_value(policy) {
+            Ok(serde_json::Value::Object(fields)) => {
+                for (key, value) in fields {
+                    if !value.is_null() {
+                        output::indent(&format!("{} = {}", key, value), 2);
+                    }
+                }
+            }
+            _ => output::indent(&format!("{:?}", policy), 2),
+        }
+    }
+}
+
+fn render_list(title: &str, items: impl Iterator<Item = String>) {
+    let items: Vec<String> = items.collect();
+    if items.is_empty() {
+        return;
+    }
+    println!();
+    output::tag(title, &items.len().to_string());
+    for item in items {
+        output::indent(&item, 2);
+    }
+}
@@ -82,8 +82,7 @@ pub(super) fn load_config_with_modules(
             output::verbose(&format!("Loading module: {}", final_path.display()));
         }
         let module_config = loader::load_root_config_with_selectors(&final_path, selectors)?;
-        merged.packages.extend(module_config.packages);
-        merged.excludes.extend(module_config.excludes);
+        merged.merge_module(module_config);
     }
 
     Ok(merged)
@@ -0,0 +1,116 @@
+//! Interactive resolution of cross-backend conflicts
+//!
+//! With `--interactive-conflicts`, each package name declared on more than
+//! one backend is resolved by asking which declaration to keep for this run.
+//! With `--remember`, the declarations not kept are removed from the config
+//! files that declare them. Runs that cannot prompt fall back to the
+//! configured `on-conflict` policy.
+
+use super::{ConflictChoices, SyncOptions, planner};
+use crate::config::editor::{ConfigEditor, backup_kdl_file};
+use crate::config::loader;
+use crate::core::types::Backend;
+use crate::error::Result;
+use crate::ui as output;
+use std::fs;
+
+pub(super) fn resolve_conflicts(
+    config: &loader::MergedConfig,
+    options: &SyncOptions,
+) -> Result<ConflictChoices> {
+    let mut choices = ConflictChoices::new();
+    if !options.interactive_conflicts {
+        return Ok(choices);
+    }
+
+    let mut conflicts = config.get_cross_backend_conflicts();
+    if conflicts.is_empty() {
+        return Ok(choices);
+    }
+    if options.yes || !output::can_prompt() {
+        output::warning(
+            "--interactive-conflicts needs an interactive terminal; applying the on-conflict policy",
+        );
+        return Ok(choices);
+    }
+    conflicts.sort_by(|a, b| a.0.cmp(&b.0));
+
+    output::header("Cross-backend Conflicts");
+    for (name, mut backends) in conflicts {
+        backends.sort_by(|a, b| a.name().cmp(b.name()));
+        let labels: Vec<String> = backends
+            .iter()
+            .map(|backend| describe_declaration(config, &name, backend))
+            .collect();
+        match output::prompt_choice(
+            &format!(
+                "'{}' is declared on {} backends. Keep which?",
+                name,
+                backends.len()
+            ),
+            &labels,
+        ) {
+            Some(index) => {
+                choices.insert(name, backends[index].clone());
+            }
+            None => output::info(&format!(
+                "Kept every declaration of '{}'; the on-conflict policy applies",
+                name
+            )),
+        }
+    }
+
+    if options.remember_conflicts && !choices.is_empty() {
+        remember_choices(config, &choices, options.dry_run)?;
+    }
+    Ok(choices)
+}
+
+fn describe_declaration(config: &loader::MergedConfig, name: &str, backend: &Backend) -> String {
+    let sources = config
+        .packages
+        .iter()
+        .find(|(pkg, _)| pkg.name == name && &pkg.backend == backend)
+        .map(|(_, sources)| sources.as_slice())
+        .unwrap_or_default();
+    match sources.first() {
+        Some(source) => format!("{}:{} ({})", backend, name, source.display()),
+        None => format!("{}:{}", backend, name),
+    }
+}
+
+/// Remove declarations not kept from their config files, backing each up first.
+fn remember_choices(
+    config: &loader::MergedConfig,
+    choices: &ConflictChoices,
+    dry_run: bool,
+) -> Result<()> {
+    let editor = ConfigEditor::new();
+    for pkg in planner::conflict_losers(config, choices) {
+        let mut sources = config.packages.get(&pkg).cloned().unwrap_or_default();
+        sources.sort();
+        sources.dedup();
+
+        for source in sources {
+            let content = fs::read_to_string(&source)?;
+            let Some(updated) =
+                editor.remove_package_from_content(&content, &pkg.name, pkg.backend.name())?
+            else {
+                continue;
+            };
+            if dry_run {
+                output::info(&format!("Would remove {} from {}", pkg, source.display()));
+                continue;
+            }
+            let backup = backup_kdl_file(&source)?;
+            fs::write(&source, updated)?;
+            output::success(&format!(
+                "Removed {} from {} (backup: {})",
+                pkg,
+                source.display(),
+                backup.display()
+            ));
+        }
+    }
+    Ok(())
+}
@@ -2,56 +2,155 @@
 //!
 //! Installs, adopts, and prunes packages based on transaction plan.
 
+mod command_dump;
 mod install_ops;
+mod progress;
 mod prune;
 mod retry;
 mod snapshot;
+mod validation;
 
-use super::{InstalledSnapshot, ManagerMap, SyncOptions};
+use super::{BackendTimings, InstalledSnapshot, ManagerMap, SyncOptions};
 use crate::config::loader;
 use crate::constants::{BACKEND_OPERATION_MAX_RETRIES, BACKEND_RETRY_DELAY_MS};
-use crate::core::{resolver, types::PackageId};
-use crate::error::Result;
+use crate::core::{
+    resolver,
+    types::{Backend, PackageId},
+};
+use crate::error::{DeclarchError, Result};
+use crate::ui as output;
+pub use command_dump::planned_commands;
 use install_ops::execute_installations;
 use prune::execute_pruning;
 use snapshot::build_installed_snapshot;
+use std::collections::HashMap;
+pub use validation::validate_transaction;
 
 /// Maximum retry attempts for failed backend operations
 const MAX_RETRIES: u32 = BACKEND_OPERATION_MAX_RETRIES;
 /// Delay between retries (in milliseconds)
 const RETRY_DELAY_MS: u64 = BACKEND_RETRY_DELAY_MS;
 
+/// Remotes recorded in state for declared packages, reused when reinstalling them
+pub type PackageRemotes = HashMap<PackageId, String>;
+
+/// A backend operation that failed without aborting the sync
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncFailure {
+    pub backend: Backend,
+    /// Packages the failed operation covered
+    pub packages: Vec<String>,
+    pub error: String,
+}
+
+impl SyncFailure {
+    fn new(backend: &Backend, packages: &[String], error: &DeclarchError) -> Self {
+        Self {
+            backend: backend.clone(),
+            packages: packages.to_vec(),
+            error: error.to_string(),
+        }
+    }
+}
+
+/// The plan and the inputs every execution step reads but does not change
+struct ExecutionContext<'a> {
+    tx: &'a resolver::Transaction,
+    managers: &'a ManagerMap,
+    config: &'a loader::MergedConfig,
+    options: &'a SyncOptions,
+    hooks_enabled: bool,
+}
+
+/// Result of executing a transaction
+#[derive(Debug, Default)]
+pub struct TransactionOutcome {
+    /// Packages verified as installed; these are written to state
+    pub installed: Vec<PackageId>,
+    /// Packages removed by pruning
+    pub pruned: Vec<PackageId>,
+    pub failures: Vec<SyncFailure>,
+    /// Time spent installing and removing, per backend
+    pub backend_timings: BackendTimings,
+}
+
+impl TransactionOutcome {
+    /// Installs and removals that actually ran, per backend
+    pub fn backend_change_counts(&self) -> HashMap<Backend, usize> {
+        let mut counts = HashMap::new();
+        for pkg in self.installed.iter().chain(&self.pruned) {
+            *counts.entry(pkg.backend.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
 /// Execute transaction (install, adopt, prune)
 pub fn execute_transaction(
     transaction: &resolver::Transaction,
     managers: &ManagerMap,
     config: &loader::MergedConfig,
+    remotes: &PackageRemotes,
     options: &SyncOptions,
     hooks_enabled: bool,
-) -> Result<Vec<PackageId>> {
+) -> Result<TransactionOutcome> {
     let mut installed_snapshot = build_installed_snapshot(managers)?;
-
-    // Execute installations
-    let successfully_installed = execute_installations(
-        transaction,
+    let mut failures = Vec::new();
+    let mut backend_timings = BackendTimings::default();
+    let ctx = ExecutionContext {
+        tx: transaction,
         managers,
         config,
         options,
         hooks_enabled,
+    };
+
+    // Execute installations
+    let installed = execute_installations(
+        &ctx,
+        remotes,
         &mut installed_snapshot,
+        &mut failures,
+        &mut backend_timings,
     )?;
 
     // Execute pruning if enabled
-    if options.prune && !transaction.to_prune.is_empty() {
+    let pruned = if options.prune && !transaction.to_prune.is_empty() {
         execute_pruning(
-            config,
-            transaction,
-            managers,
-            options,
-            hooks_enabled,
+            &ctx,
             &installed_snapshot,
-        )?;
-    }
+            &mut failures,
+            &mut backend_timings,
+        )?
+    } else {
+        Vec::new()
+    };
+
+    Ok(TransactionOutcome {
+        installed,
+        pruned,
+        failures,
+        backend_timings,
+    })
+}
 
-    Ok(successfully_installed)
+/// Record a failed operation under `--continue-on-error`, otherwise propagate it.
+///
+/// Returns whether the operation succeeded.
+fn record_failure(
+    result: Result<()>,
+    options: &SyncOptions,
+    failures: &mut Vec<SyncFailure>,
+    backend: &Backend,
+    packages: &[String],
+) -> Result<bool> {
+    match result {
+        Ok(()) => Ok(true),
+        Err(e) if options.continue_on_error => {
+            output::error(&format!("{}: {}", backend, e));
+            failures.push(SyncFailure::new(backend, packages, &e));
+            Ok(false)
+        }
+        Err(e) => Err(e),
+    }
 }
@@ -0,0 +1,118 @@
+//! Shell commands a sync would run (`sync --dump-commands`)
+//!
+//! Plans installs with the same batching as `install_ops` and mirrors `prune`,
+//! so the listed commands match what a real sync spawns, sudo prefix and
+//! noconfirm flag included.
+
+use super::install_ops::{InstallBatches, plan_install_batches};
+use super::{InstalledSnapshot, ManagerMap, PackageRemotes, SyncOptions};
+use crate::config::loader;
+use crate::core::{
+    resolver,
+    types::{Backend, PackageId},
+};
+use crate::error::Result;
+use crate::packages::traits::{PackageManager, PlannedOperation};
+
+/// Commands per backend, in the order a sync would run them
+pub fn planned_commands(
+    tx: &resolver::Transaction,
+    managers: &ManagerMap,
+    config: &loader::MergedConfig,
+    remotes: &PackageRemotes,
+    installed_snapshot: &InstalledSnapshot,
+    options: &SyncOptions,
+) -> Result<Vec<(Backend, Vec<String>)>> {
+    let mut backends: Vec<&Backend> = managers.keys().collect();
+    backends.sort_by(|a, b| a.name().cmp(b.name()));
+
+    let mut planned = Vec::new();
+    for backend in backends {
+        let mgr = &managers[backend];
+        let mut lines = Vec::new();
+        if options.update {
+            lines.extend(mgr.planned_commands(PlannedOperation::Update)?);
+        }
+
+        let installs = names_for(&tx.to_install, backend);
+        if !installs.is_empty() {
+            lines.extend(install_commands(
+                mgr.as_ref(),
+                backend,
+                installs,
+                config,
+                remotes,
+                installed_snapshot,
+                options,
+            )?);
+        }
+
+        if options.prune {
+            let removals = names_for(&tx.to_prune, backend);
+            lines.extend(mgr.planned_commands(PlannedOperation::Remove(&removals))?);
+        }
+
+        if !lines.is_empty() {
+            planned.push((backend.clone(), lines));
+        }
+    }
+    Ok(planned)
+}
+
+fn install_commands(
+    mgr: &dyn PackageManager,
+    backend: &Backend,
+    pkgs: Vec<String>,
+    config: &loader::MergedConfig,
+    remotes: &PackageRemotes,
+    installed_snapshot: &InstalledSnapshot,
+    options: &SyncOptions,
+) -> Result<Vec<String>> {
+    let InstallBatches {
+        batched,
+        by_remote,
+        individual,
+        reinstalls,
+    } = plan_install_batches(backend, &pkgs, config, remotes, options, |pkg| {
+        installed_snapshot.contains_key(&PackageId {
+            name: pkg.to_string(),
+            backend: backend.clone(),
+        })
+    });
+
+    let mut lines = mgr.planned_commands(PlannedOperation::Preinstall)?;
+    if !batched.is_empty() {
+        lines.extend(mgr.planned_commands(PlannedOperation::Install {
+            packages: &batched,
+            extra_args: None,
+            remote: None,
+        })?);
+    }
+    for (remote, pkgs) in &by_remote {
+        lines.extend(mgr.planned_commands(PlannedOperation::Install {
+            packages: pkgs,
+            extra_args: None,
+            remote: Some(remote),
+        })?);
+    }
+    for (pkg, extra_args) in &individual {
+        lines.extend(mgr.planned_commands(PlannedOperation::Install {
+            packages: std::slice::from_ref(pkg),
+            extra_args: Some(extra_args),
+            remote: None,
+        })?);
+    }
+    if !reinstalls.is_empty() {
+        lines.extend(mgr.planned_commands(PlannedOperation::Reinstall(&reinstalls))?);
+    }
+    lines.extend(mgr.planned_commands(PlannedOperation::Postinstall)?);
+    Ok(lines)
+}
+
+fn names_for(packages: &[PackageId], backend: &Backend) -> Vec<String> {
+    packages
+        .iter()
+        .filter(|pkg| &pkg.backend == backend)
+        .map(|pkg| pkg.name.clone())
+        .collect()
+}
@@ -1,24 +1,89 @@
-use super::{InstalledSnapshot, MAX_RETRIES, ManagerMap, RETRY_DELAY_MS, SyncOptions};
+use super::{
+    BackendTimings, ExecutionContext, InstalledSnapshot, MAX_RETRIES, PackageRemotes,
+    RETRY_DELAY_MS, SyncFailure, SyncOptions, record_failure,
+};
 use crate::commands::sync::hooks::{execute_post_install, execute_pre_install};
 use crate::config::loader;
 use crate::core::{
+    matcher::PackageMatcher,
     resolver,
-    types::{Backend, PackageId},
+    types::{Backend, PackageId, PackageMetadata},
 };
-use crate::error::Result;
+use crate::error::{DeclarchError, Result};
+use crate::packages::PackageManager;
 use crate::ui as output;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
+use super::progress::Progress;
 use super::retry::execute_with_retry;
 
+/// What to do with packages whose install failed, asked on interactive syncs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FailureChoice {
+    /// Run the install again for the failed packages
+    Retry,
+    /// Leave them out of this sync (and of state) and carry on
+    Skip,
+    /// Stop the sync with the install error
+    Abort,
+}
+
 pub(super) fn execute_installations(
-    tx: &resolver::Transaction,
-    managers: &ManagerMap,
-    config: &loader::MergedConfig,
-    options: &SyncOptions,
-    hooks_enabled: bool,
+    ctx: &ExecutionContext<'_>,
+    remotes: &PackageRemotes,
     installed_snapshot: &mut InstalledSnapshot,
+    failures: &mut Vec<SyncFailure>,
+    timings: &mut BackendTimings,
 ) -> Result<Vec<PackageId>> {
+    let interactive = can_ask_after_failure(ctx.options);
+    execute_installations_with_prompt(
+        ctx,
+        remotes,
+        installed_snapshot,
+        failures,
+        timings,
+        &mut |backend, failed| interactive.then(|| ask_after_failure(backend, failed)),
+    )
+}
+
+/// Only an attended sync asks; `--yes`, `--noconfirm` and `--continue-on-error` keep going unasked.
+fn can_ask_after_failure(options: &SyncOptions) -> bool {
+    output::is_attended() && !options.yes && !options.noconfirm && !options.continue_on_error
+}
+
+fn ask_after_failure(backend: &Backend, failed: &[String]) -> FailureChoice {
+    let choices = vec![
+        "Retry the failed packages".to_string(),
+        "Skip them and continue".to_string(),
+        "Abort the sync".to_string(),
+    ];
+    match output::prompt_choice(
+        &format!("Install failed for {}: {}", backend, failed.join(", ")),
+        &choices,
+    ) {
+        Some(0) => FailureChoice::Retry,
+        Some(1) => FailureChoice::Skip,
+        _ => FailureChoice::Abort,
+    }
+}
+
+/// `execute_installations` with the failure prompt injected; `None` from
+/// `after_failure` records the failure and moves on to the next backend.
+fn execute_installations_with_prompt(
+    ctx: &ExecutionContext<'_>,
+    remotes: &PackageRemotes,
+    installed_snapshot: &mut InstalledSnapshot,
+    failures: &mut Vec<SyncFailure>,
+    timings: &mut BackendTimings,
+    after_failure: &mut dyn FnMut(&Backend, &[String]) -> Option<FailureChoice>,
+) -> Result<Vec<PackageId>> {
+    let ExecutionContext {
+        tx,
+        managers,
+        config,
+        options,
+        hooks_enabled,
+    } = *ctx;
     let mut installs: HashMap<Backend, Vec<String>> = HashMap::new();
     for pkg in tx.to_install.iter() {
         installs
@@ -28,18 +93,46 @@ pub(super) fn execute_installations(
     }
 
     let mut successfully_installed = Vec::new();
+    let planned = installs
+        .iter()
+        .filter(|(backend, _)| managers.contains_key(*backend))
+        .map(|(_, pkgs)| pkgs.len())
+        .sum();
+    let mut progress = Progress::new("installing", planned);
+    let report_unverified = config
+        .policy
+        .as_ref()
+        .and_then(|policy| policy.verify_installs)
+        .unwrap_or(false);
 
     for (backend, pkgs) in installs {
         if let Some(mgr) = managers.get(&backend) {
-            output::info(&format!("Installing {} packages...", backend));
+            timings.lap(&backend);
+            output::info(&progress.next_batch(&backend, &pkgs));
+
+            // Backend-defined setup runs once per batch; without it the installs cannot succeed.
+            if let Err(e) = mgr.preinstall() {
+                output::error(&format!("Skipping {} installs: {}", backend, e));
+                output::info("Continuing with other backends...");
+                failures.push(SyncFailure::new(&backend, &pkgs, &e));
+                continue;
+            }
 
+            let mut pre_install_ok = true;
             for pkg_name in &pkgs {
-                execute_pre_install(
+                let result = execute_pre_install(
                     &config.lifecycle_actions,
                     pkg_name,
                     hooks_enabled,
                     options.dry_run,
-                )?;
+                );
+                if !record_failure(result, options, failures, &backend, &pkgs)? {
+                    pre_install_ok = false;
+                    break;
+                }
+            }
+            if !pre_install_ok {
+                continue;
             }
 
             let pre_install_snapshot: HashSet<_> = match mgr.list_installed() {
@@ -49,59 +142,121 @@ pub(super) fn execute_installations(
                         "Failed to list installed packages for {}: {}",
                         backend, e
                     ));
+                    failures.push(SyncFailure::new(&backend, &pkgs, &e));
                     continue;
                 }
             };
 
-            let install_result = execute_with_retry(
-                || mgr.install(&pkgs),
-                &format!("install packages for {}", backend),
-                MAX_RETRIES,
-                RETRY_DELAY_MS,
-            );
-
-            if let Err(e) = install_result {
+            let installed_before = successfully_installed.len();
+            let mut pending = pkgs.clone();
+            let reinstalls = loop {
+                let (install_result, reinstalls) = install_packages(
+                    mgr.as_ref(),
+                    &backend,
+                    &pending,
+                    config,
+                    remotes,
+                    options,
+                    &pre_install_snapshot,
+                );
+                let Err(e) = install_result else {
+                    break Some(reinstalls);
+                };
                 output::error(&format!(
                     "Failed to install packages for {}: {}",
                     backend, e
                 ));
-                output::info("Continuing with other backends...");
-                continue;
-            }
 
-            let post_install_snapshot: HashSet<_> = match mgr.list_installed() {
-                Ok(pkgs) => pkgs.keys().cloned().collect(),
-                Err(e) => {
-                    output::warning(&format!(
-                        "Failed to verify installation for {}: {}",
-                        backend, e
-                    ));
-                    for pkg_name in &pkgs {
-                        execute_post_install(
-                            &config.lifecycle_actions,
-                            pkg_name,
+                // Per-package backends (`batch false`) may have installed some of the batch
+                let installed_now: HashSet<String> = mgr
+                    .list_installed()
+                    .map(|pkgs| pkgs.keys().cloned().collect())
+                    .unwrap_or_default();
+                let mut failed = Vec::new();
+                for pkg_name in &pending {
+                    if !pre_install_snapshot.contains(pkg_name) && installed_now.contains(pkg_name)
+                    {
+                        run_post_install(
+                            config,
+                            options,
                             hooks_enabled,
-                            options.dry_run,
+                            failures,
+                            &backend,
+                            pkg_name,
                         )?;
                         successfully_installed.push(PackageId {
                             name: pkg_name.clone(),
                             backend: backend.clone(),
                         });
+                    } else {
+                        failed.push(pkg_name.clone());
                     }
-                    continue;
                 }
-            };
 
-            for pkg_name in &pkgs {
-                if !pre_install_snapshot.contains(pkg_name)
-                    && post_install_snapshot.contains(pkg_name)
+                let choice = if failed.is_empty() {
+                    None
+                } else {
+                    after_failure(&backend, &failed)
+                };
+                match choice {
+                    Some(FailureChoice::Retry) => pending = failed,
+                    Some(FailureChoice::Skip) => {
+                        output::info(&format!(
+                            "Skipped {} package(s) for {}: {}",
+                            failed.len(),
+                            backend,
+                            failed.join(", ")
+                        ));
+                        break None;
+                    }
+                    Some(FailureChoice::Abort) => return Err(e),
+                    None => {
+                        output::info("Continuing with other backends...");
+                        failures.push(SyncFailure::new(&backend, &failed, &e));
+                        break None;
+                    }
+                }
+            };
+            let Some(reinstalls) = reinstalls else {
+                // Part of the batch landed before the failure; finish it off
+                if successfully_installed.len() > installed_before
+                    && let Err(e) = mgr.postinstall()
                 {
-                    execute_post_install(
-                        &config.lifecycle_actions,
-                        pkg_name,
-                        hooks_enabled,
-                        options.dry_run,
-                    )?;
+                    output::warning(&e.to_string());
+                }
+                continue;
+            };
+
+            if let Err(e) = mgr.postinstall() {
+                output::warning(&e.to_string());
+            }
+
+            // Only packages the backend lists afterwards are recorded; if it
+            // cannot be listed, the install command's success is trusted.
+            let unverified = match mgr.list_installed() {
+                Ok(installed_now) => missing_after_install(&backend, &pending, installed_now),
+                Err(e) => {
+                    output::warning(&format!(
+                        "Failed to verify installation for {}: {}",
+                        backend, e
+                    ));
+                    Vec::new()
+                }
+            };
+            if report_unverified && !unverified.is_empty() {
+                let e = DeclarchError::PackageManagerError(format!(
+                    "install reported success but not found afterwards: {}",
+                    unverified.join(", ")
+                ));
+                output::warning(&format!("{}: {}", backend, e));
+                failures.push(SyncFailure::new(&backend, &unverified, &e));
+            }
+
+            for pkg_name in &pending {
+                let was_present =
+                    pre_install_snapshot.contains(pkg_name) && !reinstalls.contains(pkg_name);
+                if !was_present && !unverified.contains(pkg_name) {
+                    run_post_install(config, options, hooks_enabled, failures, &backend, pkg_name)?;
                     successfully_installed.push(PackageId {
                         name: pkg_name.clone(),
                         backend: backend.clone(),
@@ -110,6 +265,7 @@ pub(super) fn execute_installations(
             }
         }
     }
+    timings.stop();
 
     if !tx.to_install.is_empty() && !successfully_installed.is_empty() {
         output::info(&format!(
@@ -121,7 +277,17 @@ pub(super) fn execute_installations(
             if !mgr.is_available() {
                 continue;
             }
-            let packages = mgr.list_installed()?;
+            let packages = match mgr.list_installed() {
+                Ok(packages) => packages,
+                Err(e) if options.continue_on_error => {
+                    output::warning(&format!(
+                        "Failed to refresh installed packages for {}: {}",
+                        backend, e
+                    ));
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
             for (name, meta) in packages {
                 let id = PackageId {
                     name,
@@ -135,19 +301,226 @@ pub(super) fn execute_installations(
     Ok(successfully_installed)
 }
 
+/// Run the install commands for `pkgs`, split the way their declarations need.
+///
+/// Returns the result of the first failing command (later ones are not run)
+/// and the packages that went through `reinstall`.
+fn install_packages(
+    mgr: &dyn PackageManager,
+    backend: &Backend,
+    pkgs: &[String],
+    config: &loader::MergedConfig,
+    remotes: &PackageRemotes,
+    options: &SyncOptions,
+    pre_install_snapshot: &HashSet<String>,
+) -> (Result<()>, Vec<String>) {
+    let InstallBatches {
+        batched,
+        by_remote,
+        individual,
+        reinstalls,
+    } = plan_install_batches(backend, pkgs, config, remotes, options, |pkg| {
+        pre_install_snapshot.contains(pkg)
+    });
+
+    let mut install_result = Ok(());
+    if !batched.is_empty() {
+        install_result = execute_with_retry(
+            || mgr.install(&batched),
+            &format!("install packages for {}", backend),
+            MAX_RETRIES,
+            RETRY_DELAY_MS,
+        );
+    }
+    for (remote, pkgs) in &by_remote {
+        if install_result.is_err() {
+            break;
+        }
+        install_result = execute_with_retry(
+            || mgr.install_from_remote(pkgs, remote),
+            &format!("install packages for {} from {}", backend, remote),
+            MAX_RETRIES,
+            RETRY_DELAY_MS,
+        );
+    }
+    for (pkg, extra_args) in &individual {
+        if install_result.is_err() {
+            break;
+        }
+        install_result = execute_with_retry(
+            || mgr.install_with_args(std::slice::from_ref(pkg), extra_args),
+            &format!("install {} for {}", pkg, backend),
+            MAX_RETRIES,
+            RETRY_DELAY_MS,
+        );
+    }
+    if install_result.is_ok() && !reinstalls.is_empty() {
+        install_result = execute_with_retry(
+            || mgr.reinstall(&reinstalls),
+            &format!("reinstall packages for {}", backend),
+            MAX_RETRIES,
+            RETRY_DELAY_MS,
+        );
+    }
+    (install_result, reinstalls)
+}
+
+/// One backend's install batch, split the way its declarations need.
+pub(super) struct InstallBatches<'a> {
+    /// Packages installed together in one `install` call
+    pub(super) batched: Vec<String>,
+    /// Packages going back to the remote recorded for them in state
+    pub(super) by_remote: BTreeMap<String, Vec<String>>,
+    /// Packages with `extra-args`, installed one by one so the args apply only to them
+    pub(super) individual: Vec<(String, &'a str)>,
+    /// Packages already present that `--reinstall` forced into the plan
+    pub(super) reinstalls: Vec<String>,
+}
+
+/// Split `pkgs` into install batches; shared by sync and `--dump-commands`
+/// so both issue the same commands. `is_present` tells whether a package
+/// is already installed on `backend`.
+pub(super) fn plan_install_batches<'a>(
+    backend: &Backend,
+    pkgs: &[String],
+    config: &'a loader::MergedConfig,
+    remotes: &PackageRemotes,
+    options: &SyncOptions,
+    is_present: impl Fn(&str) -> bool,
+) -> InstallBatches<'a> {
+    // With --reinstall, packages already present were forced in by the planner
+    let (reinstalls, fresh): (Vec<String>, Vec<String>) = pkgs
+        .iter()
+        .cloned()
+        .partition(|pkg| !options.reinstall.is_empty() && is_present(pkg));
+
+    let mut individual = Vec::new();
+    let mut rest = Vec::new();
+    for pkg in fresh {
+        match extra_args_for(config, backend, &pkg) {
+            Some(args) => individual.push((pkg, args)),
+            None => rest.push(pkg),
+        }
+    }
+    let (batched, by_remote) = group_by_remote(remotes, backend, rest);
+
+    InstallBatches {
+        batched,
+        by_remote,
+        individual,
+        reinstalls,
+    }
+}
+
+/// Per-package install arguments declared with `extra-args="..."`.
+///
+/// `name` is the package as installed; the args are keyed by the declared
+/// name, which a package alias may map to a different one.
+fn extra_args_for<'a>(
+    config: &'a loader::MergedConfig,
+    backend: &Backend,
+    name: &str,
+) -> Option<&'a str> {
+    config
+        .package_extra_args
+        .iter()
+        .find(|(declared, _)| {
+            &declared.backend == backend
+                && resolver::resolve_package_alias(config, declared).name == name
+        })
+        .map(|(_, args)| args.as_str())
+}
+
+/// Split `packages` into those without a recorded remote and per-remote groups.
+fn group_by_remote(
+    remotes: &PackageRemotes,
+    backend: &Backend,
+    packages: Vec<String>,
+) -> (Vec<String>, BTreeMap<String, Vec<String>>) {
+    let mut plain = Vec::new();
+    let mut by_remote: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for pkg in packages {
+        let id = PackageId {
+            name: pkg.clone(),
+            backend: backend.clone(),
+        };
+        match remotes.get(&id) {
+            Some(remote) => by_remote.entry(remote.clone()).or_default().push(pkg),
+            None => plain.push(pkg),
+        }
+    }
+    (plain, by_remote)
+}
+
+/// Packages of a successful batch that the backend still does not list.
+///
+/// Matching follows state resolution, so a package that landed under a variant
+/// name (`bat` as `bat-bin`) counts as present.
+fn missing_after_install(
+    backend: &Backend,
+    pkgs: &[String],
+    installed_now: HashMap<String, PackageMetadata>,
+) -> Vec<String> {
+    let snapshot: InstalledSnapshot = installed_now
+        .into_iter()
+        .map(|(name, meta)| {
+            let id = PackageId {
+                name,
+                backend: backend.clone(),
+            };
+            (id, meta)
+        })
+        .collect();
+    let matcher = PackageMatcher::new();
+    pkgs.iter()
+        .filter(|name| {
+            let id = PackageId {
+                name: (*name).clone(),
+                backend: backend.clone(),
+            };
+            matcher.find_package(&id, &snapshot).is_none()
+        })
+        .cloned()
+        .collect()
+}
+
+/// Post-install hooks; a failing hook does not undo the install itself
+fn run_post_install(
+    config: &loader::MergedConfig,
+    options: &SyncOptions,
+    hooks_enabled: bool,
+    failures: &mut Vec<SyncFailure>,
+    backend: &Backend,
+    pkg_name: &str,
+) -> Result<()> {
+    let result = execute_post_install(
+        &config.lifecycle_actions,
+        pkg_name,
+        hooks_enabled,
+        options.dry_run,
+    );
+    record_failure(result, options, failures, backend, &[pkg_name.to_string()]).map(|_| ())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::types::PackageMetadata;
+    use crate::commands::sync::ManagerMap;
     use crate::packages::traits::PackageManager;
     use chrono::Utc;
-    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
 
     struct MockManager {
         backend: Backend,
         available: bool,
         install_ok: bool,
+        preinstall_ok: bool,
         installed_sequence: Mutex<Vec<HashMap<String, PackageMetadata>>>,
+        reinstalled: Mutex<Vec<String>>,
+        /// Each install command as `pkgs` or `pkgs [args]`
+        install_calls: Arc<Mutex<Vec<String>>>,
+        postinstall_runs: Arc<AtomicUsize>,
     }
 
     impl PackageManager for MockManager {
@@ -167,7 +540,11 @@ mod tests {
             }
         }
 
-        fn install(&self, _packages: &[String]) -> Result<()> {
+        fn install(&self, packages: &[String]) -> Result<()> {
+            self.install_calls
+                .lock()
+                .expect("lock install calls")
+                .push(packages.join(" "));
             if self.install_ok {
                 Ok(())
             } else {
@@ -177,10 +554,41 @@ mod tests {
             }
         }
 
+        fn install_with_args(&self, packages: &[String], extra_args: &str) -> Result<()> {
+            self.install_calls
+                .lock()
+                .expect("lock install calls")
+                .push(format!("{} [{}]", packages.join(" "), extra_args));
+            Ok(())
+        }
+
+        fn reinstall(&self, packages: &[String]) -> Result<()> {
+            self.reinstalled
+                .lock()
+                .expect("lock reinstalled")
+                .extend_from_slice(packages);
+            self.install(packages)
+        }
+
         fn remove(&self, _packages: &[String]) -> Result<()> {
             Ok(())
         }
 
+        fn preinstall(&self) -> Result<()> {
+            if self.preinstall_ok {
+                Ok(())
+            } else {
+                Err(crate::error::DeclarchError::Other(
+                    "preinstall failed".to_string(),
+                ))
+            }
+        }
+
+        fn postinstall(&self) -> Result<()> {
+            self.postinstall_runs.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+
         fn is_available(&self) -> bool {
             self.available
         }
@@ -192,21 +600,8 @@ mod tests {
 
     fn base_options() -> SyncOptions {
         SyncOptions {
-            dry_run: false,
-            prune: false,
-            update: false,
-            verbose: false,
             yes: true,
-            force: false,
-            target: None,
-            noconfirm: false,
-            hooks: false,
-            profile: None,
-            host: None,
-            modules: Vec::new(),
-            diff: false,
-            format: None,
-            output_version: None,
+            ..Default::default()
         }
     }
 
@@ -216,6 +611,8 @@ mod tests {
             variant: None,
             installed_at: Utc::now(),
             source_file: None,
+            install_size: None,
+            origin: None,
         }
     }
 
@@ -232,6 +629,7 @@ mod tests {
             to_prune: Vec::new(),
             to_adopt: Vec::new(),
             to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
         };
 
         let mut pre = HashMap::new();
@@ -247,23 +645,583 @@ mod tests {
                 backend: backend.clone(),
                 available: true,
                 install_ok: true,
+                preinstall_ok: true,
                 installed_sequence: Mutex::new(vec![pre, post]),
+                reinstalled: Mutex::new(Vec::new()),
+                install_calls: Arc::default(),
+                postinstall_runs: Arc::default(),
             }),
         );
 
         let config = loader::MergedConfig::default();
         let mut installed_snapshot = InstalledSnapshot::new();
         let installed = execute_installations(
-            &tx,
-            &managers,
-            &config,
-            &base_options(),
-            false,
+            &ExecutionContext {
+                tx: &tx,
+                managers: &managers,
+                config: &config,
+                options: &base_options(),
+                hooks_enabled: false,
+            },
+            &PackageRemotes::new(),
             &mut installed_snapshot,
+            &mut Vec::new(),
+            &mut BackendTimings::default(),
         )
         .expect("installations should succeed");
 
         assert_eq!(installed.len(), 1);
         assert_eq!(installed[0].name, "bat");
     }
+
+    #[test]
+    fn execute_installations_skips_backend_when_preinstall_fails() {
+        let backend = Backend::from("flatpak");
+        let tx = resolver::Transaction {
+            to_install: vec![PackageId {
+                name: "org.mozilla.firefox".to_string(),
+                backend: backend.clone(),
+            }],
+            to_prune: Vec::new(),
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+
+        let mut post = HashMap::new();
+        post.insert("org.mozilla.firefox".to_string(), package_meta());
+
+        let mut managers: ManagerMap = HashMap::new();
+        managers.insert(
+            backend.clone(),
+            Box::new(MockManager {
+                backend: backend.clone(),
+                available: true,
+                install_ok: true,
+                preinstall_ok: false,
+                installed_sequence: Mutex::new(vec![HashMap::new(), post]),
+                reinstalled: Mutex::new(Vec::new()),
+                install_calls: Arc::default(),
+                postinstall_runs: Arc::default(),
+            }),
+        );
+
+        let config = loader::MergedConfig::default();
+        let mut installed_snapshot = InstalledSnapshot::new();
+        let installed = execute_installations(
+            &ExecutionContext {
+                tx: &tx,
+                managers: &managers,
+                config: &config,
+                options: &base_options(),
+                hooks_enabled: false,
+            },
+            &PackageRemotes::new(),
+            &mut installed_snapshot,
+            &mut Vec::new(),
+            &mut BackendTimings::default(),
+        )
+        .expect("preinstall failure should not abort the whole sync");
+
+        assert!(installed.is_empty());
+    }
+
+    #[test]
+    fn execute_installations_records_failed_backend_and_keeps_going() {
+        let aur = Backend::from("aur");
+        let npm = Backend::from("npm");
+        let tx = resolver::Transaction {
+            to_install: vec![
+                PackageId {
+                    name: "bat".to_string(),
+                    backend: aur.clone(),
+                },
+                PackageId {
+                    name: "typescript".to_string(),
+                    backend: npm.clone(),
+                },
+            ],
+            to_prune: Vec::new(),
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+
+        let mut post = HashMap::new();
+        post.insert("bat".to_string(), package_meta());
+
+        let mut managers: ManagerMap = HashMap::new();
+        managers.insert(
+            aur.clone(),
+            Box::new(MockManager {
+                backend: aur.clone(),
+                available: true,
+                install_ok: true,
+                preinstall_ok: true,
+                installed_sequence: Mutex::new(vec![HashMap::new(), post]),
+                reinstalled: Mutex::new(Vec::new()),
+                install_calls: Arc::default(),
+                postinstall_runs: Arc::default(),
+            }),
+        );
+        managers.insert(
+            npm.clone(),
+            Box::new(MockManager {
+                backend: npm.clone(),
+                available: true,
+                install_ok: true,
+                preinstall_ok: false,
+                installed_sequence: Mutex::new(Vec::new()),
+                reinstalled: Mutex::new(Vec::new()),
+                install_calls: Arc::default(),
+                postinstall_runs: Arc::default(),
+            }),
+        );
+
+        let mut options = base_options();
+        options.continue_on_error = true;
+        let config = loader::MergedConfig::default();
+        let mut installed_snapshot = InstalledSnapshot::new();
+        let mut failures = Vec::new();
+        let installed = execute_installations(
+            &ExecutionContext {
+                tx: &tx,
+                managers: &managers,
+                config: &config,
+                options: &options,
+                hooks_enabled: false,
+            },
+            &PackageRemotes::new(),
+            &mut installed_snapshot,
+            &mut failures,
+            &mut BackendTimings::default(),
+        )
+        .expect("failures are collected, not returned");
+
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].name, "bat");
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].backend, npm);
+        assert_eq!(failures[0].packages, vec!["typescript".to_string()]);
+    }
+
+    fn failing_install_setup(
+        installed_sequence: Vec<HashMap<String, PackageMetadata>>,
+    ) -> (resolver::Transaction, ManagerMap, Arc<Mutex<Vec<String>>>) {
+        let backend = Backend::from("aur");
+        let tx = resolver::Transaction {
+            to_install: ["bat", "bta"]
+                .into_iter()
+                .map(|name| PackageId {
+                    name: name.to_string(),
+                    backend: backend.clone(),
+                })
+                .collect(),
+            to_prune: Vec::new(),
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+        let install_calls: Arc<Mutex<Vec<String>>> = Arc::default();
+        let mut managers: ManagerMap = HashMap::new();
+        managers.insert(
+            backend.clone(),
+            Box::new(MockManager {
+                backend,
+                available: true,
+                install_ok: false,
+                preinstall_ok: true,
+                installed_sequence: Mutex::new(installed_sequence),
+                reinstalled: Mutex::new(Vec::new()),
+                install_calls: Arc::clone(&install_calls),
+                postinstall_runs: Arc::default(),
+            }),
+        );
+        (tx, managers, install_calls)
+    }
+
+    #[test]
+    fn failure_prompt_retries_then_skips_the_failed_packages() {
+        // `bat` lands despite the failed batch; the typo `bta` never does
+        let mut partial = HashMap::new();
+        partial.insert("bat".to_string(), package_meta());
+        let (tx, managers, install_calls) =
+            failing_install_setup(vec![HashMap::new(), partial.clone(), partial]);
+
+        let mut asked = Vec::new();
+        let mut failures = Vec::new();
+        let installed = execute_installations_with_prompt(
+            &ExecutionContext {
+                tx: &tx,
+                managers: &managers,
+                config: &loader::MergedConfig::default(),
+                options: &base_options(),
+                hooks_enabled: false,
+            },
+            &PackageRemotes::new(),
+            &mut InstalledSnapshot::new(),
+            &mut failures,
+            &mut BackendTimings::default(),
+            &mut |_, failed| {
+                asked.push(failed.to_vec());
+                Some(if asked.len() == 1 {
+                    FailureChoice::Retry
+                } else {
+                    FailureChoice::Skip
+                })
+            },
+        )
+        .expect("skipping keeps the sync going");
+
+        assert_eq!(
+            asked,
+            vec![vec!["bta".to_string()], vec!["bta".to_string()]]
+        );
+        // Each attempt already goes through the backend retry loop
+        let mut calls = install_calls.lock().unwrap().clone();
+        calls.dedup();
+        assert_eq!(calls, vec!["bat bta".to_string(), "bta".to_string()]);
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].name, "bat");
+        assert!(failures.is_empty(), "skipped packages are not failures");
+    }
+
+    #[test]
+    fn skipping_failed_packages_still_runs_postinstall_for_the_rest() {
+        let backend = Backend::from("aur");
+        let tx = resolver::Transaction {
+            to_install: ["bat", "bta"]
+                .into_iter()
+                .map(|name| PackageId {
+                    name: name.to_string(),
+                    backend: backend.clone(),
+                })
+                .collect(),
+            to_prune: Vec::new(),
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+        let mut partial = HashMap::new();
+        partial.insert("bat".to_string(), package_meta());
+        let postinstall_runs: Arc<AtomicUsize> = Arc::default();
+        let mut managers: ManagerMap = HashMap::new();
+        managers.insert(
+            backend.clone(),
+            Box::new(MockManager {
+                backend,
+                available: true,
+                install_ok: false,
+                preinstall_ok: true,
+                installed_sequence: Mutex::new(vec![HashMap::new(), partial]),
+                reinstalled: Mutex::new(Vec::new()),
+                install_calls: Arc::default(),
+                postinstall_runs: Arc::clone(&postinstall_runs),
+            }),
+        );
+
+        let installed = execute_installations_with_prompt(
+            &ExecutionContext {
+                tx: &tx,
+                managers: &managers,
+                config: &loader::MergedConfig::default(),
+                options: &base_options(),
+                hooks_enabled: false,
+            },
+            &PackageRemotes::new(),
+            &mut InstalledSnapshot::new(),
+            &mut Vec::new(),
+            &mut BackendTimings::default(),
+            &mut |_, _| Some(FailureChoice::Skip),
+        )
+        .expect("skipping keeps the sync going");
+
+        assert_eq!(installed.len(), 1);
+        assert_eq!(postinstall_runs.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn failure_prompt_abort_stops_the_sync() {
+        let (tx, managers, install_calls) = failing_install_setup(Vec::new());
+
+        let result = execute_installations_with_prompt(
+            &ExecutionContext {
+                tx: &tx,
+                managers: &managers,
+                config: &loader::MergedConfig::default(),
+                options: &base_options(),
+                hooks_enabled: false,
+            },
+            &PackageRemotes::new(),
+            &mut InstalledSnapshot::new(),
+            &mut Vec::new(),
+            &mut BackendTimings::default(),
+            &mut |_, _| Some(FailureChoice::Abort),
+        );
+
+        assert!(result.is_err());
+        assert!(
+            install_calls
+                .lock()
+                .unwrap()
+                .iter()
+                .all(|call| call == "bat bta"),
+            "abort does not retry"
+        );
+    }
+
+    #[test]
+    fn execute_installations_reinstalls_present_packages() {
+        let backend = Backend::from("aur");
+        let tx = resolver::Transaction {
+            to_install: vec![
+                PackageId {
+                    name: "bat".to_string(),
+                    backend: backend.clone(),
+                },
+                PackageId {
+                    name: "fd".to_string(),
+                    backend: backend.clone(),
+                },
+            ],
+            to_prune: Vec::new(),
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+
+        let mut pre = HashMap::new();
+        pre.insert("bat".to_string(), package_meta());
+        let mut post = pre.clone();
+        post.insert("fd".to_string(), package_meta());
+
+        let manager = MockManager {
+            backend: backend.clone(),
+            available: true,
+            install_ok: true,
+            preinstall_ok: true,
+            installed_sequence: Mutex::new(vec![pre, post]),
+            reinstalled: Mutex::new(Vec::new()),
+            install_calls: Arc::default(),
+            postinstall_runs: Arc::default(),
+        };
+        let mut managers: ManagerMap = HashMap::new();
+        managers.insert(backend.clone(), Box::new(manager));
+
+        let mut options = base_options();
+        options.reinstall = vec!["bat".to_string()];
+        let config = loader::MergedConfig::default();
+        let mut installed_snapshot = InstalledSnapshot::new();
+        let mut installed = execute_installations(
+            &ExecutionContext {
+                tx: &tx,
+                managers: &managers,
+                config: &config,
+                options: &options,
+                hooks_enabled: false,
+            },
+            &PackageRemotes::new(),
+            &mut installed_snapshot,
+            &mut Vec::new(),
+            &mut BackendTimings::default(),
+        )
+        .expect("installations should succeed");
+
+        installed.sort_by(|a, b| a.name.cmp(&b.name));
+        let names: Vec<_> = installed.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["bat", "fd"]);
+    }
+
+    #[test]
+    fn package_with_extra_args_is_installed_in_its_own_command() {
+        let pip = Backend::from("pip");
+        let id = |name: &str| PackageId {
+            name: name.to_string(),
+            backend: pip.clone(),
+        };
+        let tx = resolver::Transaction {
+            to_install: vec![id("numpy"), id("tensorflow"), id("requests")],
+            to_prune: Vec::new(),
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+
+        let install_calls = Arc::new(Mutex::new(Vec::new()));
+        let mut managers: ManagerMap = HashMap::new();
+        managers.insert(
+            pip.clone(),
+            Box::new(MockManager {
+                backend: pip.clone(),
+                available: true,
+                install_ok: true,
+                preinstall_ok: true,
+                installed_sequence: Mutex::new(Vec::new()),
+                reinstalled: Mutex::new(Vec::new()),
+                install_calls: Arc::clone(&install_calls),
+                postinstall_runs: Arc::default(),
+            }),
+        );
+
+        let mut config = loader::MergedConfig::default();
+        config
+            .package_extra_args
+            .insert(id("tensorflow"), "--no-build-isolation".to_string());
+        execute_installations(
+            &ExecutionContext {
+                tx: &tx,
+                managers: &managers,
+                config: &config,
+                options: &base_options(),
+                hooks_enabled: false,
+            },
+            &PackageRemotes::new(),
+            &mut InstalledSnapshot::new(),
+            &mut Vec::new(),
+            &mut BackendTimings::default(),
+        )
+        .expect("installations should succeed");
+
+        assert_eq!(
+            *install_calls.lock().unwrap(),
+            vec!["numpy requests", "tensorflow [--no-build-isolation]"]
+        );
+    }
+
+    #[test]
+    fn aliased_package_keeps_extra_args_declared_under_its_config_name() {
+        let pip = Backend::from("pip");
+        let tx = resolver::Transaction {
+            to_install: vec![PackageId {
+                name: "tensorflow-cpu".to_string(),
+                backend: pip.clone(),
+            }],
+            to_prune: Vec::new(),
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+
+        let install_calls = Arc::new(Mutex::new(Vec::new()));
+        let mut managers: ManagerMap = HashMap::new();
+        managers.insert(
+            pip.clone(),
+            Box::new(MockManager {
+                backend: pip.clone(),
+                available: true,
+                install_ok: true,
+                preinstall_ok: true,
+                installed_sequence: Mutex::new(Vec::new()),
+                reinstalled: Mutex::new(Vec::new()),
+                install_calls: Arc::clone(&install_calls),
+                postinstall_runs: Arc::default(),
+            }),
+        );
+
+        let mut config = loader::MergedConfig::default();
+        config
+            .package_aliases
+            .insert("tensorflow".to_string(), "tensorflow-cpu".to_string());
+        config.package_extra_args.insert(
+            PackageId {
+                name: "tensorflow".to_string(),
+                backend: pip.clone(),
+            },
+            "--no-build-isolation".to_string(),
+        );
+        execute_installations(
+            &ExecutionContext {
+                tx: &tx,
+                managers: &managers,
+                config: &config,
+                options: &base_options(),
+                hooks_enabled: false,
+            },
+            &PackageRemotes::new(),
+            &mut InstalledSnapshot::new(),
+            &mut Vec::new(),
+            &mut BackendTimings::default(),
+        )
+        .expect("installations should succeed");
+
+        assert_eq!(
+            *install_calls.lock().unwrap(),
+            vec!["tensorflow-cpu [--no-build-isolation]"]
+        );
+    }
+
+    #[test]
+    fn packages_missing_after_install_are_not_recorded_and_policy_reports_them() {
+        let backend = Backend::from("aur");
+        let tx = resolver::Transaction {
+            to_install: ["bat", "ghost"]
+                .iter()
+                .map(|name| PackageId {
+                    name: name.to_string(),
+                    backend: backend.clone(),
+                })
+                .collect(),
+            to_prune: Vec::new(),
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+        let manager = || {
+            let mut post = HashMap::new();
+            post.insert("bat-bin".to_string(), package_meta());
+            let mut managers: ManagerMap = HashMap::new();
+            managers.insert(
+                backend.clone(),
+                Box::new(MockManager {
+                    backend: backend.clone(),
+                    available: true,
+                    install_ok: true,
+                    preinstall_ok: true,
+                    installed_sequence: Mutex::new(vec![HashMap::new(), post]),
+                    reinstalled: Mutex::new(Vec::new()),
+                    install_calls: Arc::default(),
+                    postinstall_runs: Arc::default(),
+                }),
+            );
+            managers
+        };
+        let run = |config: &loader::MergedConfig, failures: &mut Vec<SyncFailure>| {
+            let mut names: Vec<String> = execute_installations(
+                &ExecutionContext {
+                    tx: &tx,
+                    managers: &manager(),
+                    config,
+                    options: &base_options(),
+                    hooks_enabled: false,
+                },
+                &PackageRemotes::new(),
+                &mut InstalledSnapshot::new(),
+                failures,
+                &mut BackendTimings::default(),
+            )
+            .expect("installations should succeed")
+            .into_iter()
+            .map(|pkg| pkg.name)
+            .collect();
+            names.sort();
+            names
+        };
+
+        let mut failures = Vec::new();
+        let unreported = run(&loader::MergedConfig::default(), &mut failures);
+        assert_eq!(unreported, vec!["bat"]);
+        assert!(failures.is_empty());
+
+        let config = loader::MergedConfig {
+            policy: Some(crate::config::kdl::PolicyConfig {
+                verify_installs: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let verified = run(&config, &mut failures);
+        assert_eq!(verified, vec!["bat"]);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].packages, vec!["ghost".to_string()]);
+    }
 }
@@ -0,0 +1,79 @@
+use crate::core::types::Backend;
+
+/// Package names shown in a batch progress line before eliding the rest
+const MAX_NAMES_SHOWN: usize = 5;
+
+/// `[done/total]` counter across the install or remove batches of one sync.
+///
+/// Backends work in batches, so the counter advances by batch size rather
+/// than by one.
+pub(super) struct Progress {
+    verb: &'static str,
+    done: usize,
+    total: usize,
+}
+
+impl Progress {
+    pub(super) fn new(verb: &'static str, total: usize) -> Self {
+        Self {
+            verb,
+            done: 0,
+            total,
+        }
+    }
+
+    /// Line announcing the next batch; counts its packages as started.
+    pub(super) fn next_batch(&mut self, backend: &Backend, packages: &[String]) -> String {
+        let first = self.done + 1;
+        self.done += packages.len();
+
+        match packages {
+            [single] => format!(
+                "[{}/{}] {} {}:{}",
+                first, self.total, self.verb, backend, single
+            ),
+            _ => {
+                let mut names = packages
+                    .iter()
+                    .take(MAX_NAMES_SHOWN)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if packages.len() > MAX_NAMES_SHOWN {
+                    names.push_str(&format!(" (+{} more)", packages.len() - MAX_NAMES_SHOWN));
+                }
+                format!(
+                    "[{}-{}/{}] {} {}: {}",
+                    first, self.done, self.total, self.verb, backend, names
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn counter_advances_by_batch_size() {
+        let mut progress = Progress::new("installing", 8);
+        let aur = Backend::from("aur");
+
+        assert_eq!(
+            progress.next_batch(&aur, &names(&["neovim"])),
+            "[1/8] installing aur:neovim"
+        );
+        assert_eq!(
+            progress.next_batch(
+                &Backend::from("npm"),
+                &names(&["a", "b", "c", "d", "e", "f", "g"])
+            ),
+            "[2-8/8] installing npm: a, b, c, d, e (+2 more)"
+        );
+    }
+}
@@ -1,22 +1,56 @@
-use super::{InstalledSnapshot, ManagerMap, SyncOptions};
+use super::progress::Progress;
+use super::{BackendTimings, ExecutionContext, InstalledSnapshot, SyncFailure, record_failure};
 use crate::commands::sync::hooks::{execute_post_remove, execute_pre_remove};
 use crate::commands::sync::variants::resolve_installed_package_name;
-use crate::config::loader;
-use crate::constants::CRITICAL_PACKAGES;
-use crate::core::{resolver, types::Backend};
+use crate::constants::{CRITICAL_PACKAGES, CriticalCategory, critical_category};
+use crate::core::{
+    resolver,
+    types::{Backend, PackageId},
+};
 use crate::error::Result;
 use crate::ui as output;
 use colored::Colorize;
 use std::collections::{HashMap, HashSet};
 
 pub(super) fn execute_pruning(
-    config: &loader::MergedConfig,
-    tx: &resolver::Transaction,
-    managers: &ManagerMap,
-    options: &SyncOptions,
-    hooks_enabled: bool,
+    ctx: &ExecutionContext<'_>,
     installed_snapshot: &InstalledSnapshot,
-) -> Result<()> {
+    failures: &mut Vec<SyncFailure>,
+    timings: &mut BackendTimings,
+) -> Result<Vec<PackageId>> {
+    execute_pruning_with_confirm(
+        ctx,
+        installed_snapshot,
+        failures,
+        timings,
+        &mut confirm_critical_prune,
+    )
+}
+
+/// Typed confirmation for kernel/bootloader/init prunes; `--yes` does not skip it.
+fn confirm_critical_prune(name: &str, category: CriticalCategory) -> bool {
+    output::warning(&format!(
+        "'{}' looks like a {} package. Removing it can leave the system unbootable.",
+        name,
+        category.label()
+    ));
+    output::prompt_typed_confirmation("Remove it anyway?", name)
+}
+
+fn execute_pruning_with_confirm(
+    ctx: &ExecutionContext<'_>,
+    installed_snapshot: &InstalledSnapshot,
+    failures: &mut Vec<SyncFailure>,
+    timings: &mut BackendTimings,
+    confirm_critical: &mut dyn FnMut(&str, CriticalCategory) -> bool,
+) -> Result<Vec<PackageId>> {
+    let ExecutionContext {
+        tx,
+        managers,
+        config,
+        options,
+        hooks_enabled,
+    } = *ctx;
     let orphan_strategy = config
         .policy
         .as_ref()
@@ -26,7 +60,7 @@ pub(super) fn execute_pruning(
 
     if orphan_strategy == "keep" {
         output::info("Skipping orphan removal (policy.orphans = \"keep\")");
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     if orphan_strategy == "ask"
@@ -34,15 +68,16 @@ pub(super) fn execute_pruning(
         && !output::prompt_yes_no("Policy requests confirmation for orphan removal. Continue?")
     {
         output::info("Skipping orphan removal");
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     let mut protected_physical_names: Vec<String> = Vec::new();
     for pkg in config.packages.keys() {
-        if config.excludes.contains(&pkg.name) {
+        if config.is_excluded(pkg) {
             continue;
         }
-        let real_name = resolve_installed_package_name(pkg, installed_snapshot);
+        let pkg = resolver::resolve_package_alias(config, pkg);
+        let real_name = resolve_installed_package_name(&pkg, installed_snapshot);
         protected_physical_names.push(real_name);
     }
 
@@ -59,12 +94,28 @@ pub(super) fn execute_pruning(
             continue;
         }
 
-        execute_pre_remove(
+        if let Some(category) = critical_category(&pkg.name)
+            && !confirm_critical(&pkg.name, category)
+        {
+            output::info(&format!("Keeping '{}' (not confirmed)", pkg.name));
+            continue;
+        }
+
+        let pre_remove = execute_pre_remove(
             &config.lifecycle_actions,
             &pkg.name,
             hooks_enabled,
             options.dry_run,
-        )?;
+        );
+        if !record_failure(
+            pre_remove,
+            options,
+            failures,
+            &pkg.backend,
+            std::slice::from_ref(&pkg.name),
+        )? {
+            continue;
+        }
 
         let real_name = resolve_installed_package_name(pkg, installed_snapshot);
         if protected_physical_names.contains(&real_name) {
@@ -85,31 +136,44 @@ pub(super) fn execute_pruning(
             .push((real_name, pkg.name.clone()));
     }
 
+    let planned = removes
+        .iter()
+        .filter(|(backend, _)| managers.contains_key(*backend))
+        .map(|(_, pkgs)| pkgs.len())
+        .sum();
+    let mut progress = Progress::new("removing", planned);
+    let mut pruned = Vec::new();
+
     for (backend, pkgs) in removes {
         if !pkgs.is_empty()
             && let Some(mgr) = managers.get(&backend)
         {
-            output::info(&format!("Removing {} packages...", backend));
+            timings.lap(&backend);
+            output::info(&progress.next_batch(&backend, &pkgs));
             match mgr.remove(&pkgs) {
                 Ok(()) => {
-                    if let Some(hook_entries) = remove_hooks.get(&backend) {
-                        for (_, config_name) in hook_entries {
-                            execute_post_remove(
-                                &config.lifecycle_actions,
-                                config_name,
-                                hooks_enabled,
-                                options.dry_run,
-                            )?;
-                        }
-                    } else {
-                        for pkg_name in &pkgs {
-                            execute_post_remove(
-                                &config.lifecycle_actions,
-                                pkg_name,
-                                hooks_enabled,
-                                options.dry_run,
-                            )?;
-                        }
+                    let hook_names: Vec<&String> = match remove_hooks.get(&backend) {
+                        Some(hook_entries) => hook_entries.iter().map(|(_, name)| name).collect(),
+                        None => pkgs.iter().collect(),
+                    };
+                    for pkg_name in hook_names {
+                        pruned.push(PackageId {
+                            name: pkg_name.clone(),
+                            backend: backend.clone(),
+                        });
+                        let post_remove = execute_post_remove(
+                            &config.lifecycle_actions,
+                            pkg_name,
+                            hooks_enabled,
+                            options.dry_run,
+                        );
+                        record_failure(
+                            post_remove,
+                            options,
+                            failures,
+                            &backend,
+                            std::slice::from_ref(pkg_name),
+                        )?;
                     }
                 }
                 Err(e) => {
@@ -122,26 +186,29 @@ pub(super) fn execute_pruning(
                         ));
                         output::info(&format!("Packages not removed: {}", pkgs.join(", ")));
                     } else {
-                        return Err(e);
+                        record_failure(Err(e), options, failures, &backend, &pkgs)?;
                     }
                 }
             }
         }
     }
+    timings.stop();
 
-    Ok(())
+    Ok(pruned)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::types::PackageId;
+    use crate::commands::sync::{ManagerMap, SyncOptions};
+    use crate::config::loader;
     use crate::packages::traits::PackageManager;
+    use std::sync::Arc;
     use std::sync::atomic::{AtomicUsize, Ordering};
 
     struct MockManager {
         backend: Backend,
-        remove_calls: AtomicUsize,
+        remove_calls: Arc<AtomicUsize>,
         remove_error: Option<String>,
     }
 
@@ -179,21 +246,9 @@ mod tests {
 
     fn base_options() -> SyncOptions {
         SyncOptions {
-            dry_run: false,
             prune: true,
-            update: false,
-            verbose: false,
             yes: true,
-            force: false,
-            target: None,
-            noconfirm: false,
-            hooks: false,
-            profile: None,
-            host: None,
-            modules: Vec::new(),
-            diff: false,
-            format: None,
-            output_version: None,
+            ..Default::default()
         }
     }
 
@@ -212,11 +267,23 @@ mod tests {
             }],
             to_adopt: Vec::new(),
             to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
         };
         let managers: ManagerMap = HashMap::new();
         let snapshot = InstalledSnapshot::new();
 
-        let out = execute_pruning(&config, &tx, &managers, &base_options(), false, &snapshot);
+        let out = execute_pruning(
+            &ExecutionContext {
+                tx: &tx,
+                managers: &managers,
+                config: &config,
+                options: &base_options(),
+                hooks_enabled: false,
+            },
+            &snapshot,
+            &mut Vec::new(),
+            &mut BackendTimings::default(),
+        );
         assert!(out.is_ok());
     }
 
@@ -236,18 +303,157 @@ mod tests {
             }],
             to_adopt: Vec::new(),
             to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
         };
 
         let manager = MockManager {
             backend: backend.clone(),
-            remove_calls: AtomicUsize::new(0),
+            remove_calls: Arc::new(AtomicUsize::new(0)),
             remove_error: Some("backend does not support removing".to_string()),
         };
         let mut managers: ManagerMap = HashMap::new();
         managers.insert(backend, Box::new(manager));
         let snapshot = InstalledSnapshot::new();
 
-        let out = execute_pruning(&config, &tx, &managers, &base_options(), false, &snapshot);
+        let out = execute_pruning(
+            &ExecutionContext {
+                tx: &tx,
+                managers: &managers,
+                config: &config,
+                options: &base_options(),
+                hooks_enabled: false,
+            },
+            &snapshot,
+            &mut Vec::new(),
+            &mut BackendTimings::default(),
+        );
         assert!(out.is_ok());
     }
+
+    #[test]
+    fn prune_failure_is_recorded_with_continue_on_error() {
+        let config = loader::MergedConfig::default();
+        let backend = Backend::from("aur");
+        let tx = resolver::Transaction {
+            to_install: Vec::new(),
+            to_prune: vec![PackageId {
+                name: "bat".to_string(),
+                backend: backend.clone(),
+            }],
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+        let mut managers: ManagerMap = HashMap::new();
+        managers.insert(
+            backend.clone(),
+            Box::new(MockManager {
+                backend: backend.clone(),
+                remove_calls: Arc::new(AtomicUsize::new(0)),
+                remove_error: Some("target not found".to_string()),
+            }),
+        );
+        let snapshot = InstalledSnapshot::new();
+
+        let strict = execute_pruning(
+            &ExecutionContext {
+                tx: &tx,
+                managers: &managers,
+                config: &config,
+                options: &base_options(),
+                hooks_enabled: false,
+            },
+            &snapshot,
+            &mut Vec::new(),
+            &mut BackendTimings::default(),
+        );
+        assert!(strict.is_err());
+
+        let mut options = base_options();
+        options.continue_on_error = true;
+        let mut failures = Vec::new();
+        let pruned = execute_pruning(
+            &ExecutionContext {
+                tx: &tx,
+                managers: &managers,
+                config: &config,
+                options: &options,
+                hooks_enabled: false,
+            },
+            &snapshot,
+            &mut failures,
+            &mut BackendTimings::default(),
+        )
+        .expect("failure should be recorded, not returned");
+        assert!(pruned.is_empty(), "a failed removal is not a change");
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].backend, backend);
+        assert_eq!(failures[0].packages, vec!["bat".to_string()]);
+    }
+
+    fn prune_with_declined_confirmation(name: &str) -> (Vec<String>, usize, Vec<PackageId>) {
+        let config = loader::MergedConfig::default();
+        let backend = Backend::from("aur");
+        let tx = resolver::Transaction {
+            to_install: Vec::new(),
+            to_prune: vec![PackageId {
+                name: name.to_string(),
+                backend: backend.clone(),
+            }],
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+        let remove_calls = Arc::new(AtomicUsize::new(0));
+        let manager = MockManager {
+            backend: backend.clone(),
+            remove_calls: remove_calls.clone(),
+            remove_error: None,
+        };
+        let mut managers: ManagerMap = HashMap::new();
+        managers.insert(backend, Box::new(manager));
+        let snapshot = InstalledSnapshot::new();
+
+        let mut prompted = Vec::new();
+        let out = execute_pruning_with_confirm(
+            &ExecutionContext {
+                tx: &tx,
+                managers: &managers,
+                config: &config,
+                options: &base_options(),
+                hooks_enabled: false,
+            },
+            &snapshot,
+            &mut Vec::new(),
+            &mut BackendTimings::default(),
+            &mut |pkg: &str, _| {
+                prompted.push(pkg.to_string());
+                false
+            },
+        );
+        let pruned = out.expect("declining keeps the sync going");
+        (prompted, remove_calls.load(Ordering::SeqCst), pruned)
+    }
+
+    #[test]
+    fn critical_category_prune_demands_typed_confirmation_even_with_yes() {
+        let (prompted, remove_calls, pruned) = prune_with_declined_confirmation("linux-cachyos");
+        assert_eq!(prompted, vec!["linux-cachyos".to_string()]);
+        assert_eq!(remove_calls, 0);
+        assert!(pruned.is_empty());
+    }
+
+    #[test]
+    fn ordinary_prune_skips_typed_confirmation() {
+        let (prompted, remove_calls, pruned) = prune_with_declined_confirmation("bat");
+        assert!(prompted.is_empty());
+        assert_eq!(remove_calls, 1);
+        assert_eq!(
+            pruned,
+            vec![PackageId {
+                name: "bat".to_string(),
+                backend: Backend::from("aur"),
+            }]
+        );
+    }
 }
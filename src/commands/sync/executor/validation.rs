@@ -0,0 +1,137 @@
+//! Read-only validation of a transaction before it is executed
+//!
+//! Backends with `dry_install`/`dry_remove` commands get to reject the plan
+//! (unknown packages, conflicts) before anything is installed or removed.
+
+use super::ManagerMap;
+use crate::core::{resolver, types::Backend};
+use crate::error::{DeclarchError, Result};
+
+/// Ask each backend to check its share of the plan; the first rejection aborts.
+pub fn validate_transaction(
+    tx: &resolver::Transaction,
+    managers: &ManagerMap,
+    prune: bool,
+) -> Result<()> {
+    // Backends in plan order, each with its (installs, removes)
+    let mut planned: Vec<(&Backend, Vec<String>, Vec<String>)> = Vec::new();
+    let prunes = if prune { tx.to_prune.as_slice() } else { &[] };
+    for (pkg, is_install) in tx
+        .to_install
+        .iter()
+        .map(|p| (p, true))
+        .chain(prunes.iter().map(|p| (p, false)))
+    {
+        let idx = match planned.iter().position(|(b, _, _)| *b == &pkg.backend) {
+            Some(idx) => idx,
+            None => {
+                planned.push((&pkg.backend, Vec::new(), Vec::new()));
+                planned.len() - 1
+            }
+        };
+        let entry = &mut planned[idx];
+        if is_install {
+            entry.1.push(pkg.name.clone());
+        } else {
+            entry.2.push(pkg.name.clone());
+        }
+    }
+
+    for (backend, installs, removes) in planned {
+        let Some(mgr) = managers.get(backend) else {
+            continue;
+        };
+        mgr.validate_transaction(&installs, &removes).map_err(|e| {
+            DeclarchError::Other(format!("Plan validation failed for {}: {}", backend, e))
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::sync::test_support::pkg;
+    use crate::core::types::PackageMetadata;
+    use crate::packages::traits::PackageManager;
+    use std::collections::HashMap;
+
+    struct ValidatingManager {
+        backend: Backend,
+        reject: Option<&'static str>,
+    }
+
+    impl PackageManager for ValidatingManager {
+        fn backend_type(&self) -> Backend {
+            self.backend.clone()
+        }
+
+        fn list_installed(&self) -> Result<HashMap<String, PackageMetadata>> {
+            Ok(HashMap::new())
+        }
+
+        fn install(&self, _packages: &[String]) -> Result<()> {
+            panic!("validation must not install");
+        }
+
+        fn remove(&self, _packages: &[String]) -> Result<()> {
+            panic!("validation must not remove");
+        }
+
+        fn validate_transaction(&self, to_install: &[String], to_remove: &[String]) -> Result<()> {
+            match self.reject {
+                Some(pkg) if to_install.iter().chain(to_remove).any(|p| p == pkg) => Err(
+                    DeclarchError::PackageManagerError(format!("target not found: {}", pkg)),
+                ),
+                _ => Ok(()),
+            }
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn get_required_by(&self, _package: &str) -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn transaction() -> resolver::Transaction {
+        resolver::Transaction {
+            to_install: vec![pkg("aur", "bat"), pkg("aur", "nosuchpkg")],
+            to_prune: vec![pkg("aur", "htop")],
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        }
+    }
+
+    fn managers(reject: Option<&'static str>) -> ManagerMap {
+        let mut managers: ManagerMap = HashMap::new();
+        managers.insert(
+            Backend::from("aur"),
+            Box::new(ValidatingManager {
+                backend: Backend::from("aur"),
+                reject,
+            }),
+        );
+        managers
+    }
+
+    #[test]
+    fn backend_rejection_aborts_with_its_error() {
+        let err = validate_transaction(&transaction(), &managers(Some("nosuchpkg")), false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Plan validation failed for aur"));
+        assert!(err.contains("target not found: nosuchpkg"));
+    }
+
+    #[test]
+    fn prunes_are_only_checked_when_pruning() {
+        assert!(validate_transaction(&transaction(), &managers(None), true).is_ok());
+        assert!(validate_transaction(&transaction(), &managers(Some("htop")), false).is_ok());
+        assert!(validate_transaction(&transaction(), &managers(Some("htop")), true).is_err());
+    }
+}
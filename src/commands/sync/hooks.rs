@@ -2,6 +2,7 @@
 //!
 //! Executes pre and post-sync hooks from lifecycle configuration.
 
+use crate::commands::hooks::BackendChangeCounts;
 use crate::config::kdl_modules::types::LifecycleConfig;
 use crate::error::Result;
 
@@ -17,10 +18,11 @@ pub fn execute_pre_sync(
 /// Execute post-sync hooks
 pub fn execute_post_sync(
     lifecycle_actions: &Option<LifecycleConfig>,
+    backend_changes: &BackendChangeCounts,
     enabled: bool,
     dry_run: bool,
 ) -> Result<()> {
-    crate::commands::hooks::execute_post_sync(lifecycle_actions, enabled, dry_run)
+    crate::commands::hooks::execute_post_sync(lifecycle_actions, backend_changes, enabled, dry_run)
 }
 
 /// Execute success hooks
@@ -90,6 +92,25 @@ pub fn execute_post_remove(
     crate::commands::hooks::execute_post_remove(lifecycle_actions, package_name, enabled, dry_run)
 }
 
+/// Execute on-upgrade hooks for a package whose version changed
+pub fn execute_on_upgrade(
+    lifecycle_actions: &Option<LifecycleConfig>,
+    package_name: &str,
+    old_version: &str,
+    new_version: &str,
+    enabled: bool,
+    dry_run: bool,
+) -> Result<()> {
+    crate::commands::hooks::execute_on_upgrade(
+        lifecycle_actions,
+        package_name,
+        old_version,
+        new_version,
+        enabled,
+        dry_run,
+    )
+}
+
 /// Execute sync hooks (legacy wrapper)
 #[deprecated(
     since = "0.8.0",
@@ -102,6 +123,11 @@ pub fn execute_sync_hooks(
     dry_run: bool,
 ) -> Result<()> {
     execute_pre_sync(lifecycle_actions, enabled, dry_run)?;
-    execute_post_sync(lifecycle_actions, enabled, dry_run)?;
+    execute_post_sync(
+        lifecycle_actions,
+        &BackendChangeCounts::new(),
+        enabled,
+        dry_run,
+    )?;
     execute_on_success(lifecycle_actions, enabled, dry_run)
 }
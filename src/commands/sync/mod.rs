@@ -9,25 +9,40 @@
 
 mod backend_overrides;
 mod backend_runtime;
+mod config_dump;
 mod config_loading;
+mod conflicts;
 mod executor;
 mod hooks;
+mod orphans;
 mod planner;
 mod policy;
 mod presentation;
 mod state_sync;
+mod stats;
 mod targeting;
 mod variants;
 
 // Re-export public API
-pub use executor::execute_transaction;
+pub use executor::{
+    PackageRemotes, SyncFailure, TransactionOutcome, execute_transaction, planned_commands,
+    validate_transaction,
+};
 pub use hooks::{
-    execute_on_failure, execute_on_success, execute_on_update, execute_post_sync, execute_pre_sync,
+    execute_on_failure, execute_on_success, execute_on_update, execute_on_upgrade,
+    execute_post_sync, execute_pre_sync,
 };
 pub use planner::{
-    check_variant_transitions, create_transaction, display_transaction_plan, warn_partial_upgrade,
+    InstallReason, InstallReasonMap, PruneReason, PruneReasonMap, check_variant_transitions,
+    create_transaction, display_orphan_preview, display_transaction_plan, explain_prunes,
+    filter_conflict_choices, warn_partial_upgrade,
+};
+pub use state_sync::{
+    VersionChange, canonicalize_state_backends, recorded_remotes, refresh_pinned_flags,
+    refresh_state_versions, sync_history_entry, update_state, update_state_with_success,
+    version_changes,
 };
-pub use state_sync::{update_state, update_state_with_success};
+pub use stats::BackendTimings;
 pub use variants::{find_variant, resolve_installed_package_name};
 
 use crate::config::loader;
@@ -35,6 +50,7 @@ use crate::core::types::SyncTarget;
 use crate::error::Result;
 use crate::project_identity;
 use crate::ui as output;
+use crate::utils::fuzzy;
 use crate::utils::machine_output;
 use crate::utils::paths;
 use serde::Serialize;
@@ -46,14 +62,24 @@ use crate::state::types::Backend;
 pub(crate) use backend_overrides::{
     apply_backend_env_overrides, apply_backend_option_overrides, apply_backend_package_sources,
 };
+pub use backend_runtime::{estimate_install_sizes, initialize_managers_and_snapshot};
 use backend_runtime::{
-    execute_backend_updates, initialize_managers_and_snapshot, refresh_installed_snapshot,
+    execute_backend_updates, execute_backend_upgrades, initialize_managers_and_snapshot_timed,
+    refresh_installed_snapshot, upgrade_candidates,
 };
 use config_loading::{load_config_with_modules, load_single_module};
-use policy::{enforce_sync_policy, resolve_hooks_enabled};
-use presentation::{build_sync_preview_report, show_sync_diff, sync_target_to_string};
+use orphans::{forget_removed_orphans, handle_orphan_dependencies};
+use policy::{apply_confirm_policy, enforce_sync_policy, resolve_hooks_enabled};
+use presentation::{
+    build_sync_preview_report, show_sync_diff, show_sync_failures, sync_target_to_string,
+};
+use stats::SyncStats;
 use std::collections::HashMap;
-use targeting::{named_target_exists, resolve_target};
+use std::path::PathBuf;
+use std::time::Instant;
+use targeting::{
+    TargetResolution, named_target_exists, reinterpret_ambiguous, resolve_target, suggest_targets,
+};
 
 // Re-export dry-run display function
 pub use planner::display_dry_run_details;
@@ -61,6 +87,10 @@ pub use planner::display_dry_run_details;
 // Type aliases to reduce complexity
 pub type InstalledSnapshot = HashMap<PackageId, PackageMetadata>;
 pub type ManagerMap = HashMap<Backend, Box<dyn PackageManager>>;
+/// Backend kept for each conflicting package name (`--interactive-conflicts`)
+pub type ConflictChoices = HashMap<String, Backend>;
+/// Approximate size in bytes of planned installs, when backends report it
+pub type PackageSizes = HashMap<PackageId, u64>;
 
 #[derive(Debug, Serialize)]
 struct SyncPreviewReport {
@@ -74,9 +104,12 @@ struct SyncPreviewReport {
     to_install: Vec<String>,
     to_remove: Vec<String>,
     to_adopt: Vec<String>,
+    /// Phase timings, with `--stats`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timings: Option<SyncStats>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct SyncOptions {
     pub dry_run: bool,
     pub prune: bool,
@@ -84,19 +117,75 @@ pub struct SyncOptions {
     pub verbose: bool,
     pub yes: bool,
     pub force: bool,
-    pub target: Option<String>,
+    pub target: Vec<String>,
     pub noconfirm: bool,
     pub hooks: bool,
-    pub profile: Option<String>,
+    /// Active profiles (`--profile`, repeatable)
+    pub profiles: Vec<String>,
     pub host: Option<String>,
     pub modules: Vec<String>,
     pub diff: bool,
     pub format: Option<String>,
     pub output_version: Option<String>,
+    /// Write machine output to this file (`--output`) instead of stdout
+    pub output: Option<PathBuf>,
+    /// Packages treated as already satisfied for this run (never installed or pruned)
+    pub assume_installed: Vec<String>,
+    /// Backends to prune when `prune` is not set (`--prune-backend`)
+    pub prune_backends: Vec<String>,
+    /// Extra backend definitions for this run, overriding same-named ones
+    pub backend_files: Vec<PathBuf>,
+    /// Declared packages to install again even though they are present (`--reinstall`)
+    pub reinstall: Vec<String>,
+    /// Annotate dry-run entries with why they are planned (`--explain`)
+    pub explain: bool,
+    /// Prompt for which declaration to keep on cross-backend conflicts
+    pub interactive_conflicts: bool,
+    /// Remove declarations not kept by `interactive_conflicts` from config
+    pub remember_conflicts: bool,
+    /// Record backend failures and keep going instead of aborting (`--continue-on-error`)
+    pub continue_on_error: bool,
+    /// Ignore policy `noconfirm`/`auto-confirm` defaults (`--confirm`)
+    pub confirm: bool,
+    /// Print the merged config and exit (`--print-config`)
+    pub print_config: bool,
+    /// Upgrade installed packages of the targeted backends after syncing (`--upgrade`)
+    pub upgrade: bool,
+    /// Only sync packages carrying one of these tags (`--tag`)
+    pub tags: Vec<String>,
+    /// Only sync packages declared in this module, without pruning (`--target-module`)
+    pub target_module: Option<String>,
+    /// Fail with `ChangesPending` when the dry-run plan is not empty (`--check-only`)
+    pub check_only: bool,
+    /// Force hooks off for this run (`--no-hooks`)
+    pub no_hooks: bool,
+    /// Run only this lifecycle phase's hooks and exit (`--only-hooks`)
+    pub only_hooks: Option<String>,
+    /// Print the backend commands the plan would run, without running them (`--dump-commands`)
+    pub dump_commands: bool,
+    /// Print how long each phase took (`--stats`)
+    pub stats: bool,
+    /// Remove orphaned dependencies regardless of `policy.orphan-deps` (`--prune-orphans`)
+    pub prune_orphans: bool,
+    /// Run backend commands and root hooks without escalation (`--no-sudo`)
+    pub no_sudo: bool,
+    /// Capture backend command output, showing it only on failure (`--quiet-backends`)
+    pub quiet_backends: bool,
 }
 
-pub fn run(options: SyncOptions) -> Result<()> {
+pub fn run(mut options: SyncOptions) -> Result<()> {
     let machine_preview_mode = is_machine_preview_mode(&options);
+    resolve_prune_scope(&mut options);
+
+    if options.print_config {
+        let config_path = paths::config_file()?;
+        let config = load_effective_config(&options, &config_path)?;
+        return config_dump::print_config_dump(&config, &options);
+    }
+
+    if let Some(phase) = options.only_hooks.as_deref() {
+        return run_only_hooks(&options, phase);
+    }
 
     // Acquire exclusive lock at the very beginning to prevent concurrent sync
     // Lock is held until this function returns (RAII pattern)
@@ -113,7 +202,7 @@ pub fn run(options: SyncOptions) -> Result<()> {
     } else {
         // Real sync requires exclusive lock
         Some(state::io::acquire_lock().map_err(|e| {
-            crate::error::DeclarchError::Other(format!(
+            crate::error::DeclarchError::LockError(format!(
                 "Cannot start sync: {}\n\
                  If no other {} process is running, delete the lock file manually.",
                 e,
@@ -123,18 +212,19 @@ pub fn run(options: SyncOptions) -> Result<()> {
     };
 
     // 1. Load Config
+    let mut stats = SyncStats::default();
+    let mut phase_started = Instant::now();
     let config_path = paths::config_file()?;
-    let selectors = loader::LoadSelectors {
-        profile: options.profile.clone(),
-        host: options.host.clone(),
-    };
-
-    let mut config = load_sync_config(&options, &config_path, &selectors)?;
+    let mut config = load_effective_config(&options, &config_path)?;
     if options.verbose {
         output::verbose(&format!("Config file: {}", config_path.display()));
         output::verbose(&format!(
             "Runtime selectors: profile={}, host={}",
-            options.profile.as_deref().unwrap_or("(none)"),
+            if options.profiles.is_empty() {
+                "(none)".to_string()
+            } else {
+                options.profiles.join(", ")
+            },
             options.host.as_deref().unwrap_or("(none)")
         ));
         output::verbose(&format!(
@@ -146,56 +236,100 @@ pub fn run(options: SyncOptions) -> Result<()> {
             }
         ));
     }
-    enforce_sync_policy(&config)?;
+    if apply_confirm_policy(&config, &mut options) && !machine_preview_mode {
+        output::info(&format!(
+            "policy {{ auto-confirm true }} is active: prompts are answered yes. Use '{}' to be asked.",
+            project_identity::cli_with("sync --confirm")
+        ));
+    }
+    let conflict_choices = if machine_preview_mode {
+        ConflictChoices::new()
+    } else {
+        conflicts::resolve_conflicts(&config, &options)?
+    };
+    enforce_sync_policy(&config, &conflict_choices)?;
     let hooks_enabled = resolve_hooks_enabled(&config, &options);
 
     // 2. Target Resolution
-    let sync_target = resolve_target(&options.target, &config);
+    let sync_target = match resolve_target(&options.target, &config) {
+        TargetResolution::Resolved(target) => target,
+        TargetResolution::Ambiguous { target, names } => {
+            if !options.yes && !machine_preview_mode && output::is_attended() {
+                reinterpret_ambiguous(target, &names, &mut ask_prefers_package)
+            } else {
+                for name in &names {
+                    output::warning(&format!(
+                        "Target '{}' matches both a backend and a declared package or module; \
+                         treating it as the backend",
+                        name
+                    ));
+                }
+                target
+            }
+        }
+    };
     if options.verbose {
         output::verbose(&format!(
             "Sync target resolved: {}",
             sync_target_to_string(&sync_target)
         ));
     }
-    if let SyncTarget::Named(query) = &sync_target
-        && !named_target_exists(&config, query)
-    {
-        return Err(crate::error::DeclarchError::Other(format!(
-            "No package or module matched target '{}'",
-            query
-        )));
+    for target in sync_target.parts() {
+        if let SyncTarget::Named(query) = target
+            && !named_target_exists(&config, query)
+        {
+            return Err(crate::error::DeclarchError::Other(format!(
+                "No package, module or backend matched target '{}'{}",
+                query,
+                fuzzy::did_you_mean(&suggest_targets(&config, query))
+            )));
+        }
     }
 
+    stats.record("config", phase_started);
+
     // Execute pre-sync hooks
     execute_pre_sync(&config.lifecycle_actions, hooks_enabled, options.dry_run)?;
 
     // 3. Initialize Managers & Snapshot
-    let (installed_snapshot, managers) =
-        initialize_managers_and_snapshot(&config, &options, &sync_target)?;
+    phase_started = Instant::now();
+    let mut list_timings = BackendTimings::default();
+    let (installed_snapshot, managers, _) =
+        initialize_managers_and_snapshot_timed(&config, &options, &sync_target, &mut list_timings)?;
+    stats.record_with_backends("snapshot", phase_started, &list_timings);
 
     // 3.5. Run backend updates if --update flag is set
     if options.update && !options.dry_run {
+        phase_started = Instant::now();
         execute_backend_updates(&managers, options.verbose)?;
         execute_on_update(&config.lifecycle_actions, hooks_enabled, options.dry_run)?;
+        stats.record("update", phase_started);
     }
 
     // 4. Load State & Resolve
+    phase_started = Instant::now();
     // Use strict state recovery for mutating prune flows to avoid accidental
     // destructive actions when state is unreadable/corrupted and unrecoverable.
-    let state = if !options.dry_run && options.prune {
+    let mut state = if !options.dry_run && options.prune {
         state::io::load_state_strict()?
     } else {
         state::io::load_state()?
     };
+    canonicalize_state_backends(&mut state, &config);
+    // Pins follow the config on every run, so prune planning never sees a
+    // flag the config has since dropped.
+    let pins_changed = refresh_pinned_flags(&mut state, &config);
 
     // 5. Create Transaction
-    let transaction = create_transaction(
+    let mut transaction = create_transaction(
         &mut config,
         &state,
         &installed_snapshot,
         &managers,
         &sync_target,
+        &options,
     )?;
+    filter_conflict_choices(&mut transaction, &config, &conflict_choices);
 
     // 5.5 Check for dangerous variant transitions and warn about stale updates
     check_variant_transitions(
@@ -207,11 +341,20 @@ pub fn run(options: SyncOptions) -> Result<()> {
         &options,
     )?;
     if !machine_preview_mode {
-        warn_partial_upgrade(&state, &transaction, &options);
+        warn_partial_upgrade(
+            &config,
+            &state,
+            &transaction,
+            &sync_target,
+            &managers,
+            &options,
+        )?;
     }
+    stats.record("plan", phase_started);
 
     if machine_preview_mode {
-        let report = build_sync_preview_report(&options, &sync_target, &transaction);
+        let mut report = build_sync_preview_report(&options, &sync_target, &transaction);
+        report.timings = options.stats.then_some(stats);
 
         machine_output::emit_v1(
             "sync",
@@ -219,64 +362,156 @@ pub fn run(options: SyncOptions) -> Result<()> {
             Vec::new(),
             Vec::new(),
             options.format.as_deref().unwrap_or("json"),
+            options.output.as_deref(),
         )?;
+        return ensure_nothing_pending(&options, &transaction);
+    }
+
+    if options.dump_commands {
+        let remotes = recorded_remotes(&state);
+        let planned = planned_commands(
+            &transaction,
+            &managers,
+            &config,
+            &remotes,
+            &installed_snapshot,
+            &options,
+        )?;
+        for (backend, lines) in planned {
+            println!("# {}", backend);
+            for line in lines {
+                println!("{}", line);
+            }
+        }
         return Ok(());
     }
 
     // 6. Display Plan
-    if transaction.to_install.is_empty()
+    let nothing_planned = transaction.to_install.is_empty()
         && transaction.to_prune.is_empty()
-        && transaction.to_adopt.is_empty()
-    {
+        && transaction.to_adopt.is_empty();
+    if nothing_planned && !options.upgrade {
         output::success("Everything is up to date!");
-        execute_post_sync(&config.lifecycle_actions, hooks_enabled, options.dry_run)?;
+        let removed_orphans = handle_orphan_dependencies(&config, &state, &managers, &options)?;
+        let forgotten = forget_removed_orphans(&mut state, &removed_orphans);
+        if !options.dry_run && (pins_changed > 0 || forgotten > 0) {
+            match lock {
+                Some(ref lock) => state::io::save_state_locked(&state, lock)?,
+                None => state::io::save_state(&state)?,
+            }
+        }
+        execute_post_sync(
+            &config.lifecycle_actions,
+            &HashMap::new(),
+            hooks_enabled,
+            options.dry_run,
+        )?;
         execute_on_success(&config.lifecycle_actions, hooks_enabled, options.dry_run)?;
+        show_stats(&options, &stats);
         return Ok(());
     }
 
+    let install_sizes =
+        if shows_install_sizes(&options, nothing_planned) && !transaction.to_install.is_empty() {
+            estimate_install_sizes(&transaction, &installed_snapshot, &managers)
+        } else {
+            PackageSizes::new()
+        };
+
     // Show detailed dry-run info or regular plan
-    if options.dry_run {
-        display_dry_run_details(&transaction, options.prune, &installed_snapshot);
+    if nothing_planned {
+        output::success("All declared packages are in place");
+    } else if options.dry_run {
+        let install_reasons = if options.explain {
+            transaction.install_reasons.clone()
+        } else {
+            InstallReasonMap::new()
+        };
+        display_dry_run_details(
+            &transaction,
+            options.prune,
+            &installed_snapshot,
+            &install_reasons,
+        );
     } else {
-        display_transaction_plan(&transaction, options.prune);
+        let prune_reasons = if options.prune {
+            explain_prunes(&config, &state, &transaction)
+        } else {
+            PruneReasonMap::new()
+        };
+        display_transaction_plan(&transaction, options.prune, &prune_reasons, &install_sizes);
+    }
+    if options.prune && !transaction.to_prune.is_empty() {
+        display_orphan_preview(&transaction, &managers);
     }
 
-    // Handle --diff flag: Show diff and exit (like git diff)
+    // Handle --diff flag: Show diff and exit (like git diff); --check-only still applies
     if options.diff {
-        show_sync_diff(&transaction, &installed_snapshot);
-        return Ok(());
+        show_sync_diff(&transaction, &installed_snapshot, &install_sizes);
+        return ensure_nothing_pending(&options, &transaction);
     }
 
+    // 6.5 Let backends with dry commands reject the plan before anything changes
+    validate_transaction(&transaction, &managers, options.prune)?;
+
+    // Backend-scoped post-sync hooks run for backends that changed; a dry run
+    // previews them from the plan, a real run counts what actually ran.
+    let mut backend_changes = transaction.backend_change_counts(options.prune);
+
     // 7. Execute
     if !options.dry_run {
-        if !options.yes && !output::prompt_yes_no("Proceed with sync?") {
+        if !nothing_planned && !options.yes && !output::prompt_yes_no("Proceed with sync?") {
             output::info("Sync cancelled");
             return Err(crate::error::DeclarchError::Interrupted);
         }
 
-        let successfully_installed =
-            match execute_transaction(&transaction, &managers, &config, &options, hooks_enabled) {
-                Ok(installed) => installed,
-                Err(e) => {
-                    let _ = execute_on_failure(
-                        &config.lifecycle_actions,
-                        hooks_enabled,
-                        options.dry_run,
-                    );
-                    return Err(e);
-                }
-            };
+        phase_started = Instant::now();
+        let remotes = recorded_remotes(&state);
+        let outcome = match execute_transaction(
+            &transaction,
+            &managers,
+            &config,
+            &remotes,
+            &options,
+            hooks_enabled,
+        ) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                let _ =
+                    execute_on_failure(&config.lifecycle_actions, hooks_enabled, options.dry_run);
+                return Err(e);
+            }
+        };
+
+        let upgraded = if options.upgrade {
+            run_backend_upgrades(&managers, &sync_target, &options, &config, hooks_enabled)?
+        } else {
+            Vec::new()
+        };
+        let removed_orphans = handle_orphan_dependencies(&config, &state, &managers, &options)?;
+        stats.record_with_backends("execute", phase_started, &outcome.backend_timings);
+        backend_changes = outcome.backend_change_counts();
 
         // 8. Refresh installed snapshot and update state with successful packages
+        phase_started = Instant::now();
         let post_execution_snapshot = refresh_installed_snapshot(&managers);
 
-        let new_state = update_state_with_success(
+        let mut new_state = update_state_with_success(
             &state,
             &transaction,
             &post_execution_snapshot,
             &options,
-            &successfully_installed,
+            &outcome.installed,
         )?;
+        let refreshed = refresh_state_versions(&mut new_state, &post_execution_snapshot, &upgraded);
+        refresh_pinned_flags(&mut new_state, &config);
+        forget_removed_orphans(&mut new_state, &removed_orphans);
+        if refreshed > 0 {
+            output::success(&format!(
+                "Updated recorded versions of {} package(s)",
+                refreshed
+            ));
+        }
 
         // Save state with lock held (ensures no concurrent modifications)
         if let Some(ref lock) = lock {
@@ -293,22 +528,229 @@ pub fn run(options: SyncOptions) -> Result<()> {
                 return Err(e);
             }
         }
+
+        if let Some(entry) = sync_history_entry(
+            &state,
+            &new_state,
+            &transaction,
+            &outcome.installed,
+            &options.target,
+        ) && let Err(e) =
+            paths::state_history_dir().and_then(|dir| state::history::record_entry(&dir, &entry))
+        {
+            output::warning(&format!("Could not record sync history: {}", e));
+        }
+
+        // Package-scoped on-upgrade hooks, once the new versions are recorded
+        for change in version_changes(&state, &installed_snapshot, &post_execution_snapshot) {
+            if let Err(e) = execute_on_upgrade(
+                &config.lifecycle_actions,
+                &change.package,
+                &change.old_version,
+                &change.new_version,
+                hooks_enabled,
+                options.dry_run,
+            ) {
+                let _ =
+                    execute_on_failure(&config.lifecycle_actions, hooks_enabled, options.dry_run);
+                return Err(e);
+            }
+        }
+
+        stats.record("state", phase_started);
+
+        // Whatever did succeed is already in state; now surface what didn't.
+        if options.continue_on_error && !outcome.failures.is_empty() {
+            show_sync_failures(&outcome.failures);
+            let _ = execute_on_failure(&config.lifecycle_actions, hooks_enabled, options.dry_run);
+            show_stats(&options, &stats);
+            return Err(crate::error::DeclarchError::PackageManagerError(format!(
+                "{} operation(s) failed during sync",
+                outcome.failures.len()
+            )));
+        }
     } else {
+        if options.upgrade {
+            let backends = upgrade_candidates(&managers, &sync_target);
+            if backends.is_empty() {
+                output::info("No targeted backend supports upgrades");
+            } else {
+                output::info(&format!("Would upgrade: {}", join_backends(&backends)));
+            }
+        }
+        handle_orphan_dependencies(&config, &state, &managers, &options)?;
         // Dry-run complete
         output::success("Dry-run completed - no changes were made");
     }
 
-    // Execute post-sync hooks
-    execute_post_sync(&config.lifecycle_actions, hooks_enabled, options.dry_run)?;
+    // Execute post-sync hooks; backend-scoped ones only for backends that changed
+    execute_post_sync(
+        &config.lifecycle_actions,
+        &backend_changes,
+        hooks_enabled,
+        options.dry_run,
+    )?;
     execute_on_success(&config.lifecycle_actions, hooks_enabled, options.dry_run)?;
+    show_stats(&options, &stats);
+
+    ensure_nothing_pending(&options, &transaction)
+}
 
+/// Timing breakdown for `--stats`
+fn show_stats(options: &SyncOptions, stats: &SyncStats) {
+    if options.stats {
+        output::info(&format!("Timings: {}", stats.summary()));
+    }
+}
+
+fn ask_prefers_package(name: &str) -> bool {
+    let choices = vec![
+        format!("the backend '{}'", name),
+        format!("the package '{}'", name),
+    ];
+    output::prompt_choice(
+        &format!(
+            "Did you mean the backend '{}' or the package '{}'?",
+            name, name
+        ),
+        &choices,
+    ) == Some(1)
+}
+
+/// Whether the plan view shows install sizes: the regular plan and `--diff` do,
+/// the dry-run details do not. Sizes cost a `size_cmd` run per package.
+fn shows_install_sizes(options: &SyncOptions, nothing_planned: bool) -> bool {
+    options.diff || (!nothing_planned && !options.dry_run)
+}
+
+/// With `--check-only`, turn a non-empty plan into a `ChangesPending` error.
+///
+/// Removals only count when the run prunes.
+fn ensure_nothing_pending(
+    options: &SyncOptions,
+    transaction: &crate::core::resolver::Transaction,
+) -> Result<()> {
+    if !options.check_only {
+        return Ok(());
+    }
+    let prunes = if options.prune {
+        transaction.to_prune.len()
+    } else {
+        0
+    };
+    let pending = transaction.to_install.len() + transaction.to_adopt.len() + prunes;
+    if pending > 0 {
+        return Err(crate::error::DeclarchError::ChangesPending(pending));
+    }
     Ok(())
 }
 
+/// `sync --only-hooks`: run one lifecycle phase without touching packages.
+fn run_only_hooks(options: &SyncOptions, phase: &str) -> Result<()> {
+    let config_path = paths::config_file()?;
+    let config = load_effective_config(options, &config_path)?;
+    let hooks_enabled = resolve_hooks_enabled(&config, options);
+    if !hooks_enabled && !options.dry_run {
+        return Err(crate::error::DeclarchError::Other(format!(
+            "Cannot run '{}' hooks: hooks are disabled for this config",
+            phase
+        )));
+    }
+    crate::commands::hooks::execute_phase_only(
+        &config.lifecycle_actions,
+        phase,
+        hooks_enabled,
+        options.dry_run,
+    )
+}
+
+/// Upgrade the backends `--upgrade` covers, after confirming unless `--yes`.
+///
+/// Fires `on-update` hooks when anything was upgraded.
+fn run_backend_upgrades(
+    managers: &ManagerMap,
+    sync_target: &SyncTarget,
+    options: &SyncOptions,
+    config: &loader::MergedConfig,
+    hooks_enabled: bool,
+) -> Result<Vec<Backend>> {
+    crate::utils::remote::ensure_online("Upgrading packages")?;
+    let backends = upgrade_candidates(managers, sync_target);
+    if backends.is_empty() {
+        output::info("No targeted backend supports upgrades");
+        return Ok(Vec::new());
+    }
+    let prompt = format!(
+        "Upgrade all installed packages of {}?",
+        join_backends(&backends)
+    );
+    if !options.yes && !output::prompt_yes_no(&prompt) {
+        output::info("Upgrade skipped");
+        return Ok(Vec::new());
+    }
+
+    let upgraded = execute_backend_upgrades(managers, &backends);
+    if !upgraded.is_empty() {
+        execute_on_update(&config.lifecycle_actions, hooks_enabled, options.dry_run)?;
+    }
+    Ok(upgraded)
+}
+
+fn join_backends(backends: &[Backend]) -> String {
+    backends
+        .iter()
+        .map(|b| b.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Turn `--prune-backend` into a backend-scoped prune.
+///
+/// A full `sync prune` already covers every backend, so the allow-list is dropped.
+fn resolve_prune_scope(options: &mut SyncOptions) {
+    if options.prune_backends.is_empty() {
+        return;
+    }
+    if options.prune {
+        output::warning("--prune-backend is ignored: `sync prune` already prunes all backends");
+        options.prune_backends.clear();
+    } else {
+        options.prune = true;
+    }
+}
+
 fn is_machine_preview_mode(options: &SyncOptions) -> bool {
     options.dry_run
         && matches!(options.output_version.as_deref(), Some("v1"))
-        && matches!(options.format.as_deref(), Some("json" | "yaml"))
+        && matches!(options.format.as_deref(), Some("json" | "yaml" | "toml"))
+}
+
+/// Config with selectors, extra modules and `--backend-file` definitions applied.
+fn load_effective_config(
+    options: &SyncOptions,
+    config_path: &std::path::Path,
+) -> Result<loader::MergedConfig> {
+    let selectors = loader::LoadSelectors {
+        profiles: options.profiles.clone(),
+        host: options.host.clone(),
+    };
+
+    let mut config = load_sync_config(options, config_path, &selectors)?;
+    for path in &options.backend_files {
+        let backend = crate::backends::user_parser::load_backend_definition(path)?;
+        if options.verbose {
+            output::verbose(&format!(
+                "Ad-hoc backend '{}' from {}",
+                backend.name,
+                path.display()
+            ));
+        }
+        config.backends.push(backend);
+    }
+    if let Some(hooks) = &mut config.lifecycle_actions {
+        hooks.no_sudo = options.no_sudo;
+    }
+    Ok(config)
 }
 
 fn load_sync_config(
@@ -320,12 +762,14 @@ fn load_sync_config(
         return loader::load_root_config_with_selectors(config_path, selectors);
     }
 
-    if options.modules.len() == 1 && options.target.is_none() {
+    if options.modules.len() == 1 && options.target.is_empty() {
         load_single_module(config_path, &options.modules[0], selectors)
     } else {
         load_config_with_modules(config_path, &options.modules, selectors, options.verbose)
     }
 }
 
+#[cfg(test)]
+pub(crate) mod test_support;
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,463 @@
+//! Orphaned dependency cleanup
+//!
+//! Backends with an `orphans_cmd` (e.g. `pacman -Qtdq`) report dependencies
+//! that nothing requires any more. After a sync, those declarch tracks in
+//! state are kept, confirmed one by one, or removed according to
+//! `policy { orphan-deps }` (default "keep"); `--prune-orphans` removes them
+//! for the run. Declared, protected and critical packages are never removed,
+//! and removed ones are dropped from state.
+
+use super::{ManagerMap, SyncOptions};
+use crate::config::loader;
+use crate::constants::{CRITICAL_PACKAGES, critical_category};
+use crate::core::types::{Backend, PackageId};
+use crate::error::Result;
+use crate::project_identity;
+use crate::state::types::{PackageState, State};
+use crate::ui as output;
+use std::collections::HashSet;
+
+/// What to do with the orphans a backend reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum OrphanAction {
+    /// List them only (no policy set, or `orphan-deps "keep"`)
+    Keep,
+    /// Confirm each one (`orphan-deps "ask"`)
+    Ask,
+    /// Remove them all (`orphan-deps "remove"` or `--prune-orphans`)
+    Remove,
+}
+
+pub(super) fn orphan_action(config: &loader::MergedConfig, options: &SyncOptions) -> OrphanAction {
+    if options.prune_orphans {
+        return OrphanAction::Remove;
+    }
+    match config
+        .policy
+        .as_ref()
+        .and_then(|p| p.orphan_deps.as_deref())
+    {
+        Some("remove") => OrphanAction::Remove,
+        Some("ask") => OrphanAction::Ask,
+        _ => OrphanAction::Keep,
+    }
+}
+
+/// Apply the orphan policy to every targeted backend that can list orphans
+pub(super) fn handle_orphan_dependencies(
+    config: &loader::MergedConfig,
+    state: &State,
+    managers: &ManagerMap,
+    options: &SyncOptions,
+) -> Result<Vec<PackageId>> {
+    handle_orphans_with_confirm(
+        config,
+        state,
+        managers,
+        options,
+        &mut confirm_orphan_removal,
+    )
+}
+
+/// Drop removed orphans from state. Returns the number of entries dropped.
+pub(super) fn forget_removed_orphans(state: &mut State, removed: &[PackageId]) -> usize {
+    let before = state.packages.len();
+    for pkg in removed {
+        state.packages.retain(|_, entry| {
+            entry.backend != pkg.backend || !state_entry_matches(entry, &pkg.name)
+        });
+    }
+    before - state.packages.len()
+}
+
+fn state_entry_matches(entry: &PackageState, name: &str) -> bool {
+    entry.config_name == name
+        || entry.provides_name == name
+        || entry.actual_package_name.as_deref() == Some(name)
+}
+
+fn confirm_orphan_removal(pkg: &PackageId) -> bool {
+    output::prompt_yes_no_default(&format!("Remove orphaned dependency {}?", pkg), false)
+}
+
+/// Returns the orphans that were removed (or would be, on a dry run)
+fn handle_orphans_with_confirm(
+    config: &loader::MergedConfig,
+    state: &State,
+    managers: &ManagerMap,
+    options: &SyncOptions,
+    confirm: &mut dyn FnMut(&PackageId) -> bool,
+) -> Result<Vec<PackageId>> {
+    // Tag- and module-scoped runs see only part of the config; like prune,
+    // they leave everything else alone.
+    if !options.tags.is_empty() || options.target_module.is_some() {
+        return Ok(Vec::new());
+    }
+
+    let action = orphan_action(config, options);
+    let mut backends: Vec<&Backend> = managers
+        .iter()
+        .filter(|(_, mgr)| mgr.supports_orphans())
+        .map(|(backend, _)| backend)
+        .collect();
+    backends.sort_by(|a, b| a.name().cmp(b.name()));
+
+    let mut removed = Vec::new();
+    for backend in backends {
+        let mgr = &managers[backend];
+        let orphans = match mgr.list_orphans() {
+            Ok(names) => removable_orphans(config, state, backend, names),
+            Err(e) => {
+                output::warning(&format!(
+                    "Could not list orphaned dependencies for {}: {}",
+                    backend, e
+                ));
+                continue;
+            }
+        };
+        if orphans.is_empty() {
+            continue;
+        }
+
+        let chosen: Vec<String> = match action {
+            OrphanAction::Keep => {
+                output::info(&format!(
+                    "{} orphaned dependenc{} on {} kept (remove with '{}'):",
+                    orphans.len(),
+                    if orphans.len() == 1 { "y" } else { "ies" },
+                    backend,
+                    project_identity::cli_with("sync --prune-orphans")
+                ));
+                output::indent(&orphans.join(", "), 2);
+                continue;
+            }
+            OrphanAction::Ask if !options.yes && !options.dry_run => orphans
+                .into_iter()
+                .filter(|name| {
+                    confirm(&PackageId {
+                        name: name.clone(),
+                        backend: backend.clone(),
+                    })
+                })
+                .collect(),
+            OrphanAction::Ask | OrphanAction::Remove => orphans,
+        };
+        if chosen.is_empty() {
+            continue;
+        }
+
+        if options.dry_run {
+            output::info(&format!(
+                "Would remove orphaned dependencies from {}: {}",
+                backend,
+                chosen.join(", ")
+            ));
+        } else {
+            match mgr.remove(&chosen) {
+                Ok(()) => output::success(&format!(
+                    "Removed {} orphaned dependenc{} from {}",
+                    chosen.len(),
+                    if chosen.len() == 1 { "y" } else { "ies" },
+                    backend
+                )),
+                // The sync itself went through; a failed cleanup is not worth undoing it for.
+                Err(e) => {
+                    output::warning(&format!(
+                        "Could not remove orphaned dependencies from {}: {}",
+                        backend, e
+                    ));
+                    continue;
+                }
+            }
+        }
+        removed.extend(chosen.into_iter().map(|name| PackageId {
+            name,
+            backend: backend.clone(),
+        }));
+    }
+    Ok(removed)
+}
+
+/// Reported orphans that state tracks, minus declared, policy-protected and
+/// critical packages. Orphans declarch never installed are not its to remove.
+fn removable_orphans(
+    config: &loader::MergedConfig,
+    state: &State,
+    backend: &Backend,
+    names: Vec<String>,
+) -> Vec<String> {
+    let tracked = |name: &str| {
+        state
+            .packages
+            .values()
+            .any(|entry| &entry.backend == backend && state_entry_matches(entry, name))
+    };
+    let declared: HashSet<&str> = config
+        .packages
+        .keys()
+        .filter(|pkg| &pkg.backend == backend)
+        .map(|pkg| pkg.name.as_str())
+        .collect();
+    let protected = |name: &str| {
+        config
+            .policy
+            .as_ref()
+            .is_some_and(|p| p.protected.contains(name))
+    };
+
+    names
+        .into_iter()
+        .filter(|name| {
+            tracked(name)
+                && !declared.contains(name.as_str())
+                && !protected(name)
+                && !CRITICAL_PACKAGES.contains(&name.as_str())
+                && critical_category(name).is_none()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::kdl::PolicyConfig;
+    use crate::core::types::PackageMetadata;
+    use crate::packages::traits::PackageManager;
+    use crate::state::types::PackageState;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    struct OrphanManager {
+        backend: Backend,
+        orphans: Vec<String>,
+        removed: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl PackageManager for OrphanManager {
+        fn backend_type(&self) -> Backend {
+            self.backend.clone()
+        }
+
+        fn list_installed(&self) -> Result<HashMap<String, PackageMetadata>> {
+            Ok(HashMap::new())
+        }
+
+        fn install(&self, _packages: &[String]) -> Result<()> {
+            Ok(())
+        }
+
+        fn remove(&self, packages: &[String]) -> Result<()> {
+            self.removed.lock().unwrap().extend_from_slice(packages);
+            Ok(())
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn get_required_by(&self, _package: &str) -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+
+        fn list_orphans(&self) -> Result<Vec<String>> {
+            Ok(self.orphans.clone())
+        }
+
+        fn supports_orphans(&self) -> bool {
+            true
+        }
+    }
+
+    /// `libfoo`, `libbar`, `protected-lib`, `systemd` and the untracked
+    /// `libstray` reported as orphans by aur
+    fn orphan_managers() -> (ManagerMap, Arc<Mutex<Vec<String>>>) {
+        let removed = Arc::new(Mutex::new(Vec::new()));
+        let backend = Backend::from("aur");
+        let mut managers: ManagerMap = HashMap::new();
+        managers.insert(
+            backend.clone(),
+            Box::new(OrphanManager {
+                backend,
+                orphans: ["libfoo", "libbar", "protected-lib", "systemd", "libstray"]
+                    .map(String::from)
+                    .to_vec(),
+                removed: removed.clone(),
+            }),
+        );
+        (managers, removed)
+    }
+
+    /// State tracking every reported orphan except `libstray`
+    fn tracked_state() -> State {
+        let mut state = State::default();
+        for name in ["libfoo", "libbar", "protected-lib", "systemd", "ripgrep"] {
+            state.packages.insert(
+                format!("aur:{}", name),
+                PackageState {
+                    backend: Backend::from("aur"),
+                    config_name: name.to_string(),
+                    provides_name: name.to_string(),
+                    actual_package_name: None,
+                    installed_at: Utc::now(),
+                    version: None,
+                    install_reason: None,
+                    source_module: None,
+                    pinned: false,
+                    last_seen_at: None,
+                    backend_meta: None,
+                },
+            );
+        }
+        state
+    }
+
+    fn config_with_policy(orphan_deps: Option<&str>) -> loader::MergedConfig {
+        loader::MergedConfig {
+            policy: Some(PolicyConfig {
+                orphan_deps: orphan_deps.map(str::to_string),
+                protected: ["protected-lib".to_string()].into_iter().collect(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn never_asked(pkg: &PackageId) -> bool {
+        panic!("unexpected prompt for {}", pkg)
+    }
+
+    #[test]
+    fn keep_policy_and_no_policy_remove_nothing() {
+        for policy in [Some("keep"), None] {
+            let (managers, removed) = orphan_managers();
+            let out = handle_orphans_with_confirm(
+                &config_with_policy(policy),
+                &tracked_state(),
+                &managers,
+                &SyncOptions::default(),
+                &mut never_asked,
+            )
+            .unwrap();
+            assert!(out.is_empty());
+            assert!(removed.lock().unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn remove_policy_skips_protected_and_critical_packages() {
+        let (managers, removed) = orphan_managers();
+        let out = handle_orphans_with_confirm(
+            &config_with_policy(Some("remove")),
+            &tracked_state(),
+            &managers,
+            &SyncOptions::default(),
+            &mut never_asked,
+        )
+        .unwrap();
+        assert_eq!(out.len(), 2);
+        assert_eq!(*removed.lock().unwrap(), vec!["libfoo", "libbar"]);
+    }
+
+    #[test]
+    fn ask_policy_removes_only_confirmed_orphans() {
+        let (managers, removed) = orphan_managers();
+        let mut asked = Vec::new();
+        handle_orphans_with_confirm(
+            &config_with_policy(Some("ask")),
+            &tracked_state(),
+            &managers,
+            &SyncOptions::default(),
+            &mut |pkg| {
+                asked.push(pkg.name.clone());
+                pkg.name == "libbar"
+            },
+        )
+        .unwrap();
+        assert_eq!(asked, vec!["libfoo", "libbar"]);
+        assert_eq!(*removed.lock().unwrap(), vec!["libbar"]);
+    }
+
+    #[test]
+    fn prune_orphans_flag_overrides_keep_policy() {
+        let (managers, removed) = orphan_managers();
+        let options = SyncOptions {
+            prune_orphans: true,
+            ..Default::default()
+        };
+        handle_orphans_with_confirm(
+            &config_with_policy(Some("keep")),
+            &tracked_state(),
+            &managers,
+            &options,
+            &mut never_asked,
+        )
+        .unwrap();
+        assert_eq!(*removed.lock().unwrap(), vec!["libfoo", "libbar"]);
+    }
+
+    #[test]
+    fn declared_orphans_and_dry_runs_are_left_installed() {
+        let (managers, removed) = orphan_managers();
+        let mut config = config_with_policy(Some("remove"));
+        config.packages.insert(
+            PackageId {
+                name: "libfoo".to_string(),
+                backend: Backend::from("aur"),
+            },
+            Vec::new(),
+        );
+        let options = SyncOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let out = handle_orphans_with_confirm(
+            &config,
+            &tracked_state(),
+            &managers,
+            &options,
+            &mut never_asked,
+        )
+        .unwrap();
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].name, "libbar");
+        assert!(removed.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn legacy_orphans_policy_does_not_remove_dependencies() {
+        let (managers, removed) = orphan_managers();
+        let config = loader::MergedConfig {
+            policy: Some(PolicyConfig {
+                orphans: Some("remove".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        handle_orphans_with_confirm(
+            &config,
+            &tracked_state(),
+            &managers,
+            &SyncOptions::default(),
+            &mut never_asked,
+        )
+        .unwrap();
+        assert!(removed.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn removed_orphans_are_dropped_from_state() {
+        let mut state = tracked_state();
+        let dropped = forget_removed_orphans(
+            &mut state,
+            &[PackageId {
+                name: "libfoo".to_string(),
+                backend: Backend::from("aur"),
+            }],
+        );
+        assert_eq!(dropped, 1);
+        assert!(!state.packages.contains_key("aur:libfoo"));
+        assert!(state.packages.contains_key("aur:ripgrep"));
+    }
+}
@@ -3,30 +3,80 @@
 //! Determines what packages to install, adopt, prune, and update.
 
 mod filtering;
+mod orphan_preview;
 mod presentation;
+mod prune_reasons;
 mod variant_transition;
 mod warnings;
 
-use super::{InstalledSnapshot, ManagerMap, SyncOptions};
+use super::{ConflictChoices, InstalledSnapshot, ManagerMap, PackageSizes, SyncOptions};
 use crate::config::loader;
+pub use crate::core::resolver::{InstallReason, InstallReasonMap};
 use crate::core::{resolver, types::SyncTarget};
 use crate::error::Result;
 use crate::state::types::State;
-use filtering::resolve_filtered_transaction;
-use presentation::{display_dry_run_details_impl, display_transaction_plan_impl};
+use filtering::{
+    apply_assume_installed, apply_conflict_choices, apply_reinstall, apply_tag_filter,
+    declarations_not_kept, exclude_pinned_prunes, resolve_filtered_transaction,
+    restrict_prunes_to_backends, restrict_to_module,
+};
+use orphan_preview::display_orphan_preview_impl;
+use presentation::{
+    display_dry_run_details_impl, display_transaction_plan_impl, install_size_summary_impl,
+};
+use prune_reasons::explain_prunes_impl;
+pub use prune_reasons::{PruneReason, PruneReasonMap};
 use variant_transition::{collect_variant_mismatches, emit_variant_transition_error};
 use warnings::warn_partial_upgrade_impl;
 
 /// Create transaction from current state and desired config
-/// This is a wrapper that calls resolve_and_filter_packages
+/// This is a wrapper that calls resolve_and_filter_packages, then applies the
+/// plan filters of `options`: drops `assume_installed` packages from installs
+/// and prunes, keeps only packages carrying one of `tags`, forces `reinstall`
+/// packages into installs, and drops pinned prunes as well as prunes outside
+/// `prune_backends` (when non-empty). With `target_module`, only that module's
+/// packages are planned and nothing is pruned.
 pub fn create_transaction(
     config: &mut loader::MergedConfig,
     state: &State,
     installed_snapshot: &InstalledSnapshot,
     managers: &ManagerMap,
     sync_target: &SyncTarget,
+    options: &SyncOptions,
 ) -> Result<resolver::Transaction> {
-    resolve_and_filter_packages(config, state, installed_snapshot, managers, sync_target)
+    let target_module = options.target_module.as_deref();
+    if let Some(module) = target_module {
+        restrict_to_module(config, module)?;
+    }
+    let mut tx =
+        resolve_and_filter_packages(config, state, installed_snapshot, managers, sync_target)?;
+    if target_module.is_some() {
+        // Packages of other modules are out of scope, not undeclared
+        tx.to_prune.clear();
+    }
+    apply_assume_installed(&mut tx, state, &options.assume_installed);
+    apply_tag_filter(&mut tx, config, &options.tags);
+    apply_reinstall(&mut tx, config, &options.reinstall)?;
+    exclude_pinned_prunes(&mut tx, state);
+    restrict_prunes_to_backends(&mut tx, &options.prune_backends);
+    Ok(tx)
+}
+
+/// Drop the declarations not kept by interactive conflict choices
+pub fn filter_conflict_choices(
+    tx: &mut resolver::Transaction,
+    config: &loader::MergedConfig,
+    choices: &ConflictChoices,
+) {
+    apply_conflict_choices(tx, config, choices);
+}
+
+/// Declarations that conflict choices set aside, sorted
+pub fn conflict_losers(
+    config: &loader::MergedConfig,
+    choices: &ConflictChoices,
+) -> Vec<crate::core::types::PackageId> {
+    declarations_not_kept(config, choices)
 }
 
 /// Resolve transaction and filter packages by available backends
@@ -59,21 +109,67 @@ pub fn check_variant_transitions(
 }
 
 /// Warn about partial upgrades when system hasn't been updated recently
-pub fn warn_partial_upgrade(state: &State, tx: &resolver::Transaction, options: &SyncOptions) {
-    warn_partial_upgrade_impl(state, tx, options);
+///
+/// With `policy { block-partial-upgrade "true" }`, a targeted sync that would
+/// install packages from a backend with an `update` command on a stale system
+/// is refused instead.
+pub fn warn_partial_upgrade(
+    config: &loader::MergedConfig,
+    state: &State,
+    tx: &resolver::Transaction,
+    sync_target: &SyncTarget,
+    managers: &ManagerMap,
+    options: &SyncOptions,
+) -> Result<()> {
+    warn_partial_upgrade_impl(config, state, tx, sync_target, managers, options)
+}
+
+/// Classify every planned removal (left config, orphaned dependency, variant superseded,
+/// no longer declared)
+pub fn explain_prunes(
+    config: &loader::MergedConfig,
+    state: &State,
+    tx: &resolver::Transaction,
+) -> PruneReasonMap {
+    explain_prunes_impl(config, state, tx)
+}
+
+/// Show dependencies that would become orphans once prunes are applied, for
+/// backends that can report their dependency graph
+pub fn display_orphan_preview(tx: &resolver::Transaction, managers: &ManagerMap) {
+    display_orphan_preview_impl(tx, managers);
 }
 
 /// Display the transaction plan to the user with backend grouping
-pub fn display_transaction_plan(tx: &resolver::Transaction, should_prune: bool) {
-    display_transaction_plan_impl(tx, should_prune);
+///
+/// Removals are split into sections using `prune_reasons`; installs with a
+/// known size in `install_sizes` are summed into an approximate total.
+pub fn display_transaction_plan(
+    tx: &resolver::Transaction,
+    should_prune: bool,
+    prune_reasons: &PruneReasonMap,
+    install_sizes: &PackageSizes,
+) {
+    display_transaction_plan_impl(tx, should_prune, prune_reasons, install_sizes);
+}
+
+/// Approximate download size of planned installs, per backend and total
+pub fn install_size_summary(
+    tx: &resolver::Transaction,
+    install_sizes: &PackageSizes,
+) -> Option<String> {
+    install_size_summary_impl(tx, install_sizes)
 }
 
 /// Display detailed dry-run simulation
 /// Shows what would happen without actually executing
+///
+/// Packages with an entry in `install_reasons` are annotated with it.
 pub fn display_dry_run_details(
     tx: &resolver::Transaction,
     should_prune: bool,
     installed_snapshot: &InstalledSnapshot,
+    install_reasons: &InstallReasonMap,
 ) {
-    display_dry_run_details_impl(tx, should_prune, installed_snapshot);
+    display_dry_run_details_impl(tx, should_prune, installed_snapshot, install_reasons);
 }
@@ -1,11 +1,16 @@
 use crate::config::loader;
-use crate::core::{resolver, types::SyncTarget};
-use crate::error::Result;
+use crate::core::{
+    resolver,
+    types::{Backend, PackageId, SyncTarget},
+};
+use crate::error::{DeclarchError, Result};
 use crate::project_identity;
 use crate::state::types::State;
 use crate::ui as output;
+use std::collections::HashSet;
+use std::path::Path;
 
-use super::{InstalledSnapshot, ManagerMap};
+use super::{ConflictChoices, InstalledSnapshot, ManagerMap};
 
 pub(super) fn resolve_filtered_transaction(
     config: &mut loader::MergedConfig,
@@ -14,8 +19,7 @@ pub(super) fn resolve_filtered_transaction(
     managers: &ManagerMap,
     sync_target: &SyncTarget,
 ) -> Result<resolver::Transaction> {
-    use crate::core::types::Backend;
-    use std::collections::{HashMap, HashSet};
+    use std::collections::HashMap;
 
     let available_backends: HashSet<Backend> = managers.keys().cloned().collect();
     let total_packages = config.packages.len();
@@ -55,10 +59,202 @@ pub(super) fn resolve_filtered_transaction(
     Ok(tx)
 }
 
+/// Drop packages the user asserted are already installed for this run.
+///
+/// Names match the config name or the resolved `actual_package_name` in state.
+pub(super) fn apply_assume_installed(
+    tx: &mut resolver::Transaction,
+    state: &State,
+    assume_installed: &[String],
+) {
+    if assume_installed.is_empty() {
+        return;
+    }
+
+    let is_assumed = |pkg: &PackageId| {
+        let actual_name = state
+            .packages
+            .get(&resolver::make_state_key(pkg))
+            .and_then(|entry| entry.actual_package_name.as_deref());
+        assume_installed
+            .iter()
+            .any(|name| name == &pkg.name || Some(name.as_str()) == actual_name)
+    };
+
+    let before = tx.to_install.len() + tx.to_prune.len();
+    tx.to_install.retain(|pkg| !is_assumed(pkg));
+    tx.to_prune.retain(|pkg| !is_assumed(pkg));
+    let skipped = before - tx.to_install.len() - tx.to_prune.len();
+    if skipped > 0 {
+        output::info(&format!(
+            "Assuming {} package(s) already installed (--assume-installed)",
+            skipped
+        ));
+    }
+}
+
+/// Force `--reinstall` packages into installs even though they are present.
+///
+/// Names match a declared package by name or as `backend:name`; a name that
+/// matches nothing declared is an error.
+pub(super) fn apply_reinstall(
+    tx: &mut resolver::Transaction,
+    config: &loader::MergedConfig,
+    reinstall: &[String],
+) -> Result<()> {
+    for name in reinstall {
+        let mut matched: Vec<PackageId> = config
+            .packages
+            .keys()
+            .filter(|pkg| name == &pkg.name || name == &pkg.to_string())
+            .map(|pkg| resolver::resolve_package_alias(config, pkg))
+            .collect();
+        if matched.is_empty() {
+            return Err(DeclarchError::Other(format!(
+                "Cannot reinstall '{}': not a declared package",
+                name
+            )));
+        }
+        matched.sort_by_key(|pkg| pkg.to_string());
+
+        for pkg in matched {
+            tx.to_adopt.retain(|adopt| adopt != &pkg);
+            if !tx.to_install.contains(&pkg) {
+                tx.install_reasons
+                    .insert(pkg.clone(), resolver::InstallReason::Reinstall);
+                tx.to_install.push(pkg);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Keep only installs and adoptions carrying one of `tags` (`--tag`).
+///
+/// Untagged packages are left out, and so are removals: undeclared packages
+/// carry no tags.
+pub(super) fn apply_tag_filter(
+    tx: &mut resolver::Transaction,
+    config: &loader::MergedConfig,
+    tags: &[String],
+) {
+    if tags.is_empty() {
+        return;
+    }
+
+    let tagged: HashSet<PackageId> = config
+        .package_tags
+        .iter()
+        .filter(|(_, pkg_tags)| tags.iter().any(|tag| pkg_tags.contains(tag)))
+        .flat_map(|(pkg, _)| [pkg.clone(), resolver::resolve_package_alias(config, pkg)])
+        .collect();
+
+    tx.to_install.retain(|pkg| tagged.contains(pkg));
+    tx.to_adopt.retain(|pkg| tagged.contains(pkg));
+    tx.to_prune.clear();
+
+    if tagged.is_empty() {
+        output::warning(&format!(
+            "No declared package is tagged {}",
+            tags.join(" or ")
+        ));
+    }
+}
+
+/// Keep only packages declared in `module` (`--target-module`).
+///
+/// `module` is a file stem (`base`) or a path relative to the config
+/// directory (`modules/base.kdl`); `.kdl` may be left out of either.
+pub(super) fn restrict_to_module(config: &mut loader::MergedConfig, module: &str) -> Result<()> {
+    config.packages.retain(|_, sources| {
+        sources
+            .iter()
+            .any(|source| source_is_module(source, module))
+    });
+
+    if config.packages.is_empty() {
+        return Err(DeclarchError::Other(format!(
+            "No packages are declared in module '{}'",
+            module
+        )));
+    }
+    Ok(())
+}
+
+fn source_is_module(source: &Path, module: &str) -> bool {
+    let module_path = Path::new(module);
+    if module_path.components().count() == 1 && module_path.extension().is_none() {
+        return source
+            .file_stem()
+            .is_some_and(|stem| stem.to_string_lossy().eq_ignore_ascii_case(module));
+    }
+    source.ends_with(module_path) || source.ends_with(module_path.with_extension("kdl"))
+}
+
+/// Declarations set aside by conflict choices: same name, backend not kept.
+pub(super) fn declarations_not_kept(
+    config: &loader::MergedConfig,
+    choices: &ConflictChoices,
+) -> Vec<PackageId> {
+    let mut dropped: Vec<PackageId> = config
+        .packages
+        .keys()
+        .filter(|pkg| {
+            choices
+                .get(&pkg.name)
+                .is_some_and(|keep| keep != &pkg.backend)
+        })
+        .cloned()
+        .collect();
+    dropped.sort_by_key(|pkg| pkg.to_string());
+    dropped
+}
+
+/// Drop declarations not kept by `--interactive-conflicts` from this run.
+pub(super) fn apply_conflict_choices(
+    tx: &mut resolver::Transaction,
+    config: &loader::MergedConfig,
+    choices: &ConflictChoices,
+) {
+    let dropped: Vec<PackageId> = declarations_not_kept(config, choices)
+        .iter()
+        .map(|pkg| resolver::resolve_package_alias(config, pkg))
+        .collect();
+    if dropped.is_empty() {
+        return;
+    }
+    tx.to_install.retain(|pkg| !dropped.contains(pkg));
+    tx.to_adopt.retain(|pkg| !dropped.contains(pkg));
+    tx.to_update_project_metadata
+        .retain(|pkg| !dropped.contains(pkg));
+}
+
+/// Keep only prunes on the `--prune-backend` allow-list; empty means no restriction.
+pub(super) fn restrict_prunes_to_backends(tx: &mut resolver::Transaction, backends: &[String]) {
+    if backends.is_empty() {
+        return;
+    }
+    let allowed: Vec<Backend> = backends.iter().map(|b| Backend::from(b.as_str())).collect();
+    tx.to_prune.retain(|pkg| allowed.contains(&pkg.backend));
+}
+
+/// Never prune packages that state records as pinned, declared or not.
+pub(super) fn exclude_pinned_prunes(tx: &mut resolver::Transaction, state: &State) {
+    tx.to_prune.retain(|pkg| {
+        !state
+            .packages
+            .get(&resolver::make_state_key(pkg))
+            .is_some_and(|entry| entry.pinned)
+    });
+}
+
 #[cfg(test)]
 mod tests {
-    use super::resolve_filtered_transaction;
-    use crate::commands::sync::ManagerMap;
+    use super::{
+        apply_assume_installed, apply_conflict_choices, apply_reinstall, apply_tag_filter,
+        resolve_filtered_transaction, restrict_prunes_to_backends,
+    };
+    use crate::commands::sync::{ManagerMap, SyncOptions};
     use crate::config::loader;
     use crate::core::types::{Backend, PackageId, SyncTarget};
     use crate::error::Result;
@@ -131,4 +327,382 @@ mod tests {
         assert!(tx.to_install.iter().any(|p| p.name == "bat"));
         assert_eq!(config.packages.len(), original_len);
     }
+
+    #[test]
+    fn assume_installed_drops_matching_installs_and_prunes() {
+        use crate::core::resolver::{Transaction, make_state_key};
+        use crate::state::types::PackageState;
+
+        let declared = PackageId {
+            name: "neovim".to_string(),
+            backend: Backend::from("aur"),
+        };
+        let renamed = PackageId {
+            name: "python-foo".to_string(),
+            backend: Backend::from("aur"),
+        };
+        let untouched = PackageId {
+            name: "bat".to_string(),
+            backend: Backend::from("aur"),
+        };
+
+        let mut state = State::default();
+        let mut entry =
+            PackageState::from_config(renamed.name.clone(), renamed.backend.clone(), None);
+        entry.actual_package_name = Some("foo-bin".to_string());
+        state.packages.insert(make_state_key(&renamed), entry);
+
+        let mut tx = Transaction {
+            to_install: vec![declared, untouched.clone()],
+            to_prune: vec![renamed],
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+
+        apply_assume_installed(
+            &mut tx,
+            &state,
+            &["neovim".to_string(), "foo-bin".to_string()],
+        );
+
+        assert_eq!(tx.to_install, vec![untouched]);
+        assert!(tx.to_prune.is_empty());
+    }
+
+    #[test]
+    fn restrict_prunes_keeps_only_allowed_backends() {
+        use crate::core::resolver::Transaction;
+
+        let flatpak_app = PackageId {
+            name: "org.gimp.GIMP".to_string(),
+            backend: Backend::from("flatpak"),
+        };
+        let aur_pkg = PackageId {
+            name: "base-devel".to_string(),
+            backend: Backend::from("aur"),
+        };
+        let mut tx = Transaction {
+            to_install: vec![aur_pkg.clone()],
+            to_prune: vec![flatpak_app.clone(), aur_pkg.clone()],
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+
+        restrict_prunes_to_backends(&mut tx, &[]);
+        assert_eq!(tx.to_prune.len(), 2);
+
+        restrict_prunes_to_backends(&mut tx, &["Flatpak".to_string()]);
+        assert_eq!(tx.to_prune, vec![flatpak_app]);
+        assert_eq!(tx.to_install, vec![aur_pkg]);
+    }
+
+    #[test]
+    fn pinned_package_from_inactive_profile_is_not_pruned() {
+        use crate::core::resolver::make_state_key;
+        use crate::core::types::PackageMetadata;
+        use crate::state::types::PackageState;
+
+        let content = r#"
+pkg { aur { git } }
+profile "work" {
+  pkg { aur { neovim pinned=#true; slack } }
+}
+"#;
+        let mut config =
+            loader::load_root_config_from_str(content, None, &loader::LoadSelectors::default())
+                .expect("config should parse");
+
+        let mut state = State::default();
+        let mut snapshot = HashMap::new();
+        for name in ["git", "neovim", "slack"] {
+            let pkg = PackageId {
+                name: name.to_string(),
+                backend: Backend::from("aur"),
+            };
+            let mut entry = PackageState::from_config(name.to_string(), pkg.backend.clone(), None);
+            entry.pinned = name == "neovim";
+            state.packages.insert(make_state_key(&pkg), entry);
+            snapshot.insert(
+                pkg,
+                PackageMetadata {
+                    version: None,
+                    variant: None,
+                    installed_at: chrono::Utc::now(),
+                    source_file: None,
+                    install_size: None,
+                    origin: None,
+                },
+            );
+        }
+
+        let mut managers: ManagerMap = HashMap::new();
+        managers.insert(
+            Backend::from("aur"),
+            Box::new(DummyManager {
+                backend: Backend::from("aur"),
+            }),
+        );
+
+        let tx = super::super::create_transaction(
+            &mut config,
+            &state,
+            &snapshot,
+            &managers,
+            &SyncTarget::All,
+            &SyncOptions::default(),
+        )
+        .expect("transaction should be planned");
+
+        let pruned: Vec<&str> = tx.to_prune.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(pruned, vec!["slack"]);
+    }
+
+    #[test]
+    fn reinstall_forces_declared_packages_and_rejects_unknown() {
+        use crate::core::resolver::Transaction;
+
+        let bat = PackageId {
+            name: "bat".to_string(),
+            backend: Backend::from("aur"),
+        };
+        let fd = PackageId {
+            name: "fd".to_string(),
+            backend: Backend::from("aur"),
+        };
+        let mut config = loader::MergedConfig::default();
+        config.packages.insert(bat.clone(), vec![]);
+        config.packages.insert(fd.clone(), vec![]);
+
+        let mut tx = Transaction {
+            to_install: Vec::new(),
+            to_prune: Vec::new(),
+            to_adopt: vec![fd.clone()],
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+        apply_reinstall(&mut tx, &config, &["bat".to_string(), "aur:fd".to_string()])
+            .expect("declared packages can be reinstalled");
+        assert_eq!(tx.to_install, vec![bat, fd]);
+        assert!(tx.to_adopt.is_empty());
+
+        let err = apply_reinstall(&mut tx, &config, &["ripgrep".to_string()])
+            .expect_err("undeclared package must be rejected");
+        assert!(err.to_string().contains("ripgrep"));
+    }
+
+    #[test]
+    fn reinstall_reason_follows_the_alias_resolved_package() {
+        use crate::core::resolver::{InstallReason, Transaction};
+
+        let declared = PackageId {
+            name: "neovim".to_string(),
+            backend: Backend::from("aur"),
+        };
+        let resolved = PackageId {
+            name: "neovim-git".to_string(),
+            backend: Backend::from("aur"),
+        };
+        let missing = PackageId {
+            name: "fd".to_string(),
+            backend: Backend::from("aur"),
+        };
+        let mut config = loader::MergedConfig::default();
+        config.packages.insert(declared.clone(), vec![]);
+        config.packages.insert(missing.clone(), vec![]);
+        config
+            .package_aliases
+            .insert("neovim".to_string(), "neovim-git".to_string());
+
+        let mut tx = Transaction {
+            to_install: vec![missing.clone()],
+            to_prune: Vec::new(),
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::from([(missing.clone(), InstallReason::Missing)]),
+        };
+        apply_reinstall(&mut tx, &config, &["neovim".to_string(), "fd".to_string()])
+            .expect("declared packages can be reinstalled");
+
+        assert_eq!(tx.install_reasons[&resolved], InstallReason::Reinstall);
+        assert_eq!(tx.install_reasons[&missing], InstallReason::Missing);
+    }
+
+    #[test]
+    fn conflict_choices_drop_declarations_not_kept() {
+        use crate::core::resolver::Transaction;
+
+        let aur_bat = PackageId {
+            name: "bat".to_string(),
+            backend: Backend::from("aur"),
+        };
+        let cargo_bat = PackageId {
+            name: "bat".to_string(),
+            backend: Backend::from("cargo"),
+        };
+        let fd = PackageId {
+            name: "fd".to_string(),
+            backend: Backend::from("cargo"),
+        };
+        let mut config = loader::MergedConfig::default();
+        for pkg in [&aur_bat, &cargo_bat, &fd] {
+            config.packages.insert(pkg.clone(), vec![]);
+        }
+
+        let mut tx = Transaction {
+            to_install: vec![aur_bat.clone(), fd.clone()],
+            to_prune: Vec::new(),
+            to_adopt: vec![cargo_bat.clone()],
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+        let choices = HashMap::from([("bat".to_string(), Backend::from("aur"))]);
+        apply_conflict_choices(&mut tx, &config, &choices);
+
+        assert_eq!(tx.to_install, vec![aur_bat, fd]);
+        assert!(tx.to_adopt.is_empty());
+    }
+
+    fn tagged_config(entries: &[(&PackageId, &[&str])]) -> loader::MergedConfig {
+        let mut config = loader::MergedConfig::default();
+        for (pkg, tags) in entries {
+            config.packages.insert((*pkg).clone(), vec![]);
+            if !tags.is_empty() {
+                config.package_tags.insert(
+                    (*pkg).clone(),
+                    tags.iter().map(ToString::to_string).collect(),
+                );
+            }
+        }
+        config
+    }
+
+    #[test]
+    fn tag_filter_keeps_union_of_requested_tags() {
+        use crate::core::resolver::Transaction;
+
+        let pkg = |name: &str| PackageId {
+            name: name.to_string(),
+            backend: Backend::from("aur"),
+        };
+        let (neovim, gimp, steam, bat) = (pkg("neovim"), pkg("gimp"), pkg("steam"), pkg("bat"));
+        let config = tagged_config(&[
+            (&neovim, &["dev", "editor"]),
+            (&gimp, &["media"]),
+            (&steam, &["games"]),
+            (&bat, &[]),
+        ]);
+
+        let mut tx = Transaction {
+            to_install: vec![neovim.clone(), gimp.clone(), bat.clone()],
+            to_prune: vec![pkg("orphan")],
+            to_adopt: vec![steam.clone()],
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+        apply_tag_filter(
+            &mut tx,
+            &config,
+            &["editor".to_string(), "media".to_string()],
+        );
+
+        assert_eq!(tx.to_install, vec![neovim, gimp]);
+        assert!(tx.to_adopt.is_empty());
+        assert!(tx.to_prune.is_empty());
+    }
+
+    #[test]
+    fn tag_filter_without_matches_plans_nothing() {
+        use crate::core::resolver::Transaction;
+
+        let neovim = PackageId {
+            name: "neovim".to_string(),
+            backend: Backend::from("aur"),
+        };
+        let config = tagged_config(&[(&neovim, &["dev"])]);
+
+        let mut tx = Transaction {
+            to_install: vec![neovim.clone()],
+            to_prune: Vec::new(),
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+        apply_tag_filter(&mut tx, &config, &[]);
+        assert_eq!(tx.to_install, vec![neovim]);
+
+        apply_tag_filter(&mut tx, &config, &["work".to_string()]);
+        assert!(tx.to_install.is_empty());
+    }
+
+    #[test]
+    fn target_module_plans_only_that_modules_packages_without_prunes() {
+        use crate::core::resolver::make_state_key;
+        use crate::state::types::PackageState;
+        use std::path::PathBuf;
+
+        let pkg = |name: &str| PackageId {
+            name: name.to_string(),
+            backend: Backend::from("aur"),
+        };
+        let module_config = || {
+            let mut config = loader::MergedConfig::default();
+            let base = PathBuf::from("/home/u/.config/declarch/modules/base.kdl");
+            let dev = PathBuf::from("/home/u/.config/declarch/modules/dev.kdl");
+            config.packages.insert(pkg("git"), vec![base.clone()]);
+            config.packages.insert(pkg("bat"), vec![dev.clone(), base]);
+            config.packages.insert(pkg("neovim"), vec![dev]);
+            config
+        };
+
+        let mut state = State::default();
+        let orphan = pkg("htop");
+        state.packages.insert(
+            make_state_key(&orphan),
+            PackageState::from_config("htop".to_string(), orphan.backend.clone(), None),
+        );
+
+        let mut managers: ManagerMap = HashMap::new();
+        managers.insert(
+            Backend::from("aur"),
+            Box::new(DummyManager {
+                backend: Backend::from("aur"),
+            }),
+        );
+
+        for module in ["base", "modules/base.kdl", "modules/base"] {
+            let tx = super::super::create_transaction(
+                &mut module_config(),
+                &state,
+                &HashMap::new(),
+                &managers,
+                &SyncTarget::All,
+                &SyncOptions {
+                    target_module: Some(module.to_string()),
+                    ..Default::default()
+                },
+            )
+            .expect("transaction should be planned");
+
+            let mut installs: Vec<&str> = tx.to_install.iter().map(|p| p.name.as_str()).collect();
+            installs.sort();
+            assert_eq!(installs, vec!["bat", "git"], "module {}", module);
+            assert!(tx.to_prune.is_empty());
+        }
+
+        let err = super::super::create_transaction(
+            &mut module_config(),
+            &state,
+            &HashMap::new(),
+            &managers,
+            &SyncTarget::All,
+            &SyncOptions {
+                target_module: Some("missing".to_string()),
+                ..Default::default()
+            },
+        )
+        .expect_err("unknown module is rejected");
+        assert!(err.to_string().contains("module 'missing'"));
+    }
 }
@@ -0,0 +1,139 @@
+//! Orphan preview for prune plans
+//!
+//! For backends with a `dependencies` command, shows which installed
+//! dependencies would be left without any dependent once the pruned packages
+//! are removed. Nothing here removes packages; it only gives foresight into
+//! cascading cleanup.
+
+use crate::core::{resolver, types::Backend};
+use crate::packages::traits::DependencyGraph;
+use crate::ui as output;
+
+use super::ManagerMap;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+pub(super) fn display_orphan_preview_impl(tx: &resolver::Transaction, managers: &ManagerMap) {
+    let mut removed_by_backend: BTreeMap<String, (Backend, HashSet<String>)> = BTreeMap::new();
+    for pkg in &tx.to_prune {
+        removed_by_backend
+            .entry(pkg.backend.to_string())
+            .or_insert_with(|| (pkg.backend.clone(), HashSet::new()))
+            .1
+            .insert(pkg.name.clone());
+    }
+
+    for (backend, removed) in removed_by_backend.values() {
+        let Some(mgr) = managers.get(backend) else {
+            continue;
+        };
+        if !mgr.supports_dependency_graph() {
+            continue;
+        }
+        let graph = match mgr.dependency_graph() {
+            Ok(graph) => graph,
+            Err(e) => {
+                output::verbose(&format!("Skipping orphan preview for {}: {}", backend, e));
+                continue;
+            }
+        };
+
+        let orphans = would_be_orphans(&graph, removed);
+        if orphans.is_empty() {
+            continue;
+        }
+
+        output::info(&format!(
+            "After removal, {} {} dependenc{} would be left orphaned:",
+            orphans.len(),
+            backend,
+            if orphans.len() == 1 { "y" } else { "ies" }
+        ));
+        output::indent(&orphans.join(", "), 2);
+        output::indent(
+            "These are not removed by this sync; review them with the package manager afterwards.",
+            2,
+        );
+    }
+}
+
+/// Dependency-installed packages whose every dependent would be gone.
+///
+/// Removal cascades: a dependency only required by another would-be orphan is
+/// an orphan too. Packages in `removed` are never reported.
+pub(super) fn would_be_orphans(graph: &DependencyGraph, removed: &HashSet<String>) -> Vec<String> {
+    let mut gone: HashSet<&str> = removed.iter().map(String::as_str).collect();
+    let mut orphans = BTreeSet::new();
+
+    loop {
+        let newly_orphaned: Vec<&str> = graph
+            .as_dependency
+            .iter()
+            .map(String::as_str)
+            .filter(|name| !gone.contains(name))
+            .filter(|name| {
+                graph.required_by.get(*name).is_some_and(|dependents| {
+                    !dependents.is_empty() && dependents.iter().all(|d| gone.contains(d.as_str()))
+                })
+            })
+            .collect();
+
+        if newly_orphaned.is_empty() {
+            break;
+        }
+        for name in newly_orphaned {
+            gone.insert(name);
+            orphans.insert(name.to_string());
+        }
+    }
+
+    orphans.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &[&str])], deps: &[&str]) -> DependencyGraph {
+        DependencyGraph {
+            required_by: edges
+                .iter()
+                .map(|(name, by)| {
+                    (
+                        name.to_string(),
+                        by.iter().map(ToString::to_string).collect(),
+                    )
+                })
+                .collect(),
+            as_dependency: deps.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn would_be_orphans_follows_cascading_and_shared_dependencies() {
+        // hyprland -> aquamarine -> hyprutils; hyprutils is also needed by hyprlock.
+        // hyprland -> wlroots-helper (only dependent).
+        let graph = graph(
+            &[
+                ("hyprland", &[]),
+                ("hyprlock", &[]),
+                ("aquamarine", &["hyprland"]),
+                ("hyprutils", &["aquamarine", "hyprlock"]),
+                ("wlroots-helper", &["hyprland"]),
+                ("libdrm", &["aquamarine"]),
+                ("glibc", &[]),
+            ],
+            &[
+                "aquamarine",
+                "hyprutils",
+                "wlroots-helper",
+                "libdrm",
+                "glibc",
+            ],
+        );
+        let removed: HashSet<String> = ["hyprland".to_string()].into_iter().collect();
+
+        let orphans = would_be_orphans(&graph, &removed);
+
+        assert_eq!(orphans, vec!["aquamarine", "libdrm", "wlroots-helper"]);
+    }
+}
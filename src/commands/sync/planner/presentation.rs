@@ -3,12 +3,20 @@ use crate::core::resolver;
 use crate::core::types::PackageId;
 use crate::project_identity;
 use crate::ui as output;
+use crate::utils::size::format_size;
 use colored::Colorize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use super::InstalledSnapshot;
+use super::InstallReasonMap;
+use super::prune_reasons::{PruneReasonMap, bucket_prunes};
+use super::{InstalledSnapshot, PackageSizes};
 
-pub(super) fn display_transaction_plan_impl(tx: &resolver::Transaction, should_prune: bool) {
+pub(super) fn display_transaction_plan_impl(
+    tx: &resolver::Transaction,
+    should_prune: bool,
+    prune_reasons: &PruneReasonMap,
+    install_sizes: &PackageSizes,
+) {
     let has_changes = !tx.to_install.is_empty()
         || !tx.to_adopt.is_empty()
         || (!tx.to_prune.is_empty() && should_prune);
@@ -26,6 +34,9 @@ pub(super) fn display_transaction_plan_impl(tx: &resolver::Transaction, should_p
             "Install:".green(),
             format_backend_groups(&groups)
         );
+        if let Some(summary) = install_size_summary_impl(tx, install_sizes) {
+            println!("  {}    {}", "Size:".green(), summary.dimmed());
+        }
     }
 
     if !tx.to_adopt.is_empty() {
@@ -38,28 +49,31 @@ pub(super) fn display_transaction_plan_impl(tx: &resolver::Transaction, should_p
     }
 
     if !tx.to_prune.is_empty() && should_prune {
-        let groups = group_by_backend(&tx.to_prune);
-        let formatted: HashMap<String, Vec<String>> = groups
-            .into_iter()
-            .map(|(backend, packages)| {
-                let modified: Vec<String> = packages
-                    .into_iter()
-                    .map(|p| {
-                        if CRITICAL_PACKAGES.contains(&p.as_str()) {
-                            format!("{} [keep]", p)
-                        } else {
-                            p
-                        }
-                    })
-                    .collect();
-                (backend, modified)
-            })
-            .collect();
-        println!(
-            "  {}  {}",
-            "Remove:".red(),
-            format_backend_groups(&formatted)
-        );
+        println!("  {}", "Remove:".red());
+        for (reason, packages) in bucket_prunes(&tx.to_prune, prune_reasons) {
+            let groups = group_by_backend(&packages);
+            let formatted: HashMap<String, Vec<String>> = groups
+                .into_iter()
+                .map(|(backend, packages)| {
+                    let modified: Vec<String> = packages
+                        .into_iter()
+                        .map(|p| {
+                            if CRITICAL_PACKAGES.contains(&p.as_str()) {
+                                format!("{} [keep]", p)
+                            } else {
+                                p
+                            }
+                        })
+                        .collect();
+                    (backend, modified)
+                })
+                .collect();
+            println!(
+                "    {} {}",
+                format!("{} ({}):", reason.label(), packages.len()).dimmed(),
+                format_backend_groups(&formatted)
+            );
+        }
     }
 }
 
@@ -67,6 +81,7 @@ pub(super) fn display_dry_run_details_impl(
     tx: &resolver::Transaction,
     should_prune: bool,
     installed_snapshot: &InstalledSnapshot,
+    install_reasons: &InstallReasonMap,
 ) {
     let has_changes = !tx.to_install.is_empty()
         || !tx.to_adopt.is_empty()
@@ -108,12 +123,21 @@ pub(super) fn display_dry_run_details_impl(
 
     if !tx.to_install.is_empty() {
         println!("{}", "Packages to install:".green().bold());
-        display_package_groups_detailed(&tx.to_install, installed_snapshot);
+        display_package_groups_detailed(&tx.to_install, installed_snapshot, install_reasons);
     }
 
     if !tx.to_adopt.is_empty() {
         println!("{}", "\nPackages to adopt:".yellow().bold());
-        display_package_groups_detailed(&tx.to_adopt, installed_snapshot);
+        display_package_groups_detailed(&tx.to_adopt, installed_snapshot, install_reasons);
+    }
+
+    if !tx.to_update_project_metadata.is_empty() {
+        println!("{}", "\nTracked versions to refresh:".blue().bold());
+        display_package_groups_detailed(
+            &tx.to_update_project_metadata,
+            installed_snapshot,
+            install_reasons,
+        );
     }
 
     if !tx.to_prune.is_empty() && should_prune {
@@ -200,11 +224,22 @@ fn format_backend_groups(groups: &HashMap<String, Vec<String>>) -> String {
     result
 }
 
-fn display_package_groups_detailed(packages: &[PackageId], installed_snapshot: &InstalledSnapshot) {
+fn display_package_groups_detailed(
+    packages: &[PackageId],
+    installed_snapshot: &InstalledSnapshot,
+    install_reasons: &InstallReasonMap,
+) {
     let groups = group_by_backend(packages);
     for (backend, pkg_names) in groups {
         println!("  {}:", backend.cyan());
         for name in pkg_names {
+            let reason = install_reasons
+                .get(&PackageId {
+                    name: name.clone(),
+                    backend: backend.as_str().into(),
+                })
+                .map(|reason| format!(" [{}]", reason.label()).cyan().to_string())
+                .unwrap_or_default();
             let variant_info = installed_snapshot
                 .iter()
                 .find(|(pkg_id, _)| pkg_id.name == name && pkg_id.backend.to_string() == backend);
@@ -212,20 +247,86 @@ fn display_package_groups_detailed(packages: &[PackageId], installed_snapshot: &
             if let Some((_, meta)) = variant_info {
                 if let Some(ref version) = meta.version {
                     println!(
-                        "    • {} {}",
+                        "    • {} {}{}",
                         name.green(),
-                        format!("(v{} already installed)", version).dimmed()
+                        format!("(v{} already installed)", version).dimmed(),
+                        reason
                     );
                 } else {
                     println!(
-                        "    • {} {}",
+                        "    • {} {}{}",
                         name.green(),
-                        "(already installed, untracked)".dimmed()
+                        "(already installed, untracked)".dimmed(),
+                        reason
                     );
                 }
             } else {
-                println!("    • {}", name.green());
+                println!("    • {}{}", name.green(), reason);
             }
         }
     }
 }
+
+/// "approx. download size" line for planned installs, per backend and total.
+///
+/// `None` when no planned install has a known size.
+pub(super) fn install_size_summary_impl(
+    tx: &resolver::Transaction,
+    install_sizes: &PackageSizes,
+) -> Option<String> {
+    let mut per_backend: BTreeMap<String, u64> = BTreeMap::new();
+    let mut unknown = 0;
+    for pkg in &tx.to_install {
+        match install_sizes.get(pkg) {
+            Some(size) => *per_backend.entry(pkg.backend.to_string()).or_default() += size,
+            None => unknown += 1,
+        }
+    }
+    if per_backend.is_empty() {
+        return None;
+    }
+
+    let total: u64 = per_backend.values().sum();
+    let backends: Vec<String> = per_backend
+        .iter()
+        .map(|(backend, size)| format!("{} {}", backend, format_size(*size)))
+        .collect();
+    let mut summary = format!(
+        "approx. download size {} ({})",
+        format_size(total),
+        backends.join(", ")
+    );
+    if unknown > 0 {
+        summary.push_str(&format!("; unknown for {} package(s)", unknown));
+    }
+    Some(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::sync::test_support::pkg;
+
+    #[test]
+    fn install_size_summary_totals_per_backend_and_counts_unknown() {
+        let tx = resolver::Transaction {
+            to_install: vec![pkg("npm", "eslint"), pkg("aur", "bat"), pkg("aur", "fd")],
+            to_prune: Vec::new(),
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+        let sizes = PackageSizes::from([
+            (pkg("aur", "bat"), 3 * 1024 * 1024),
+            (pkg("npm", "eslint"), 1024 * 1024),
+        ]);
+
+        assert_eq!(
+            install_size_summary_impl(&tx, &sizes).as_deref(),
+            Some(
+                "approx. download size 4.0 MiB (aur 3.0 MiB, npm 1.0 MiB); unknown for 1 package(s)"
+            )
+        );
+        assert_eq!(install_size_summary_impl(&tx, &PackageSizes::new()), None);
+    }
+}
@@ -0,0 +1,153 @@
+//! Prune explanations
+//!
+//! Classifies each planned removal so the plan can show why a package is
+//! about to be pruned.
+
+use crate::config::loader;
+use crate::constants::is_variant;
+use crate::core::{resolver, types::PackageId};
+use crate::state::types::State;
+use std::collections::HashMap;
+
+/// Why a tracked package is planned for removal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PruneReason {
+    /// Declared earlier, no longer present in config.
+    LeftConfig,
+    /// Entered state outside a config declaration (e.g. via `switch`) and nothing claims it now.
+    OrphanedDependency,
+    /// Replaced by a variant of the same package declared on the same backend.
+    VariantSuperseded,
+    /// Tracked with an install reason this version does not know; nothing claims it now.
+    NoLongerDeclared,
+}
+
+impl PruneReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            PruneReason::LeftConfig => "left config",
+            PruneReason::OrphanedDependency => "orphaned dependency",
+            PruneReason::VariantSuperseded => "variant superseded",
+            PruneReason::NoLongerDeclared => "no longer declared",
+        }
+    }
+}
+
+/// Reason for every package in `to_prune`, keyed by package id.
+pub type PruneReasonMap = HashMap<PackageId, PruneReason>;
+
+pub(super) fn explain_prunes_impl(
+    config: &loader::MergedConfig,
+    state: &State,
+    tx: &resolver::Transaction,
+) -> PruneReasonMap {
+    tx.to_prune
+        .iter()
+        .map(|pkg| (pkg.clone(), classify_prune(config, state, pkg)))
+        .collect()
+}
+
+fn classify_prune(config: &loader::MergedConfig, state: &State, pkg: &PackageId) -> PruneReason {
+    let superseded = config.packages.keys().any(|declared| {
+        declared.backend == pkg.backend
+            && (is_variant(&declared.name, &pkg.name) || is_variant(&pkg.name, &declared.name))
+    });
+    if superseded {
+        return PruneReason::VariantSuperseded;
+    }
+
+    let install_reason = state
+        .packages
+        .get(&resolver::make_state_key(pkg))
+        .and_then(|entry| entry.install_reason.as_deref());
+    match install_reason {
+        None | Some("declared") | Some("adopted") => PruneReason::LeftConfig,
+        Some("manual-sync") => PruneReason::OrphanedDependency,
+        Some(_) => PruneReason::NoLongerDeclared,
+    }
+}
+
+/// Group prunes by reason in display order, keeping unknown entries as "left config".
+pub(super) fn bucket_prunes(
+    to_prune: &[PackageId],
+    reasons: &PruneReasonMap,
+) -> Vec<(PruneReason, Vec<PackageId>)> {
+    let mut buckets: HashMap<PruneReason, Vec<PackageId>> = HashMap::new();
+    for pkg in to_prune {
+        let reason = reasons.get(pkg).copied().unwrap_or(PruneReason::LeftConfig);
+        buckets.entry(reason).or_default().push(pkg.clone());
+    }
+
+    let mut ordered: Vec<_> = buckets.into_iter().collect();
+    ordered.sort_by_key(|(reason, _)| *reason);
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::sync::test_support::pkg;
+    use crate::state::types::PackageState;
+
+    fn tracked(state: &mut State, id: &PackageId, reason: Option<&str>) {
+        let mut entry = PackageState::from_config(id.name.clone(), id.backend.clone(), None);
+        entry.install_reason = reason.map(ToString::to_string);
+        state.packages.insert(resolver::make_state_key(id), entry);
+    }
+
+    #[test]
+    fn prunes_are_bucketed_by_reason() {
+        let mut config = loader::MergedConfig::default();
+        config.packages.insert(pkg("aur", "hyprland-git"), vec![]);
+
+        let left = pkg("aur", "bat");
+        let orphan = pkg("aur", "libfoo");
+        let superseded = pkg("aur", "hyprland");
+        let legacy = pkg("npm", "typescript");
+        let unknown = pkg("npm", "eslint");
+
+        let mut state = State::default();
+        tracked(&mut state, &left, Some("declared"));
+        tracked(&mut state, &orphan, Some("manual-sync"));
+        tracked(&mut state, &superseded, Some("declared"));
+        tracked(&mut state, &legacy, None);
+        tracked(&mut state, &unknown, Some("from-the-future"));
+
+        let tx = resolver::Transaction {
+            to_install: Vec::new(),
+            to_prune: vec![
+                left.clone(),
+                orphan.clone(),
+                superseded.clone(),
+                legacy.clone(),
+                unknown.clone(),
+            ],
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+
+        let reasons = explain_prunes_impl(&config, &state, &tx);
+        assert_eq!(reasons[&left], PruneReason::LeftConfig);
+        assert_eq!(reasons[&orphan], PruneReason::OrphanedDependency);
+        assert_eq!(reasons[&superseded], PruneReason::VariantSuperseded);
+        assert_eq!(reasons[&legacy], PruneReason::LeftConfig);
+        assert_eq!(reasons[&unknown], PruneReason::NoLongerDeclared);
+
+        let buckets = bucket_prunes(&tx.to_prune, &reasons);
+        let order: Vec<_> = buckets.iter().map(|(reason, _)| *reason).collect();
+        assert_eq!(
+            order,
+            vec![
+                PruneReason::LeftConfig,
+                PruneReason::OrphanedDependency,
+                PruneReason::VariantSuperseded,
+                PruneReason::NoLongerDeclared,
+            ]
+        );
+        assert_eq!(buckets[0].1.len(), 2);
+        assert_eq!(buckets[1].1, vec![orphan]);
+        assert_eq!(buckets[2].1, vec![superseded]);
+        assert_eq!(buckets[3].1, vec![unknown]);
+    }
+}
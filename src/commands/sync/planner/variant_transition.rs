@@ -24,7 +24,11 @@ pub(super) fn collect_variant_mismatches(
     let matcher = PackageMatcher::new();
     let mut variant_mismatches: Vec<VariantMismatch> = Vec::new();
 
-    if matches!(sync_target, SyncTarget::All | SyncTarget::Backend(_)) {
+    if sync_target
+        .parts()
+        .iter()
+        .any(|target| matches!(target, SyncTarget::All | SyncTarget::Backend(_)))
+    {
         let available_backends: HashSet<Backend> = installed_snapshot
             .keys()
             .map(|pkg_id| pkg_id.backend.clone())
@@ -148,6 +152,8 @@ mod tests {
                 variant: None,
                 installed_at: Utc::now(),
                 source_file: None,
+                install_size: None,
+                origin: None,
             },
         );
 
@@ -156,6 +162,7 @@ mod tests {
             to_prune: Vec::new(),
             to_adopt: Vec::new(),
             to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
         };
 
         let state = State::default();
@@ -189,6 +196,8 @@ mod tests {
                 variant: None,
                 installed_at: Utc::now(),
                 source_file: None,
+                install_size: None,
+                origin: None,
             },
         );
 
@@ -204,6 +213,7 @@ mod tests {
                 version: Some("1.0".to_string()),
                 install_reason: None,
                 source_module: None,
+                pinned: false,
                 last_seen_at: None,
                 backend_meta: None,
             },
@@ -214,6 +224,7 @@ mod tests {
             to_prune: Vec::new(),
             to_adopt: Vec::new(),
             to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
         };
 
         let mismatches =
@@ -1,16 +1,25 @@
-use crate::core::resolver;
+use crate::config::loader;
+use crate::core::{
+    resolver,
+    types::{PackageId, SyncTarget},
+};
+use crate::error::{DeclarchError, Result};
+use crate::project_identity;
 use crate::state::types::State;
 use crate::ui as output;
 use chrono::Utc;
 use colored::Colorize;
 
-use super::SyncOptions;
+use super::{ManagerMap, SyncOptions};
 
 pub(super) fn warn_partial_upgrade_impl(
+    config: &loader::MergedConfig,
     state: &State,
     tx: &resolver::Transaction,
+    sync_target: &SyncTarget,
+    managers: &ManagerMap,
     options: &SyncOptions,
-) {
+) -> Result<()> {
     if !options.update && !tx.to_install.is_empty() {
         let should_warn = match state.meta.last_update {
             Some(last) => Utc::now().signed_duration_since(last).num_hours() > 24,
@@ -24,6 +33,27 @@ pub(super) fn warn_partial_upgrade_impl(
                 .map(|t| format!("{}h ago", Utc::now().signed_duration_since(t).num_hours()))
                 .unwrap_or("unknown".to_string());
 
+            let block_policy = config
+                .policy
+                .as_ref()
+                .and_then(|p| p.block_partial_upgrade)
+                .unwrap_or(false);
+            let risky = partial_upgrade_installs(tx, sync_target, managers);
+            if block_policy && !risky.is_empty() {
+                return Err(DeclarchError::ConfigError(format!(
+                    "Policy violation: block-partial-upgrade=true and a targeted sync would install {} without refreshing the package database (last system update: {}).\n\
+                     Run `{}` or a full `{}` first.",
+                    risky
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    time_str,
+                    project_identity::cli_with("sync --update"),
+                    project_identity::cli_with("sync")
+                )));
+            }
+
             output::separator();
             println!(
                 "{} Last system update: {}. Use {} to refresh.",
@@ -33,4 +63,93 @@ pub(super) fn warn_partial_upgrade_impl(
             );
         }
     }
+
+    Ok(())
+}
+
+/// Installs that a `--target` restricted sync would run on their own, for
+/// backends whose package database `sync --update` refreshes.
+fn partial_upgrade_installs<'a>(
+    tx: &'a resolver::Transaction,
+    sync_target: &SyncTarget,
+    managers: &ManagerMap,
+) -> Vec<&'a PackageId> {
+    if matches!(sync_target, SyncTarget::All) {
+        return Vec::new();
+    }
+    tx.to_install
+        .iter()
+        .filter(|pkg| {
+            managers
+                .get(&pkg.backend)
+                .is_some_and(|mgr| mgr.supports_update())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::sync::test_support::pkg;
+    use crate::core::types::{Backend, PackageMetadata};
+    use crate::packages::traits::PackageManager;
+    use std::collections::HashMap;
+
+    struct UpdatableManager {
+        backend: Backend,
+        updates: bool,
+    }
+
+    impl PackageManager for UpdatableManager {
+        fn backend_type(&self) -> Backend {
+            self.backend.clone()
+        }
+        fn list_installed(&self) -> Result<HashMap<String, PackageMetadata>> {
+            Ok(HashMap::new())
+        }
+        fn install(&self, _packages: &[String]) -> Result<()> {
+            Ok(())
+        }
+        fn remove(&self, _packages: &[String]) -> Result<()> {
+            Ok(())
+        }
+        fn is_available(&self) -> bool {
+            true
+        }
+        fn get_required_by(&self, _package: &str) -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+        fn supports_update(&self) -> bool {
+            self.updates
+        }
+    }
+
+    #[test]
+    fn partial_upgrade_installs_only_flags_targeted_installs_from_updatable_backends() {
+        let tx = resolver::Transaction {
+            to_install: vec![pkg("aur", "hyprland"), pkg("npm", "typescript")],
+            to_prune: Vec::new(),
+            to_adopt: Vec::new(),
+            to_update_project_metadata: Vec::new(),
+            install_reasons: HashMap::new(),
+        };
+        let mut managers: ManagerMap = HashMap::new();
+        for (backend, updates) in [("aur", true), ("npm", false)] {
+            managers.insert(
+                Backend::from(backend),
+                Box::new(UpdatableManager {
+                    backend: Backend::from(backend),
+                    updates,
+                }),
+            );
+        }
+
+        assert!(partial_upgrade_installs(&tx, &SyncTarget::All, &managers).is_empty());
+
+        let targeted = SyncTarget::Named("hyprland".to_string());
+        assert_eq!(
+            partial_upgrade_installs(&tx, &targeted, &managers),
+            vec![&pkg("aur", "hyprland")]
+        );
+    }
 }
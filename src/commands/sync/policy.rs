@@ -1,11 +1,18 @@
-use super::SyncOptions;
+use super::{ConflictChoices, SyncOptions};
 use crate::config::loader;
+use crate::constants::EXPERIMENTAL_ENABLE_HOOKS;
 use crate::error::Result;
 use crate::project_identity;
 use crate::ui as output;
 
 pub(super) fn resolve_hooks_enabled(config: &loader::MergedConfig, options: &SyncOptions) -> bool {
-    if !options.hooks {
+    let run_hooks_policy = config
+        .policy
+        .as_ref()
+        .and_then(|p| p.run_hooks)
+        .unwrap_or(false);
+    let requested = options.hooks || options.only_hooks.is_some() || run_hooks_policy;
+    if options.no_hooks || !requested {
         return false;
     }
 
@@ -19,7 +26,7 @@ pub(super) fn resolve_hooks_enabled(config: &loader::MergedConfig, options: &Syn
         return false;
     }
 
-    if config.is_experimental_enabled("enable-hooks") {
+    if config.is_experimental_enabled(EXPERIMENTAL_ENABLE_HOOKS) {
         return true;
     }
 
@@ -30,7 +37,11 @@ pub(super) fn resolve_hooks_enabled(config: &loader::MergedConfig, options: &Syn
     false
 }
 
-pub(super) fn enforce_sync_policy(config: &loader::MergedConfig) -> Result<()> {
+/// Conflicts resolved by `choices` no longer count against `on-conflict`.
+pub(super) fn enforce_sync_policy(
+    config: &loader::MergedConfig,
+    choices: &ConflictChoices,
+) -> Result<()> {
     let Some(policy) = config.policy.as_ref() else {
         return Ok(());
     };
@@ -63,7 +74,8 @@ pub(super) fn enforce_sync_policy(config: &loader::MergedConfig) -> Result<()> {
     }
 
     if policy.conflict_is_error() {
-        let conflicts = config.get_cross_backend_conflicts();
+        let mut conflicts = config.get_cross_backend_conflicts();
+        conflicts.retain(|(name, _)| !choices.contains_key(name));
         if !conflicts.is_empty() {
             return Err(crate::error::DeclarchError::ConfigError(format!(
                 "Policy violation: on-conflict=error and {} cross-backend conflict(s) were found",
@@ -74,3 +86,29 @@ pub(super) fn enforce_sync_policy(config: &loader::MergedConfig) -> Result<()> {
 
     Ok(())
 }
+
+/// Fill `yes`/`noconfirm` from policy where the CLI left them off.
+///
+/// `--confirm` opts this run out of both defaults. Returns whether policy
+/// turned on auto-confirm, so the caller can say so before anything runs.
+pub(super) fn apply_confirm_policy(
+    config: &loader::MergedConfig,
+    options: &mut SyncOptions,
+) -> bool {
+    let Some(policy) = config.policy.as_ref() else {
+        return false;
+    };
+    if options.confirm {
+        return false;
+    }
+
+    if policy.noconfirm.unwrap_or(false) {
+        options.noconfirm = true;
+    }
+
+    let auto_confirm = policy.auto_confirm.unwrap_or(false) && !options.yes;
+    if auto_confirm {
+        options.yes = true;
+    }
+    auto_confirm
+}
@@ -1,4 +1,6 @@
-use super::{InstalledSnapshot, SyncOptions, SyncPreviewReport};
+use super::{
+    InstalledSnapshot, PackageSizes, SyncFailure, SyncOptions, SyncPreviewReport, planner,
+};
 use crate::core::types::{PackageId, SyncTarget};
 use crate::project_identity;
 use crate::ui as output;
@@ -31,6 +33,7 @@ pub(super) fn build_sync_preview_report(
             .iter()
             .map(package_id_to_string)
             .collect(),
+        timings: None,
     }
 }
 
@@ -43,6 +46,11 @@ pub(super) fn sync_target_to_string(target: &SyncTarget) -> String {
         SyncTarget::All => "all".to_string(),
         SyncTarget::Backend(b) => format!("backend:{}", b),
         SyncTarget::Named(name) => format!("named:{}", name),
+        SyncTarget::Multiple(targets) => targets
+            .iter()
+            .map(sync_target_to_string)
+            .collect::<Vec<_>>()
+            .join(","),
     }
 }
 
@@ -50,6 +58,7 @@ pub(super) fn sync_target_to_string(target: &SyncTarget) -> String {
 pub(super) fn show_sync_diff(
     transaction: &crate::core::resolver::Transaction,
     installed_snapshot: &InstalledSnapshot,
+    install_sizes: &PackageSizes,
 ) {
     use colored::Colorize;
 
@@ -91,8 +100,24 @@ pub(super) fn show_sync_diff(
     let total_changes =
         transaction.to_install.len() + transaction.to_prune.len() + transaction.to_adopt.len();
     output::info(&format!("Total changes: {}", total_changes));
+    if let Some(summary) = planner::install_size_summary(transaction, install_sizes) {
+        output::info(&format!("Install size: {}", summary));
+    }
     output::info(&format!(
         "Run '{}' to apply these changes",
         project_identity::cli_with("sync")
     ));
 }
+
+/// Summarize failures collected under `--continue-on-error`.
+pub(super) fn show_sync_failures(failures: &[SyncFailure]) {
+    output::header(&format!("Sync Failures ({})", failures.len()));
+    for failure in failures {
+        output::error(&format!(
+            "{} [{}]: {}",
+            failure.backend,
+            failure.packages.join(", "),
+            failure.error
+        ));
+    }
+}
@@ -2,13 +2,15 @@
 //!
 //! Updates state.json with new package information.
 
-use super::{InstalledSnapshot, SyncOptions};
+use super::{InstalledSnapshot, PackageRemotes, SyncOptions};
+use crate::config::loader::MergedConfig;
 use crate::core::{
     resolver,
     types::{PackageId, PackageMetadata},
 };
 use crate::error::Result;
-use crate::state::types::{PackageState, State};
+use crate::state::history::HistoryEntry;
+use crate::state::types::{Backend, PackageBackendMeta, PackageState, State};
 use crate::ui;
 use chrono::Utc;
 use std::collections::HashSet;
@@ -24,6 +26,52 @@ pub fn update_state(
     update_state_with_success(state, transaction, installed_snapshot, options, &[])
 }
 
+/// Move entries recorded under a backend alias (`paru:bat`) to the primary
+/// backend (`aur:bat`), so they keep matching their canonicalized declaration.
+/// Returns the number of entries moved.
+pub fn canonicalize_state_backends(state: &mut State, config: &MergedConfig) -> usize {
+    if config.backend_aliases.is_empty() {
+        return 0;
+    }
+    let mut moved = 0;
+    for (key, mut entry) in std::mem::take(&mut state.packages) {
+        let backend = config.resolve_backend(&entry.backend);
+        if backend == entry.backend {
+            state.packages.insert(key, entry);
+            continue;
+        }
+        entry.backend = backend;
+        moved += 1;
+        let key = resolver::make_state_key(&PackageId {
+            name: entry.config_name.clone(),
+            backend: entry.backend.clone(),
+        });
+        // An entry already under the primary backend wins
+        state.packages.entry(key).or_insert(entry);
+    }
+    moved
+}
+
+/// Warn when distinct packages would be written to the same state key.
+///
+/// The later entry silently replaces the earlier one, so one of them stops
+/// being tracked. Returns the number of colliding keys.
+fn warn_state_key_collisions<'a>(packages: impl IntoIterator<Item = &'a PackageId>) -> usize {
+    let collisions = resolver::state_key_collisions(packages);
+    for (key, ids) in &collisions {
+        let names: Vec<String> = ids
+            .iter()
+            .map(|pkg| format!("{} (backend '{}')", pkg.name, pkg.backend))
+            .collect();
+        ui::warning(&format!(
+            "State key '{}' is shared by {}; only one of them will be tracked",
+            key,
+            names.join(" and ")
+        ));
+    }
+    collisions.len()
+}
+
 /// Update state with knowledge of which packages successfully installed
 pub fn update_state_with_success(
     state: &State,
@@ -46,6 +94,14 @@ pub fn update_state_with_success(
     let mut added_count = 0;
     let mut failed_count = 0;
 
+    let recorded: Vec<&PackageId> = transaction
+        .to_install
+        .iter()
+        .filter(|pkg| success_set.contains(pkg))
+        .chain(&transaction.to_adopt)
+        .collect();
+    warn_state_key_collisions(recorded);
+
     // Process to_install - only add successful ones
     for pkg in &transaction.to_install {
         if !success_set.contains(pkg) {
@@ -53,13 +109,14 @@ pub fn update_state_with_success(
             continue;
         }
 
-        let (version, actual_name) =
+        let (version, origin, actual_name) =
             if let Some((meta, name)) = find_package_info(pkg, installed_snapshot) {
-                (meta.version.clone(), name)
+                (meta.version.clone(), meta.origin.clone(), name)
             } else {
-                (None, None)
+                (None, None, None)
             };
         let key = resolver::make_state_key(pkg);
+        let backend_meta = backend_meta_for(origin, state.packages.get(&key));
 
         state.packages.insert(
             key,
@@ -72,8 +129,9 @@ pub fn update_state_with_success(
                 version,
                 install_reason: Some("declared".to_string()),
                 source_module: None,
+                pinned: false,
                 last_seen_at: Some(Utc::now()),
-                backend_meta: None,
+                backend_meta,
             },
         );
         added_count += 1;
@@ -81,13 +139,14 @@ pub fn update_state_with_success(
 
     // Process adoptions (these are already installed, so always add)
     for pkg in &transaction.to_adopt {
-        let (version, actual_name) =
+        let (version, origin, actual_name) =
             if let Some((meta, name)) = find_package_info(pkg, installed_snapshot) {
-                (meta.version.clone(), name)
+                (meta.version.clone(), meta.origin.clone(), name)
             } else {
-                (None, None)
+                (None, None, None)
             };
         let key = resolver::make_state_key(pkg);
+        let backend_meta = backend_meta_for(origin, state.packages.get(&key));
 
         state.packages.insert(
             key,
@@ -100,8 +159,9 @@ pub fn update_state_with_success(
                 version,
                 install_reason: Some("adopted".to_string()),
                 source_module: None,
+                pinned: false,
                 last_seen_at: Some(Utc::now()),
-                backend_meta: None,
+                backend_meta,
             },
         );
         added_count += 1;
@@ -127,6 +187,169 @@ pub fn update_state_with_success(
     Ok(state)
 }
 
+/// Backend details to store for a package: the remote reported by the backend,
+/// else the one already recorded.
+fn backend_meta_for(
+    origin: Option<String>,
+    previous: Option<&PackageState>,
+) -> Option<PackageBackendMeta> {
+    let remote = origin.or_else(|| previous?.remote().map(str::to_string))?;
+    Some(PackageBackendMeta {
+        remote: Some(remote),
+    })
+}
+
+/// Remotes recorded in state, keyed by the declared package they belong to.
+pub fn recorded_remotes(state: &State) -> PackageRemotes {
+    state
+        .packages
+        .values()
+        .filter_map(|pkg_state| {
+            let remote = pkg_state.remote()?;
+            let id = PackageId {
+                name: pkg_state.config_name.clone(),
+                backend: pkg_state.backend.clone(),
+            };
+            Some((id, remote.to_string()))
+        })
+        .collect()
+}
+
+/// Record the versions of tracked packages after their backends were upgraded.
+///
+/// Returns the number of packages whose recorded version changed.
+pub fn refresh_state_versions(
+    state: &mut State,
+    installed_snapshot: &InstalledSnapshot,
+    upgraded_backends: &[Backend],
+) -> usize {
+    let mut changed = 0;
+    for pkg_state in state.packages.values_mut() {
+        if !upgraded_backends.contains(&pkg_state.backend) {
+            continue;
+        }
+        let id = PackageId {
+            name: pkg_state
+                .actual_package_name
+                .clone()
+                .unwrap_or_else(|| pkg_state.provides_name.clone()),
+            backend: pkg_state.backend.clone(),
+        };
+        let Some(meta) = installed_snapshot.get(&id) else {
+            continue;
+        };
+        if meta.version.is_some() && meta.version != pkg_state.version {
+            pkg_state.version = meta.version.clone();
+            pkg_state.last_seen_at = Some(Utc::now());
+            changed += 1;
+        }
+    }
+    changed
+}
+
+/// A tracked package whose installed version changed during a sync
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionChange {
+    /// Name the package is declared and hooked under
+    pub package: String,
+    pub backend: Backend,
+    pub old_version: String,
+    pub new_version: String,
+}
+
+/// Tracked packages whose version differs between the snapshots taken before
+/// and after the sync ran, sorted by backend and name.
+///
+/// Packages without a version on either side are not compared.
+pub fn version_changes(
+    state: &State,
+    before: &InstalledSnapshot,
+    after: &InstalledSnapshot,
+) -> Vec<VersionChange> {
+    let mut changes: Vec<VersionChange> = state
+        .packages
+        .values()
+        .filter_map(|pkg_state| {
+            let id = PackageId {
+                name: pkg_state
+                    .actual_package_name
+                    .clone()
+                    .unwrap_or_else(|| pkg_state.provides_name.clone()),
+                backend: pkg_state.backend.clone(),
+            };
+            let old_version = before.get(&id)?.version.clone()?;
+            let new_version = after.get(&id)?.version.clone()?;
+            (old_version != new_version).then(|| VersionChange {
+                package: pkg_state.provides_name.clone(),
+                backend: pkg_state.backend.clone(),
+                old_version,
+                new_version,
+            })
+        })
+        .collect();
+    changes.sort_by(|a, b| (a.backend.name(), &a.package).cmp(&(b.backend.name(), &b.package)));
+    changes
+}
+
+/// Mirror `pinned=#true` from config onto the state entries of declared packages.
+///
+/// Entries for packages that are not declared (e.g. under an inactive profile)
+/// keep their recorded flag. Returns the number of entries whose flag changed.
+pub fn refresh_pinned_flags(state: &mut State, config: &MergedConfig) -> usize {
+    let mut changed = 0;
+    for declared in config.packages.keys() {
+        let pkg = resolver::resolve_package_alias(config, declared);
+        let Some(entry) = state.packages.get_mut(&resolver::make_state_key(&pkg)) else {
+            continue;
+        };
+        let pinned = config.pinned.contains(declared);
+        if entry.pinned != pinned {
+            entry.pinned = pinned;
+            changed += 1;
+        }
+    }
+    changed
+}
+
+/// History entry for a sync that went from `previous` to `saved`.
+///
+/// Only packages whose state entry actually appeared or disappeared are
+/// listed; returns `None` when the sync changed nothing.
+pub fn sync_history_entry(
+    previous: &State,
+    saved: &State,
+    transaction: &resolver::Transaction,
+    installed: &[PackageId],
+    target: &[String],
+) -> Option<HistoryEntry> {
+    let keys = |pkgs: &[PackageId], keep: &dyn Fn(&str) -> bool| -> Vec<String> {
+        pkgs.iter()
+            .map(resolver::make_state_key)
+            .filter(|key| keep(key))
+            .collect()
+    };
+    let installed = keys(installed, &|_| true);
+    let pruned = keys(&transaction.to_prune, &|key| {
+        previous.packages.contains_key(key) && !saved.packages.contains_key(key)
+    });
+    let adopted = keys(&transaction.to_adopt, &|key| {
+        !previous.packages.contains_key(key) && saved.packages.contains_key(key)
+    });
+
+    if installed.is_empty() && pruned.is_empty() && adopted.is_empty() {
+        return None;
+    }
+
+    Some(HistoryEntry {
+        timestamp: Utc::now(),
+        target: (!target.is_empty()).then(|| target.join(",")),
+        installed,
+        pruned,
+        adopted,
+        state: saved.clone(),
+    })
+}
+
 /// Find package info using smart matching
 /// Returns (metadata, actual_package_name) where actual_package_name is the
 /// real name from system (may differ from config name for variants)
@@ -147,3 +370,263 @@ fn find_package_info<'a>(
     // Return metadata + actual package name (variant)
     Some((meta, Some(matched_id.name.clone())))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracked_state(keys: &[&str]) -> State {
+        let mut state = State::default();
+        for key in keys {
+            let (backend, name) = key.split_once(':').unwrap();
+            state.packages.insert(
+                key.to_string(),
+                PackageState {
+                    backend: Backend::from(backend),
+                    config_name: name.to_string(),
+                    provides_name: name.to_string(),
+                    actual_package_name: None,
+                    installed_at: Utc::now(),
+                    version: None,
+                    install_reason: None,
+                    source_module: None,
+                    pinned: false,
+                    last_seen_at: None,
+                    backend_meta: None,
+                },
+            );
+        }
+        state
+    }
+
+    #[test]
+    fn history_entry_lists_only_applied_changes() {
+        let id = |backend: &str, name: &str| PackageId {
+            name: name.to_string(),
+            backend: Backend::from(backend),
+        };
+        let tx = resolver::Transaction {
+            to_install: vec![id("aur", "bat"), id("aur", "broken")],
+            to_prune: vec![id("aur", "htop"), id("aur", "kept")],
+            to_adopt: vec![id("npm", "tsx")],
+            to_update_project_metadata: Vec::new(),
+            install_reasons: std::collections::HashMap::new(),
+        };
+        let previous = tracked_state(&["aur:htop", "aur:kept"]);
+        let saved = tracked_state(&["aur:bat", "aur:kept", "npm:tsx"]);
+
+        let entry = sync_history_entry(
+            &previous,
+            &saved,
+            &tx,
+            &[id("aur", "bat")],
+            &["aur".to_string()],
+        )
+        .expect("sync changed state");
+        assert_eq!(entry.installed, vec!["aur:bat"]);
+        assert_eq!(entry.pruned, vec!["aur:htop"]);
+        assert_eq!(entry.adopted, vec!["npm:tsx"]);
+        assert_eq!(entry.target.as_deref(), Some("aur"));
+
+        assert!(sync_history_entry(&saved, &saved, &tx, &[], &[]).is_none());
+    }
+
+    #[test]
+    fn version_changes_compare_tracked_packages_across_snapshots() {
+        let mut state = tracked_state(&["aur:linux", "aur:bat", "aur:fd", "aur:new"]);
+        state
+            .packages
+            .get_mut("aur:bat")
+            .unwrap()
+            .actual_package_name = Some("bat-bin".to_string());
+        let snapshot = |versions: &[(&str, &str)]| -> InstalledSnapshot {
+            versions
+                .iter()
+                .map(|(name, version)| {
+                    (
+                        PackageId {
+                            name: name.to_string(),
+                            backend: Backend::from("aur"),
+                        },
+                        PackageMetadata {
+                            version: Some(version.to_string()),
+                            variant: None,
+                            installed_at: Utc::now(),
+                            source_file: None,
+                            install_size: None,
+                            origin: None,
+                        },
+                    )
+                })
+                .collect()
+        };
+        let before = snapshot(&[("linux", "6.9.1"), ("bat-bin", "0.24.0"), ("fd", "10.1")]);
+        let after = snapshot(&[
+            ("linux", "6.9.2"),
+            ("bat-bin", "0.25.0"),
+            ("fd", "10.1"),
+            ("new", "1.0"),
+        ]);
+
+        let changes = version_changes(&state, &before, &after);
+        assert_eq!(
+            changes,
+            vec![
+                VersionChange {
+                    package: "bat".to_string(),
+                    backend: Backend::from("aur"),
+                    old_version: "0.24.0".to_string(),
+                    new_version: "0.25.0".to_string(),
+                },
+                VersionChange {
+                    package: "linux".to_string(),
+                    backend: Backend::from("aur"),
+                    old_version: "6.9.1".to_string(),
+                    new_version: "6.9.2".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn colliding_state_keys_are_warned() {
+        let split_name = PackageId {
+            name: "b:c".to_string(),
+            backend: Backend::from("a"),
+        };
+        let split_backend = PackageId {
+            name: "c".to_string(),
+            backend: Backend::from("a:b"),
+        };
+        let unrelated = PackageId {
+            name: "bat".to_string(),
+            backend: Backend::from("aur"),
+        };
+
+        assert_eq!(
+            warn_state_key_collisions([&split_name, &split_backend, &unrelated]),
+            1
+        );
+        assert_eq!(warn_state_key_collisions([&split_name, &split_name]), 0);
+    }
+
+    #[test]
+    fn upgraded_backends_get_fresh_versions() {
+        let tracked = |backend: &str, name: &str| PackageState {
+            backend: Backend::from(backend),
+            config_name: name.to_string(),
+            provides_name: name.to_string(),
+            actual_package_name: None,
+            installed_at: Utc::now(),
+            version: Some("1.0".to_string()),
+            install_reason: Some("declared".to_string()),
+            source_module: None,
+            pinned: false,
+            last_seen_at: None,
+            backend_meta: None,
+        };
+        let mut state = State::default();
+        state
+            .packages
+            .insert("aur:bat".to_string(), tracked("aur", "bat"));
+        state
+            .packages
+            .insert("npm:tsx".to_string(), tracked("npm", "tsx"));
+
+        let mut snapshot = InstalledSnapshot::new();
+        for (backend, name) in [("aur", "bat"), ("npm", "tsx")] {
+            snapshot.insert(
+                PackageId {
+                    name: name.to_string(),
+                    backend: Backend::from(backend),
+                },
+                PackageMetadata {
+                    version: Some("2.0".to_string()),
+                    variant: None,
+                    installed_at: Utc::now(),
+                    source_file: None,
+                    install_size: None,
+                    origin: None,
+                },
+            );
+        }
+
+        let changed = refresh_state_versions(&mut state, &snapshot, &[Backend::from("aur")]);
+
+        assert_eq!(changed, 1);
+        assert_eq!(state.packages["aur:bat"].version.as_deref(), Some("2.0"));
+        assert_eq!(state.packages["npm:tsx"].version.as_deref(), Some("1.0"));
+    }
+
+    #[test]
+    fn remotes_are_recorded_and_kept_for_reinstalls() {
+        let mut state =
+            tracked_state(&["flatpak:org.mozilla.firefox", "flatpak:com.spotify.Client"]);
+        let firefox = state
+            .packages
+            .get_mut("flatpak:org.mozilla.firefox")
+            .unwrap();
+        firefox.backend_meta = backend_meta_for(Some("flathub-beta".to_string()), None);
+
+        // The list output no longer reports an origin: the recorded one stays
+        let kept = backend_meta_for(None, state.packages.get("flatpak:org.mozilla.firefox"));
+        assert_eq!(
+            kept.and_then(|meta| meta.remote).as_deref(),
+            Some("flathub-beta")
+        );
+        assert_eq!(backend_meta_for(None, None), None);
+
+        let remotes = recorded_remotes(&state);
+        assert_eq!(remotes.len(), 1);
+        assert_eq!(
+            remotes
+                .get(&PackageId {
+                    name: "org.mozilla.firefox".to_string(),
+                    backend: Backend::from("flatpak"),
+                })
+                .map(String::as_str),
+            Some("flathub-beta")
+        );
+    }
+
+    #[test]
+    fn pinned_flags_follow_declarations_only() {
+        let pkg = |name: &str| PackageId {
+            name: name.to_string(),
+            backend: Backend::from("aur"),
+        };
+        let mut state = State::default();
+        for (name, pinned) in [("neovim", false), ("bat", true), ("htop", true)] {
+            let mut entry = PackageState::from_config(name.to_string(), Backend::from("aur"), None);
+            entry.pinned = pinned;
+            state.packages.insert(format!("aur:{}", name), entry);
+        }
+
+        let mut config = MergedConfig::default();
+        config.packages.insert(pkg("neovim"), vec![]);
+        config.packages.insert(pkg("bat"), vec![]);
+        config.pinned.insert(pkg("neovim"));
+
+        assert_eq!(refresh_pinned_flags(&mut state, &config), 2);
+        assert!(state.packages["aur:neovim"].pinned);
+        assert!(!state.packages["aur:bat"].pinned);
+        // Undeclared here (e.g. only under another profile): flag is kept.
+        assert!(state.packages["aur:htop"].pinned);
+    }
+
+    #[test]
+    fn alias_state_entries_move_to_primary_backend() {
+        let mut state = tracked_state(&["paru:bat", "aur:fd", "paru:fd", "npm:ts"]);
+        let mut config = MergedConfig::default();
+        config
+            .backend_aliases
+            .insert("paru".to_string(), "aur".to_string());
+
+        assert_eq!(canonicalize_state_backends(&mut state, &config), 2);
+
+        let mut keys: Vec<&str> = state.packages.keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, vec!["aur:bat", "aur:fd", "npm:ts"]);
+        assert_eq!(state.packages["aur:bat"].backend, Backend::from("aur"));
+    }
+}
@@ -0,0 +1,208 @@
+//! Phase timings for `sync --stats`
+//!
+//! Phases are always timed (one clock read each); the breakdown is only
+//! printed or reported when `--stats` is set.
+
+use crate::core::types::Backend;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Time spent per backend within one phase
+#[derive(Debug, Default)]
+pub struct BackendTimings {
+    totals: HashMap<Backend, Duration>,
+    running: Option<(Backend, Instant)>,
+}
+
+impl BackendTimings {
+    /// Start timing `backend`, ending the lap of the previous one
+    pub fn lap(&mut self, backend: &Backend) {
+        self.stop();
+        self.running = Some((backend.clone(), Instant::now()));
+    }
+
+    /// End the running lap, if any
+    pub fn stop(&mut self) {
+        if let Some((backend, started)) = self.running.take() {
+            self.add(backend, started.elapsed());
+        }
+    }
+
+    pub fn add(&mut self, backend: Backend, elapsed: Duration) {
+        *self.totals.entry(backend).or_default() += elapsed;
+    }
+
+    /// Backends by name, with their total time
+    fn sorted(&self) -> Vec<(String, Duration)> {
+        let mut totals: Vec<(String, Duration)> = self
+            .totals
+            .iter()
+            .map(|(backend, elapsed)| (backend.to_string(), *elapsed))
+            .collect();
+        totals.sort();
+        totals
+    }
+}
+
+/// Phases of one sync, in the order they ran
+#[derive(Debug, Default)]
+pub(super) struct SyncStats {
+    phases: Vec<PhaseTiming>,
+}
+
+#[derive(Debug)]
+struct PhaseTiming {
+    name: &'static str,
+    elapsed: Duration,
+    backends: Vec<(String, Duration)>,
+}
+
+impl SyncStats {
+    /// Record phase `name` as having run from `started` until now
+    pub(super) fn record(&mut self, name: &'static str, started: Instant) {
+        self.record_with_backends(name, started, &BackendTimings::default());
+    }
+
+    /// Like `record`, keeping the per-backend split of the phase
+    pub(super) fn record_with_backends(
+        &mut self,
+        name: &'static str,
+        started: Instant,
+        backends: &BackendTimings,
+    ) {
+        self.phases.push(PhaseTiming {
+            name,
+            elapsed: started.elapsed(),
+            backends: backends.sorted(),
+        });
+    }
+
+    /// One-line breakdown, e.g. `config 0.2s, snapshot 3.1s (aur 2.9s, npm 0.2s), plan 0.1s`
+    pub(super) fn summary(&self) -> String {
+        self.phases
+            .iter()
+            .map(|phase| {
+                let mut part = format!("{} {}", phase.name, seconds(phase.elapsed));
+                if !phase.backends.is_empty() {
+                    let backends: Vec<String> = phase
+                        .backends
+                        .iter()
+                        .map(|(backend, elapsed)| format!("{} {}", backend, seconds(*elapsed)))
+                        .collect();
+                    part.push_str(&format!(" ({})", backends.join(", ")));
+                }
+                part
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn seconds(elapsed: Duration) -> String {
+    format!("{:.1}s", elapsed.as_secs_f64())
+}
+
+/// Seconds rounded to milliseconds, for machine output
+fn rounded_secs(elapsed: Duration) -> f64 {
+    (elapsed.as_secs_f64() * 1000.0).round() / 1000.0
+}
+
+/// `{"config": {"seconds": 0.2}, "snapshot": {"seconds": 3.1, "backends": {"aur": 2.9}}}`
+impl Serialize for SyncStats {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.phases.len()))?;
+        for phase in &self.phases {
+            map.serialize_entry(phase.name, &PhaseOut(phase))?;
+        }
+        map.end()
+    }
+}
+
+struct PhaseOut<'a>(&'a PhaseTiming);
+
+impl Serialize for PhaseOut<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let phase = self.0;
+        let len = if phase.backends.is_empty() { 1 } else { 2 };
+        let mut map = serializer.serialize_map(Some(len))?;
+        map.serialize_entry("seconds", &rounded_secs(phase.elapsed))?;
+        if !phase.backends.is_empty() {
+            let backends: Vec<(&str, f64)> = phase
+                .backends
+                .iter()
+                .map(|(backend, elapsed)| (backend.as_str(), rounded_secs(*elapsed)))
+                .collect();
+            map.serialize_entry("backends", &BackendsOut(&backends))?;
+        }
+        map.end()
+    }
+}
+
+struct BackendsOut<'a>(&'a [(&'a str, f64)]);
+
+impl Serialize for BackendsOut<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (backend, secs) in self.0 {
+            map.serialize_entry(backend, secs)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats() -> SyncStats {
+        let mut snapshot = BackendTimings::default();
+        snapshot.add(Backend::from("npm"), Duration::from_millis(200));
+        snapshot.add(Backend::from("aur"), Duration::from_millis(2900));
+        SyncStats {
+            phases: vec![
+                PhaseTiming {
+                    name: "config",
+                    elapsed: Duration::from_millis(210),
+                    backends: Vec::new(),
+                },
+                PhaseTiming {
+                    name: "snapshot",
+                    elapsed: Duration::from_millis(3100),
+                    backends: snapshot.sorted(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn summary_lists_phases_in_order_with_backend_split() {
+        assert_eq!(
+            stats().summary(),
+            "config 0.2s, snapshot 3.1s (aur 2.9s, npm 0.2s)"
+        );
+    }
+
+    #[test]
+    fn machine_timings_keep_phase_order() {
+        let json = serde_json::to_string(&stats()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"config":{"seconds":0.21},"snapshot":{"seconds":3.1,"backends":{"aur":2.9,"npm":0.2}}}"#
+        );
+    }
+
+    #[test]
+    fn laps_accumulate_per_backend() {
+        let mut timings = BackendTimings::default();
+        let aur = Backend::from("aur");
+        timings.lap(&aur);
+        timings.lap(&Backend::from("npm"));
+        timings.lap(&aur);
+        timings.stop();
+
+        let names: Vec<String> = timings.sorted().into_iter().map(|(b, _)| b).collect();
+        assert_eq!(names, vec!["aur", "npm"]);
+        assert!(timings.running.is_none());
+    }
+}
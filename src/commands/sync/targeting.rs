@@ -1,29 +1,98 @@
 use crate::config::loader;
 use crate::core::types::SyncTarget;
 use crate::state::types::Backend;
+use crate::utils::fuzzy;
 
-pub(super) fn resolve_target(target: &Option<String>, config: &loader::MergedConfig) -> SyncTarget {
-    if let Some(t) = target {
-        let normalized_backend = Backend::from(t.as_str());
-        let matches_backend_in_packages = config
-            .packages
-            .keys()
-            .any(|pkg_id| pkg_id.backend == normalized_backend);
-        let matches_backend_in_imports = config
-            .backends
-            .iter()
-            .any(|backend| backend.name.eq_ignore_ascii_case(t));
-
-        if matches_backend_in_packages || matches_backend_in_imports {
-            SyncTarget::Backend(normalized_backend)
-        } else {
-            SyncTarget::Named(t.clone())
+/// Outcome of resolving `--target` values
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum TargetResolution {
+    Resolved(SyncTarget),
+    /// `names` match both a backend and a declared package or module;
+    /// `target` reads them as backends, the non-interactive precedence.
+    Ambiguous {
+        target: SyncTarget,
+        names: Vec<String>,
+    },
+}
+
+/// Resolve `--target` values; each one is a backend or a package/module name.
+pub(super) fn resolve_target(
+    targets: &[String],
+    config: &loader::MergedConfig,
+) -> TargetResolution {
+    let target = match targets {
+        [] => SyncTarget::All,
+        [single] => resolve_single_target(single, config),
+        many => SyncTarget::Multiple(
+            many.iter()
+                .map(|t| resolve_single_target(t, config))
+                .collect(),
+        ),
+    };
+
+    let mut names: Vec<String> = targets
+        .iter()
+        .filter(|t| matches_backend(t, config) && named_target_exists(config, t))
+        .cloned()
+        .collect();
+    names.dedup();
+
+    if names.is_empty() {
+        TargetResolution::Resolved(target)
+    } else {
+        TargetResolution::Ambiguous { target, names }
+    }
+}
+
+/// Re-read ambiguous backend targets as package/module names where
+/// `prefers_package` says so.
+pub(super) fn reinterpret_ambiguous(
+    target: SyncTarget,
+    names: &[String],
+    prefers_package: &mut dyn FnMut(&str) -> bool,
+) -> SyncTarget {
+    match target {
+        SyncTarget::Backend(backend)
+            if names.iter().any(|n| n == backend.name()) && prefers_package(backend.name()) =>
+        {
+            SyncTarget::Named(backend.name().to_string())
         }
+        SyncTarget::Multiple(targets) => SyncTarget::Multiple(
+            targets
+                .into_iter()
+                .map(|t| reinterpret_ambiguous(t, names, prefers_package))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+fn resolve_single_target(t: &str, config: &loader::MergedConfig) -> SyncTarget {
+    if matches_backend(t, config) {
+        SyncTarget::Backend(config.resolve_backend(&Backend::from(t)))
     } else {
-        SyncTarget::All
+        SyncTarget::Named(t.to_string())
     }
 }
 
+fn matches_backend(t: &str, config: &loader::MergedConfig) -> bool {
+    let normalized_backend = Backend::from(t);
+    let matches_backend_in_packages = config
+        .packages
+        .keys()
+        .any(|pkg_id| pkg_id.backend == normalized_backend);
+    let matches_backend_in_imports = config
+        .backends
+        .iter()
+        .any(|backend| backend.name.eq_ignore_ascii_case(t));
+
+    matches_backend_in_packages
+        || matches_backend_in_imports
+        || config
+            .backend_aliases
+            .contains_key(normalized_backend.name())
+}
+
 pub(super) fn named_target_exists(config: &loader::MergedConfig, query: &str) -> bool {
     let query_lower = query.to_lowercase();
 
@@ -43,3 +112,102 @@ pub(super) fn named_target_exists(config: &loader::MergedConfig, query: &str) ->
 
     false
 }
+
+/// Declared package names, module stems and backend names close to `query`.
+pub(super) fn suggest_targets(config: &loader::MergedConfig, query: &str) -> Vec<String> {
+    let mut candidates: Vec<String> = Vec::new();
+    for (pkg_id, sources) in &config.packages {
+        candidates.push(pkg_id.name.clone());
+        candidates.push(pkg_id.backend.to_string());
+        candidates.extend(
+            sources
+                .iter()
+                .filter_map(|source| source.file_stem())
+                .map(|stem| stem.to_string_lossy().into_owned()),
+        );
+    }
+    candidates.extend(config.backends.iter().map(|backend| backend.name.clone()));
+
+    fuzzy::closest_matches(query, candidates.iter().map(String::as_str), 2, 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::PackageId;
+    use std::path::PathBuf;
+
+    #[test]
+    fn suggestions_cover_packages_modules_and_backends() {
+        let mut config = loader::MergedConfig::default();
+        for (backend, name, module) in [
+            ("aur", "neovim", "editors.kdl"),
+            ("flatpak", "org.gimp.GIMP", "media.kdl"),
+        ] {
+            config.packages.insert(
+                PackageId {
+                    name: name.to_string(),
+                    backend: Backend::from(backend),
+                },
+                vec![PathBuf::from(module)],
+            );
+        }
+
+        assert_eq!(suggest_targets(&config, "neovm"), vec!["neovim"]);
+        assert_eq!(suggest_targets(&config, "editor"), vec!["editors"]);
+        assert_eq!(suggest_targets(&config, "flatpack"), vec!["flatpak"]);
+        assert!(suggest_targets(&config, "steam").is_empty());
+    }
+
+    fn declare(config: &mut loader::MergedConfig, backend: &str, name: &str) {
+        config.packages.insert(
+            PackageId {
+                name: name.to_string(),
+                backend: Backend::from(backend),
+            },
+            vec![PathBuf::from("base.kdl")],
+        );
+    }
+
+    #[test]
+    fn target_naming_a_backend_and_a_package_is_ambiguous() {
+        let mut config = loader::MergedConfig::default();
+        declare(&mut config, "fd", "fd-tool");
+        declare(&mut config, "aur", "fd");
+
+        let resolution = resolve_target(&["fd".to_string(), "aur".to_string()], &config);
+        let TargetResolution::Ambiguous { target, names } = resolution else {
+            panic!("expected an ambiguous target, got {:?}", resolution);
+        };
+        assert_eq!(names, vec!["fd"]);
+        assert!(target.targets_backend(&Backend::from("fd")));
+
+        let picked = reinterpret_ambiguous(target.clone(), &names, &mut |_| true);
+        assert_eq!(
+            picked,
+            SyncTarget::Multiple(vec![
+                SyncTarget::Named("fd".to_string()),
+                SyncTarget::Backend(Backend::from("aur")),
+            ])
+        );
+        assert_eq!(
+            reinterpret_ambiguous(target.clone(), &names, &mut |_| false),
+            target
+        );
+    }
+
+    #[test]
+    fn plain_backend_or_package_targets_are_not_ambiguous() {
+        let mut config = loader::MergedConfig::default();
+        declare(&mut config, "aur", "bat");
+
+        assert_eq!(
+            resolve_target(&["aur".to_string()], &config),
+            TargetResolution::Resolved(SyncTarget::Backend(Backend::from("aur")))
+        );
+        assert_eq!(
+            resolve_target(&["bat".to_string()], &config),
+            TargetResolution::Resolved(SyncTarget::Named("bat".to_string()))
+        );
+    }
+}
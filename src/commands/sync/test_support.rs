@@ -0,0 +1,10 @@
+//! Fixtures shared by the sync tests
+
+use crate::core::types::{Backend, PackageId};
+
+pub(crate) fn pkg(backend: &str, name: &str) -> PackageId {
+    PackageId {
+        name: name.to_string(),
+        backend: Backend::from(backend),
+    }
+}
@@ -1,6 +1,7 @@
+use super::test_support::pkg;
 use super::*;
 use crate::backends::config::BackendConfig;
-use crate::core::types::PackageId;
+use crate::core::types::{Backend, PackageId};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
@@ -138,13 +139,42 @@ fn test_resolve_target_detects_backend_from_imports() {
         ..Default::default()
     });
 
-    let target = resolve_target(&Some("paru".to_string()), &merged);
+    let target = resolve_target(&["paru".to_string()], &merged);
     match target {
-        SyncTarget::Backend(b) => assert_eq!(b.name(), "paru"),
+        TargetResolution::Resolved(SyncTarget::Backend(b)) => assert_eq!(b.name(), "paru"),
         _ => panic!("expected backend target"),
     }
 }
 
+#[test]
+fn test_resolve_target_repeated_values_become_multiple() {
+    let mut merged = loader::MergedConfig::default();
+    merged.backends.push(BackendConfig {
+        name: "aur".to_string(),
+        ..Default::default()
+    });
+
+    assert_eq!(
+        resolve_target(&[], &merged),
+        TargetResolution::Resolved(SyncTarget::All)
+    );
+
+    let TargetResolution::Resolved(target) =
+        resolve_target(&["aur".to_string(), "bat".to_string()], &merged)
+    else {
+        panic!("expected an unambiguous target");
+    };
+    assert_eq!(
+        target,
+        SyncTarget::Multiple(vec![
+            SyncTarget::Backend(Backend::from("aur")),
+            SyncTarget::Named("bat".to_string()),
+        ])
+    );
+    assert!(target.targets_backend(&Backend::from("aur")));
+    assert_eq!(sync_target_to_string(&target), "backend:aur,named:bat");
+}
+
 #[test]
 fn test_named_target_exists_by_package_or_module_stem() {
     let mut merged = loader::MergedConfig::default();
@@ -161,6 +191,47 @@ fn test_named_target_exists_by_package_or_module_stem() {
     assert!(!named_target_exists(&merged, "unknown"));
 }
 
+#[test]
+fn test_apply_backend_package_sources_orders_by_priority_regardless_of_merge_order() {
+    let apply = |declared: &[&str]| {
+        let mut backend = BackendConfig {
+            name: "apt".to_string(),
+            ..Default::default()
+        };
+        let mut merged = loader::MergedConfig::default();
+        merged.package_sources.insert(
+            "apt".to_string(),
+            declared.iter().map(ToString::to_string).collect(),
+        );
+        merged.package_source_priorities.insert(
+            "apt".to_string(),
+            HashMap::from([
+                ("backports".to_string(), 2),
+                ("main".to_string(), 1),
+                ("security".to_string(), 1),
+            ]),
+        );
+        apply_backend_package_sources(&mut backend, "apt", &merged);
+        backend.package_sources.expect("sources applied")
+    };
+
+    let expected = vec![
+        "main".to_string(),
+        "security".to_string(),
+        "backports".to_string(),
+        "contrib".to_string(),
+        "non-free".to_string(),
+    ];
+    assert_eq!(
+        apply(&["contrib", "backports", "security", "non-free", "main"]),
+        expected
+    );
+    assert_eq!(
+        apply(&["main", "contrib", "security", "backports", "non-free"]),
+        expected
+    );
+}
+
 #[test]
 fn test_apply_backend_package_sources_normalizes_and_dedupes() {
     let mut backend = BackendConfig {
@@ -190,3 +261,156 @@ fn test_apply_backend_package_sources_normalizes_and_dedupes() {
         ])
     );
 }
+
+#[test]
+fn test_install_sizes_are_only_estimated_for_views_that_show_them() {
+    let mut options = SyncOptions::default();
+    assert!(shows_install_sizes(&options, false));
+    assert!(!shows_install_sizes(&options, true));
+
+    options.dry_run = true;
+    assert!(!shows_install_sizes(&options, false));
+
+    options.diff = true;
+    assert!(shows_install_sizes(&options, false));
+}
+
+fn config_with_confirm_policy() -> loader::MergedConfig {
+    loader::MergedConfig {
+        policy: Some(crate::config::kdl::PolicyConfig {
+            noconfirm: Some(true),
+            auto_confirm: Some(true),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_confirm_policy_applies_when_cli_is_silent() {
+    let config = config_with_confirm_policy();
+    let mut options = SyncOptions::default();
+
+    assert!(policy::apply_confirm_policy(&config, &mut options));
+    assert!(options.yes);
+    assert!(options.noconfirm);
+}
+
+#[test]
+fn test_confirm_flag_overrides_policy() {
+    let config = config_with_confirm_policy();
+    let mut options = SyncOptions {
+        confirm: true,
+        ..Default::default()
+    };
+
+    assert!(!policy::apply_confirm_policy(&config, &mut options));
+    assert!(!options.yes);
+    assert!(!options.noconfirm);
+}
+
+#[test]
+fn test_confirm_policy_is_silent_when_cli_already_says_yes() {
+    let config = config_with_confirm_policy();
+    let mut options = SyncOptions {
+        yes: true,
+        ..Default::default()
+    };
+
+    assert!(!policy::apply_confirm_policy(&config, &mut options));
+    assert!(options.yes);
+}
+
+#[test]
+fn test_config_dump_groups_packages_by_backend_with_sources() {
+    let mut config = loader::MergedConfig::default();
+    for (backend, name, source) in [
+        ("aur", "fd", "modules/base.kdl"),
+        ("aur", "bat", "modules/base.kdl"),
+        ("npm", "typescript", "modules/dev.kdl"),
+    ] {
+        config.packages.insert(
+            PackageId {
+                name: name.to_string(),
+                backend: Backend::from(backend),
+            },
+            vec![PathBuf::from(source)],
+        );
+    }
+    config.excludes = vec!["vim".to_string(), "nano".to_string(), "vim".to_string()];
+    config
+        .package_aliases
+        .insert("python".to_string(), "python3".to_string());
+
+    let options = SyncOptions {
+        profiles: vec!["desktop".to_string(), "dev".to_string()],
+        ..Default::default()
+    };
+    let dump = config_dump::build_config_dump(&config, &options);
+
+    let aur: Vec<_> = dump.packages["aur"]
+        .iter()
+        .map(|p| p.name.as_str())
+        .collect();
+    assert_eq!(aur, vec!["bat", "fd"]);
+    assert_eq!(
+        dump.packages["npm"][0].sources,
+        vec![PathBuf::from("modules/dev.kdl")]
+    );
+    assert_eq!(dump.excludes, vec!["nano".to_string(), "vim".to_string()]);
+    assert_eq!(
+        dump.package_aliases.get("python").map(String::as_str),
+        Some("python3")
+    );
+    assert_eq!(dump.profiles, vec!["desktop", "dev"]);
+    assert!(toml::to_string_pretty(&dump).is_ok());
+}
+
+#[test]
+fn check_only_reports_pending_changes() {
+    let pkg = PackageId {
+        name: "bat".to_string(),
+        backend: Backend::from("aur"),
+    };
+    let mut transaction = crate::core::resolver::Transaction {
+        to_install: Vec::new(),
+        to_prune: vec![pkg.clone()],
+        to_adopt: Vec::new(),
+        to_update_project_metadata: Vec::new(),
+        install_reasons: HashMap::new(),
+    };
+    let mut options = SyncOptions {
+        check_only: true,
+        ..Default::default()
+    };
+
+    assert!(ensure_nothing_pending(&options, &transaction).is_ok());
+
+    options.prune = true;
+    let err = ensure_nothing_pending(&options, &transaction).expect_err("prune is pending");
+    assert_eq!(err.exit_code(), 7);
+
+    transaction.to_prune.clear();
+    transaction.to_install.push(pkg);
+    options.check_only = false;
+    assert!(ensure_nothing_pending(&options, &transaction).is_ok());
+}
+
+#[test]
+fn outcome_change_counts_cover_only_operations_that_ran() {
+    let outcome = TransactionOutcome {
+        installed: vec![pkg("aur", "bat"), pkg("aur", "fd")],
+        pruned: vec![pkg("npm", "typescript")],
+        failures: vec![SyncFailure {
+            backend: Backend::from("flatpak"),
+            packages: vec!["org.gimp.GIMP".to_string()],
+            error: "remote not found".to_string(),
+        }],
+        ..Default::default()
+    };
+
+    let counts = outcome.backend_change_counts();
+    assert_eq!(counts.get(&Backend::from("aur")), Some(&2));
+    assert_eq!(counts.get(&Backend::from("npm")), Some(&1));
+    assert!(!counts.contains_key(&Backend::from("flatpak")));
+}
@@ -0,0 +1,31 @@
+//! Unlock command
+//!
+//! Removes a state lock left behind by a crashed run. The lock is only
+//! removed when the process recorded in it is no longer alive.
+
+use crate::error::Result;
+use crate::state::{self, io::UnlockOutcome};
+use crate::ui as output;
+
+pub fn run() -> Result<()> {
+    report(state::io::force_unlock()?);
+    Ok(())
+}
+
+/// Remove a stale lock before running a command (`--force-unlock`).
+pub fn force_before_command() -> Result<()> {
+    if let UnlockOutcome::Removed(owner) = state::io::force_unlock()? {
+        report(UnlockOutcome::Removed(owner));
+    }
+    Ok(())
+}
+
+fn report(outcome: UnlockOutcome) {
+    match outcome {
+        UnlockOutcome::NoLock => output::info("No lock file present"),
+        UnlockOutcome::Removed(Some(owner)) => {
+            output::success(&format!("Removed stale lock left by {}", owner.describe()))
+        }
+        UnlockOutcome::Removed(None) => output::success("Removed stale lock"),
+    }
+}
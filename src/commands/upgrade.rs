@@ -12,6 +12,7 @@ use crate::error::Result;
 use crate::packages::traits::PackageManager;
 use crate::project_identity;
 use crate::ui as output;
+use crate::utils::remote;
 use std::collections::HashSet;
 
 pub struct UpgradeOptions {
@@ -21,10 +22,16 @@ pub struct UpgradeOptions {
     pub no_sync: bool,
     /// Verbose output
     pub verbose: bool,
+    /// Run upgrades without escalation (`--no-sudo`)
+    pub no_sudo: bool,
+    /// Capture upgrade output, showing it only on failure (`--quiet-backends`)
+    pub quiet_backends: bool,
 }
 
 /// Run upgrade for configured backends
 pub fn run(options: UpgradeOptions) -> Result<()> {
+    remote::ensure_online("Upgrading packages")?;
+
     output::separator();
     output::info("Loading backend configurations...");
 
@@ -94,12 +101,17 @@ pub fn run(options: UpgradeOptions) -> Result<()> {
             continue;
         }
 
-        let manager: Box<dyn PackageManager> =
-            Box::new(crate::backends::GenericManager::from_config(
+        let manager: Box<dyn PackageManager> = Box::new(
+            crate::backends::GenericManager::from_config(
                 config,
                 Backend::from(name.as_str()),
                 false,
-            ));
+            )
+            .with_escalation(runtime_config.escalation_program())
+            .with_no_sudo(options.no_sudo)
+            .with_quiet_output(options.quiet_backends)
+            .with_echo_commands(options.verbose),
+        );
         if manager.is_available() && manager.supports_upgrade() {
             upgradable_backends.push((name, manager));
         } else if !manager.is_available() {
@@ -165,21 +177,11 @@ pub fn run(options: UpgradeOptions) -> Result<()> {
 
         // Run sync with adopt mode to update state with new versions
         crate::commands::sync::run(crate::commands::sync::SyncOptions {
-            dry_run: false,
-            prune: false,
-            update: false,
             verbose: options.verbose,
             yes: true, // Auto-yes since we just did upgrade
-            force: false,
-            target: None,
-            noconfirm: false,
-            hooks: false,
-            profile: None,
-            host: None,
-            modules: Vec::new(),
-            diff: false,
-            format: None,
-            output_version: None,
+            no_sudo: options.no_sudo,
+            quiet_backends: options.quiet_backends,
+            ..Default::default()
         })?;
 
         output::separator();
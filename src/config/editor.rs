@@ -1,7 +1,8 @@
 //! Config Editor Module
 //!
 //! This module provides functionality to programmatically edit KDL configuration files.
-//! It's used by the `install` command to add packages to config files.
+//! It's used by the `install` command to add packages to config files, and by
+//! `lint --fix` to drop duplicate declarations.
 
 mod backup_ops;
 mod default_backend;
@@ -12,7 +13,7 @@ mod path_ops;
 use crate::error::{DeclarchError, Result};
 pub use backup_ops::{backup_kdl_file, restore_from_backup};
 use default_backend::detect_default_backend;
-use kdl::{KdlDocument, KdlNode};
+use kdl::KdlDocument;
 #[cfg(test)]
 use package_spec::is_valid_backend;
 pub use package_spec::parse_package_string;
@@ -120,8 +121,6 @@ impl ConfigEditor {
         package: &str,
         backend: Option<&str>,
     ) -> Result<(String, Vec<String>)> {
-        use std::iter::Iterator;
-
         // Parse existing content to AST
         let mut doc: KdlDocument = content
             .parse()
@@ -131,77 +130,178 @@ impl ConfigEditor {
         let backend_name = backend.unwrap_or_else(|| detect_default_backend());
 
         // Structure: pkg { backend { package } }
-        // Step 1: Find or create 'pkg' node
-        let pkg_node_idx = doc.nodes().iter().position(|n| n.name().value() == "pkg");
-
-        let pkg_idx = if let Some(idx) = pkg_node_idx {
-            idx
-        } else {
-            // Create pkg node
-            let mut new_pkg = KdlNode::new("pkg");
-            new_pkg.set_children(KdlDocument::new());
-            doc.nodes_mut().push(new_pkg);
-            doc.nodes().len() - 1
+        // New nodes are spliced into the parsed document so comments and
+        // formatting elsewhere in the file survive the edit.
+        let Some(pkg_idx) = doc.nodes().iter().position(|n| n.name().value() == "pkg") else {
+            formatting::append_top_level_node(
+                &mut doc,
+                &format!("pkg {{\n  {} {{\n    {}\n  }}\n}}", backend_name, package),
+            )?;
+            return Ok((doc.to_string(), vec![package.to_string()]));
         };
 
-        // Step 2: Find or create backend node inside pkg
         let pkg_node = &mut doc.nodes_mut()[pkg_idx];
-        let backend_node_idx = if let Some(children) = pkg_node.children() {
-            children
-                .nodes()
-                .iter()
-                .position(|n| n.name().value() == backend_name)
-        } else {
-            None
+        let pkg_indent = formatting::indent_of(pkg_node).unwrap_or_default();
+        if pkg_node.children().is_none()
+            && let Some(format) = pkg_node.format_mut()
+        {
+            format.before_children = " ".to_string();
+        }
+        let pkg_children = pkg_node.ensure_children();
+        // Reuse the file's indentation step when a backend block already exists
+        let step = pkg_children
+            .nodes()
+            .first()
+            .and_then(formatting::indent_of)
+            .and_then(|indent| indent.strip_prefix(&pkg_indent).map(str::to_string))
+            .filter(|step| !step.is_empty())
+            .unwrap_or_else(|| formatting::INDENT.to_string());
+        let backend_indent = format!("{}{}", pkg_indent, step);
+
+        let Some(backend_node) = pkg_children
+            .nodes_mut()
+            .iter_mut()
+            .find(|n| n.name().value() == backend_name)
+        else {
+            formatting::append_node(
+                pkg_children,
+                &format!("{} {{\n{}{}\n}}", backend_name, step, package),
+                &backend_indent,
+                &pkg_indent,
+            )?;
+            return Ok((doc.to_string(), vec![package.to_string()]));
         };
 
-        if let Some(backend_idx) = backend_node_idx {
-            // Backend node exists, check if package already exists
-            if let Some(children) = pkg_node.children()
-                && let Some(backend_node) = children.nodes().get(backend_idx)
-                && let Some(backend_children) = backend_node.children()
-            {
-                for child in backend_children.nodes() {
-                    if child.name().value() == package {
-                        // Already exists, return unchanged
-                        return Ok((content.to_string(), Vec::new()));
-                    }
-                }
-            }
+        if backend_node
+            .children()
+            .is_some_and(|children| children.nodes().iter().any(|n| n.name().value() == package))
+        {
+            // Already exists, return unchanged
+            return Ok((content.to_string(), Vec::new()));
+        }
+
+        let backend_indent = formatting::indent_of(backend_node).unwrap_or(backend_indent);
+        if backend_node.children().is_none()
+            && let Some(format) = backend_node.format_mut()
+        {
+            format.before_children = " ".to_string();
+        }
+        formatting::append_node(
+            backend_node.ensure_children(),
+            package,
+            &format!("{}{}", backend_indent, step),
+            &backend_indent,
+        )?;
+        let updated_content = doc.to_string();
+
+        Ok((updated_content, vec![package.to_string()]))
+    }
+
+    /// Remove every declaration of `backend:package` from KDL content
+    ///
+    /// Handles `pkg { backend { package } }`, `pkg { backend:package }` and
+    /// `pkg:backend { package }`. The rest of the file is left byte-for-byte
+    /// intact. Returns `None` when the content has no such declaration.
+    pub fn remove_package_from_content(
+        &self,
+        content: &str,
+        package: &str,
+        backend: &str,
+    ) -> Result<Option<String>> {
+        let spans = package_spans(content, package, backend)?;
+        if spans.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(remove_spans(content, spans)))
+    }
+
+    /// Remove all but the first declaration of `backend:package` from KDL content
+    ///
+    /// Same forms as [`Self::remove_package_from_content`]. Returns `None`
+    /// when the package is declared at most once.
+    pub fn remove_repeated_package_from_content(
+        &self,
+        content: &str,
+        package: &str,
+        backend: &str,
+    ) -> Result<Option<String>> {
+        let mut spans = package_spans(content, package, backend)?;
+        if spans.len() < 2 {
+            return Ok(None);
+        }
+        spans.sort_by_key(|(start, _)| *start);
+        spans.remove(0);
+        Ok(Some(remove_spans(content, spans)))
+    }
+}
+
+/// Byte ranges of every declaration of `backend:package` in KDL content.
+fn package_spans(content: &str, package: &str, backend: &str) -> Result<Vec<(usize, usize)>> {
+    let doc: KdlDocument = content
+        .parse()
+        .map_err(|e| DeclarchError::Other(format!("KDL parsing error: {}", e)))?;
+
+    let inline_name = format!("{}:{}", backend, package);
+    let block_name = format!("pkg:{}", backend);
+    let mut spans = Vec::new();
 
-            // Add package to existing backend node
-            if let Some(children) = pkg_node.children_mut() {
-                let backend_node = &mut children.nodes_mut()[backend_idx];
-                let package_node = KdlNode::new(package);
-
-                if let Some(backend_children) = backend_node.children_mut() {
-                    backend_children.nodes_mut().push(package_node);
-                } else {
-                    let mut backend_children_doc = KdlDocument::new();
-                    backend_children_doc.nodes_mut().push(package_node);
-                    backend_node.set_children(backend_children_doc);
+    for node in doc.nodes() {
+        let name = node.name().value();
+        let Some(children) = node.children() else {
+            continue;
+        };
+
+        if name == block_name {
+            collect_named_spans(children, package, &mut spans);
+        } else if name == "pkg" {
+            collect_named_spans(children, &inline_name, &mut spans);
+            for child in children.nodes() {
+                if child.name().value() == backend
+                    && let Some(backend_children) = child.children()
+                {
+                    collect_named_spans(backend_children, package, &mut spans);
                 }
             }
-        } else {
-            // Create new backend node with package
-            let mut backend_node = KdlNode::new(backend_name);
-            let mut backend_children_doc = KdlDocument::new();
-            backend_children_doc.nodes_mut().push(KdlNode::new(package));
-            backend_node.set_children(backend_children_doc);
-
-            if let Some(children) = pkg_node.children_mut() {
-                children.nodes_mut().push(backend_node);
-            } else {
-                let mut children_doc = KdlDocument::new();
-                children_doc.nodes_mut().push(backend_node);
-                pkg_node.set_children(children_doc);
-            }
         }
+    }
+    Ok(spans)
+}
 
-        // Generate KDL from modified AST and normalize formatting.
-        let updated_content = formatting::normalize_kdl_output(doc.to_string())?;
+/// Cut `spans` out of `content`, with their surrounding line where it empties.
+fn remove_spans(content: &str, mut spans: Vec<(usize, usize)>) -> String {
+    // Splice from the end so earlier offsets stay valid
+    spans.sort_by_key(|(start, _)| std::cmp::Reverse(*start));
+    let mut updated = content.to_string();
+    for (start, end) in spans {
+        let (start, end) = removal_range(&updated, start, end);
+        updated.replace_range(start..end, "");
+    }
+    updated
+}
 
-        Ok((updated_content, vec![package.to_string()]))
+/// Byte ranges of child nodes named `name`.
+fn collect_named_spans(doc: &KdlDocument, name: &str, spans: &mut Vec<(usize, usize)>) {
+    for node in doc.nodes() {
+        if node.name().value() == name {
+            let span = node.span();
+            spans.push((span.offset(), span.offset() + span.len()));
+        }
+    }
+}
+
+/// Widen a node span to its whole line when nothing else shares that line.
+fn removal_range(content: &str, start: usize, end: usize) -> (usize, usize) {
+    let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = content[end..]
+        .find('\n')
+        .map_or(content.len(), |i| end + i + 1);
+
+    let before = &content[line_start..start];
+    let after = content[end..line_end].trim();
+    if before.trim().is_empty() && (after.is_empty() || after == ";") {
+        (line_start, line_end)
+    } else {
+        (start, end)
     }
 }
 
@@ -1,57 +1,117 @@
-use crate::error::{DeclarchError, Result};
-
-pub(super) fn normalize_kdl_output(mut content: String) -> Result<String> {
-    content = content.replace("pkg{", "pkg {");
+//! Format-preserving insertion into a parsed KDL document
+//!
+//! New nodes are parsed from indented text so they carry their own
+//! whitespace; comments, blank lines and indentation already in the
+//! document are serialized back untouched.
 
-    let backend_block_re = regex::Regex::new(r"(?m)^([a-zA-Z][a-zA-Z0-9_-]*)\s*\r?\n\s*\{")
-        .map_err(|e| DeclarchError::ConfigError(format!("Invalid regex: {}", e)))?;
+use crate::error::{DeclarchError, Result};
+use kdl::{KdlDocument, KdlDocumentFormat, KdlNode};
 
-    content = backend_block_re
-        .replace_all(&content, |caps: &regex::Captures| {
-            format!("{} {{", &caps[1])
-        })
-        .to_string();
+/// One level of indentation for generated nodes.
+pub(super) const INDENT: &str = "  ";
 
-    content = content
-        .replace("}pkg", "}\npkg")
-        .replace("}meta", "}\nmeta")
-        .replace("}imports", "}\nimports")
-        .replace("}hooks", "}\nhooks");
+/// Indentation of `node`: the whitespace after the last newline of its leading trivia.
+pub(super) fn indent_of(node: &KdlNode) -> Option<String> {
+    let leading = &node.format()?.leading;
+    let indent = leading.rsplit('\n').next().unwrap_or_default();
+    indent
+        .chars()
+        .all(|c| c == ' ' || c == '\t')
+        .then(|| indent.to_string())
+}
 
-    let lines: Vec<&str> = content.lines().collect();
-    let mut formatted_lines = Vec::new();
-    let mut in_packages_block = false;
+/// Append `source` (a node written at column 0) as the last node of `doc`.
+///
+/// The new node is indented like its last sibling, falling back to
+/// `indent`. `closing_indent` is placed before the enclosing `}` when the
+/// block was previously empty or written on one line.
+pub(super) fn append_node(
+    doc: &mut KdlDocument,
+    source: &str,
+    indent: &str,
+    closing_indent: &str,
+) -> Result<()> {
+    let indent = doc
+        .nodes()
+        .last()
+        .and_then(indent_of)
+        .unwrap_or_else(|| indent.to_string());
+    let node = parse_node(source, &indent)?;
 
-    for line in lines {
-        let trimmed = line.trim();
+    // The new node must start on its own line
+    if let Some(last) = doc.nodes_mut().last_mut()
+        && !last.to_string().ends_with('\n')
+        && let Some(format) = last.format_mut()
+    {
+        format.trailing.push('\n');
+    }
 
-        if trimmed.starts_with("pkg") && trimmed.contains('{') {
-            in_packages_block = true;
-            if line.contains("//") {
-                formatted_lines.push(line.to_string());
-            } else {
-                formatted_lines.push(trimmed.to_string());
-            }
-            continue;
+    let was_empty = doc.nodes().is_empty();
+    if doc.format().is_none() {
+        doc.set_format(KdlDocumentFormat::default());
+    }
+    if let Some(format) = doc.format_mut() {
+        if was_empty && doc_is_blank(&format.leading) && !format.leading.contains('\n') {
+            format.leading = "\n".to_string();
         }
-
-        if trimmed == "}" {
-            in_packages_block = false;
-            formatted_lines.push(trimmed.to_string());
-            continue;
+        if doc_is_blank(&format.trailing) && !format.trailing.contains('\n') {
+            format.trailing = closing_indent.to_string();
         }
+    }
 
-        if in_packages_block && !trimmed.is_empty() {
-            formatted_lines.push(format!("  {}", trimmed));
-            continue;
-        }
+    doc.nodes_mut().push(node);
+    Ok(())
+}
 
-        if !trimmed.is_empty() {
-            formatted_lines.push(trimmed.to_string());
-        } else {
-            formatted_lines.push(String::new());
-        }
+/// Like [`append_node`] for the top level of a file, separated by a blank line.
+pub(super) fn append_top_level_node(doc: &mut KdlDocument, source: &str) -> Result<()> {
+    let separated = if doc.nodes().is_empty() {
+        source.to_string()
+    } else {
+        format!("\n{}", source)
+    };
+    let node = parse_node(&separated, "")?;
+
+    if let Some(last) = doc.nodes_mut().last_mut()
+        && !last.to_string().ends_with('\n')
+        && let Some(format) = last.format_mut()
+    {
+        format.trailing.push('\n');
     }
 
-    Ok(formatted_lines.join("\n"))
+    doc.nodes_mut().push(node);
+    Ok(())
+}
+
+fn doc_is_blank(trivia: &str) -> bool {
+    trivia.chars().all(char::is_whitespace)
+}
+
+/// Parse `source` as a single node with every line prefixed by `indent`.
+fn parse_node(source: &str, indent: &str) -> Result<KdlNode> {
+    let text: String = source
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                "\n".to_string()
+            } else {
+                format!("{}{}\n", indent, line)
+            }
+        })
+        .collect();
+    let doc: KdlDocument = text
+        .parse()
+        .map_err(|e| DeclarchError::Other(format!("KDL parsing error: {}", e)))?;
+    let leading = doc.format().map(|f| f.leading.clone()).unwrap_or_default();
+    let mut node = doc
+        .nodes()
+        .first()
+        .cloned()
+        .ok_or_else(|| DeclarchError::Other(format!("Not a KDL node: {}", source)))?;
+
+    // Whitespace before the first node may be attributed to the document
+    if let Some(format) = node.format_mut() {
+        format.leading = format!("{}{}", leading, format.leading);
+    }
+    Ok(node)
 }
@@ -153,6 +153,38 @@ fn test_add_to_default_pkg_block() {
     assert!(updated.contains("pkg {"));
 }
 
+#[test]
+fn test_add_package_preserves_comments_and_blank_lines() {
+    let editor = ConfigEditor::new();
+    let content = "// Workstation packages\n\nmeta {\n  title \"desk\"\n}\n\npkg {\n  // CLI tools\n  soar {\n    vim // editor\n\n    fd\n  }\n}\n// end of file\n";
+
+    let (updated, added) = editor
+        .add_package_to_content(content, "bat", Some("soar"))
+        .expect("add package");
+
+    assert_eq!(added, vec!["bat"]);
+    assert_eq!(
+        updated,
+        "// Workstation packages\n\nmeta {\n  title \"desk\"\n}\n\npkg {\n  // CLI tools\n  soar {\n    vim // editor\n\n    fd\n    bat\n  }\n}\n// end of file\n"
+    );
+}
+
+#[test]
+fn test_add_backend_block_keeps_existing_layout() {
+    let editor = ConfigEditor::new();
+    let content = "pkg {\n    soar {\n        vim\n    }\n}\n\n// notes: keep this\n";
+
+    let (updated, _) = editor
+        .add_package_to_content(content, "ripgrep", Some("aur"))
+        .expect("add package");
+
+    assert_eq!(
+        updated,
+        "pkg {\n    soar {\n        vim\n    }\n    aur {\n        ripgrep\n    }\n}\n\n// notes: keep this\n"
+    );
+    assert!(updated.parse::<kdl::KdlDocument>().is_ok());
+}
+
 #[test]
 fn test_add_multiple_backends() {
     let editor = ConfigEditor::new();
@@ -177,3 +209,29 @@ fn test_add_multiple_backends() {
     assert!(updated.contains("bat"));
     assert!(updated.contains("fd"));
 }
+
+#[test]
+fn test_remove_package_from_all_declaration_forms() {
+    let editor = ConfigEditor::new();
+    let content = "pkg {\n  aur {\n    bat\n    fd\n  }\n  aur:bat\n}\npkg:aur {\n  bat\n}\n";
+
+    let updated = editor
+        .remove_package_from_content(content, "bat", "aur")
+        .unwrap()
+        .expect("bat is declared");
+
+    assert!(!updated.contains("bat"));
+    assert!(updated.contains("fd"));
+    assert!(updated.parse::<KdlDocument>().is_ok());
+}
+
+#[test]
+fn test_remove_package_leaves_other_backends_untouched() {
+    let editor = ConfigEditor::new();
+    let content = "pkg {\n  npm {\n    bat\n  }\n}\n";
+
+    let result = editor
+        .remove_package_from_content(content, "bat", "aur")
+        .unwrap();
+    assert!(result.is_none());
+}
@@ -1,5 +1,5 @@
 use crate::config::kdl_modules::types::{
-    ActionType, ErrorBehavior, LifecycleAction, LifecycleConfig, LifecyclePhase,
+    ActionCondition, ActionType, ErrorBehavior, LifecycleAction, LifecycleConfig, LifecyclePhase,
 };
 use crate::error::{DeclarchError, Result};
 use kdl::KdlNode;
@@ -10,6 +10,13 @@ use kdl::KdlNode;
 /// 1. Global hooks: pre-sync "command"
 /// 2. Package hooks (block): docker { post-install "command" --sudo }
 /// 3. Package hooks (shorthand): docker:post-install "command" --sudo
+/// 4. Backend hooks (shorthand): aur:post-sync "command" [--always]
+///    Runs only when that backend changed, unless `--always` is set.
+/// 5. Version-change hooks: linux:on-upgrade "mkinitcpio -P" --sudo
+///    Runs when the package's installed version changed during sync.
+///
+/// Any hook may add `when="..."` (e.g. `when="os:arch"`) to run only when
+/// the predicate holds; see `commands::hooks` for the predicates.
 pub fn parse_hooks(node: &KdlNode, hooks: &mut LifecycleConfig) -> Result<()> {
     if let Some(children) = node.children() {
         for child in children.nodes() {
@@ -25,13 +32,22 @@ pub fn parse_hooks(node: &KdlNode, hooks: &mut LifecycleConfig) -> Result<()> {
 
                     if let Some(command) = super::meta::get_first_string(child) {
                         let (action_type, error_behavior) = parse_hook_flags(child)?;
+                        let when = hook_when(child, &phase)?;
+                        let conditions = if phase == LifecyclePhase::PostSync
+                            && !has_hook_flag(child, "--always")
+                        {
+                            vec![ActionCondition::IfChanged(package.to_string())]
+                        } else {
+                            vec![]
+                        };
                         hooks.actions.push(LifecycleAction {
                             command: command.to_string(),
                             action_type,
                             phase,
                             package: Some(package.to_string()),
-                            conditions: vec![], // Phase 2
+                            conditions,
                             error_behavior,
+                            when,
                         });
                     }
                 }
@@ -46,6 +62,7 @@ pub fn parse_hooks(node: &KdlNode, hooks: &mut LifecycleConfig) -> Result<()> {
                 let phase = parse_hook_phase(child_name)?;
                 if let Some(command) = super::meta::get_first_string(child) {
                     let (action_type, error_behavior) = parse_hook_flags(child)?;
+                    let when = hook_when(child, &phase)?;
                     hooks.actions.push(LifecycleAction {
                         command: command.to_string(),
                         action_type,
@@ -53,6 +70,7 @@ pub fn parse_hooks(node: &KdlNode, hooks: &mut LifecycleConfig) -> Result<()> {
                         package: None,
                         conditions: vec![], // Phase 2
                         error_behavior,
+                        when,
                     });
                 }
             }
@@ -73,6 +91,7 @@ pub fn parse_hook_phase(s: &str) -> Result<LifecyclePhase> {
         "pre-remove" => Ok(LifecyclePhase::PreRemove),
         "post-remove" => Ok(LifecyclePhase::PostRemove),
         "on-update" => Ok(LifecyclePhase::OnUpdate),
+        "on-upgrade" => Ok(LifecyclePhase::OnUpgrade),
         _ => Err(DeclarchError::ConfigError(format!(
             "Invalid hook phase '{}'. Valid phases: {}",
             s,
@@ -85,7 +104,8 @@ pub fn parse_hook_phase(s: &str) -> Result<LifecyclePhase> {
                 "post-install",
                 "pre-remove",
                 "post-remove",
-                "on-update"
+                "on-update",
+                "on-upgrade"
             ]
             .join(", ")
         ))),
@@ -115,6 +135,48 @@ pub fn parse_hook_flags(node: &KdlNode) -> Result<(ActionType, ErrorBehavior)> {
     Ok((action_type, error_behavior))
 }
 
+/// The hook's `when="..."` predicate, if any
+///
+/// `backend:NAME changed` needs the transaction's installs and removals,
+/// which only post-sync hooks see, so other phases reject it.
+pub fn hook_when(node: &KdlNode, phase: &LifecyclePhase) -> Result<Option<String>> {
+    let when = node
+        .entries()
+        .iter()
+        .find(|entry| entry.name().is_some_and(|name| name.value() == "when"))
+        .and_then(|entry| entry.value().as_string())
+        .map(|when| when.trim().to_string())
+        .filter(|when| !when.is_empty());
+
+    if let Some(expr) = &when
+        && *phase != LifecyclePhase::PostSync
+        && is_backend_change_predicate(expr)
+    {
+        return Err(DeclarchError::ConfigError(format!(
+            "Hook '{}': when=\"{}\" is only available on post-sync hooks",
+            node.name().value(),
+            expr
+        )));
+    }
+    Ok(when)
+}
+
+fn is_backend_change_predicate(expr: &str) -> bool {
+    let expr = expr.trim();
+    match expr.strip_prefix('!').or_else(|| expr.strip_prefix("not ")) {
+        Some(inner) => is_backend_change_predicate(inner),
+        None => expr.starts_with("backend:"),
+    }
+}
+
+/// Check whether a hook node carries a flag (e.g. `--always`)
+pub fn has_hook_flag(node: &KdlNode, flag: &str) -> bool {
+    node.entries()
+        .iter()
+        .skip(1)
+        .any(|entry| entry.value().as_string() == Some(flag))
+}
+
 /// Check if a node is a package block (has children with hook phases)
 pub fn is_package_block(node: &KdlNode) -> bool {
     if let Some(children) = node.children() {
@@ -122,7 +184,12 @@ pub fn is_package_block(node: &KdlNode) -> bool {
             let name = child.name().value();
             if matches!(
                 name,
-                "pre-install" | "post-install" | "pre-remove" | "post-remove" | "on-update"
+                "pre-install"
+                    | "post-install"
+                    | "pre-remove"
+                    | "post-remove"
+                    | "on-update"
+                    | "on-upgrade"
             ) {
                 return true;
             }
@@ -144,6 +211,7 @@ pub fn parse_package_hook_block(
 
             if let Some(command) = super::meta::get_first_string(child) {
                 let (action_type, error_behavior) = parse_hook_flags(child)?;
+                let when = hook_when(child, &phase)?;
                 hooks.actions.push(LifecycleAction {
                     command: command.to_string(),
                     action_type,
@@ -151,6 +219,7 @@ pub fn parse_package_hook_block(
                     package: Some(package.clone()),
                     conditions: vec![], // Phase 2
                     error_behavior,
+                    when,
                 });
             }
         }
@@ -2,6 +2,7 @@ pub mod conflicts;
 pub mod env;
 pub mod hooks;
 pub mod meta;
+pub mod package_mappings;
 pub mod packages;
 pub mod policy;
 pub mod repositories;
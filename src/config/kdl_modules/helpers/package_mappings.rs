@@ -0,0 +1,57 @@
+use crate::error::{DeclarchError, Result};
+use kdl::KdlNode;
+use std::collections::HashMap;
+
+/// Parse package aliases: `aliases-pkg { python python3 }`
+///
+/// `aliases-pkg:apt { python python3 }` scopes the aliases to one backend;
+/// the resolver consults those before the global ones.
+pub fn parse_package_aliases(
+    node: &KdlNode,
+    global: &mut HashMap<String, String>,
+    by_backend: &mut HashMap<String, HashMap<String, String>>,
+) -> Result<()> {
+    let target = match node.name().value().split_once(':') {
+        Some((_, backend)) => by_backend.entry(backend.to_string()).or_default(),
+        None => global,
+    };
+
+    // Inline pairs: aliases-pkg python python3
+    let inline: Vec<&str> = node
+        .entries()
+        .iter()
+        .filter(|entry| entry.name().is_none())
+        .filter_map(|entry| entry.value().as_string())
+        .collect();
+    if !inline.len().is_multiple_of(2) {
+        return Err(DeclarchError::ConfigError(format!(
+            "{} expects name/target pairs, got {} value(s)",
+            node.name().value(),
+            inline.len()
+        )));
+    }
+    for pair in inline.chunks(2) {
+        target.insert(pair[0].to_string(), pair[1].to_string());
+    }
+
+    // Block form: one `from to` per line
+    if let Some(children) = node.children() {
+        for child in children.nodes() {
+            let from = child.name().value();
+            let to = child
+                .entries()
+                .first()
+                .and_then(|entry| entry.value().as_string())
+                .ok_or_else(|| {
+                    DeclarchError::ConfigError(format!(
+                        "alias '{}' in {} is missing a target package",
+                        from,
+                        node.name().value()
+                    ))
+                })?;
+            target.insert(from.to_string(), to.to_string());
+        }
+    }
+
+    Ok(())
+}
@@ -11,9 +11,7 @@ pub fn extract_packages_to(node: &KdlNode, target: &mut Vec<PackageEntry>) {
     // Extract from string arguments of this node
     for entry in node.entries() {
         if let Some(val) = entry.value().as_string() {
-            target.push(PackageEntry {
-                name: val.to_string(),
-            });
+            target.push(PackageEntry::new(val.to_string()));
         }
     }
 
@@ -24,31 +22,127 @@ pub fn extract_packages_to(node: &KdlNode, target: &mut Vec<PackageEntry>) {
             let child_entries: Vec<_> = child
                 .entries()
                 .iter()
+                .filter(|e| e.name().is_none())
                 .filter_map(|e| e.value().as_string())
                 .collect();
 
             if child_entries.is_empty() {
                 // No string arguments, just the node name
-                target.push(PackageEntry {
-                    name: child_name.to_string(),
-                });
+                target.push(package_entry(child, child_name.to_string()));
             } else {
                 // Has string arguments - push node name AND all arguments
                 // First, push the node name
-                target.push(PackageEntry {
-                    name: child_name.to_string(),
-                });
+                target.push(package_entry(child, child_name.to_string()));
                 // Then push all string arguments
                 for entry in &child_entries {
-                    target.push(PackageEntry {
-                        name: entry.to_string(),
-                    });
+                    target.push(PackageEntry::new(entry.to_string()));
                 }
             }
         }
     }
 }
 
+/// Package entry for a node, reading both the terse inline properties
+/// (`neovim tags="dev" pinned=#true`) and the block form:
+///
+/// ```kdl
+/// neovim {
+///     version "0.9.5"
+///     reason "editor"
+///     tags "dev" "editor"
+/// }
+/// ```
+///
+/// Block values win over inline properties; tags from both are kept.
+pub fn package_entry(node: &KdlNode, name: impl Into<String>) -> PackageEntry {
+    let mut entry = PackageEntry {
+        ignore_version: ignore_version(node),
+        tags: tags(node),
+        pinned: pinned(node),
+        extra_args: extra_args(node),
+        ..PackageEntry::new(name)
+    };
+
+    let Some(children) = node.children() else {
+        return entry;
+    };
+    for child in children.nodes() {
+        let first_string = || {
+            child
+                .entries()
+                .iter()
+                .find(|e| e.name().is_none())
+                .and_then(|e| e.value().as_string())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let flag = || {
+            child
+                .entries()
+                .iter()
+                .find(|e| e.name().is_none())
+                .and_then(|e| e.value().as_bool())
+                .unwrap_or(true)
+        };
+        match child.name().value() {
+            "version" => entry.version = first_string(),
+            "reason" => entry.reason = first_string(),
+            "extra-args" => entry.extra_args = first_string(),
+            "pinned" => entry.pinned = flag(),
+            "ignore-version" => entry.ignore_version = flag(),
+            "tags" => {
+                for value in child.entries().iter().filter(|e| e.name().is_none()) {
+                    if let Some(raw) = value.value().as_string() {
+                        entry.tags.extend(split_tags(raw));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    entry
+}
+
+/// Whether a package node carries `ignore-version=#true`
+pub fn ignore_version(node: &KdlNode) -> bool {
+    node.get("ignore-version")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+/// Whether a package node carries `pinned=#true`
+pub fn pinned(node: &KdlNode) -> bool {
+    node.get("pinned")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+/// Extra install arguments from a package node's `extra-args="..."` property
+pub fn extra_args(node: &KdlNode) -> Option<String> {
+    node.get("extra-args")
+        .and_then(|value| value.as_string())
+        .map(str::trim)
+        .filter(|args| !args.is_empty())
+        .map(str::to_string)
+}
+
+/// Tags from a package node's `tags="dev,editor"` property
+pub fn tags(node: &KdlNode) -> Vec<String> {
+    node.get("tags")
+        .and_then(|value| value.as_string())
+        .map(split_tags)
+        .unwrap_or_default()
+}
+
+fn split_tags(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
 /// Extract mixed string values from a node (both entries and children names)
 pub fn extract_mixed_values(node: &KdlNode, target: &mut Vec<String>) {
     // Extract from children node names
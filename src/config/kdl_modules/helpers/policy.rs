@@ -1,5 +1,6 @@
 use crate::config::kdl_modules::types::PolicyConfig;
-use crate::error::Result;
+use crate::constants::urls::BackendRegistry;
+use crate::error::{DeclarchError, Result};
 use kdl::KdlNode;
 
 /// Parse policy block: policy { protected { linux systemd } orphans "keep" }
@@ -32,6 +33,14 @@ pub fn parse_policy(node: &KdlNode, policy: &mut PolicyConfig) -> Result<()> {
                         policy.orphans = Some(strategy.to_lowercase());
                     }
                 }
+                "orphan-deps" | "orphan_deps" => {
+                    // Orphaned dependency cleanup after sync: "keep" | "remove" | "ask"
+                    if let Some(val) = child.entries().first()
+                        && let Some(strategy) = val.value().as_string()
+                    {
+                        policy.orphan_deps = Some(strategy.to_lowercase());
+                    }
+                }
                 "require-backend" | "require_backend" => {
                     if let Some(value) = parse_first_bool(child) {
                         policy.require_backend = Some(value);
@@ -42,6 +51,61 @@ pub fn parse_policy(node: &KdlNode, policy: &mut PolicyConfig) -> Result<()> {
                         policy.forbid_hooks = Some(value);
                     }
                 }
+                "block-partial-upgrade" | "block_partial_upgrade" => {
+                    if let Some(value) = parse_first_bool(child) {
+                        policy.block_partial_upgrade = Some(value);
+                    }
+                }
+                "noconfirm" | "no-confirm" | "no_confirm" => {
+                    if let Some(value) = parse_first_bool(child) {
+                        policy.noconfirm = Some(value);
+                    }
+                }
+                "auto-confirm" | "auto_confirm" => {
+                    if let Some(value) = parse_first_bool(child) {
+                        policy.auto_confirm = Some(value);
+                    }
+                }
+                "verify-installs" | "verify_installs" => {
+                    if let Some(value) = parse_first_bool(child) {
+                        policy.verify_installs = Some(value);
+                    }
+                }
+                "run-hooks" | "run_hooks" => {
+                    if let Some(value) = parse_first_bool(child) {
+                        policy.run_hooks = Some(value);
+                    }
+                }
+                "escalation" => {
+                    if let Some(val) = child.entries().first()
+                        && let Some(program) = val.value().as_string()
+                    {
+                        let program = program.trim();
+                        if program.is_empty() || program.contains(char::is_whitespace) {
+                            return Err(DeclarchError::ConfigError(format!(
+                                "policy escalation must be a single program such as \
+                                 \"sudo\", \"doas\" or \"run0\", got '{}'",
+                                program
+                            )));
+                        }
+                        policy.escalation = Some(program.to_string());
+                    }
+                }
+                "registries" => {
+                    // Backend registries: registries "github:org/repo" "https://..."
+                    for entry in child.entries() {
+                        if let Some(spec) = entry.value().as_string() {
+                            if BackendRegistry::parse(spec).is_none() {
+                                return Err(DeclarchError::ConfigError(format!(
+                                    "policy registries entry '{}' must be github:org/repo[/branch] \
+                                     or an https:// URL",
+                                    spec
+                                )));
+                            }
+                            policy.registries.push(spec.trim().to_string());
+                        }
+                    }
+                }
                 "on-duplicate" | "on_duplicate" => {
                     if let Some(val) = child.entries().first()
                         && let Some(mode) = val.value().as_string()
@@ -1,9 +1,16 @@
-use crate::error::Result;
+use crate::error::{DeclarchError, Result};
 use kdl::KdlNode;
 use std::collections::HashMap;
 
 /// Parse package sources: repos:paru { "https://..." }
-pub fn parse_repositories(node: &KdlNode, repos: &mut HashMap<String, Vec<String>>) -> Result<()> {
+///
+/// Child entries may carry `priority=N`; sources are sorted by it when
+/// applied to the backend (see `apply_backend_package_sources`).
+pub fn parse_repositories(
+    node: &KdlNode,
+    repos: &mut HashMap<String, Vec<String>>,
+    priorities: &mut HashMap<String, HashMap<String, i64>>,
+) -> Result<()> {
     // Check for colon syntax: repos:paru
     let backend_name = if let Some((_, backend)) = node.name().value().split_once(':') {
         backend.to_string()
@@ -13,10 +20,13 @@ pub fn parse_repositories(node: &KdlNode, repos: &mut HashMap<String, Vec<String
     };
 
     let mut repo_urls = Vec::new();
+    let mut repo_priorities = Vec::new();
 
     // Extract from string arguments
     for entry in node.entries() {
-        if let Some(val) = entry.value().as_string() {
+        if entry.name().is_none()
+            && let Some(val) = entry.value().as_string()
+        {
             repo_urls.push(val.to_string());
         }
     }
@@ -24,15 +34,37 @@ pub fn parse_repositories(node: &KdlNode, repos: &mut HashMap<String, Vec<String
     // Extract from children
     if let Some(children) = node.children() {
         for child in children.nodes() {
-            repo_urls.push(child.name().value().to_string());
+            let name = child.name().value().to_string();
+            if let Some(priority) = child.get("priority") {
+                let priority = priority.as_integer().ok_or_else(|| {
+                    DeclarchError::ConfigError(format!(
+                        "repos:{} source '{}' priority must be an integer",
+                        backend_name, name
+                    ))
+                })?;
+                repo_priorities.push((name.trim().to_string(), priority as i64));
+            }
+            repo_urls.push(name);
             for entry in child.entries() {
-                if let Some(val) = entry.value().as_string() {
+                if entry.name().is_none()
+                    && let Some(val) = entry.value().as_string()
+                {
                     repo_urls.push(val.to_string());
                 }
             }
         }
     }
 
+    if !repo_priorities.is_empty() {
+        let entry = priorities.entry(backend_name.clone()).or_default();
+        for (source, priority) in repo_priorities {
+            entry
+                .entry(source)
+                .and_modify(|existing| *existing = (*existing).min(priority))
+                .or_insert(priority);
+        }
+    }
+
     if !repo_urls.is_empty() {
         let entry = repos.entry(backend_name).or_default();
         for url in repo_urls {
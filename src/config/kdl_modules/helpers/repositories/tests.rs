@@ -12,8 +12,8 @@ fn parse_repositories_merges_without_duplicates() {
     let node1 = first_node(r#"repos:paru "core" "extra""#);
     let node2 = first_node(r#"repos:paru "extra" "multilib""#);
 
-    parse_repositories(&node1, &mut repos).expect("parse node1");
-    parse_repositories(&node2, &mut repos).expect("parse node2");
+    parse_repositories(&node1, &mut repos, &mut HashMap::new()).expect("parse node1");
+    parse_repositories(&node2, &mut repos, &mut HashMap::new()).expect("parse node2");
 
     let paru = repos.get("paru").expect("paru repos present");
     assert_eq!(
@@ -31,7 +31,38 @@ fn parse_repositories_ignores_non_colon_syntax() {
     let mut repos = HashMap::new();
     let node = first_node(r#"repos "core""#);
 
-    parse_repositories(&node, &mut repos).expect("parse node");
+    parse_repositories(&node, &mut repos, &mut HashMap::new()).expect("parse node");
 
     assert!(repos.is_empty());
 }
+
+#[test]
+fn parse_repositories_reads_child_priorities() {
+    let mut repos = HashMap::new();
+    let mut priorities = HashMap::new();
+    let node = first_node(r#"repos:apt { "backports" priority=2; "main" priority=1; "contrib" }"#);
+
+    parse_repositories(&node, &mut repos, &mut priorities).expect("parse node");
+
+    assert_eq!(
+        repos["apt"],
+        vec![
+            "backports".to_string(),
+            "main".to_string(),
+            "contrib".to_string()
+        ]
+    );
+    let apt = &priorities["apt"];
+    assert_eq!(apt.get("main"), Some(&1));
+    assert_eq!(apt.get("backports"), Some(&2));
+    assert_eq!(apt.get("contrib"), None);
+}
+
+#[test]
+fn parse_repositories_rejects_non_integer_priority() {
+    let node = first_node(r#"repos:apt { "main" priority="high" }"#);
+
+    let result = parse_repositories(&node, &mut HashMap::new(), &mut HashMap::new());
+
+    assert!(result.is_err());
+}
@@ -1,12 +1,13 @@
 use crate::config::kdl_modules::helpers::{
-    conflicts, env, hooks, meta, packages, policy, repositories,
+    conflicts, env, hooks, meta, package_mappings, packages, policy, repositories,
 };
 use crate::config::kdl_modules::types::{
-    ActionType, ErrorBehavior, LifecycleAction, LifecyclePhase, McpConfig, PackageEntry, RawConfig,
+    ActionType, ErrorBehavior, LifecycleAction, LifecyclePhase, McpConfig, PackageEntry,
+    PackageListInclude, RawConfig,
 };
-use crate::error::Result;
+use crate::error::{DeclarchError, Result};
 use kdl::KdlNode;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 pub(super) fn map_node_into_config(node: &KdlNode, config: &mut RawConfig) -> Result<()> {
     let node_name = node.name().value();
@@ -18,9 +19,15 @@ pub(super) fn map_node_into_config(node: &KdlNode, config: &mut RawConfig) -> Re
         "exclude" | "excludes" => {
             packages::extract_mixed_values(node, &mut config.excludes);
         }
+        name if name.starts_with("exclude:") || name.starts_with("excludes:") => {
+            parse_backend_excludes(node, &mut config.backend_excludes);
+        }
         "backends" => {
             packages::extract_strings(node, &mut config.backend_imports);
         }
+        "include" => {
+            parse_package_include(node, &mut config.package_includes)?;
+        }
         "experimental" => {
             parse_experimental_flags(node, &mut config.experimental);
         }
@@ -55,7 +62,18 @@ pub(super) fn map_node_into_config(node: &KdlNode, config: &mut RawConfig) -> Re
             env::parse_env_vars(node, &mut config.env, None)?;
         }
         name if name.starts_with("repos") || name.starts_with("repositories") => {
-            repositories::parse_repositories(node, &mut config.package_sources)?;
+            repositories::parse_repositories(
+                node,
+                &mut config.package_sources,
+                &mut config.package_source_priorities,
+            )?;
+        }
+        name if name == "aliases-pkg" || name.starts_with("aliases-pkg:") => {
+            package_mappings::parse_package_aliases(
+                node,
+                &mut config.package_aliases,
+                &mut config.backend_package_aliases,
+            )?;
         }
         "policy" => {
             policy::parse_policy(node, &mut config.policy)?;
@@ -72,6 +90,7 @@ pub(super) fn map_node_into_config(node: &KdlNode, config: &mut RawConfig) -> Re
                     package: None,
                     conditions: vec![],
                     error_behavior: ErrorBehavior::default(),
+                    when: None,
                 });
             }
         }
@@ -84,6 +103,7 @@ pub(super) fn map_node_into_config(node: &KdlNode, config: &mut RawConfig) -> Re
                     package: None,
                     conditions: vec![],
                     error_behavior: ErrorBehavior::default(),
+                    when: None,
                 });
             }
         }
@@ -96,6 +116,7 @@ pub(super) fn map_node_into_config(node: &KdlNode, config: &mut RawConfig) -> Re
                     package: None,
                     conditions: vec![],
                     error_behavior: ErrorBehavior::default(),
+                    when: None,
                 });
             }
         }
@@ -112,6 +133,47 @@ pub(super) fn map_node_into_config(node: &KdlNode, config: &mut RawConfig) -> Re
     Ok(())
 }
 
+/// Parse `include "path.txt" backend="name"`
+fn parse_package_include(node: &KdlNode, includes: &mut Vec<PackageListInclude>) -> Result<()> {
+    let path = node
+        .entries()
+        .iter()
+        .find(|entry| entry.name().is_none())
+        .and_then(|entry| entry.value().as_string())
+        .ok_or_else(|| {
+            DeclarchError::ConfigError(
+                "include requires a file path. Usage: include \"packages.txt\" backend=\"pip\""
+                    .to_string(),
+            )
+        })?;
+
+    let backend = node
+        .entries()
+        .iter()
+        .find(|entry| entry.name().is_some_and(|name| name.value() == "backend"))
+        .and_then(|entry| entry.value().as_string())
+        .map(str::trim)
+        .filter(|backend| !backend.is_empty())
+        .ok_or_else(|| {
+            DeclarchError::ConfigError(format!("include \"{}\" is missing backend=\"...\"", path))
+        })?;
+
+    includes.push(PackageListInclude {
+        path: path.to_string(),
+        backend: backend.to_string(),
+    });
+    Ok(())
+}
+
+/// `excludes:pip { setuptools }`: names excluded for one backend only
+fn parse_backend_excludes(node: &KdlNode, target: &mut HashMap<String, HashSet<String>>) {
+    let Some((_, backend)) = node.name().value().split_once(':') else {
+        return;
+    };
+    let names = packages::extract_mixed_values_return(node);
+    target.entry(backend.to_string()).or_default().extend(names);
+}
+
 pub(super) fn parse_backend_options(
     node: &KdlNode,
     options: &mut std::collections::HashMap<String, std::collections::HashMap<String, String>>,
@@ -252,9 +314,7 @@ fn parse_pkg_node(node: &KdlNode, config: &mut RawConfig) -> Result<()> {
                     .packages_by_backend
                     .entry(backend.to_string())
                     .or_default()
-                    .push(PackageEntry {
-                        name: package.to_string(),
-                    });
+                    .push(packages::package_entry(child, package.to_string()));
             } else {
                 let packages = extract_packages_from_node(child);
                 if !packages.is_empty() {
@@ -276,9 +336,7 @@ fn parse_pkg_node(node: &KdlNode, config: &mut RawConfig) -> Result<()> {
                 .packages_by_backend
                 .entry(backend.to_string())
                 .or_default()
-                .push(PackageEntry {
-                    name: package.to_string(),
-                });
+                .push(PackageEntry::new(package.to_string()));
         }
     }
 
@@ -290,15 +348,14 @@ fn extract_packages_from_node(node: &KdlNode) -> Vec<PackageEntry> {
 
     if let Some(children) = node.children() {
         for child in children.nodes() {
-            packages.push(PackageEntry {
-                name: child.name().value().to_string(),
-            });
+            packages.push(packages::package_entry(
+                child,
+                child.name().value().to_string(),
+            ));
 
-            for entry in child.entries() {
+            for entry in child.entries().iter().filter(|e| e.name().is_none()) {
                 if let Some(val) = entry.value().as_string() {
-                    packages.push(PackageEntry {
-                        name: val.to_string(),
-                    });
+                    packages.push(PackageEntry::new(val.to_string()));
                 }
             }
         }
@@ -306,9 +363,7 @@ fn extract_packages_from_node(node: &KdlNode) -> Vec<PackageEntry> {
 
     for entry in node.entries() {
         if let Some(val) = entry.value().as_string() {
-            packages.push(PackageEntry {
-                name: val.to_string(),
-            });
+            packages.push(PackageEntry::new(val.to_string()));
         }
     }
 
@@ -340,9 +395,7 @@ fn parse_packages_node_legacy(node: &KdlNode, config: &mut RawConfig) -> Result<
                         .packages_by_backend
                         .entry(backend.to_string())
                         .or_default()
-                        .push(PackageEntry {
-                            name: package.to_string(),
-                        });
+                        .push(packages::package_entry(child, package.to_string()));
                 }
             } else if child.children().is_some() {
                 let packages = extract_packages_from_node(child);
@@ -358,19 +411,15 @@ fn parse_packages_node_legacy(node: &KdlNode, config: &mut RawConfig) -> Result<
                     .packages_by_backend
                     .entry("default".to_string())
                     .or_default()
-                    .push(PackageEntry {
-                        name: child_name.to_string(),
-                    });
+                    .push(packages::package_entry(child, child_name.to_string()));
 
-                for entry in child.entries() {
+                for entry in child.entries().iter().filter(|e| e.name().is_none()) {
                     if let Some(val) = entry.value().as_string() {
                         config
                             .packages_by_backend
                             .entry("default".to_string())
                             .or_default()
-                            .push(PackageEntry {
-                                name: val.to_string(),
-                            });
+                            .push(PackageEntry::new(val.to_string()));
                     }
                 }
             }
@@ -385,18 +434,14 @@ fn parse_packages_node_legacy(node: &KdlNode, config: &mut RawConfig) -> Result<
                         .packages_by_backend
                         .entry(backend.to_string())
                         .or_default()
-                        .push(PackageEntry {
-                            name: package.to_string(),
-                        });
+                        .push(PackageEntry::new(package.to_string()));
                 }
             } else {
                 config
                     .packages_by_backend
                     .entry("default".to_string())
                     .or_default()
-                    .push(PackageEntry {
-                        name: val.to_string(),
-                    });
+                    .push(PackageEntry::new(val.to_string()));
             }
         }
     }
@@ -70,9 +70,12 @@ impl BackendParserRegistry {
                         .packages_by_backend
                         .entry(backend.to_string())
                         .or_default()
-                        .push(crate::config::kdl_modules::types::PackageEntry {
-                            name: package.to_string(),
-                        });
+                        .push(
+                            crate::config::kdl_modules::helpers::packages::package_entry(
+                                child,
+                                package.to_string(),
+                            ),
+                        );
                 } else {
                     let mut packages = Vec::new();
                     crate::config::kdl_modules::helpers::packages::extract_packages_to(
@@ -103,9 +106,9 @@ impl BackendParserRegistry {
                 .packages_by_backend
                 .entry(backend.to_string())
                 .or_default()
-                .push(crate::config::kdl_modules::types::PackageEntry {
-                    name: package.to_string(),
-                });
+                .push(crate::config::kdl_modules::types::PackageEntry::new(
+                    package.to_string(),
+                ));
         }
         Ok(())
     }
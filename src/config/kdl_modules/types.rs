@@ -1,10 +1,11 @@
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 /// Raw configuration parsed from KDL files
 ///
 /// In v0.6+, this uses unified package storage where all packages are
 /// organized by backend name in a HashMap. No backend-specific fields.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RawConfig {
     /// Import statements
     pub imports: Vec<String>,
@@ -29,6 +30,11 @@ pub struct RawConfig {
     /// Packages to exclude from sync
     pub excludes: Vec<String>,
 
+    /// Packages to exclude for one backend only, alongside the global excludes
+    /// Syntax in KDL:
+    ///   excludes:pip { setuptools }
+    pub backend_excludes: HashMap<String, HashSet<String>>,
+
     /// Project metadata
     pub project_metadata: ProjectMetadata,
 
@@ -44,6 +50,21 @@ pub struct RawConfig {
     /// Custom package sources (repositories)
     pub package_sources: HashMap<String, Vec<String>>,
 
+    /// Source priorities: backend_name -> source -> priority (lower comes first)
+    /// Syntax in KDL:
+    ///   repos:apt { "main" priority=1; "backports" priority=2 }
+    pub package_source_priorities: HashMap<String, HashMap<String, i64>>,
+
+    /// Global package aliases: declared name -> actual package name
+    /// Syntax in KDL:
+    ///   aliases-pkg { python python3 }
+    pub package_aliases: HashMap<String, String>,
+
+    /// Backend-scoped package aliases, consulted before the global ones
+    /// Syntax in KDL:
+    ///   aliases-pkg:apt { python python3 }
+    pub backend_package_aliases: HashMap<String, HashMap<String, String>>,
+
     /// Package lifecycle policies
     pub policy: PolicyConfig,
 
@@ -54,6 +75,11 @@ pub struct RawConfig {
     /// These define which backends are available for package operations
     pub backend_imports: Vec<String>,
 
+    /// Plain-text package list includes
+    /// Example:
+    /// include "packages/work.txt" backend="pip"
+    pub package_includes: Vec<PackageListInclude>,
+
     /// Experimental feature flags.
     /// Example:
     /// experimental {
@@ -70,14 +96,51 @@ pub struct RawConfig {
     pub mcp: McpConfig,
 }
 
-/// Package entry (minimal - just name for now)
-#[derive(Debug, Clone)]
+/// Plain-text package list included for a single backend
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageListInclude {
+    /// Path to the list file, relative to the including config
+    pub path: String,
+    /// Backend that receives every package in the file
+    pub backend: String,
+}
+
+/// Package entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PackageEntry {
     pub name: String,
+    /// Compare only presence, never version (`neovim ignore-version=#true`)
+    pub ignore_version: bool,
+    /// Grouping tags for `sync --tag` (`neovim tags="dev,editor"`)
+    pub tags: Vec<String>,
+    /// Never pruned once tracked, even when undeclared (`neovim pinned=#true`)
+    pub pinned: bool,
+    /// Arguments appended to the install command for this package only
+    /// (`tensorflow extra-args="--no-build-isolation"`)
+    pub extra_args: Option<String>,
+    /// Version noted in the block form (`neovim { version "0.9.5" }`)
+    pub version: Option<String>,
+    /// Why the package is declared (`neovim { reason "editor" }`)
+    pub reason: Option<String>,
+}
+
+impl PackageEntry {
+    /// Entry with no metadata, as declared by a bare name or string argument
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ignore_version: false,
+            tags: Vec::new(),
+            pinned: false,
+            extra_args: None,
+            version: None,
+            reason: None,
+        }
+    }
 }
 
 /// Project metadata
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ProjectMetadata {
     pub title: Option<String>,
     pub description: Option<String>,
@@ -94,19 +157,21 @@ pub struct ProjectMetadata {
 }
 
 /// Conflict entry - mutually exclusive packages
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConflictEntry {
     pub packages: Vec<String>,
     pub condition: Option<String>, // Future: for conditional conflicts
 }
 
 /// Package lifecycle policies
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PolicyConfig {
     /// Protected packages that won't be removed even with --prune
     pub protected: HashSet<String>,
     /// Strategy for handling orphans: "keep", "remove", "ask"
     pub orphans: Option<String>,
+    /// Orphaned dependencies reported by backends after sync: "keep", "remove", "ask"
+    pub orphan_deps: Option<String>,
     /// Require explicit backend declarations (no implicit/legacy default backend)
     pub require_backend: Option<bool>,
     /// Disallow hook execution even when CLI uses --hooks
@@ -115,6 +180,20 @@ pub struct PolicyConfig {
     pub on_duplicate: Option<String>,
     /// Cross-backend conflict policy: "warn" | "error"
     pub on_conflict: Option<String>,
+    /// Refuse targeted installs from backends with an `update` command on a stale system instead of warning
+    pub block_partial_upgrade: Option<bool>,
+    /// Default for `sync --noconfirm` (skip package manager prompts)
+    pub noconfirm: Option<bool>,
+    /// Default for `--yes` during sync (skip declarch's own prompts)
+    pub auto_confirm: Option<bool>,
+    /// Re-list each backend after installing and flag packages that did not appear
+    pub verify_installs: Option<bool>,
+    /// Default for `sync --hooks` (still subject to experimental/forbid gating)
+    pub run_hooks: Option<bool>,
+    /// Program used instead of `sudo` for `needs_sudo` backends (e.g. "doas")
+    pub escalation: Option<String>,
+    /// Extra backend registries for `init --backend`, tried before the default
+    pub registries: Vec<String>,
 }
 
 impl PolicyConfig {
@@ -128,14 +207,20 @@ impl PolicyConfig {
 }
 
 /// Lifecycle action configuration
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LifecycleConfig {
     /// All lifecycle actions (organized by phase during execution)
     pub actions: Vec<LifecycleAction>,
+    /// `policy { escalation }` program for root hooks, filled in by the loader
+    #[serde(default)]
+    pub escalation: Option<String>,
+    /// Run root hooks without escalation (`--no-sudo`), set by the command
+    #[serde(skip)]
+    pub no_sudo: bool,
 }
 
 /// Lifecycle action entry
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LifecycleAction {
     pub command: String,
     pub action_type: ActionType,
@@ -143,17 +228,20 @@ pub struct LifecycleAction {
     pub package: Option<String>,
     pub conditions: Vec<ActionCondition>,
     pub error_behavior: ErrorBehavior,
+    /// `when="..."` predicate, evaluated just before the hook runs
+    #[serde(default)]
+    pub when: Option<String>,
 }
 
 /// Action type
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ActionType {
     User, // Run without sudo
     Root, // Run with sudo
 }
 
 /// Lifecycle phase - when the action should run
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LifecyclePhase {
     PreSync,
     PostSync,
@@ -164,10 +252,12 @@ pub enum LifecyclePhase {
     PreRemove,
     PostRemove,
     OnUpdate,
+    /// A package's installed version changed during sync (`on-upgrade`)
+    OnUpgrade,
 }
 
 /// Action condition - when to run the action
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ActionCondition {
     IfInstalled(String),
     IfChanged(String),
@@ -176,7 +266,7 @@ pub enum ActionCondition {
 }
 
 /// Error behavior for hooks
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub enum ErrorBehavior {
     #[default]
     Warn,
@@ -185,7 +275,7 @@ pub enum ErrorBehavior {
 }
 
 /// MCP policy configuration.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct McpConfig {
     /// "read-only" (default) or "write-enabled"
     pub mode: Option<String>,
@@ -22,6 +22,157 @@ fn test_pkg_block_single_backend() {
     assert!(paru_packages.iter().any(|p| p.name == "waybar"));
 }
 
+#[test]
+fn test_pkg_ignore_version_annotation() {
+    let kdl = r#"
+            pkg {
+                aur {
+                    neovim ignore-version=#true
+                    bat
+                }
+                npm:typescript ignore-version=#true
+            }
+        "#;
+
+    let config = parse_kdl_content(kdl).unwrap();
+    let aur = config.packages_by_backend.get("aur").unwrap();
+    assert!(aur.iter().any(|p| p.name == "neovim" && p.ignore_version));
+    assert!(aur.iter().any(|p| p.name == "bat" && !p.ignore_version));
+
+    let npm = config.packages_by_backend.get("npm").unwrap();
+    assert!(npm[0].ignore_version);
+}
+
+#[test]
+fn test_pkg_pinned_annotation() {
+    let kdl = r#"
+            pkg {
+                aur {
+                    neovim pinned=#true
+                    bat
+                }
+            }
+        "#;
+
+    let config = parse_kdl_content(kdl).unwrap();
+    let aur = config.packages_by_backend.get("aur").unwrap();
+    assert!(aur.iter().any(|p| p.name == "neovim" && p.pinned));
+    assert!(aur.iter().any(|p| p.name == "bat" && !p.pinned));
+}
+
+#[test]
+fn test_pkg_extra_args_annotation() {
+    let kdl = r#"
+            pkg {
+                pip:tensorflow extra-args="--no-build-isolation"
+                pip:numpy
+            }
+        "#;
+
+    let config = parse_kdl_content(kdl).unwrap();
+    let pip = config.packages_by_backend.get("pip").unwrap();
+    let tensorflow = pip.iter().find(|p| p.name == "tensorflow").unwrap();
+    assert_eq!(
+        tensorflow.extra_args.as_deref(),
+        Some("--no-build-isolation")
+    );
+    assert!(
+        pip.iter()
+            .any(|p| p.name == "numpy" && p.extra_args.is_none())
+    );
+}
+
+#[test]
+fn test_pkg_tags_annotation() {
+    let kdl = r#"
+            pkg {
+                aur {
+                    neovim tags="dev, editor"
+                    bat
+                }
+                npm:typescript tags="dev"
+            }
+        "#;
+
+    let config = parse_kdl_content(kdl).unwrap();
+    let aur = config.packages_by_backend.get("aur").unwrap();
+    let neovim = aur.iter().find(|p| p.name == "neovim").unwrap();
+    assert_eq!(neovim.tags, vec!["dev", "editor"]);
+    assert!(aur.iter().any(|p| p.name == "bat" && p.tags.is_empty()));
+
+    let npm = config.packages_by_backend.get("npm").unwrap();
+    assert_eq!(npm[0].tags, vec!["dev"]);
+}
+
+#[test]
+fn test_pkg_block_form_metadata() {
+    let kdl = r#"
+            pkg {
+                aur {
+                    neovim { version "0.9.5"; reason "editor"; tags "dev" }
+                    ripgrep {
+                        reason "fast grep"
+                        tags "cli" "search,dev"
+                        pinned
+                    }
+                }
+                npm:typescript {
+                    version "5.4.0"
+                    extra-args "--no-fund"
+                }
+            }
+        "#;
+
+    let config = parse_kdl_content(kdl).unwrap();
+    let aur = config.packages_by_backend.get("aur").unwrap();
+    assert_eq!(aur.len(), 2);
+
+    let neovim = aur.iter().find(|p| p.name == "neovim").unwrap();
+    assert_eq!(neovim.version.as_deref(), Some("0.9.5"));
+    assert_eq!(neovim.reason.as_deref(), Some("editor"));
+    assert_eq!(neovim.tags, vec!["dev"]);
+    assert!(!neovim.pinned);
+
+    let ripgrep = aur.iter().find(|p| p.name == "ripgrep").unwrap();
+    assert_eq!(ripgrep.version, None);
+    assert_eq!(ripgrep.reason.as_deref(), Some("fast grep"));
+    assert_eq!(ripgrep.tags, vec!["cli", "search", "dev"]);
+    assert!(ripgrep.pinned);
+
+    let npm = config.packages_by_backend.get("npm").unwrap();
+    assert_eq!(npm[0].version.as_deref(), Some("5.4.0"));
+    assert_eq!(npm[0].extra_args.as_deref(), Some("--no-fund"));
+}
+
+#[test]
+fn test_pkg_block_form_mixed_with_inline() {
+    let kdl = r#"
+            pkg:aur {
+                bat fd
+                neovim tags="editor" { version "0.9.5"; tags "dev" }
+                htop pinned=#true
+            }
+        "#;
+
+    let config = parse_kdl_content(kdl).unwrap();
+    let aur = config.packages_by_backend.get("aur").unwrap();
+    let names: Vec<_> = aur.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["bat", "fd", "neovim", "htop"]);
+
+    let neovim = aur.iter().find(|p| p.name == "neovim").unwrap();
+    assert_eq!(neovim.version.as_deref(), Some("0.9.5"));
+    assert_eq!(neovim.tags, vec!["editor", "dev"]);
+
+    let htop = aur.iter().find(|p| p.name == "htop").unwrap();
+    assert!(htop.pinned);
+    assert_eq!(htop.version, None);
+    assert!(
+        aur.iter()
+            .filter(|p| p.name != "neovim")
+            .all(|p| p.reason.is_none())
+    );
+}
+
 #[test]
 fn test_pkg_block_multiple_backends() {
     let kdl = r#"
@@ -124,6 +275,27 @@ fn test_legacy_packages_colon_backend_alias_still_works() {
     assert!(npm_packages.iter().any(|p| p.name == "prettier"));
 }
 
+#[test]
+fn test_legacy_packages_block_accepts_user_defined_backend() {
+    // No per-backend parser exists or is needed: any backend name maps through.
+    let kdl = r#"
+            packages:mybackend {
+                foo
+                bar
+            }
+            pkg:other-backend { baz }
+        "#;
+
+    let config = parse_kdl_content(kdl).unwrap();
+    let mine = config.packages_by_backend.get("mybackend").unwrap();
+    assert_eq!(mine.len(), 2);
+    assert!(mine.iter().any(|p| p.name == "foo"));
+    assert_eq!(
+        config.packages_by_backend.get("other-backend").unwrap()[0].name,
+        "baz"
+    );
+}
+
 #[test]
 fn test_legacy_packages_nested_backend_alias_still_works() {
     let kdl = r#"
@@ -151,6 +323,24 @@ fn test_excludes_parsing() {
     assert!(config.excludes.contains(&"bad-package".to_string()));
 }
 
+#[test]
+fn test_backend_scoped_excludes_parsing() {
+    let kdl = r#"
+            excludes global-bad
+            excludes:pip setuptools
+            excludes:pip {
+                wheel
+            }
+        "#;
+
+    let config = parse_kdl_content(kdl).unwrap();
+    assert_eq!(config.excludes, vec!["global-bad".to_string()]);
+    let pip = config.backend_excludes.get("pip").unwrap();
+    assert_eq!(pip.len(), 2);
+    assert!(pip.contains("setuptools") && pip.contains("wheel"));
+    assert!(!config.backend_excludes.contains_key("apt"));
+}
+
 #[test]
 fn test_meta_block_parsing() {
     let kdl = r#"
@@ -292,6 +482,14 @@ fn test_policy_extended_keys_parsing() {
                 forbid_hooks "true"
                 on_duplicate "error"
                 on_conflict "warn"
+                block-partial-upgrade "true"
+                noconfirm #true
+                auto-confirm "yes"
+                verify-installs #true
+                run-hooks #true
+                orphan-deps "Ask"
+                escalation "doas"
+                registries "github:acme/backends" "https://mycorp.example/backends"
             }
         "#;
 
@@ -300,6 +498,17 @@ fn test_policy_extended_keys_parsing() {
     assert_eq!(config.policy.forbid_hooks, Some(true));
     assert_eq!(config.policy.on_duplicate.as_deref(), Some("error"));
     assert_eq!(config.policy.on_conflict.as_deref(), Some("warn"));
+    assert_eq!(config.policy.block_partial_upgrade, Some(true));
+    assert_eq!(config.policy.noconfirm, Some(true));
+    assert_eq!(config.policy.auto_confirm, Some(true));
+    assert_eq!(config.policy.verify_installs, Some(true));
+    assert_eq!(config.policy.run_hooks, Some(true));
+    assert_eq!(config.policy.orphan_deps.as_deref(), Some("ask"));
+    assert_eq!(config.policy.escalation.as_deref(), Some("doas"));
+    assert_eq!(
+        config.policy.registries,
+        vec!["github:acme/backends", "https://mycorp.example/backends"]
+    );
 }
 
 #[test]
@@ -341,3 +550,124 @@ fn test_mixed_syntax() {
     assert!(config.packages_by_backend.contains_key("npm"));
     assert!(config.packages_by_backend.contains_key("flatpak"));
 }
+
+#[test]
+fn test_backend_post_sync_hooks_require_changes_unless_always() {
+    use crate::config::kdl_modules::types::ActionCondition;
+
+    let kdl = r#"
+            hooks {
+                aur:post-sync "mkinitcpio -P" --sudo
+                flatpak:post-sync "flatpak update --appstream" --always
+                post-sync "notify-send done"
+            }
+        "#;
+
+    let config = parse_kdl_content(kdl).unwrap();
+    let actions = &config.lifecycle_actions.actions;
+    assert_eq!(actions.len(), 3);
+
+    assert_eq!(
+        actions[0].conditions,
+        vec![ActionCondition::IfChanged("aur".to_string())]
+    );
+    assert!(actions[1].conditions.is_empty());
+    assert!(actions[2].conditions.is_empty());
+}
+
+#[test]
+fn test_on_upgrade_hooks_are_package_scoped() {
+    use crate::config::kdl_modules::types::{ActionType, LifecyclePhase};
+
+    let kdl = r#"
+            hooks {
+                linux:on-upgrade "mkinitcpio -P" --sudo
+                nvidia {
+                    on-upgrade "nvidia-modprobe"
+                }
+            }
+        "#;
+
+    let config = parse_kdl_content(kdl).unwrap();
+    let actions = &config.lifecycle_actions.actions;
+    assert_eq!(actions.len(), 2);
+    assert!(
+        actions
+            .iter()
+            .all(|action| action.phase == LifecyclePhase::OnUpgrade && action.conditions.is_empty())
+    );
+    assert_eq!(actions[0].package.as_deref(), Some("linux"));
+    assert_eq!(actions[0].action_type, ActionType::Root);
+    assert_eq!(actions[1].package.as_deref(), Some("nvidia"));
+}
+
+#[test]
+fn test_hooks_parse_when_predicate() {
+    let kdl = r#"
+            hooks {
+                post-sync "update-grub" when="backend:aur changed" --sudo
+                pre-sync "pacman-key --refresh-keys" when="os:arch"
+                docker {
+                    post-install "systemctl enable docker" when="!dry-run"
+                }
+                on-success "notify-send done"
+            }
+        "#;
+
+    let config = parse_kdl_content(kdl).unwrap();
+    let when: Vec<_> = config
+        .lifecycle_actions
+        .actions
+        .iter()
+        .map(|action| action.when.as_deref())
+        .collect();
+    assert_eq!(
+        when,
+        vec![
+            Some("backend:aur changed"),
+            Some("os:arch"),
+            Some("!dry-run"),
+            None
+        ]
+    );
+    // The predicate is not mistaken for a flag
+    assert_eq!(
+        config.lifecycle_actions.actions[0].action_type,
+        crate::config::kdl_modules::types::ActionType::Root
+    );
+}
+
+#[test]
+fn test_hooks_reject_backend_change_predicate_outside_post_sync() {
+    for hook in [
+        r#"pre-sync "echo" when="backend:aur changed""#,
+        r#"on-success "echo" when="not backend:aur changed""#,
+        r#"docker:post-install "echo" when="!backend:aur changed""#,
+    ] {
+        let kdl = format!("hooks {{\n    {}\n}}", hook);
+        let err = parse_kdl_content(&kdl).expect_err(hook).to_string();
+        assert!(err.contains("only available on post-sync hooks"), "{}", err);
+    }
+}
+
+#[test]
+fn test_package_aliases_global_and_backend_scoped() {
+    let kdl = r#"
+            aliases-pkg "python" "python-is-python3"
+            aliases-pkg:apt {
+                python "python3"
+                fd "fd-find"
+            }
+        "#;
+
+    let config = parse_kdl_content(kdl).unwrap();
+    assert_eq!(
+        config.package_aliases.get("python").map(String::as_str),
+        Some("python-is-python3")
+    );
+    let apt = &config.backend_package_aliases["apt"];
+    assert_eq!(apt.get("python").map(String::as_str), Some("python3"));
+    assert_eq!(apt.get("fd").map(String::as_str), Some("fd-find"));
+
+    assert!(parse_kdl_content(r#"aliases-pkg "python""#).is_err());
+}
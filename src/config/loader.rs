@@ -1,7 +1,10 @@
 mod import_context;
 mod merging;
+mod package_includes;
+mod parse_cache;
 mod path_resolution;
 mod selector_filter;
+mod stdin_root;
 
 use crate::config::kdl::{
     ConflictEntry, LifecycleConfig, McpConfig, PolicyConfig, ProjectMetadata, RawConfig,
@@ -9,32 +12,51 @@ use crate::config::kdl::{
 };
 use crate::core::types::{Backend, PackageId};
 use crate::error::{DeclarchError, Result};
+use crate::utils::paths;
 use import_context::ImportContext;
-use merging::merge_raw_config;
+use merging::{drop_profile_repeats, merge_raw_config};
+use package_includes::process_package_include;
+use parse_cache::ParseCache;
+pub use parse_cache::set_parse_cache_disabled;
 use path_resolution::{
-    parent_dir_of, resolve_backend_import_path, resolve_module_import_path,
+    expand_module_import, glob_import_matches, parent_dir_of, resolve_backend_import_path,
     resolve_primary_config_path,
 };
-use selector_filter::filter_content_by_selectors;
+use selector_filter::{cross_profile_repeats, filter_content_by_selectors};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use stdin_root::stdin_root_for;
+pub use stdin_root::{STDIN_CONFIG_NAME, set_stdin_root_config};
 
 #[derive(Debug, Clone, Default)]
 pub struct LoadSelectors {
-    pub profile: Option<String>,
+    /// Active profiles; blocks of every one of them are included (union)
+    pub profiles: Vec<String>,
     pub host: Option<String>,
 }
 
 impl LoadSelectors {
     fn normalized(&self) -> Self {
+        let mut profiles: Vec<String> = Vec::new();
+        for profile in &self.profiles {
+            let profile = profile.trim();
+            if !profile.is_empty() && !profiles.iter().any(|p| p == profile) {
+                profiles.push(profile.to_string());
+            }
+        }
         Self {
-            profile: normalize_selector(&self.profile),
+            profiles,
             host: normalize_selector(&self.host),
         }
     }
 
     fn is_empty(&self) -> bool {
-        self.profile.is_none() && self.host.is_none()
+        self.profiles.is_empty() && self.host.is_none()
+    }
+
+    /// Whether `profile "name" { ... }` blocks for `name` are active
+    pub fn has_profile(&self, name: &str) -> bool {
+        self.profiles.iter().any(|profile| profile == name)
     }
 }
 
@@ -52,6 +74,20 @@ pub struct MergedConfig {
     pub packages: HashMap<PackageId, Vec<PathBuf>>,
     /// Packages to exclude from sync
     pub excludes: Vec<String>,
+    /// Packages excluded for one backend only (`excludes:pip { setuptools }`)
+    pub backend_excludes: HashMap<String, HashSet<String>>,
+    /// Packages whose installed version is never compared (`ignore-version=#true`)
+    pub ignore_version: HashSet<PackageId>,
+    /// Packages that state keeps out of prune (`pinned=#true`)
+    pub pinned: HashSet<PackageId>,
+    /// Per-package install arguments (`extra-args="..."`); these install on their own
+    pub package_extra_args: HashMap<PackageId, String>,
+    /// Tags declared on each package (`tags="dev,editor"`), unioned across files
+    pub package_tags: HashMap<PackageId, HashSet<String>>,
+    /// Versions noted in package blocks (`neovim { version "0.9.5" }`)
+    pub package_versions: HashMap<PackageId, String>,
+    /// Why each package is declared (`neovim { reason "editor" }`)
+    pub package_reasons: HashMap<PackageId, String>,
     /// Project metadata (merged from first config with meta)
     pub project_metadata: Option<ProjectMetadata>,
     /// Mutually exclusive packages (accumulated from all configs)
@@ -62,6 +98,12 @@ pub struct MergedConfig {
     pub env: HashMap<String, Vec<String>>,
     /// Custom package sources (merged)
     pub package_sources: HashMap<String, Vec<String>>,
+    /// Source priorities per backend (merged, lowest wins)
+    pub package_source_priorities: HashMap<String, HashMap<String, i64>>,
+    /// Global package aliases (merged, later configs win)
+    pub package_aliases: HashMap<String, String>,
+    /// Backend-scoped package aliases (merged, later configs win)
+    pub backend_package_aliases: HashMap<String, HashMap<String, String>>,
     /// Package lifecycle policies (merged from last config)
     pub policy: Option<PolicyConfig>,
     /// Pre/post sync hooks (accumulated from all configs)
@@ -70,12 +112,16 @@ pub struct MergedConfig {
     pub editor: Option<String>,
     /// Backend definitions loaded from imports
     pub backends: Vec<crate::backends::config::BackendConfig>,
+    /// Alias -> primary backend name, from `alias` entries of `backends`
+    pub backend_aliases: HashMap<String, String>,
     /// Source files for each backend definition in load order
     pub backend_sources: HashMap<String, Vec<PathBuf>>,
     /// Experimental feature flags merged from all config files
     pub experimental: HashSet<String>,
     /// MCP policy merged from configs
     pub mcp: Option<McpConfig>,
+    /// Config files loaded, root first, in load order
+    pub config_files: Vec<PathBuf>,
 }
 
 impl MergedConfig {
@@ -90,6 +136,175 @@ impl MergedConfig {
             .collect()
     }
 
+    /// Packages declared in more than one distinct source file, sorted by package.
+    pub fn get_shadowed(&self) -> Vec<(&PackageId, Vec<&PathBuf>)> {
+        let mut shadowed: Vec<_> = self
+            .packages
+            .iter()
+            .filter_map(|(pkg, sources)| {
+                let mut distinct: Vec<&PathBuf> = Vec::new();
+                for source in sources {
+                    if !distinct.contains(&source) {
+                        distinct.push(source);
+                    }
+                }
+                (distinct.len() > 1).then_some((pkg, distinct))
+            })
+            .collect();
+        shadowed.sort_by_key(|(pkg, _)| pkg.to_string());
+        shadowed
+    }
+
+    /// Merge packages from an extra module, keeping every source path per package.
+    pub fn extend_packages(&mut self, packages: HashMap<PackageId, Vec<PathBuf>>) {
+        for (pkg, sources) in packages {
+            self.packages.entry(pkg).or_default().extend(sources);
+        }
+    }
+
+    /// Program elevating mutating backend commands and root hooks:
+    /// `DECLARCH_SUDO`, then `policy { escalation }`, then `sudo`.
+    pub fn escalation_program(&self) -> String {
+        crate::utils::platform::escalation_program(
+            self.policy
+                .as_ref()
+                .and_then(|policy| policy.escalation.as_deref()),
+        )
+    }
+
+    /// Give root hooks the policy escalation program; hooks only see their
+    /// own `LifecycleConfig`.
+    fn attach_escalation_to_hooks(&mut self) {
+        if let Some(hooks) = self.lifecycle_actions.as_mut() {
+            hooks.escalation = self
+                .policy
+                .as_ref()
+                .and_then(|policy| policy.escalation.clone());
+        }
+    }
+
+    /// Merge per-backend exclude lists, keeping names from every config.
+    pub fn extend_backend_excludes(&mut self, excludes: HashMap<String, HashSet<String>>) {
+        for (backend, names) in excludes {
+            self.backend_excludes
+                .entry(backend)
+                .or_default()
+                .extend(names);
+        }
+    }
+
+    /// Merge an extra module (`--modules`) loaded as its own root config:
+    /// its packages, excludes and per-package metadata.
+    pub fn merge_module(&mut self, module: MergedConfig) {
+        self.extend_packages(module.packages);
+        self.excludes.extend(module.excludes);
+        self.extend_backend_excludes(module.backend_excludes);
+        self.ignore_version.extend(module.ignore_version);
+        self.pinned.extend(module.pinned);
+        self.package_extra_args.extend(module.package_extra_args);
+        self.package_versions.extend(module.package_versions);
+        self.package_reasons.extend(module.package_reasons);
+        for (pkg, tags) in module.package_tags {
+            self.package_tags.entry(pkg).or_default().extend(tags);
+        }
+    }
+
+    /// Alias -> primary backend name, from `alias` entries of loaded backends.
+    pub fn backend_alias_map(&self) -> HashMap<String, String> {
+        self.backends
+            .iter()
+            .flat_map(|backend| {
+                let primary = backend.name.to_lowercase();
+                backend
+                    .aliases
+                    .iter()
+                    .map(move |alias| (alias.to_lowercase(), primary.clone()))
+            })
+            .collect()
+    }
+
+    /// The primary backend for `backend` when it is an alias, else `backend` itself.
+    pub fn resolve_backend(&self, backend: &Backend) -> Backend {
+        match self.backend_aliases.get(backend.name()) {
+            Some(primary) => Backend(primary.clone()),
+            None => backend.clone(),
+        }
+    }
+
+    /// Record the alias map of the loaded backends and rewrite aliased
+    /// declarations to their primary backend, so `paru:x` and `aur:x`
+    /// collapse into one declaration when `paru` is an alias of `aur`.
+    pub fn canonicalize_backend_aliases(&mut self) {
+        self.backend_aliases = self.backend_alias_map();
+        if self.backend_aliases.is_empty() {
+            return;
+        }
+        let aliases = self.backend_aliases.clone();
+        let canonical = |pkg: PackageId| match aliases.get(pkg.backend.name()) {
+            Some(primary) => PackageId {
+                name: pkg.name,
+                backend: Backend(primary.clone()),
+            },
+            None => pkg,
+        };
+
+        for (pkg, sources) in std::mem::take(&mut self.packages) {
+            self.packages
+                .entry(canonical(pkg))
+                .or_default()
+                .extend(sources);
+        }
+        self.ignore_version = std::mem::take(&mut self.ignore_version)
+            .into_iter()
+            .map(canonical)
+            .collect();
+        self.pinned = std::mem::take(&mut self.pinned)
+            .into_iter()
+            .map(canonical)
+            .collect();
+        self.package_extra_args = std::mem::take(&mut self.package_extra_args)
+            .into_iter()
+            .map(|(pkg, args)| (canonical(pkg), args))
+            .collect();
+        self.package_versions = std::mem::take(&mut self.package_versions)
+            .into_iter()
+            .map(|(pkg, version)| (canonical(pkg), version))
+            .collect();
+        self.package_reasons = std::mem::take(&mut self.package_reasons)
+            .into_iter()
+            .map(|(pkg, reason)| (canonical(pkg), reason))
+            .collect();
+        for (pkg, tags) in std::mem::take(&mut self.package_tags) {
+            self.package_tags
+                .entry(canonical(pkg))
+                .or_default()
+                .extend(tags);
+        }
+        for (backend, names) in std::mem::take(&mut self.backend_excludes) {
+            let backend = aliases.get(&backend).cloned().unwrap_or(backend);
+            self.backend_excludes
+                .entry(backend)
+                .or_default()
+                .extend(names);
+        }
+        for (backend, scoped) in std::mem::take(&mut self.backend_package_aliases) {
+            let backend = aliases.get(&backend).cloned().unwrap_or(backend);
+            self.backend_package_aliases
+                .entry(backend)
+                .or_default()
+                .extend(scoped);
+        }
+    }
+
+    /// Whether `pkg` is excluded, globally or for its backend (`excludes:<backend>`)
+    pub fn is_excluded(&self, pkg: &PackageId) -> bool {
+        self.excludes.contains(&pkg.name)
+            || self
+                .backend_excludes
+                .get(pkg.backend.name())
+                .is_some_and(|names| names.contains(&pkg.name))
+    }
+
     /// Find packages with the same name across different backends
     pub fn get_cross_backend_conflicts(&self) -> Vec<(String, Vec<Backend>)> {
         let mut name_to_backends: HashMap<String, Vec<Backend>> = HashMap::new();
@@ -149,16 +364,128 @@ pub fn load_root_config(path: &Path) -> Result<MergedConfig> {
 pub fn load_root_config_with_selectors(
     path: &Path,
     selectors: &LoadSelectors,
+) -> Result<MergedConfig> {
+    if let Some(content) = stdin_root_for(path) {
+        let base_dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+        return load_root_config_from_str(content, base_dir, selectors);
+    }
+
+    let mut cache = ParseCache::open();
+    let merged = load_root_with_cache(path, selectors, &mut cache)?;
+    cache.save();
+    Ok(merged)
+}
+
+/// Load a root config given as text rather than a file (`--config -`).
+///
+/// Relative imports resolve against `base_dir`; without one they are an
+/// error. Unlike file configs, an import that does not exist is an error
+/// too, since there is no config directory to make it optional against.
+pub fn load_root_config_from_str(
+    content: &str,
+    base_dir: Option<&Path>,
+    selectors: &LoadSelectors,
 ) -> Result<MergedConfig> {
     let mut merged = MergedConfig::default();
     let mut context = ImportContext::new();
+    let mut cache = ParseCache::open();
     let normalized = selectors.normalized();
 
-    recursive_load(path, &mut merged, &mut context, &normalized)?;
+    let filtered_content = filter_content_by_selectors(content, &normalized)?;
+    let raw = parse_kdl_content_with_path(&filtered_content, Some(STDIN_CONFIG_NAME))?;
+    let root_path = base_dir.unwrap_or(Path::new("")).join(STDIN_CONFIG_NAME);
+    merged.config_files.push(root_path.clone());
+    let pending_imports = merge_raw_config(&mut merged, raw, &root_path);
+    drop_profile_repeats(
+        &mut merged,
+        &cross_profile_repeats(content, &normalized)?,
+        &root_path,
+    );
+
+    let base_dir = match base_dir {
+        Some(dir) => dir,
+        None => {
+            let relative = pending_imports
+                .imports
+                .iter()
+                .chain(&pending_imports.backend_imports)
+                .chain(pending_imports.package_includes.iter().map(|i| &i.path))
+                .find(|import| !import.starts_with('/') && !import.starts_with("~/"));
+            if let Some(import) = relative {
+                return Err(DeclarchError::ConfigError(format!(
+                    "Config from stdin imports '{}' but has no directory to resolve it \
+                     against; use an absolute path",
+                    import
+                )));
+            }
+            // Only absolute imports remain, which ignore the base
+            Path::new("")
+        }
+    };
+
+    for backend_import in pending_imports.backend_imports {
+        process_backend_import(&mut merged, base_dir, &backend_import)?;
+    }
+    for include in &pending_imports.package_includes {
+        process_package_include(&mut merged, base_dir, &root_path, include)?;
+    }
+    for import_str in pending_imports.imports {
+        for import_path in expand_module_import(base_dir, &import_str, |_| false)? {
+            match recursive_load(
+                &import_path,
+                &mut merged,
+                &mut context,
+                &normalized,
+                &mut cache,
+            ) {
+                Err(DeclarchError::ConfigNotFound { path }) => {
+                    return Err(DeclarchError::ConfigError(format!(
+                        "Config from stdin imports '{}', which does not exist (resolved to {})",
+                        import_str,
+                        path.display()
+                    )));
+                }
+                result => result?,
+            }
+        }
+    }
+    cache.save();
+
+    merged.canonicalize_backend_aliases();
+    merged.attach_escalation_to_hooks();
+
+    Ok(merged)
+}
+
+fn load_root_with_cache(
+    path: &Path,
+    selectors: &LoadSelectors,
+    cache: &mut ParseCache,
+) -> Result<MergedConfig> {
+    let mut merged = MergedConfig::default();
+    let mut context = ImportContext::new();
+    let normalized = selectors.normalized();
+
+    recursive_load(path, &mut merged, &mut context, &normalized, cache)?;
+
+    // Packages were parsed before backend definitions were known
+    merged.canonicalize_backend_aliases();
+    merged.attach_escalation_to_hooks();
 
     Ok(merged)
 }
 
+/// Whether the root config at `path` can be loaded: the file exists, or it is
+/// the `--config -` placeholder served from stdin.
+pub fn root_config_available(path: &Path) -> bool {
+    stdin_root_for(path).is_some() || path.exists()
+}
+
+/// Whether `path` is the `--config -` placeholder, which cannot be edited.
+pub fn is_stdin_root(path: &Path) -> bool {
+    stdin_root_for(path).is_some()
+}
+
 /// Filesystem-based configuration loader implementing the ConfigLoader trait
 pub struct FilesystemConfigLoader;
 
@@ -168,7 +495,7 @@ impl crate::traits::ConfigLoader for FilesystemConfigLoader {
     }
 
     fn exists(&self, path: &Path) -> bool {
-        path.exists()
+        root_config_available(path)
     }
 }
 
@@ -177,20 +504,23 @@ fn recursive_load(
     merged: &mut MergedConfig,
     context: &mut ImportContext,
     selectors: &LoadSelectors,
+    cache: &mut ParseCache,
 ) -> Result<()> {
     let canonical_path = resolve_primary_config_path(path)?;
 
-    // Check for circular imports using the context
-    if context.contains(&canonical_path) {
-        // Already processed in a different branch, skip
+    // A file imported from two branches (diamond) is loaded once; a file still
+    // being loaded further up the chain is a cycle, which `push` reports
+    if context.contains(&canonical_path) && !context.is_loading(&canonical_path) {
         return Ok(());
     }
 
     // Add to context for cycle detection
     context.push(canonical_path.clone())?;
+    merged.config_files.push(canonical_path.clone());
 
-    let raw = load_raw_config(&canonical_path, selectors)?;
+    let (raw, profile_repeats) = load_raw_config(&canonical_path, selectors, cache)?;
     let pending_imports = merge_raw_config(merged, raw, &canonical_path);
+    drop_profile_repeats(merged, &profile_repeats, &canonical_path);
 
     // Process backend imports (NEW: explicit backend loading)
     let parent_dir = parent_dir_of(&canonical_path)?;
@@ -199,18 +529,31 @@ fn recursive_load(
         process_backend_import(merged, parent_dir.as_path(), &backend_import)?;
     }
 
+    for include in &pending_imports.package_includes {
+        if let Err(e) =
+            process_package_include(merged, parent_dir.as_path(), &canonical_path, include)
+        {
+            context.pop();
+            return Err(e);
+        }
+    }
+
     // Process regular imports (modules)
     for import_str in pending_imports.imports {
-        let import_path = resolve_module_import_path(parent_dir.as_path(), &import_str)?;
-
-        match recursive_load(&import_path, merged, context, selectors) {
-            Ok(()) => {}
-            Err(DeclarchError::ConfigNotFound { .. }) => {
-                // Silently skip missing imports
-            }
-            Err(e) => {
-                context.pop();
-                return Err(e);
+        let import_paths = expand_module_import(parent_dir.as_path(), &import_str, |path| {
+            context.is_loading(path)
+        })?;
+
+        for import_path in import_paths {
+            match recursive_load(&import_path, merged, context, selectors, cache) {
+                Ok(()) => {}
+                Err(DeclarchError::ConfigNotFound { .. }) => {
+                    // Silently skip missing imports
+                }
+                Err(e) => {
+                    context.pop();
+                    return Err(e);
+                }
             }
         }
     }
@@ -221,11 +564,25 @@ fn recursive_load(
     Ok(())
 }
 
-fn load_raw_config(path: &Path, selectors: &LoadSelectors) -> Result<RawConfig> {
+/// Parse `path` with `selectors` applied, along with the packages its active
+/// profiles repeat (see [`cross_profile_repeats`]).
+fn load_raw_config(
+    path: &Path,
+    selectors: &LoadSelectors,
+    cache: &mut ParseCache,
+) -> Result<(RawConfig, Vec<PackageId>)> {
     let content = std::fs::read_to_string(path)?;
     let filtered_content = filter_content_by_selectors(&content, selectors)?;
+    let profile_repeats = cross_profile_repeats(&content, selectors)?;
+    let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    if let Some(raw) = cache.get(path, mtime, &filtered_content) {
+        return Ok((raw, profile_repeats));
+    }
+
     let file_path_str = path.display().to_string();
-    parse_kdl_content_with_path(&filtered_content, Some(&file_path_str))
+    let raw = parse_kdl_content_with_path(&filtered_content, Some(&file_path_str))?;
+    cache.insert(path, mtime, &filtered_content, &raw);
+    Ok((raw, profile_repeats))
 }
 
 fn process_backend_import(
@@ -235,37 +592,23 @@ fn process_backend_import(
 ) -> Result<()> {
     let backend_path = resolve_backend_import_path(parent_dir, backend_import)?;
 
-    if backend_path.exists() {
-        match crate::backends::user_parser::load_user_backends(&backend_path) {
-            Ok(backends) => {
-                for backend in backends {
-                    // Check for duplicate backend names
-                    if merged.backends.iter().any(|b| b.name == backend.name) {
-                        eprintln!(
-                            "Warning: Duplicate backend '{}' from '{}'",
-                            backend.name,
-                            backend_path.display()
-                        );
-                    }
-                    merged
-                        .backend_sources
-                        .entry(backend.name.clone())
-                        .or_default()
-                        .push(backend_path.clone());
-                    merged.backends.push(backend);
-                }
-            }
-            Err(e) => {
-                eprintln!(
-                    "Warning: Failed to load backends from '{}': {}",
-                    backend_path.display(),
-                    e
-                );
+    if paths::is_glob(backend_import) {
+        for path in glob_import_matches(&backend_path, backend_import)? {
+            // Config files matched by a broad pattern are not backend definitions
+            let loaded = std::fs::canonicalize(&path)
+                .is_ok_and(|canonical| merged.config_files.contains(&canonical));
+            if !loaded {
+                load_backend_import(merged, &path);
             }
         }
         return Ok(());
     }
 
+    if backend_path.exists() {
+        load_backend_import(merged, &backend_path);
+        return Ok(());
+    }
+
     Err(DeclarchError::ConfigError(format!(
         "Backend import not found: '{}' (resolved to: {})",
         backend_import,
@@ -273,5 +616,35 @@ fn process_backend_import(
     )))
 }
 
+fn load_backend_import(merged: &mut MergedConfig, backend_path: &Path) {
+    match crate::backends::user_parser::load_user_backends(backend_path) {
+        Ok(backends) => {
+            for backend in backends {
+                // Check for duplicate backend names
+                if merged.backends.iter().any(|b| b.name == backend.name) {
+                    eprintln!(
+                        "Warning: Duplicate backend '{}' from '{}'",
+                        backend.name,
+                        backend_path.display()
+                    );
+                }
+                merged
+                    .backend_sources
+                    .entry(backend.name.clone())
+                    .or_default()
+                    .push(backend_path.to_path_buf());
+                merged.backends.push(backend);
+            }
+        }
+        Err(e) => {
+            eprintln!(
+                "Warning: Failed to load backends from '{}': {}",
+                backend_path.display(),
+                e
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -41,6 +41,11 @@ impl ImportContext {
     pub(super) fn contains(&self, path: &Path) -> bool {
         self.visited.contains(path)
     }
+
+    /// Whether `path` is on the current import chain (not yet finished).
+    pub(super) fn is_loading(&self, path: &Path) -> bool {
+        self.stack.iter().any(|p| p == path)
+    }
 }
 
 #[cfg(test)]
@@ -72,5 +77,6 @@ mod tests {
         ctx.pop();
 
         assert!(ctx.contains(&a));
+        assert!(!ctx.is_loading(&a));
     }
 }
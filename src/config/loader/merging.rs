@@ -1,11 +1,28 @@
 use super::MergedConfig;
-use crate::config::kdl::{McpConfig, RawConfig};
+use crate::config::kdl::{McpConfig, PackageListInclude, RawConfig};
 use crate::core::types::{Backend, PackageId};
 use std::path::Path;
 
 pub(super) struct PendingImports {
     pub(super) imports: Vec<String>,
     pub(super) backend_imports: Vec<String>,
+    pub(super) package_includes: Vec<PackageListInclude>,
+}
+
+/// Drop one `source` entry per repeat, so a package shared by several active
+/// profiles of one file counts as a single declaration.
+pub(super) fn drop_profile_repeats(
+    merged: &mut MergedConfig,
+    repeats: &[PackageId],
+    source: &Path,
+) {
+    for pkg in repeats {
+        if let Some(sources) = merged.packages.get_mut(pkg)
+            && let Some(index) = sources.iter().rposition(|path| path == source)
+        {
+            sources.remove(index);
+        }
+    }
 }
 
 pub(super) fn merge_raw_config(
@@ -18,14 +35,19 @@ pub(super) fn merge_raw_config(
         editor,
         packages_by_backend,
         excludes,
+        backend_excludes,
         project_metadata,
         conflicts,
         backend_options,
         env,
         package_sources,
+        package_source_priorities,
+        package_aliases,
+        backend_package_aliases,
         policy,
         lifecycle_actions,
         backend_imports,
+        package_includes,
         experimental,
         mcp,
     } = raw;
@@ -36,6 +58,30 @@ pub(super) fn merge_raw_config(
                 name: pkg_entry.name,
                 backend: Backend::from(backend_name.clone()),
             };
+            if pkg_entry.ignore_version {
+                merged.ignore_version.insert(pkg_id.clone());
+            }
+            if pkg_entry.pinned {
+                merged.pinned.insert(pkg_id.clone());
+            }
+            if let Some(args) = &pkg_entry.extra_args {
+                merged
+                    .package_extra_args
+                    .insert(pkg_id.clone(), args.clone());
+            }
+            if let Some(version) = pkg_entry.version {
+                merged.package_versions.insert(pkg_id.clone(), version);
+            }
+            if let Some(reason) = pkg_entry.reason {
+                merged.package_reasons.insert(pkg_id.clone(), reason);
+            }
+            if !pkg_entry.tags.is_empty() {
+                merged
+                    .package_tags
+                    .entry(pkg_id.clone())
+                    .or_default()
+                    .extend(pkg_entry.tags);
+            }
             merged
                 .packages
                 .entry(pkg_id)
@@ -45,6 +91,7 @@ pub(super) fn merge_raw_config(
     }
 
     merged.excludes.extend(excludes);
+    merged.extend_backend_excludes(backend_excludes);
 
     if merged.project_metadata.is_none() {
         let has_description = project_metadata.description.is_some();
@@ -80,12 +127,39 @@ pub(super) fn merge_raw_config(
             .extend(repos);
     }
 
+    for (backend, priorities) in package_source_priorities {
+        let merged_priorities = merged.package_source_priorities.entry(backend).or_default();
+        for (source, priority) in priorities {
+            merged_priorities
+                .entry(source)
+                .and_modify(|existing| *existing = (*existing).min(priority))
+                .or_insert(priority);
+        }
+    }
+
+    merged.package_aliases.extend(package_aliases);
+    for (backend, aliases) in backend_package_aliases {
+        merged
+            .backend_package_aliases
+            .entry(backend)
+            .or_default()
+            .extend(aliases);
+    }
+
     if policy.protected.iter().any(|p| !p.is_empty())
         || policy.orphans.is_some()
+        || policy.orphan_deps.is_some()
         || policy.require_backend.is_some()
         || policy.forbid_hooks.is_some()
         || policy.on_duplicate.is_some()
         || policy.on_conflict.is_some()
+        || policy.block_partial_upgrade.is_some()
+        || policy.noconfirm.is_some()
+        || policy.auto_confirm.is_some()
+        || policy.verify_installs.is_some()
+        || policy.run_hooks.is_some()
+        || policy.escalation.is_some()
+        || !policy.registries.is_empty()
     {
         merged.policy = Some(policy);
     }
@@ -104,6 +178,7 @@ pub(super) fn merge_raw_config(
     PendingImports {
         imports,
         backend_imports,
+        package_includes,
     }
 }
 
@@ -0,0 +1,122 @@
+//! Plain-text package list includes
+//!
+//! `include "packages/work.txt" backend="pip"` reads one package per line,
+//! ignoring blank lines and `#` comments. A list may pull in another list
+//! with an `include <path>` line, resolved relative to that list file.
+
+use super::MergedConfig;
+use super::import_context::ImportContext;
+use super::path_resolution::{parent_dir_of, resolve_backend_import_path};
+use crate::config::kdl::PackageListInclude;
+use crate::core::types::{Backend, PackageId};
+use crate::error::{DeclarchError, Result};
+use std::path::Path;
+
+#[derive(Debug, PartialEq, Eq)]
+enum ListEntry {
+    Package(String),
+    Include(String),
+}
+
+pub(super) fn process_package_include(
+    merged: &mut MergedConfig,
+    parent_dir: &Path,
+    referenced_from: &Path,
+    include: &PackageListInclude,
+) -> Result<()> {
+    let mut context = ImportContext::new();
+    load_package_list(
+        merged,
+        parent_dir,
+        referenced_from,
+        &include.path,
+        &include.backend,
+        &mut context,
+    )
+}
+
+fn load_package_list(
+    merged: &mut MergedConfig,
+    parent_dir: &Path,
+    referenced_from: &Path,
+    include_path: &str,
+    backend: &str,
+    context: &mut ImportContext,
+) -> Result<()> {
+    let path = resolve_backend_import_path(parent_dir, include_path)?;
+    let canonical_path = std::fs::canonicalize(&path).map_err(|_| {
+        DeclarchError::ConfigError(format!(
+            "Package list include not found: '{}' (resolved to: {}, referenced from: {})",
+            include_path,
+            path.display(),
+            referenced_from.display()
+        ))
+    })?;
+
+    // Same stack-based guard as module imports: a list including itself (directly or not) fails.
+    context.push(canonical_path.clone())?;
+
+    let content = std::fs::read_to_string(&canonical_path).map_err(|e| DeclarchError::IoError {
+        path: canonical_path.clone(),
+        source: e,
+    })?;
+    let list_dir = parent_dir_of(&canonical_path)?;
+
+    for entry in parse_package_list(&content) {
+        match entry {
+            ListEntry::Package(name) => {
+                merged
+                    .packages
+                    .entry(PackageId {
+                        name,
+                        backend: Backend::from(backend),
+                    })
+                    .or_default()
+                    .push(canonical_path.clone());
+            }
+            ListEntry::Include(nested) => {
+                load_package_list(
+                    merged,
+                    list_dir.as_path(),
+                    &canonical_path,
+                    &nested,
+                    backend,
+                    context,
+                )?;
+            }
+        }
+    }
+
+    context.pop();
+    Ok(())
+}
+
+fn parse_package_list(content: &str) -> Vec<ListEntry> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.strip_prefix("include ") {
+            Some(nested) => ListEntry::Include(nested.trim().trim_matches('"').to_string()),
+            None => ListEntry::Package(line.to_string()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ListEntry, parse_package_list};
+
+    #[test]
+    fn parse_package_list_skips_comments_and_blanks() {
+        let content = "# work tools\nrequests\n\n  black  # formatter\ninclude \"extra.txt\"\n";
+        assert_eq!(
+            parse_package_list(content),
+            vec![
+                ListEntry::Package("requests".to_string()),
+                ListEntry::Package("black".to_string()),
+                ListEntry::Include("extra.txt".to_string()),
+            ]
+        );
+    }
+}
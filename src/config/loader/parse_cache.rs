@@ -0,0 +1,147 @@
+//! On-disk cache of parsed config files
+//!
+//! Each file's parse result is stored with its mtime and a hash of its
+//! selector-filtered content; both must match for a hit. Entries are per
+//! file, so an edited import is reparsed even when the file importing it is
+//! unchanged. The cache is best-effort: an unreadable cache, or one written
+//! by another release, is ignored and rebuilt.
+
+use crate::config::kdl::RawConfig;
+use crate::utils::paths;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::SystemTime;
+
+const CACHE_FILE_NAME: &str = "parse-cache.json";
+
+/// Tests never touch the user's cache unless they opt in with `ParseCache::at`.
+static PARSE_CACHE_DISABLED: AtomicBool = AtomicBool::new(cfg!(test));
+
+/// Bypass the parse cache for this process (`--no-cache`).
+pub fn set_parse_cache_disabled(disabled: bool) {
+    PARSE_CACHE_DISABLED.store(disabled, Ordering::Relaxed);
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    version: String,
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    mtime: Option<SystemTime>,
+    hash: u64,
+    raw: RawConfig,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct ParseCache {
+    /// Where the cache is persisted; `None` disables caching entirely
+    location: Option<PathBuf>,
+    file: CacheFile,
+    dirty: bool,
+}
+
+impl ParseCache {
+    /// The user's parse cache, or a disabled cache with `--no-cache`.
+    pub(super) fn open() -> Self {
+        if PARSE_CACHE_DISABLED.load(Ordering::Relaxed) {
+            return Self::default();
+        }
+        match paths::cache_dir() {
+            Ok(dir) => Self::at(dir.join(CACHE_FILE_NAME)),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// A cache persisted at `location`.
+    pub(super) fn at(location: PathBuf) -> Self {
+        let file = fs::read_to_string(&location)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<CacheFile>(&raw).ok())
+            .filter(|file| file.version == env!("CARGO_PKG_VERSION"))
+            .unwrap_or_default();
+        Self {
+            location: Some(location),
+            file,
+            dirty: false,
+        }
+    }
+
+    fn enabled(&self) -> bool {
+        self.location.is_some()
+    }
+
+    /// Cached parse of `path` if its mtime and filtered content are unchanged.
+    pub(super) fn get(
+        &self,
+        path: &Path,
+        mtime: Option<SystemTime>,
+        content: &str,
+    ) -> Option<RawConfig> {
+        if !self.enabled() {
+            return None;
+        }
+        let entry = self.file.entries.get(path)?;
+        (entry.mtime.is_some() && entry.mtime == mtime && entry.hash == content_hash(content))
+            .then(|| entry.raw.clone())
+    }
+
+    pub(super) fn insert(
+        &mut self,
+        path: &Path,
+        mtime: Option<SystemTime>,
+        content: &str,
+        raw: &RawConfig,
+    ) {
+        if !self.enabled() {
+            return;
+        }
+        self.file.entries.insert(
+            path.to_path_buf(),
+            CacheEntry {
+                mtime,
+                hash: content_hash(content),
+                raw: raw.clone(),
+            },
+        );
+        self.dirty = true;
+    }
+
+    /// Persist new entries, dropping those for files that no longer exist.
+    pub(super) fn save(mut self) {
+        let Some(location) = self.location.take() else {
+            return;
+        };
+        if !self.dirty {
+            return;
+        }
+        self.file.version = env!("CARGO_PKG_VERSION").to_string();
+        self.file.entries.retain(|path, _| path.exists());
+
+        let Ok(raw) = serde_json::to_string(&self.file) else {
+            return;
+        };
+        if let Some(parent) = location.parent()
+            && fs::create_dir_all(parent).is_err()
+        {
+            return;
+        }
+        // Write-then-rename so a concurrent reader never sees a partial file
+        let tmp = location.with_extension(format!("json.{}.tmp", std::process::id()));
+        if fs::write(&tmp, raw).is_ok() && fs::rename(&tmp, &location).is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+    }
+}
+
+/// FNV-1a: stable across builds, unlike `DefaultHasher`.
+fn content_hash(content: &str) -> u64 {
+    content.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
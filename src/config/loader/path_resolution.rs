@@ -1,5 +1,6 @@
 use crate::error::{DeclarchError, Result};
-use crate::utils::paths::expand_home;
+use crate::ui;
+use crate::utils::paths::{self, expand_home};
 use std::path::{Path, PathBuf};
 
 pub(super) fn resolve_primary_config_path(path: &Path) -> Result<PathBuf> {
@@ -57,6 +58,43 @@ pub(super) fn resolve_module_import_path(parent_dir: &Path, import_str: &str) ->
     Ok(parent_dir.join(import_str))
 }
 
+/// Files a module import names: the import itself, or every file matching a
+/// glob import (`modules/*.kdl`) in sorted order.
+///
+/// Glob matches for which `skip` holds (files on the current import chain,
+/// such as the root config) are left out.
+pub(super) fn expand_module_import(
+    parent_dir: &Path,
+    import_str: &str,
+    skip: impl Fn(&Path) -> bool,
+) -> Result<Vec<PathBuf>> {
+    let import_path = resolve_module_import_path(parent_dir, import_str)?;
+    if !paths::is_glob(import_str) {
+        return Ok(vec![import_path]);
+    }
+
+    let matches = glob_import_matches(&import_path, import_str)?;
+    Ok(matches
+        .into_iter()
+        .filter(|path| !std::fs::canonicalize(path).is_ok_and(|canonical| skip(&canonical)))
+        .collect())
+}
+
+/// Files matching a glob import, warning when there are none.
+pub(super) fn glob_import_matches(pattern: &Path, import_str: &str) -> Result<Vec<PathBuf>> {
+    let pattern = expand_home(pattern)
+        .map_err(|e| DeclarchError::Other(format!("Path expansion error: {}", e)))?;
+    let matches = paths::glob_files(&pattern)?;
+    if matches.is_empty() {
+        ui::warning(&format!(
+            "Import '{}' matched no files ({})",
+            import_str,
+            pattern.display()
+        ));
+    }
+    Ok(matches)
+}
+
 #[cfg(test)]
 mod tests {
     use super::{resolve_module_import_path, resolve_primary_config_path};
@@ -1,6 +1,9 @@
 use super::LoadSelectors;
+use crate::config::kdl::parse_kdl_content;
+use crate::core::types::{Backend, PackageId};
 use crate::error::{DeclarchError, Result};
 use kdl::{KdlDocument, KdlNode};
+use std::collections::{HashMap, HashSet};
 
 pub(super) fn filter_content_by_selectors(
     content: &str,
@@ -19,11 +22,7 @@ pub(super) fn filter_content_by_selectors(
     for node in doc.nodes() {
         match node.name().value() {
             "profile" => {
-                if selectors
-                    .profile
-                    .as_ref()
-                    .zip(selector_name(node))
-                    .is_some_and(|(selected, current)| selected == &current)
+                if selector_name(node).is_some_and(|current| selectors.has_profile(&current))
                     && let Some(children) = node.children()
                 {
                     for child in children.nodes() {
@@ -56,6 +55,62 @@ pub(super) fn filter_content_by_selectors(
     Ok(output)
 }
 
+/// Packages declared by more than one active profile, once per extra profile.
+///
+/// Active profiles are a union, so a package several of them declare is one
+/// declaration; the loader drops these repeats from the package's sources.
+/// Repeats inside a single profile are left alone and still lint as duplicates.
+pub(super) fn cross_profile_repeats(
+    content: &str,
+    selectors: &LoadSelectors,
+) -> Result<Vec<PackageId>> {
+    if selectors.profiles.len() < 2 {
+        return Ok(Vec::new());
+    }
+
+    let doc: KdlDocument = content
+        .parse()
+        .map_err(|e: kdl::KdlError| DeclarchError::ConfigError(format!("Invalid KDL: {}", e)))?;
+
+    let mut per_profile: HashMap<String, HashSet<PackageId>> = HashMap::new();
+    for node in doc.nodes() {
+        if node.name().value() != "profile" {
+            continue;
+        }
+        let Some(profile) = selector_name(node).filter(|name| selectors.has_profile(name)) else {
+            continue;
+        };
+        let Some(children) = node.children() else {
+            continue;
+        };
+        let block: String = children
+            .nodes()
+            .iter()
+            .map(|child| format!("{}\n", child))
+            .collect();
+        let declared = per_profile.entry(profile).or_default();
+        for (backend, packages) in parse_kdl_content(&block)?.packages_by_backend {
+            for entry in packages {
+                declared.insert(PackageId {
+                    name: entry.name,
+                    backend: Backend::from(backend.clone()),
+                });
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut repeats = Vec::new();
+    for declared in per_profile.into_values() {
+        for pkg in declared {
+            if !seen.insert(pkg.clone()) {
+                repeats.push(pkg);
+            }
+        }
+    }
+    Ok(repeats)
+}
+
 fn selector_name(node: &KdlNode) -> Option<String> {
     node.entries()
         .first()
@@ -0,0 +1,28 @@
+//! Root config read from stdin (`--config -`)
+//!
+//! The content is kept for the whole process and served in place of the file
+//! at the placeholder path, so every command that loads the root config sees it.
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// File name of the placeholder root path; never exists on disk.
+pub const STDIN_CONFIG_NAME: &str = "<stdin>";
+
+static STDIN_ROOT: OnceLock<String> = OnceLock::new();
+
+/// Serve `content` as the root config for this process.
+pub fn set_stdin_root_config(content: String) {
+    let _ = STDIN_ROOT.set(content);
+}
+
+/// The stdin content, when `path` is the placeholder root.
+pub(super) fn stdin_root_for(path: &Path) -> Option<&'static str> {
+    let is_placeholder = path
+        .file_name()
+        .is_some_and(|name| name == STDIN_CONFIG_NAME);
+    STDIN_ROOT
+        .get()
+        .filter(|_| is_placeholder)
+        .map(String::as_str)
+}
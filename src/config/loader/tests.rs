@@ -27,7 +27,7 @@ host "vps-1" {
     let out = filter_content_by_selectors(
         content,
         &LoadSelectors {
-            profile: Some("desktop".to_string()),
+            profiles: vec!["desktop".to_string()],
             host: Some("vps-1".to_string()),
         },
     )
@@ -39,3 +39,422 @@ host "vps-1" {
     assert!(!out.contains("profile \"desktop\""));
     assert!(!out.contains("host \"vps-1\""));
 }
+
+fn declared_names(config: &MergedConfig) -> Vec<String> {
+    let mut names: Vec<String> = config.packages.keys().map(|p| p.name.clone()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn active_profiles_union_their_packages() {
+    let content = r#"
+pkg { aur { git } }
+profile "gaming" {
+  pkg { aur { steam } }
+}
+profile "dev" {
+  pkg { aur { neovim } }
+}
+profile "work" {
+  pkg { aur { slack } }
+}
+"#;
+    let selectors = |profiles: &[&str]| LoadSelectors {
+        profiles: profiles.iter().map(ToString::to_string).collect(),
+        host: None,
+    };
+
+    let single = load_root_config_from_str(content, None, &selectors(&["gaming"])).unwrap();
+    assert_eq!(declared_names(&single), vec!["git", "steam"]);
+
+    let both = load_root_config_from_str(content, None, &selectors(&["gaming", " dev ", "gaming"]))
+        .unwrap();
+    assert_eq!(declared_names(&both), vec!["git", "neovim", "steam"]);
+
+    let none = load_root_config_from_str(content, None, &selectors(&[])).unwrap();
+    assert_eq!(declared_names(&none), vec!["git"]);
+}
+
+#[test]
+fn package_guarded_by_two_active_profiles_is_declared_once() {
+    let content = r#"
+profile "gaming" {
+  pkg { aur { discord steam } }
+}
+profile "dev" {
+  pkg { aur { discord neovim } }
+}
+"#;
+    let config = load_root_config_from_str(
+        content,
+        None,
+        &LoadSelectors {
+            profiles: vec!["gaming".to_string(), "dev".to_string()],
+            host: None,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(declared_names(&config), vec!["discord", "neovim", "steam"]);
+    let discord = PackageId {
+        name: "discord".to_string(),
+        backend: Backend::from("aur"),
+    };
+    assert_eq!(config.packages[&discord].len(), 1);
+    assert!(config.get_duplicates().is_empty());
+
+    let dev_only = load_root_config_from_str(
+        content,
+        None,
+        &LoadSelectors {
+            profiles: vec!["dev".to_string()],
+            host: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(declared_names(&dev_only), vec!["discord", "neovim"]);
+}
+
+#[test]
+fn package_repeated_inside_one_active_profile_is_still_a_duplicate() {
+    let config = load_root_config_from_str(
+        r#"
+profile "gaming" {
+  pkg { aur { discord } }
+  pkg { aur { discord } }
+}
+profile "dev" {
+  pkg { aur { neovim } }
+}
+"#,
+        None,
+        &LoadSelectors {
+            profiles: vec!["gaming".to_string(), "dev".to_string()],
+            host: None,
+        },
+    )
+    .unwrap();
+
+    let duplicates: Vec<String> = config
+        .get_duplicates()
+        .into_iter()
+        .map(|(pkg, _)| pkg.name.clone())
+        .collect();
+    assert_eq!(duplicates, vec!["discord"]);
+}
+
+#[test]
+fn include_merges_text_package_list_for_backend() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::create_dir(dir.path().join("packages")).expect("create packages dir");
+    std::fs::write(
+        dir.path().join("packages/work.txt"),
+        "# generated\nrequests\n\nblack\n",
+    )
+    .expect("write list");
+    let config_path = dir.path().join("declarch.kdl");
+    std::fs::write(
+        &config_path,
+        "pkg { aur { git } }\ninclude \"packages/work.txt\" backend=\"pip\"\n",
+    )
+    .expect("write config");
+
+    let merged = load_root_config(&config_path).expect("config should load");
+    for name in ["requests", "black"] {
+        assert!(merged.packages.contains_key(&PackageId {
+            name: name.to_string(),
+            backend: Backend::from("pip"),
+        }));
+    }
+    assert_eq!(merged.packages.len(), 3);
+}
+
+#[test]
+fn include_missing_file_names_path_and_referrer() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let config_path = dir.path().join("declarch.kdl");
+    std::fs::write(&config_path, "include \"missing.txt\" backend=\"pip\"\n")
+        .expect("write config");
+
+    let err = load_root_config(&config_path).expect_err("missing include should fail");
+    assert!(matches!(err, DeclarchError::ConfigError(_)));
+    let msg = err.to_string();
+    assert!(msg.contains("missing.txt"));
+    assert!(msg.contains("declarch.kdl"));
+}
+
+#[test]
+fn include_cycle_between_lists_is_rejected() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(dir.path().join("a.txt"), "ripgrep\ninclude b.txt\n").expect("write a");
+    std::fs::write(dir.path().join("b.txt"), "fd\ninclude a.txt\n").expect("write b");
+    let config_path = dir.path().join("declarch.kdl");
+    std::fs::write(&config_path, "include \"a.txt\" backend=\"cargo\"\n").expect("write config");
+
+    let err = load_root_config(&config_path).expect_err("cycle should fail");
+    assert!(err.to_string().contains("Circular import detected"));
+}
+
+#[test]
+fn module_import_cycle_is_rejected_with_trace() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(dir.path().join("a.kdl"), "imports { \"b.kdl\" }\n").expect("write a");
+    std::fs::write(dir.path().join("b.kdl"), "imports { \"a.kdl\" }\n").expect("write b");
+    let config_path = dir.path().join("declarch.kdl");
+    std::fs::write(&config_path, "imports { \"a.kdl\" }\n").expect("write config");
+
+    let err = load_root_config(&config_path).expect_err("cycle should fail");
+    assert!(matches!(err, DeclarchError::ConfigError(_)));
+    let msg = err.to_string();
+    assert!(msg.contains("Circular import detected"));
+    let a = msg.find("a.kdl").expect("trace names a.kdl");
+    let b = msg.find("b.kdl").expect("trace names b.kdl");
+    assert!(a < b);
+    assert_eq!(msg.matches("a.kdl").count(), 2);
+}
+
+#[test]
+fn module_self_import_is_rejected() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let config_path = dir.path().join("declarch.kdl");
+    std::fs::write(&config_path, "imports { \"declarch.kdl\" }\n").expect("write config");
+
+    let err = load_root_config(&config_path).expect_err("self-import should fail");
+    assert!(err.to_string().contains("Circular import detected"));
+}
+
+#[test]
+fn glob_import_loads_matches_in_sorted_order() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let modules = dir.path().join("modules");
+    std::fs::create_dir(&modules).expect("create modules");
+    for (name, pkg) in [("zsh.kdl", "zsh"), ("base.kdl", "git"), ("dev.kdl", "bat")] {
+        std::fs::write(modules.join(name), format!("pkg {{ aur {{ {} }} }}\n", pkg))
+            .expect("write module");
+    }
+    std::fs::write(modules.join("notes.txt"), "not a module").expect("write notes");
+    let config_path = dir.path().join("declarch.kdl");
+    std::fs::write(&config_path, "imports { \"modules/*.kdl\" }\n").expect("write config");
+
+    let merged = load_root_config(&config_path).expect("glob import loads");
+    let names: Vec<_> = merged
+        .config_files
+        .iter()
+        .skip(1)
+        .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+        .collect();
+    assert_eq!(names, vec!["base.kdl", "dev.kdl", "zsh.kdl"]);
+    assert_eq!(merged.packages.len(), 3);
+}
+
+#[test]
+fn glob_import_skips_root_config_and_tolerates_no_matches() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(dir.path().join("extra.kdl"), "pkg { aur { bat } }\n").expect("write extra");
+    let config_path = dir.path().join("declarch.kdl");
+    std::fs::write(
+        &config_path,
+        "imports { \"*.kdl\"; \"missing/*.kdl\" }\npkg { aur { git } }\n",
+    )
+    .expect("write config");
+
+    let merged = load_root_config(&config_path).expect("no-match glob only warns");
+    assert_eq!(merged.config_files.len(), 2);
+    assert_eq!(merged.packages.len(), 2);
+
+    let matches = expand_module_import(dir.path(), "missing/*.kdl", |_| false)
+        .expect("zero matches is not an error");
+    assert!(matches.is_empty());
+}
+
+#[test]
+fn module_imported_twice_loads_once() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(dir.path().join("base.kdl"), "pkg { aur { git } }\n").expect("write base");
+    std::fs::write(dir.path().join("a.kdl"), "imports { \"base.kdl\" }\n").expect("write a");
+    let config_path = dir.path().join("declarch.kdl");
+    std::fs::write(&config_path, "imports {\n  \"a.kdl\"\n  \"base.kdl\"\n}\n")
+        .expect("write config");
+
+    let merged = load_root_config(&config_path).expect("diamond imports load");
+    assert_eq!(merged.packages.len(), 1);
+}
+
+#[test]
+fn backend_alias_declarations_collapse_into_primary_backend() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(
+        dir.path().join("alias-aur.kdl"),
+        "backend \"alias-aur\" {\n  binary \"true\"\n  install \"true {packages}\"\n  alias \"alias-paru\"\n}\n",
+    )
+    .expect("write backend");
+    let config_path = dir.path().join("declarch.kdl");
+    std::fs::write(
+        &config_path,
+        "backends {\n  \"alias-aur.kdl\"\n}\npkg {\n  alias-aur { bat }\n  alias-paru { bat fd }\n}\n",
+    )
+    .expect("write config");
+
+    let merged = load_root_config(&config_path).expect("config should load");
+
+    let backends: HashSet<_> = merged.packages.keys().map(|p| p.backend.clone()).collect();
+    assert_eq!(backends, HashSet::from([Backend::from("alias-aur")]));
+    assert_eq!(merged.packages.len(), 2);
+    let bat = PackageId {
+        name: "bat".to_string(),
+        backend: Backend::from("alias-aur"),
+    };
+    assert_eq!(merged.packages[&bat].len(), 2);
+    // Names built after loading resolve through the config, not globally
+    assert_eq!(
+        merged.resolve_backend(&Backend::from("alias-paru")),
+        Backend::from("alias-aur")
+    );
+    assert_ne!(Backend::from("alias-paru"), Backend::from("alias-aur"));
+}
+
+#[test]
+fn parse_cache_reparses_an_edited_import_when_the_importer_is_unchanged() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let cache_path = dir.path().join("cache").join("parse-cache.json");
+    let config_path = dir.path().join("declarch.kdl");
+    let base_path = dir.path().join("base.kdl");
+    std::fs::write(&config_path, "imports {\n  \"base.kdl\"\n}\n").expect("write config");
+    std::fs::write(&base_path, "pkg { aur { bat } }\n").expect("write base");
+
+    let mut cache = ParseCache::at(cache_path.clone());
+    let merged = load_root_with_cache(&config_path, &LoadSelectors::default(), &mut cache)
+        .expect("config should load");
+    assert_eq!(merged.packages.len(), 1);
+    assert_eq!(merged.config_files.len(), 2);
+    cache.save();
+
+    // Unchanged files are served from the cache
+    let cache = ParseCache::at(cache_path.clone());
+    let root = std::fs::canonicalize(&config_path).expect("canonical root");
+    let mtime = std::fs::metadata(&root).and_then(|m| m.modified()).ok();
+    let content = std::fs::read_to_string(&root).expect("read root");
+    assert!(cache.get(&root, mtime, &content).is_some());
+
+    // The content hash catches edits even within the same mtime tick
+    std::fs::write(&base_path, "pkg { aur { bat fd } }\n").expect("edit base");
+    let mut cache = ParseCache::at(cache_path);
+    let merged = load_root_with_cache(&config_path, &LoadSelectors::default(), &mut cache)
+        .expect("config should reload");
+    assert_eq!(merged.packages.len(), 2);
+}
+
+#[test]
+fn stdin_config_resolves_imports_from_base_dir() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::create_dir(dir.path().join("modules")).expect("modules dir");
+    std::fs::write(dir.path().join("modules/dev.kdl"), "pkg { aur { neovim } }")
+        .expect("write module");
+
+    let content = r#"
+imports { "modules/dev.kdl" }
+pkg { aur { git } }
+"#;
+    let merged =
+        load_root_config_from_str(content, Some(dir.path()), &LoadSelectors::default()).unwrap();
+
+    let names: HashSet<&str> = merged.packages.keys().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, HashSet::from(["git", "neovim"]));
+    assert_eq!(merged.config_files[0], dir.path().join(STDIN_CONFIG_NAME));
+}
+
+#[test]
+fn stdin_config_rejects_unresolvable_imports() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let content = r#"imports { "modules/missing.kdl" }"#;
+
+    let err = load_root_config_from_str(content, Some(dir.path()), &LoadSelectors::default())
+        .expect_err("missing import must fail");
+    assert!(err.to_string().contains("imports 'modules/missing.kdl'"));
+
+    let err = load_root_config_from_str(content, None, &LoadSelectors::default())
+        .expect_err("relative import without a base must fail");
+    assert!(err.to_string().contains("no directory to resolve"));
+
+    let plain = load_root_config_from_str("pkg { aur { git } }", None, &LoadSelectors::default())
+        .expect("no imports needs no base");
+    assert_eq!(plain.packages.len(), 1);
+}
+
+#[test]
+fn stdin_placeholder_counts_as_an_available_root() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let placeholder = dir.path().join(STDIN_CONFIG_NAME);
+    set_stdin_root_config("pkg { aur { git } }".to_string());
+
+    assert!(root_config_available(&placeholder));
+    assert!(is_stdin_root(&placeholder));
+    assert!(!root_config_available(&dir.path().join("declarch.kdl")));
+    assert!(!is_stdin_root(&dir.path().join("declarch.kdl")));
+}
+
+#[test]
+fn merge_module_keeps_package_metadata_from_the_module() {
+    let no_selectors = LoadSelectors::default();
+    let mut root = load_root_config_from_str("pkg { aur { git } }", None, &no_selectors).unwrap();
+    let module = load_root_config_from_str(
+        r#"
+pkg {
+  aur {
+    neovim { version "0.9.5"; reason "editor"; tags "dev"; pinned }
+  }
+  pip:tensorflow extra-args="--no-build-isolation"
+}
+excludes:aur { vim }
+"#,
+        None,
+        &no_selectors,
+    )
+    .unwrap();
+
+    root.merge_module(module);
+
+    let neovim = PackageId {
+        name: "neovim".to_string(),
+        backend: Backend::from("aur"),
+    };
+    let tensorflow = PackageId {
+        name: "tensorflow".to_string(),
+        backend: Backend::from("pip"),
+    };
+    assert_eq!(declared_names(&root), vec!["git", "neovim", "tensorflow"]);
+    assert!(root.pinned.contains(&neovim));
+    assert_eq!(
+        root.package_versions.get(&neovim).map(String::as_str),
+        Some("0.9.5")
+    );
+    assert_eq!(
+        root.package_reasons.get(&neovim).map(String::as_str),
+        Some("editor")
+    );
+    assert!(root.package_tags[&neovim].contains("dev"));
+    assert_eq!(
+        root.package_extra_args.get(&tensorflow).map(String::as_str),
+        Some("--no-build-isolation")
+    );
+    assert!(root.backend_excludes["aur"].contains("vim"));
+}
+
+#[test]
+fn policy_escalation_reaches_root_hooks() {
+    let config = load_root_config_from_str(
+        r#"
+policy { escalation "doas"; }
+hooks {
+  post-sync "mkinitcpio -P" --sudo
+}
+"#,
+        None,
+        &LoadSelectors::default(),
+    )
+    .unwrap();
+
+    let hooks = config.lifecycle_actions.expect("hooks are loaded");
+    assert_eq!(hooks.escalation.as_deref(), Some("doas"));
+}
@@ -121,7 +121,7 @@ impl Settings {
                 }
             }
             "format" => {
-                let valid = ["table", "json", "yaml"];
+                let valid = ["table", "json", "yaml", "toml"];
                 if !valid.contains(&value) {
                     return Err(DeclarchError::Other(format!(
                         "Invalid value for '{}': '{}'. Valid: {}",
@@ -142,11 +142,9 @@ impl Settings {
                     )));
                 }
             }
-            "editor" => {
-                // Allow any non-empty string (editor command)
-                if value.trim().is_empty() {
-                    return Err(DeclarchError::Other("Editor cannot be empty".to_string()));
-                }
+            // Allow any non-empty string (editor command)
+            "editor" if value.trim().is_empty() => {
+                return Err(DeclarchError::Other("Editor cannot be empty".to_string()));
             }
             "compact" => {
                 let valid = ["true", "false"];
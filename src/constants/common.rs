@@ -29,6 +29,12 @@ pub const MODULES_DIR_NAME: &str = "modules";
 /// State file name
 pub const STATE_FILE_NAME: &str = "state.json";
 
+/// Experimental flag that allows lifecycle hooks to run.
+pub const EXPERIMENTAL_ENABLE_HOOKS: &str = "enable-hooks";
+
+/// Experimental flags the current release understands.
+pub const KNOWN_EXPERIMENTAL_FLAGS: &[&str] = &[EXPERIMENTAL_ENABLE_HOOKS];
+
 /// Default timeout (seconds) for backend command execution.
 pub const BACKEND_COMMAND_TIMEOUT_SECS: u64 = 300;
 
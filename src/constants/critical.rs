@@ -67,5 +67,69 @@ pub const ALL: &[&str] = &[
     "declarch-bin",
 ];
 
+/// System-critical package categories detected by name pattern.
+///
+/// Unlike `ALL`, these packages may be pruned, but only after the user
+/// types the package name to confirm (`--yes` does not apply).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CriticalCategory {
+    Kernel,
+    Bootloader,
+    Init,
+}
+
+impl CriticalCategory {
+    pub fn label(self) -> &'static str {
+        match self {
+            CriticalCategory::Kernel => "kernel",
+            CriticalCategory::Bootloader => "bootloader",
+            CriticalCategory::Init => "init system",
+        }
+    }
+}
+
+/// Kernel packages and their variants (linux-cachyos, kernel-core, ...)
+const KERNEL_PATTERNS: &[&str] = &["linux", "kernel"];
+
+/// Boot loaders and initramfs generators
+const BOOTLOADER_PATTERNS: &[&str] = &[
+    "grub",
+    "systemd-boot",
+    "refind",
+    "limine",
+    "syslinux",
+    "efibootmgr",
+    "shim",
+    "mkinitcpio",
+    "dracut",
+    "booster",
+];
+
+/// Init systems and their service suites
+const INIT_PATTERNS: &[&str] = &["systemd", "openrc", "runit", "s6", "dinit", "sysvinit"];
+
+/// Category of a package name, matching a pattern exactly or as a `pattern-` prefix.
+pub fn critical_category(name: &str) -> Option<CriticalCategory> {
+    let matches = |patterns: &[&str]| {
+        patterns.iter().any(|pattern| {
+            name == *pattern
+                || name
+                    .strip_prefix(pattern)
+                    .is_some_and(|rest| rest.starts_with('-'))
+        })
+    };
+
+    // Bootloader before init so systemd-boot isn't reported as an init package.
+    if matches(BOOTLOADER_PATTERNS) {
+        Some(CriticalCategory::Bootloader)
+    } else if matches(KERNEL_PATTERNS) {
+        Some(CriticalCategory::Kernel)
+    } else if matches(INIT_PATTERNS) {
+        Some(CriticalCategory::Init)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -35,3 +35,26 @@ fn test_not_contains_yay() {
 fn test_not_contains_git() {
     assert!(!ALL.contains(&"git"));
 }
+
+#[test]
+fn test_critical_category_patterns() {
+    assert_eq!(
+        critical_category("linux-cachyos"),
+        Some(CriticalCategory::Kernel)
+    );
+    assert_eq!(
+        critical_category("kernel-core"),
+        Some(CriticalCategory::Kernel)
+    );
+    assert_eq!(
+        critical_category("systemd-boot"),
+        Some(CriticalCategory::Bootloader)
+    );
+    assert_eq!(
+        critical_category("limine"),
+        Some(CriticalCategory::Bootloader)
+    );
+    assert_eq!(critical_category("openrc"), Some(CriticalCategory::Init));
+    assert_eq!(critical_category("linuxqq"), None);
+    assert_eq!(critical_category("bat"), None);
+}
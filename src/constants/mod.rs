@@ -6,9 +6,9 @@ pub mod urls;
 pub use common::{
     BACKEND_COMMAND_TIMEOUT_SECS, BACKEND_OPERATION_MAX_RETRIES, BACKEND_RETRY_DELAY_MS,
     CONFIG_DIR_NAME, CONFIG_EXTENSION, CONFIG_FILE_NAME, DECLARCH_DIR_NAME, DEFAULT_BRANCHES,
-    HOOK_TIMEOUT_SECS, MODULES_DIR_NAME, PROJECT_NAME, PROJECT_ORG, PROJECT_QUALIFIER,
-    SEARCH_BACKEND_TIMEOUT_SECS, STATE_FILE_NAME,
+    EXPERIMENTAL_ENABLE_HOOKS, HOOK_TIMEOUT_SECS, KNOWN_EXPERIMENTAL_FLAGS, MODULES_DIR_NAME,
+    PROJECT_NAME, PROJECT_ORG, PROJECT_QUALIFIER, SEARCH_BACKEND_TIMEOUT_SECS, STATE_FILE_NAME,
 };
-pub use critical::ALL as CRITICAL_PACKAGES;
+pub use critical::{ALL as CRITICAL_PACKAGES, CriticalCategory, critical_category};
 pub use package_suffixes::{VARIANTS, build_variants, is_variant};
 pub use urls::{DEFAULT_REGISTRY, RemoteUrlBuilder};
@@ -1,3 +1,4 @@
+use crate::error::{DeclarchError, Result};
 use crate::project_identity;
 
 /// External URLs and URL patterns
@@ -47,6 +48,53 @@ pub mod installation {
     pub const SOAR_INSTALL: &str = "https://github.com/pkgforge/soar#installation";
 }
 
+/// A registry that `init --backend` can fetch backend definitions from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackendRegistry {
+    /// Registry as the user wrote it (or the default registry slug)
+    pub name: String,
+    /// Base URL that backend files live under (`<base>/<backend>.kdl`)
+    pub base_url: String,
+}
+
+impl BackendRegistry {
+    /// Parse a registry spec: `github:org/repo[/branch]` or an http(s) URL
+    ///
+    /// GitHub specs point at the repo's `backends/` directory (branch defaults
+    /// to `main`); URLs are used as the base directly.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let spec = spec.trim();
+
+        if spec.starts_with("http://") || spec.starts_with("https://") {
+            return Some(Self {
+                name: spec.to_string(),
+                base_url: spec.trim_end_matches('/').to_string(),
+            });
+        }
+
+        let path = spec.strip_prefix("github:")?;
+        let parts: Vec<&str> = path.split('/').collect();
+        if !(2..=3).contains(&parts.len()) || parts.iter().any(|p| p.trim().is_empty()) {
+            return None;
+        }
+        let branch = parts.get(2).copied().unwrap_or("main");
+
+        Some(Self {
+            name: spec.to_string(),
+            base_url: format!(
+                "https://raw.githubusercontent.com/{}/{}/{}/backends",
+                parts[0], parts[1], branch
+            ),
+        })
+    }
+
+    /// URL of `backend` in this registry
+    pub fn backend_url(&self, backend: &str) -> String {
+        let clean = backend.strip_suffix(".kdl").unwrap_or(backend);
+        format!("{}/{}.kdl", self.base_url, clean)
+    }
+}
+
 /// URL builder for remote init
 ///
 /// Handles multiple URL patterns:
@@ -118,6 +166,36 @@ impl RemoteUrlBuilder {
         urls
     }
 
+    /// Registries to try for `init --backend`, in order
+    ///
+    /// `specs` come first (environment, then config), followed by the default
+    /// registry. Duplicates are dropped, keeping the earliest occurrence.
+    pub fn backend_registries(&self, specs: &[String]) -> Result<Vec<BackendRegistry>> {
+        let mut registries: Vec<BackendRegistry> = Vec::new();
+
+        for spec in specs {
+            let registry = BackendRegistry::parse(spec).ok_or_else(|| {
+                DeclarchError::ConfigError(format!(
+                    "Invalid backend registry '{}': expected github:org/repo[/branch] or an https:// URL",
+                    spec
+                ))
+            })?;
+            if !registries.iter().any(|r| r.base_url == registry.base_url) {
+                registries.push(registry);
+            }
+        }
+
+        let default = BackendRegistry {
+            name: project_identity::REGISTRY_SLUG.to_string(),
+            base_url: format!("{}/backends", self.registry.trim_end_matches('/')),
+        };
+        if !registries.iter().any(|r| r.base_url == default.base_url) {
+            registries.push(default);
+        }
+
+        Ok(registries)
+    }
+
     /// Build URLs for variant syntax (user/repo:variant or user/repo/branch:variant)
     fn build_variant_urls(&self, target: &str) -> Vec<String> {
         let mut urls = Vec::new();
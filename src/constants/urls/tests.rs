@@ -32,3 +32,56 @@ fn test_build_urls_direct() {
     assert_eq!(urls.len(), 1);
     assert_eq!(urls[0], "https://example.com/config.kdl");
 }
+
+#[test]
+fn backend_registry_specs_resolve_to_backends_directories() {
+    let gh = BackendRegistry::parse("github:acme/backends").unwrap();
+    assert_eq!(
+        gh.base_url,
+        "https://raw.githubusercontent.com/acme/backends/main/backends"
+    );
+    assert_eq!(
+        gh.backend_url("paru"),
+        "https://raw.githubusercontent.com/acme/backends/main/backends/paru.kdl"
+    );
+
+    let branch = BackendRegistry::parse("github:acme/backends/dev").unwrap();
+    assert!(branch.base_url.contains("/acme/backends/dev/backends"));
+
+    let url = BackendRegistry::parse("https://mycorp.example/backends/").unwrap();
+    assert_eq!(
+        url.backend_url("nix.kdl"),
+        "https://mycorp.example/backends/nix.kdl"
+    );
+
+    assert!(BackendRegistry::parse("acme/backends").is_none());
+    assert!(BackendRegistry::parse("github:acme").is_none());
+}
+
+#[test]
+fn backend_registries_keep_order_and_end_with_default() {
+    let builder = RemoteUrlBuilder::default();
+    let registries = builder
+        .backend_registries(&[
+            "https://mycorp.example/backends".to_string(),
+            "github:acme/backends".to_string(),
+            "https://mycorp.example/backends/".to_string(),
+        ])
+        .unwrap();
+
+    let names: Vec<&str> = registries.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "https://mycorp.example/backends",
+            "github:acme/backends",
+            project_identity::REGISTRY_SLUG,
+        ]
+    );
+
+    assert!(
+        builder
+            .backend_registries(&["ftp://x".to_string()])
+            .is_err()
+    );
+}
@@ -8,6 +8,8 @@ fn mock_metadata() -> PackageMetadata {
         variant: None,
         installed_at: Utc::now(),
         source_file: None,
+        install_size: None,
+        origin: None,
     }
 }
 
@@ -1,9 +1,9 @@
 use crate::config::loader::MergedConfig;
 use crate::core::matcher::PackageMatcher;
-use crate::core::types::{PackageId, PackageMetadata, SyncTarget};
+use crate::core::types::{Backend, PackageId, PackageMetadata, SyncTarget};
 use crate::error::Result;
 use crate::state::types::State;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 #[derive(Debug)]
 pub struct Transaction {
@@ -11,6 +11,50 @@ pub struct Transaction {
     pub to_prune: Vec<PackageId>,
     pub to_adopt: Vec<PackageId>,
     pub to_update_project_metadata: Vec<PackageId>,
+    /// Why each planned install, adoption and metadata update is in the plan
+    pub install_reasons: InstallReasonMap,
+}
+
+/// Why a declared package is planned for install, adoption or a state update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum InstallReason {
+    /// Declared and never tracked before.
+    New,
+    /// Tracked in state, but no longer installed.
+    Missing,
+    /// Installed, but at a different version than state recorded.
+    VersionMismatch,
+    /// Already installed; forced back in with `--reinstall`.
+    Reinstall,
+    /// Installed but untracked; taken back into state without installing.
+    Readopt,
+}
+
+impl InstallReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            InstallReason::New => "new declaration",
+            InstallReason::Missing => "tracked but missing",
+            InstallReason::VersionMismatch => "version mismatch",
+            InstallReason::Reinstall => "reinstall",
+            InstallReason::Readopt => "readopt",
+        }
+    }
+}
+
+/// Reason for every planned install, adoption and metadata update, keyed by package id.
+pub type InstallReasonMap = HashMap<PackageId, InstallReason>;
+
+impl Transaction {
+    /// Installs (and removals, when pruning) planned per backend
+    pub fn backend_change_counts(&self, include_prunes: bool) -> HashMap<Backend, usize> {
+        let prunes: &[PackageId] = if include_prunes { &self.to_prune } else { &[] };
+        let mut counts = HashMap::new();
+        for pkg in self.to_install.iter().chain(prunes) {
+            *counts.entry(pkg.backend.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
 }
 
 /// Helper to generate consistent state keys
@@ -19,6 +63,46 @@ pub fn make_state_key(pkg: &PackageId) -> String {
     format!("{}:{}", pkg.backend, pkg.name)
 }
 
+/// State keys shared by more than one distinct package, with the packages
+/// mapping to each (sorted). Such packages would overwrite each other's
+/// state entry, e.g. `a` + `b:c` and `a:b` + `c` both become `a:b:c`.
+pub fn state_key_collisions<'a>(
+    packages: impl IntoIterator<Item = &'a PackageId>,
+) -> Vec<(String, Vec<PackageId>)> {
+    let mut by_key: BTreeMap<String, Vec<PackageId>> = BTreeMap::new();
+    for pkg in packages {
+        let ids = by_key.entry(make_state_key(pkg)).or_default();
+        if !ids.contains(pkg) {
+            ids.push(pkg.clone());
+        }
+    }
+
+    by_key
+        .into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .map(|(key, mut ids)| {
+            ids.sort_by(|a, b| (a.backend.name(), &a.name).cmp(&(b.backend.name(), &b.name)));
+            (key, ids)
+        })
+        .collect()
+}
+
+/// Package actually meant by a declaration: a backend-scoped alias wins over
+/// a global one, and unaliased names resolve to themselves.
+pub fn resolve_package_alias(config: &MergedConfig, pkg: &PackageId) -> PackageId {
+    let name = config
+        .backend_package_aliases
+        .get(pkg.backend.name())
+        .and_then(|aliases| aliases.get(&pkg.name))
+        .or_else(|| config.package_aliases.get(&pkg.name))
+        .cloned()
+        .unwrap_or_else(|| pkg.name.clone());
+    PackageId {
+        name,
+        backend: pkg.backend.clone(),
+    }
+}
+
 pub fn resolve(
     config: &MergedConfig,
     state: &State,
@@ -30,15 +114,21 @@ pub fn resolve(
         to_prune: vec![],
         to_adopt: vec![],
         to_update_project_metadata: vec![],
+        install_reasons: HashMap::new(),
     };
 
     let target_packages = resolve_target_scope(config, target);
 
     // Create smart matcher for package resolution
     let matcher = PackageMatcher::new();
+    let mut seen = HashSet::new();
 
-    for pkg_id in target_packages {
-        if config.excludes.contains(&pkg_id.name) {
+    for declared in target_packages {
+        if config.is_excluded(&declared) {
+            continue;
+        }
+        let pkg_id = resolve_package_alias(config, &declared);
+        if !seen.insert(pkg_id.clone()) {
             continue;
         }
 
@@ -53,19 +143,37 @@ pub fn resolve(
 
         if let Some(meta) = found_meta {
             if let Some(stored_state) = state_pkg {
-                if stored_state.version != meta.version {
+                // ignore-version packages only need to be present
+                if stored_state.version != meta.version
+                    && !config.ignore_version.contains(&declared)
+                {
                     tx.to_update_project_metadata.push(pkg_id.clone());
+                    tx.install_reasons
+                        .insert(pkg_id.clone(), InstallReason::VersionMismatch);
                 }
             } else {
                 tx.to_adopt.push(pkg_id.clone());
+                tx.install_reasons
+                    .insert(pkg_id.clone(), InstallReason::Readopt);
             }
         } else {
+            let reason = if state_pkg.is_some() {
+                InstallReason::Missing
+            } else {
+                InstallReason::New
+            };
             tx.to_install.push(pkg_id.clone());
+            tx.install_reasons.insert(pkg_id.clone(), reason);
         }
     }
 
     // Pruning Logic
     if *target == SyncTarget::All {
+        let declared: HashSet<PackageId> = config
+            .packages
+            .keys()
+            .map(|pkg| resolve_package_alias(config, pkg))
+            .collect();
         for (key, state_pkg) in &state.packages {
             // Backend is now the same type from core::types
             let core_backend = state_pkg.backend.clone();
@@ -80,7 +188,7 @@ pub fn resolve(
                 backend: core_backend,
             };
 
-            if !config.packages.contains_key(&pkg_id) && !config.excludes.contains(&name_part) {
+            if !declared.contains(&pkg_id) && !config.is_excluded(&pkg_id) {
                 tx.to_prune.push(pkg_id);
             }
         }
@@ -119,6 +227,11 @@ fn resolve_target_scope(config: &MergedConfig, target: &SyncTarget) -> HashSet<P
             }
             matched
         }
+
+        SyncTarget::Multiple(targets) => targets
+            .iter()
+            .flat_map(|target| resolve_target_scope(config, target))
+            .collect(),
     }
 }
 
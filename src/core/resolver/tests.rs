@@ -17,18 +17,30 @@ fn mock_config(pkgs: Vec<(&str, &str)>) -> MergedConfig {
     MergedConfig {
         packages: map,
         excludes: vec![],
+        backend_excludes: std::collections::HashMap::new(),
+        ignore_version: std::collections::HashSet::new(),
+        pinned: std::collections::HashSet::new(),
+        package_extra_args: std::collections::HashMap::new(),
+        package_tags: std::collections::HashMap::new(),
+        package_versions: std::collections::HashMap::new(),
+        package_reasons: std::collections::HashMap::new(),
         project_metadata: None,
         conflicts: vec![],
         backend_options: std::collections::HashMap::new(),
         env: std::collections::HashMap::new(),
         package_sources: std::collections::HashMap::new(),
+        package_source_priorities: std::collections::HashMap::new(),
+        package_aliases: std::collections::HashMap::new(),
+        backend_package_aliases: std::collections::HashMap::new(),
         policy: None,
         lifecycle_actions: None,
         editor: None,
         backends: vec![],
+        backend_aliases: std::collections::HashMap::new(),
         backend_sources: std::collections::HashMap::new(),
         experimental: std::collections::HashSet::new(),
         mcp: None,
+        config_files: vec![],
     }
 }
 
@@ -54,6 +66,7 @@ fn mock_state(pkgs: Vec<(&str, &str, &str)>) -> State {
                 version: Some(version.to_string()),
                 install_reason: None,
                 source_module: None,
+                pinned: false,
                 last_seen_at: None,
                 backend_meta: None,
             },
@@ -77,6 +90,8 @@ fn mock_snapshot(pkgs: Vec<(&str, &str, &str)>) -> HashMap<PackageId, PackageMet
                 variant: None,
                 installed_at: Utc::now(),
                 source_file: None,
+                install_size: None,
+                origin: None,
             },
         );
     }
@@ -96,6 +111,31 @@ fn test_install_flow() {
     assert_eq!(tx.to_install[0].name, "git");
 }
 
+#[test]
+fn test_install_reasons_are_recorded_while_resolving() {
+    let config = mock_config(vec![
+        ("git", "aur"),
+        ("fd", "aur"),
+        ("bat", "aur"),
+        ("htop", "aur"),
+    ]);
+    let state = mock_state(vec![("fd", "aur", "1.0"), ("htop", "aur", "1.0")]);
+    let snapshot = mock_snapshot(vec![("bat", "aur", "1.0"), ("htop", "aur", "2.0")]);
+
+    let tx = resolve(&config, &state, &snapshot, &SyncTarget::All).unwrap();
+    let reason = |name: &str| {
+        tx.install_reasons[&PackageId {
+            name: name.to_string(),
+            backend: Backend::from("aur"),
+        }]
+    };
+
+    assert_eq!(reason("git"), InstallReason::New);
+    assert_eq!(reason("fd"), InstallReason::Missing);
+    assert_eq!(reason("bat"), InstallReason::Readopt);
+    assert_eq!(reason("htop"), InstallReason::VersionMismatch);
+}
+
 #[test]
 fn test_smart_match_suffix() {
     // Case: Config has "gdu", System has "gdu-bin" -> Adopt "gdu" (Mapped)
@@ -136,6 +176,31 @@ fn test_prune_logic_standard() {
     assert_eq!(tx.to_prune[0].name, "htop");
 }
 
+#[test]
+fn test_multiple_target_unions_backend_and_named_entries() {
+    let config = mock_config(vec![
+        ("bat", "aur"),
+        ("fd", "aur"),
+        ("org.gimp.GIMP", "flatpak"),
+        ("ripgrep", "cargo"),
+        ("typescript", "npm"),
+    ]);
+    let state = mock_state(vec![("htop", "aur", "1.0")]);
+    let snapshot = HashMap::new();
+
+    let target = SyncTarget::Multiple(vec![
+        SyncTarget::Backend(Backend::from("aur")),
+        SyncTarget::Named("ripgrep".to_string()),
+    ]);
+    let tx = resolve(&config, &state, &snapshot, &target).unwrap();
+
+    let mut names: Vec<&str> = tx.to_install.iter().map(|p| p.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, vec!["bat", "fd", "ripgrep"]);
+    // Partial targets never prune
+    assert!(tx.to_prune.is_empty());
+}
+
 #[test]
 fn test_flatpak_fuzzy() {
     // Case: Config "spotify", System "com.spotify.Client" -> Adopt
@@ -149,3 +214,119 @@ fn test_flatpak_fuzzy() {
     assert_eq!(tx.to_adopt.len(), 1);
     assert_eq!(tx.to_adopt[0].name, "spotify");
 }
+
+#[test]
+fn test_ignore_version_package_only_requires_presence() {
+    // Case: neovim is tracked at 0.10.0 but a rolling build reports 0.11.0-dev
+    let neovim = PackageId {
+        name: "neovim".to_string(),
+        backend: Backend::from("aur"),
+    };
+    let state = mock_state(vec![("neovim", "aur", "0.10.0"), ("htop", "aur", "3.2")]);
+    let snapshot = mock_snapshot(vec![
+        ("neovim", "aur", "0.11.0-dev"),
+        ("htop", "aur", "3.3"),
+    ]);
+    let mismatched = |tx: &Transaction| {
+        let mut names: Vec<String> = tx
+            .to_update_project_metadata
+            .iter()
+            .map(|p| p.name.clone())
+            .collect();
+        names.sort();
+        names
+    };
+
+    // Without the flag the version drift takes the mismatch path
+    let mut config = mock_config(vec![("neovim", "aur"), ("htop", "aur")]);
+    let tx = resolve(&config, &state, &snapshot, &SyncTarget::All).unwrap();
+    assert_eq!(mismatched(&tx), vec!["htop", "neovim"]);
+    assert!(tx.to_install.is_empty());
+
+    // With it, neovim only has to be present
+    config.ignore_version.insert(neovim);
+    let tx = resolve(&config, &state, &snapshot, &SyncTarget::All).unwrap();
+    assert_eq!(mismatched(&tx), vec!["htop"]);
+    assert!(tx.to_install.is_empty());
+    assert!(tx.to_adopt.is_empty());
+}
+
+#[test]
+fn test_backend_change_counts_only_counts_prunes_when_pruning() {
+    let aur = |name: &str| PackageId {
+        name: name.to_string(),
+        backend: Backend::from("aur"),
+    };
+    let tx = Transaction {
+        to_install: vec![aur("bat")],
+        to_prune: vec![PackageId {
+            name: "typescript".to_string(),
+            backend: Backend::from("npm"),
+        }],
+        to_adopt: vec![aur("fd")],
+        to_update_project_metadata: Vec::new(),
+        install_reasons: HashMap::new(),
+    };
+
+    let counts = tx.backend_change_counts(false);
+    assert_eq!(counts.get(&Backend::from("aur")), Some(&1));
+    assert!(!counts.contains_key(&Backend::from("npm")));
+
+    let counts = tx.backend_change_counts(true);
+    assert_eq!(counts.get(&Backend::from("npm")), Some(&1));
+}
+
+#[test]
+fn test_backend_scoped_alias_overrides_global_for_that_backend_only() {
+    let mut config = mock_config(vec![("python", "apt"), ("python", "aur")]);
+    config
+        .package_aliases
+        .insert("python".to_string(), "python-global".to_string());
+    config.backend_package_aliases.insert(
+        "apt".to_string(),
+        HashMap::from([("python".to_string(), "python3".to_string())]),
+    );
+    let state = mock_state(vec![("python3", "apt", "3.12")]);
+    let snapshot = mock_snapshot(vec![("python3", "apt", "3.12")]);
+
+    let tx = resolve(&config, &state, &snapshot, &SyncTarget::All).unwrap();
+
+    // apt uses its scoped alias, is already installed and must not be pruned
+    assert!(tx.to_prune.is_empty());
+    assert_eq!(tx.to_install.len(), 1);
+    assert_eq!(tx.to_install[0].name, "python-global");
+    assert_eq!(tx.to_install[0].backend, Backend::from("aur"));
+}
+
+#[test]
+fn test_backend_scoped_exclude_applies_to_that_backend_only() {
+    let mut config = mock_config(vec![("setuptools", "pip"), ("setuptools", "apt")]);
+    config.backend_excludes.insert(
+        "pip".to_string(),
+        std::collections::HashSet::from(["setuptools".to_string(), "wheel".to_string()]),
+    );
+    let state = mock_state(vec![("wheel", "pip", "0.43"), ("wheel", "apt", "0.43")]);
+    let snapshot = mock_snapshot(vec![("wheel", "pip", "0.43"), ("wheel", "apt", "0.43")]);
+
+    let tx = resolve(&config, &state, &snapshot, &SyncTarget::All).unwrap();
+
+    // pip:setuptools is excluded, apt:setuptools still installs
+    assert_eq!(tx.to_install.len(), 1);
+    assert_eq!(tx.to_install[0].backend, Backend::from("apt"));
+    // pip:wheel is kept out of prune by the scoped exclude; apt:wheel is not
+    assert_eq!(tx.to_prune.len(), 1);
+    assert_eq!(tx.to_prune[0].backend, Backend::from("apt"));
+}
+
+#[test]
+fn test_state_key_collisions_group_distinct_packages_only() {
+    let config = mock_config(vec![("b:c", "a"), ("c", "a:b"), ("bat", "aur")]);
+
+    let collisions = state_key_collisions(config.packages.keys());
+
+    assert_eq!(collisions.len(), 1);
+    let (key, ids) = &collisions[0];
+    assert_eq!(key, "a:b:c");
+    assert_eq!(ids[0].backend, Backend::from("a"));
+    assert_eq!(ids[1].backend, Backend::from("a:b"));
+}
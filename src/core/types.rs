@@ -145,6 +145,12 @@ pub struct PackageMetadata {
     pub variant: Option<String>,
     pub installed_at: DateTime<Utc>,
     pub source_file: Option<String>,
+    /// Installed size in bytes, when the backend reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_size: Option<u64>,
+    /// Remote/repository the package was installed from, when the backend reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
 }
 
 /// Sync target for partial syncs
@@ -153,4 +159,23 @@ pub enum SyncTarget {
     All,
     Backend(Backend),
     Named(String),
+    /// Union of several targets (repeated `--target`)
+    Multiple(Vec<SyncTarget>),
+}
+
+impl SyncTarget {
+    /// The individual targets this one is made of.
+    pub fn parts(&self) -> &[SyncTarget] {
+        match self {
+            SyncTarget::Multiple(targets) => targets,
+            single => std::slice::from_ref(single),
+        }
+    }
+
+    /// Whether `backend` was requested explicitly as a backend target.
+    pub fn targets_backend(&self, backend: &Backend) -> bool {
+        self.parts()
+            .iter()
+            .any(|target| matches!(target, SyncTarget::Backend(b) if b == backend))
+    }
 }
@@ -40,6 +40,9 @@ pub enum DeclarchError {
     #[error(transparent)]
     YamlError(#[from] serde_yml::Error),
 
+    #[error(transparent)]
+    TomlError(#[from] toml::ser::Error),
+
     #[error("Config file not found at: {path}")]
     ConfigNotFound { path: PathBuf },
 
@@ -70,8 +73,103 @@ pub enum DeclarchError {
     #[error("Backup operation failed: {0}")]
     BackupError(String),
 
+    /// `sync --check-only` found changes to apply
+    #[error("{0} change(s) pending; system is not in sync")]
+    ChangesPending(usize),
+
     #[error("{0}")]
     Other(String),
 }
 
+impl DeclarchError {
+    /// Stable identifier for scripts; never reworded between releases.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ConfigError(_) | Self::ParseError { .. } | Self::KdlError(_) => "E_CONFIG",
+            Self::ConfigNotFound { .. } => "E_CONFIG_NOT_FOUND",
+            Self::InvalidRegex(_) => "E_REGEX",
+            Self::TargetNotFound(_) => "E_TARGET",
+            Self::PackageManagerError(_) => "E_PKGMGR",
+            Self::SystemCommandFailed { .. } => "E_SYSTEM_COMMAND",
+            Self::DependencyMissing(_) => "E_DEPENDENCY",
+            Self::RemoteFetchError(_) => "E_NETWORK",
+            Self::LockError(_) => "E_LOCK",
+            Self::Interrupted => "E_INTERRUPTED",
+            Self::IoError { .. } | Self::StdIoError(_) => "E_IO",
+            Self::JsonError(_)
+            | Self::YamlError(_)
+            | Self::TomlError(_)
+            | Self::SerializationError(_) => "E_SERIALIZE",
+            Self::PathError(_) => "E_PATH",
+            Self::ChangesPending(_) => "E_DRIFT",
+            Self::BackupError(_) => "E_BACKUP",
+            Self::Other(_) => "E_OTHER",
+        }
+    }
+
+    /// Process exit code for the error's category.
+    ///
+    /// 2 is left to argument parsing errors; 130 follows the shell
+    /// convention for Ctrl-C.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::ConfigError(_)
+            | Self::ParseError { .. }
+            | Self::KdlError(_)
+            | Self::ConfigNotFound { .. }
+            | Self::InvalidRegex(_)
+            | Self::TargetNotFound(_) => 3,
+            Self::PackageManagerError(_)
+            | Self::SystemCommandFailed { .. }
+            | Self::DependencyMissing(_) => 4,
+            Self::RemoteFetchError(_) => 5,
+            Self::LockError(_) => 6,
+            Self::ChangesPending(_) => 7,
+            Self::Interrupted => 130,
+            Self::IoError { .. }
+            | Self::StdIoError(_)
+            | Self::JsonError(_)
+            | Self::YamlError(_)
+            | Self::TomlError(_)
+            | Self::SerializationError(_)
+            | Self::PathError(_)
+            | Self::BackupError(_)
+            | Self::Other(_) => 1,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, DeclarchError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_map_to_category_exit_codes() {
+        let cases = [
+            (DeclarchError::ConfigError("x".into()), "E_CONFIG", 3),
+            (
+                DeclarchError::ConfigNotFound {
+                    path: PathBuf::from("declarch.kdl"),
+                },
+                "E_CONFIG_NOT_FOUND",
+                3,
+            ),
+            (
+                DeclarchError::PackageManagerError("x".into()),
+                "E_PKGMGR",
+                4,
+            ),
+            (DeclarchError::RemoteFetchError("x".into()), "E_NETWORK", 5),
+            (DeclarchError::LockError("x".into()), "E_LOCK", 6),
+            (DeclarchError::ChangesPending(3), "E_DRIFT", 7),
+            (DeclarchError::Interrupted, "E_INTERRUPTED", 130),
+            (DeclarchError::Other("x".into()), "E_OTHER", 1),
+        ];
+        for (error, code, exit_code) in cases {
+            assert_eq!(error.code(), code);
+            assert_eq!(error.exit_code(), exit_code, "{}", code);
+        }
+    }
+}
@@ -14,11 +14,13 @@ pub mod utils;
 
 use clap::Parser;
 use std::process::exit;
+use std::time::Instant;
 
 /// Run declarch CLI entrypoint.
 pub fn run_cli() {
-    // 0. Initialize color settings (must be first)
-    ui::init_colors();
+    // 0. Parse, then initialize color settings before any output
+    let mut args = cli::args::Cli::parse();
+    ui::init_colors(args.global.color_mode());
 
     // 1. Signal Handling (mark cancellation and let command flow rollback safely)
     ctrlc::set_handler(move || {
@@ -28,13 +30,66 @@ pub fn run_cli() {
     })
     .expect("Error setting Ctrl-C handler");
 
-    // 2. Parse & Run
-    let args = cli::args::Cli::parse();
-    ui::set_quiet(args.global.quiet);
-    ui::set_verbose(args.global.verbose);
+    // 2. Run
+    args.global.apply_output_defaults();
+    ui::set_log_level(args.global.effective_log_level());
+    config::loader::set_parse_cache_disabled(args.global.no_cache);
+    utils::remote::set_frozen(args.global.frozen);
+    if let Some(path) = &args.global.state_file {
+        utils::paths::set_state_file_override(path.clone());
+    }
+    let machine_mode = matches!(
+        args.global.format.as_deref(),
+        Some("json" | "yaml" | "toml" | "jsonl")
+    );
+    if let Some(path) = &args.global.config
+        && let Err(e) = apply_config_override(path)
+    {
+        report_error(&e, machine_mode);
+    }
+
+    let started = Instant::now();
+    let result = cli::dispatcher::dispatch(&args);
+    ui::trace(&format!("Command finished in {:.2?}", started.elapsed()));
+
+    if let Err(e) = result {
+        report_error(&e, machine_mode);
+    }
+}
+
+/// `--config <PATH>`, or `--config -` to read the root config from stdin.
+///
+/// A stdin config resolves modules and imports from the current directory.
+fn apply_config_override(path: &std::path::Path) -> error::Result<()> {
+    use std::io::{IsTerminal, Read};
+
+    if path.as_os_str() != "-" {
+        return utils::paths::set_config_file_override(path);
+    }
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        return Err(error::DeclarchError::ConfigError(
+            "--config - reads the config from stdin; pipe a KDL config into it".to_string(),
+        ));
+    }
+    let mut content = String::new();
+    stdin.read_to_string(&mut content)?;
+    config::loader::set_stdin_root_config(content);
+    ui::mark_stdin_taken();
+
+    let base_dir = std::env::current_dir().unwrap_or_default();
+    utils::paths::set_config_file_placeholder(base_dir.join(config::loader::STDIN_CONFIG_NAME));
+    Ok(())
+}
 
-    if let Err(e) = cli::dispatcher::dispatch(&args) {
+/// Print the error and exit with its category's code.
+///
+/// Machine mode prefixes the stable error code so scripts need not parse the message.
+fn report_error(e: &error::DeclarchError, machine_mode: bool) -> ! {
+    if machine_mode {
+        eprintln!("error[{}]: {}", e.code(), e);
+    } else {
         ui::error(&format!("{}", e));
-        exit(1);
     }
+    exit(e.exit_code());
 }
@@ -63,6 +63,16 @@ impl BackendRegistry {
         _config: &GlobalConfig,
         noconfirm: bool,
     ) -> Result<Box<dyn PackageManager>, String> {
+        Ok(Box::new(self.create_generic_manager(backend, noconfirm)?))
+    }
+
+    /// Create the generic manager for the given backend, for callers that
+    /// still need to configure it (e.g. `with_no_sudo`)
+    pub fn create_generic_manager(
+        &self,
+        backend: &Backend,
+        noconfirm: bool,
+    ) -> Result<GenericManager, String> {
         let backend_name = backend.name();
 
         // Look up backend config
@@ -75,11 +85,11 @@ impl BackendRegistry {
         })?;
 
         // Create GenericManager from config
-        Ok(Box::new(GenericManager::from_config(
+        Ok(GenericManager::from_config(
             backend_config.clone(),
             backend.clone(),
             noconfirm,
-        )))
+        ))
     }
 }
 
@@ -120,5 +130,18 @@ pub fn create_manager(
     registry_guard.create_manager(backend, config, noconfirm)
 }
 
+/// Create a generic manager using the global registry
+pub fn create_generic_manager(
+    backend: &Backend,
+    noconfirm: bool,
+) -> Result<GenericManager, String> {
+    let registry = get_registry();
+    let registry_guard = registry
+        .lock()
+        .map_err(|e| format!("Registry lock failed: {}", e))?;
+
+    registry_guard.create_generic_manager(backend, noconfirm)
+}
+
 #[cfg(test)]
 mod tests;
@@ -1,7 +1,7 @@
 use crate::core::types::{Backend, PackageMetadata}; // PackageId dihapus
-use crate::error::Result;
+use crate::error::{DeclarchError, Result};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Search result from package repository
 #[derive(Debug, Clone, Serialize)]
@@ -10,15 +10,165 @@ pub struct PackageSearchResult {
     pub version: Option<String>,
     pub description: Option<String>,
     pub backend: Backend,
+    /// Version recorded in state when the package is already installed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installed_version: Option<String>,
+}
+
+/// Installed package with a newer version available
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutdatedPackage {
+    pub name: String,
+    pub current: Option<String>,
+    pub available: Option<String>,
+    pub backend: Backend,
+}
+
+/// Details about one package, as reported by the backend's `info_cmd`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PackageInfo {
+    pub name: String,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub homepage: Option<String>,
+    pub depends: Vec<String>,
+    pub backend: Backend,
+}
+
+/// Reverse-dependency view of the installed packages, from `dependencies_cmd`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependencyGraph {
+    /// Package name -> installed packages that require it
+    pub required_by: HashMap<String, Vec<String>>,
+    /// Packages installed as a dependency (not explicitly)
+    pub as_dependency: HashSet<String>,
+}
+
+/// A sync step whose shell commands can be listed without running them
+#[derive(Debug, Clone, Copy)]
+pub enum PlannedOperation<'a> {
+    /// Backend-defined setup run before a batch of installs
+    Preinstall,
+    Install {
+        packages: &'a [String],
+        /// Per-package `extra-args`
+        extra_args: Option<&'a str>,
+        /// Remote recorded in state
+        remote: Option<&'a str>,
+    },
+    Reinstall(&'a [String]),
+    /// Backend-defined step run after a batch of installs
+    Postinstall,
+    Remove(&'a [String]),
+    /// Package index refresh (`sync --update`)
+    Update,
+}
+
+/// Outcome category of a backend health probe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HealthStatus {
+    Ok,
+    BinaryMissing,
+    CommandFailed,
+    ParseFailed,
+}
+
+impl HealthStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            HealthStatus::Ok => "ok",
+            HealthStatus::BinaryMissing => "binary-missing",
+            HealthStatus::CommandFailed => "command-failed",
+            HealthStatus::ParseFailed => "parse-failed",
+        }
+    }
+}
+
+/// Result of probing a backend: binary lookup plus one `list_installed` run
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub backend: Backend,
+    pub status: HealthStatus,
+    /// Number of installed packages reported, when listing succeeded
+    pub package_count: Option<usize>,
+    /// Error message or note explaining the status
+    pub detail: Option<String>,
+}
+
+impl HealthReport {
+    pub fn binary_missing(backend: Backend) -> Self {
+        Self {
+            backend,
+            status: HealthStatus::BinaryMissing,
+            package_count: None,
+            detail: None,
+        }
+    }
+
+    /// Categorize a `list_installed` outcome.
+    ///
+    /// Command runtime errors and non-zero exits are command failures; any
+    /// other error comes from turning the output into packages.
+    pub fn from_list_result(
+        backend: Backend,
+        result: Result<HashMap<String, PackageMetadata>>,
+    ) -> Self {
+        let (status, package_count, detail) = match result {
+            Ok(packages) => (HealthStatus::Ok, Some(packages.len()), None),
+            Err(
+                e @ (DeclarchError::SystemCommandFailed { .. }
+                | DeclarchError::PackageManagerError(_)
+                | DeclarchError::DependencyMissing(_)
+                | DeclarchError::StdIoError(_)),
+            ) => (HealthStatus::CommandFailed, None, Some(e.to_string())),
+            Err(e) => (HealthStatus::ParseFailed, None, Some(e.to_string())),
+        };
+        Self {
+            backend,
+            status,
+            package_count,
+            detail,
+        }
+    }
 }
 
 pub trait PackageManager: Send + Sync {
     fn backend_type(&self) -> Backend;
     fn list_installed(&self) -> Result<HashMap<String, PackageMetadata>>;
     fn install(&self, packages: &[String]) -> Result<()>;
+
+    /// Install packages with `extra_args` appended to the install command
+    /// Default: not supported
+    fn install_with_args(&self, _packages: &[String], extra_args: &str) -> Result<()> {
+        Err(crate::error::DeclarchError::PackageManagerError(format!(
+            "{} does not support per-package install arguments ({})",
+            self.backend_type(),
+            extra_args
+        )))
+    }
+
+    /// Install packages from a specific remote (the one recorded in state)
+    /// Default: a regular install
+    fn install_from_remote(&self, packages: &[String], _remote: &str) -> Result<()> {
+        self.install(packages)
+    }
+
+    /// Install packages again even though they are already present
+    /// Default: a regular install
+    fn reinstall(&self, packages: &[String]) -> Result<()> {
+        self.install(packages)
+    }
+
     fn remove(&self, packages: &[String]) -> Result<()>;
     fn is_available(&self) -> bool;
 
+    /// Shell commands `operation` would run, built but not run
+    /// Default: none known
+    fn planned_commands(&self, _operation: PlannedOperation<'_>) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
     /// Get list of packages that depend on the given package (reverse dependencies)
     fn get_required_by(&self, package: &str) -> Result<Vec<String>>;
 
@@ -32,6 +182,12 @@ pub trait PackageManager: Send + Sync {
         false // Default: no search support
     }
 
+    /// Search packages by description
+    /// Default: regular search; callers filter results by description
+    fn search_description(&self, query: &str) -> Result<Vec<PackageSearchResult>> {
+        self.search(query)
+    }
+
     /// Update package list/index (e.g., apt update, pacman -Sy)
     /// Default: no update support
     fn update(&self) -> Result<()> {
@@ -65,6 +221,25 @@ pub trait PackageManager: Send + Sync {
         false // Default: no upgrade support
     }
 
+    /// Run the backend-defined setup command before an install batch
+    /// Default: nothing to run
+    fn preinstall(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Run the backend-defined command after an install batch
+    /// Default: nothing to run
+    fn postinstall(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Check that the planned installs/removals would be accepted, without
+    /// changing anything
+    /// Default: no validation
+    fn validate_transaction(&self, _to_install: &[String], _to_remove: &[String]) -> Result<()> {
+        Ok(())
+    }
+
     /// Search for packages in locally installed packages only
     /// Default: no local search support
     fn search_local(&self, _query: &str) -> Result<Vec<PackageSearchResult>> {
@@ -75,4 +250,72 @@ pub trait PackageManager: Send + Sync {
     fn supports_search_local(&self) -> bool {
         false // Default: no local search support
     }
+
+    /// Download/install size of a package not yet installed, in bytes
+    /// Default: unknown
+    fn package_size(&self, _package: &str) -> Result<Option<u64>> {
+        Ok(None)
+    }
+
+    /// Check if this package manager can report package sizes
+    fn supports_package_size(&self) -> bool {
+        false // Default: no size support
+    }
+
+    /// Installed packages that have a newer version available
+    /// Default: none reported
+    fn list_outdated(&self) -> Result<Vec<OutdatedPackage>> {
+        Ok(Vec::new())
+    }
+
+    /// Check if this package manager can report outdated packages
+    fn supports_outdated(&self) -> bool {
+        false // Default: no outdated support
+    }
+
+    /// Dependencies that no installed package requires any more
+    /// Default: none reported
+    fn list_orphans(&self) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Check if this package manager can report orphaned dependencies
+    fn supports_orphans(&self) -> bool {
+        false // Default: no orphans support
+    }
+
+    /// Which installed packages require which, and which were installed as dependencies
+    /// Default: empty graph
+    fn dependency_graph(&self) -> Result<DependencyGraph> {
+        Ok(DependencyGraph::default())
+    }
+
+    /// Check if this package manager can report its dependency graph
+    fn supports_dependency_graph(&self) -> bool {
+        false // Default: no dependency graph support
+    }
+
+    /// Details about one package (description, version, dependencies, homepage)
+    /// Default: not supported
+    fn info(&self, package: &str) -> Result<PackageInfo> {
+        Err(DeclarchError::PackageManagerError(format!(
+            "info unsupported for backend '{}' (cannot show '{}')",
+            self.backend_type(),
+            package
+        )))
+    }
+
+    /// Check if this package manager can report package details
+    fn supports_info(&self) -> bool {
+        false // Default: no info support
+    }
+
+    /// Probe availability and a single `list_installed` run
+    /// Default: no time limit beyond what `list_installed` enforces
+    fn health_check(&self) -> HealthReport {
+        if !self.is_available() {
+            return HealthReport::binary_missing(self.backend_type());
+        }
+        HealthReport::from_list_result(self.backend_type(), self.list_installed())
+    }
 }
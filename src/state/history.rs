@@ -0,0 +1,150 @@
+//! Sync history
+//!
+//! Each sync that changes packages appends one JSON file to the `history`
+//! directory next to the state file: what was installed, pruned and adopted,
+//! the target used, and the state the sync left behind.
+
+use crate::error::{DeclarchError, Result};
+use crate::state::types::State;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    /// Sync target, `None` for a full sync
+    #[serde(default)]
+    pub target: Option<String>,
+    /// Packages installed by this sync (`backend:name`)
+    #[serde(default)]
+    pub installed: Vec<String>,
+    /// Packages removed by prune (`backend:name`)
+    #[serde(default)]
+    pub pruned: Vec<String>,
+    /// Already-installed packages newly recorded in state (`backend:name`)
+    #[serde(default)]
+    pub adopted: Vec<String>,
+    /// State as saved after this sync
+    pub state: State,
+}
+
+impl HistoryEntry {
+    /// One-line audit summary, e.g. `2024-06-01 12:00 — +3 -1, target=aur`
+    pub fn summary(&self) -> String {
+        let mut line = format!(
+            "{} — +{} -{}",
+            self.timestamp.format("%Y-%m-%d %H:%M"),
+            self.installed.len(),
+            self.pruned.len()
+        );
+        if !self.adopted.is_empty() {
+            line.push_str(&format!(" ~{}", self.adopted.len()));
+        }
+        line.push_str(&format!(
+            ", target={}",
+            self.target.as_deref().unwrap_or("all")
+        ));
+        line
+    }
+}
+
+/// Write `entry` as a new file in `dir`, creating the directory if needed.
+pub fn record_entry(dir: &Path, entry: &HistoryEntry) -> Result<PathBuf> {
+    fs::create_dir_all(dir).map_err(|e| DeclarchError::IoError {
+        path: dir.to_path_buf(),
+        source: e,
+    })?;
+
+    let path = dir.join(format!(
+        "{}.json",
+        entry.timestamp.format("%Y%m%dT%H%M%S%.3fZ")
+    ));
+    let content = serde_json::to_string_pretty(entry)?;
+    fs::write(&path, content).map_err(|e| DeclarchError::IoError {
+        path: path.clone(),
+        source: e,
+    })?;
+    Ok(path)
+}
+
+/// Load history entries, newest first. Files that do not parse are skipped.
+pub fn load_entries(dir: &Path) -> Result<Vec<HistoryEntry>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(dir).map_err(|e| DeclarchError::IoError {
+        path: dir.to_path_buf(),
+        source: e,
+    })?;
+
+    let mut history: Vec<HistoryEntry> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|content| serde_json::from_str(&content).ok())
+        .collect();
+    history.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp));
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::types::StateMeta;
+    use chrono::TimeZone;
+    use std::collections::HashMap;
+
+    fn entry(minute: u32, target: Option<&str>) -> HistoryEntry {
+        let timestamp = Utc.with_ymd_and_hms(2024, 6, 1, 12, minute, 0).unwrap();
+        HistoryEntry {
+            timestamp,
+            target: target.map(str::to_string),
+            installed: vec!["aur:bat".into(), "aur:fd".into(), "aur:rg".into()],
+            pruned: vec!["aur:htop".into()],
+            adopted: Vec::new(),
+            state: State {
+                meta: StateMeta {
+                    last_sync: timestamp,
+                    ..State::default().meta
+                },
+                packages: HashMap::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn summary_is_human_auditable() {
+        assert_eq!(
+            entry(0, Some("aur")).summary(),
+            "2024-06-01 12:00 — +3 -1, target=aur"
+        );
+        assert_eq!(
+            entry(5, None).summary(),
+            "2024-06-01 12:05 — +3 -1, target=all"
+        );
+    }
+
+    #[test]
+    fn entries_round_trip_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let history_dir = dir.path().join("history");
+        record_entry(&history_dir, &entry(0, None)).unwrap();
+        record_entry(&history_dir, &entry(30, Some("aur"))).unwrap();
+        fs::write(history_dir.join("broken.json"), "{").unwrap();
+
+        let loaded = load_entries(&history_dir).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].target.as_deref(), Some("aur"));
+        assert_eq!(loaded[1].installed.len(), 3);
+
+        assert!(
+            load_entries(&dir.path().join("missing"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
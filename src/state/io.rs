@@ -8,7 +8,9 @@ use crate::error::{DeclarchError, Result};
 use crate::state::types::State;
 use crate::utils::paths;
 use load_recovery::load_state_from_path;
-pub use locking::{StateLock, acquire_lock};
+#[cfg(test)]
+use load_recovery::written_by_newer_version;
+pub use locking::{LockOwner, StateLock, UnlockOutcome, acquire_lock, force_unlock, is_lock_stale};
 use migration::sanitize_state_in_place;
 use persist::prepare_and_write_state;
 use std::fs::{self};
@@ -33,8 +33,23 @@ pub(super) fn restore_from_backup(state_path: &PathBuf) -> Result<Option<State>>
     Ok(None)
 }
 
+/// Keep the current state file as `state.json.bak.1`, shifting older backups.
+///
+/// A primary that no longer parses is not backed up, so it can never push the
+/// last good copy out of rotation.
 pub(super) fn rotate_backups(dir: &Path, path: &Path) -> Result<()> {
     if path.exists() {
+        let parses = fs::read_to_string(path)
+            .ok()
+            .is_some_and(|content| serde_json::from_str::<State>(&content).is_ok());
+        if !parses {
+            ui::warning(&format!(
+                "Not backing up unreadable state file {}",
+                path.display()
+            ));
+            return Ok(());
+        }
+
         let max_backups = 3;
         for i in (1..max_backups).rev() {
             let old_bak = dir.join(format!("state.json.bak.{}", i));
@@ -2,6 +2,7 @@ use crate::error::{DeclarchError, Result};
 use crate::project_identity;
 use crate::state::types::State;
 use crate::ui;
+use crate::utils::update_check::{compare_versions, current_version};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -19,6 +20,19 @@ pub(super) fn load_state_from_path(path: &Path, strict_recovery: bool) -> Result
     let state = match content {
         Ok(content) => match serde_json::from_str::<State>(&content) {
             Ok(mut state) => {
+                let running = current_version();
+                if written_by_newer_version(state.meta.declarch_version.as_deref(), &running) {
+                    ui::warning(&format!(
+                        "State was last written by a newer version ({}) than this binary ({})",
+                        state.meta.declarch_version.as_deref().unwrap_or_default(),
+                        running
+                    ));
+                    ui::indent(
+                        "The state schema may be incompatible; consider upgrading before syncing.",
+                        2,
+                    );
+                }
+
                 let issues = validate_state_integrity(&state);
                 if !issues.is_empty() {
                     ui::warning("State integrity issues detected:");
@@ -59,6 +73,12 @@ pub(super) fn load_state_from_path(path: &Path, strict_recovery: bool) -> Result
     Ok(state)
 }
 
+/// Whether the recorded writer version is newer than the running binary.
+pub(super) fn written_by_newer_version(recorded: Option<&str>, running: &str) -> bool {
+    recorded
+        .is_some_and(|recorded| compare_versions(recorded, running) == std::cmp::Ordering::Greater)
+}
+
 fn recover_or_default(
     path: &PathBuf,
     strict_recovery: bool,
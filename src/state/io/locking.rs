@@ -1,10 +1,12 @@
 use crate::error::{DeclarchError, Result};
 use crate::project_identity;
 use crate::ui;
+use crate::utils::platform;
+use chrono::{DateTime, Utc};
 use fs2::FileExt;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 const LOCK_TIMEOUT_SECONDS: u64 = 300;
@@ -20,16 +22,127 @@ impl Drop for StateLock {
     }
 }
 
+/// Process recorded in a lock file (`<pid>\n<started_at>`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockOwner {
+    pub pid: u32,
+    pub started_at: Option<DateTime<Utc>>,
+}
+
+impl LockOwner {
+    fn parse(content: &str) -> Option<Self> {
+        let mut lines = content.lines().map(str::trim);
+        let pid = lines.next()?.parse().ok()?;
+        let started_at = lines
+            .next()
+            .and_then(|line| DateTime::parse_from_rfc3339(line).ok())
+            .map(|t| t.with_timezone(&Utc));
+        Some(Self { pid, started_at })
+    }
+
+    pub fn describe(&self) -> String {
+        match self.started_at {
+            Some(started) => format!("PID {} (started {})", self.pid, started.to_rfc3339()),
+            None => format!("PID {}", self.pid),
+        }
+    }
+}
+
+fn read_lock_owner(lock_path: &Path) -> Option<LockOwner> {
+    fs::read_to_string(lock_path)
+        .ok()
+        .and_then(|content| LockOwner::parse(&content))
+}
+
+fn lock_path_for_state_path(path: &Path) -> Result<PathBuf> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| DeclarchError::Other("Could not determine state directory".into()))?;
+    Ok(dir.join("state.lock"))
+}
+
+/// Whether a lock file exists whose recorded owner process is gone.
+pub fn is_lock_stale() -> Result<bool> {
+    let path = super::get_state_path()?;
+    is_lock_stale_for_state_path(&path)
+}
+
+fn is_lock_stale_for_state_path(path: &Path) -> Result<bool> {
+    let lock_path = lock_path_for_state_path(path)?;
+    if !lock_path.exists() {
+        return Ok(false);
+    }
+    match read_lock_owner(&lock_path) {
+        Some(owner) => Ok(!platform::process_alive(owner.pid)),
+        // No usable owner recorded: fall back to whether anyone holds it
+        None => Ok(stale_lock_for_state_path(path)?.is_some()),
+    }
+}
+
+/// Result of [`force_unlock`].
+#[derive(Debug, PartialEq)]
+pub enum UnlockOutcome {
+    /// There was no lock file
+    NoLock,
+    /// A stale lock was removed; its owner if one was recorded
+    Removed(Option<LockOwner>),
+}
+
+/// Remove the lock file if its owner is gone (`--force-unlock`, `unlock`).
+///
+/// A lock held by a live process is refused.
+pub fn force_unlock() -> Result<UnlockOutcome> {
+    let path = super::get_state_path()?;
+    force_unlock_for_state_path(&path)
+}
+
+fn force_unlock_for_state_path(path: &Path) -> Result<UnlockOutcome> {
+    let lock_path = lock_path_for_state_path(path)?;
+    if !lock_path.exists() {
+        return Ok(UnlockOutcome::NoLock);
+    }
+
+    let owner = read_lock_owner(&lock_path);
+    if !is_lock_stale_for_state_path(path)? {
+        return Err(DeclarchError::Other(format!(
+            "Lock file {} is held by a running process ({}); refusing to remove it.",
+            lock_path.display(),
+            owner
+                .as_ref()
+                .map(LockOwner::describe)
+                .unwrap_or_else(|| "unknown PID".to_string())
+        )));
+    }
+
+    fs::remove_file(&lock_path).map_err(|e| DeclarchError::IoError {
+        path: lock_path.clone(),
+        source: e,
+    })?;
+    Ok(UnlockOutcome::Removed(owner))
+}
+
 pub fn acquire_lock() -> Result<StateLock> {
     let path = super::get_state_path()?;
     acquire_lock_for_state_path(path)
 }
 
+/// Lock file that no process holds an flock on, if any (fallback when no owner is recorded).
+fn stale_lock_for_state_path(path: &Path) -> Result<Option<PathBuf>> {
+    let lock_path = lock_path_for_state_path(path)?;
+    if !lock_path.exists() {
+        return Ok(None);
+    }
+
+    let file = OpenOptions::new().write(true).open(&lock_path)?;
+    if file.try_lock_exclusive().is_ok() {
+        let _ = FileExt::unlock(&file);
+        return Ok(Some(lock_path));
+    }
+    Ok(None)
+}
+
 fn acquire_lock_for_state_path(path: PathBuf) -> Result<StateLock> {
-    let dir = path
-        .parent()
-        .ok_or_else(|| DeclarchError::Other("Could not determine state directory".into()))?;
-    let lock_path = dir.join("state.lock");
+    let lock_path = lock_path_for_state_path(&path)?;
 
     if lock_path.exists() {
         let metadata = fs::metadata(&lock_path)?;
@@ -57,13 +170,18 @@ fn acquire_lock_for_state_path(path: PathBuf) -> Result<StateLock> {
                 } else {
                     String::new()
                 };
+                let owner = read_lock_owner(&lock_path)
+                    .map(|owner| format!(" ({})", owner.describe()))
+                    .unwrap_or_default();
                 return Err(DeclarchError::Other(format!(
-                    "Another {} process is currently running.\n\
+                    "Another {} process is currently running{}.\n\
                      Lock file: {}{}\n\
-                     Wait for it to complete, or delete the lock file if you're sure no other process is running.",
+                     Wait for it to complete, or run '{}' if that process is gone.",
                     project_identity::BINARY_NAME,
+                    owner,
                     lock_path.display(),
-                    age_hint
+                    age_hint,
+                    project_identity::cli_with("unlock")
                 )));
             }
         }
@@ -84,7 +202,7 @@ fn acquire_lock_for_state_path(path: PathBuf) -> Result<StateLock> {
         .map_err(|e| DeclarchError::Other(format!("Failed to lock state file: {}", e)))?;
 
     let pid = std::process::id();
-    let _ = writeln!(&lock_file, "{}", pid);
+    let _ = writeln!(&lock_file, "{}\n{}", pid, Utc::now().to_rfc3339());
 
     Ok(StateLock {
         _file: lock_file,
@@ -94,7 +212,10 @@ fn acquire_lock_for_state_path(path: PathBuf) -> Result<StateLock> {
 
 #[cfg(test)]
 mod tests {
-    use super::acquire_lock_for_state_path;
+    use super::{
+        LockOwner, UnlockOutcome, acquire_lock_for_state_path, force_unlock_for_state_path,
+        is_lock_stale_for_state_path, stale_lock_for_state_path,
+    };
     use tempfile::tempdir;
 
     #[test]
@@ -108,4 +229,67 @@ mod tests {
             Err(err) => assert!(err.to_string().contains("currently running")),
         }
     }
+
+    #[test]
+    fn stale_lock_is_reported_only_when_unheld() {
+        let dir = tempdir().expect("tempdir");
+        let state_path = dir.path().join("state.json");
+        assert!(stale_lock_for_state_path(&state_path).unwrap().is_none());
+
+        let lock = acquire_lock_for_state_path(state_path.clone()).expect("lock");
+        assert!(stale_lock_for_state_path(&state_path).unwrap().is_none());
+
+        // A crashed process leaves the file behind without holding the lock
+        drop(lock);
+        std::fs::write(dir.path().join("state.lock"), "12345\n").unwrap();
+        assert!(stale_lock_for_state_path(&state_path).unwrap().is_some());
+    }
+
+    // Far above any kernel pid_max, so never a live process
+    const DEAD_PID: u32 = i32::MAX as u32;
+
+    #[test]
+    fn lock_records_owner_pid_and_start_time() {
+        let dir = tempdir().expect("tempdir");
+        let state_path = dir.path().join("state.json");
+
+        let _lock = acquire_lock_for_state_path(state_path).expect("lock");
+        let content = std::fs::read_to_string(dir.path().join("state.lock")).unwrap();
+        let owner = LockOwner::parse(&content).expect("owner recorded");
+        assert_eq!(owner.pid, std::process::id());
+        assert!(owner.started_at.is_some());
+    }
+
+    #[test]
+    fn force_unlock_removes_lock_of_dead_pid() {
+        let dir = tempdir().expect("tempdir");
+        let state_path = dir.path().join("state.json");
+        let lock_path = dir.path().join("state.lock");
+        std::fs::write(&lock_path, format!("{}\n2026-01-01T00:00:00Z\n", DEAD_PID)).unwrap();
+
+        assert!(is_lock_stale_for_state_path(&state_path).unwrap());
+        let UnlockOutcome::Removed(Some(owner)) = force_unlock_for_state_path(&state_path).unwrap()
+        else {
+            panic!("stale lock should be removed");
+        };
+        assert_eq!(owner.pid, DEAD_PID);
+        assert!(!lock_path.exists());
+        assert_eq!(
+            force_unlock_for_state_path(&state_path).unwrap(),
+            UnlockOutcome::NoLock
+        );
+    }
+
+    #[test]
+    fn force_unlock_refuses_live_pid() {
+        let dir = tempdir().expect("tempdir");
+        let state_path = dir.path().join("state.json");
+        let lock_path = dir.path().join("state.lock");
+        std::fs::write(&lock_path, format!("{}\n", std::process::id())).unwrap();
+
+        assert!(!is_lock_stale_for_state_path(&state_path).unwrap());
+        let err = force_unlock_for_state_path(&state_path).unwrap_err();
+        assert!(err.to_string().contains("refusing"));
+        assert!(lock_path.exists());
+    }
 }
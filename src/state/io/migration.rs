@@ -128,6 +128,7 @@ mod tests {
             version: Some("0.25.0".to_string()),
             install_reason: None,
             source_module: None,
+            pinned: false,
             last_seen_at: None,
             backend_meta: None,
         };
@@ -2,6 +2,7 @@ use crate::error::{DeclarchError, Result};
 use crate::project_identity;
 use crate::state::types::State;
 use crate::ui;
+use crate::utils::update_check::current_version;
 use std::fs;
 use std::io::Write;
 use std::path::Path;
@@ -25,6 +26,7 @@ fn normalize_state_for_persist(state: &State) -> State {
     if state.meta.generator.is_none() {
         state.meta.generator = Some(project_identity::STABLE_PROJECT_ID.to_string());
     }
+    state.meta.declarch_version = Some(current_version());
     state
 }
 
@@ -1,6 +1,9 @@
-use super::{load_state_from_path, sanitize_state_in_place, validate_state_integrity};
+use super::{
+    load_state_from_path, prepare_and_write_state, sanitize_state_in_place,
+    validate_state_integrity, written_by_newer_version,
+};
 use crate::project_identity;
-use crate::state::types::{Backend, PackageState, State};
+use crate::state::types::{Backend, PackageBackendMeta, PackageState, State};
 use chrono::Utc;
 use std::fs;
 use tempfile::tempdir;
@@ -19,6 +22,7 @@ fn sanitize_removes_empty_and_rekeys() {
             version: Some("1.0".to_string()),
             install_reason: None,
             source_module: None,
+            pinned: false,
             last_seen_at: None,
             backend_meta: None,
         },
@@ -34,6 +38,7 @@ fn sanitize_removes_empty_and_rekeys() {
             version: None,
             install_reason: None,
             source_module: None,
+            pinned: false,
             last_seen_at: None,
             backend_meta: None,
         },
@@ -60,6 +65,7 @@ fn validate_flags_non_canonical_keys() {
             version: None,
             install_reason: None,
             source_module: None,
+            pinned: false,
             last_seen_at: None,
             backend_meta: None,
         },
@@ -130,6 +136,7 @@ fn load_state_strict_uses_next_backup_when_first_is_invalid() {
             version: Some("1.0".to_string()),
             install_reason: None,
             source_module: None,
+            pinned: false,
             last_seen_at: None,
             backend_meta: None,
         },
@@ -143,3 +150,85 @@ fn load_state_strict_uses_next_backup_when_first_is_invalid() {
     let loaded = load_state_from_path(&path, true).expect("strict should use .bak.2");
     assert!(loaded.packages.contains_key("aur:bat"));
 }
+
+fn state_for_host(hostname: &str) -> State {
+    let mut state = State::default();
+    state.meta.hostname = hostname.to_string();
+    state
+}
+
+#[test]
+fn save_keeps_previous_state_for_recovery_after_corruption() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("state.json");
+    prepare_and_write_state(&state_for_host("first"), &path, false).expect("first save");
+    prepare_and_write_state(&state_for_host("second"), &path, true).expect("second save");
+    assert!(!dir.path().join("state.tmp").exists());
+
+    fs::write(&path, "{\"meta\": {trunc").expect("corrupt primary");
+
+    let loaded = load_state_from_path(&path, false).expect("recover from backup");
+    assert_eq!(loaded.meta.hostname, "first");
+}
+
+#[test]
+fn backend_meta_remote_round_trips_through_state() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("state.json");
+    let mut state = State::default();
+    let mut entry = PackageState::from_config(
+        "org.mozilla.firefox".to_string(),
+        Backend::from("flatpak"),
+        None,
+    );
+    entry.backend_meta = Some(PackageBackendMeta {
+        remote: Some("flathub-beta".to_string()),
+    });
+    state.packages.insert(entry.state_key(), entry);
+    state.packages.insert(
+        "flatpak:com.spotify.Client".to_string(),
+        PackageState::from_config(
+            "com.spotify.Client".to_string(),
+            Backend::from("flatpak"),
+            None,
+        ),
+    );
+
+    prepare_and_write_state(&state, &path, false).expect("save");
+    let loaded = load_state_from_path(&path, true).expect("load");
+
+    assert_eq!(
+        loaded.packages["flatpak:org.mozilla.firefox"].remote(),
+        Some("flathub-beta")
+    );
+    assert_eq!(loaded.packages["flatpak:com.spotify.Client"].remote(), None);
+    assert!(
+        !fs::read_to_string(&path)
+            .unwrap()
+            .contains("\"backend_meta\": null")
+    );
+}
+
+#[test]
+fn corrupted_primary_is_not_rotated_into_backups() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("state.json");
+    prepare_and_write_state(&state_for_host("good"), &path, false).expect("first save");
+    prepare_and_write_state(&state_for_host("good"), &path, false).expect("second save");
+    fs::write(&path, "{broken json").expect("corrupt primary");
+
+    prepare_and_write_state(&state_for_host("next"), &path, false).expect("save over corruption");
+
+    let backup = fs::read_to_string(dir.path().join("state.json.bak.1")).expect("read backup");
+    let backup: State = serde_json::from_str(&backup).expect("backup stays valid");
+    assert_eq!(backup.meta.hostname, "good");
+}
+
+#[test]
+fn newer_writer_version_is_detected() {
+    assert!(written_by_newer_version(Some("0.9.0"), "0.8.3"));
+    assert!(written_by_newer_version(Some("v1.0.0"), "0.8.3"));
+    assert!(!written_by_newer_version(Some("0.8.3"), "0.8.3"));
+    assert!(!written_by_newer_version(Some("0.8.0"), "0.8.3"));
+    assert!(!written_by_newer_version(None, "0.8.3"));
+}
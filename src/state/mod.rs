@@ -1,2 +1,3 @@
+pub mod history;
 pub mod io;
 pub mod types;
@@ -30,6 +30,10 @@ pub struct StateMeta {
     #[serde(default)]
     pub generator: Option<String>,
 
+    /// Version of the binary that last wrote this state (absent in older state files)
+    #[serde(default)]
+    pub declarch_version: Option<String>,
+
     /// Optional feature flags written to state (non-breaking extension point)
     #[serde(default)]
     pub features: Option<Vec<String>>,
@@ -66,13 +70,25 @@ pub struct PackageState {
     #[serde(default)]
     pub source_module: Option<String>,
 
+    /// Kept out of prune even when no longer declared (`pinned=#true`)
+    #[serde(default)]
+    pub pinned: bool,
+
     /// Last time this package was observed in installed snapshot
     #[serde(default)]
     pub last_seen_at: Option<DateTime<Utc>>,
 
-    /// Backend-specific extension payload for future features.
-    #[serde(default)]
-    pub backend_meta: Option<serde_json::Value>,
+    /// Backend-specific details recorded at install time (e.g. flatpak remote)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend_meta: Option<PackageBackendMeta>,
+}
+
+/// Backend-specific details of an installed package.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageBackendMeta {
+    /// Remote/repository the package was installed from (`flathub`, `flathub-beta`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
 }
 
 // Implement Default for PackageState to help with serde defaults
@@ -87,6 +103,7 @@ impl Default for PackageState {
             version: None,
             install_reason: None,
             source_module: None,
+            pinned: false,
             last_seen_at: None,
             backend_meta: None,
         }
@@ -105,6 +122,7 @@ impl PackageState {
             version,
             install_reason: Some("declared".to_string()),
             source_module: None,
+            pinned: false,
             last_seen_at: Some(Utc::now()),
             backend_meta: None,
         }
@@ -125,6 +143,11 @@ impl PackageState {
     pub fn state_key(&self) -> String {
         format!("{}:{}", self.backend, self.provides_name)
     }
+
+    /// Remote recorded for this package, if any
+    pub fn remote(&self) -> Option<&str> {
+        self.backend_meta.as_ref()?.remote.as_deref()
+    }
 }
 
 impl Default for State {
@@ -137,6 +160,7 @@ impl Default for State {
                 last_update: None, // Default is never updated via the CLI
                 state_revision: Some(1),
                 generator: Some(project_identity::STABLE_PROJECT_ID.to_string()),
+                declarch_version: None,
                 features: None,
             },
             packages: HashMap::new(),
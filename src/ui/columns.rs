@@ -0,0 +1,128 @@
+//! Column layout for terminal tables
+//!
+//! Widths are measured in terminal cells, not bytes: CJK and emoji take two
+//! cells, markers like `✓` take one, and ANSI color codes take none, so cells
+//! may be pre-colored by the caller.
+
+use unicode_width::UnicodeWidthStr;
+
+/// Gap between columns
+const COLUMN_GAP: &str = "  ";
+
+/// Number of terminal cells `s` occupies, ignoring ANSI escape sequences.
+pub fn display_width(s: &str) -> usize {
+    strip_ansi(s).width()
+}
+
+/// Lay out `headers` and `rows` as aligned lines (header first, if any).
+///
+/// The last column is not padded, and trailing whitespace is trimmed.
+pub fn format_table(headers: &[&str], rows: &[Vec<String>]) -> Vec<String> {
+    let header_row: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+    let all_rows: Vec<&Vec<String>> = (!headers.is_empty())
+        .then_some(&header_row)
+        .into_iter()
+        .chain(rows)
+        .collect();
+
+    let widths = column_widths(&all_rows);
+    all_rows
+        .iter()
+        .map(|row| {
+            let mut line = String::new();
+            for (i, cell) in row.iter().enumerate() {
+                if i > 0 {
+                    line.push_str(COLUMN_GAP);
+                }
+                line.push_str(cell);
+                if i + 1 < row.len() {
+                    let padding = widths[i].saturating_sub(display_width(cell));
+                    line.push_str(&" ".repeat(padding));
+                }
+            }
+            line.trim_end().to_string()
+        })
+        .collect()
+}
+
+fn column_widths(rows: &[&Vec<String>]) -> Vec<usize> {
+    let mut widths: Vec<usize> = Vec::new();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            let width = display_width(cell);
+            match widths.get_mut(i) {
+                Some(current) => *current = (*current).max(width),
+                None => widths.push(width),
+            }
+        }
+    }
+    widths
+}
+
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // CSI sequence: ESC [ params... final byte in '@'..='~'
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_width_counts_terminal_cells() {
+        assert_eq!(display_width("bat"), 3);
+        assert_eq!(display_width("bat ✓"), 5);
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("📦 pkg"), 6);
+        assert_eq!(display_width("\x1b[32mbat\x1b[0m"), 3);
+    }
+
+    #[test]
+    fn format_table_pads_by_display_width() {
+        let rows = vec![
+            vec!["bat ✓".to_string(), "cat clone".to_string()],
+            vec!["日本語".to_string(), "cjk".to_string()],
+            vec!["📦".to_string(), String::new()],
+        ];
+
+        let lines = format_table(&["NAME", "DESCRIPTION"], &rows);
+
+        assert_eq!(
+            lines,
+            vec![
+                "NAME    DESCRIPTION",
+                "bat ✓   cat clone",
+                "日本語  cjk",
+                "📦",
+            ]
+        );
+    }
+
+    #[test]
+    fn format_table_without_headers_only_lays_out_rows() {
+        let rows = vec![
+            vec!["\x1b[36mfd\x1b[0m".to_string(), "find".to_string()],
+            vec!["ripgrep".to_string(), "grep".to_string()],
+        ];
+
+        let lines = format_table(&[], &rows);
+
+        assert_eq!(lines[0], "\x1b[36mfd\x1b[0m       find");
+        assert_eq!(lines[1], "ripgrep  grep");
+    }
+}
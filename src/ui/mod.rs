@@ -1,50 +1,148 @@
 use colored::Colorize;
 use std::io::{self, IsTerminal, Write};
 use std::sync::OnceLock;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 
+mod columns;
 pub mod progress;
 
+pub use columns::{display_width, format_table};
+
 static COLOR_MODE: OnceLock<ColorMode> = OnceLock::new();
-static QUIET_MODE: AtomicBool = AtomicBool::new(false);
-static VERBOSE_MODE: AtomicBool = AtomicBool::new(false);
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Info as u8);
 static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static STDIN_TAKEN: AtomicBool = AtomicBool::new(false);
 
-#[derive(Clone, Copy, PartialEq)]
-enum ColorMode {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
     Auto,
     Always,
     Never,
 }
 
-/// Initialize color mode from settings
+/// Initialize color mode from `--color`, the environment and settings
 /// Should be called once at startup
-pub fn init_colors() {
-    if let Ok(settings) = crate::config::settings::Settings::load() {
-        let mode = match settings.get("color").map(|s| s.as_str()) {
-            Some("always") => ColorMode::Always,
-            Some("never") => ColorMode::Never,
-            _ => ColorMode::Auto, // default
-        };
-        COLOR_MODE.get_or_init(|| mode);
-    } else {
-        COLOR_MODE.get_or_init(|| ColorMode::Auto);
+pub fn init_colors(flag: Option<ColorMode>) {
+    let setting = crate::config::settings::Settings::load()
+        .ok()
+        .and_then(|settings| settings.get("color").cloned());
+    let mode = resolve_color_mode(
+        flag,
+        std::env::var("NO_COLOR").ok().as_deref(),
+        std::env::var("CLICOLOR_FORCE").ok().as_deref(),
+        setting.as_deref(),
+    );
+    set_color_mode(mode);
+}
+
+/// `--color` wins, then `NO_COLOR` (any non-empty value), then `CLICOLOR_FORCE`
+/// (non-empty and not `0`), then the `color` setting; auto otherwise.
+fn resolve_color_mode(
+    flag: Option<ColorMode>,
+    no_color: Option<&str>,
+    clicolor_force: Option<&str>,
+    setting: Option<&str>,
+) -> ColorMode {
+    if let Some(mode) = flag {
+        return mode;
+    }
+    if no_color.is_some_and(|value| !value.is_empty()) {
+        return ColorMode::Never;
+    }
+    if clicolor_force.is_some_and(|value| !value.is_empty() && value != "0") {
+        return ColorMode::Always;
+    }
+    match setting {
+        Some("always") => ColorMode::Always,
+        Some("never") => ColorMode::Never,
+        _ => ColorMode::Auto,
+    }
+}
+
+/// Fix the color mode for the process; forced modes also override the
+/// `colored` crate's own TTY detection for text styled directly.
+fn set_color_mode(mode: ColorMode) {
+    match *COLOR_MODE.get_or_init(|| mode) {
+        ColorMode::Always => colored::control::set_override(true),
+        ColorMode::Never => colored::control::set_override(false),
+        ColorMode::Auto => {}
+    }
+}
+
+/// Output verbosity, ordered from least to most chatty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error = 0,
+    Warn = 1,
+    Info = 2,
+    Debug = 3,
+    Trace = 4,
+}
+
+impl LogLevel {
+    /// Resolve the effective level from CLI flags.
+    /// An explicit `--log-level` wins over `-q`, which wins over `-v` repeats.
+    pub fn from_flags(quiet: bool, verbose_count: u8, explicit: Option<LogLevel>) -> Self {
+        if let Some(level) = explicit {
+            return level;
+        }
+        if quiet {
+            return LogLevel::Warn;
+        }
+        match verbose_count {
+            0 => LogLevel::Info,
+            1 => LogLevel::Debug,
+            _ => LogLevel::Trace,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => LogLevel::Error,
+            1 => LogLevel::Warn,
+            2 => LogLevel::Info,
+            3 => LogLevel::Debug,
+            _ => LogLevel::Trace,
+        }
     }
+
+    /// Whether a message of `severity` is shown at this level.
+    fn allows(self, severity: LogLevel) -> bool {
+        severity <= self
+    }
+}
+
+/// Set the global output level.
+pub fn set_log_level(level: LogLevel) {
+    LOG_LEVEL.store(level as u8, Ordering::Relaxed);
 }
 
-/// Enable or disable quiet mode globally.
+/// Current global output level.
+pub fn log_level() -> LogLevel {
+    LogLevel::from_u8(LOG_LEVEL.load(Ordering::Relaxed))
+}
+
+/// Enable or disable quiet mode globally (warnings and errors only).
 pub fn set_quiet(enabled: bool) {
-    QUIET_MODE.store(enabled, Ordering::Relaxed);
+    if enabled {
+        set_log_level(LogLevel::Warn);
+    } else if log_level() < LogLevel::Info {
+        set_log_level(LogLevel::Info);
+    }
 }
 
 /// Enable or disable verbose mode globally.
 pub fn set_verbose(enabled: bool) {
-    VERBOSE_MODE.store(enabled, Ordering::Relaxed);
+    if enabled {
+        set_log_level(LogLevel::Debug);
+    } else if log_level() > LogLevel::Info {
+        set_log_level(LogLevel::Info);
+    }
 }
 
 /// Check whether verbose mode is enabled.
 pub fn is_verbose() -> bool {
-    VERBOSE_MODE.load(Ordering::Relaxed)
+    enabled(LogLevel::Debug)
 }
 
 /// Mark an interruption request (e.g. Ctrl+C).
@@ -57,21 +155,44 @@ pub fn is_interrupted() -> bool {
     INTERRUPTED.load(Ordering::Relaxed)
 }
 
+/// Mark stdin as consumed by input (`--config -`): prompts decline at once
+/// instead of reading end-of-file as the default answer.
+pub fn mark_stdin_taken() {
+    STDIN_TAKEN.store(true, Ordering::Relaxed);
+}
+
+/// Decline `question` without asking when stdin is taken.
+fn stdin_taken_declines(question: &str) -> bool {
+    if !STDIN_TAKEN.load(Ordering::Relaxed) {
+        return false;
+    }
+    warning(&format!(
+        "Cannot ask \"{}\": stdin carries the config (--config -); pass --yes",
+        question
+    ));
+    true
+}
+
+fn enabled(severity: LogLevel) -> bool {
+    log_level().allows(severity)
+}
+
 fn is_quiet() -> bool {
-    QUIET_MODE.load(Ordering::Relaxed)
+    !enabled(LogLevel::Info)
 }
 
 /// Check if colors should be applied based on current mode
 fn should_colorize() -> bool {
     let mode = COLOR_MODE.get().copied().unwrap_or(ColorMode::Auto);
+    mode_colorizes(mode, || io::stdout().is_terminal())
+}
 
+/// Forced modes decide on their own; auto colors only a terminal.
+fn mode_colorizes(mode: ColorMode, is_terminal: impl FnOnce() -> bool) -> bool {
     match mode {
         ColorMode::Always => true,
         ColorMode::Never => false,
-        ColorMode::Auto => {
-            // Check if we're in a TTY
-            io::stdout().is_terminal()
-        }
+        ColorMode::Auto => is_terminal(),
     }
 }
 
@@ -95,7 +216,11 @@ pub fn success(msg: &str) {
     if is_quiet() {
         return;
     }
-    println!("{}", color_str(msg, |s| s.green()));
+    println!("{}", success_text(msg));
+}
+
+fn success_text(msg: &str) -> String {
+    color_str(msg, |s| s.green())
 }
 
 pub fn info(msg: &str) {
@@ -107,18 +232,32 @@ pub fn info(msg: &str) {
 
 /// Print a message only in verbose mode.
 pub fn verbose(msg: &str) {
-    if is_quiet() || !is_verbose() {
+    if !enabled(LogLevel::Debug) {
         return;
     }
     println!("{}", color_str(msg, |s| s.dimmed()));
 }
 
+/// Print a message only at trace level (`-vv` or `--log-level trace`).
+pub fn trace(msg: &str) {
+    if !enabled(LogLevel::Trace) {
+        return;
+    }
+    eprintln!("{}", color_str(msg, |s| s.bright_black()));
+}
+
 pub fn warning(msg: &str) {
+    if !enabled(LogLevel::Warn) {
+        return;
+    }
     let symbol = color_str("⚠", |s| s.yellow().bold());
     eprintln!("{} {}", symbol, msg);
 }
 
 pub fn error(msg: &str) {
+    if !enabled(LogLevel::Error) {
+        return;
+    }
     let symbol = color_str("✗", |s| s.red().bold());
     eprintln!("{} {}", symbol, msg);
 }
@@ -153,12 +292,31 @@ pub fn indent(msg: &str, level: usize) {
     println!("{}{}", spaces, msg);
 }
 
+/// Print rows as aligned columns with a dimmed header line.
+///
+/// Pass empty `headers` to print rows only.
+pub fn table(headers: &[&str], rows: &[Vec<String>]) {
+    if is_quiet() {
+        return;
+    }
+    let lines = format_table(headers, rows);
+    let mut lines = lines.iter();
+    if !headers.is_empty()
+        && let Some(header) = lines.next()
+    {
+        println!("{}", color_str(header, |s| s.dimmed()));
+    }
+    for line in lines {
+        println!("{}", line);
+    }
+}
+
 pub fn prompt_yes_no(question: &str) -> bool {
     prompt_yes_no_default(question, true)
 }
 
 pub fn prompt_yes_no_default(question: &str, default: bool) -> bool {
-    if is_interrupted() {
+    if is_interrupted() || stdin_taken_declines(question) {
         return false;
     }
 
@@ -197,3 +355,152 @@ pub fn prompt_yes_no_default(question: &str, default: bool) -> bool {
         }
     }
 }
+
+/// Ask the user to type `expected` verbatim; anything else declines.
+pub fn prompt_typed_confirmation(question: &str, expected: &str) -> bool {
+    if is_interrupted() || stdin_taken_declines(question) {
+        return false;
+    }
+
+    let symbol = color_str("!", |s| s.red().bold());
+    print!("{} {} Type '{}' to confirm: ", symbol, question, expected);
+
+    if let Err(e) = io::stdout().flush() {
+        eprintln!("\nWarning: Failed to flush terminal: {}", e);
+        return false;
+    }
+
+    let mut input = String::new();
+    match io::stdin().read_line(&mut input) {
+        Ok(_) => !is_interrupted() && input.trim() == expected,
+        Err(e) => {
+            if e.kind() != io::ErrorKind::Interrupted && !is_interrupted() {
+                eprintln!("\nWarning: Failed to read input: {}", e);
+            }
+            false
+        }
+    }
+}
+
+/// Whether prompts can be answered (stdin is a terminal not taken by input).
+pub fn can_prompt() -> bool {
+    !STDIN_TAKEN.load(Ordering::Relaxed) && io::stdin().is_terminal()
+}
+
+/// Whether someone is watching and can answer: prompts work and stdout is a terminal.
+pub fn is_attended() -> bool {
+    can_prompt() && io::stdout().is_terminal()
+}
+
+/// Ask the user to pick one of `choices` by number.
+///
+/// Returns `None` on empty input, an out-of-range answer, or a read failure.
+pub fn prompt_choice(question: &str, choices: &[String]) -> Option<usize> {
+    if is_interrupted() || stdin_taken_declines(question) {
+        return None;
+    }
+
+    let symbol = color_str("?", |s| s.yellow().bold());
+    println!("{} {}", symbol, question);
+    for (i, choice) in choices.iter().enumerate() {
+        println!("  {}) {}", i + 1, choice);
+    }
+    print!("  Choice [1-{}, empty to skip]: ", choices.len());
+
+    if let Err(e) = io::stdout().flush() {
+        eprintln!("\nWarning: Failed to flush terminal: {}", e);
+        return None;
+    }
+
+    let mut input = String::new();
+    match io::stdin().read_line(&mut input) {
+        Ok(_) if !is_interrupted() => parse_choice(&input, choices.len()),
+        Ok(_) => None,
+        Err(e) => {
+            if e.kind() != io::ErrorKind::Interrupted && !is_interrupted() {
+                eprintln!("\nWarning: Failed to read input: {}", e);
+            }
+            None
+        }
+    }
+}
+
+fn parse_choice(input: &str, count: usize) -> Option<usize> {
+    input
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|n| (1..=count).contains(n))
+        .map(|n| n - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ColorMode, LogLevel, mode_colorizes, parse_choice, resolve_color_mode};
+
+    #[test]
+    fn color_mode_prefers_flag_then_env_then_setting() {
+        use ColorMode::{Always, Auto, Never};
+
+        assert_eq!(resolve_color_mode(None, None, None, None), Auto);
+        assert_eq!(resolve_color_mode(None, None, None, Some("never")), Never);
+        assert_eq!(
+            resolve_color_mode(None, None, Some("1"), Some("never")),
+            Always
+        );
+        assert_eq!(resolve_color_mode(None, None, Some("0"), None), Auto);
+        assert_eq!(resolve_color_mode(None, Some("1"), Some("1"), None), Never);
+        assert_eq!(resolve_color_mode(None, Some(""), None, None), Auto);
+        assert_eq!(
+            resolve_color_mode(Some(Always), Some("1"), None, None),
+            Always
+        );
+        assert_eq!(
+            resolve_color_mode(Some(Never), None, Some("1"), None),
+            Never
+        );
+    }
+
+    #[test]
+    fn forced_color_modes_ignore_the_terminal() {
+        assert!(!mode_colorizes(ColorMode::Never, || true));
+        assert!(mode_colorizes(ColorMode::Always, || false));
+        assert!(mode_colorizes(ColorMode::Auto, || true));
+        assert!(!mode_colorizes(ColorMode::Auto, || false));
+    }
+
+    #[test]
+    fn parse_choice_accepts_only_listed_numbers() {
+        assert_eq!(parse_choice("2\n", 3), Some(1));
+        assert_eq!(parse_choice(" 1 ", 3), Some(0));
+        assert_eq!(parse_choice("\n", 3), None);
+        assert_eq!(parse_choice("0", 3), None);
+        assert_eq!(parse_choice("4", 3), None);
+        assert_eq!(parse_choice("aur", 3), None);
+    }
+
+    #[test]
+    fn log_level_gates_messages_by_severity() {
+        assert!(LogLevel::Error.allows(LogLevel::Error));
+        assert!(!LogLevel::Error.allows(LogLevel::Warn));
+        assert!(LogLevel::Warn.allows(LogLevel::Warn));
+        assert!(!LogLevel::Warn.allows(LogLevel::Info));
+        assert!(LogLevel::Info.allows(LogLevel::Info));
+        assert!(!LogLevel::Info.allows(LogLevel::Debug));
+        assert!(LogLevel::Debug.allows(LogLevel::Debug));
+        assert!(!LogLevel::Debug.allows(LogLevel::Trace));
+        assert!(LogLevel::Trace.allows(LogLevel::Trace));
+    }
+
+    #[test]
+    fn log_level_from_flags_prefers_explicit_then_quiet() {
+        assert_eq!(LogLevel::from_flags(false, 0, None), LogLevel::Info);
+        assert_eq!(LogLevel::from_flags(false, 1, None), LogLevel::Debug);
+        assert_eq!(LogLevel::from_flags(false, 3, None), LogLevel::Trace);
+        assert_eq!(LogLevel::from_flags(true, 2, None), LogLevel::Warn);
+        assert_eq!(
+            LogLevel::from_flags(true, 2, Some(LogLevel::Error)),
+            LogLevel::Error
+        );
+    }
+}
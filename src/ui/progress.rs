@@ -47,17 +47,10 @@ impl ProgressBar {
 
     /// Draw the progress bar
     fn draw(&self) {
-        let percent = if self.total > 0 {
-            (self.current * 100) / self.total
-        } else {
-            100
-        };
-
-        let filled = if self.total > 0 {
-            (self.current * self.width) / self.total
-        } else {
-            self.width
-        };
+        let percent = (self.current * 100).checked_div(self.total).unwrap_or(100);
+        let filled = (self.current * self.width)
+            .checked_div(self.total)
+            .unwrap_or(self.width);
 
         let bar = "█".repeat(filled);
         let empty = "░".repeat(self.width.saturating_sub(filled));
@@ -0,0 +1,58 @@
+//! "Did you mean" suggestions for mistyped names
+//!
+//! Candidates are ranked by Levenshtein distance, compared case-insensitively.
+
+/// Levenshtein distance between two strings, counted in chars.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Candidates within `max_distance` of `query`, closest first (ties by name),
+/// at most `limit` of them. Exact matches are not suggestions and are skipped.
+pub fn closest_matches<'a>(
+    query: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+    max_distance: usize,
+    limit: usize,
+) -> Vec<String> {
+    let query = query.to_lowercase();
+    let mut ranked: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let distance = edit_distance(&query, &candidate.to_lowercase());
+            (distance > 0 && distance <= max_distance).then_some((distance, candidate))
+        })
+        .collect();
+    ranked.sort_unstable();
+    ranked.dedup_by(|a, b| a.1 == b.1);
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(_, candidate)| candidate.to_string())
+        .collect()
+}
+
+/// Render suggestions as ` (did you mean 'a' or 'b'?)`, or nothing.
+pub fn did_you_mean(suggestions: &[String]) -> String {
+    let quoted: Vec<String> = suggestions.iter().map(|s| format!("'{}'", s)).collect();
+    match quoted.as_slice() {
+        [] => String::new(),
+        [only] => format!(" (did you mean {}?)", only),
+        [rest @ .., last] => format!(" (did you mean {} or {}?)", rest.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,52 @@
+use super::*;
+
+#[test]
+fn edit_distance_counts_single_edits() {
+    assert_eq!(edit_distance("neovim", "neovim"), 0);
+    assert_eq!(edit_distance("neovm", "neovim"), 1);
+    assert_eq!(edit_distance("nevoim", "neovim"), 2);
+    assert_eq!(edit_distance("", "bat"), 3);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+}
+
+#[test]
+fn closest_matches_ranks_by_distance_then_name() {
+    let candidates = ["neovim", "vim", "nvim", "neomutt", "bat"];
+    assert_eq!(closest_matches("neovm", candidates, 2, 3), vec!["neovim"]);
+    assert_eq!(
+        closest_matches("vin", candidates, 2, 3),
+        vec!["vim", "nvim"]
+    );
+}
+
+#[test]
+fn closest_matches_caps_distance_and_count() {
+    let candidates = ["aa", "ab", "ac", "ad", "zzzz"];
+    assert_eq!(
+        closest_matches("a", candidates, 2, 3),
+        vec!["aa", "ab", "ac"]
+    );
+    assert!(closest_matches("qqqq", candidates, 2, 3).is_empty());
+}
+
+#[test]
+fn closest_matches_ignores_case_and_duplicates() {
+    let candidates = ["Firefox", "firefox", "Firefox"];
+    assert_eq!(
+        closest_matches("FIREFX", candidates, 2, 3),
+        vec!["Firefox", "firefox"]
+    );
+}
+
+#[test]
+fn did_you_mean_lists_suggestions() {
+    assert_eq!(did_you_mean(&[]), "");
+    assert_eq!(
+        did_you_mean(&["neovim".to_string()]),
+        " (did you mean 'neovim'?)"
+    );
+    assert_eq!(
+        did_you_mean(&["a".to_string(), "b".to_string(), "c".to_string()]),
+        " (did you mean 'a', 'b' or 'c'?)"
+    );
+}
@@ -1,6 +1,66 @@
-use crate::error::Result;
+use crate::error::{DeclarchError, Result};
 use chrono::Utc;
 use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Destination for machine reports: the `--output` file when set, else stdout.
+///
+/// The file is opened (and truncated) on first write, so one writer can carry
+/// a whole line-delimited stream.
+#[derive(Debug)]
+pub struct ReportWriter {
+    path: Option<PathBuf>,
+    file: Option<File>,
+}
+
+impl ReportWriter {
+    pub fn new(output: Option<&Path>) -> Self {
+        Self {
+            path: output.map(Path::to_path_buf),
+            file: None,
+        }
+    }
+
+    /// Write one machine report (or stream line).
+    pub fn write_line(&mut self, out: &str) -> Result<()> {
+        let Some(path) = self.path.as_ref() else {
+            let mut stdout = std::io::stdout().lock();
+            writeln!(stdout, "{}", out)?;
+            stdout.flush()?;
+            return Ok(());
+        };
+        let io_error = |source| DeclarchError::IoError {
+            path: path.clone(),
+            source,
+        };
+
+        if self.file.is_none() {
+            if let Some(parent) = path.parent()
+                && !parent.as_os_str().is_empty()
+            {
+                fs::create_dir_all(parent).map_err(io_error)?;
+            }
+            self.file = Some(File::create(path).map_err(io_error)?);
+        }
+
+        if let Some(file) = self.file.as_mut() {
+            writeln!(file, "{}", out)
+                .and_then(|_| file.flush())
+                .map_err(io_error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write one machine report to `output` (`--output`), or stdout when unset.
+///
+/// Every machine-readable payload should go through here so `--output` applies.
+pub fn write_report(out: &str, output: Option<&Path>) -> Result<()> {
+    ReportWriter::new(output).write_line(out)
+}
 
 #[derive(Debug, Serialize)]
 pub struct MachineEnvelope<T>
@@ -27,6 +87,7 @@ pub fn emit_v1<T>(
     warnings: Vec<String>,
     errors: Vec<String>,
     format: &str,
+    output: Option<&Path>,
 ) -> Result<()>
 where
     T: Serialize,
@@ -43,20 +104,78 @@ where
         },
     };
 
-    match format {
-        "json" => {
-            let out = serde_json::to_string_pretty(&envelope)?;
-            println!("{}", out);
-        }
-        "yaml" => {
-            let out = serde_yml::to_string(&envelope)?;
-            println!("{}", out);
-        }
-        _ => {}
+    if let Some(out) = render_envelope(&envelope, format)? {
+        write_report(&out, output)?;
     }
 
     Ok(())
 }
 
+/// One line of a JSON Lines (`--format jsonl`) stream.
+///
+/// `type` tags the record (e.g. `result`, `summary`); `data` fields are inlined.
+#[derive(Debug, Serialize)]
+struct StreamRecord<'a, T>
+where
+    T: Serialize,
+{
+    version: &'a str,
+    command: &'a str,
+    #[serde(rename = "type")]
+    record_type: &'a str,
+    #[serde(flatten)]
+    data: &'a T,
+}
+
+/// Write one line-delimited v1 record to `out` immediately.
+///
+/// Consumers can process records as they arrive; the producer is expected to
+/// finish with a `summary` record.
+pub fn emit_v1_stream<T>(
+    out: &mut ReportWriter,
+    command: &str,
+    record_type: &str,
+    data: &T,
+) -> Result<()>
+where
+    T: Serialize,
+{
+    out.write_line(&render_stream_record(command, record_type, data)?)
+}
+
+fn render_stream_record<T>(command: &str, record_type: &str, data: &T) -> Result<String>
+where
+    T: Serialize,
+{
+    Ok(serde_json::to_string(&StreamRecord {
+        version: "v1",
+        command,
+        record_type,
+        data,
+    })?)
+}
+
+fn render_envelope<T>(envelope: &MachineEnvelope<T>, format: &str) -> Result<Option<String>>
+where
+    T: Serialize,
+{
+    let out = match format {
+        "json" => serde_json::to_string_pretty(envelope)?,
+        "yaml" => serde_yml::to_string(envelope)?,
+        "toml" => toml::to_string_pretty(envelope)?,
+        _ => return Ok(None),
+    };
+    Ok(Some(out))
+}
+
+/// Render `value` as TOML under `key`; TOML documents can't have a top-level array.
+pub fn to_toml_keyed<T>(key: &str, value: &T) -> Result<String>
+where
+    T: Serialize + ?Sized,
+{
+    let wrapped = BTreeMap::from([(key, value)]);
+    Ok(toml::to_string_pretty(&wrapped)?)
+}
+
 #[cfg(test)]
 mod tests;
@@ -1,4 +1,4 @@
-use super::MachineEnvelope;
+use super::{MachineEnvelope, ReportWriter, render_envelope, render_stream_record, to_toml_keyed};
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
@@ -6,6 +6,18 @@ struct Payload {
     value: u32,
 }
 
+#[derive(Debug, Serialize)]
+struct ResultsPayload {
+    total: usize,
+    results: Vec<ResultRow>,
+}
+
+#[derive(Debug, Serialize)]
+struct ResultRow {
+    name: String,
+    installed: bool,
+}
+
 #[test]
 fn envelope_ok_reflects_errors_empty() {
     let env = MachineEnvelope {
@@ -46,3 +58,100 @@ fn envelope_serializes_core_fields_for_contract() {
     assert!(json.get("errors").is_some());
     assert!(json.get("meta").is_some());
 }
+
+#[test]
+fn toml_envelope_round_trips_to_same_values() {
+    let env = MachineEnvelope {
+        version: "v1".to_string(),
+        command: "search".to_string(),
+        ok: true,
+        data: ResultsPayload {
+            total: 2,
+            results: vec![
+                ResultRow {
+                    name: "bat".to_string(),
+                    installed: true,
+                },
+                ResultRow {
+                    name: "ripgrep".to_string(),
+                    installed: false,
+                },
+            ],
+        },
+        warnings: vec!["warn".to_string()],
+        errors: vec![],
+        meta: super::MachineMeta {
+            generated_at: "2026-02-18T00:00:00Z".to_string(),
+        },
+    };
+
+    let rendered = render_envelope(&env, "toml")
+        .expect("render")
+        .expect("toml is a supported format");
+    let parsed: toml::Table = toml::from_str(&rendered).expect("emitted TOML parses");
+
+    assert_eq!(
+        serde_json::to_value(&parsed).expect("to json"),
+        serde_json::to_value(&env).expect("to json")
+    );
+}
+
+#[test]
+fn toml_keyed_wraps_top_level_lists() {
+    let rows = vec![ResultRow {
+        name: "bat".to_string(),
+        installed: true,
+    }];
+
+    let rendered = to_toml_keyed("packages", &rows).expect("render");
+    let parsed: toml::Table = toml::from_str(&rendered).expect("emitted TOML parses");
+
+    assert_eq!(parsed["packages"][0]["name"].as_str(), Some("bat"));
+}
+
+#[test]
+fn stream_records_are_single_tagged_lines() {
+    let row = ResultRow {
+        name: "bat".to_string(),
+        installed: true,
+    };
+
+    let line = render_stream_record("search", "result", &row).expect("render");
+
+    assert!(!line.contains('\n'));
+    let parsed: serde_json::Value = serde_json::from_str(&line).expect("valid json");
+    assert_eq!(parsed["version"], "v1");
+    assert_eq!(parsed["command"], "search");
+    assert_eq!(parsed["type"], "result");
+    assert_eq!(parsed["name"], "bat");
+    assert_eq!(parsed["installed"], true);
+}
+
+#[test]
+fn report_file_creates_parent_dirs_and_keeps_stream_lines() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("reports/nested/search.jsonl");
+
+    let mut report = ReportWriter::new(Some(&path));
+    report
+        .write_line(r#"{"type":"result"}"#)
+        .expect("first line");
+    report
+        .write_line(r#"{"type":"summary"}"#)
+        .expect("second line");
+
+    let content = std::fs::read_to_string(&path).expect("read report");
+    assert_eq!(content, "{\"type\":\"result\"}\n{\"type\":\"summary\"}\n");
+}
+
+#[test]
+fn report_file_truncates_previous_run() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("report.json");
+    std::fs::write(&path, "previous report that is longer").expect("write");
+
+    ReportWriter::new(Some(&path))
+        .write_line("{}")
+        .expect("write report");
+    assert_eq!(std::fs::read_to_string(&path).expect("read"), "{}\n");
+}
@@ -1,8 +1,10 @@
+pub mod fuzzy;
 pub mod machine_output;
 pub mod paths;
 pub mod platform;
 pub mod regex_cache;
 pub mod remote;
 pub mod sanitize;
+pub mod size;
 pub mod templates;
 pub mod update_check;
@@ -3,8 +3,46 @@ use crate::constants::{
     PROJECT_QUALIFIER, STATE_FILE_NAME,
 };
 use crate::error::{DeclarchError, Result};
+use crate::project_identity;
 use directories::{ProjectDirs, UserDirs};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+static STATE_FILE_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+static CONFIG_FILE_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `path` as the state file for this process (`--state-file`).
+pub fn set_state_file_override(path: PathBuf) {
+    let _ = STATE_FILE_OVERRIDE.set(path);
+}
+
+/// Use `path` as the root config for this process (`--config`).
+///
+/// Fails when the file does not exist. The config directory (modules,
+/// hosts, backends) becomes the file's parent directory.
+pub fn set_config_file_override(path: &Path) -> Result<()> {
+    let path = resolve_config_override(path)?;
+    let _ = CONFIG_FILE_OVERRIDE.set(path);
+    Ok(())
+}
+
+/// Use `path` as the root config without checking that it exists.
+///
+/// For `--config -`, where the root is a placeholder served from stdin.
+pub fn set_config_file_placeholder(path: PathBuf) {
+    let _ = CONFIG_FILE_OVERRIDE.set(path);
+}
+
+fn resolve_config_override(path: &Path) -> Result<PathBuf> {
+    let expanded = expand_home(path)?;
+    if !expanded.is_file() {
+        return Err(DeclarchError::PathError(format!(
+            "Config file not found: {}",
+            expanded.display()
+        )));
+    }
+    Ok(std::path::absolute(&expanded)?)
+}
 
 pub fn expand_home(path: &Path) -> Result<PathBuf> {
     let path_str = path.to_string_lossy();
@@ -30,7 +68,30 @@ pub fn expand_home(path: &Path) -> Result<PathBuf> {
     Ok(home.join(stripped))
 }
 
+/// Whether an import path is a glob pattern (`modules/*.kdl`).
+pub fn is_glob(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
+/// Files matching a glob pattern, sorted by path. Directories are skipped.
+pub fn glob_files(pattern: &Path) -> Result<Vec<PathBuf>> {
+    let pattern_str = pattern.to_string_lossy();
+    let matches = glob::glob(&pattern_str).map_err(|e| {
+        DeclarchError::ConfigError(format!("Invalid import pattern '{}': {}", pattern_str, e))
+    })?;
+
+    let mut files: Vec<PathBuf> = matches
+        .filter_map(|entry| entry.ok())
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
 pub fn config_dir() -> Result<PathBuf> {
+    if let Some(dir) = CONFIG_FILE_OVERRIDE.get().and_then(|path| path.parent()) {
+        return Ok(dir.to_path_buf());
+    }
     let proj = project_dirs()?;
     Ok(proj.config_dir().to_path_buf())
 }
@@ -43,11 +104,43 @@ pub fn state_dir() -> Result<PathBuf> {
     Ok(state_dir.to_path_buf())
 }
 
+/// Cache directory (e.g. `~/.cache/declarch`); safe to delete at any time.
+pub fn cache_dir() -> Result<PathBuf> {
+    Ok(project_dirs()?.cache_dir().to_path_buf())
+}
+
+/// State file location: `--state-file`, then `DECLARCH_STATE_FILE`, then the default.
 pub fn state_file() -> Result<PathBuf> {
+    resolve_state_file(
+        STATE_FILE_OVERRIDE.get().map(PathBuf::as_path),
+        project_identity::env_get("STATE_FILE"),
+    )
+}
+
+fn resolve_state_file(flag: Option<&Path>, env: Option<String>) -> Result<PathBuf> {
+    if let Some(path) = flag {
+        return expand_home(path);
+    }
+    if let Some(path) = env.filter(|value| !value.trim().is_empty()) {
+        return expand_home(Path::new(&path));
+    }
     Ok(state_dir()?.join(STATE_FILE_NAME))
 }
 
+/// Directory holding per-sync state snapshots (`history` next to the state file).
+pub fn state_history_dir() -> Result<PathBuf> {
+    let state_file = state_file()?;
+    let dir = state_file
+        .parent()
+        .ok_or_else(|| DeclarchError::PathError("Could not determine state directory".into()))?;
+    Ok(dir.join("history"))
+}
+
+/// Root config location: `--config`, then the default.
 pub fn config_file() -> Result<PathBuf> {
+    if let Some(path) = CONFIG_FILE_OVERRIDE.get() {
+        return Ok(path.clone());
+    }
     Ok(config_dir()?.join(CONFIG_FILE_NAME))
 }
 
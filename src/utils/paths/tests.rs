@@ -18,3 +18,58 @@ fn state_file_uses_expected_filename() {
         Some(STATE_FILE_NAME)
     );
 }
+
+#[test]
+fn state_file_override_prefers_flag_then_env() {
+    let flag = Path::new("/tmp/flag/state.json");
+    let env = Some("/tmp/env/state.json".to_string());
+
+    assert_eq!(
+        resolve_state_file(Some(flag), env.clone()).unwrap(),
+        PathBuf::from("/tmp/flag/state.json")
+    );
+    assert_eq!(
+        resolve_state_file(None, env).unwrap(),
+        PathBuf::from("/tmp/env/state.json")
+    );
+    assert_eq!(
+        resolve_state_file(None, Some("  ".to_string())).unwrap(),
+        state_dir().unwrap().join(STATE_FILE_NAME)
+    );
+}
+
+#[test]
+fn config_override_requires_an_existing_file() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let missing = dir.path().join("missing.kdl");
+    let err = resolve_config_override(&missing).expect_err("missing config must fail");
+    assert!(err.to_string().contains(&missing.display().to_string()));
+
+    let config = dir.path().join("project.kdl");
+    std::fs::write(&config, "pkg {}\n").expect("write config");
+    let resolved = resolve_config_override(&config).expect("existing config");
+    assert_eq!(resolved, config);
+    assert_eq!(resolved.parent(), Some(dir.path()));
+}
+
+#[test]
+fn glob_files_are_sorted_and_skip_directories() {
+    let dir = tempfile::tempdir().unwrap();
+    for name in ["b.kdl", "a.kdl", "c.txt"] {
+        std::fs::write(dir.path().join(name), "").unwrap();
+    }
+    std::fs::create_dir(dir.path().join("d.kdl")).unwrap();
+
+    let files = glob_files(&dir.path().join("*.kdl")).unwrap();
+    assert_eq!(
+        files,
+        vec![dir.path().join("a.kdl"), dir.path().join("b.kdl")]
+    );
+    assert!(
+        glob_files(&dir.path().join("none/*.kdl"))
+            .unwrap()
+            .is_empty()
+    );
+    assert!(is_glob("modules/*.kdl"));
+    assert!(!is_glob("modules/base.kdl"));
+}
@@ -1,17 +1,109 @@
-#[cfg(any(windows, not(any(unix, windows))))]
-use crate::error::DeclarchError;
-use crate::error::Result;
+use crate::error::{DeclarchError, Result};
+use crate::project_identity;
 use std::process::Command;
 
+/// Suffix of the environment variable overriding the escalation program
+/// (`DECLARCH_SUDO=doas`).
+const ESCALATION_ENV_SUFFIX: &str = "SUDO";
+
+/// Escalation program used when neither the environment nor policy sets one.
+pub const DEFAULT_ESCALATION: &str = "sudo";
+
+/// Program that prefixes elevated commands: `DECLARCH_SUDO`, then `configured`
+/// (`policy { escalation "..." }`), then `sudo`.
+pub fn escalation_program(configured: Option<&str>) -> String {
+    let env = project_identity::env_get(ESCALATION_ENV_SUFFIX);
+    resolve_escalation(env.as_deref(), configured)
+}
+
+fn resolve_escalation(env: Option<&str>, configured: Option<&str>) -> String {
+    [env, configured]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|program| !program.is_empty())
+        .unwrap_or(DEFAULT_ESCALATION)
+        .to_string()
+}
+
+/// Fail with a hint when the escalation program is not on `PATH`.
+pub fn require_escalation(program: &str) -> Result<()> {
+    if which::which(program).is_ok() {
+        return Ok(());
+    }
+    Err(DeclarchError::PackageManagerError(format!(
+        "Escalation command '{}' not found. Install it, choose another with \
+         policy {{ escalation \"doas\" }} or {}=..., or run with --no-sudo.",
+        program,
+        project_identity::env_key(ESCALATION_ENV_SUFFIX)
+    )))
+}
+
+/// Whether a process with `pid` exists. Assumes alive where this can't be checked.
+pub fn process_alive(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        let Ok(pid) = libc::pid_t::try_from(pid) else {
+            return false;
+        };
+        // SAFETY: signal 0 only performs existence and permission checks.
+        if unsafe { libc::kill(pid, 0) } == 0 {
+            return true;
+        }
+        // EPERM: the process exists but belongs to another user
+        std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = pid;
+        true
+    }
+}
+
+/// Whether the process runs with an effective uid of 0.
+pub fn is_root() -> bool {
+    #[cfg(unix)]
+    {
+        // SAFETY: geteuid has no preconditions and cannot fail.
+        unsafe { libc::geteuid() == 0 }
+    }
+
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
 /// Build a shell command in a platform-aware way.
 ///
-/// - Unix: `sh -c <command>` or `sudo sh -c <command>`
+/// - Unix: `sh -c <command>` or `<escalation> sh -c <command>`
 /// - Windows: `cmd /C <command>` (elevated shell not yet supported)
 pub fn build_shell_command(command: &str, elevated: bool) -> Result<Command> {
+    build_shell_command_as(
+        command,
+        elevated.then(|| escalation_program(None)).as_deref(),
+    )
+}
+
+/// The command line [`build_shell_command_as`] runs, quoted for display.
+pub fn shell_command_line(command: &str, escalation: Option<&str>) -> String {
+    if cfg!(windows) {
+        return format!("cmd /C {}", command);
+    }
+    let quoted = crate::utils::sanitize::shell_escape(command);
+    match escalation {
+        Some(escalation) => format!("{} sh -c {}", escalation, quoted),
+        None => format!("sh -c {}", quoted),
+    }
+}
+
+/// Like [`build_shell_command`], elevating through `escalation` when given.
+pub fn build_shell_command_as(command: &str, escalation: Option<&str>) -> Result<Command> {
     #[cfg(unix)]
     {
-        let cmd = if elevated {
-            let mut c = Command::new("sudo");
+        let cmd = if let Some(escalation) = escalation {
+            let mut c = Command::new(escalation);
             c.arg("sh").arg("-c").arg(command);
             c
         } else {
@@ -25,7 +117,7 @@ pub fn build_shell_command(command: &str, elevated: bool) -> Result<Command> {
 
     #[cfg(windows)]
     {
-        if elevated {
+        if escalation.is_some() {
             return Err(DeclarchError::Other(
                 "Elevated shell execution is not implemented for Windows yet".to_string(),
             ));
@@ -38,7 +130,7 @@ pub fn build_shell_command(command: &str, elevated: bool) -> Result<Command> {
 
     #[cfg(not(any(unix, windows)))]
     {
-        if elevated {
+        if escalation.is_some() {
             return Err(DeclarchError::Other(
                 "Elevated shell execution is not implemented on this platform".to_string(),
             ));
@@ -52,13 +144,17 @@ pub fn build_shell_command(command: &str, elevated: bool) -> Result<Command> {
 
 /// Build a direct program invocation in a platform-aware way.
 ///
-/// - Unix: `program args...` or `sudo program args...`
+/// - Unix: `program args...` or `<escalation> program args...`
 /// - Windows: `program args...` (elevated direct execution not yet supported)
-pub fn build_program_command(program: &str, args: &[String], elevated: bool) -> Result<Command> {
+pub fn build_program_command(
+    program: &str,
+    args: &[String],
+    escalation: Option<&str>,
+) -> Result<Command> {
     #[cfg(unix)]
     {
-        let cmd = if elevated {
-            let mut c = Command::new("sudo");
+        let cmd = if let Some(escalation) = escalation {
+            let mut c = Command::new(escalation);
             c.arg(program);
             c.args(args);
             c
@@ -73,7 +169,7 @@ pub fn build_program_command(program: &str, args: &[String], elevated: bool) ->
 
     #[cfg(windows)]
     {
-        if elevated {
+        if escalation.is_some() {
             return Err(DeclarchError::Other(
                 "Elevated direct execution is not implemented for Windows yet".to_string(),
             ));
@@ -86,7 +182,7 @@ pub fn build_program_command(program: &str, args: &[String], elevated: bool) ->
 
     #[cfg(not(any(unix, windows)))]
     {
-        if elevated {
+        if escalation.is_some() {
             return Err(DeclarchError::Other(
                 "Elevated direct execution is not implemented on this platform".to_string(),
             ));
@@ -113,6 +209,16 @@ pub fn current_os_tag() -> &'static str {
     }
 }
 
+/// `ID` from `/etc/os-release` (e.g. "arch", "ubuntu"), when available.
+pub fn os_release_id() -> Option<String> {
+    let content = std::fs::read_to_string("/etc/os-release").ok()?;
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("ID="))
+        .map(|id| id.trim().trim_matches('"').to_string())
+        .filter(|id| !id.is_empty())
+}
+
 /// Check whether a backend is compatible with the current operating system.
 /// If supported_os is not set, backend is treated as cross-platform.
 pub fn backend_supports_current_os(backend: &crate::backends::config::BackendConfig) -> bool {
@@ -2,7 +2,7 @@ use super::*;
 
 #[test]
 fn build_program_non_elevated_works() {
-    let cmd = build_program_command("echo", &["ok".to_string()], false).unwrap();
+    let cmd = build_program_command("echo", &["ok".to_string()], None).unwrap();
     let debug = format!("{:?}", cmd);
     assert!(debug.contains("echo"));
 }
@@ -15,6 +15,22 @@ fn build_shell_non_elevated_works() {
     assert!(debug.contains("\"sh\""));
 }
 
+#[test]
+fn escalation_prefers_env_then_policy_then_sudo() {
+    assert_eq!(resolve_escalation(Some("run0"), Some("doas")), "run0");
+    assert_eq!(resolve_escalation(Some("  "), Some("doas")), "doas");
+    assert_eq!(resolve_escalation(None, None), DEFAULT_ESCALATION);
+}
+
+#[cfg(unix)]
+#[test]
+fn build_shell_elevated_uses_given_escalation() {
+    let cmd = build_shell_command_as("echo ok", Some("doas")).unwrap();
+    assert_eq!(cmd.get_program(), "doas");
+    let args: Vec<_> = cmd.get_args().collect();
+    assert_eq!(args, ["sh", "-c", "echo ok"]);
+}
+
 #[test]
 fn current_os_tag_is_not_empty() {
     assert!(!current_os_tag().is_empty());
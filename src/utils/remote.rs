@@ -1,26 +1,54 @@
 mod security;
 mod url_builders;
 
+use crate::constants::urls::{BackendRegistry, RemoteUrlBuilder};
 use crate::constants::{CONFIG_EXTENSION, PROJECT_NAME};
 use crate::error::{DeclarchError, Result};
 use crate::project_identity;
 use crate::ui as output;
 use reqwest::blocking::Client;
-use security::validate_url;
 #[cfg(test)]
 use security::{first_private_ip, is_private_address};
+use security::{private_registries_allowed, validate_url};
 #[cfg(test)]
 use std::net::IpAddr;
 use std::sync::LazyLock;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 use std::time::Duration;
-use url_builders::{build_backend_urls, build_urls};
+use url_builders::build_urls;
 
 static DEFAULT_REGISTRY: LazyLock<String> = LazyLock::new(project_identity::registry_raw_base_url);
-static BACKENDS_REGISTRY: LazyLock<String> =
-    LazyLock::new(|| format!("{}/backends", *DEFAULT_REGISTRY));
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 
+static FROZEN: AtomicBool = AtomicBool::new(false);
+
+/// Globally forbid network access (`--frozen`).
+pub fn set_frozen(enabled: bool) {
+    FROZEN.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether network access is forbidden: `--frozen` or `DECLARCH_OFFLINE=1`.
+pub fn is_frozen() -> bool {
+    FROZEN.load(Ordering::Relaxed) || offline_env_enabled(project_identity::env_get("OFFLINE"))
+}
+
+fn offline_env_enabled(value: Option<String>) -> bool {
+    value.is_some_and(|v| v.trim() == "1")
+}
+
+/// Fail before `operation` touches the network when running frozen.
+pub fn ensure_online(operation: &str) -> Result<()> {
+    if !is_frozen() {
+        return Ok(());
+    }
+    Err(DeclarchError::Other(format!(
+        "{} needs network access, which is disabled by --frozen ({}=1)",
+        operation,
+        project_identity::env_key("OFFLINE")
+    )))
+}
+
 /// Fetch module content from remote repository
 ///
 /// Supports multiple sources:
@@ -50,6 +78,8 @@ const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 ///    declarch init https://example.com/config.kdl
 ///    ```
 pub fn fetch_module_content(target_path: &str) -> Result<String> {
+    ensure_online(&format!("Fetching '{}'", target_path))?;
+
     let client = Client::builder()
         .timeout(REQUEST_TIMEOUT)
         .build()
@@ -60,7 +90,7 @@ pub fn fetch_module_content(target_path: &str) -> Result<String> {
 
     let mut failures = Vec::new();
     for url in urls {
-        match fetch_url(&client, &url) {
+        match fetch_url(&client, &url, false) {
             Ok(content) => {
                 // Show successful fetch
                 output::info(&format!("fetch: {}", url));
@@ -87,63 +117,121 @@ pub fn fetch_module_content(target_path: &str) -> Result<String> {
     )))
 }
 
-/// Fetch backend configuration from remote repository
+/// Backend definition fetched by [`fetch_backend_content`]
+#[derive(Debug)]
+pub struct FetchedBackend {
+    pub content: String,
+    /// Name of the registry the backend was found in
+    pub registry: String,
+    /// URL the definition was fetched from
+    pub url: String,
+}
+
+/// Backend registries to try, in order: `DECLARCH_REGISTRY`, then the
+/// configured `policy { registries ... }`, then the default registry.
+pub fn backend_registries(configured: &[String]) -> Result<Vec<BackendRegistry>> {
+    let mut specs = registry_env_specs(project_identity::env_get("REGISTRY"));
+    specs.extend(configured.iter().cloned());
+    RemoteUrlBuilder::default().backend_registries(&specs)
+}
+
+fn registry_env_specs(value: Option<String>) -> Vec<String> {
+    value
+        .map(|v| {
+            v.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Fetch backend configuration from the backend registries
 ///
-/// Tries to fetch from declarch-packages/backends/ first,
-/// then falls back to local template generation if not found.
-pub fn fetch_backend_content(backend_name: &str) -> Result<String> {
+/// Each registry from [`backend_registries`] is tried in order until one
+/// has the backend.
+pub fn fetch_backend_content(
+    backend_name: &str,
+    configured_registries: &[String],
+) -> Result<FetchedBackend> {
+    let fetched = fetch_backend_content_silent(backend_name, configured_registries)?;
+    output::info(&format!("fetch backend: {}", fetched.url));
+    Ok(fetched)
+}
+
+/// [`fetch_backend_content`] without reporting the URL, for callers that
+/// fetch many backends at once or print machine output
+pub fn fetch_backend_content_silent(
+    backend_name: &str,
+    configured_registries: &[String],
+) -> Result<FetchedBackend> {
+    ensure_online(&format!("Fetching backend '{}'", backend_name))?;
+
+    let registries = backend_registries(configured_registries)?;
+
     let client = Client::builder()
         .timeout(REQUEST_TIMEOUT)
         .build()
         .map_err(|e| DeclarchError::Other(format!("Failed to create HTTP client: {}", e)))?;
 
-    // Build URLs to try for backend
-    let urls = build_backend_urls(backend_name);
+    // Only registry fetches may opt in to private hosts; modules never do.
+    let allow_private =
+        private_registries_allowed(project_identity::env_get("ALLOW_PRIVATE_REGISTRY"));
+    fetch_from_registries(backend_name, &registries, |url| {
+        fetch_url_with_retry(&client, url, allow_private)
+    })
+}
 
+fn fetch_from_registries<F>(
+    backend_name: &str,
+    registries: &[BackendRegistry],
+    mut fetch: F,
+) -> Result<FetchedBackend>
+where
+    F: FnMut(&str) -> Result<String>,
+{
     let mut failures = Vec::new();
-    for url in urls {
-        let mut last_error: Option<DeclarchError> = None;
-
-        // Retry transient backend fetch failures once. This reduces flakiness
-        // when GitHub raw endpoints or DNS have short hiccups.
-        for attempt in 1..=2 {
-            match fetch_url(&client, &url) {
-                Ok(content) => {
-                    output::info(&format!("fetch backend: {}", url));
-                    return Ok(content);
-                }
-                Err(e) => {
-                    let should_retry = attempt == 1 && is_retryable_fetch_error(&e);
-                    last_error = Some(e);
-                    if should_retry {
-                        thread::sleep(Duration::from_millis(250));
-                        continue;
-                    }
-                    break;
-                }
+    for registry in registries {
+        let url = registry.backend_url(backend_name);
+        match fetch(&url) {
+            Ok(content) => {
+                return Ok(FetchedBackend {
+                    content,
+                    registry: registry.name.clone(),
+                    url,
+                });
             }
-        }
-
-        if let Some(err) = last_error {
-            failures.push(format_fetch_failure(&url, &err.to_string()));
+            Err(e) => failures.push(format_fetch_failure(&url, &e.to_string())),
         }
     }
 
     Err(DeclarchError::TargetNotFound(format!(
-        "Backend '{}' not found in registry.\n{}",
+        "Backend '{}' not found in any registry.\n{}",
         backend_name,
         format_failure_summary(&failures)
     )))
 }
 
+fn fetch_url_with_retry(client: &Client, url: &str, allow_private: bool) -> Result<String> {
+    // Retry transient backend fetch failures once. This reduces flakiness
+    // when GitHub raw endpoints or DNS have short hiccups.
+    match fetch_url(client, url, allow_private) {
+        Err(e) if is_retryable_fetch_error(&e) => {
+            thread::sleep(Duration::from_millis(250));
+            fetch_url(client, url, allow_private)
+        }
+        result => result,
+    }
+}
+
 fn is_retryable_fetch_error(err: &DeclarchError) -> bool {
     let msg = err.to_string().to_ascii_lowercase();
     !(msg.contains("http 404") || msg.contains("not found"))
 }
 
-fn fetch_url(client: &Client, url: &str) -> Result<String> {
+fn fetch_url(client: &Client, url: &str, allow_private: bool) -> Result<String> {
     // Validate URL scheme before making request
-    validate_url(url)?;
+    validate_url(url, allow_private)?;
 
     let resp = client
         .get(url)
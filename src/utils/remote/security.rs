@@ -5,7 +5,8 @@ use std::net::{IpAddr, ToSocketAddrs};
 const SECURE_SCHEME: &str = "https";
 const INSECURE_SCHEME: &str = "http";
 
-pub(super) fn validate_url(url_str: &str) -> Result<()> {
+/// Reject malformed, non-https and (unless `allow_private`) private-network URLs.
+pub(super) fn validate_url(url_str: &str, allow_private: bool) -> Result<()> {
     let parsed = reqwest::Url::parse(url_str)
         .map_err(|_| DeclarchError::RemoteFetchError(format!("Invalid URL: {}", url_str)))?;
 
@@ -21,11 +22,8 @@ pub(super) fn validate_url(url_str: &str) -> Result<()> {
     let host = parsed.host_str().ok_or_else(|| {
         DeclarchError::RemoteFetchError(format!("URL must include a valid host: {}", url_str))
     })?;
-    if is_private_address(host) {
-        return Err(DeclarchError::RemoteFetchError(format!(
-            "Access to private addresses is not allowed: {}",
-            host
-        )));
+    if !allow_private && is_private_address(host) {
+        return Err(private_address_error(host));
     }
 
     let port = parsed.port_or_known_default().unwrap_or(443);
@@ -38,9 +36,9 @@ pub(super) fn validate_url(url_str: &str) -> Result<()> {
             host
         )));
     }
-    if let Some(private_ip) = first_private_ip(&resolved) {
-        return Err(DeclarchError::RemoteFetchError(format!(
-            "Access to private addresses is not allowed: {} -> {}",
+    if !allow_private && let Some(private_ip) = first_private_ip(&resolved) {
+        return Err(private_address_error(&format!(
+            "{} -> {}",
             host, private_ip
         )));
     }
@@ -48,6 +46,20 @@ pub(super) fn validate_url(url_str: &str) -> Result<()> {
     Ok(())
 }
 
+fn private_address_error(target: &str) -> DeclarchError {
+    DeclarchError::RemoteFetchError(format!(
+        "Access to private addresses is not allowed: {}. Backend registries hosted on a private network can be allowed with {}=1.",
+        target,
+        project_identity::env_key("ALLOW_PRIVATE_REGISTRY")
+    ))
+}
+
+/// Whether backend registries may live on private addresses
+/// (`DECLARCH_ALLOW_PRIVATE_REGISTRY=1`).
+pub(super) fn private_registries_allowed(value: Option<String>) -> bool {
+    value.is_some_and(|v| v.trim() == "1")
+}
+
 fn is_allowed_scheme(scheme: &str) -> bool {
     if scheme == SECURE_SCHEME {
         return true;
@@ -161,12 +161,12 @@ fn test_private_address_checks() {
 
 #[test]
 fn test_validate_url_rejects_malformed_host() {
-    assert!(validate_url("https://").is_err());
+    assert!(validate_url("https://", false).is_err());
 }
 
 #[test]
 fn test_validate_url_rejects_http_by_default() {
-    assert!(validate_url("http://example.com/config.kdl").is_err());
+    assert!(validate_url("http://example.com/config.kdl", false).is_err());
 }
 
 #[test]
@@ -194,3 +194,85 @@ fn test_failure_summary_formats_attempts() {
     assert!(summary.contains("u1"));
     assert!(summary.contains("... and 1 more"));
 }
+
+#[test]
+fn offline_env_requires_exact_opt_in() {
+    assert!(offline_env_enabled(Some("1".to_string())));
+    assert!(offline_env_enabled(Some(" 1 ".to_string())));
+    assert!(!offline_env_enabled(Some("0".to_string())));
+    assert!(!offline_env_enabled(Some(String::new())));
+    assert!(!offline_env_enabled(None));
+}
+
+#[test]
+fn registry_env_accepts_comma_or_space_separated_specs() {
+    assert_eq!(
+        registry_env_specs(Some(
+            "github:a/b, https://x.example/backends  github:c/d".into()
+        )),
+        vec!["github:a/b", "https://x.example/backends", "github:c/d"]
+    );
+    assert!(registry_env_specs(Some("  ".into())).is_empty());
+    assert!(registry_env_specs(None).is_empty());
+}
+
+#[test]
+fn backend_fetch_falls_through_registries_in_order() {
+    let registries = RemoteUrlBuilder::default()
+        .backend_registries(&[
+            "github:acme/first".to_string(),
+            "https://mycorp.example/backends".to_string(),
+        ])
+        .unwrap();
+
+    let mut tried = Vec::new();
+    let fetched = fetch_from_registries("paru", &registries, |url| {
+        tried.push(url.to_string());
+        if url.starts_with("https://mycorp.example/") {
+            Ok("backend \"paru\" {}".to_string())
+        } else {
+            Err(DeclarchError::RemoteFetchError("HTTP 404 Not Found".into()))
+        }
+    })
+    .unwrap();
+
+    assert_eq!(fetched.registry, "https://mycorp.example/backends");
+    assert_eq!(
+        tried,
+        vec![
+            "https://raw.githubusercontent.com/acme/first/main/backends/paru.kdl",
+            "https://mycorp.example/backends/paru.kdl",
+        ]
+    );
+}
+
+#[test]
+fn backend_fetch_reports_every_registry_when_all_fail() {
+    let registries = RemoteUrlBuilder::default()
+        .backend_registries(&["github:acme/first".to_string()])
+        .unwrap();
+
+    let mut calls = 0;
+    let err = fetch_from_registries("paru", &registries, |_| {
+        calls += 1;
+        Err(DeclarchError::RemoteFetchError("HTTP 404 Not Found".into()))
+    })
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(calls, 2);
+    assert!(err.contains("acme/first"));
+    assert!(err.contains(project_identity::REGISTRY_SLUG));
+}
+
+#[test]
+fn private_registry_hosts_need_explicit_opt_in() {
+    assert!(validate_url("https://10.0.0.5/backends/soar.kdl", false).is_err());
+    assert!(validate_url("https://10.0.0.5/backends/soar.kdl", true).is_ok());
+    // The opt-in relaxes only the address check, not the scheme
+    assert!(validate_url("http://10.0.0.5/backends/soar.kdl", true).is_err());
+
+    assert!(private_registries_allowed(Some("1".to_string())));
+    assert!(!private_registries_allowed(Some("true".to_string())));
+    assert!(!private_registries_allowed(None));
+}
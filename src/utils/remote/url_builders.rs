@@ -1,40 +1,6 @@
-use super::{BACKENDS_REGISTRY, DEFAULT_REGISTRY};
+use super::DEFAULT_REGISTRY;
 use crate::constants::{CONFIG_EXTENSION, DEFAULT_BRANCHES, PROJECT_NAME};
 
-pub(super) fn build_backend_urls(backend_name: &str) -> Vec<String> {
-    let mut urls = Vec::new();
-
-    if backend_name.starts_with("http://") || backend_name.starts_with("https://") {
-        urls.push(backend_name.to_string());
-        return urls;
-    }
-
-    let clean_name = backend_name
-        .strip_suffix(&format!(".{}", CONFIG_EXTENSION))
-        .unwrap_or(backend_name);
-
-    urls.push(format!(
-        "{}/{}.{}",
-        *BACKENDS_REGISTRY, clean_name, CONFIG_EXTENSION
-    ));
-
-    if clean_name.contains('/') {
-        let parts: Vec<&str> = clean_name.split('/').collect();
-        if parts.len() >= 2 {
-            let (owner, repo) = (parts[0], parts[1]);
-            urls.push(format!(
-                "https://raw.githubusercontent.com/{}/{}/main/backends/{}.{}",
-                owner,
-                repo,
-                parts.last().unwrap_or(&"backend"),
-                CONFIG_EXTENSION
-            ));
-        }
-    }
-
-    urls
-}
-
 pub(super) fn build_urls(target: &str) -> Vec<String> {
     let mut urls = Vec::new();
 
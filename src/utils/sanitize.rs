@@ -132,5 +132,22 @@ pub fn validate_search_query(query: &str) -> Result<()> {
     Ok(())
 }
 
+/// Validate a backend registry/mirror URL (`registry "..."`)
+///
+/// Must be an absolute http(s) URL with a host; it is passed to backend
+/// commands, so it is shell-escaped there rather than restricted further.
+pub fn validate_registry_url(url: &str) -> Result<()> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| {
+        DeclarchError::ConfigError(format!("Invalid registry URL '{}': {}", url, e))
+    })?;
+    if !matches!(parsed.scheme(), "https" | "http") || parsed.host_str().is_none() {
+        return Err(DeclarchError::ConfigError(format!(
+            "Invalid registry URL '{}': expected an http(s) URL with a host",
+            url
+        )));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests;
@@ -51,3 +51,12 @@ fn test_shell_escape_whitespace_and_symbols() {
 fn test_shell_escape_safe_passthrough() {
     assert_eq!(shell_escape("aur:bat@1.0+git"), "aur:bat@1.0+git");
 }
+
+#[test]
+fn test_registry_url_validation() {
+    assert!(validate_registry_url("https://mirror.example.org/soar").is_ok());
+    assert!(validate_registry_url("http://10.0.0.5:8080/").is_ok());
+    assert!(validate_registry_url("mirror.example.org").is_err());
+    assert!(validate_registry_url("file:///srv/registry").is_err());
+    assert!(validate_registry_url("https://").is_err());
+}
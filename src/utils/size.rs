@@ -0,0 +1,47 @@
+//! Package size parsing and formatting
+//!
+//! Backends report sizes as raw byte counts or human-readable strings
+//! ("12.3 MiB", "450K", "1,2 GB"); these are normalized to bytes.
+
+/// Parse a size reported by a backend into bytes.
+///
+/// Accepts a plain number (bytes) or a number followed by a unit. Both
+/// binary (KiB) and decimal (KB) units are treated as powers of 1024, as
+/// most package managers do. Returns `None` for anything else.
+pub fn parse_size(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number.replace(',', ".").parse().ok()?;
+
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" | "bytes" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        "t" | "tb" | "tib" => 1 << 40,
+        _ => return None,
+    };
+    (number >= 0.0).then(|| (number * multiplier as f64).round() as u64)
+}
+
+/// Format a byte count for display, e.g. `12.3 MiB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests;
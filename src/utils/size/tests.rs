@@ -0,0 +1,24 @@
+use super::*;
+
+#[test]
+fn parse_size_accepts_bytes_and_units() {
+    assert_eq!(parse_size("2048"), Some(2048));
+    assert_eq!(parse_size("1.5 KiB"), Some(1536));
+    assert_eq!(parse_size("2M"), Some(2 * 1024 * 1024));
+    assert_eq!(parse_size("1,5 GB"), Some(1_610_612_736));
+    assert_eq!(parse_size(" 12 mib "), Some(12 * 1024 * 1024));
+}
+
+#[test]
+fn parse_size_rejects_unknown_text() {
+    assert_eq!(parse_size(""), None);
+    assert_eq!(parse_size("unknown"), None);
+    assert_eq!(parse_size("12 parsecs"), None);
+}
+
+#[test]
+fn format_size_picks_largest_unit() {
+    assert_eq!(format_size(512), "512 B");
+    assert_eq!(format_size(1536), "1.5 KiB");
+    assert_eq!(format_size(5 * 1024 * 1024 * 1024), "5.0 GiB");
+}
@@ -10,6 +10,9 @@ struct GitHubRelease {
 }
 
 pub(super) fn fetch_latest_version(timeout: Duration) -> Option<String> {
+    if crate::utils::remote::is_frozen() {
+        return None;
+    }
     let client = Client::builder().timeout(timeout).build().ok()?;
 
     let response = client
@@ -173,6 +173,21 @@ fn e2e_sync_preview_with_mock_backend() {
         );
 }
 
+#[test]
+fn e2e_check_only_with_diff_reports_pending_changes() {
+    let env = TestEnv::new();
+
+    // `alpha` is installed but not tracked yet, so the plan adopts it
+    let mut cmd = declarch();
+    env.apply(&mut cmd);
+
+    cmd.arg("sync")
+        .arg("--check-only")
+        .arg("--diff")
+        .assert()
+        .code(7);
+}
+
 #[test]
 fn e2e_install_no_sync_updates_module_file() {
     let env = TestEnv::new();
@@ -16,6 +16,8 @@ fn create_snapshot(packages: Vec<(&str, Backend, &str)>) -> HashMap<PackageId, P
             variant: None,
             installed_at: Utc::now(),
             source_file: None,
+            install_size: None,
+            origin: None,
         };
         snapshot.insert(id, meta);
     }